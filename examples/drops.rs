@@ -4,6 +4,7 @@ fn main() {
     dioxus_desktop::launch(app);
 }
 
+#[allow(deprecated)]
 fn app(cx: Scope) -> Element {
     let count = if cx.generation() % 2 == 0 { 10 } else { 0 };
 
@@ -5,6 +5,7 @@ fn main() {
 }
 
 #[component]
+#[allow(deprecated)]
 fn App(cx: Scope) -> Element {
     let val = use_state(cx, || "0.0001");
 
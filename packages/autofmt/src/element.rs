@@ -252,6 +252,20 @@ impl Writer<'_> {
                 write!(self.out, "{}: {}", name.to_token_stream(), out)?;
             }
 
+            ElementAttr::Spread { value, .. } => {
+                let out = self.retrieve_formatted_expr(value).to_string();
+
+                let mut lines = out.split('\n');
+                let first = lines.next().unwrap();
+                write!(self.out, "..{first}")?;
+
+                for line in lines {
+                    writeln!(self.out)?;
+                    self.out.indented_tab()?;
+                    write!(self.out, "{line}")?;
+                }
+            }
+
             ElementAttr::EventTokens { name, tokens } => {
                 let out = self.retrieve_formatted_expr(tokens).to_string();
 
@@ -375,6 +389,7 @@ impl Writer<'_> {
                         },
                         BodyNode::ForLoop(_forloop) => return None,
                         BodyNode::IfChain(_chain) => return None,
+                        BodyNode::Match(_match) => return None,
                     }
                 }
 
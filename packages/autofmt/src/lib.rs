@@ -47,17 +47,25 @@ pub struct FormattedBlock {
 /// back to the file precisely.
 ///
 /// Nested blocks of RSX will be handled automatically
+///
+/// Panics if the file or any of its rsx! blocks fail to parse. Use [`try_fmt_file`] to get the error instead.
 pub fn fmt_file(contents: &str) -> Vec<FormattedBlock> {
+    try_fmt_file(contents).unwrap()
+}
+
+/// Format a file into a list of `FormattedBlock`s, returning the parse error if the file or one of its rsx! blocks is
+/// not valid.
+pub fn try_fmt_file(contents: &str) -> syn::Result<Vec<FormattedBlock>> {
     let mut formatted_blocks = Vec::new();
 
-    let parsed = syn::parse_file(contents).unwrap();
+    let parsed = syn::parse_file(contents)?;
 
     let mut macros = vec![];
     collect_macros::collect_from_file(&parsed, &mut macros);
 
     // No macros, no work to do
     if macros.is_empty() {
-        return formatted_blocks;
+        return Ok(formatted_blocks);
     }
 
     let mut writer = Writer::new(contents);
@@ -72,56 +80,88 @@ pub fn fmt_file(contents: &str) -> Vec<FormattedBlock> {
             continue;
         }
 
-        let body = item.parse_body::<CallBody>().unwrap();
+        let block = fmt_macro(&mut writer, item)?;
 
-        let rsx_start = macro_path.span().start();
+        end_span = delimiter_span(item).end();
 
-        writer.out.indent = leading_whitespaces(writer.src[rsx_start.line - 1]) / 4;
+        if contents[block.start..block.end] == block.formatted {
+            continue;
+        }
 
-        write_body(&mut writer, &body);
+        formatted_blocks.push(block);
+    }
 
-        // writing idents leaves the final line ended at the end of the last ident
-        if writer.out.buf.contains('\n') {
-            writer.out.new_line().unwrap();
-            writer.out.tab().unwrap();
-        }
+    Ok(formatted_blocks)
+}
 
-        let span = match item.delimiter {
-            MacroDelimiter::Paren(b) => b.span,
-            MacroDelimiter::Brace(b) => b.span,
-            MacroDelimiter::Bracket(b) => b.span,
-        }
-        .join();
+/// Format every rsx! block in a file and return the new contents of the file.
+///
+/// Code outside of rsx! blocks is left untouched, so this pairs well with rustfmt which skips macro bodies.
+pub fn format_file(contents: &str) -> syn::Result<String> {
+    let blocks = try_fmt_file(contents)?;
+    Ok(apply_formats(contents, blocks))
+}
 
-        let mut formatted = String::new();
+/// Format a single rsx! invocation like `rsx! { div { "hello" } }` and return the formatted invocation.
+///
+/// The body is indented relative to the indentation of the first line of the invocation.
+pub fn fmt_rsx(invocation: &str) -> syn::Result<String> {
+    let item = syn::parse_str::<syn::Macro>(invocation)?;
 
-        std::mem::swap(&mut formatted, &mut writer.out.buf);
+    let mut writer = Writer::new(invocation);
+    let block = fmt_macro(&mut writer, &item)?;
 
-        let start = byte_offset(contents, span.start()) + 1;
-        let end = byte_offset(contents, span.end()) - 1;
+    Ok(apply_format(invocation, block))
+}
 
-        // Rustfmt will remove the space between the macro and the opening paren if the macro is a single expression
-        let body_is_solo_expr = body.roots.len() == 1
-            && matches!(body.roots[0], BodyNode::RawExpr(_) | BodyNode::Text(_));
+/// Format the body of a single macro, returning the edit that replaces the contents between its delimiters
+fn fmt_macro(writer: &mut Writer, item: &syn::Macro) -> syn::Result<FormattedBlock> {
+    let body = item.parse_body::<CallBody>()?;
 
-        if formatted.len() <= 80 && !formatted.contains('\n') && !body_is_solo_expr {
-            formatted = format!(" {formatted} ");
-        }
+    let rsx_start = item.path.segments[0].ident.span().start();
 
-        end_span = span.end();
+    writer.out.indent = leading_whitespaces(writer.src[rsx_start.line - 1]) / 4;
 
-        if contents[start..end] == formatted {
-            continue;
-        }
+    write_body(writer, &body);
 
-        formatted_blocks.push(FormattedBlock {
-            formatted,
-            start,
-            end,
-        });
+    // writing idents leaves the final line ended at the end of the last ident
+    if writer.out.buf.contains('\n') {
+        writer.out.new_line().unwrap();
+        writer.out.tab().unwrap();
     }
 
-    formatted_blocks
+    let span = delimiter_span(item);
+
+    let mut formatted = String::new();
+
+    std::mem::swap(&mut formatted, &mut writer.out.buf);
+
+    let start = byte_offset(writer.raw_src, span.start()) + 1;
+    let end = byte_offset(writer.raw_src, span.end()) - 1;
+
+    // Rustfmt will remove the space between the macro and the opening paren if the macro is a single expression
+    let body_is_solo_expr =
+        body.roots.len() == 1 && matches!(body.roots[0], BodyNode::RawExpr(_) | BodyNode::Text(_));
+
+    if formatted.len() <= 80 && !formatted.contains('\n') && !body_is_solo_expr {
+        formatted = format!(" {formatted} ");
+    }
+
+    Ok(FormattedBlock {
+        formatted,
+        start,
+        end,
+    })
+}
+
+/// The span of the delimiters around the body of a macro
+fn delimiter_span(item: &syn::Macro) -> proc_macro2::Span {
+    match item.delimiter {
+        MacroDelimiter::Paren(b) => b.span,
+        MacroDelimiter::Brace(b) => b.span,
+        MacroDelimiter::Bracket(b) => b.span,
+    }
+    .join()
 }
 
 pub fn write_block_out(body: CallBody) -> Option<String> {
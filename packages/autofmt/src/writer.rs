@@ -1,11 +1,11 @@
-use dioxus_rsx::{BodyNode, ElementAttr, ElementAttrNamed, ForLoop};
+use dioxus_rsx::{BodyNode, ElementAttr, ElementAttrNamed, ForLoop, IfChain, Match};
 use proc_macro2::{LineColumn, Span};
 use quote::ToTokens;
 use std::{
     collections::{HashMap, VecDeque},
     fmt::{Result, Write},
 };
-use syn::{spanned::Spanned, Expr, ExprIf};
+use syn::{spanned::Spanned, Expr};
 
 use crate::buffer::Buffer;
 use crate::ifmt_to_string;
@@ -54,6 +54,7 @@ impl<'a> Writer<'a> {
             BodyNode::RawExpr(exp) => self.write_raw_expr(exp.span()),
             BodyNode::ForLoop(forloop) => self.write_for_loop(forloop),
             BodyNode::IfChain(ifchain) => self.write_if_chain(ifchain),
+            BodyNode::Match(expr) => self.write_match(expr),
         }
     }
 
@@ -159,6 +160,14 @@ impl<'a> Writer<'a> {
                 ElementAttr::CustomAttrExpression { name, value } => {
                     name.to_token_stream().to_string().len() + value.span().line_length() + 6
                 }
+                ElementAttr::Spread { value, .. } => {
+                    let formatted = self.retrieve_formatted_expr(value);
+                    if formatted.contains('\n') {
+                        10000
+                    } else {
+                        formatted.len() + 2
+                    }
+                }
                 ElementAttr::EventTokens { tokens, name } => {
                     let location = Location::new(tokens.span().start());
 
@@ -213,8 +222,81 @@ impl<'a> Writer<'a> {
         Ok(())
     }
 
-    fn write_if_chain(&mut self, ifchain: &ExprIf) -> std::fmt::Result {
-        self.write_raw_expr(ifchain.span())
+    fn write_if_chain(&mut self, ifchain: &IfChain) -> std::fmt::Result {
+        let mut chain = Some(ifchain);
+        while let Some(IfChain {
+            cond,
+            then_branch,
+            else_if_branch,
+            else_branch,
+            ..
+        }) = chain
+        {
+            write!(self.out, "if {} {{", prettyplease::unparse_expr(cond))?;
+            self.write_branch(then_branch)?;
+
+            if let Some(else_branch) = else_branch {
+                write!(self.out, " else {{")?;
+                self.write_branch(else_branch)?;
+            }
+
+            chain = else_if_branch.as_deref();
+            if chain.is_some() {
+                write!(self.out, " else ")?;
+            }
+        }
+
+        Ok(())
+    }
+
+    fn write_match(&mut self, expr: &Match) -> std::fmt::Result {
+        write!(
+            self.out,
+            "match {} {{",
+            prettyplease::unparse_expr(&expr.expr)
+        )?;
+
+        self.out.indent += 1;
+        for arm in &expr.arms {
+            self.out.tabbed_line()?;
+            self.write_raw_expr(arm.pat.span())?;
+            if let Some(guard) = &arm.guard {
+                write!(self.out, " if ")?;
+                self.write_raw_expr(guard.span())?;
+            }
+            write!(self.out, " => ")?;
+
+            match (&arm.brace_token, arm.body.as_slice()) {
+                (None, [node]) => {
+                    self.write_ident(node)?;
+                    write!(self.out, ",")?;
+                }
+                _ => {
+                    write!(self.out, "{{")?;
+                    self.write_branch(&arm.body)?;
+                }
+            }
+        }
+        self.out.indent -= 1;
+
+        self.out.tabbed_line()?;
+        write!(self.out, "}}")?;
+
+        Ok(())
+    }
+
+    fn write_branch(&mut self, branch: &[BodyNode]) -> std::fmt::Result {
+        if branch.is_empty() {
+            write!(self.out, "}}")?;
+            return Ok(());
+        }
+
+        self.write_body_indented(branch)?;
+
+        self.out.tabbed_line()?;
+        write!(self.out, "}}")?;
+
+        Ok(())
     }
 }
 
@@ -0,0 +1,27 @@
+#[test]
+fn formats_a_whole_file() {
+    let src = "fn app(cx: Scope) -> Element {\n    cx.render(rsx! { div {   class: \"item\",   \"hello\" } })\n}\n";
+
+    let out = dioxus_autofmt::format_file(src).unwrap();
+
+    pretty_assertions::assert_eq!(
+        out,
+        "fn app(cx: Scope) -> Element {\n    cx.render(rsx! { div { class: \"item\", \"hello\" } })\n}\n"
+    );
+}
+
+#[test]
+fn formats_a_single_invocation() {
+    let out = dioxus_autofmt::fmt_rsx("rsx! {div{h1{\"title\"} p{\"body\"}}}").unwrap();
+
+    pretty_assertions::assert_eq!(
+        out,
+        "rsx! {\n    div {\n        h1 { \"title\" }\n        p { \"body\" }\n    }\n}"
+    );
+}
+
+#[test]
+fn parse_errors_are_returned() {
+    assert!(dioxus_autofmt::try_fmt_file("fn app() { rsx! { div { class: } } }").is_err());
+    assert!(dioxus_autofmt::fmt_rsx("rsx! { div {").is_err());
+}
@@ -30,17 +30,20 @@ twoway![
     commentshard,
     complex,
     emoji,
+    ifchain_branches,
     ifchain_forloop,
     immediate_expr,
     key,
     long_exprs,
     long,
     manual_props,
+    match_arms,
     messy_indent,
     multirsx,
     raw_strings,
     reallylong,
     simple,
+    spread_attributes,
     t2,
     tiny,
     tinynoopt,
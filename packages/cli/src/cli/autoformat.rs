@@ -56,8 +56,13 @@ impl Autoformat {
 
             match file_content {
                 Ok(s) => {
-                    let edits = dioxus_autofmt::fmt_file(&s);
-                    let out = dioxus_autofmt::apply_formats(&s, edits);
+                    let out = match dioxus_autofmt::format_file(&s) {
+                        Ok(out) => out,
+                        Err(e) => {
+                            eprintln!("failed to parse {}: {}", file, e);
+                            exit(1);
+                        }
+                    };
                     if file == "-" {
                         print!("{}", out);
                     } else {
@@ -107,7 +112,8 @@ async fn autoformat_project(check: bool) -> Result<()> {
             let res = tokio::spawn(async move {
                 let contents = tokio::fs::read_to_string(&path).await?;
 
-                let edits = dioxus_autofmt::fmt_file(&contents);
+                let edits = dioxus_autofmt::try_fmt_file(&contents)
+                    .map_err(|e| tokio::io::Error::new(tokio::io::ErrorKind::InvalidData, e))?;
                 let len = edits.len();
 
                 if !edits.is_empty() {
@@ -3,6 +3,7 @@ use axum::{
     response::IntoResponse,
     Extension, TypedHeader,
 };
+use dioxus_hot_reload::HotReloadMsg;
 
 use crate::server::HotReloadState;
 
@@ -28,8 +29,9 @@ pub async fn hot_reload_handler(
                         .collect()
                 };
                 for template in templates {
+                    let msg = HotReloadMsg::UpdateTemplate(template);
                     if socket
-                        .send(Message::Text(serde_json::to_string(&template).unwrap()))
+                        .send(Message::Text(serde_json::to_string(&msg).unwrap()))
                         .await
                         .is_err()
                     {
@@ -43,8 +45,9 @@ pub async fn hot_reload_handler(
         let mut rx = state.messages.subscribe();
         loop {
             if let Ok(rsx) = rx.recv().await {
+                let msg = HotReloadMsg::UpdateTemplate(rsx);
                 if socket
-                    .send(Message::Text(serde_json::to_string(&rsx).unwrap()))
+                    .send(Message::Text(serde_json::to_string(&msg).unwrap()))
                     .await
                     .is_err()
                 {
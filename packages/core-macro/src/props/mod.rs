@@ -163,7 +163,7 @@ mod util {
 }
 
 mod field_info {
-    use crate::props::type_from_inside_option;
+    use crate::props::{is_element_type, type_from_inside_option};
     use proc_macro2::TokenStream;
     use quote::quote;
     use syn::spanned::Spanned;
@@ -192,8 +192,8 @@ mod field_info {
             if let Some(ref name) = field.ident {
                 let mut builder_attr = field_defaults.with(&field.attrs)?;
 
-                // children field is automatically defaulted to None
-                if name == "children" {
+                // children and other slots are automatically defaulted to None
+                if name == "children" || is_element_type(&field.ty) {
                     builder_attr.default = Some(
                         syn::parse(quote!(::core::default::Default::default()).into()).unwrap(),
                     );
@@ -438,6 +438,45 @@ mod field_info {
     }
 }
 
+/// Get the lifetime and value type of a render prop like `row: RenderProp<'a, T>`
+fn render_prop_args(ty: &syn::Type) -> Option<(&syn::Lifetime, TokenStream)> {
+    let path = match ty {
+        syn::Type::Path(type_path) if type_path.qself.is_none() => &type_path.path,
+        _ => return None,
+    };
+    let segment = path.segments.last()?;
+    if segment.ident != "RenderProp" {
+        return None;
+    }
+    let args = match &segment.arguments {
+        syn::PathArguments::AngleBracketed(args) => &args.args,
+        _ => return None,
+    };
+    let lifetime = match args.first()? {
+        syn::GenericArgument::Lifetime(lifetime) => lifetime,
+        _ => return None,
+    };
+    let value = match args.iter().nth(1) {
+        Some(syn::GenericArgument::Type(value)) => quote!(#value),
+        Some(_) => return None,
+        None => quote!(()),
+    };
+    Some((lifetime, value))
+}
+
+/// Check if a field is a slot like `header: Element<'a>`
+fn is_element_type(ty: &syn::Type) -> bool {
+    match ty {
+        syn::Type::Path(type_path) if type_path.qself.is_none() => {
+            match type_path.path.segments.last() {
+                Some(segment) => segment.ident == "Element",
+                None => false,
+            }
+        }
+        _ => false,
+    }
+}
+
 fn type_from_inside_option(ty: &syn::Type, check_option_name: bool) -> Option<&syn::Type> {
     let path = if let syn::Type::Path(type_path) = ty {
         if type_path.qself.is_some() {
@@ -473,6 +512,7 @@ mod struct_info {
     use syn::Expr;
 
     use super::field_info::{FieldBuilderAttr, FieldInfo};
+    use super::render_prop_args;
     use super::util::{
         empty_type, empty_type_tuple, expr_to_single_string, make_punctuated_single,
         modify_types_generics_hack, path_to_single_string, strip_raw_ident_prefix, type_tuple,
@@ -620,9 +660,26 @@ Finally, call `.build()` to create the instance of `{name}`.
                     .extend(predicates.predicates.clone());
             }
 
-            let can_memoize = match are_there_generics {
-                true => quote! { false  },
-                false => quote! { self == other },
+            let can_memoize = match (&self.builder_attr.memo, are_there_generics) {
+                (Some(memo), true) => {
+                    return Err(Error::new_spanned(
+                        memo,
+                        "A custom memo can only be used with props that have no generics, since they might borrow from the parent",
+                    ))
+                }
+                (Some(memo), false) => quote! { (#memo)(self, other) },
+                (None, true) => quote! { false  },
+                (None, false) => quote! { self == other },
+            };
+
+            // Generic props can't be checked for Debug here, so they are never described
+            let inspect = match are_there_generics {
+                true => quote! { ::core::option::Option::None },
+                false => quote! {
+                    #[allow(unused_imports)]
+                    use ::dioxus::core::{InspectDebug as _, InspectFallback as _};
+                    (&::dioxus::core::InspectProps(self)).inspect_props()
+                },
             };
 
             let is_static = match are_there_generics {
@@ -670,6 +727,9 @@ Finally, call `.build()` to create the instance of `{name}`.
                     unsafe fn memoize(&self, other: &Self) -> bool {
                         #can_memoize
                     }
+                    fn inspect(&self) -> ::core::option::Option<::std::string::String> {
+                        #inspect
+                    }
                 }
 
             })
@@ -802,6 +862,12 @@ Finally, call `.build()` to create the instance of `{name}`.
                     quote!(impl ::core::convert::Into<#arg_type>),
                     quote!(#field_name.into()),
                 )
+            } else if let Some((lifetime, value)) = render_prop_args(arg_type) {
+                // render props accept closures directly so the types of their arguments can be inferred
+                (
+                    quote!(impl ::dioxus::core::IntoRenderProp<#lifetime, #value>),
+                    quote!(::dioxus::core::IntoRenderProp::into_render_prop(#field_name)),
+                )
             } else {
                 (quote!(#arg_type), quote!(#field_name))
             };
@@ -1085,6 +1151,10 @@ Finally, call `.build()` to create the instance of `{name}`.
         /// specify `doc` instead and a default value will be filled in here.
         pub build_method_doc: Option<syn::Expr>,
 
+        /// A `fn(&Props, &Props) -> bool` used to decide whether the component can skip rendering, in place of
+        /// `PartialEq`.
+        pub memo: Option<syn::Expr>,
+
         pub field_defaults: FieldBuilderAttr,
     }
 
@@ -1092,7 +1162,10 @@ Finally, call `.build()` to create the instance of `{name}`.
         pub fn new(attrs: &[syn::Attribute]) -> Result<TypeBuilderAttr, Error> {
             let mut result = TypeBuilderAttr::default();
             for attr in attrs {
-                if path_to_single_string(attr.path()).as_deref() != Some("builder") {
+                if !matches!(
+                    path_to_single_string(attr.path()).as_deref(),
+                    Some("builder" | "props")
+                ) {
                     continue;
                 }
 
@@ -1139,6 +1212,10 @@ Finally, call `.build()` to create the instance of `{name}`.
                             self.doc = true;
                             Ok(())
                         }
+                        "memo" => {
+                            self.memo = Some(*assign.right);
+                            Ok(())
+                        }
                         _ => Err(Error::new_spanned(
                             &assign,
                             format!("Unknown parameter {name:?}"),
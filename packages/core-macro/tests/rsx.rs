@@ -2,4 +2,6 @@
 fn rsx() {
     let t = trybuild::TestCases::new();
     t.compile_fail("tests/rsx/trailing-comma-0.rs");
+    t.compile_fail("tests/rsx/duplicate-key-0.rs");
+    t.compile_fail("tests/rsx/missing-key-0.rs");
 }
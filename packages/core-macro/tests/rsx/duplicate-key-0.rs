@@ -0,0 +1,12 @@
+// Given a list whose items all get the same literal key,
+// ensure the error points at the key.
+
+use dioxus::prelude::*;
+
+fn main() {
+    rsx! {
+        for i in 0..10 {
+            li { key: "item", "{i}" }
+        }
+    };
+}
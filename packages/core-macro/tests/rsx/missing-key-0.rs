@@ -0,0 +1,19 @@
+// Given a list of components without keys,
+// ensure the warning points at the loop.
+
+#![deny(deprecated)]
+
+use dioxus::prelude::*;
+
+#[allow(non_snake_case)]
+fn Item(cx: Scope) -> Element {
+    render! { "item" }
+}
+
+fn main() {
+    rsx! {
+        for _ in 0..10 {
+            Item {}
+        }
+    };
+}
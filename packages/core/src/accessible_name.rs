@@ -0,0 +1,146 @@
+use crate::{
+    innerlude::VNode,
+    walk::{TreeVisitor, VisitedAttribute},
+    ElementId, VirtualDom,
+};
+
+impl VirtualDom {
+    /// Compute the accessible name of an element in the resolved tree of this node.
+    ///
+    /// This implements the core of the [WAI-ARIA accessible name computation](https://www.w3.org/TR/accname-1.2/),
+    /// using the first of these that is not empty:
+    /// 1. The `aria-label` attribute
+    /// 2. The text of the elements referenced by `aria-labelledby`
+    /// 3. The text content of the element, leaving out anything under `aria-hidden="true"`
+    /// 4. The `title` attribute
+    /// 5. The `placeholder` attribute
+    ///
+    /// Whitespace is collapsed. If the element is not part of the tree or has no name, an empty string is returned.
+    ///
+    /// ```rust, ignore
+    /// let node = match dom.base_scope().root_node() {
+    ///     RenderReturn::Ready(node) => node,
+    ///     _ => return,
+    /// };
+    ///
+    /// let name = dom.accessible_name(node, button_id);
+    /// ```
+    pub fn accessible_name<'b>(&'b self, node: &'b VNode<'b>, element: ElementId) -> String {
+        let mut collector = NameCollector::default();
+        self.visit_node(node, &mut collector);
+
+        let target = match collector
+            .elements
+            .iter()
+            .find(|collected| collected.id == Some(element))
+        {
+            Some(target) => target,
+            None => return String::new(),
+        };
+
+        if let Some(label) = target.attribute("aria-label").map(collapse_whitespace) {
+            if !label.is_empty() {
+                return label;
+            }
+        }
+
+        if let Some(labelled_by) = target.attribute("aria-labelledby") {
+            let label = labelled_by
+                .split_whitespace()
+                .filter_map(|id| {
+                    collector
+                        .elements
+                        .iter()
+                        .find(|collected| collected.attribute("id") == Some(id))
+                })
+                .map(|referenced| match referenced.attribute("aria-label") {
+                    Some(label) if !label.trim().is_empty() => label.to_string(),
+                    _ => referenced.text.clone(),
+                })
+                .collect::<Vec<_>>()
+                .join(" ");
+            let label = collapse_whitespace(&label);
+            if !label.is_empty() {
+                return label;
+            }
+        }
+
+        let text = collapse_whitespace(&target.text);
+        if !text.is_empty() {
+            return text;
+        }
+
+        ["title", "placeholder"]
+            .iter()
+            .filter_map(|name| target.attribute(name))
+            .map(collapse_whitespace)
+            .find(|value| !value.is_empty())
+            .unwrap_or_default()
+    }
+}
+
+fn collapse_whitespace(text: &str) -> String {
+    text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+struct CollectedElement<'b> {
+    id: Option<ElementId>,
+    attributes: Vec<(&'b str, String)>,
+    text: String,
+}
+
+impl CollectedElement<'_> {
+    fn attribute(&self, name: &str) -> Option<&str> {
+        self.attributes
+            .iter()
+            .find(|(attr, _)| *attr == name)
+            .map(|(_, value)| value.as_str())
+    }
+}
+
+#[derive(Default)]
+struct NameCollector<'b> {
+    elements: Vec<CollectedElement<'b>>,
+    // The indices of the elements we are currently inside of, and whether their text is hidden
+    open: Vec<(usize, bool)>,
+}
+
+impl<'b> TreeVisitor<'b> for NameCollector<'b> {
+    fn enter_element(
+        &mut self,
+        _tag: &'b str,
+        _namespace: Option<&'b str>,
+        id: Option<ElementId>,
+        attrs: &[VisitedAttribute<'b>],
+    ) {
+        let element = CollectedElement {
+            id,
+            attributes: attrs
+                .iter()
+                .filter_map(|attr| Some((attr.name(), attr.text_value(attrs)?)))
+                .collect(),
+            text: String::new(),
+        };
+
+        let hidden = element.attribute("aria-hidden") == Some("true")
+            || matches!(self.open.last(), Some((_, true)));
+
+        self.open.push((self.elements.len(), hidden));
+        self.elements.push(element);
+    }
+
+    fn exit_element(&mut self) {
+        self.open.pop();
+    }
+
+    fn text(&mut self, value: &'b str, _id: Option<ElementId>) {
+        if matches!(self.open.last(), Some((_, true))) {
+            return;
+        }
+
+        // Text belongs to the content of every element it is inside of
+        for (idx, _) in &self.open {
+            self.elements[*idx].text.push_str(value);
+        }
+    }
+}
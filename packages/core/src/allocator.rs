@@ -0,0 +1,96 @@
+use std::{any::Any, cell::RefCell, fmt::Arguments};
+
+use bumpalo::{boxed::Box as BumpBox, Bump};
+
+use crate::{
+    innerlude::{AnyValue, VText},
+    AttributeValue, DynamicNode, Event,
+};
+
+/// An allocator the dynamic parts of a [`crate::VNode`] can be built in.
+///
+/// By default, nodes are built in the [`Bump`] allocator of the scope that renders them, which is reset every time
+/// the scope renders. Implement this trait to build them somewhere else, like a pooled arena on an embedded target.
+/// Only the two allocation methods are required, the node constructors are built on top of them.
+///
+/// Values are never dropped by the allocator. The VirtualDom drops listeners and [`AnyValue`]s in place when it is
+/// done with them, but the memory is only reclaimed when the allocator frees it. Everything allocated has to outlive
+/// the VNode it is part of.
+///
+/// The ids of the roots of a VNode are still kept in a bumpalo Vec, because they are pushed to when the node is
+/// mounted.
+///
+/// ```rust, ignore
+/// let nodes = arena.alloc([arena.text_node(format_args!("{count}"))]);
+/// let attrs = arena.alloc([Attribute::new("onclick", arena.listener(|_: Event<MouseData>| {}), None, false)]);
+/// ```
+pub trait NodeAllocator<'a> {
+    /// Move a value into the allocator
+    #[allow(clippy::mut_from_ref)]
+    fn alloc<T>(&'a self, value: T) -> &'a mut T;
+
+    /// Copy a string into the allocator
+    fn alloc_str(&'a self, value: &str) -> &'a str;
+
+    /// Allocate some text from [`Arguments`]. Static text is not copied.
+    fn alloc_fmt(&'a self, args: Arguments) -> &'a str {
+        match args.as_str() {
+            Some(text) => text,
+            None => self.alloc_str(&args.to_string()),
+        }
+    }
+
+    /// Create a dynamic text node from [`Arguments`]
+    fn text_node(&'a self, args: Arguments) -> DynamicNode<'a> {
+        DynamicNode::Text(VText::new(self.alloc_fmt(args)))
+    }
+
+    /// Create a new [`AttributeValue`] with the listener variant from a callback
+    fn listener<T: 'static>(
+        &'a self,
+        mut callback: impl FnMut(Event<T>) + 'a,
+    ) -> AttributeValue<'a> {
+        // safety: there's no other way to create a dynamicly-dispatched bump box other than alloc + from-raw
+        // A BumpBox only drops its value in place, so it can point into any allocator
+        let boxed: BumpBox<'a, dyn FnMut(_) + 'a> = unsafe {
+            BumpBox::from_raw(self.alloc(move |event: Event<dyn Any>| {
+                if let Ok(data) = event.data.downcast::<T>() {
+                    callback(Event {
+                        propagates: event.propagates,
+                        default_prevented: event.default_prevented,
+                        data,
+                    });
+                }
+            }))
+        };
+
+        AttributeValue::Listener(RefCell::new(Some(boxed)))
+    }
+
+    /// Create a new [`AttributeValue`] with a value that implements [`AnyValue`]
+    fn any_value<T: AnyValue>(&'a self, value: T) -> AttributeValue<'a> {
+        // safety: there's no other way to create a dynamicly-dispatched bump box other than alloc + from-raw
+        let boxed: BumpBox<'a, dyn AnyValue> = unsafe { BumpBox::from_raw(self.alloc(value)) };
+        AttributeValue::Any(RefCell::new(Some(boxed)))
+    }
+}
+
+impl<'a> NodeAllocator<'a> for Bump {
+    #[allow(clippy::mut_from_ref)]
+    fn alloc<T>(&'a self, value: T) -> &'a mut T {
+        Bump::alloc(self, value)
+    }
+
+    fn alloc_str(&'a self, value: &str) -> &'a str {
+        Bump::alloc_str(self, value)
+    }
+
+    fn alloc_fmt(&'a self, args: Arguments) -> &'a str {
+        args.as_str().unwrap_or_else(|| {
+            use bumpalo::core_alloc::fmt::Write;
+            let mut str_buf = bumpalo::collections::String::new_in(self);
+            str_buf.write_fmt(args).unwrap();
+            str_buf.into_bump_str()
+        })
+    }
+}
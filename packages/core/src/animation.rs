@@ -0,0 +1,173 @@
+use std::time::Duration;
+
+use crate::{
+    innerlude::{BorrowedAttributeValue, Mutations, WriteMutations},
+    Attribute, AttributeValue, ElementId, VirtualDom,
+};
+
+/// Describes how changes to an attribute should be animated.
+///
+/// When a float attribute with a transition changes between two renders, the renderer is not sent the new value right
+/// away. Instead, [`VirtualDom::tick`] sends the values in between, until the new value is reached after `duration`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct AttributeTransition {
+    /// The name of the attribute to animate
+    pub name: &'static str,
+
+    /// The namespace of the attribute to animate
+    pub namespace: Option<&'static str>,
+
+    /// How long it takes to move from the old value to the new value
+    pub duration: Duration,
+}
+
+impl AttributeTransition {
+    /// Animate changes to the attribute with this name over the given duration
+    pub fn new(name: &'static str, duration: Duration) -> Self {
+        Self {
+            name,
+            namespace: None,
+            duration,
+        }
+    }
+
+    /// Only animate the attribute in this namespace
+    pub fn with_namespace(mut self, namespace: &'static str) -> Self {
+        self.namespace = Some(namespace);
+        self
+    }
+
+    fn matches(&self, attribute: &Attribute) -> bool {
+        self.name == attribute.name && self.namespace == attribute.namespace
+    }
+}
+
+#[derive(Default)]
+pub(crate) struct Animations {
+    transitions: Vec<AttributeTransition>,
+    active: Vec<ActiveAnimation>,
+}
+
+struct ActiveAnimation {
+    id: ElementId,
+    transition: AttributeTransition,
+    from: f64,
+    to: f64,
+    current: f64,
+    // Filled in by the first tick after the animation starts
+    start: Option<Duration>,
+}
+
+impl VirtualDom {
+    /// Animate changes to an attribute instead of jumping straight to the new value.
+    ///
+    /// The renderer drives the animation by calling [`VirtualDom::tick`] with its own clock.
+    ///
+    /// ```rust, ignore
+    /// let dom = VirtualDom::new(app)
+    ///     .with_attribute_transition(AttributeTransition::new("opacity", Duration::from_millis(100)));
+    /// ```
+    pub fn with_attribute_transition(mut self, transition: AttributeTransition) -> Self {
+        self.animations.transitions.push(transition);
+        self
+    }
+
+    /// Advance every running attribute animation to `now`, returning the intermediate values.
+    ///
+    /// `now` can be measured from any point, as long as the renderer uses the same clock for every call. Each animation
+    /// starts at the first tick after the change that caused it.
+    pub fn tick(&mut self, now: Duration) -> Mutations<'_> {
+        let mut mutations = Mutations::default();
+        for animation in &mut self.animations.active {
+            let start = *animation.start.get_or_insert(now);
+            let elapsed = now.saturating_sub(start).as_secs_f64();
+            let duration = animation.transition.duration.as_secs_f64();
+            let progress = if duration > 0.0 {
+                (elapsed / duration).min(1.0)
+            } else {
+                1.0
+            };
+
+            animation.current = animation.from + (animation.to - animation.from) * progress;
+            mutations.set_attribute(
+                animation.transition.name,
+                animation.transition.namespace,
+                &BorrowedAttributeValue::Float(animation.current),
+                animation.id,
+            );
+        }
+
+        self.animations
+            .active
+            .retain(|animation| match animation.start {
+                Some(start) => now.saturating_sub(start) < animation.transition.duration,
+                None => true,
+            });
+
+        mutations
+    }
+
+    /// Are there any attribute animations that still need to be ticked?
+    pub fn has_running_animations(&self) -> bool {
+        !self.animations.active.is_empty()
+    }
+
+    /// Start animating an attribute that changed between renders, returning false if the change is not animated
+    pub(crate) fn start_animation(
+        &mut self,
+        new: &Attribute,
+        old: &Attribute,
+        id: ElementId,
+    ) -> bool {
+        // A change that isn't animated takes over from any animation that is still running
+        let running = self.stop_animation(id, new.name);
+
+        let to = match new.value {
+            AttributeValue::Float(to) => to,
+            _ => return false,
+        };
+        let from = match (running, &old.value) {
+            (Some(current), _) => current,
+            (None, AttributeValue::Float(from)) => *from,
+            _ => return false,
+        };
+        let transition = match self
+            .animations
+            .transitions
+            .iter()
+            .find(|transition| transition.matches(new))
+        {
+            Some(transition) => *transition,
+            None => return false,
+        };
+
+        if from != to {
+            self.animations.active.push(ActiveAnimation {
+                id,
+                transition,
+                from,
+                to,
+                current: from,
+                start: None,
+            });
+        }
+
+        true
+    }
+
+    /// Stop animating an attribute, returning the last value the renderer was sent
+    fn stop_animation(&mut self, id: ElementId, name: &str) -> Option<f64> {
+        let active = &mut self.animations.active;
+        let idx = active
+            .iter()
+            .position(|animation| animation.id == id && animation.transition.name == name)?;
+        Some(active.remove(idx).current)
+    }
+
+    /// Drop the animations of an element that was removed, so they don't write to whatever reuses its id
+    pub(crate) fn stop_element_animations(&mut self, id: ElementId) {
+        self.animations
+            .active
+            .retain(|animation| animation.id != id);
+    }
+}
@@ -13,13 +13,16 @@ use std::panic::AssertUnwindSafe;
 /// This should not be implemented outside this module
 pub(crate) unsafe trait AnyProps<'a> {
     fn props_ptr(&self) -> *const ();
+    fn props_mut_ptr(&mut self) -> *mut ();
     fn render(&'a self, bump: &'a ScopeState) -> RenderReturn<'a>;
     unsafe fn memoize(&self, other: &dyn AnyProps) -> bool;
+    fn inspect(&self) -> Option<String>;
 }
 
 pub(crate) struct VProps<'a, P> {
     pub render_fn: fn(Scope<'a, P>) -> Element<'a>,
     pub memo: unsafe fn(&P, &P) -> bool,
+    pub inspect: fn(&P) -> Option<String>,
     pub props: P,
 }
 
@@ -27,11 +30,13 @@ impl<'a, P> VProps<'a, P> {
     pub(crate) fn new(
         render_fn: fn(Scope<'a, P>) -> Element<'a>,
         memo: unsafe fn(&P, &P) -> bool,
+        inspect: fn(&P) -> Option<String>,
         props: P,
     ) -> Self {
         Self {
             render_fn,
             memo,
+            inspect,
             props,
         }
     }
@@ -42,6 +47,10 @@ unsafe impl<'a, P> AnyProps<'a> for VProps<'a, P> {
         &self.props as *const _ as *const ()
     }
 
+    fn props_mut_ptr(&mut self) -> *mut () {
+        &mut self.props as *mut _ as *mut ()
+    }
+
     // Safety:
     // this will downcast the other ptr as our swallowed type!
     // you *must* make this check *before* calling this method
@@ -52,6 +61,10 @@ unsafe impl<'a, P> AnyProps<'a> for VProps<'a, P> {
         (self.memo)(real_us, real_other)
     }
 
+    fn inspect(&self) -> Option<String> {
+        (self.inspect)(&self.props)
+    }
+
     fn render(&'a self, cx: &'a ScopeState) -> RenderReturn<'a> {
         let res = std::panic::catch_unwind(AssertUnwindSafe(move || {
             // Call the render function directly
@@ -69,6 +82,15 @@ unsafe impl<'a, P> AnyProps<'a> for VProps<'a, P> {
             Err(err) => {
                 let component_name = cx.name();
                 tracing::error!("Error while rendering component `{component_name}`: {err:?}");
+
+                // The panic is captured like any other error, so a boundary can render a fallback
+                let message = err
+                    .downcast_ref::<&str>()
+                    .map(|message| message.to_string())
+                    .or_else(|| err.downcast_ref::<String>().cloned())
+                    .unwrap_or_default();
+                cx.throw(format!("`{}` panicked: {}", component_name, message));
+
                 RenderReturn::default()
             }
         }
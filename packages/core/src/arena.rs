@@ -2,7 +2,7 @@ use std::ptr::NonNull;
 
 use crate::{
     innerlude::DirtyScope, nodes::RenderReturn, nodes::VNode, virtual_dom::VirtualDom,
-    AttributeValue, DynamicNode, ScopeId,
+    AttributeValue, DynamicNode, ScopeId, TemplatePath,
 };
 
 /// An Element's unique identifier.
@@ -26,7 +26,7 @@ pub(crate) struct ElementRef {
 
 #[derive(Clone, Copy, Debug)]
 pub enum ElementPath {
-    Deep(&'static [u8]),
+    Deep(TemplatePath<'static>),
     Root(usize),
 }
 
@@ -41,7 +41,11 @@ impl ElementRef {
 }
 
 impl VirtualDom {
-    pub(crate) fn next_element(&mut self, template: &VNode, path: &'static [u8]) -> ElementId {
+    pub(crate) fn next_element(
+        &mut self,
+        template: &VNode,
+        path: TemplatePath<'static>,
+    ) -> ElementId {
         self.next_reference(template, ElementPath::Deep(path))
     }
 
@@ -84,6 +88,11 @@ impl VirtualDom {
             );
         }
 
+        self.stop_element_animations(el);
+        self.forget_delegated_listeners(el);
+        self.forget_element_refs(el);
+        self.stale_text.remove(&el);
+
         self.elements.try_remove(el.0)
     }
 
@@ -102,6 +111,10 @@ impl VirtualDom {
         });
 
         self.ensure_drop_safety(id);
+        self.drop_effects(id);
+        self.runtime.dependencies.forget_scope(id);
+        self.resume_suspended(id);
+        self.portals.remove(&id);
 
         if recursive {
             if let Some(root) = self.scopes[id.0].try_root_node() {
@@ -181,19 +194,19 @@ impl VirtualDom {
 }
 
 impl ElementPath {
-    pub(crate) fn is_decendant(&self, small: &&[u8]) -> bool {
+    pub(crate) fn is_decendant(&self, small: &TemplatePath) -> bool {
         match *self {
-            ElementPath::Deep(big) => small.len() <= big.len() && *small == &big[..small.len()],
-            ElementPath::Root(r) => small.len() == 1 && small[0] == r as u8,
+            ElementPath::Deep(big) => big.starts_with(small),
+            ElementPath::Root(r) => small.len() == 1 && small.first() == Some(r as u8),
         }
     }
 }
 
-impl PartialEq<&[u8]> for ElementPath {
-    fn eq(&self, other: &&[u8]) -> bool {
+impl PartialEq<TemplatePath<'_>> for ElementPath {
+    fn eq(&self, other: &TemplatePath) -> bool {
         match *self {
-            ElementPath::Deep(deep) => deep.eq(*other),
-            ElementPath::Root(r) => other.len() == 1 && other[0] == r as u8,
+            ElementPath::Deep(deep) => deep == *other,
+            ElementPath::Root(r) => other.len() == 1 && other.first() == Some(r as u8),
         }
     }
 }
@@ -0,0 +1,105 @@
+use rustc_hash::{FxHashMap, FxHashSet};
+
+use crate::{ScopeId, VirtualDom};
+
+/// A limit on how many times a single scope can render in one tick, to catch components that re-render themselves in
+/// a loop.
+///
+/// A tick is a single call to [`VirtualDom::render_immediate`], [`VirtualDom::render_with_deadline`], or
+/// [`VirtualDom::render_with_yield`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RenderBudget {
+    /// The number of times a scope can render in one tick before it is over budget
+    pub max_renders: usize,
+
+    /// Put off any renders of a scope that is over budget until the next tick
+    ///
+    /// If this is false, the scope keeps rendering and is only reported.
+    pub defer: bool,
+}
+
+impl Default for RenderBudget {
+    fn default() -> Self {
+        Self {
+            max_renders: 100,
+            defer: true,
+        }
+    }
+}
+
+pub(crate) struct RenderBudgetState {
+    budget: RenderBudget,
+    on_exceeded: Box<dyn FnMut(ScopeId)>,
+    renders: FxHashMap<ScopeId, usize>,
+    deferred: FxHashSet<ScopeId>,
+}
+
+impl VirtualDom {
+    /// Limit how many times each scope can render in a single tick.
+    ///
+    /// The first time a scope goes over budget in a tick, `on_exceeded` is called with its id.
+    ///
+    /// ```rust, ignore
+    /// let dom = VirtualDom::new(app).with_render_budget(RenderBudget::default(), |scope| {
+    ///     tracing::warn!("{:?} is re-rendering in a loop", scope);
+    /// });
+    /// ```
+    pub fn with_render_budget(
+        mut self,
+        budget: RenderBudget,
+        on_exceeded: impl FnMut(ScopeId) + 'static,
+    ) -> Self {
+        self.render_budget = Some(RenderBudgetState {
+            budget,
+            on_exceeded: Box::new(on_exceeded),
+            renders: FxHashMap::default(),
+            deferred: FxHashSet::default(),
+        });
+        self
+    }
+
+    /// Reset the render counts and bring back any renders that were put off in the last tick
+    pub(crate) fn start_render_budget_tick(&mut self) {
+        let deferred = match &mut self.render_budget {
+            Some(state) => {
+                state.renders.clear();
+                std::mem::take(&mut state.deferred)
+            }
+            None => return,
+        };
+
+        for id in deferred {
+            self.mark_dirty(id);
+        }
+    }
+
+    /// Check if any renders were put off until the next tick
+    pub(crate) fn has_deferred_renders(&self) -> bool {
+        matches!(&self.render_budget, Some(state) if !state.deferred.is_empty())
+    }
+
+    /// Count a render of this scope, returning true if the render should be put off until the next tick
+    pub(crate) fn over_render_budget(&mut self, id: ScopeId) -> bool {
+        let state = match &mut self.render_budget {
+            Some(state) => state,
+            None => return false,
+        };
+
+        let renders = state.renders.entry(id).or_default();
+        *renders += 1;
+
+        if *renders <= state.budget.max_renders {
+            return false;
+        }
+
+        if *renders == state.budget.max_renders + 1 {
+            (state.on_exceeded)(id);
+        }
+
+        if state.budget.defer {
+            state.deferred.insert(id);
+        }
+
+        state.budget.defer
+    }
+}
@@ -0,0 +1,74 @@
+use crate::innerlude::*;
+use std::{cell::Cell, rc::Rc};
+
+impl<'src> ScopeState {
+    /// Render a child that is only rebuilt when its dependencies change.
+    ///
+    /// The child is rendered as its own component. When this scope re-renders with dependencies that are equal to the
+    /// last ones, the child is skipped entirely - the render closure is not called and the subtree it rendered before
+    /// is kept as is. The key identifies the child if it is rendered as part of a list.
+    ///
+    /// Because the render closure can outlive this render, it can't borrow from this scope. Clone or move anything it
+    /// needs into it.
+    ///
+    /// ```rust, ignore
+    /// fn Dashboard(cx: Scope<DashboardProps>) -> Element {
+    ///     let points = cx.props.points.clone();
+    ///     cx.render(rsx! {
+    ///         h1 { "{cx.props.title}" }
+    ///         cx.cache_child("chart", cx.props.points.len(), move |cx| {
+    ///             cx.render(rsx! { Chart { points: points.clone() } })
+    ///         })
+    ///     })
+    /// }
+    /// ```
+    pub fn cache_child(
+        &'src self,
+        key: &'src str,
+        deps: impl AnyValue,
+        render: impl for<'a> Fn(&'a ScopeState) -> Element<'a> + 'static,
+    ) -> Element<'src> {
+        static TEMPLATE: Template = Template {
+            name: concat!(file!(), ":", line!(), ":", column!(), ":0"),
+            roots: &[TemplateNode::Dynamic { id: 0 }],
+            node_paths: &[PackedPath::new(&[0])],
+            attr_paths: &[],
+            ..Template::EMPTY
+        };
+
+        let props = CachedChildProps {
+            deps: Box::new(deps),
+            render: Rc::new(render),
+        };
+
+        Some(VNode {
+            key: Some(key),
+            parent: Default::default(),
+            template: Cell::new(TEMPLATE),
+            root_ids: bumpalo::collections::Vec::new_in(self.bump()).into(),
+            dynamic_nodes: self
+                .bump()
+                .alloc([self.component(CachedChild, props, "CachedChild")]),
+            dynamic_attrs: &[],
+        })
+    }
+}
+
+struct CachedChildProps {
+    deps: Box<dyn AnyValue>,
+    render: Rc<dyn for<'a> Fn(&'a ScopeState) -> Element<'a>>,
+}
+
+impl Properties for CachedChildProps {
+    type Builder = ();
+    const IS_STATIC: bool = true;
+    fn builder() -> Self::Builder {}
+    unsafe fn memoize(&self, other: &Self) -> bool {
+        self.deps.any_cmp(&*other.deps)
+    }
+}
+
+#[allow(non_snake_case)]
+fn CachedChild(cx: Scope<CachedChildProps>) -> Element {
+    (cx.props.render)(cx)
+}
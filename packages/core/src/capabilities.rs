@@ -0,0 +1,75 @@
+use crate::VirtualDom;
+
+/// The features a renderer supports beyond creating and updating a tree of nodes.
+///
+/// The VirtualDom avoids sending mutations that the renderer can't handle, and falls back to something the renderer
+/// can handle where possible. By default, every capability is assumed to be supported, except for the ones that
+/// introduce new kinds of mutations for older renderers to trip over.
+///
+/// ```rust, ignore
+/// let dom = VirtualDom::new(app).with_capabilities(Capabilities {
+///     raw_html: false,
+///     ..Default::default()
+/// });
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Capabilities {
+    /// Can the renderer set the inner HTML of an element with the `dangerous_inner_html` attribute?
+    ///
+    /// If not, dynamic `dangerous_inner_html` values are sent with [`crate::Mutation::SetText`] so they show up as
+    /// escaped text instead.
+    pub raw_html: bool,
+
+    /// Can the renderer scroll elements? If not, [`crate::Mutation::CaptureScroll`] and
+    /// [`crate::Mutation::RestoreScroll`] are never sent.
+    pub scrolling: bool,
+
+    /// Can the renderer inject stylesheets? If not, [`crate::Mutation::InjectStyle`] is never sent.
+    pub styles: bool,
+
+    /// Does the renderer understand [`crate::Mutation::CreateElementWithAttrs`]? If so, template roots are created
+    /// together with their dynamic attributes and listeners in a single mutation instead of one mutation each.
+    ///
+    /// This is off by default.
+    pub grouped_attributes: bool,
+
+    /// Does the renderer listen for events at the root of the document? If so, listeners are announced with
+    /// [`crate::Mutation::RegisterDelegated`] once per event instead of [`crate::Mutation::NewEventListener`] once per
+    /// element. `mounted` listeners are still attached to each element.
+    ///
+    /// This is off by default.
+    pub delegated_events: bool,
+
+    /// Does the renderer wait for the listeners of an event before it finishes the event? If not,
+    /// [`crate::Event::prevent_default`] can't stop the default action, and calling it logs a warning that points to the
+    /// `prevent_default` attribute instead.
+    ///
+    /// This is off by default.
+    pub runtime_prevent_default: bool,
+}
+
+impl Default for Capabilities {
+    fn default() -> Self {
+        Self {
+            raw_html: true,
+            scrolling: true,
+            styles: true,
+            grouped_attributes: false,
+            delegated_events: false,
+            runtime_prevent_default: false,
+        }
+    }
+}
+
+impl VirtualDom {
+    /// Build the virtualdom for a renderer with the given capabilities
+    pub fn with_capabilities(mut self, capabilities: Capabilities) -> Self {
+        self.capabilities = capabilities;
+        self
+    }
+
+    /// Get the capabilities of the renderer this virtualdom is rendering to
+    pub fn capabilities(&self) -> Capabilities {
+        self.capabilities
+    }
+}
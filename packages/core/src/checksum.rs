@@ -0,0 +1,141 @@
+use crate::{
+    innerlude::VNode,
+    walk::{TreeVisitor, VisitedAttribute},
+    ElementId, VirtualDom,
+};
+
+/// A hasher over the structure of a mounted tree: element tags, attributes, and text.
+///
+/// Both ends of a remote renderer can feed their view of the tree into this hasher and compare the results to detect
+/// when they have fallen out of sync. The VirtualDom side is computed with [`VNode::structure_checksum`]. The renderer
+/// side should walk its tree in document order, calling [`StructureHasher::enter_element`] and
+/// [`StructureHasher::exit_element`] around the children of each element.
+///
+/// Placeholders are part of the structure since every renderer needs to keep a node around for them. Listeners are
+/// not part of the structure.
+///
+/// The hash is FNV-1a, so it is stable across platforms, processes, and compiler versions.
+#[derive(Debug, Clone)]
+pub struct StructureHasher {
+    hash: u64,
+}
+
+const FNV_OFFSET: u64 = 0xcbf2_9ce4_8422_2325;
+const FNV_PRIME: u64 = 0x0000_0100_0000_01b3;
+
+impl Default for StructureHasher {
+    fn default() -> Self {
+        Self { hash: FNV_OFFSET }
+    }
+}
+
+impl StructureHasher {
+    /// Create a new hasher for an empty tree
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Start an element with the given tag and attributes.
+    ///
+    /// The order of the attributes does not matter - they are sorted by name before being hashed.
+    pub fn enter_element<'a>(
+        &mut self,
+        tag: &str,
+        attributes: impl IntoIterator<Item = (&'a str, &'a str)>,
+    ) {
+        let mut attributes = attributes.into_iter().collect::<Vec<_>>();
+        attributes.sort_unstable();
+
+        self.write_tag(b'<');
+        self.write_str(tag);
+        for (name, value) in attributes {
+            self.write_tag(b'=');
+            self.write_str(name);
+            self.write_str(value);
+        }
+    }
+
+    /// Finish the most recently entered element
+    pub fn exit_element(&mut self) {
+        self.write_tag(b'>');
+    }
+
+    /// Add a text node
+    pub fn text(&mut self, value: &str) {
+        self.write_tag(b'"');
+        self.write_str(value);
+    }
+
+    /// Add a placeholder node
+    pub fn placeholder(&mut self) {
+        self.write_tag(b'?');
+    }
+
+    /// Get the checksum of everything written so far
+    pub fn finish(&self) -> u64 {
+        self.hash
+    }
+
+    fn write_tag(&mut self, tag: u8) {
+        self.write(&[tag]);
+    }
+
+    fn write_str(&mut self, value: &str) {
+        // Prefix with the length so "ab" + "c" and "a" + "bc" don't collide
+        self.write(&(value.len() as u64).to_le_bytes());
+        self.write(value.as_bytes());
+    }
+
+    fn write(&mut self, bytes: &[u8]) {
+        for byte in bytes {
+            self.hash ^= *byte as u64;
+            self.hash = self.hash.wrapping_mul(FNV_PRIME);
+        }
+    }
+}
+
+impl<'b> VNode<'b> {
+    /// Compute a checksum of the structure of this node as it is mounted in the renderer.
+    ///
+    /// This descends into fragments and the rendered output of components, so the VirtualDom that owns the node is
+    /// required. See [`StructureHasher`] for how a renderer can compute the matching checksum on its side.
+    pub fn structure_checksum(&'b self, dom: &'b VirtualDom) -> u64 {
+        let mut hasher = StructureHasher::new();
+        dom.visit_node(self, &mut hasher);
+        hasher.finish()
+    }
+}
+
+impl<'b> TreeVisitor<'b> for StructureHasher {
+    fn enter_element(
+        &mut self,
+        tag: &'b str,
+        _namespace: Option<&'b str>,
+        _id: Option<ElementId>,
+        attrs: &[VisitedAttribute<'b>],
+    ) {
+        let values = attrs
+            .iter()
+            // Listeners aren't structure, custom values are opaque, and none is a removed attribute
+            .filter_map(|attr| Some((attr.name(), attr.text_value(attrs)?)))
+            .collect::<Vec<_>>();
+
+        StructureHasher::enter_element(
+            self,
+            tag,
+            values.iter().map(|(name, value)| (*name, value.as_str())),
+        );
+    }
+
+    fn exit_element(&mut self) {
+        StructureHasher::exit_element(self);
+    }
+
+    fn text(&mut self, value: &'b str, _id: Option<ElementId>) {
+        StructureHasher::text(self, value);
+    }
+
+    fn placeholder(&mut self, _id: Option<ElementId>) {
+        StructureHasher::placeholder(self);
+    }
+}
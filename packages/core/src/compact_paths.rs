@@ -0,0 +1,279 @@
+use std::fmt::Debug;
+
+use crate::Template;
+
+/// A path to a node in a template packed into a single `u32`.
+///
+/// Each of the first seven nibbles holds the index of a child, and the last nibble holds the length of the path. Only
+/// paths that are at most [`PackedPath::MAX_DEPTH`] deep and never go past the 16th child of an element can be packed.
+/// Paths that don't fit are stored as [`PackedPath::unpacked`] with the index of the path in
+/// [`Template::unpacked_paths`].
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct PackedPath(u32);
+
+impl PackedPath {
+    /// The deepest path that can be packed
+    pub const MAX_DEPTH: usize = 7;
+
+    // The length nibble of a path that points into the unpacked paths of the template
+    const UNPACKED_TAG: u32 = 0xF << 28;
+
+    // Every bit that holds a step of the path
+    const STEPS: u32 = !(0xF << 28);
+
+    /// Pack a path
+    ///
+    /// Panics if the path doesn't fit. Use [`PackedPath::pack`] for paths that may be too deep or too wide.
+    pub const fn new(path: &[u8]) -> Self {
+        match Self::pack(path) {
+            Some(packed) => packed,
+            None => panic!("the path is too deep or too wide to be packed"),
+        }
+    }
+
+    /// Mark a path that was too deep or too wide to be packed. It is read from [`Template::unpacked_paths`] at
+    /// `index` instead.
+    pub const fn unpacked(index: usize) -> Self {
+        assert!(index < 1 << 28, "too many unpacked paths in one template");
+        Self(Self::UNPACKED_TAG | index as u32)
+    }
+
+    /// Pack a path, if it is shallow and narrow enough to fit
+    pub const fn pack(path: &[u8]) -> Option<Self> {
+        if path.len() > Self::MAX_DEPTH {
+            return None;
+        }
+
+        let mut packed = (path.len() as u32) << 28;
+        let mut idx = 0;
+        while idx < path.len() {
+            if path[idx] > 0xF {
+                return None;
+            }
+            packed |= (path[idx] as u32) << (idx * 4);
+            idx += 1;
+        }
+
+        Some(Self(packed))
+    }
+
+    /// The raw bits of the path, for renderers that walk packed paths themselves
+    pub const fn to_bits(self) -> u32 {
+        self.0
+    }
+
+    /// Was this path too deep or too wide to be packed?
+    pub const fn is_unpacked(self) -> bool {
+        self.0 & Self::UNPACKED_TAG == Self::UNPACKED_TAG
+    }
+
+    /// The index in [`Template::unpacked_paths`] an unpacked path is read from
+    pub const fn unpacked_index(self) -> Option<usize> {
+        if self.is_unpacked() {
+            Some((self.0 & Self::STEPS) as usize)
+        } else {
+            None
+        }
+    }
+
+    /// The number of steps in the path. Unpacked paths have no steps of their own.
+    pub const fn len(self) -> usize {
+        if self.is_unpacked() {
+            0
+        } else {
+            (self.0 >> 28) as usize
+        }
+    }
+
+    /// Is this the path to the root?
+    pub const fn is_empty(self) -> bool {
+        self.len() == 0
+    }
+
+    /// Get the child index at one step of the path
+    pub const fn get(self, idx: usize) -> Option<u8> {
+        if idx < self.len() {
+            Some(((self.0 >> (idx * 4)) & 0xF) as u8)
+        } else {
+            None
+        }
+    }
+
+    /// Iterate over the child indices of the path
+    pub fn iter(self) -> impl Iterator<Item = u8> {
+        (0..self.len()).map(move |idx| ((self.0 >> (idx * 4)) & 0xF) as u8)
+    }
+
+    /// Unpack the path into its child indices
+    pub fn to_vec(self) -> Vec<u8> {
+        self.iter().collect()
+    }
+
+    // The same path without its first step
+    const fn without_first(self) -> Self {
+        match self.len() {
+            0 => self,
+            len => Self((((len - 1) as u32) << 28) | ((self.0 & Self::STEPS) >> 4)),
+        }
+    }
+}
+
+impl Debug for PackedPath {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.unpacked_index() {
+            Some(index) => write!(f, "PackedPath::unpacked({index})"),
+            None => f.debug_tuple("PackedPath").field(&self.to_vec()).finish(),
+        }
+    }
+}
+
+/// Pack the paths of a template. Paths that don't fit are pushed to `unpacked`, and point to their index in it.
+pub fn pack_paths<'p>(
+    paths: impl IntoIterator<Item = &'p [u8]>,
+    unpacked: &mut Vec<&'p [u8]>,
+) -> Vec<PackedPath> {
+    paths
+        .into_iter()
+        .map(|path| {
+            PackedPath::pack(path).unwrap_or_else(|| {
+                unpacked.push(path);
+                PackedPath::unpacked(unpacked.len() - 1)
+            })
+        })
+        .collect()
+}
+
+/// A path to a dynamic node or attribute of a [`Template`], or to a node the renderer loads from a template.
+///
+/// Most paths are [`TemplatePath::Packed`]. Only the paths that were too deep or too wide to pack are slices.
+#[derive(Clone, Copy)]
+pub enum TemplatePath<'a> {
+    /// A packed path
+    Packed(PackedPath),
+
+    /// A path as a slice of child indices
+    Slice(&'a [u8]),
+}
+
+impl<'a> TemplatePath<'a> {
+    fn select(packed: &'a [PackedPath], unpacked: &'a [&'a [u8]], idx: usize) -> Option<Self> {
+        let path = *packed.get(idx)?;
+        match path.unpacked_index() {
+            Some(index) => unpacked.get(index).copied().map(TemplatePath::Slice),
+            None => Some(TemplatePath::Packed(path)),
+        }
+    }
+
+    /// The number of steps in the path
+    pub fn len(&self) -> usize {
+        match self {
+            TemplatePath::Packed(path) => path.len(),
+            TemplatePath::Slice(path) => path.len(),
+        }
+    }
+
+    /// Is this the path to the root?
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Get the child index at one step of the path
+    pub fn get(&self, idx: usize) -> Option<u8> {
+        match self {
+            TemplatePath::Packed(path) => path.get(idx),
+            TemplatePath::Slice(path) => path.get(idx).copied(),
+        }
+    }
+
+    /// The index of the root the path starts at
+    pub fn first(&self) -> Option<u8> {
+        self.get(0)
+    }
+
+    /// Iterate over the child indices of the path
+    pub fn iter(self) -> impl Iterator<Item = u8> + 'a {
+        (0..self.len()).filter_map(move |idx| self.get(idx))
+    }
+
+    /// Unpack the path into its child indices
+    pub fn to_vec(&self) -> Vec<u8> {
+        self.iter().collect()
+    }
+
+    /// Does this path start with every step of `ancestor`?
+    pub fn starts_with(&self, ancestor: &TemplatePath) -> bool {
+        ancestor.len() <= self.len() && ancestor.iter().zip(self.iter()).all(|(a, b)| a == b)
+    }
+
+    /// The path relative to the root it starts at, which is the form renderers load nodes with
+    pub(crate) fn without_root(self) -> Self {
+        match self {
+            TemplatePath::Packed(path) => TemplatePath::Packed(path.without_first()),
+            TemplatePath::Slice(path) => TemplatePath::Slice(path.get(1..).unwrap_or_default()),
+        }
+    }
+}
+
+// Paths print as their steps, so packed and slice paths look the same
+impl Debug for TemplatePath<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+impl<'b> PartialEq<TemplatePath<'b>> for TemplatePath<'_> {
+    fn eq(&self, other: &TemplatePath<'b>) -> bool {
+        match (self, other) {
+            (TemplatePath::Packed(a), TemplatePath::Packed(b)) => a == b,
+            _ => self.len() == other.len() && self.iter().eq(other.iter()),
+        }
+    }
+}
+
+impl Eq for TemplatePath<'_> {}
+
+// Paths are sent to renderers as their steps, in the same format as a slice of child indices
+#[cfg(feature = "serialize")]
+impl serde::Serialize for TemplatePath<'_> {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_seq(self.iter())
+    }
+}
+
+#[cfg(feature = "serialize")]
+impl<'de> serde::Deserialize<'de> for TemplatePath<'_> {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        use serde::Deserialize;
+
+        let path = Vec::<u8>::deserialize(deserializer)?;
+        Ok(match PackedPath::pack(&path) {
+            Some(packed) => TemplatePath::Packed(packed),
+            None => TemplatePath::Slice(Box::leak(path.into_boxed_slice())),
+        })
+    }
+}
+
+impl<'a> Template<'a> {
+    /// The path to a dynamic node
+    pub fn node_path(&self, idx: usize) -> Option<TemplatePath<'a>> {
+        TemplatePath::select(self.node_paths, self.unpacked_paths, idx)
+    }
+
+    /// The path to a dynamic attribute
+    pub fn attr_path(&self, idx: usize) -> Option<TemplatePath<'a>> {
+        TemplatePath::select(self.attr_paths, self.unpacked_paths, idx)
+    }
+
+    /// Iterate over the paths to the dynamic nodes of this template
+    pub fn node_path_iter(&self) -> impl Iterator<Item = TemplatePath<'a>> + 'a {
+        let (packed, unpacked) = (self.node_paths, self.unpacked_paths);
+        (0..packed.len()).filter_map(move |idx| TemplatePath::select(packed, unpacked, idx))
+    }
+
+    /// Iterate over the paths to the dynamic attributes of this template
+    pub fn attr_path_iter(&self) -> impl Iterator<Item = TemplatePath<'a>> + 'a {
+        let (packed, unpacked) = (self.attr_paths, self.unpacked_paths);
+        (0..packed.len()).filter_map(move |idx| TemplatePath::select(packed, unpacked, idx))
+    }
+}
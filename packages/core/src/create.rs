@@ -1,18 +1,24 @@
 use crate::any_props::AnyProps;
-use crate::innerlude::{BorrowedAttributeValue, VComponent, VPlaceholder, VText};
-use crate::mutations::Mutation;
-use crate::mutations::Mutation::*;
+use crate::drag::DRAG_EVENTS;
+use crate::events::listener_event;
+use crate::innerlude::{BorrowedAttributeValue, InitialAttribute, VComponent, VPlaceholder, VText};
 use crate::nodes::VNode;
 use crate::nodes::{DynamicNode, TemplateNode};
+use crate::spread::spread_attributes;
+use crate::style_map::style_map_attributes;
 use crate::virtual_dom::VirtualDom;
-use crate::{AttributeValue, ElementId, RenderReturn, ScopeId, Template};
+use crate::{
+    AttributeValue, ElementId, RenderReturn, ScopeId, Template, TemplatePath, WriteMutations,
+};
 use std::cell::Cell;
 use std::iter::Peekable;
 use TemplateNode::*;
 
 #[cfg(debug_assertions)]
-fn sort_bfs(paths: &[&'static [u8]]) -> Vec<(usize, &'static [u8])> {
-    let mut with_indecies = paths.iter().copied().enumerate().collect::<Vec<_>>();
+fn sort_bfs(
+    paths: impl Iterator<Item = TemplatePath<'static>>,
+) -> Vec<(usize, TemplatePath<'static>)> {
+    let mut with_indecies = paths.enumerate().collect::<Vec<_>>();
     with_indecies.sort_unstable_by(|(_, a), (_, b)| {
         let mut a = a.iter();
         let mut b = b.iter();
@@ -20,7 +26,7 @@ fn sort_bfs(paths: &[&'static [u8]]) -> Vec<(usize, &'static [u8])> {
             match (a.next(), b.next()) {
                 (Some(a), Some(b)) => {
                     if a != b {
-                        return a.cmp(b);
+                        return a.cmp(&b);
                     }
                 }
                 // The shorter path goes first
@@ -44,7 +50,7 @@ fn sorting() {
         (4, &[1, 2]),
     ];
     assert_eq!(
-        sort_bfs(&[&[0, 1,], &[0, 2,], &[1, 0,], &[1, 0, 1,], &[1, 2,],]),
+        sort_slices(&[&[0, 1,], &[0, 2,], &[1, 0,], &[1, 0, 1,], &[1, 2,],]),
         r
     );
     let r: [(usize, &[u8]); 6] = [
@@ -56,27 +62,46 @@ fn sorting() {
         (5, &[2]),
     ];
     assert_eq!(
-        sort_bfs(&[&[0], &[0, 1], &[0, 1, 2], &[1], &[1, 2], &[2],]),
+        sort_slices(&[&[0], &[0, 1], &[0, 1, 2], &[1], &[1, 2], &[2],]),
         r
     );
+
+    fn sort_slices(paths: &[&'static [u8]]) -> Vec<(usize, &'static [u8])> {
+        sort_bfs(paths.iter().copied().map(TemplatePath::Slice))
+            .into_iter()
+            .map(|(idx, path)| match path {
+                TemplatePath::Slice(path) => (idx, path),
+                TemplatePath::Packed(_) => unreachable!(),
+            })
+            .collect()
+    }
 }
 
 impl<'b> VirtualDom {
-    /// Create a new template [`VNode`] and write it to the [`Mutations`] buffer.
+    /// Create a new template [`VNode`] and write its mutations to the renderer.
     ///
     /// This method pushes the ScopeID to the internal scopestack and returns the number of nodes created.
-    pub(crate) fn create_scope(&mut self, scope: ScopeId, template: &'b VNode<'b>) -> usize {
+    pub(crate) fn create_scope(
+        &mut self,
+        to: &mut impl WriteMutations<'b>,
+        scope: ScopeId,
+        template: &'b VNode<'b>,
+    ) -> usize {
         self.runtime.scope_stack.borrow_mut().push(scope);
-        let nodes = self.create(template);
+        let nodes = self.create(to, template);
         self.runtime.scope_stack.borrow_mut().pop();
         nodes
     }
 
     /// Create this template and write its mutations
-    pub(crate) fn create(&mut self, node: &'b VNode<'b>) -> usize {
+    pub(crate) fn create(
+        &mut self,
+        to: &mut impl WriteMutations<'b>,
+        node: &'b VNode<'b>,
+    ) -> usize {
         // check for a overriden template
         #[cfg(debug_assertions)]
-        {
+        if self.templates_replaced {
             let (path, byte_index) = node.template.get().name.rsplit_once(':').unwrap();
             if let Some(template) = self
                 .templates
@@ -87,6 +112,12 @@ impl<'b> VirtualDom {
             }
         }
 
+        // Link the node to the element it bubbles events to
+        node.parent.set(self.mount_parent);
+
+        // Apply or drop the attributes that depend on runtime flags before anything sees the template
+        self.resolve_conditional_attributes(node);
+
         // Initialize the root nodes slice
         {
             let mut nodes_mut = node.root_ids.borrow_mut();
@@ -96,30 +127,18 @@ impl<'b> VirtualDom {
 
         // The best renderers will have templates prehydrated and registered
         // Just in case, let's create the template using instructions anyways
-        self.register_template(node.template.get());
-
-        // we know that this will generate at least one mutation per node
-        self.mutations
-            .edits
-            .reserve(node.template.get().roots.len());
+        self.register_template(to, node.template.get());
+        self.mount_template_use(node);
 
         // Walk the roots, creating nodes and assigning IDs
         // nodes in an iterator of ((dynamic_node_index, sorted_index), path)
         // todo: adjust dynamic nodes to be in the order of roots and then leaves (ie BFS)
         #[cfg(not(debug_assertions))]
         let (mut attrs, mut nodes) = (
+            node.template.get().attr_path_iter().enumerate().peekable(),
             node.template
                 .get()
-                .attr_paths
-                .iter()
-                .copied()
-                .enumerate()
-                .peekable(),
-            node.template
-                .get()
-                .node_paths
-                .iter()
-                .copied()
+                .node_path_iter()
                 .enumerate()
                 .map(|(i, path)| ((i, i), path))
                 .peekable(),
@@ -129,8 +148,8 @@ impl<'b> VirtualDom {
         #[cfg(debug_assertions)]
         let (attrs_sorted, nodes_sorted) = {
             (
-                sort_bfs(node.template.get().attr_paths),
-                sort_bfs(node.template.get().node_paths),
+                sort_bfs(node.template.get().attr_path_iter()),
+                sort_bfs(node.template.get().node_path_iter()),
             )
         };
         #[cfg(debug_assertions)]
@@ -146,7 +165,8 @@ impl<'b> VirtualDom {
             )
         };
 
-        node.template
+        let m = node
+            .template
             .get()
             .roots
             .iter()
@@ -154,55 +174,73 @@ impl<'b> VirtualDom {
             .map(|(idx, root)| match root {
                 DynamicText { id } | Dynamic { id } => {
                     nodes.next().unwrap();
-                    self.write_dynamic_root(node, *id)
+                    self.write_dynamic_root(to, node, *id)
                 }
                 Element { .. } => {
                     #[cfg(not(debug_assertions))]
-                    let id = self.write_element_root(node, idx, &mut attrs, &mut nodes, &[]);
+                    let id = self.write_element_root(to, node, idx, &mut attrs, &mut nodes, &[]);
                     #[cfg(debug_assertions)]
-                    let id =
-                        self.write_element_root(node, idx, &mut attrs, &mut nodes, &nodes_sorted);
+                    let id = self.write_element_root(
+                        to,
+                        node,
+                        idx,
+                        &mut attrs,
+                        &mut nodes,
+                        &nodes_sorted,
+                    );
                     id
                 }
-                Text { .. } => self.write_static_text_root(node, idx),
+                Text { .. } => self.write_static_text_root(to, node, idx),
             })
-            .sum()
+            .sum();
+
+        self.mount_scroll_restoration(node);
+        self.mount_element_key(node);
+
+        m
     }
 
-    fn write_static_text_root(&mut self, node: &VNode, idx: usize) -> usize {
+    fn write_static_text_root(
+        &mut self,
+        to: &mut impl WriteMutations<'b>,
+        node: &VNode,
+        idx: usize,
+    ) -> usize {
         // Simply just load the template root, no modifications needed
-        self.load_template_root(node, idx);
+        self.load_template_root(to, node, idx);
 
         // Text producs just one node on the stack
         1
     }
 
-    fn write_dynamic_root(&mut self, template: &'b VNode<'b>, idx: usize) -> usize {
+    pub(crate) fn write_dynamic_root(
+        &mut self,
+        to: &mut impl WriteMutations<'b>,
+        template: &'b VNode<'b>,
+        idx: usize,
+    ) -> usize {
         use DynamicNode::*;
         match &template.dynamic_nodes[idx] {
             node @ Component { .. } | node @ Fragment(_) => {
-                self.create_dynamic_node(template, node, idx)
+                self.create_dynamic_node(to, template, node, idx)
             }
             Placeholder(VPlaceholder { id }) => {
                 let id = self.set_slot(template, id, idx);
-                self.mutations.push(CreatePlaceholder { id });
+                to.create_placeholder(id);
                 1
             }
             Text(VText { id, value }) => {
                 let id = self.set_slot(template, id, idx);
-                self.create_static_text(value, id);
+                self.create_static_text(to, value, id);
                 1
             }
         }
     }
 
-    fn create_static_text(&mut self, value: &str, id: ElementId) {
+    fn create_static_text(&mut self, to: &mut impl WriteMutations<'b>, value: &str, id: ElementId) {
         // Safety: we promise not to re-alias this text later on after committing it to the mutation
         let unbounded_text: &str = unsafe { std::mem::transmute(value) };
-        self.mutations.push(CreateTextNode {
-            value: unbounded_text,
-            id,
-        });
+        to.create_text_node(unbounded_text, id);
     }
 
     /// We write all the descndent data for this element
@@ -212,20 +250,33 @@ impl<'b> VirtualDom {
     /// We want to make sure we write these nodes while on top of the root
     fn write_element_root(
         &mut self,
+        to: &mut impl WriteMutations<'b>,
         template: &'b VNode<'b>,
         root_idx: usize,
-        dynamic_attrs: &mut Peekable<impl Iterator<Item = (usize, &'static [u8])>>,
-        dynamic_nodes_iter: &mut Peekable<impl Iterator<Item = ((usize, usize), &'static [u8])>>,
-        dynamic_nodes: &[(usize, &'static [u8])],
+        dynamic_attrs: &mut Peekable<impl Iterator<Item = (usize, TemplatePath<'static>)>>,
+        dynamic_nodes_iter: &mut Peekable<
+            impl Iterator<Item = ((usize, usize), TemplatePath<'static>)>,
+        >,
+        dynamic_nodes: &[(usize, TemplatePath<'static>)],
     ) -> usize {
         // Load the template root and get the ID for the node on the stack
-        let root_on_stack = self.load_template_root(template, root_idx);
+        let root_on_stack = if self.capabilities.grouped_attributes {
+            self.load_template_root_with_attrs(to, template, root_idx, dynamic_attrs)
+        } else {
+            self.load_template_root(to, template, root_idx)
+        };
 
         // Write all the attributes below this root
-        self.write_attrs_on_root(dynamic_attrs, root_idx as u8, root_on_stack, template);
+        self.write_attrs_on_root(to, dynamic_attrs, root_idx as u8, root_on_stack, template);
 
         // Load in all of the placeholder or dynamic content under this root too
-        self.load_placeholders(dynamic_nodes_iter, dynamic_nodes, root_idx as u8, template);
+        self.load_placeholders(
+            to,
+            dynamic_nodes_iter,
+            dynamic_nodes,
+            root_idx as u8,
+            template,
+        );
 
         1
     }
@@ -246,8 +297,11 @@ impl<'b> VirtualDom {
     #[allow(unused)]
     fn load_placeholders(
         &mut self,
-        dynamic_nodes_iter: &mut Peekable<impl Iterator<Item = ((usize, usize), &'static [u8])>>,
-        dynamic_nodes: &[(usize, &'static [u8])],
+        to: &mut impl WriteMutations<'b>,
+        dynamic_nodes_iter: &mut Peekable<
+            impl Iterator<Item = ((usize, usize), TemplatePath<'static>)>,
+        >,
+        dynamic_nodes: &[(usize, TemplatePath<'static>)],
         root_idx: u8,
         template: &'b VNode<'b>,
     ) {
@@ -265,29 +319,35 @@ impl<'b> VirtualDom {
             .map(|sorted_index| dynamic_nodes[sorted_index].0);
 
         for idx in reversed_iter {
-            let m = self.create_dynamic_node(template, &template.dynamic_nodes[idx], idx);
+            let m = self.create_dynamic_node(to, template, &template.dynamic_nodes[idx], idx);
             if m > 0 {
                 // The path is one shorter because the top node is the root
-                let path = &template.template.get().node_paths[idx][1..];
-                self.mutations.push(ReplacePlaceholder { m, path });
+                let path = node_path(template, idx).without_root();
+                to.replace_placeholder(path, m);
             }
         }
     }
 
     fn write_attrs_on_root(
         &mut self,
-        attrs: &mut Peekable<impl Iterator<Item = (usize, &'static [u8])>>,
+        to: &mut impl WriteMutations<'b>,
+        attrs: &mut Peekable<impl Iterator<Item = (usize, TemplatePath<'static>)>>,
         root_idx: u8,
         root: ElementId,
-        node: &VNode,
+        node: &'b VNode<'b>,
     ) {
-        while let Some((mut attr_id, path)) =
-            attrs.next_if(|(_, p)| p.first().copied() == Some(root_idx))
-        {
-            let id = self.assign_static_node_as_dynamic(path, root, node, attr_id);
+        while let Some((mut attr_id, path)) = attrs.next_if(|(_, p)| p.first() == Some(root_idx)) {
+            let id = self.assign_static_node_as_dynamic(to, path, root, node);
 
+            // The selection of a text field is set after its value, so the value doesn't move it again
+            let mut selection = Vec::new();
             loop {
-                self.write_attribute(&node.dynamic_attrs[attr_id], id);
+                let attribute = &node.dynamic_attrs[attr_id];
+                if attribute.is_selection() {
+                    selection.push(attribute);
+                } else {
+                    self.write_attribute(to, attribute, id);
+                }
 
                 // Only push the dynamic attributes forward if they match the current path (same element)
                 match attrs.next_if(|(_, p)| *p == path) {
@@ -295,10 +355,18 @@ impl<'b> VirtualDom {
                     None => break,
                 }
             }
+            for attribute in selection {
+                self.write_attribute(to, attribute, id);
+            }
         }
     }
 
-    fn write_attribute(&mut self, attribute: &'b crate::Attribute<'b>, id: ElementId) {
+    fn write_attribute(
+        &mut self,
+        to: &mut impl WriteMutations<'b>,
+        attribute: &'b crate::Attribute<'b>,
+        id: ElementId,
+    ) {
         // Make sure we set the attribute's associated id
         attribute.mounted_element.set(id);
 
@@ -307,37 +375,173 @@ impl<'b> VirtualDom {
 
         match &attribute.value {
             AttributeValue::Listener(_) => {
-                self.mutations.push(NewEventListener {
-                    // all listeners start with "on"
-                    name: &unbounded_name[2..],
-                    id,
-                })
+                // Capture listeners need the renderer to listen for the same event
+                let (name, _) = listener_event(unbounded_name);
+                if !self.delegate_listener(to, name, id) {
+                    to.new_event_listener(name, id)
+                }
             }
-            _ => {
-                // Safety: we promise not to re-alias this text later on after committing it to the mutation
-                let value: BorrowedAttributeValue<'b> = (&attribute.value).into();
-                let unbounded_value = unsafe { std::mem::transmute(value) };
-
-                self.mutations.push(SetAttribute {
-                    name: unbounded_name,
-                    value: unbounded_value,
-                    ns: attribute.namespace,
-                    id,
-                })
+            // The element is created without the attribute, so there is nothing to remove
+            AttributeValue::None => {}
+            // The VirtualDom needs the drag events of the element to pick up its payload
+            _ if attribute.is_drag_payload() => {
+                for name in DRAG_EVENTS {
+                    if !self.delegate_listener(to, name, id) {
+                        to.new_event_listener(name, id)
+                    }
+                }
             }
+            _ => self.set_attribute(to, attribute, id),
         }
     }
 
-    fn load_template_root(&mut self, template: &VNode, root_idx: usize) -> ElementId {
+    /// Send the value of an attribute to the renderer
+    pub(crate) fn set_attribute(
+        &mut self,
+        to: &mut impl WriteMutations<'b>,
+        attribute: &'b crate::Attribute<'b>,
+        id: ElementId,
+    ) {
+        // The remount token and drag payloads are only for the VirtualDom
+        if attribute.is_remount_token() || attribute.is_drag_payload() {
+            return;
+        }
+
+        if let AttributeValue::ElementRefByKey(key) = attribute.value {
+            return self.track_element_ref(attribute, key, id);
+        }
+        self.forget_element_ref(attribute, id);
+
+        if let AttributeValue::StyleMap(properties) = attribute.value {
+            return self.set_style_map(to, properties, id);
+        }
+
+        if let AttributeValue::Spread(attributes) = attribute.value {
+            return self.set_spread(to, attributes, id);
+        }
+
+        // Safety: we promise not to re-alias this text later on after committing it to the mutation
+        let unbounded_name: &str = unsafe { std::mem::transmute(attribute.name) };
+        let value: BorrowedAttributeValue<'b> = (&attribute.value).into();
+        let unbounded_value: BorrowedAttributeValue<'static> =
+            unsafe { std::mem::transmute(value) };
+
+        // Renderers without raw html support show the html as text instead
+        if !self.capabilities.raw_html && attribute.name == "dangerous_inner_html" {
+            match unbounded_value {
+                BorrowedAttributeValue::Text(value) => return to.set_text(value, id),
+                BorrowedAttributeValue::None => return to.set_text("", id),
+                _ => {}
+            }
+        }
+
+        to.set_attribute(unbounded_name, attribute.namespace, &unbounded_value, id)
+    }
+
+    fn load_template_root(
+        &mut self,
+        to: &mut impl WriteMutations<'b>,
+        template: &VNode,
+        root_idx: usize,
+    ) -> ElementId {
         // Get an ID for this root since it's a real root
         let this_id = self.next_root(template, root_idx);
         template.root_ids.borrow_mut()[root_idx] = this_id;
 
-        self.mutations.push(LoadTemplate {
-            name: template.template.get().name,
-            index: root_idx,
-            id: this_id,
-        });
+        to.load_template(template.template.get().name, root_idx, this_id);
+
+        this_id
+    }
+
+    /// Load the template root along with the dynamic attributes and listeners on the root itself
+    fn load_template_root_with_attrs(
+        &mut self,
+        to: &mut impl WriteMutations<'b>,
+        template: &'b VNode<'b>,
+        root_idx: usize,
+        dynamic_attrs: &mut Peekable<impl Iterator<Item = (usize, TemplatePath<'static>)>>,
+    ) -> ElementId {
+        let on_root = |(_, path): &(usize, TemplatePath<'static>)| {
+            path.len() == 1 && path.first() == Some(root_idx as u8)
+        };
+
+        let tag = match template.template.get().roots[root_idx] {
+            Element { tag, .. } if matches!(dynamic_attrs.peek(), Some(attr) if on_root(attr)) => {
+                tag
+            }
+            _ => return self.load_template_root(to, template, root_idx),
+        };
+
+        let this_id = self.next_root(template, root_idx);
+        template.root_ids.borrow_mut()[root_idx] = this_id;
+
+        let mut attrs = Vec::new();
+        let mut listeners = Vec::new();
+        let mut as_text = None;
+        while let Some((attr_id, _)) = dynamic_attrs.next_if(on_root) {
+            let attribute = &template.dynamic_attrs[attr_id];
+            attribute.mounted_element.set(this_id);
+
+            // Safety: we promise not to re-alias this text later on after committing it to the mutation
+            let unbounded_name: &str = unsafe { std::mem::transmute(attribute.name) };
+
+            match &attribute.value {
+                AttributeValue::Listener(_) => {
+                    let (name, _) = listener_event(unbounded_name);
+                    if !self.delegate_listener(to, name, this_id) {
+                        listeners.push(name)
+                    }
+                }
+                _ if attribute.is_drag_payload() => {
+                    for name in DRAG_EVENTS {
+                        if !self.delegate_listener(to, name, this_id) {
+                            listeners.push(name)
+                        }
+                    }
+                }
+                _ if attribute.is_remount_token() => {}
+                AttributeValue::ElementRefByKey(key) => {
+                    self.track_element_ref(attribute, key, this_id)
+                }
+                AttributeValue::None => {}
+                AttributeValue::StyleMap(properties) => {
+                    let properties = style_map_attributes(properties);
+                    attrs.extend(properties.map(|attr| unsafe { std::mem::transmute(attr) }))
+                }
+                AttributeValue::Spread(spread) => {
+                    let spread = spread_attributes(spread);
+                    attrs.extend(spread.map(|attr| unsafe { std::mem::transmute(attr) }))
+                }
+                // Html shown as text needs its own mutation once the element exists
+                _ if !self.capabilities.raw_html && attribute.name == "dangerous_inner_html" => {
+                    as_text = Some(attribute)
+                }
+                value => {
+                    let value: BorrowedAttributeValue<'b> = value.into();
+                    attrs.push(InitialAttribute {
+                        name: unbounded_name,
+                        value: unsafe { std::mem::transmute(value) },
+                        ns: attribute.namespace,
+                    })
+                }
+            }
+        }
+
+        // The selection of a text field is set after its value, so the value doesn't move it again
+        attrs.sort_by_key(|attr| attr.is_selection());
+
+        to.create_element_with_attrs(
+            template.template.get().name,
+            root_idx,
+            this_id,
+            tag,
+            &attrs,
+            &listeners,
+        );
+
+        if let Some(attribute) = as_text {
+            self.set_attribute(to, attribute, this_id);
+        }
 
         this_id
     }
@@ -351,10 +555,10 @@ impl<'b> VirtualDom {
     /// If the node is not on the stack, we create a new ID for it and assign it
     fn assign_static_node_as_dynamic(
         &mut self,
-        path: &'static [u8],
+        to: &mut impl WriteMutations<'b>,
+        path: TemplatePath<'static>,
         this_id: ElementId,
         template: &VNode,
-        attr_id: usize,
     ) -> ElementId {
         if path.len() == 1 {
             return this_id;
@@ -362,18 +566,17 @@ impl<'b> VirtualDom {
 
         // if attribute is on a root node, then we've already created the element
         // Else, it's deep in the template and we should create a new id for it
-        let id = self.next_element(template, template.template.get().attr_paths[attr_id]);
+        let id = self.next_element(template, path);
 
-        self.mutations.push(Mutation::AssignId {
-            path: &path[1..],
-            id,
-        });
+        to.assign_id(path.without_root(), id);
 
         id
     }
 
     /// Insert a new template into the VirtualDom's template registry
     pub(crate) fn register_template_first_byte_index(&mut self, mut template: Template<'static>) {
+        self.templates_replaced = true;
+
         // First, make sure we mark the template as seen, regardless if we process it
         let (path, _) = template.name.rsplit_once(':').unwrap();
         if let Some((_, old_template)) = self
@@ -396,23 +599,39 @@ impl<'b> VirtualDom {
 
         // If it's all dynamic nodes, then we don't need to register it
         if !template.is_completely_dynamic() {
-            self.mutations.templates.push(template);
+            self.queued_templates.push(template);
         }
     }
 
     /// Insert a new template into the VirtualDom's template registry
     // used in conditional compilation
     #[allow(unused_mut)]
-    pub(crate) fn register_template(&mut self, mut template: Template<'static>) {
+    pub(crate) fn register_template(
+        &mut self,
+        to: &mut impl WriteMutations<'b>,
+        mut template: Template<'static>,
+    ) {
+        // Templates are identified by the address of their name, which is a different static for every rsx! call
+        if !self.seen_templates.insert(template.name.as_ptr() as usize) {
+            return;
+        }
+
         let (path, byte_index) = template.name.rsplit_once(':').unwrap();
         let byte_index = byte_index.parse::<usize>().unwrap();
-        // First, check if we've already seen this template
-        if self
+        // First, check if we've already seen this template under another address
+        if let Some(existing) = self
             .templates
             .get(&path)
-            .filter(|set| set.contains_key(&byte_index))
-            .is_none()
+            .and_then(|set| set.get(&byte_index))
         {
+            // Names only hold the path of the file in its crate, so two crates can generate the same name
+            if !self.templates_replaced && *existing != template {
+                tracing::warn!(
+                    "Two different templates are named {}. The renderer only knows the first one",
+                    template.name
+                );
+            }
+        } else {
             // if hot reloading is enabled, then we need to check for a template that has overriten this one
             #[cfg(debug_assertions)]
             if let Some(mut new_template) = self
@@ -430,36 +649,55 @@ impl<'b> VirtualDom {
                 .or_default()
                 .insert(byte_index, template);
 
+            // Styles are injected the first time the template is seen, no matter how many instances it has
+            #[cfg(feature = "scoped-styles")]
+            if let Some(style) = template.style.filter(|_| self.capabilities.styles) {
+                to.inject_style(template.name, style);
+            }
+
             // If it's all dynamic nodes, then we don't need to register it
             if !template.is_completely_dynamic() {
-                self.mutations.templates.push(template);
+                to.register_template(template);
             }
         }
     }
 
     pub(crate) fn create_dynamic_node(
         &mut self,
+        to: &mut impl WriteMutations<'b>,
         template: &'b VNode<'b>,
         node: &'b DynamicNode<'b>,
         idx: usize,
     ) -> usize {
         use DynamicNode::*;
         match node {
-            Text(text) => self.create_dynamic_text(template, text, idx),
-            Placeholder(place) => self.create_placeholder(place, template, idx),
-            Component(component) => self.create_component_node(template, component),
-            Fragment(frag) => frag.iter().map(|child| self.create(child)).sum(),
+            Text(text) => self.create_dynamic_text(to, template, text, idx),
+            Placeholder(place) => self.create_placeholder(to, place, template, idx),
+            Component(component) => {
+                let parent = self.slot_parent(template, idx);
+                self.with_mount_parent(parent, |dom| {
+                    dom.create_component_node(to, template, component)
+                })
+            }
+            Fragment(frag) => {
+                let parent = self.slot_parent(template, idx);
+                self.with_mount_parent(parent, |dom| {
+                    frag.iter().map(|child| dom.create(to, child)).sum()
+                })
+            }
         }
     }
 
     fn create_dynamic_text(
         &mut self,
+        to: &mut impl WriteMutations<'b>,
         template: &'b VNode<'b>,
         text: &'b VText<'b>,
         idx: usize,
     ) -> usize {
         // Allocate a dynamic element reference for this text node
-        let new_id = self.next_element(template, template.template.get().node_paths[idx]);
+        let path = node_path(template, idx);
+        let new_id = self.next_element(template, path);
 
         // Make sure the text node is assigned to the correct element
         text.id.set(Some(new_id));
@@ -468,11 +706,7 @@ impl<'b> VirtualDom {
         let value = unsafe { std::mem::transmute(text.value) };
 
         // Add the mutation to the list
-        self.mutations.push(HydrateText {
-            id: new_id,
-            path: &template.template.get().node_paths[idx][1..],
-            value,
-        });
+        to.hydrate_text(path.without_root(), value, new_id);
 
         // Since we're hydrating an existing node, we don't create any new nodes
         0
@@ -480,21 +714,20 @@ impl<'b> VirtualDom {
 
     pub(crate) fn create_placeholder(
         &mut self,
+        to: &mut impl WriteMutations<'b>,
         placeholder: &VPlaceholder,
         template: &'b VNode<'b>,
         idx: usize,
     ) -> usize {
         // Allocate a dynamic element reference for this text node
-        let id = self.next_element(template, template.template.get().node_paths[idx]);
+        let path = node_path(template, idx);
+        let id = self.next_element(template, path);
 
         // Make sure the text node is assigned to the correct element
         placeholder.id.set(Some(id));
 
         // Assign the ID to the existing node in the template
-        self.mutations.push(AssignId {
-            path: &template.template.get().node_paths[idx][1..],
-            id,
-        });
+        to.assign_id(path.without_root(), id);
 
         // Since the placeholder is already in the DOM, we don't create any new nodes
         0
@@ -502,6 +735,7 @@ impl<'b> VirtualDom {
 
     pub(super) fn create_component_node(
         &mut self,
+        to: &mut impl WriteMutations<'b>,
         template: &'b VNode<'b>,
         component: &'b VComponent<'b>,
     ) -> usize {
@@ -511,11 +745,15 @@ impl<'b> VirtualDom {
         let scope = self.load_scope_from_vcomponent(component);
 
         component.scope.set(Some(scope));
+        self.scopes[scope.0].parent_element.set(self.mount_parent);
 
         match unsafe { self.run_scope(scope).extend_lifetime_ref() } {
             // Create the component's root element
-            Ready(t) => self.create_scope(scope, t),
-            Aborted(t) => self.mount_aborted(template, t),
+            Ready(t) => match self.scopes[scope.0].portal_target.get() {
+                Some(target) => self.create_portal(to, scope, t, target),
+                None => self.create_scope(to, scope, t),
+            },
+            Aborted(t) => self.mount_aborted(to, template, t),
         }
     }
 
@@ -531,9 +769,14 @@ impl<'b> VirtualDom {
             .unwrap_or_else(|| component.scope.get().unwrap())
     }
 
-    fn mount_aborted(&mut self, parent: &'b VNode<'b>, placeholder: &VPlaceholder) -> usize {
-        let id = self.next_element(parent, &[]);
-        self.mutations.push(Mutation::CreatePlaceholder { id });
+    fn mount_aborted(
+        &mut self,
+        to: &mut impl WriteMutations<'b>,
+        parent: &'b VNode<'b>,
+        placeholder: &VPlaceholder,
+    ) -> usize {
+        let id = self.next_element(parent, TemplatePath::Slice(&[]));
+        to.create_placeholder(id);
         placeholder.id.set(Some(id));
         1
     }
@@ -544,26 +787,28 @@ impl<'b> VirtualDom {
         slot: &'b Cell<Option<ElementId>>,
         id: usize,
     ) -> ElementId {
-        let id = self.next_element(template, template.template.get().node_paths[id]);
+        let id = self.next_element(template, node_path(template, id));
         slot.set(Some(id));
         id
     }
 }
 
+fn node_path(template: &VNode, idx: usize) -> TemplatePath<'static> {
+    template.template.get().node_path(idx).unwrap()
+}
+
 fn collect_dyn_node_range(
-    dynamic_nodes: &mut Peekable<impl Iterator<Item = ((usize, usize), &'static [u8])>>,
+    dynamic_nodes: &mut Peekable<impl Iterator<Item = ((usize, usize), TemplatePath<'static>)>>,
     root_idx: u8,
 ) -> Option<(usize, usize)> {
     let start = match dynamic_nodes.peek() {
-        Some(((_, idx), [first, ..])) if *first == root_idx => *idx,
+        Some(((_, idx), path)) if path.first() == Some(root_idx) => *idx,
         _ => return None,
     };
 
     let mut end = start;
 
-    while let Some(((_, idx), p)) =
-        dynamic_nodes.next_if(|(_, p)| matches!(p, [idx, ..] if *idx == root_idx))
-    {
+    while let Some(((_, idx), p)) = dynamic_nodes.next_if(|(_, p)| p.first() == Some(root_idx)) {
         if p.len() == 1 {
             continue;
         }
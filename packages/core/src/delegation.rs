@@ -0,0 +1,78 @@
+use rustc_hash::{FxHashMap, FxHashSet};
+
+use crate::{ElementId, VirtualDom, WriteMutations};
+
+/// The elements with a handler for each event, when listeners are delegated to the root
+#[derive(Default)]
+pub(crate) struct DelegatedEvents {
+    // Event names are kept around even once they have no handlers, so each name is only interned once
+    handlers: FxHashMap<&'static str, FxHashSet<ElementId>>,
+
+    // The events the renderer is listening for at the root
+    registered: FxHashSet<&'static str>,
+}
+
+impl VirtualDom {
+    /// Register a handler with the root listener for its event, announcing the event if it is the first handler.
+    ///
+    /// Returns false if the renderer needs a listener on the element itself instead.
+    pub(crate) fn delegate_listener<'a>(
+        &mut self,
+        to: &mut impl WriteMutations<'a>,
+        name: &str,
+        id: ElementId,
+    ) -> bool {
+        if !self.capabilities.delegated_events || name == "mounted" {
+            return false;
+        }
+
+        let events = &mut self.delegated_events;
+        if !events.handlers.contains_key(name) {
+            // There is only a small, fixed set of event names
+            let name: &'static str = Box::leak(name.to_string().into_boxed_str());
+            events.handlers.insert(name, Default::default());
+        }
+
+        let (&name, _) = events.handlers.get_key_value(name).unwrap();
+        events.handlers.get_mut(name).unwrap().insert(id);
+        if events.registered.insert(name) {
+            to.register_delegated(name);
+        }
+
+        true
+    }
+
+    /// Forget which events the renderer is listening for. A rebuild may go to a renderer that isn't listening for any
+    /// event yet, like after hydration failed, so it announces every event again.
+    pub(crate) fn reset_delegated_events(&mut self) {
+        self.delegated_events.registered.clear();
+    }
+
+    /// Forget the handlers of an element that was removed
+    pub(crate) fn forget_delegated_listeners(&mut self, id: ElementId) {
+        for handlers in self.delegated_events.handlers.values_mut() {
+            handlers.remove(&id);
+        }
+    }
+
+    /// Unregister the events that have no handlers left at the end of a batch of mutations
+    ///
+    /// This waits until the end of the batch so the removal of the last handler doesn't come between the mutations
+    /// that remove its element, and so an event that gets a new handler in the same batch stays registered.
+    pub(crate) fn flush_delegated_events<'a>(&mut self, to: &mut impl WriteMutations<'a>) {
+        let events = &mut self.delegated_events;
+        let handlers = &events.handlers;
+        events.registered.retain(|name| {
+            let unused = handlers[name].is_empty();
+            if unused {
+                to.unregister_delegated(name);
+            }
+            !unused
+        });
+    }
+
+    /// Check if an element has a handler for an event that is delegated to the root listener
+    pub fn has_delegated_listener(&self, id: ElementId, name: &str) -> bool {
+        matches!(self.delegated_events.handlers.get(name), Some(handlers) if handlers.contains(&id))
+    }
+}
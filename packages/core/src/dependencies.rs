@@ -0,0 +1,104 @@
+use std::cell::RefCell;
+
+use rustc_hash::{FxHashMap, FxHashSet};
+
+use crate::{innerlude::ScopeState, slots::SlotDependencies, ScopeId, VirtualDom};
+
+/// An identifier for a value that lives outside of the VirtualDom, like an entry in a store or a field bound to an
+/// attribute.
+///
+/// Components record which values they read with [`ScopeState::read_value`]. When a value changes,
+/// [`VirtualDom::affected_scopes`] finds the components that have to re-render to pick up the change.
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug, PartialOrd, Ord)]
+pub struct ValueId(pub usize);
+
+/// The values each scope read during its last render
+#[derive(Default)]
+pub(crate) struct Dependencies {
+    readers: RefCell<FxHashMap<ValueId, FxHashSet<ScopeId>>>,
+    reads: RefCell<FxHashMap<ScopeId, FxHashSet<ValueId>>>,
+
+    // Values read by text slots are recorded for the slot instead of the scope
+    pub(crate) slots: SlotDependencies,
+}
+
+impl Dependencies {
+    fn read(&self, value: ValueId, scope: ScopeId) {
+        if self.slots.read(value) {
+            return;
+        }
+
+        self.readers
+            .borrow_mut()
+            .entry(value)
+            .or_default()
+            .insert(scope);
+        self.reads
+            .borrow_mut()
+            .entry(scope)
+            .or_default()
+            .insert(value);
+    }
+
+    /// Forget everything the scope read. Called before it renders again and when it is dropped.
+    pub(crate) fn forget_scope(&self, scope: ScopeId) {
+        self.slots.forget_scope(scope);
+
+        let values = match self.reads.borrow_mut().remove(&scope) {
+            Some(values) => values,
+            None => return,
+        };
+
+        let mut readers = self.readers.borrow_mut();
+        for value in values {
+            if let Some(scopes) = readers.get_mut(&value) {
+                scopes.remove(&scope);
+                if scopes.is_empty() {
+                    readers.remove(&value);
+                }
+            }
+        }
+    }
+}
+
+impl ScopeState {
+    /// Record that this component read an external value during this render.
+    ///
+    /// Reads only last until the component renders again, so read the value every render that depends on it. Reads
+    /// inside of a [text slot](ScopeState::text_slot) are recorded for the slot instead of the component.
+    ///
+    /// ```rust, ignore
+    /// fn Price(cx: Scope<PriceProps>) -> Element {
+    ///     cx.read_value(ValueId(cx.props.product));
+    ///     let price = store.price(cx.props.product);
+    ///     cx.render(rsx! { span { "data-price": price } })
+    /// }
+    /// ```
+    pub fn read_value(&self, value: ValueId) {
+        self.runtime.dependencies.read(value, self.scope_id());
+    }
+}
+
+impl VirtualDom {
+    /// Get the scopes that read the value during their last render, in ascending order.
+    ///
+    /// Only these scopes need to be marked dirty when the value changes.
+    ///
+    /// ```rust, ignore
+    /// for scope in dom.affected_scopes(ValueId(product)) {
+    ///     dom.mark_dirty(scope);
+    /// }
+    /// ```
+    pub fn affected_scopes(&self, value: ValueId) -> Vec<ScopeId> {
+        let mut scopes: Vec<_> = self
+            .runtime
+            .dependencies
+            .readers
+            .borrow()
+            .get(&value)
+            .map(|scopes| scopes.iter().copied().collect())
+            .unwrap_or_default();
+        scopes.sort_unstable();
+        scopes
+    }
+}
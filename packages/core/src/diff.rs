@@ -1,20 +1,22 @@
 use crate::{
     any_props::AnyProps,
     arena::ElementId,
-    innerlude::{BorrowedAttributeValue, DirtyScope, VComponent, VPlaceholder, VText},
-    mutations::Mutation,
+    innerlude::{
+        remount_token_changed, BorrowedAttributeValue, DirtyScope, VComponent, VPlaceholder, VText,
+    },
+    mutations::WriteMutations,
     nodes::RenderReturn,
     nodes::{DynamicNode, VNode},
     scopes::ScopeId,
     virtual_dom::VirtualDom,
-    Attribute, TemplateNode,
+    Attribute, AttributeValue, TemplateNode, TemplatePath,
 };
 
 use rustc_hash::{FxHashMap, FxHashSet};
 use DynamicNode::*;
 
 impl<'b> VirtualDom {
-    pub(super) fn diff_scope(&mut self, scope: ScopeId) {
+    pub(super) fn diff_scope(&mut self, to: &mut impl WriteMutations<'b>, scope: ScopeId) {
         self.runtime.scope_stack.borrow_mut().push(scope);
         let scope_state = &mut self.get_scope(scope).unwrap();
         unsafe {
@@ -31,57 +33,61 @@ impl<'b> VirtualDom {
 
             use RenderReturn::{Aborted, Ready};
 
+            // A new root is mounted where the component is
+            let parent = scope_state.parent_element.get();
+            let outer = std::mem::replace(&mut self.mount_parent, parent);
             match (old, new) {
                 // Normal pathway
-                (Ready(l), Ready(r)) => self.diff_node(l, r),
+                (Ready(l), Ready(r)) => self.diff_node(to, l, r),
 
                 // Unwind the mutations if need be
-                (Ready(l), Aborted(p)) => self.diff_ok_to_err(l, p),
+                (Ready(l), Aborted(p)) => self.diff_ok_to_err(to, l, p),
 
                 // Just move over the placeholder
                 (Aborted(l), Aborted(r)) => r.id.set(l.id.get()),
 
                 // Placeholder becomes something
                 // We should also clear the error now
-                (Aborted(l), Ready(r)) => self.replace_placeholder(l, [r]),
+                (Aborted(l), Ready(r)) => self.replace_placeholder(to, l, [r]),
             };
+            self.mount_parent = outer;
         }
         self.runtime.scope_stack.borrow_mut().pop();
     }
 
-    fn diff_ok_to_err(&mut self, l: &'b VNode<'b>, p: &'b VPlaceholder) {
+    fn diff_ok_to_err(
+        &mut self,
+        to: &mut impl WriteMutations<'b>,
+        l: &'b VNode<'b>,
+        p: &'b VPlaceholder,
+    ) {
         let id = self.next_null();
         p.id.set(Some(id));
-        self.mutations.push(Mutation::CreatePlaceholder { id });
-
-        let pre_edits = self.mutations.edits.len();
-
-        self.remove_node(l, true);
-
-        // We should always have a remove mutation
-        // Eventually we don't want to generate placeholders, so this might not be true. But it's true today
-        assert!(self.mutations.edits.len() > pre_edits);
+        to.create_placeholder(id);
 
         // We want to optimize the replace case to use one less mutation if possible
-        // Since mutations are done in reverse, the last node removed will be the first in the stack
-        // Instead of *just* removing it, we can use the replace mutation
-        match self.mutations.edits.pop().unwrap() {
-            Mutation::Remove { id } => self.mutations.push(Mutation::ReplaceWith { id, m: 1 }),
-            _ => panic!("Expected remove mutation from remove_node"),
-        };
+        // Instead of *just* removing the last node, we can replace it with the placeholder
+        self.remove_node(to, l, Some(1), true);
     }
 
-    fn diff_node(&mut self, left_template: &'b VNode<'b>, right_template: &'b VNode<'b>) {
+    fn diff_node(
+        &mut self,
+        to: &mut impl WriteMutations<'b>,
+        left_template: &'b VNode<'b>,
+        right_template: &'b VNode<'b>,
+    ) {
+        right_template.parent.set(left_template.parent.get());
+
         // If hot reloading is enabled, we need to make sure we're using the latest template
         #[cfg(debug_assertions)]
-        {
+        if self.templates_replaced {
             let (path, byte_index) = right_template.template.get().name.rsplit_once(':').unwrap();
             if let Some(map) = self.templates.get(path) {
                 let byte_index = byte_index.parse::<usize>().unwrap();
                 if let Some(&template) = map.get(&byte_index) {
                     right_template.template.set(template);
                     if template != left_template.template.get() {
-                        return self.replace(left_template, [right_template]);
+                        return self.replace(to, left_template, [right_template]);
                     }
                 }
             }
@@ -92,17 +98,33 @@ impl<'b> VirtualDom {
             return;
         }
 
+        self.resolve_conditional_attributes(right_template);
+
         // If the templates are different by name, we need to replace the entire template
         if templates_are_different(left_template, right_template) {
-            return self.light_diff_templates(left_template, right_template);
+            return self.light_diff_templates(to, left_template, right_template);
+        }
+
+        // A new remount token throws away the old node and everything under it, even though nothing else changed
+        if remount_token_changed(left_template, right_template) {
+            return self.replace(to, left_template, [right_template]);
         }
 
         // If the templates are the same, we can diff the attributes and children
         // Start with the attributes
-        left_template
+        //
+        // Only dynamic attributes are ever diffed. Static attributes belong to the template, so a form field without a
+        // dynamic value keeps whatever the user typed into it no matter how often its parents re-render.
+        //
+        // The selection of a text field is updated after everything else, so a new value doesn't move it again
+        let attrs = left_template
             .dynamic_attrs
             .iter()
-            .zip(right_template.dynamic_attrs.iter())
+            .zip(right_template.dynamic_attrs.iter());
+        attrs
+            .clone()
+            .filter(|(_, right_attr)| !right_attr.is_selection())
+            .chain(attrs.filter(|(_, right_attr)| right_attr.is_selection()))
             .for_each(|(left_attr, right_attr)| {
                 // Move over the ID from the old to the new
                 right_attr
@@ -112,19 +134,28 @@ impl<'b> VirtualDom {
                 // We want to make sure anything that gets pulled is valid
                 self.update_template(left_attr.mounted_element.get(), right_template);
 
-                // If the attributes are different (or volatile), we need to update them
-                if left_attr.value != right_attr.value || left_attr.volatile {
-                    self.update_attribute(right_attr, left_attr);
+                // If the attributes are different (or volatile), we need to update them. Volatile attributes are
+                // written in this same pass so the mutations follow the source order of the attributes
+                //
+                // A value that was reused from the last render is the same allocation, so it isn't compared at all
+                let changed = !left_attr.value.ptr_eq(&right_attr.value)
+                    && left_attr.value != right_attr.value;
+                if changed || left_attr.volatile {
+                    self.update_attribute(to, right_attr, left_attr);
                 }
             });
 
-        // Now diff the dynamic nodes
+        // Now diff the dynamic nodes. Anything created in them is mounted under the same element as the old nodes
         left_template
             .dynamic_nodes
             .iter()
             .zip(right_template.dynamic_nodes.iter())
-            .for_each(|(left_node, right_node)| {
-                self.diff_dynamic_node(left_node, right_node, right_template);
+            .enumerate()
+            .for_each(|(idx, (left_node, right_node))| {
+                let parent = self.slot_parent(left_template, idx);
+                self.with_mount_parent(parent, |dom| {
+                    dom.diff_dynamic_node(to, left_node, right_node, right_template, idx)
+                });
             });
 
         // Make sure the roots get transferred over while we're here
@@ -136,6 +167,11 @@ impl<'b> VirtualDom {
             }
         }
 
+        // Elements that are referenced by key follow the key of the node they belong to now
+        if left_template.key != right_template.key {
+            self.mount_element_key(right_template);
+        }
+
         let root_ids = right_template.root_ids.borrow();
 
         // Update the node refs
@@ -148,35 +184,80 @@ impl<'b> VirtualDom {
 
     fn diff_dynamic_node(
         &mut self,
+        to: &mut impl WriteMutations<'b>,
         left_node: &'b DynamicNode<'b>,
         right_node: &'b DynamicNode<'b>,
         node: &'b VNode<'b>,
+        idx: usize,
     ) {
         match (left_node, right_node) {
-            (Text(left), Text(right)) => self.diff_vtext(left, right, node),
-            (Fragment(left), Fragment(right)) => self.diff_non_empty_fragment(left, right),
+            (Text(left), Text(right)) => self.diff_vtext(to, left, right, node),
+            (Fragment(left), Fragment(right)) => self.diff_non_empty_fragment(to, left, right),
             (Placeholder(left), Placeholder(right)) => right.id.set(left.id.get()),
-            (Component(left), Component(right)) => self.diff_vcomponent(left, right, node),
-            (Placeholder(left), Fragment(right)) => self.replace_placeholder(left, *right),
-            (Fragment(left), Placeholder(right)) => self.node_to_placeholder(left, right),
-            _ => todo!("This is an usual custom case for dynamic nodes. We don't know how to handle it yet."),
+            (Component(left), Component(right)) => self.diff_vcomponent(to, left, right, node),
+            (Placeholder(left), Fragment(right)) => self.replace_placeholder(to, left, *right),
+            (Fragment(left), Placeholder(right)) => self.node_to_placeholder(to, left, right),
+            _ => self.replace_dynamic_node(to, left_node, node, idx),
         };
     }
 
-    fn update_attribute(&mut self, right_attr: &'b Attribute<'b>, left_attr: &'b Attribute) {
-        let name = unsafe { std::mem::transmute(left_attr.name) };
-        let value: BorrowedAttributeValue<'b> = (&right_attr.value).into();
-        let value = unsafe { std::mem::transmute(value) };
-        self.mutations.push(Mutation::SetAttribute {
-            id: left_attr.mounted_element.get(),
-            ns: right_attr.namespace,
-            name,
-            value,
-        });
+    /// Replace a dynamic node with a dynamic node of a different kind, like text that becomes a placeholder
+    fn replace_dynamic_node(
+        &mut self,
+        to: &mut impl WriteMutations<'b>,
+        left: &'b DynamicNode<'b>,
+        right_template: &'b VNode<'b>,
+        idx: usize,
+    ) {
+        let m = self.write_dynamic_root(to, right_template, idx);
+
+        // The last node removed can be replaced with the new nodes instead of removed
+        match left {
+            Fragment(nodes) => self.remove_nodes(to, nodes, Some(m)),
+            node => self.remove_dynamic_node(to, node, Some(m), true),
+        }
+    }
+
+    fn update_attribute(
+        &mut self,
+        to: &mut impl WriteMutations<'b>,
+        right_attr: &'b Attribute<'b>,
+        left_attr: &'b Attribute,
+    ) {
+        // The new closure is picked up as soon as the attribute has the element's id, so the renderer doesn't need to
+        // hear about it - even if the listener is volatile
+        if let AttributeValue::Listener(_) = right_attr.value {
+            return;
+        }
+
+        let id = left_attr.mounted_element.get();
+        if self.start_animation(right_attr, left_attr, id) {
+            return;
+        }
+
+        match (&left_attr.value, &right_attr.value) {
+            (AttributeValue::StyleMap(old), AttributeValue::StyleMap(new)) => {
+                return self.diff_style_map(to, old, new, id);
+            }
+            // Clear out the old style string so it doesn't leave properties behind
+            (_, AttributeValue::StyleMap(_)) => to.set_attribute(
+                unsafe { std::mem::transmute(right_attr.name) },
+                right_attr.namespace,
+                &BorrowedAttributeValue::None,
+                id,
+            ),
+            (AttributeValue::Spread(old), AttributeValue::Spread(new)) => {
+                return self.diff_spread(to, old, new, id);
+            }
+            _ => {}
+        }
+
+        self.set_attribute(to, right_attr, id);
     }
 
     fn diff_vcomponent(
         &mut self,
+        to: &mut impl WriteMutations<'b>,
         left: &'b VComponent<'b>,
         right: &'b VComponent<'b>,
         right_template: &'b VNode<'b>,
@@ -187,7 +268,7 @@ impl<'b> VirtualDom {
 
         // Replace components that have different render fns
         if left.render_fn != right.render_fn {
-            return self.replace_vcomponent(right_template, right, left);
+            return self.replace_vcomponent(to, right_template, right, left);
         }
 
         // Make sure the new vcomponent has the right scopeid associated to it
@@ -218,7 +299,7 @@ impl<'b> VirtualDom {
 
         // Now run the component and diff it
         self.run_scope(scope_id);
-        self.diff_scope(scope_id);
+        self.diff_scope(to, scope_id);
 
         self.dirty_scopes.remove(&DirtyScope {
             height: self.runtime.get_context(scope_id).unwrap().height,
@@ -228,25 +309,16 @@ impl<'b> VirtualDom {
 
     fn replace_vcomponent(
         &mut self,
+        to: &mut impl WriteMutations<'b>,
         right_template: &'b VNode<'b>,
         right: &'b VComponent<'b>,
         left: &'b VComponent<'b>,
     ) {
-        let m = self.create_component_node(right_template, right);
-
-        let pre_edits = self.mutations.edits.len();
-
-        self.remove_component_node(left, true);
-
-        assert!(self.mutations.edits.len() > pre_edits);
+        let m = self.create_component_node(to, right_template, right);
 
         // We want to optimize the replace case to use one less mutation if possible
-        // Since mutations are done in reverse, the last node removed will be the first in the stack
-        // Instead of *just* removing it, we can use the replace mutation
-        match self.mutations.edits.pop().unwrap() {
-            Mutation::Remove { id } => self.mutations.push(Mutation::ReplaceWith { id, m }),
-            at => panic!("Expected remove mutation from remove_node {:#?}", at),
-        };
+        // Instead of *just* removing the last node, we can replace it with the new nodes
+        self.remove_component_node(to, left, Some(m), true);
     }
 
     /// Lightly diff the two templates, checking only their roots.
@@ -286,12 +358,24 @@ impl<'b> VirtualDom {
     ///     Component { ..props }
     /// }
     /// ```
-    fn light_diff_templates(&mut self, left: &'b VNode<'b>, right: &'b VNode<'b>) {
+    fn light_diff_templates(
+        &mut self,
+        to: &mut impl WriteMutations<'b>,
+        left: &'b VNode<'b>,
+        right: &'b VNode<'b>,
+    ) {
         match matching_components(left, right) {
-            None => self.replace(left, [right]),
-            Some(components) => components
-                .into_iter()
-                .for_each(|(l, r)| self.diff_vcomponent(l, r, right)),
+            None => self.replace(to, left, [right]),
+            Some(components) => {
+                // The new node takes the place of the old one without being created
+                self.unmount_template_use(left);
+                self.mount_template_use(right);
+                self.with_mount_parent(left.parent.get(), |dom| {
+                    components
+                        .into_iter()
+                        .for_each(|(l, r)| dom.diff_vcomponent(to, l, r, right))
+                })
+            }
         }
     }
 
@@ -299,20 +383,32 @@ impl<'b> VirtualDom {
     ///
     /// This just moves the ID of the old node over to the new node, and then sets the text of the new node if it's
     /// different.
-    fn diff_vtext(&mut self, left: &'b VText<'b>, right: &'b VText<'b>, node: &'b VNode<'b>) {
+    fn diff_vtext(
+        &mut self,
+        to: &mut impl WriteMutations<'b>,
+        left: &'b VText<'b>,
+        right: &'b VText<'b>,
+        node: &'b VNode<'b>,
+    ) {
         let id = left
             .id
             .get()
-            .unwrap_or_else(|| self.next_element(node, &[0]));
+            .unwrap_or_else(|| self.next_element(node, TemplatePath::Slice(&[0])));
 
         right.id.set(Some(id));
-        if left.value != right.value {
+        let stale = self.stale_text.remove(&id);
+        if stale || left.value != right.value {
             let value = unsafe { std::mem::transmute(right.value) };
-            self.mutations.push(Mutation::SetText { id, value });
+            to.set_text(value, id);
         }
     }
 
-    fn diff_non_empty_fragment(&mut self, old: &'b [VNode<'b>], new: &'b [VNode<'b>]) {
+    fn diff_non_empty_fragment(
+        &mut self,
+        to: &mut impl WriteMutations<'b>,
+        old: &'b [VNode<'b>],
+        new: &'b [VNode<'b>],
+    ) {
         let new_is_keyed = new[0].key.is_some();
         let old_is_keyed = old[0].key.is_some();
         debug_assert!(
@@ -325,9 +421,9 @@ impl<'b> VirtualDom {
         );
 
         if new_is_keyed && old_is_keyed {
-            self.diff_keyed_children(old, new);
+            self.diff_keyed_children(to, old, new);
         } else {
-            self.diff_non_keyed_children(old, new);
+            self.diff_non_keyed_children(to, old, new);
         }
     }
 
@@ -339,7 +435,12 @@ impl<'b> VirtualDom {
     //     [... parent]
     //
     // the change list stack is in the same state when this function returns.
-    fn diff_non_keyed_children(&mut self, old: &'b [VNode<'b>], new: &'b [VNode<'b>]) {
+    fn diff_non_keyed_children(
+        &mut self,
+        to: &mut impl WriteMutations<'b>,
+        old: &'b [VNode<'b>],
+        new: &'b [VNode<'b>],
+    ) {
         use std::cmp::Ordering;
 
         // Handled these cases in `diff_children` before calling this function.
@@ -347,13 +448,15 @@ impl<'b> VirtualDom {
         debug_assert!(!old.is_empty());
 
         match old.len().cmp(&new.len()) {
-            Ordering::Greater => self.remove_nodes(&old[new.len()..]),
-            Ordering::Less => self.create_and_insert_after(&new[old.len()..], old.last().unwrap()),
+            Ordering::Greater => self.remove_nodes(to, &old[new.len()..], None),
+            Ordering::Less => {
+                self.create_and_insert_after(to, &new[old.len()..], old.last().unwrap())
+            }
             Ordering::Equal => {}
         }
 
         for (new, old) in new.iter().zip(old.iter()) {
-            self.diff_node(old, new);
+            self.diff_node(to, old, new);
         }
     }
 
@@ -373,7 +476,12 @@ impl<'b> VirtualDom {
     // https://github.com/infernojs/inferno/blob/36fd96/packages/inferno/src/DOM/patching.ts#L530-L739
     //
     // The stack is empty upon entry.
-    fn diff_keyed_children(&mut self, old: &'b [VNode<'b>], new: &'b [VNode<'b>]) {
+    fn diff_keyed_children(
+        &mut self,
+        to: &mut impl WriteMutations<'b>,
+        old: &'b [VNode<'b>],
+        new: &'b [VNode<'b>],
+    ) {
         if cfg!(debug_assertions) {
             let mut keys = rustc_hash::FxHashSet::default();
             let mut assert_unique_keys = |children: &'b [VNode<'b>]| {
@@ -401,7 +509,7 @@ impl<'b> VirtualDom {
         //
         // `shared_prefix_count` is the count of how many nodes at the start of
         // `new` and `old` share the same keys.
-        let (left_offset, right_offset) = match self.diff_keyed_ends(old, new) {
+        let (left_offset, right_offset) = match self.diff_keyed_ends(to, old, new) {
             Some(count) => count,
             None => return,
         };
@@ -420,25 +528,27 @@ impl<'b> VirtualDom {
 
         if new_middle.is_empty() {
             // remove the old elements
-            self.remove_nodes(old_middle);
+            self.remove_nodes(to, old_middle, None);
         } else if old_middle.is_empty() {
             // there were no old elements, so just create the new elements
             // we need to find the right "foothold" though - we shouldn't use the "append" at all
             if left_offset == 0 {
                 // insert at the beginning of the old list
                 let foothold = &old[old.len() - right_offset];
-                self.create_and_insert_before(new_middle, foothold);
+                self.create_and_insert_before(to, new_middle, foothold);
             } else if right_offset == 0 {
                 // insert at the end  the old list
                 let foothold = old.last().unwrap();
-                self.create_and_insert_after(new_middle, foothold);
+                self.create_and_insert_after(to, new_middle, foothold);
             } else {
-                // inserting in the middle
-                let foothold = &old[left_offset - 1];
-                self.create_and_insert_after(new_middle, foothold);
+                // inserting in the middle, before the first node of the shared suffix
+                let foothold = &old[old.len() - right_offset];
+                self.create_and_insert_before(to, new_middle, foothold);
             }
         } else {
-            self.diff_keyed_middle(old_middle, new_middle);
+            // Nodes moved to the end of the middle are anchored to the first node of the shared suffix, if there is one
+            let anchor = new.get(new.len() - right_offset);
+            self.diff_keyed_middle(to, old_middle, new_middle, anchor);
         }
     }
 
@@ -449,6 +559,7 @@ impl<'b> VirtualDom {
     /// If there is no offset, then this function returns None and the diffing is complete.
     fn diff_keyed_ends(
         &mut self,
+        to: &mut impl WriteMutations<'b>,
         old: &'b [VNode<'b>],
         new: &'b [VNode<'b>],
     ) -> Option<(usize, usize)> {
@@ -459,21 +570,23 @@ impl<'b> VirtualDom {
             if old.key != new.key {
                 break;
             }
-            self.diff_node(old, new);
+            self.diff_node(to, old, new);
             left_offset += 1;
         }
 
         // If that was all of the old children, then create and append the remaining
         // new children and we're finished.
         if left_offset == old.len() {
-            self.create_and_insert_after(&new[left_offset..], old.last().unwrap());
+            if left_offset < new.len() {
+                self.create_and_insert_after(to, &new[left_offset..], old.last().unwrap());
+            }
             return None;
         }
 
         // And if that was all of the new children, then remove all of the remaining
         // old children and we're finished.
         if left_offset == new.len() {
-            self.remove_nodes(&old[left_offset..]);
+            self.remove_nodes(to, &old[left_offset..], None);
             return None;
         }
 
@@ -484,7 +597,7 @@ impl<'b> VirtualDom {
             if old.key != new.key {
                 break;
             }
-            self.diff_node(old, new);
+            self.diff_node(to, old, new);
             right_offset += 1;
         }
 
@@ -505,7 +618,13 @@ impl<'b> VirtualDom {
     //
     // Upon exit from this function, it will be restored to that same self.
     #[allow(clippy::too_many_lines)]
-    fn diff_keyed_middle(&mut self, old: &'b [VNode<'b>], new: &'b [VNode<'b>]) {
+    fn diff_keyed_middle(
+        &mut self,
+        to: &mut impl WriteMutations<'b>,
+        old: &'b [VNode<'b>],
+        new: &'b [VNode<'b>],
+        anchor: Option<&'b VNode<'b>>,
+    ) {
         /*
         1. Map the old keys into a numerical ordering based on indices.
         2. Create a map of old key to its index
@@ -561,8 +680,8 @@ impl<'b> VirtualDom {
         // create the new children afresh.
         if shared_keys.is_empty() {
             if old.get(0).is_some() {
-                self.remove_nodes(&old[1..]);
-                self.replace(&old[0], new);
+                self.remove_nodes(to, &old[1..], None);
+                self.replace(to, &old[0], new);
             } else {
                 // I think this is wrong - why are we appending?
                 // only valid of the if there are no trailing elements
@@ -578,7 +697,7 @@ impl<'b> VirtualDom {
         for child in old {
             let key = child.key.unwrap();
             if !shared_keys.contains(&key) {
-                self.remove_node(child, true);
+                self.remove_node(to, child, None, true);
             }
         }
 
@@ -605,7 +724,7 @@ impl<'b> VirtualDom {
         }
 
         for idx in &lis_sequence {
-            self.diff_node(&old[new_index_to_old_index[*idx]], &new[*idx]);
+            self.diff_node(to, &old[new_index_to_old_index[*idx]], &new[*idx]);
         }
 
         let mut nodes_created = 0;
@@ -617,19 +736,25 @@ impl<'b> VirtualDom {
                 let new_idx = idx + last + 1;
                 let old_index = new_index_to_old_index[new_idx];
                 if old_index == u32::MAX as usize {
-                    nodes_created += self.create(new_node);
+                    nodes_created += self.create(to, new_node);
                 } else {
-                    self.diff_node(&old[old_index], new_node);
-                    nodes_created += self.push_all_real_nodes(new_node);
+                    self.diff_node(to, &old[old_index], new_node);
+                    nodes_created += self.push_all_real_nodes(to, new_node);
                 }
             }
 
-            let id = self.find_last_element(&new[last]);
             if nodes_created > 0 {
-                self.mutations.push(Mutation::InsertAfter {
-                    id,
-                    m: nodes_created,
-                })
+                match anchor {
+                    Some(anchor) => {
+                        let id = self.find_first_element(anchor);
+                        to.insert_before(id, nodes_created)
+                    }
+                    // Only fall back to the last node that stayed in place at the very end of the list
+                    None => {
+                        let id = self.find_last_element(&new[last]);
+                        to.insert_after(id, nodes_created)
+                    }
+                }
             }
             nodes_created = 0;
         }
@@ -643,19 +768,16 @@ impl<'b> VirtualDom {
                     let new_idx = idx + next + 1;
                     let old_index = new_index_to_old_index[new_idx];
                     if old_index == u32::MAX as usize {
-                        nodes_created += self.create(new_node);
+                        nodes_created += self.create(to, new_node);
                     } else {
-                        self.diff_node(&old[old_index], new_node);
-                        nodes_created += self.push_all_real_nodes(new_node);
+                        self.diff_node(to, &old[old_index], new_node);
+                        nodes_created += self.push_all_real_nodes(to, new_node);
                     }
                 }
 
                 let id = self.find_first_element(&new[last]);
                 if nodes_created > 0 {
-                    self.mutations.push(Mutation::InsertBefore {
-                        id,
-                        m: nodes_created,
-                    });
+                    to.insert_before(id, nodes_created);
                 }
 
                 nodes_created = 0;
@@ -669,25 +791,26 @@ impl<'b> VirtualDom {
             for (idx, new_node) in new[..first_lis].iter().enumerate() {
                 let old_index = new_index_to_old_index[idx];
                 if old_index == u32::MAX as usize {
-                    nodes_created += self.create(new_node);
+                    nodes_created += self.create(to, new_node);
                 } else {
-                    self.diff_node(&old[old_index], new_node);
-                    nodes_created += self.push_all_real_nodes(new_node);
+                    self.diff_node(to, &old[old_index], new_node);
+                    nodes_created += self.push_all_real_nodes(to, new_node);
                 }
             }
 
             let id = self.find_first_element(&new[first_lis]);
             if nodes_created > 0 {
-                self.mutations.push(Mutation::InsertBefore {
-                    id,
-                    m: nodes_created,
-                });
+                to.insert_before(id, nodes_created);
             }
         }
     }
 
     /// Push all the real nodes on the stack
-    fn push_all_real_nodes(&mut self, node: &'b VNode<'b>) -> usize {
+    fn push_all_real_nodes(
+        &mut self,
+        to: &mut impl WriteMutations<'b>,
+        node: &'b VNode<'b>,
+    ) -> usize {
         node.template
             .get()
             .roots
@@ -697,40 +820,38 @@ impl<'b> VirtualDom {
                 let node = match node.dynamic_root(idx) {
                     Some(node) => node,
                     None => {
-                        self.mutations.push(Mutation::PushRoot {
-                            id: node.root_ids.borrow()[idx],
-                        });
+                        to.push_root(node.root_ids.borrow()[idx]);
                         return 1;
                     }
                 };
 
                 match node {
                     Text(t) => {
-                        self.mutations.push(Mutation::PushRoot {
-                            id: t.id.get().unwrap(),
-                        });
+                        to.push_root(t.id.get().unwrap());
                         1
                     }
                     Placeholder(t) => {
-                        self.mutations.push(Mutation::PushRoot {
-                            id: t.id.get().unwrap(),
-                        });
+                        to.push_root(t.id.get().unwrap());
                         1
                     }
                     Fragment(nodes) => nodes
                         .iter()
-                        .map(|node| self.push_all_real_nodes(node))
+                        .map(|node| self.push_all_real_nodes(to, node))
                         .sum(),
 
                     Component(comp) => {
                         let scope = comp.scope.get().unwrap();
+                        if let Some(id) = self.portal_placeholder(scope) {
+                            to.push_root(id);
+                            return 1;
+                        }
                         match unsafe {
                             self.get_scope(scope)
                                 .unwrap()
                                 .root_node()
                                 .extend_lifetime_ref()
                         } {
-                            RenderReturn::Ready(node) => self.push_all_real_nodes(node),
+                            RenderReturn::Ready(node) => self.push_all_real_nodes(to, node),
                             RenderReturn::Aborted(_node) => todo!(),
                         }
                     }
@@ -739,86 +860,112 @@ impl<'b> VirtualDom {
             .sum()
     }
 
-    fn create_children(&mut self, nodes: impl IntoIterator<Item = &'b VNode<'b>>) -> usize {
+    fn create_children(
+        &mut self,
+        to: &mut impl WriteMutations<'b>,
+        nodes: impl IntoIterator<Item = &'b VNode<'b>>,
+    ) -> usize {
         nodes
             .into_iter()
-            .fold(0, |acc, child| acc + self.create(child))
+            .fold(0, |acc, child| acc + self.create(to, child))
     }
 
-    fn create_and_insert_before(&mut self, new: &'b [VNode<'b>], before: &'b VNode<'b>) {
-        let m = self.create_children(new);
+    fn create_and_insert_before(
+        &mut self,
+        to: &mut impl WriteMutations<'b>,
+        new: &'b [VNode<'b>],
+        before: &'b VNode<'b>,
+    ) {
+        let m = self.create_children(to, new);
         let id = self.find_first_element(before);
-        self.mutations.push(Mutation::InsertBefore { id, m })
+        to.insert_before(id, m)
     }
 
-    fn create_and_insert_after(&mut self, new: &'b [VNode<'b>], after: &'b VNode<'b>) {
-        let m = self.create_children(new);
+    fn create_and_insert_after(
+        &mut self,
+        to: &mut impl WriteMutations<'b>,
+        new: &'b [VNode<'b>],
+        after: &'b VNode<'b>,
+    ) {
+        let m = self.create_children(to, new);
         let id = self.find_last_element(after);
-        self.mutations.push(Mutation::InsertAfter { id, m })
+        to.insert_after(id, m)
     }
 
     /// Simply replace a placeholder with a list of nodes
     fn replace_placeholder(
         &mut self,
+        to: &mut impl WriteMutations<'b>,
         l: &'b VPlaceholder,
         r: impl IntoIterator<Item = &'b VNode<'b>>,
     ) {
-        let m = self.create_children(r);
+        let m = self.create_children(to, r);
         let id = l.id.get().unwrap();
-        self.mutations.push(Mutation::ReplaceWith { id, m });
+        to.replace_with(id, m);
         self.reclaim(id);
     }
 
-    fn replace(&mut self, left: &'b VNode<'b>, right: impl IntoIterator<Item = &'b VNode<'b>>) {
-        let m = self.create_children(right);
-
-        let pre_edits = self.mutations.edits.len();
-
-        self.remove_node(left, true);
-
-        // We should always have a remove mutation
-        // Eventually we don't want to generate placeholders, so this might not be true. But it's true today
-        assert!(self.mutations.edits.len() > pre_edits);
+    fn replace(
+        &mut self,
+        to: &mut impl WriteMutations<'b>,
+        left: &'b VNode<'b>,
+        right: impl IntoIterator<Item = &'b VNode<'b>>,
+    ) {
+        let m = self.with_mount_parent(left.parent.get(), |dom| dom.create_children(to, right));
 
         // We want to optimize the replace case to use one less mutation if possible
-        // Since mutations are done in reverse, the last node removed will be the first in the stack
-        // Instead of *just* removing it, we can use the replace mutation
-        match self.mutations.edits.pop().unwrap() {
-            Mutation::Remove { id } => self.mutations.push(Mutation::ReplaceWith { id, m }),
-            _ => panic!("Expected remove mutation from remove_node"),
-        };
+        // Instead of *just* removing the last node, we can replace it with the new nodes
+        self.remove_node(to, left, Some(m), true);
     }
 
-    fn node_to_placeholder(&mut self, l: &'b [VNode<'b>], r: &'b VPlaceholder) {
+    fn node_to_placeholder(
+        &mut self,
+        to: &mut impl WriteMutations<'b>,
+        l: &'b [VNode<'b>],
+        r: &'b VPlaceholder,
+    ) {
         // Create the placeholder first, ensuring we get a dedicated ID for the placeholder
-        let placeholder = self.next_element(&l[0], &[]);
+        let placeholder = self.next_element(&l[0], TemplatePath::Slice(&[]));
 
         r.id.set(Some(placeholder));
 
-        self.mutations
-            .push(Mutation::CreatePlaceholder { id: placeholder });
-
-        self.remove_nodes(l);
+        to.create_placeholder(placeholder);
 
         // We want to optimize the replace case to use one less mutation if possible
-        // Since mutations are done in reverse, the last node removed will be the first in the stack
-        // Instead of *just* removing it, we can use the replace mutation
-        match self.mutations.edits.pop().unwrap() {
-            Mutation::Remove { id } => self.mutations.push(Mutation::ReplaceWith { id, m: 1 }),
-            _ => panic!("Expected remove mutation from remove_node"),
-        };
+        // Instead of *just* removing the last node, we can replace it with the placeholder
+        self.remove_nodes(to, l, Some(1));
     }
 
     /// Remove these nodes from the dom
     /// Wont generate mutations for the inner nodes
-    fn remove_nodes(&mut self, nodes: &'b [VNode<'b>]) {
-        nodes
-            .iter()
-            .rev()
-            .for_each(|node| self.remove_node(node, true));
+    ///
+    /// With `replace_with`, the last element removed is replaced with that many nodes from the stack instead. The
+    /// nodes are removed in reverse, so this is an element of the first node.
+    fn remove_nodes(
+        &mut self,
+        to: &mut impl WriteMutations<'b>,
+        nodes: &'b [VNode<'b>],
+        replace_with: Option<usize>,
+    ) {
+        for (idx, node) in nodes.iter().enumerate().rev() {
+            let replace_with = replace_with.filter(|_| idx == 0);
+            self.remove_node(to, node, replace_with, true);
+        }
     }
 
-    fn remove_node(&mut self, node: &'b VNode<'b>, gen_muts: bool) {
+    fn remove_node(
+        &mut self,
+        to: &mut impl WriteMutations<'b>,
+        node: &'b VNode<'b>,
+        replace_with: Option<usize>,
+        gen_muts: bool,
+    ) {
+        self.unmount_template_use(node);
+
+        if gen_muts {
+            self.unmount_scroll_restoration(to, node);
+        }
+
         // Clean up any attributes that have claimed a static node as dynamic for mount/unmounta
         // Will not generate mutations!
         self.reclaim_attributes(node);
@@ -826,21 +973,30 @@ impl<'b> VirtualDom {
         // Remove the nested dynamic nodes
         // We don't generate mutations for these, as they will be removed by the parent (in the next line)
         // But we still need to make sure to reclaim them from the arena and drop their hooks, etc
-        self.remove_nested_dyn_nodes(node);
+        self.remove_nested_dyn_nodes(to, node);
 
         // Clean up the roots, assuming we need to generate mutations for these
         // This is done last in order to preserve Node ID reclaim order (reclaim in reverse order of claim)
-        self.reclaim_roots(node, gen_muts);
+        self.reclaim_roots(to, node, replace_with, gen_muts);
     }
 
-    fn reclaim_roots(&mut self, node: &VNode, gen_muts: bool) {
-        for (idx, _) in node.template.get().roots.iter().enumerate() {
+    fn reclaim_roots(
+        &mut self,
+        to: &mut impl WriteMutations<'b>,
+        node: &'b VNode<'b>,
+        replace_with: Option<usize>,
+        gen_muts: bool,
+    ) {
+        let roots = node.template.get().roots.len();
+        for idx in 0..roots {
+            // Only the last root removed is replaced
+            let replace_with = replace_with.filter(|_| idx == roots - 1);
             if let Some(dy) = node.dynamic_root(idx) {
-                self.remove_dynamic_node(dy, gen_muts);
+                self.remove_dynamic_node(to, dy, replace_with, gen_muts);
             } else {
                 let id = node.root_ids.borrow()[idx];
                 if gen_muts {
-                    self.mutations.push(Mutation::Remove { id });
+                    remove_or_replace(to, id, replace_with);
                 }
                 self.reclaim(id);
             }
@@ -851,12 +1007,7 @@ impl<'b> VirtualDom {
         let mut id = None;
         for (idx, attr) in node.dynamic_attrs.iter().enumerate() {
             // We'll clean up the root nodes either way, so don't worry
-            let path_len = node
-                .template
-                .get()
-                .attr_paths
-                .get(idx)
-                .map(|path| path.len());
+            let path_len = node.template.get().attr_path(idx).map(|path| path.len());
             // if the path is 1 the attribute is in the root, so we don't need to clean it up
             // if the path is 0, the attribute is a not attached at all, so we don't need to clean it up
 
@@ -878,67 +1029,103 @@ impl<'b> VirtualDom {
         }
     }
 
-    fn remove_nested_dyn_nodes(&mut self, node: &VNode) {
+    fn remove_nested_dyn_nodes(&mut self, to: &mut impl WriteMutations<'b>, node: &'b VNode<'b>) {
         for (idx, dyn_node) in node.dynamic_nodes.iter().enumerate() {
-            let path_len = node
-                .template
-                .get()
-                .node_paths
-                .get(idx)
-                .map(|path| path.len());
+            let path_len = node.template.get().node_path(idx).map(|path| path.len());
             // Roots are cleaned up automatically above and nodes with a empty path are placeholders
             if let Some(2..) = path_len {
-                self.remove_dynamic_node(dyn_node, false)
+                self.remove_dynamic_node(to, dyn_node, None, false)
             }
         }
     }
 
-    fn remove_dynamic_node(&mut self, node: &DynamicNode, gen_muts: bool) {
+    fn remove_dynamic_node(
+        &mut self,
+        to: &mut impl WriteMutations<'b>,
+        node: &'b DynamicNode<'b>,
+        replace_with: Option<usize>,
+        gen_muts: bool,
+    ) {
         match node {
-            Component(comp) => self.remove_component_node(comp, gen_muts),
-            Text(t) => self.remove_text_node(t, gen_muts),
-            Placeholder(t) => self.remove_placeholder(t, gen_muts),
-            Fragment(nodes) => nodes
-                .iter()
-                .for_each(|node| self.remove_node(node, gen_muts)),
+            Component(comp) => self.remove_component_node(to, comp, replace_with, gen_muts),
+            Text(t) => self.remove_text_node(to, t, replace_with, gen_muts),
+            Placeholder(t) => self.remove_placeholder(to, t, replace_with, gen_muts),
+            Fragment(nodes) => {
+                for (idx, node) in nodes.iter().enumerate() {
+                    // Only the last node removed is replaced
+                    let replace_with = replace_with.filter(|_| idx == nodes.len() - 1);
+                    self.remove_node(to, node, replace_with, gen_muts);
+                }
+            }
         };
     }
 
-    fn remove_placeholder(&mut self, t: &VPlaceholder, gen_muts: bool) {
+    fn remove_placeholder(
+        &mut self,
+        to: &mut impl WriteMutations<'b>,
+        t: &VPlaceholder,
+        replace_with: Option<usize>,
+        gen_muts: bool,
+    ) {
         if let Some(id) = t.id.take() {
             if gen_muts {
-                self.mutations.push(Mutation::Remove { id });
+                remove_or_replace(to, id, replace_with);
             }
             self.reclaim(id)
         }
     }
 
-    fn remove_text_node(&mut self, t: &VText, gen_muts: bool) {
+    fn remove_text_node(
+        &mut self,
+        to: &mut impl WriteMutations<'b>,
+        t: &VText,
+        replace_with: Option<usize>,
+        gen_muts: bool,
+    ) {
         if let Some(id) = t.id.take() {
             if gen_muts {
-                self.mutations.push(Mutation::Remove { id });
+                remove_or_replace(to, id, replace_with);
             }
             self.reclaim(id)
         }
     }
 
-    fn remove_component_node(&mut self, comp: &VComponent, gen_muts: bool) {
+    fn remove_component_node(
+        &mut self,
+        to: &mut impl WriteMutations<'b>,
+        comp: &VComponent,
+        replace_with: Option<usize>,
+        gen_muts: bool,
+    ) {
         // Remove the component reference from the vcomponent so they're not tied together
         let scope = comp
             .scope
             .take()
             .expect("VComponents to always have a scope");
 
-        // Remove the component from the dom
+        // Remove the component from the dom. The root of a portal isn't under the parent being removed, so it is
+        // always removed on its own, before the placeholder in its place. The placeholder is replaced instead then.
+        let portal = self.portals.remove(&scope);
+        let root_replace_with = replace_with.filter(|_| portal.is_none());
         match unsafe {
             self.get_scope(scope)
                 .unwrap()
                 .root_node()
                 .extend_lifetime_ref()
         } {
-            RenderReturn::Ready(t) => self.remove_node(t, gen_muts),
-            RenderReturn::Aborted(placeholder) => self.remove_placeholder(placeholder, gen_muts),
+            RenderReturn::Ready(t) => {
+                self.remove_node(to, t, root_replace_with, gen_muts || portal.is_some())
+            }
+            RenderReturn::Aborted(placeholder) => {
+                self.remove_placeholder(to, placeholder, root_replace_with, gen_muts)
+            }
         };
+        if let Some(id) = portal {
+            if gen_muts {
+                remove_or_replace(to, id, replace_with);
+            }
+            self.reclaim(id);
+        }
 
         // Restore the props back to the vcomponent in case it gets rendered again
         let props = self.scopes[scope.0].props.take();
@@ -956,6 +1143,9 @@ impl<'b> VirtualDom {
             Some(Placeholder(t)) => t.id.get().unwrap(),
             Some(Component(comp)) => {
                 let scope = comp.scope.get().unwrap();
+                if let Some(id) = self.portal_placeholder(scope) {
+                    return id;
+                }
                 match unsafe {
                     self.get_scope(scope)
                         .unwrap()
@@ -977,6 +1167,9 @@ impl<'b> VirtualDom {
             Some(Placeholder(t)) => t.id.get().unwrap(),
             Some(Component(comp)) => {
                 let scope = comp.scope.get().unwrap();
+                if let Some(id) = self.portal_placeholder(scope) {
+                    return id;
+                }
                 match unsafe {
                     self.get_scope(scope)
                         .unwrap()
@@ -991,6 +1184,18 @@ impl<'b> VirtualDom {
     }
 }
 
+/// Remove an element, or replace it with the top `replace_with` nodes on the stack
+fn remove_or_replace<'b>(
+    to: &mut impl WriteMutations<'b>,
+    id: ElementId,
+    replace_with: Option<usize>,
+) {
+    match replace_with {
+        Some(m) => to.replace_with(id, m),
+        None => to.remove(id),
+    }
+}
+
 /// Are the templates the same?
 ///
 /// We need to check for the obvious case, and the non-obvious case where the template as cloned
@@ -1047,15 +1252,15 @@ fn matching_components<'a>(
 #[allow(dead_code)]
 fn is_dyn_node_only_child(node: &VNode, idx: usize) -> bool {
     let template = node.template.get();
-    let path = template.node_paths[idx];
+    let path = template.node_path(idx).unwrap();
 
     // use a loop to index every static node's children until the path has run out
     // only break if the last path index is a dynamic node
-    let mut static_node = &template.roots[path[0] as usize];
+    let mut static_node = &template.roots[path.first().unwrap() as usize];
 
-    for i in 1..path.len() - 1 {
+    for child in path.iter().take(path.len() - 1).skip(1) {
         match static_node {
-            TemplateNode::Element { children, .. } => static_node = &children[path[i] as usize],
+            TemplateNode::Element { children, .. } => static_node = &children[child as usize],
             _ => return false,
         }
     }
@@ -0,0 +1,168 @@
+use crate::{
+    innerlude::{remount_token_changed, DynamicNode, VComponent, VNode},
+    style_map::property_changes,
+    AttributeValue,
+};
+use rustc_hash::{FxHashMap, FxHashSet};
+
+/// An estimate of how much work diffing two nodes would take, from [`diff_cost`]
+///
+/// Attribute and text updates are counted exactly. Nodes that would be created or removed are counted by their roots,
+/// and components are only counted, since the cost of diffing them depends on what they render next.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct DiffCost {
+    /// The number of [`crate::Mutation::SetAttribute`]s the diff would send
+    pub set_attributes: usize,
+
+    /// The number of [`crate::Mutation::SetText`]s the diff would send
+    pub set_texts: usize,
+
+    /// The number of root nodes the diff would create
+    pub created: usize,
+
+    /// The number of root nodes the diff would remove
+    pub removed: usize,
+
+    /// The number of components that would have to be diffed to know their cost
+    pub components: usize,
+}
+
+impl DiffCost {
+    /// The estimated number of DOM operations the renderer would have to do
+    pub fn dom_operations(&self) -> usize {
+        self.set_attributes + self.set_texts + self.created + self.removed
+    }
+
+    /// Is there nothing to do at all?
+    pub fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
+}
+
+/// Estimate the cost of diffing two nodes without diffing them.
+///
+/// Unlike a real diff, this doesn't move any ids over to the new node or touch any scopes, so both nodes are left as
+/// they were. A scheduler can use this to decide whether to apply an update right away or defer it.
+///
+/// ```rust, ignore
+/// let cost = diff_cost(old, new);
+/// if cost.dom_operations() > 1000 {
+///     // put the update off until the next idle period
+/// }
+/// ```
+pub fn diff_cost<'a>(old: &VNode<'a>, new: &VNode<'a>) -> DiffCost {
+    let mut cost = DiffCost::default();
+    tally_node(old, new, &mut cost);
+    cost
+}
+
+fn tally_node<'a>(old: &VNode<'a>, new: &VNode<'a>, cost: &mut DiffCost) {
+    if std::ptr::eq(old, new) {
+        return;
+    }
+
+    let different_templates = !std::ptr::eq(old.template.get().name, new.template.get().name);
+    if different_templates || remount_token_changed(old, new) {
+        cost.removed += old.template.get().roots.len();
+        cost.created += new.template.get().roots.len();
+        return;
+    }
+
+    for (old_attr, new_attr) in old.dynamic_attrs.iter().zip(new.dynamic_attrs.iter()) {
+        match (&old_attr.value, &new_attr.value) {
+            // Swapping a listener never reaches the renderer
+            (_, AttributeValue::Listener(_)) => {}
+            (AttributeValue::StyleMap(old), AttributeValue::StyleMap(new))
+            | (AttributeValue::Spread(old), AttributeValue::Spread(new)) => {
+                cost.set_attributes += property_changes(old, new)
+            }
+            (old_value, new_value) => {
+                if old_value != new_value || old_attr.volatile {
+                    cost.set_attributes += 1;
+                }
+            }
+        }
+    }
+
+    for (old_node, new_node) in old.dynamic_nodes.iter().zip(new.dynamic_nodes.iter()) {
+        tally_dynamic_node(old_node, new_node, cost);
+    }
+}
+
+fn tally_dynamic_node<'a>(old: &DynamicNode<'a>, new: &DynamicNode<'a>, cost: &mut DiffCost) {
+    match (old, new) {
+        (DynamicNode::Text(old), DynamicNode::Text(new)) => {
+            if old.value != new.value {
+                cost.set_texts += 1;
+            }
+        }
+        (DynamicNode::Placeholder(_), DynamicNode::Placeholder(_)) => {}
+        (DynamicNode::Fragment(old), DynamicNode::Fragment(new)) => tally_children(old, new, cost),
+        (DynamicNode::Component(old), DynamicNode::Component(new)) => {
+            tally_component(old, new, cost)
+        }
+        (DynamicNode::Placeholder(_), DynamicNode::Fragment(new)) => {
+            cost.removed += 1;
+            cost.created += count_roots(new);
+        }
+        (DynamicNode::Fragment(old), DynamicNode::Placeholder(_)) => {
+            cost.removed += count_roots(old);
+            cost.created += 1;
+        }
+        _ => {
+            cost.removed += 1;
+            cost.created += 1;
+        }
+    }
+}
+
+fn tally_children<'a>(old: &[VNode<'a>], new: &[VNode<'a>], cost: &mut DiffCost) {
+    let keyed = matches!(new.first(), Some(node) if node.key.is_some());
+
+    if !keyed {
+        for (old, new) in old.iter().zip(new.iter()) {
+            tally_node(old, new, cost);
+        }
+        cost.removed += count_roots(old.get(new.len()..).unwrap_or_default());
+        cost.created += count_roots(new.get(old.len()..).unwrap_or_default());
+        return;
+    }
+
+    let old_by_key: FxHashMap<&str, &VNode<'a>> = old
+        .iter()
+        .filter_map(|node| Some((node.key?, node)))
+        .collect();
+    let new_keys: FxHashSet<&str> = new.iter().filter_map(|node| node.key).collect();
+
+    for new_node in new {
+        match new_node.key.and_then(|key| old_by_key.get(key)) {
+            Some(old_node) => tally_node(old_node, new_node, cost),
+            None => cost.created += new_node.template.get().roots.len(),
+        }
+    }
+    for old_node in old {
+        if !matches!(old_node.key, Some(key) if new_keys.contains(key)) {
+            cost.removed += old_node.template.get().roots.len();
+        }
+    }
+}
+
+fn tally_component<'a>(old: &VComponent<'a>, new: &VComponent<'a>, cost: &mut DiffCost) {
+    if std::ptr::eq(old, new) {
+        return;
+    }
+
+    if old.render_fn != new.render_fn {
+        cost.removed += 1;
+        cost.created += 1;
+    } else {
+        cost.components += 1;
+    }
+}
+
+fn count_roots(nodes: &[VNode]) -> usize {
+    nodes
+        .iter()
+        .map(|node| node.template.get().roots.len())
+        .sum()
+}
@@ -1,6 +1,6 @@
-use std::hash::Hash;
+use std::{collections::BTreeSet, hash::Hash};
 
-use crate::ScopeId;
+use crate::{innerlude::Priority, ScopeId};
 
 #[derive(Debug, Clone, Eq, PartialOrd, Ord)]
 pub struct DirtyScope {
@@ -19,3 +19,48 @@ impl Hash for DirtyScope {
         self.id.hash(state);
     }
 }
+
+/// The scopes waiting to be rendered, with one lane for each [`Priority`]
+///
+/// Each lane is ordered by height so parents render before their children. A scope is only ever in one lane, the most
+/// urgent one it was scheduled in.
+#[derive(Default)]
+pub(crate) struct DirtyScopes {
+    lanes: [BTreeSet<DirtyScope>; Priority::LANES],
+}
+
+impl DirtyScopes {
+    pub fn insert(&mut self, scope: DirtyScope, priority: Priority) {
+        let lane = priority as usize;
+        if self.lanes[..lane]
+            .iter()
+            .any(|more_urgent| more_urgent.contains(&scope))
+        {
+            return;
+        }
+
+        for less_urgent in &mut self.lanes[lane + 1..] {
+            less_urgent.remove(&scope);
+        }
+        self.lanes[lane].insert(scope);
+    }
+
+    pub fn remove(&mut self, scope: &DirtyScope) {
+        for lane in &mut self.lanes {
+            lane.remove(scope);
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.lanes.iter().all(BTreeSet::is_empty)
+    }
+
+    /// Take the highest scope out of the most urgent lane that has any work
+    pub fn pop(&mut self) -> Option<DirtyScope> {
+        self.lanes.iter_mut().find_map(|lane| {
+            let scope = lane.iter().next().cloned()?;
+            lane.remove(&scope);
+            Some(scope)
+        })
+    }
+}
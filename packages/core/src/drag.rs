@@ -0,0 +1,128 @@
+use std::{
+    any::Any,
+    fmt::{Debug, Formatter},
+    rc::Rc,
+};
+
+use bumpalo::{boxed::Box as BumpBox, Bump};
+
+use crate::{
+    innerlude::{AnyValue, AttributeValue, IntoAttributeValue},
+    runtime::with_runtime,
+    Attribute, ElementId, VirtualDom,
+};
+
+/// The name of the attribute that attaches a [`DragPayload`] to an element.
+///
+/// The payload is never sent to the renderer. Instead, the renderer is told to listen for `dragstart` and `dragend` on
+/// the element. When the element fires `dragstart`, the VirtualDom holds on to its payload until the drag ends, and
+/// every listener in between can read it with [`drag_payload`].
+///
+/// ```rust, ignore
+/// rsx! {
+///     div { draggable: "true", "drag_payload": DragPayload::new(card.id), "{card.title}" }
+///     div {
+///         ondrop: move |_| {
+///             if let Some(id) = drag_payload().and_then(|payload| payload.downcast_ref::<CardId>().copied()) {
+///                 move_card(id);
+///             }
+///         }
+///     }
+/// }
+/// ```
+pub const DRAG_PAYLOAD: &str = "drag_payload";
+
+/// The events the renderer has to send for an element with a [`DragPayload`], even if the element doesn't listen for
+/// them itself
+pub(crate) const DRAG_EVENTS: [&str; 2] = ["dragstart", "dragend"];
+
+/// Data that travels with an element while it is being dragged
+#[derive(Clone)]
+pub struct DragPayload(Rc<dyn Any>);
+
+impl DragPayload {
+    /// Create a payload from any value
+    pub fn new<T: 'static>(value: T) -> Self {
+        Self(Rc::new(value))
+    }
+
+    /// Get the value of the payload, if it is of type `T`
+    pub fn downcast_ref<T: 'static>(&self) -> Option<&T> {
+        self.0.downcast_ref()
+    }
+
+    /// Check if the payload is of type `T`
+    pub fn is<T: 'static>(&self) -> bool {
+        self.0.is::<T>()
+    }
+}
+
+impl PartialEq for DragPayload {
+    fn eq(&self, other: &Self) -> bool {
+        Rc::ptr_eq(&self.0, &other.0)
+    }
+}
+
+impl Debug for DragPayload {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_tuple("DragPayload").field(&"...").finish()
+    }
+}
+
+impl<'a> IntoAttributeValue<'a> for DragPayload {
+    fn into_value(self, bump: &'a Bump) -> AttributeValue<'a> {
+        // safety: there's no other way to create a dynamicly-dispatched bump box other than alloc + from-raw
+        let boxed: BumpBox<'a, dyn AnyValue> = unsafe { BumpBox::from_raw(bump.alloc(self)) };
+        AttributeValue::Any(std::cell::RefCell::new(Some(boxed)))
+    }
+}
+
+impl Attribute<'_> {
+    pub(crate) fn is_drag_payload(&self) -> bool {
+        self.name == DRAG_PAYLOAD
+    }
+}
+
+/// Get the payload of the element that is currently being dragged, if it has one
+pub fn drag_payload() -> Option<DragPayload> {
+    with_runtime(|rt| rt.drag_payload.borrow().clone()).flatten()
+}
+
+impl VirtualDom {
+    /// Keep track of the payload being dragged around as drag events come in
+    pub(crate) fn track_drag(&mut self, name: &str, element: ElementId) {
+        if name == "dragstart" {
+            let payload = self.find_drag_payload(element);
+            *self.runtime.drag_payload.borrow_mut() = payload;
+        }
+    }
+
+    /// Forget the payload once the drag is over and every listener has seen it
+    pub(crate) fn finish_drag(&mut self, name: &str) {
+        if name == "dragend" {
+            self.runtime.drag_payload.borrow_mut().take();
+        }
+    }
+
+    fn find_drag_payload(&self, element: ElementId) -> Option<DragPayload> {
+        let el_ref = self.elements.get(element.0)?;
+
+        // safety: we maintain references of all vnodes in the element slab
+        let node = unsafe { el_ref.template?.as_ref() };
+        let template = node.template.get();
+
+        node.dynamic_attrs
+            .iter()
+            .zip(template.attr_path_iter())
+            .filter(|(attr, path)| attr.is_drag_payload() && el_ref.path == *path)
+            .find_map(|(attr, _)| match &attr.value {
+                AttributeValue::Any(value) => value
+                    .borrow()
+                    .as_ref()?
+                    .as_any()
+                    .downcast_ref::<DragPayload>()
+                    .cloned(),
+                _ => None,
+            })
+    }
+}
@@ -0,0 +1,68 @@
+use crate::{runtime::RuntimeGuard, scope_context::ScopeContext, ScopeId, ScopeState, VirtualDom};
+
+/// A closure that runs after the mutations of the render that queued it are applied
+pub(crate) struct Effect {
+    pub(crate) scope: ScopeId,
+    pub(crate) run: Box<dyn FnOnce()>,
+}
+
+impl ScopeContext {
+    /// Queue a closure to run once the mutations of the current render are applied
+    pub fn queue_effect(&self, effect: impl FnOnce() + 'static) {
+        self.tasks.effects.borrow_mut().push(Effect {
+            scope: self.id,
+            run: Box::new(effect),
+        });
+    }
+}
+
+impl ScopeState {
+    /// Queue a closure to run once the mutations of the current render are applied.
+    ///
+    /// Effects run the next time [`VirtualDom::wait_for_work`] is called, or before the next render if that comes
+    /// first. If this scope is unmounted before then, the effect is dropped without running.
+    ///
+    /// ```rust, ignore
+    /// fn app(cx: Scope) -> Element {
+    ///     cx.queue_effect(|| log::info!("the title is on the screen"));
+    ///     render! { h1 { "title" } }
+    /// }
+    /// ```
+    pub fn queue_effect(&self, effect: impl FnOnce() + 'static) {
+        self.context().queue_effect(effect)
+    }
+}
+
+impl VirtualDom {
+    /// Run every effect queued since the last time effects were run.
+    ///
+    /// This is called by [`VirtualDom::wait_for_work`] and before every render, so renderers only need to call it if
+    /// they want effects to run as soon as they have applied the mutations.
+    pub fn run_effects(&mut self) {
+        let effects = std::mem::take(&mut *self.runtime.scheduler.effects.borrow_mut());
+        if effects.is_empty() {
+            return;
+        }
+
+        let _runtime = RuntimeGuard::new(self.runtime.clone());
+        for effect in effects {
+            self.runtime.scope_stack.borrow_mut().push(effect.scope);
+            self.runtime.rendering.set(false);
+            (effect.run)();
+            self.runtime.scope_stack.borrow_mut().pop();
+            self.runtime.rendering.set(true);
+        }
+    }
+
+    /// Forget the effects of a scope that is being dropped
+    pub(crate) fn drop_effects(&mut self, id: ScopeId) {
+        // The closures are dropped after the queue is released, in case anything they own touches it
+        let dropped: Vec<Effect> = {
+            let mut effects = self.runtime.scheduler.effects.borrow_mut();
+            let (dropped, kept) = effects.drain(..).partition(|effect| effect.scope == id);
+            *effects = kept;
+            dropped
+        };
+        drop(dropped);
+    }
+}
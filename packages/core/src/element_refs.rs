@@ -0,0 +1,120 @@
+use rustc_hash::{FxHashMap, FxHashSet};
+
+use crate::{
+    innerlude::{BorrowedAttributeValue, VNode},
+    Attribute, ElementId, VirtualDom, WriteMutations,
+};
+
+/// Attributes that reference another element by key, and the keyed elements they can point to
+#[derive(Default)]
+pub(crate) struct ElementRefs {
+    // The first element of every mounted node with a key
+    keyed: FxHashMap<String, ElementId>,
+    keys: FxHashMap<ElementId, String>,
+
+    // The referencing attributes on each element
+    refs: FxHashMap<ElementId, FxHashMap<&'static str, ElementRef>>,
+
+    // Attribute names are kept around once they are used, so each name is only interned once
+    names: FxHashSet<&'static str>,
+}
+
+struct ElementRef {
+    key: String,
+    namespace: Option<&'static str>,
+    // The id the renderer last received for the attribute
+    sent: Option<ElementId>,
+}
+
+impl VirtualDom {
+    /// Make a keyed node available to the attributes that reference it
+    pub(crate) fn mount_element_key(&mut self, node: &VNode) {
+        let key = match node.key {
+            Some(key) => key,
+            None => return,
+        };
+
+        if let Some(id) = node.root_ids.borrow().iter().find(|id| id.0 != 0) {
+            let refs = &mut self.element_refs;
+            refs.keyed.insert(key.to_string(), *id);
+            if let Some(old_key) = refs.keys.insert(*id, key.to_string()) {
+                // The element was moved over from a node with a different key
+                if old_key != key && refs.keyed.get(&old_key) == Some(id) {
+                    refs.keyed.remove(&old_key);
+                }
+            }
+        }
+    }
+
+    /// Point an attribute at the element with a key. The attribute is sent once the batch of mutations is done.
+    pub(crate) fn track_element_ref(&mut self, attribute: &Attribute, key: &str, id: ElementId) {
+        let refs = &mut self.element_refs;
+        let name = match refs.names.get(attribute.name) {
+            Some(name) => *name,
+            None => {
+                // Attribute names come from templates, so there is only a small, fixed set of them
+                let name: &'static str = Box::leak(attribute.name.to_string().into_boxed_str());
+                refs.names.insert(name);
+                name
+            }
+        };
+
+        refs.refs.entry(id).or_default().insert(
+            name,
+            ElementRef {
+                key: key.to_string(),
+                namespace: attribute.namespace,
+                sent: None,
+            },
+        );
+    }
+
+    /// Stop resolving an attribute that now has a regular value
+    pub(crate) fn forget_element_ref(&mut self, attribute: &Attribute, id: ElementId) {
+        if let Some(refs) = self.element_refs.refs.get_mut(&id) {
+            refs.remove(attribute.name);
+        }
+    }
+
+    /// Forget the references on an element that was removed, and the key of the node it belonged to
+    pub(crate) fn forget_element_refs(&mut self, id: ElementId) {
+        let refs = &mut self.element_refs;
+        refs.refs.remove(&id);
+
+        if let Some(key) = refs.keys.remove(&id) {
+            // A node with the same key may have been mounted before this one was removed
+            if refs.keyed.get(&key) == Some(&id) {
+                refs.keyed.remove(&key);
+            }
+        }
+    }
+
+    /// Send every reference that resolves to a different element than last time
+    ///
+    /// This waits until the end of the batch so forward references can see elements created after them.
+    pub(crate) fn flush_element_refs<'a>(&mut self, to: &mut impl WriteMutations<'a>) {
+        let refs = &mut self.element_refs;
+        let mut elements: Vec<_> = refs.refs.keys().copied().collect();
+        elements.sort_unstable();
+
+        for id in elements {
+            let mut names: Vec<_> = refs.refs[&id].keys().copied().collect();
+            names.sort_unstable();
+
+            for name in names {
+                let element_ref = refs.refs.get_mut(&id).unwrap().get_mut(name).unwrap();
+                let target = refs.keyed.get(&element_ref.key).copied();
+                if target == element_ref.sent {
+                    continue;
+                }
+
+                element_ref.sent = target;
+                let value = match target {
+                    Some(target) => BorrowedAttributeValue::Int(target.0 as i64),
+                    None => BorrowedAttributeValue::None,
+                };
+                to.set_attribute(name, element_ref.namespace, &value, id);
+            }
+        }
+    }
+}
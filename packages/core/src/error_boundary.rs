@@ -1,14 +1,22 @@
-use crate::{ScopeId, ScopeState};
+use crate::{
+    innerlude::{LazyNodes, Properties, Scope, VNode},
+    Element, ScopeId, ScopeState,
+};
 use std::{
     any::{Any, TypeId},
     cell::RefCell,
     fmt::Debug,
+    rc::Rc,
+    sync::Arc,
 };
 
 /// A boundary that will capture any errors from child components
 pub struct ErrorBoundary {
-    error: RefCell<Option<CapturedError>>,
-    _id: ScopeId,
+    error: RefCell<Option<Rc<CapturedError>>>,
+    id: ScopeId,
+
+    // Renders the boundary again when it captures an error. The boundary of the VirtualDom itself has nothing to render.
+    rerender: Option<Arc<dyn Fn(ScopeId) + Send + Sync>>,
 }
 
 /// An instance of an error captured by a descendant component.
@@ -33,16 +41,131 @@ impl CapturedError {
 }
 
 impl ErrorBoundary {
+    /// Create a boundary for the scope that only records the errors thrown into it
     pub fn new(id: ScopeId) -> Self {
         Self {
             error: RefCell::new(None),
-            _id: id,
+            id,
+            rerender: None,
         }
     }
 
     /// Push an error into this Error Boundary
     pub fn insert_error(&self, scope: ScopeId, error: Box<dyn Debug + 'static>) {
-        self.error.replace(Some(CapturedError { error, scope }));
+        let previous = self
+            .error
+            .replace(Some(Rc::new(CapturedError { error, scope })));
+
+        if let (None, Some(rerender)) = (previous, &self.rerender) {
+            rerender(self.id);
+        }
+    }
+
+    /// Get the error the boundary captured, if any
+    pub fn error(&self) -> Option<Rc<CapturedError>> {
+        self.error.borrow().clone()
+    }
+
+    /// Clear the captured error and render the children of the boundary again
+    ///
+    /// The children are mounted from scratch, so a component that failed because of its state gets a fresh start.
+    pub fn reset(&self) {
+        if self.error.take().is_some() {
+            if let Some(rerender) = &self.rerender {
+                rerender(self.id);
+            }
+        }
+    }
+}
+
+/// Render a fallback instead of the children if any component below the boundary throws an error or panics
+///
+/// The fallback gets the captured error and the boundary, which can [reset](ErrorBoundary::reset) to try rendering the
+/// children again. Components outside of any boundary throw into the boundary of the VirtualDom, which only records the
+/// error.
+///
+/// ```rust, ignore
+/// rsx! {
+///     ErrorBoundary {
+///         fallback: |error: Rc<CapturedError>, boundary: Rc<ErrorBoundary>| rsx! {
+///             "Failed to load: {error.error:?}"
+///             button { onclick: move |_| boundary.reset(), "Retry" }
+///         },
+///         UserProfile { id: 1 }
+///     }
+/// }
+/// ```
+#[allow(non_snake_case)]
+pub fn ErrorBoundary<'a>(cx: Scope<'a, ErrorBoundaryProps<'a>>) -> Element<'a> {
+    let boundary = cx.use_hook(|| {
+        cx.provide_context(Rc::new(ErrorBoundary {
+            rerender: Some(cx.schedule_update_any()),
+            ..ErrorBoundary::new(cx.scope_id())
+        }))
+    });
+
+    match (boundary.error(), &cx.props.fallback) {
+        (Some(error), Some(fallback)) => cx.render(fallback(error, boundary.clone())),
+        (Some(_), None) => None,
+        (None, _) => {
+            let children = cx.props.children.as_ref()?;
+            Some(VNode {
+                key: children.key,
+                parent: children.parent.clone(),
+                template: children.template.clone(),
+                root_ids: children.root_ids.clone(),
+                dynamic_nodes: children.dynamic_nodes,
+                dynamic_attrs: children.dynamic_attrs,
+            })
+        }
+    }
+}
+
+type ErrorFallback<'a> =
+    Box<dyn Fn(Rc<CapturedError>, Rc<ErrorBoundary>) -> LazyNodes<'a, 'a> + 'a>;
+
+/// The props of an [`ErrorBoundary`]
+pub struct ErrorBoundaryProps<'a> {
+    fallback: Option<ErrorFallback<'a>>,
+    children: Element<'a>,
+}
+
+/// The builder of [`ErrorBoundaryProps`]
+pub struct ErrorBoundaryBuilder<'a>(ErrorBoundaryProps<'a>);
+
+impl<'a> ErrorBoundaryBuilder<'a> {
+    /// Set what is rendered instead of the children once an error is captured
+    pub fn fallback(
+        mut self,
+        fallback: impl Fn(Rc<CapturedError>, Rc<ErrorBoundary>) -> LazyNodes<'a, 'a> + 'a,
+    ) -> Self {
+        self.0.fallback = Some(Box::new(fallback));
+        self
+    }
+
+    /// Set the children of the boundary
+    pub fn children(mut self, children: Element<'a>) -> Self {
+        self.0.children = children;
+        self
+    }
+
+    /// Finish building the props
+    pub fn build(self) -> ErrorBoundaryProps<'a> {
+        self.0
+    }
+}
+
+impl<'a> Properties for ErrorBoundaryProps<'a> {
+    type Builder = ErrorBoundaryBuilder<'a>;
+    const IS_STATIC: bool = false;
+    fn builder() -> Self::Builder {
+        ErrorBoundaryBuilder(ErrorBoundaryProps {
+            fallback: None,
+            children: None,
+        })
+    }
+    unsafe fn memoize(&self, _other: &Self) -> bool {
+        false
     }
 }
 
@@ -25,6 +25,7 @@ pub struct Event<T: 'static + ?Sized> {
     /// The data associated with this event
     pub data: Rc<T>,
     pub(crate) propagates: Rc<Cell<bool>>,
+    pub(crate) default_prevented: Rc<Cell<bool>>,
 }
 
 impl<T> Event<T> {
@@ -63,6 +64,36 @@ impl<T> Event<T> {
         self.propagates.set(false);
     }
 
+    /// Prevent the renderer from running the default action of this event, like following a link or submitting a form.
+    ///
+    /// Unlike the `prevent_default` attribute, this can be decided while the event is handled. Only renderers with
+    /// [`crate::Capabilities::runtime_prevent_default`] respect it, because they wait for the listeners to run before
+    /// finishing the event. The web renderer does. The desktop and liveview renderers send events to the VirtualDom
+    /// asynchronously, so they only respect the attribute, and calling this logs a warning.
+    ///
+    /// # Example
+    ///
+    /// ```rust, ignore
+    /// rsx! {
+    ///     a {
+    ///         href: "/settings",
+    ///         onclick: move |evt: Event<MouseData>| {
+    ///             if unsaved_changes {
+    ///                 evt.prevent_default();
+    ///             }
+    ///         }
+    ///     }
+    /// }
+    /// ```
+    pub fn prevent_default(&self) {
+        self.default_prevented.set(true);
+    }
+
+    /// Check if a listener has called [`Event::prevent_default`] on this event
+    pub fn default_prevented(&self) -> bool {
+        self.default_prevented.get()
+    }
+
     /// Get a reference to the inner data from this event
     ///
     /// ```rust, ignore
@@ -82,10 +113,23 @@ impl<T> Event<T> {
     }
 }
 
+/// Split the name of a listener attribute into the event it listens to, and if it listens in the capture phase.
+///
+/// `onclick` is called as a click bubbles up from the target, and `oncaptureclick` is called as the click is captured
+/// down to the target, before any `onclick` listener.
+pub(crate) fn listener_event(attribute: &str) -> (&str, bool) {
+    let name = attribute.strip_prefix("on").unwrap_or(attribute);
+    match name.strip_prefix("capture") {
+        Some(event) => (event, true),
+        None => (name, false),
+    }
+}
+
 impl<T: ?Sized> Clone for Event<T> {
     fn clone(&self) -> Self {
         Self {
             propagates: self.propagates.clone(),
+            default_prevented: self.default_prevented.clone(),
             data: self.data.clone(),
         }
     }
@@ -102,6 +146,7 @@ impl<T: std::fmt::Debug> std::fmt::Debug for Event<T> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         f.debug_struct("UiEvent")
             .field("bubble_state", &self.propagates)
+            .field("default_prevented", &self.default_prevented)
             .field("data", &self.data)
             .finish()
     }
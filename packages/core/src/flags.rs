@@ -0,0 +1,136 @@
+use rustc_hash::FxHashMap;
+
+use crate::{innerlude::VNode, Template, TemplateAttribute, TemplateNode, VirtualDom};
+
+type FlagProvider = Box<dyn Fn(&str) -> bool>;
+
+#[derive(Default)]
+pub(crate) struct FlagState {
+    provider: Option<FlagProvider>,
+
+    // The templates with conditional attributes we have seen, along with what they were resolved to
+    resolved: FxHashMap<&'static str, (Template<'static>, Template<'static>)>,
+}
+
+impl VirtualDom {
+    /// Decide which [`TemplateAttribute::Conditional`] attributes are applied.
+    ///
+    /// The provider is called with the flag of each conditional attribute the first time a template is mounted, and
+    /// the attribute is only applied if it returns true. Without a provider, every flag is off.
+    ///
+    /// ```rust, ignore
+    /// let dom = VirtualDom::new(app).with_flag_provider(|flag| experiments.is_enabled(flag));
+    /// ```
+    pub fn with_flag_provider(mut self, provider: impl Fn(&str) -> bool + 'static) -> Self {
+        self.flags.provider = Some(Box::new(provider));
+        self
+    }
+
+    /// Swap the template of a node for one with its conditional attributes resolved
+    pub(crate) fn resolve_conditional_attributes(&mut self, node: &VNode) {
+        let template = node.template.get();
+
+        if let Some((original, resolved)) = self.flags.resolved.get(template.name) {
+            // Hot reloading can swap out the template under the same name
+            if *original == template {
+                node.template.set(*resolved);
+                return;
+            }
+        }
+
+        if !template.roots.iter().any(has_conditional_attributes) {
+            return;
+        }
+
+        let flags = &self.flags;
+        let enabled = |flag: &str| match &flags.provider {
+            Some(provider) => provider(flag),
+            None => false,
+        };
+
+        // Templates live for the rest of the program, so the resolved copy does too
+        let resolved = Template {
+            roots: Box::leak(
+                template
+                    .roots
+                    .iter()
+                    .map(|root| resolve_node(root, &enabled))
+                    .collect(),
+            ),
+            ..template
+        };
+
+        self.flags
+            .resolved
+            .insert(template.name, (template, resolved));
+        node.template.set(resolved);
+    }
+}
+
+fn has_conditional_attributes(node: &TemplateNode) -> bool {
+    match node {
+        TemplateNode::Element {
+            attrs, children, ..
+        } => {
+            attrs
+                .iter()
+                .any(|attr| matches!(attr, TemplateAttribute::Conditional { .. }))
+                || children.iter().any(has_conditional_attributes)
+        }
+        _ => false,
+    }
+}
+
+fn resolve_node(
+    node: &TemplateNode<'static>,
+    enabled: &impl Fn(&str) -> bool,
+) -> TemplateNode<'static> {
+    match *node {
+        TemplateNode::Element {
+            tag,
+            namespace,
+            attrs,
+            children,
+        } => TemplateNode::Element {
+            tag,
+            namespace,
+            attrs: Box::leak(
+                attrs
+                    .iter()
+                    .filter_map(|attr| match *attr {
+                        TemplateAttribute::Conditional {
+                            name,
+                            value,
+                            namespace,
+                            flag,
+                        } if enabled(flag) => Some(TemplateAttribute::Static {
+                            name,
+                            value,
+                            namespace,
+                        }),
+                        TemplateAttribute::Conditional { .. } => None,
+                        TemplateAttribute::Static {
+                            name,
+                            value,
+                            namespace,
+                        } => Some(TemplateAttribute::Static {
+                            name,
+                            value,
+                            namespace,
+                        }),
+                        TemplateAttribute::Dynamic { id } => {
+                            Some(TemplateAttribute::Dynamic { id })
+                        }
+                    })
+                    .collect(),
+            ),
+            children: Box::leak(
+                children
+                    .iter()
+                    .map(|child| resolve_node(child, enabled))
+                    .collect(),
+            ),
+        },
+        node => node,
+    }
+}
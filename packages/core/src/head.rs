@@ -0,0 +1,382 @@
+//! Elements that components hoist into the `<head>` of the document, like the title of the page
+
+use std::{
+    cell::{Cell, RefCell},
+    rc::Rc,
+};
+
+use crate::{
+    innerlude::{DynamicNode, PackedPath, Properties, Scope, TemplateNode, VNode},
+    Element, ScopeState, Template, VirtualDom,
+};
+
+/// The attribute renderers mark the head elements they create with, so they can find and replace them later on
+///
+/// The title is the exception, since it is set on the document instead of with an element.
+pub const HEAD_ATTRIBUTE: &str = "data-dioxus-head";
+
+/// An element in the head of the document, like a `title`, `meta` or `link` tag
+///
+/// Components declare head elements with [`ScopeState::use_head`] or the [`Title`], [`Meta`] and [`Link`] components.
+/// Renderers read them with [`VirtualDom::head`]: the web and desktop renderers keep the head of the page in sync, and
+/// SSR writes them into the head section of the page.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HeadElement {
+    /// The tag of the element, like `title` or `meta`
+    pub tag: &'static str,
+
+    /// The attributes of the element, in the order they are written
+    pub attributes: Vec<(&'static str, String)>,
+
+    /// The text inside the element, for elements like `title`
+    pub text: Option<String>,
+}
+
+impl HeadElement {
+    /// Create an element without any attributes
+    pub fn new(tag: &'static str) -> Self {
+        Self {
+            tag,
+            attributes: Vec::new(),
+            text: None,
+        }
+    }
+
+    /// Add an attribute to the element
+    pub fn with_attribute(mut self, name: &'static str, value: impl Into<String>) -> Self {
+        self.attributes.push((name, value.into()));
+        self
+    }
+
+    /// Set the text inside the element
+    pub fn with_text(mut self, text: impl Into<String>) -> Self {
+        self.text = Some(text.into());
+        self
+    }
+
+    /// Get the value of an attribute of the element
+    pub fn attribute(&self, name: &str) -> Option<&str> {
+        self.attributes
+            .iter()
+            .find(|(attribute, _)| *attribute == name)
+            .map(|(_, value)| value.as_str())
+    }
+
+    /// The key the element is deduplicated by. When several mounted components declare elements with the same key,
+    /// only the one declared last is in the head.
+    ///
+    /// - There is only ever one `title`
+    /// - A `meta` is keyed by its `charset`, `name`, `property` or `http-equiv`
+    /// - A `link` is keyed by its `rel` and `href`
+    ///
+    /// Other elements have no key and are never deduplicated.
+    pub fn key(&self) -> Option<String> {
+        match self.tag {
+            "title" => Some("title".to_string()),
+            "meta" if self.attribute("charset").is_some() => Some("meta charset".to_string()),
+            "meta" => ["name", "property", "http-equiv"].iter().find_map(|name| {
+                self.attribute(name)
+                    .map(|value| format!("meta {}={}", name, value))
+            }),
+            "link" => Some(format!(
+                "link {} {}",
+                self.attribute("rel").unwrap_or_default(),
+                self.attribute("href").unwrap_or_default()
+            )),
+            _ => None,
+        }
+    }
+}
+
+/// Every head element declared by a mounted component, in the order they were declared
+#[derive(Default)]
+pub(crate) struct Head {
+    elements: RefCell<Vec<(usize, HeadElement)>>,
+    next_id: Cell<usize>,
+    changed: Cell<bool>,
+}
+
+impl Head {
+    fn insert(&self, element: HeadElement) -> usize {
+        let id = self.next_id.get();
+        self.next_id.set(id + 1);
+        self.elements.borrow_mut().push((id, element));
+        self.changed.set(true);
+        id
+    }
+
+    fn update(&self, id: usize, element: HeadElement) {
+        let mut elements = self.elements.borrow_mut();
+        if let Some((_, old)) = elements.iter_mut().find(|(other, _)| *other == id) {
+            if *old != element {
+                *old = element;
+                self.changed.set(true);
+            }
+        }
+    }
+
+    fn remove(&self, id: usize) {
+        self.elements.borrow_mut().retain(|(other, _)| *other != id);
+        self.changed.set(true);
+    }
+
+    /// The elements that are in the head, with every key only kept for the element declared last
+    fn resolve(&self) -> Vec<HeadElement> {
+        let elements = self.elements.borrow();
+        let mut resolved = Vec::new();
+        for (idx, (_, element)) in elements.iter().enumerate() {
+            let key = element.key();
+            let replaced = key.is_some()
+                && elements[idx + 1..]
+                    .iter()
+                    .any(|(_, later)| later.key() == key);
+            if !replaced {
+                resolved.push(element.clone());
+            }
+        }
+        resolved
+    }
+}
+
+/// Removes the element of a component from the head when the component is dropped
+struct HeadEntry {
+    head: Rc<Head>,
+    id: usize,
+}
+
+impl Drop for HeadEntry {
+    fn drop(&mut self) {
+        self.head.remove(self.id);
+    }
+}
+
+impl ScopeState {
+    /// Hoist an element into the head of the document for as long as this component is mounted
+    ///
+    /// The element is updated every time the component renders with a different element. Elements with the same
+    /// [`HeadElement::key`] are deduplicated, so a component deeper in the tree can replace the title set by its
+    /// parent.
+    ///
+    /// ```rust, ignore
+    /// fn Article(cx: Scope<ArticleProps>) -> Element {
+    ///     cx.use_head(HeadElement::new("meta").with_attribute("name", "author").with_attribute("content", cx.props.author));
+    ///     render! { article { "{cx.props.body}" } }
+    /// }
+    /// ```
+    pub fn use_head(&self, element: HeadElement) {
+        let mut element = Some(element);
+        let entry = self.use_hook(|| {
+            let head = self.runtime.head.clone();
+            let id = head.insert(element.take().unwrap());
+            HeadEntry { head, id }
+        });
+        if let Some(element) = element {
+            entry.head.update(entry.id, element);
+        }
+    }
+}
+
+impl VirtualDom {
+    /// The elements mounted components hoisted into the head of the document, deduplicated by [`HeadElement::key`]
+    pub fn head(&self) -> Vec<HeadElement> {
+        self.runtime.head.resolve()
+    }
+
+    /// Get the elements of the head if they changed since the last time this was called
+    ///
+    /// Renderers that keep the head of the page in sync call this after they apply each batch of mutations.
+    pub fn take_head_changes(&mut self) -> Option<Vec<HeadElement>> {
+        self.runtime
+            .head
+            .changed
+            .replace(false)
+            .then(|| self.head())
+    }
+}
+
+static HEAD_TEMPLATE: Template<'static> = Template {
+    name: concat!(file!(), ":", line!(), ":", column!(), ":0"),
+    roots: &[TemplateNode::Dynamic { id: 0 }],
+    node_paths: &[PackedPath::new(&[0])],
+    ..Template::EMPTY
+};
+
+/// Declare an element in the head, and render a placeholder in its place in the body
+fn head_element<'a, P>(cx: Scope<'a, P>, element: HeadElement) -> Element<'a> {
+    cx.use_head(element);
+    Some(VNode {
+        key: None,
+        parent: Default::default(),
+        template: Cell::new(HEAD_TEMPLATE),
+        root_ids: RefCell::new(bumpalo::collections::Vec::new_in(cx.bump())),
+        dynamic_nodes: cx
+            .bump()
+            .alloc([DynamicNode::Placeholder(Default::default())]),
+        dynamic_attrs: &[],
+    })
+}
+
+/// Set the title of the page while this component is mounted
+///
+/// The title is the text of the children. When several titles are mounted, the one mounted last is shown.
+///
+/// ```rust, ignore
+/// rsx! {
+///     Title { "{user.name} - Profile" }
+/// }
+/// ```
+#[allow(non_snake_case)]
+pub fn Title<'a>(cx: Scope<'a, TitleProps<'a>>) -> Element<'a> {
+    let mut title = String::new();
+    if let Some(children) = &cx.props.children {
+        write_text(children, &mut title);
+    }
+    head_element(cx, HeadElement::new("title").with_text(title))
+}
+
+/// Collect the text in the roots of a node
+fn write_text(node: &VNode, buf: &mut String) {
+    for root in node.template.get().roots {
+        match root {
+            TemplateNode::Text { text } => buf.push_str(text),
+            TemplateNode::Dynamic { id } | TemplateNode::DynamicText { id } => {
+                match &node.dynamic_nodes[*id] {
+                    DynamicNode::Text(text) => buf.push_str(text.value),
+                    DynamicNode::Fragment(nodes) => {
+                        for node in *nodes {
+                            write_text(node, buf);
+                        }
+                    }
+                    _ => {}
+                }
+            }
+            TemplateNode::Element { .. } => {}
+        }
+    }
+}
+
+/// The props of a [`Title`]
+pub struct TitleProps<'a> {
+    children: Element<'a>,
+}
+
+/// The builder of [`TitleProps`]
+pub struct TitleBuilder<'a>(TitleProps<'a>);
+
+impl<'a> TitleBuilder<'a> {
+    /// Set the text of the title
+    pub fn children(mut self, children: Element<'a>) -> Self {
+        self.0.children = children;
+        self
+    }
+
+    /// Finish building the props
+    pub fn build(self) -> TitleProps<'a> {
+        self.0
+    }
+}
+
+impl<'a> Properties for TitleProps<'a> {
+    type Builder = TitleBuilder<'a>;
+    const IS_STATIC: bool = false;
+    fn builder() -> Self::Builder {
+        TitleBuilder(TitleProps { children: None })
+    }
+    unsafe fn memoize(&self, _other: &Self) -> bool {
+        false
+    }
+}
+
+/// Create an element that only has attributes
+fn attributes_element(tag: &'static str, attributes: &[(&'static str, &str)]) -> HeadElement {
+    HeadElement {
+        tag,
+        attributes: attributes
+            .iter()
+            .map(|(name, value)| (*name, value.to_string()))
+            .collect(),
+        text: None,
+    }
+}
+
+macro_rules! attribute_props {
+    ($component:literal, $props:ident, $builder:ident { $($method:ident => $name:literal,)* }) => {
+        #[doc = concat!("The props of a [`", $component, "`]")]
+        pub struct $props<'a> {
+            attributes: Vec<(&'static str, &'a str)>,
+        }
+
+        #[doc = concat!("The builder of [`", stringify!($props), "`]")]
+        pub struct $builder<'a>($props<'a>);
+
+        impl<'a> $builder<'a> {
+            $(
+                #[doc = concat!("Set the `", $name, "` attribute")]
+                pub fn $method(mut self, value: &'a str) -> Self {
+                    self.0.attributes.push(($name, value));
+                    self
+                }
+            )*
+
+            /// Finish building the props
+            pub fn build(self) -> $props<'a> {
+                self.0
+            }
+        }
+
+        impl<'a> Properties for $props<'a> {
+            type Builder = $builder<'a>;
+            const IS_STATIC: bool = false;
+            fn builder() -> Self::Builder {
+                $builder($props {
+                    attributes: Vec::new(),
+                })
+            }
+            unsafe fn memoize(&self, _other: &Self) -> bool {
+                false
+            }
+        }
+    };
+}
+
+attribute_props!("Meta", MetaProps, MetaBuilder {
+    name => "name",
+    content => "content",
+    property => "property",
+    charset => "charset",
+    http_equiv => "http-equiv",
+});
+
+attribute_props!("Link", LinkProps, LinkBuilder {
+    rel => "rel",
+    href => "href",
+    media => "media",
+    sizes => "sizes",
+    hreflang => "hreflang",
+    crossorigin => "crossorigin",
+    integrity => "integrity",
+});
+
+/// Add a `meta` tag to the head while this component is mounted
+///
+/// ```rust, ignore
+/// rsx! {
+///     Meta { name: "description", content: "{article.summary}" }
+/// }
+/// ```
+#[allow(non_snake_case)]
+pub fn Meta<'a>(cx: Scope<'a, MetaProps<'a>>) -> Element<'a> {
+    head_element(cx, attributes_element("meta", &cx.props.attributes))
+}
+
+/// Add a `link` tag to the head while this component is mounted
+///
+/// ```rust, ignore
+/// rsx! {
+///     Link { rel: "stylesheet", href: "/theme.css" }
+/// }
+/// ```
+#[allow(non_snake_case)]
+pub fn Link<'a>(cx: Scope<'a, LinkProps<'a>>) -> Element<'a> {
+    head_element(cx, attributes_element("link", &cx.props.attributes))
+}
@@ -0,0 +1,106 @@
+use crate::{
+    innerlude::{DynamicNode, VNode},
+    nodes::RenderReturn,
+    ScopeId, Template, TemplateNode, TemplatePath, VirtualDom,
+};
+
+/// What a scope has to do to pick up a hot-reloaded template
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReloadAction {
+    /// Only the static parts of the template changed. Its dynamic nodes and attributes line up with the mounted
+    /// node, so the renderer can patch the static parts and everything dynamic is kept.
+    PatchInPlace,
+
+    /// The dynamic parts of the template moved, so the mounted node has to be removed and created again.
+    Remount,
+}
+
+impl Template<'_> {
+    /// Check if a new version of this template has its dynamic nodes and attributes in the same places, so mounted
+    /// nodes can switch to it without being created again.
+    ///
+    /// Both templates must have the same roots and the same paths to their dynamic parts, and every dynamic node
+    /// must stay text or stay a full node. Static elements, attributes and text can change freely.
+    pub fn is_hot_reload_compatible(&self, other: &Template) -> bool {
+        self.roots.len() == other.roots.len()
+            && self.node_path_iter().eq(other.node_path_iter())
+            && self.attr_path_iter().eq(other.attr_path_iter())
+            && self
+                .node_path_iter()
+                .all(|path| is_text(self, path) == is_text(other, path))
+    }
+}
+
+// Check if the dynamic node at this path is a text node
+fn is_text(template: &Template, path: TemplatePath) -> Option<bool> {
+    let mut steps = path.iter();
+    let mut node = template.roots.get(steps.next()? as usize)?;
+    for idx in steps {
+        node = match node {
+            TemplateNode::Element { children, .. } => children.get(idx as usize)?,
+            _ => return None,
+        };
+    }
+
+    match node {
+        TemplateNode::DynamicText { .. } => Some(true),
+        TemplateNode::Dynamic { .. } => Some(false),
+        _ => None,
+    }
+}
+
+impl VirtualDom {
+    /// Plan how every mounted scope picks up a batch of hot-reloaded templates, without changing anything.
+    ///
+    /// Each scope that renders one of the templates is listed once, in ascending order. If any of its templates is
+    /// not [compatible](Template::is_hot_reload_compatible) with what is mounted, the scope has to remount, otherwise
+    /// it can patch the templates in place. Templates are matched by the place in the source code they came from,
+    /// like [`VirtualDom::replace_template`].
+    ///
+    /// ```rust, ignore
+    /// for (scope, action) in dom.hot_reload_plan(&changed) {
+    ///     tracing::debug!("{scope:?} will {action:?}");
+    /// }
+    /// ```
+    pub fn hot_reload_plan(&self, changed_templates: &[Template]) -> Vec<(ScopeId, ReloadAction)> {
+        let mut plan = Vec::new();
+        for (_, scope) in self.scopes.iter() {
+            if let Some(RenderReturn::Ready(node)) = scope.try_root_node() {
+                let mut action = None;
+                plan_node(node, changed_templates, &mut action);
+                if let Some(action) = action {
+                    plan.push((scope.scope_id(), action));
+                }
+            }
+        }
+        plan.sort_by_key(|(scope, _)| *scope);
+        plan
+    }
+}
+
+// Find the action for the templates rendered by this node and its fragments. Components have their own plan.
+fn plan_node(node: &VNode, changed_templates: &[Template], action: &mut Option<ReloadAction>) {
+    let mounted = node.template.get();
+    if let Some(changed) = changed_templates
+        .iter()
+        .find(|changed| source_location(changed.name) == source_location(mounted.name))
+    {
+        *action = match (*action, mounted.is_hot_reload_compatible(changed)) {
+            (Some(ReloadAction::Remount), _) | (_, false) => Some(ReloadAction::Remount),
+            _ => Some(ReloadAction::PatchInPlace),
+        };
+    }
+
+    for dynamic in node.dynamic_nodes.iter() {
+        if let DynamicNode::Fragment(children) = dynamic {
+            for child in children.iter() {
+                plan_node(child, changed_templates, action);
+            }
+        }
+    }
+}
+
+// Template names end with the index of the template inside its rsx call
+fn source_location(name: &str) -> &str {
+    name.rsplit_once(':').map_or(name, |(location, _)| location)
+}
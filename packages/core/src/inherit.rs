@@ -0,0 +1,87 @@
+use crate::{
+    innerlude::VNode,
+    walk::{TreeVisitor, VisitedAttribute},
+    ElementId, VirtualDom,
+};
+use rustc_hash::FxHashMap;
+
+impl VirtualDom {
+    /// Resolve the effective values of attributes that cascade down the tree, like `lang` or `dir`.
+    ///
+    /// Each of the given attributes is inherited from the nearest ancestor that sets it, unless the element sets it
+    /// itself. This is intended for renderers that don't have a CSS engine to do this work for them.
+    ///
+    /// The result maps each element with an [`ElementId`] to the effective values of the requested attributes.
+    /// Attributes that are not set anywhere on the path to the element are left out.
+    ///
+    /// ```rust, ignore
+    /// let node = match dom.base_scope().root_node() {
+    ///     RenderReturn::Ready(node) => node,
+    ///     _ => return,
+    /// };
+    ///
+    /// let inherited = dom.resolve_inherited(node, &["lang", "dir"]);
+    /// ```
+    pub fn resolve_inherited<'b, 'n>(
+        &'b self,
+        node: &'b VNode<'b>,
+        names: &[&'n str],
+    ) -> FxHashMap<ElementId, Vec<(&'n str, String)>> {
+        let mut resolver = InheritanceResolver {
+            names,
+            stack: Vec::new(),
+            resolved: FxHashMap::default(),
+        };
+
+        self.visit_node(node, &mut resolver);
+
+        resolver.resolved
+    }
+}
+
+struct InheritanceResolver<'a, 'n> {
+    names: &'a [&'n str],
+    stack: Vec<Vec<Option<String>>>,
+    resolved: FxHashMap<ElementId, Vec<(&'n str, String)>>,
+}
+
+impl<'b> TreeVisitor<'b> for InheritanceResolver<'_, '_> {
+    fn enter_element(
+        &mut self,
+        _tag: &'b str,
+        _namespace: Option<&'b str>,
+        id: Option<ElementId>,
+        attrs: &[VisitedAttribute<'b>],
+    ) {
+        let parent = self.stack.last();
+
+        let effective = self
+            .names
+            .iter()
+            .enumerate()
+            .map(|(idx, name)| {
+                attrs
+                    .iter()
+                    .filter(|attr| attr.name() == *name)
+                    .find_map(|attr| attr.text_value(attrs))
+                    .or_else(|| parent.and_then(|parent| parent[idx].clone()))
+            })
+            .collect::<Vec<_>>();
+
+        if let Some(id) = id {
+            let values = self
+                .names
+                .iter()
+                .zip(effective.iter())
+                .filter_map(|(name, value)| Some((*name, value.clone()?)))
+                .collect();
+            self.resolved.insert(id, values);
+        }
+
+        self.stack.push(effective);
+    }
+
+    fn exit_element(&mut self) {
+        self.stack.pop();
+    }
+}
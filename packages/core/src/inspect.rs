@@ -0,0 +1,127 @@
+use crate::{
+    innerlude::{DynamicNode, VNode},
+    ElementId, RenderReturn, ScopeId, VirtualDom,
+};
+
+/// A read-only snapshot of a mounted component, for devtools and other tools that display the component tree
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ComponentInfo {
+    /// The scope the component is mounted to
+    pub id: ScopeId,
+
+    /// The name of the component
+    pub name: &'static str,
+
+    /// The scope of the component that rendered this one, if this isn't the root
+    pub parent: Option<ScopeId>,
+
+    /// The components this component rendered, in the order they appear in the tree
+    pub children: Vec<ScopeId>,
+
+    /// The [`std::fmt::Debug`] output of the props, if the props describe themselves with
+    /// [`crate::Properties::inspect`]
+    pub props: Option<String>,
+
+    /// The number of hooks the component used in its last render
+    pub hooks: usize,
+
+    /// How many times the component has rendered
+    pub generation: usize,
+
+    /// The mounted elements, text nodes, and placeholders this component rendered directly, without the ones rendered
+    /// by its children
+    pub elements: Vec<ElementId>,
+
+    /// If the last render of the component suspended or threw an error
+    pub aborted: bool,
+}
+
+impl VirtualDom {
+    /// Walk every mounted component, starting from the root, parents before their children.
+    ///
+    /// ```rust, ignore
+    /// for component in dom.inspect() {
+    ///     let indent = "  ".repeat(dom.get_scope(component.id).unwrap().height() as usize);
+    ///     println!("{}{} {}", indent, component.name, component.props.unwrap_or_default());
+    /// }
+    /// ```
+    pub fn inspect(&self) -> Vec<ComponentInfo> {
+        let mut components = Vec::new();
+        let mut stack = vec![ScopeId::ROOT];
+        while let Some(id) = stack.pop() {
+            if let Some(info) = self.inspect_scope(id) {
+                stack.extend(info.children.iter().rev());
+                components.push(info);
+            }
+        }
+        components
+    }
+
+    /// Get a snapshot of a single mounted component
+    pub fn inspect_scope(&self, id: ScopeId) -> Option<ComponentInfo> {
+        let scope = self.get_scope(id)?;
+
+        let mut info = ComponentInfo {
+            id,
+            name: scope.context().name,
+            parent: scope.parent(),
+            children: Vec::new(),
+            props: scope.props.as_ref().and_then(|props| props.inspect()),
+            hooks: scope.hooks.borrow().len(),
+            generation: scope.generation(),
+            elements: Vec::new(),
+            aborted: false,
+        };
+
+        match scope.try_root_node() {
+            Some(RenderReturn::Ready(node)) => inspect_node(node, &mut info),
+            Some(RenderReturn::Aborted(placeholder)) => {
+                info.aborted = true;
+                info.elements.extend(placeholder.mounted_element());
+            }
+            None => {}
+        }
+
+        Some(info)
+    }
+}
+
+/// Collect the elements and child components of a node, without descending into the children
+fn inspect_node(node: &VNode, info: &mut ComponentInfo) {
+    for id in node.root_ids.borrow().iter() {
+        info.add_element(*id);
+    }
+    for attr in node.dynamic_attrs.iter() {
+        info.add_element(attr.mounted_element());
+    }
+
+    for dynamic in node.dynamic_nodes.iter() {
+        match dynamic {
+            DynamicNode::Component(component) => info.children.extend(component.mounted_scope()),
+            DynamicNode::Text(text) => {
+                if let Some(id) = text.mounted_element() {
+                    info.add_element(id);
+                }
+            }
+            DynamicNode::Placeholder(placeholder) => {
+                if let Some(id) = placeholder.mounted_element() {
+                    info.add_element(id);
+                }
+            }
+            DynamicNode::Fragment(nodes) => {
+                for node in nodes.iter() {
+                    inspect_node(node, info);
+                }
+            }
+        }
+    }
+}
+
+impl ComponentInfo {
+    // Roots and dynamic attributes can share an element
+    fn add_element(&mut self, id: ElementId) {
+        if !self.elements.contains(&id) {
+            self.elements.push(id);
+        }
+    }
+}
@@ -3,41 +3,102 @@
 #![doc(html_favicon_url = "https://avatars.githubusercontent.com/u/79236386")]
 #![warn(missing_docs)]
 
+mod accessible_name;
+mod allocator;
+mod animation;
 mod any_props;
 mod arena;
+mod budget;
 mod bump_frame;
+mod cache;
+mod capabilities;
+mod checksum;
+mod compact_paths;
 mod create;
+mod delegation;
+mod dependencies;
 mod diff;
+mod diff_cost;
 mod dirty_scope;
+mod drag;
+mod effects;
+mod element_refs;
 mod error_boundary;
 mod events;
+mod flags;
 mod fragment;
+mod head;
+mod hot_reload_plan;
+mod inherit;
+mod inspect;
 mod lazynodes;
+mod mock_dom;
 mod mutations;
 mod nodes;
+mod orphans;
+mod parent_links;
+mod portal;
 mod properties;
+mod reachable;
+mod recorder;
+mod remote;
+mod remount;
+mod render_prop;
 mod runtime;
 mod scheduler;
 mod scope_arena;
 mod scope_context;
 mod scopes;
+mod scroll;
+mod select;
+mod selection;
+mod sendable;
+mod sendable_diff;
+mod slots;
+mod spread;
+mod style_map;
+mod template_usage;
 mod virtual_dom;
+mod walk;
 
 pub(crate) mod innerlude {
+    pub use crate::allocator::*;
+    pub use crate::animation::*;
     pub use crate::arena::*;
+    pub use crate::budget::*;
+    pub use crate::capabilities::*;
+    pub use crate::checksum::*;
+    pub use crate::compact_paths::*;
+    pub use crate::dependencies::*;
+    pub use crate::diff_cost::*;
     pub use crate::dirty_scope::*;
+    pub use crate::drag::*;
     pub use crate::error_boundary::*;
     pub use crate::events::*;
     pub use crate::fragment::*;
+    pub use crate::head::*;
+    pub use crate::hot_reload_plan::*;
+    pub use crate::inspect::*;
     pub use crate::lazynodes::*;
+    pub use crate::mock_dom::*;
     pub use crate::mutations::*;
     pub use crate::nodes::RenderReturn;
     pub use crate::nodes::*;
+    pub use crate::portal::*;
     pub use crate::properties::*;
+    pub use crate::recorder::*;
+    pub use crate::remote::*;
+    pub use crate::remount::*;
+    pub use crate::render_prop::*;
     pub use crate::runtime::{Runtime, RuntimeGuard};
     pub use crate::scheduler::*;
     pub use crate::scope_context::*;
     pub use crate::scopes::*;
+    pub use crate::select::*;
+    pub use crate::selection::*;
+    pub use crate::sendable::*;
+    pub use crate::sendable_diff::*;
+    pub use crate::spread::*;
     pub use crate::virtual_dom::*;
 
     /// An [`Element`] is a possibly-none [`VNode`] created by calling `render` on [`Scope`] or [`ScopeState`].
@@ -76,11 +137,21 @@ pub(crate) mod innerlude {
 }
 
 pub use crate::innerlude::{
-    fc_to_builder, vdom_is_rendering, AnyValue, Attribute, AttributeValue, BorrowedAttributeValue,
-    CapturedError, Component, DynamicNode, Element, ElementId, Event, Fragment, IntoDynNode,
-    LazyNodes, Mutation, Mutations, Properties, RenderReturn, Scope, ScopeId, ScopeState, Scoped,
-    TaskId, Template, TemplateAttribute, TemplateNode, VComponent, VNode, VPlaceholder, VText,
-    VirtualDom,
+    diff_cost, diff_explained, diff_owned, drag_payload, fc_to_builder, inverse_mutation,
+    pack_paths, replay, vdom_is_rendering, AnyValue, Attribute, AttributeSegment,
+    AttributeTemplate, AttributeTransition, AttributeValue, BorrowedAttributeValue, Capabilities,
+    CapturedError, Component, ComponentInfo, DerivedAttribute, DerivedInputs, DiffCost,
+    DragPayload, DynamicNode, Element, ElementId, ErrorBoundary, ErrorBoundaryProps, Event,
+    Fragment, HeadElement, InitialAttribute, InspectDebug, InspectFallback, InspectProps,
+    IntoDynNode, IntoRenderProp, LazyNodes, Link, LinkBuilder, LinkProps, Meta, MetaBuilder,
+    MetaProps, MockDom, Mutation, MutationLog, MutationRecorder, Mutations, NodeAllocator,
+    PackedPath, Portal, PortalProps, PortalTarget, Priority, Properties, RecordedBatch,
+    ReloadAction, RemoteEvent, RenderBudget, RenderProp, RenderReturn, Scope, ScopeId, ScopeState,
+    Scoped, SelectedElement, SendableAttribute, SendableMutations, SendableNode, SendableValue,
+    SpreadAttributes, StructureHasher, StyleMap, SuspenseBoundary, SuspenseBoundaryProps,
+    SuspenseContext, TaskId, Template, TemplateAttribute, TemplateId, TemplateNode, TemplatePath,
+    Title, TitleBuilder, TitleProps, VComponent, VNode, VPlaceholder, VText, ValueId, VirtualDom,
+    WriteMutations, DRAG_PAYLOAD, HEAD_ATTRIBUTE, REMOUNT_TOKEN, SELECTION_ATTRIBUTES,
 };
 
 /// The purpose of this module is to alleviate imports of many common types
@@ -88,12 +159,14 @@ pub use crate::innerlude::{
 /// This includes types like [`Scope`], [`Element`], and [`Component`].
 pub mod prelude {
     pub use crate::innerlude::{
-        consume_context, consume_context_from_scope, current_scope_id, fc_to_builder, has_context,
-        provide_context, provide_context_to_scope, provide_root_context, push_future,
+        consume_context, consume_context_from_scope, current_scope_id, drag_payload, fc_to_builder,
+        has_context, provide_context, provide_context_to_scope, provide_root_context, push_future,
         remove_future, schedule_update_any, spawn, spawn_forever, suspend, throw, AnyValue,
-        Component, Element, Event, EventHandler, Fragment, IntoAttributeValue, IntoDynNode,
-        LazyNodes, Properties, Runtime, RuntimeGuard, Scope, ScopeId, ScopeState, Scoped, TaskId,
-        Template, TemplateAttribute, TemplateNode, Throw, VNode, VirtualDom,
+        Component, DragPayload, Element, ErrorBoundary, Event, EventHandler, Fragment,
+        IntoAttributeValue, IntoDynNode, LazyNodes, Portal, Priority, Properties, RenderProp,
+        Runtime, RuntimeGuard, Scope, ScopeId, ScopeState, Scoped, SpreadAttributes, StyleMap,
+        SuspenseBoundary, TaskId, Template, TemplateAttribute, TemplateNode, Throw, VNode, ValueId,
+        VirtualDom,
     };
 }
 
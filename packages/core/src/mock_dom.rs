@@ -0,0 +1,668 @@
+use std::cell::RefCell;
+use std::fmt::{Display, Formatter};
+
+use bumpalo::Bump;
+use rustc_hash::FxHashMap;
+
+use crate::{
+    innerlude::{BorrowedAttributeValue, DerivedAttribute, DerivedInputs, Mutation, Mutations},
+    ElementId, Template, TemplateAttribute, TemplateNode, TemplatePath,
+};
+
+/// An in-memory renderer that applies [`Mutation`]s to a simple tree of nodes.
+///
+/// This is meant for tests and tooling that need to see the tree a renderer would end up with, without a real
+/// renderer. Displaying the MockDom renders the children of the root as HTML.
+///
+/// Every string the MockDom stores is kept until the MockDom is dropped, so values that were overwritten can still be
+/// borrowed. This is what lets [`inverse_mutation`] refer to the old value of an attribute or text node.
+///
+/// ```rust, ignore
+/// let mut vdom = VirtualDom::new(app);
+/// let dom = MockDom::new();
+/// dom.apply(&vdom.rebuild());
+///
+/// assert_eq!(dom.to_string(), "<div>hello world</div>");
+/// ```
+#[derive(Default)]
+pub struct MockDom {
+    tree: RefCell<MockTree>,
+    strings: Bump,
+}
+
+struct MockTree {
+    nodes: Vec<MockNode>,
+    ids: Vec<Option<usize>>,
+    stack: Vec<usize>,
+    templates: FxHashMap<String, Vec<usize>>,
+}
+
+struct MockNode {
+    parent: Option<usize>,
+    kind: MockNodeKind,
+}
+
+#[derive(Clone)]
+enum MockNodeKind {
+    Element {
+        tag: &'static str,
+        attrs: Vec<MockAttribute>,
+        listeners: Vec<&'static str>,
+        children: Vec<usize>,
+    },
+    Text(&'static str),
+    Placeholder,
+}
+
+#[derive(Clone, Copy)]
+struct MockAttribute {
+    name: &'static str,
+    namespace: Option<&'static str>,
+    value: Option<&'static str>,
+    derived: Option<DerivedAttribute>,
+}
+
+impl Default for MockTree {
+    fn default() -> Self {
+        let root = MockNode {
+            parent: None,
+            kind: MockNodeKind::Element {
+                tag: "root",
+                attrs: Vec::new(),
+                listeners: Vec::new(),
+                children: Vec::new(),
+            },
+        };
+
+        Self {
+            nodes: vec![root],
+            ids: vec![Some(0)],
+            stack: vec![0],
+            templates: FxHashMap::default(),
+        }
+    }
+}
+
+impl MockDom {
+    /// Create a MockDom with an empty root element mounted as [`ElementId(0)`](ElementId)
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Register the templates and apply the edits of a batch of mutations
+    pub fn apply(&self, mutations: &Mutations) {
+        for template in &mutations.templates {
+            self.register_template(template);
+        }
+
+        for edit in &mutations.edits {
+            self.apply_edit(edit);
+        }
+    }
+
+    /// Register a template so it can be loaded by later edits
+    pub fn register_template(&self, template: &Template) {
+        let mut tree = self.tree.borrow_mut();
+        let roots = template
+            .roots
+            .iter()
+            .map(|root| self.create_template_node(&mut tree, root))
+            .collect();
+        tree.templates.insert(template.name.to_string(), roots);
+    }
+
+    /// Apply a single edit
+    ///
+    /// Unlike most renderers, removing a node that is still on the stack takes it off the stack. This makes
+    /// [`Mutation::Remove`] the inverse of the mutations that create a node.
+    pub fn apply_edit(&self, edit: &Mutation) {
+        let mut tree = self.tree.borrow_mut();
+        let tree = &mut *tree;
+
+        match edit {
+            Mutation::AppendChildren { id, m } => {
+                let children = tree.stack.split_off(tree.stack.len() - m);
+                let parent = tree.node(*id);
+                for child in children {
+                    tree.append(parent, child);
+                }
+            }
+            Mutation::AppendToAnchor { anchor, m } => {
+                let children = tree.stack.split_off(tree.stack.len() - m);
+                let parent = self.anchor(tree, anchor);
+                for child in children {
+                    tree.append(parent, child);
+                }
+            }
+            Mutation::AssignId { path, id } => {
+                let node = tree.load_child(*path);
+                tree.assign_id(node, *id);
+            }
+            Mutation::CreatePlaceholder { id } => {
+                let node = tree.create(MockNodeKind::Placeholder);
+                tree.assign_id(node, *id);
+                tree.stack.push(node);
+            }
+            Mutation::CreateTextNode { value, id } => {
+                let node = tree.create(MockNodeKind::Text(self.intern(value)));
+                tree.assign_id(node, *id);
+                tree.stack.push(node);
+            }
+            Mutation::HydrateText { path, value, id } => {
+                let node = tree.load_child(*path);
+                match &mut tree.nodes[node].kind {
+                    MockNodeKind::Text(text) => {
+                        *text = self.intern(value);
+                        tree.assign_id(node, *id);
+                    }
+                    _ => {
+                        let text = tree.create(MockNodeKind::Text(self.intern(value)));
+                        tree.replace(node, vec![text]);
+                        tree.assign_id(text, *id);
+                    }
+                }
+            }
+            Mutation::LoadTemplate { name, index, id } => {
+                let node = tree.load_template(name, *index);
+                tree.assign_id(node, *id);
+                tree.stack.push(node);
+            }
+            Mutation::CreateElementWithAttrs {
+                name,
+                index,
+                id,
+                attrs,
+                listeners,
+                ..
+            } => {
+                let node = tree.load_template(name, *index);
+                tree.assign_id(node, *id);
+                tree.stack.push(node);
+                for attr in attrs {
+                    self.set_attribute(tree, node, attr.name, &attr.value, attr.ns);
+                }
+                for listener in listeners {
+                    tree.new_event_listener(node, self.intern(listener));
+                }
+            }
+            Mutation::ReplaceWith { id, m } => {
+                let new_nodes = tree.stack.split_off(tree.stack.len() - m);
+                let old = tree.node(*id);
+                tree.replace(old, new_nodes);
+            }
+            Mutation::ReplacePlaceholder { path, m } => {
+                let new_nodes = tree.stack.split_off(tree.stack.len() - m);
+                let old = tree.load_child(*path);
+                tree.replace(old, new_nodes);
+            }
+            Mutation::InsertAfter { id, m } => {
+                let new_nodes = tree.stack.split_off(tree.stack.len() - m);
+                let anchor = tree.node(*id);
+                tree.insert(anchor, new_nodes, 1);
+            }
+            Mutation::InsertBefore { id, m } => {
+                let new_nodes = tree.stack.split_off(tree.stack.len() - m);
+                let anchor = tree.node(*id);
+                tree.insert(anchor, new_nodes, 0);
+            }
+            Mutation::SetAttribute {
+                name,
+                value,
+                id,
+                ns,
+            } => {
+                let node = tree.node(*id);
+                self.set_attribute(tree, node, name, value, *ns);
+            }
+            Mutation::SetText { value, id } => {
+                let node = tree.node(*id);
+                if let MockNodeKind::Text(text) = &mut tree.nodes[node].kind {
+                    *text = self.intern(value);
+                }
+            }
+            Mutation::NewEventListener { name, id } => {
+                let node = tree.node(*id);
+                tree.new_event_listener(node, self.intern(name));
+            }
+            Mutation::RemoveEventListener { name, id } => {
+                let node = tree.node(*id);
+                if let MockNodeKind::Element { listeners, .. } = &mut tree.nodes[node].kind {
+                    listeners.retain(|listener| listener != name);
+                }
+            }
+            Mutation::Remove { id } => {
+                let node = tree.node(*id);
+                tree.detach(node);
+                tree.stack.retain(|on_stack| *on_stack != node);
+            }
+            Mutation::PushRoot { id } => {
+                let node = tree.node(*id);
+                tree.stack.push(node);
+            }
+            // Delegated listeners live on the root
+            Mutation::RegisterDelegated { name } => tree.new_event_listener(0, self.intern(name)),
+            Mutation::UnregisterDelegated { name } => {
+                if let MockNodeKind::Element { listeners, .. } = &mut tree.nodes[0].kind {
+                    listeners.retain(|listener| listener != name);
+                }
+            }
+            // There is no document to scroll or style
+            Mutation::CaptureScroll { .. }
+            | Mutation::RestoreScroll { .. }
+            | Mutation::InjectStyle { .. } => {}
+        }
+    }
+
+    /// Change the value of a form field the way a user typing into it would, without going through the VirtualDom
+    pub fn set_user_value(&self, id: ElementId, value: &str) {
+        let mut tree = self.tree.borrow_mut();
+        let node = tree.node(id);
+        self.set_attribute(
+            &mut tree,
+            node,
+            "value",
+            &BorrowedAttributeValue::Text(value),
+            None,
+        );
+    }
+
+    /// Get the text of a text node
+    pub fn text(&self, id: ElementId) -> Option<&str> {
+        let tree = self.tree.borrow();
+        match tree.nodes[*tree.ids.get(id.0)?.as_ref()?].kind {
+            MockNodeKind::Text(text) => Some(text),
+            _ => None,
+        }
+    }
+
+    /// Get the value of an attribute on an element
+    pub fn attribute(&self, id: ElementId, name: &str) -> Option<&str> {
+        let tree = self.tree.borrow();
+        match &tree.nodes[*tree.ids.get(id.0)?.as_ref()?].kind {
+            MockNodeKind::Element { attrs, .. } => attrs
+                .iter()
+                .find(|attr| attr.name == name)
+                .and_then(|attr| attr.value),
+            _ => None,
+        }
+    }
+
+    /// Get the names of the events an element is listening for
+    pub fn listeners(&self, id: ElementId) -> Vec<&str> {
+        let tree = self.tree.borrow();
+        let node = match tree.ids.get(id.0).copied().flatten() {
+            Some(node) => node,
+            None => return Vec::new(),
+        };
+        match &tree.nodes[node].kind {
+            MockNodeKind::Element { listeners, .. } => listeners.clone(),
+            _ => Vec::new(),
+        }
+    }
+
+    // Find the element with this id under the root, or create it. Anchors stand in for elements outside of the
+    // VirtualDom, like a modal root in the page.
+    fn anchor(&self, tree: &mut MockTree, name: &str) -> usize {
+        let existing = tree.children(0).iter().copied().find(|child| {
+            matches!(&tree.nodes[*child].kind, MockNodeKind::Element { attrs, .. }
+                if attrs.iter().any(|attr| attr.name == "id" && attr.value == Some(name)))
+        });
+        if let Some(anchor) = existing {
+            return anchor;
+        }
+
+        let anchor = tree.create(MockNodeKind::Element {
+            tag: "div",
+            attrs: vec![MockAttribute {
+                name: "id",
+                namespace: None,
+                value: Some(self.intern(name)),
+                derived: None,
+            }],
+            listeners: Vec::new(),
+            children: Vec::new(),
+        });
+        tree.append(0, anchor);
+        anchor
+    }
+
+    fn set_attribute(
+        &self,
+        tree: &mut MockTree,
+        node: usize,
+        name: &str,
+        value: &BorrowedAttributeValue,
+        namespace: Option<&str>,
+    ) {
+        let attrs = match &mut tree.nodes[node].kind {
+            MockNodeKind::Element { attrs, .. } => attrs,
+            _ => return,
+        };
+
+        let (value, derived) = match value {
+            BorrowedAttributeValue::Text(value) => (Some(self.intern(value)), None),
+            BorrowedAttributeValue::Float(value) => (Some(self.intern(&value.to_string())), None),
+            BorrowedAttributeValue::Int(value) => (Some(self.intern(&value.to_string())), None),
+            BorrowedAttributeValue::Bool(value) => (Some(self.intern(&value.to_string())), None),
+            BorrowedAttributeValue::Derived(derived) => (None, Some(*derived)),
+            // Custom values and binary data only mean something to the renderer they were made for
+            BorrowedAttributeValue::Any(_) | BorrowedAttributeValue::Bytes(_) => return,
+            BorrowedAttributeValue::None => {
+                attrs.retain(|attr| attr.name != name || attr.namespace != namespace);
+                self.update_derived(attrs);
+                return;
+            }
+        };
+
+        match attrs
+            .iter_mut()
+            .find(|attr| attr.name == name && attr.namespace == namespace)
+        {
+            Some(attr) => {
+                attr.value = value;
+                attr.derived = derived;
+            }
+            None => attrs.push(MockAttribute {
+                name: self.intern(name),
+                namespace: namespace.map(|ns| self.intern(ns)),
+                value,
+                derived,
+            }),
+        }
+
+        self.update_derived(attrs);
+    }
+
+    /// Recompute the derived attributes of an element after its other attributes changed
+    fn update_derived(&self, attrs: &mut [MockAttribute]) {
+        for idx in 0..attrs.len() {
+            if let Some(derived) = attrs[idx].derived {
+                let value = {
+                    let attrs = &*attrs;
+                    let lookup = |name: &str| {
+                        attrs
+                            .iter()
+                            .find(|attr| attr.name == name)
+                            .and_then(|attr| attr.value)
+                            .map(str::to_string)
+                    };
+                    derived.compute(&DerivedInputs::new(&lookup))
+                };
+                attrs[idx].value = value.map(|value| self.intern(&value));
+            }
+        }
+    }
+
+    fn create_template_node(&self, tree: &mut MockTree, node: &TemplateNode) -> usize {
+        match node {
+            TemplateNode::Element {
+                tag,
+                attrs,
+                children,
+                ..
+            } => {
+                let attrs = attrs
+                    .iter()
+                    .filter_map(|attr| match attr {
+                        TemplateAttribute::Static {
+                            name,
+                            value,
+                            namespace,
+                        } => Some(MockAttribute {
+                            name: self.intern(name),
+                            namespace: namespace.map(|ns| self.intern(ns)),
+                            value: Some(self.intern(value)),
+                            derived: None,
+                        }),
+                        TemplateAttribute::Dynamic { .. }
+                        | TemplateAttribute::Conditional { .. } => None,
+                    })
+                    .collect();
+                let element = tree.create(MockNodeKind::Element {
+                    tag: self.intern(tag),
+                    attrs,
+                    listeners: Vec::new(),
+                    children: Vec::new(),
+                });
+                for child in children.iter() {
+                    let child = self.create_template_node(tree, child);
+                    tree.append(element, child);
+                }
+                element
+            }
+            TemplateNode::Text { text } => tree.create(MockNodeKind::Text(self.intern(text))),
+            TemplateNode::Dynamic { .. } => tree.create(MockNodeKind::Placeholder),
+            TemplateNode::DynamicText { .. } => tree.create(MockNodeKind::Text("")),
+        }
+    }
+
+    fn intern(&self, value: &str) -> &'static str {
+        // Safety: the strings are never freed before the MockDom is dropped, and they are only handed out borrowed
+        // from the MockDom
+        unsafe { std::mem::transmute(&*self.strings.alloc_str(value)) }
+    }
+}
+
+impl MockTree {
+    fn create(&mut self, kind: MockNodeKind) -> usize {
+        self.nodes.push(MockNode { parent: None, kind });
+        self.nodes.len() - 1
+    }
+
+    fn node(&self, id: ElementId) -> usize {
+        match self.ids.get(id.0).copied().flatten() {
+            Some(node) => node,
+            None => panic!("{:?} is not mounted in the MockDom", id),
+        }
+    }
+
+    fn assign_id(&mut self, node: usize, id: ElementId) {
+        if self.ids.len() <= id.0 {
+            self.ids.resize(id.0 + 1, None);
+        }
+        self.ids[id.0] = Some(node);
+    }
+
+    fn load_child(&self, path: TemplatePath) -> usize {
+        let mut current = *self.stack.last().unwrap();
+        for idx in path.iter() {
+            current = self.children(current)[idx as usize];
+        }
+        current
+    }
+
+    fn load_template(&mut self, name: &str, index: usize) -> usize {
+        let root = self.templates[name][index];
+        self.deep_clone(root)
+    }
+
+    fn deep_clone(&mut self, node: usize) -> usize {
+        let mut kind = self.nodes[node].kind.clone();
+        if let MockNodeKind::Element { children, .. } = &mut kind {
+            let old_children = std::mem::take(children);
+            let clone = self.create(kind);
+            for child in old_children {
+                let child = self.deep_clone(child);
+                self.append(clone, child);
+            }
+            return clone;
+        }
+        self.create(kind)
+    }
+
+    fn children(&self, node: usize) -> &[usize] {
+        match &self.nodes[node].kind {
+            MockNodeKind::Element { children, .. } => children,
+            _ => &[],
+        }
+    }
+
+    fn children_mut(&mut self, node: usize) -> &mut Vec<usize> {
+        match &mut self.nodes[node].kind {
+            MockNodeKind::Element { children, .. } => children,
+            _ => panic!("only elements can have children"),
+        }
+    }
+
+    fn append(&mut self, parent: usize, child: usize) {
+        self.detach(child);
+        self.children_mut(parent).push(child);
+        self.nodes[child].parent = Some(parent);
+    }
+
+    /// Insert the nodes next to the anchor, either before it (offset 0) or after it (offset 1)
+    fn insert(&mut self, anchor: usize, nodes: Vec<usize>, offset: usize) {
+        for node in &nodes {
+            self.detach(*node);
+        }
+        let parent = match self.nodes[anchor].parent {
+            Some(parent) => parent,
+            None => return,
+        };
+        let idx = self.index_in_parent(parent, anchor) + offset;
+        for node in &nodes {
+            self.nodes[*node].parent = Some(parent);
+        }
+        let children = self.children_mut(parent);
+        let tail = children.split_off(idx);
+        children.extend(nodes);
+        children.extend(tail);
+    }
+
+    fn replace(&mut self, old: usize, nodes: Vec<usize>) {
+        self.insert(old, nodes, 0);
+        self.detach(old);
+    }
+
+    fn detach(&mut self, node: usize) {
+        if let Some(parent) = self.nodes[node].parent.take() {
+            let idx = self.index_in_parent(parent, node);
+            self.children_mut(parent).remove(idx);
+        }
+    }
+
+    fn index_in_parent(&self, parent: usize, node: usize) -> usize {
+        self.children(parent)
+            .iter()
+            .position(|child| *child == node)
+            .unwrap()
+    }
+
+    fn new_event_listener(&mut self, node: usize, name: &'static str) {
+        if let MockNodeKind::Element { listeners, .. } = &mut self.nodes[node].kind {
+            if !listeners.contains(&name) {
+                listeners.push(name);
+            }
+        }
+    }
+
+    fn write_node(&self, node: usize, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match &self.nodes[node].kind {
+            MockNodeKind::Element {
+                tag,
+                attrs,
+                children,
+                ..
+            } => {
+                write!(f, "<{}", tag)?;
+                for attr in attrs {
+                    if let Some(value) = attr.value {
+                        write!(f, " {}=\"{}\"", attr.name, value)?;
+                    }
+                }
+                write!(f, ">")?;
+                for child in children {
+                    self.write_node(*child, f)?;
+                }
+                write!(f, "</{}>", tag)
+            }
+            MockNodeKind::Text(text) => write!(f, "{}", text),
+            MockNodeKind::Placeholder => write!(f, "<!--placeholder-->"),
+        }
+    }
+}
+
+impl Display for MockDom {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let tree = self.tree.borrow();
+        for child in tree.children(0) {
+            tree.write_node(*child, f)?;
+        }
+        Ok(())
+    }
+}
+
+/// Compute the mutation that undoes the given mutation, based on the state of the dom before it is applied.
+///
+/// Only mutations that change a single node in place or create a new node can be undone with a single mutation:
+/// - [`Mutation::SetText`] and [`Mutation::HydrateText`] are undone by setting the old text
+/// - [`Mutation::SetAttribute`] is undone by setting the old value, or removing the attribute if it wasn't set
+/// - [`Mutation::NewEventListener`] and [`Mutation::RemoveEventListener`] undo each other
+/// - [`Mutation::RegisterDelegated`] and [`Mutation::UnregisterDelegated`] undo each other
+/// - Mutations that create a node on the stack are undone by [`Mutation::Remove`]
+///
+/// Everything else returns `None`.
+///
+/// ```rust, ignore
+/// let inverse = inverse_mutation(&edit, &dom);
+/// dom.apply_edit(&edit);
+///
+/// // Later, roll the edit back
+/// if let Some(inverse) = inverse {
+///     dom.apply_edit(&inverse);
+/// }
+/// ```
+pub fn inverse_mutation<'a>(mutation: &Mutation<'a>, dom: &'a MockDom) -> Option<Mutation<'a>> {
+    match mutation {
+        Mutation::SetText { id, .. } => Some(Mutation::SetText {
+            value: dom.text(*id)?,
+            id: *id,
+        }),
+        Mutation::HydrateText { path, id, .. } => {
+            let tree = dom.tree.borrow();
+            match tree.nodes[tree.load_child(*path)].kind {
+                MockNodeKind::Text(value) => Some(Mutation::SetText { value, id: *id }),
+                _ => None,
+            }
+        }
+        Mutation::SetAttribute { name, id, ns, .. } => {
+            let tree = dom.tree.borrow();
+            let attrs = match &tree.nodes[tree.node(*id)].kind {
+                MockNodeKind::Element { attrs, .. } => attrs,
+                _ => return None,
+            };
+            let value = match attrs
+                .iter()
+                .find(|attr| attr.name == *name && attr.namespace == *ns)
+            {
+                Some(MockAttribute {
+                    derived: Some(derived),
+                    ..
+                }) => BorrowedAttributeValue::Derived(*derived),
+                Some(MockAttribute {
+                    value: Some(value), ..
+                }) => BorrowedAttributeValue::Text(value),
+                _ => BorrowedAttributeValue::None,
+            };
+            Some(Mutation::SetAttribute {
+                name,
+                value,
+                id: *id,
+                ns: *ns,
+            })
+        }
+        Mutation::NewEventListener { name, id } => {
+            Some(Mutation::RemoveEventListener { name, id: *id })
+        }
+        Mutation::RemoveEventListener { name, id } => {
+            Some(Mutation::NewEventListener { name, id: *id })
+        }
+        Mutation::RegisterDelegated { name } => Some(Mutation::UnregisterDelegated { name }),
+        Mutation::UnregisterDelegated { name } => Some(Mutation::RegisterDelegated { name }),
+        Mutation::CreatePlaceholder { id }
+        | Mutation::CreateTextNode { id, .. }
+        | Mutation::LoadTemplate { id, .. }
+        | Mutation::CreateElementWithAttrs { id, .. } => Some(Mutation::Remove { id: *id }),
+        _ => None,
+    }
+}
@@ -1,6 +1,6 @@
 use rustc_hash::FxHashSet;
 
-use crate::{arena::ElementId, innerlude::BorrowedAttributeValue, ScopeId, Template};
+use crate::{arena::ElementId, innerlude::BorrowedAttributeValue, ScopeId, Template, TemplatePath};
 
 /// A container for all the relevant steps to modify the Real DOM
 ///
@@ -13,7 +13,12 @@ use crate::{arena::ElementId, innerlude::BorrowedAttributeValue, ScopeId, Templa
 /// Templates, however, apply to all subtrees, not just target subtree.
 ///
 /// Mutations are the only link between the RealDOM and the VirtualDOM.
-#[cfg_attr(feature = "serialize", derive(serde::Serialize))]
+///
+/// With the `serialize` feature, mutations can be sent to a renderer in another process, like a LiveView client. Every
+/// [`Mutation`] and [`crate::TemplateNode`] is tagged with its variant name under `"type"`, so the wire format doesn't
+/// depend on the order of the variants.
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serialize", serde(bound(deserialize = "'de: 'static")))]
 #[derive(Debug, Default)]
 #[must_use = "not handling edits can lead to visual inconsistencies in UI"]
 pub struct Mutations<'a> {
@@ -26,9 +31,11 @@ pub struct Mutations<'a> {
     /// Any templates encountered while diffing the DOM.
     ///
     /// These must be loaded into a cache before applying the edits
+    #[cfg_attr(feature = "serialize", serde(borrow))]
     pub templates: Vec<Template<'a>>,
 
     /// Any mutations required to patch the renderer to match the layout of the VirtualDom
+    #[cfg_attr(feature = "serialize", serde(borrow))]
     pub edits: Vec<Mutation<'a>>,
 }
 
@@ -38,17 +45,258 @@ impl<'a> Mutations<'a> {
     /// Used really only for testing
     pub fn santize(mut self) -> Self {
         for edit in self.edits.iter_mut() {
-            if let Mutation::LoadTemplate { name, .. } = edit {
-                *name = "template"
+            match edit {
+                Mutation::LoadTemplate { name, .. }
+                | Mutation::CreateElementWithAttrs { name, .. } => *name = "template",
+                _ => {}
             }
         }
 
         self
     }
 
-    /// Push a new mutation into the dom_edits list
-    pub(crate) fn push(&mut self, mutation: Mutation<'static>) {
-        self.edits.push(mutation)
+    /// Register every template and then write every edit to the given renderer, in order
+    pub fn write_to(&self, to: &mut impl WriteMutations<'a>) {
+        for template in &self.templates {
+            to.register_template(*template);
+        }
+
+        for edit in &self.edits {
+            edit.write_to(to);
+        }
+    }
+}
+
+/// A renderer that applies edits one at a time as they are handed over by the [`crate::VirtualDom`].
+///
+/// The VirtualDom writes every edit through this trait while it diffs, so renderers that implement it can use
+/// [`crate::VirtualDom::rebuild_to`] and [`crate::VirtualDom::render_immediate_to`] to apply each edit as soon as it
+/// is made. [`Mutations`] implements this trait by collecting the edits into a list. Every method matches the
+/// [`Mutation`] of the same name, so see its docs for what the renderer should do.
+///
+/// Text and names borrow from the VirtualDom for `'a`, which ends before the next render.
+///
+/// Edits that are only sent to renderers that opt into a [`crate::Capabilities`] or that don't change the tree have a
+/// default implementation.
+pub trait WriteMutations<'a> {
+    /// Save a template so it can be loaded by [`WriteMutations::load_template`].
+    fn register_template(&mut self, template: Template<'a>);
+
+    /// See [`Mutation::AppendChildren`]
+    fn append_children(&mut self, id: ElementId, m: usize);
+
+    /// See [`Mutation::AssignId`]
+    fn assign_id(&mut self, path: TemplatePath<'static>, id: ElementId);
+
+    /// See [`Mutation::CreatePlaceholder`]
+    fn create_placeholder(&mut self, id: ElementId);
+
+    /// See [`Mutation::CreateTextNode`]
+    fn create_text_node(&mut self, value: &'a str, id: ElementId);
+
+    /// See [`Mutation::HydrateText`]
+    fn hydrate_text(&mut self, path: TemplatePath<'static>, value: &'a str, id: ElementId);
+
+    /// See [`Mutation::LoadTemplate`]
+    fn load_template(&mut self, name: &'static str, index: usize, id: ElementId);
+
+    /// See [`Mutation::CreateElementWithAttrs`]
+    ///
+    /// By default, this loads the template and then sets each attribute and listener separately.
+    fn create_element_with_attrs(
+        &mut self,
+        name: &'static str,
+        index: usize,
+        id: ElementId,
+        _tag: &'a str,
+        attrs: &[InitialAttribute<'a>],
+        listeners: &[&'a str],
+    ) {
+        self.load_template(name, index, id);
+        for attr in attrs {
+            self.set_attribute(attr.name, attr.ns, &attr.value, id);
+        }
+        for listener in listeners {
+            self.new_event_listener(listener, id);
+        }
+    }
+
+    /// See [`Mutation::ReplaceWith`]
+    fn replace_with(&mut self, id: ElementId, m: usize);
+
+    /// See [`Mutation::ReplacePlaceholder`]
+    fn replace_placeholder(&mut self, path: TemplatePath<'static>, m: usize);
+
+    /// See [`Mutation::InsertAfter`]
+    fn insert_after(&mut self, id: ElementId, m: usize);
+
+    /// See [`Mutation::InsertBefore`]
+    fn insert_before(&mut self, id: ElementId, m: usize);
+
+    /// See [`Mutation::SetAttribute`]
+    fn set_attribute(
+        &mut self,
+        name: &'a str,
+        ns: Option<&'a str>,
+        value: &BorrowedAttributeValue<'a>,
+        id: ElementId,
+    );
+
+    /// See [`Mutation::SetText`]
+    fn set_text(&mut self, value: &'a str, id: ElementId);
+
+    /// See [`Mutation::NewEventListener`]
+    fn new_event_listener(&mut self, name: &'a str, id: ElementId);
+
+    /// See [`Mutation::RemoveEventListener`]
+    fn remove_event_listener(&mut self, name: &'a str, id: ElementId);
+
+    /// See [`Mutation::Remove`]
+    fn remove(&mut self, id: ElementId);
+
+    /// See [`Mutation::PushRoot`]
+    fn push_root(&mut self, id: ElementId);
+
+    /// See [`Mutation::AppendToAnchor`]
+    fn append_to_anchor(&mut self, anchor: &'a str, m: usize);
+
+    /// See [`Mutation::CaptureScroll`]. Does nothing by default.
+    fn capture_scroll(&mut self, _id: ElementId) {}
+
+    /// See [`Mutation::RestoreScroll`]. Does nothing by default.
+    fn restore_scroll(&mut self, _id: ElementId, _x: f64, _y: f64) {}
+
+    /// See [`Mutation::InjectStyle`]. Does nothing by default.
+    fn inject_style(&mut self, _name: &'a str, _style: &'a str) {}
+
+    /// See [`Mutation::RegisterDelegated`]. Does nothing by default.
+    fn register_delegated(&mut self, _name: &'a str) {}
+
+    /// See [`Mutation::UnregisterDelegated`]. Does nothing by default.
+    fn unregister_delegated(&mut self, _name: &'a str) {}
+}
+
+impl<'a> WriteMutations<'a> for Mutations<'a> {
+    fn register_template(&mut self, template: Template<'a>) {
+        self.templates.push(template);
+    }
+
+    fn append_children(&mut self, id: ElementId, m: usize) {
+        self.edits.push(Mutation::AppendChildren { id, m });
+    }
+
+    fn assign_id(&mut self, path: TemplatePath<'static>, id: ElementId) {
+        self.edits.push(Mutation::AssignId { path, id });
+    }
+
+    fn create_placeholder(&mut self, id: ElementId) {
+        self.edits.push(Mutation::CreatePlaceholder { id });
+    }
+
+    fn create_text_node(&mut self, value: &'a str, id: ElementId) {
+        self.edits.push(Mutation::CreateTextNode { value, id });
+    }
+
+    fn hydrate_text(&mut self, path: TemplatePath<'static>, value: &'a str, id: ElementId) {
+        self.edits.push(Mutation::HydrateText { path, value, id });
+    }
+
+    fn load_template(&mut self, name: &'static str, index: usize, id: ElementId) {
+        self.edits.push(Mutation::LoadTemplate { name, index, id });
+    }
+
+    fn create_element_with_attrs(
+        &mut self,
+        name: &'static str,
+        index: usize,
+        id: ElementId,
+        tag: &'a str,
+        attrs: &[InitialAttribute<'a>],
+        listeners: &[&'a str],
+    ) {
+        self.edits.push(Mutation::CreateElementWithAttrs {
+            name,
+            index,
+            id,
+            tag,
+            attrs: attrs.to_vec(),
+            listeners: listeners.to_vec(),
+        });
+    }
+
+    fn replace_with(&mut self, id: ElementId, m: usize) {
+        self.edits.push(Mutation::ReplaceWith { id, m });
+    }
+
+    fn replace_placeholder(&mut self, path: TemplatePath<'static>, m: usize) {
+        self.edits.push(Mutation::ReplacePlaceholder { path, m });
+    }
+
+    fn insert_after(&mut self, id: ElementId, m: usize) {
+        self.edits.push(Mutation::InsertAfter { id, m });
+    }
+
+    fn insert_before(&mut self, id: ElementId, m: usize) {
+        self.edits.push(Mutation::InsertBefore { id, m });
+    }
+
+    fn set_attribute(
+        &mut self,
+        name: &'a str,
+        ns: Option<&'a str>,
+        value: &BorrowedAttributeValue<'a>,
+        id: ElementId,
+    ) {
+        self.edits.push(Mutation::SetAttribute {
+            name,
+            value: value.clone(),
+            id,
+            ns,
+        });
+    }
+
+    fn set_text(&mut self, value: &'a str, id: ElementId) {
+        self.edits.push(Mutation::SetText { value, id });
+    }
+
+    fn new_event_listener(&mut self, name: &'a str, id: ElementId) {
+        self.edits.push(Mutation::NewEventListener { name, id });
+    }
+
+    fn remove_event_listener(&mut self, name: &'a str, id: ElementId) {
+        self.edits.push(Mutation::RemoveEventListener { name, id });
+    }
+
+    fn remove(&mut self, id: ElementId) {
+        self.edits.push(Mutation::Remove { id });
+    }
+
+    fn push_root(&mut self, id: ElementId) {
+        self.edits.push(Mutation::PushRoot { id });
+    }
+
+    fn append_to_anchor(&mut self, anchor: &'a str, m: usize) {
+        self.edits.push(Mutation::AppendToAnchor { anchor, m });
+    }
+
+    fn capture_scroll(&mut self, id: ElementId) {
+        self.edits.push(Mutation::CaptureScroll { id });
+    }
+
+    fn restore_scroll(&mut self, id: ElementId, x: f64, y: f64) {
+        self.edits.push(Mutation::RestoreScroll { id, x, y });
+    }
+
+    fn inject_style(&mut self, name: &'a str, style: &'a str) {
+        self.edits.push(Mutation::InjectStyle { name, style });
+    }
+
+    fn register_delegated(&mut self, name: &'a str) {
+        self.edits.push(Mutation::RegisterDelegated { name });
+    }
+
+    fn unregister_delegated(&mut self, name: &'a str) {
+        self.edits.push(Mutation::UnregisterDelegated { name });
     }
 }
 
@@ -82,8 +330,9 @@ pub enum Mutation<'a> {
         /// The path of the child of the topmost node on the stack
         ///
         /// A path of `[]` represents the topmost node. A path of `[0]` represents the first child.
-        /// `[0,1,2]` represents 1st child's 2nd child's 3rd child.
-        path: &'static [u8],
+        /// `[0,1,2]` represents 1st child's 2nd child's 3rd child. Most paths are packed into a single integer, see
+        /// [`TemplatePath`].
+        path: TemplatePath<'static>,
 
         /// The ID we're assigning to this element/placeholder.
         ///
@@ -119,8 +368,9 @@ pub enum Mutation<'a> {
         /// The path of the child of the topmost node on the stack
         ///
         /// A path of `[]` represents the topmost node. A path of `[0]` represents the first child.
-        /// `[0,1,2]` represents 1st child's 2nd child's 3rd child.
-        path: &'static [u8],
+        /// `[0,1,2]` represents 1st child's 2nd child's 3rd child. Most paths are packed into a single integer, see
+        /// [`TemplatePath`].
+        path: TemplatePath<'static>,
 
         /// The value of the textnode that we want to set the placeholder with
         value: &'a str,
@@ -150,6 +400,33 @@ pub enum Mutation<'a> {
         id: ElementId,
     },
 
+    /// Load a root element from a template like [`Mutation::LoadTemplate`], and set up all of its initial dynamic
+    /// attributes and listeners at once.
+    ///
+    /// This is only sent to renderers that opt into [`crate::Capabilities::grouped_attributes`]. Attributes and
+    /// listeners on elements nested inside the root are still sent separately.
+    CreateElementWithAttrs {
+        /// The "name" of the template. When paired with `rsx!`, this is autogenerated
+        name: &'static str,
+
+        /// Which root are we loading from the template?
+        index: usize,
+
+        /// The ID we're assigning to this element being loaded from the template
+        id: ElementId,
+
+        /// The tag of the element
+        tag: &'a str,
+
+        /// The attributes to set on the element
+        #[cfg_attr(feature = "serialize", serde(borrow))]
+        attrs: Vec<InitialAttribute<'a>>,
+
+        /// The names of the events to listen for on the element
+        #[cfg_attr(feature = "serialize", serde(borrow))]
+        listeners: Vec<&'a str>,
+    },
+
     /// Replace the target element (given by its ID) with the topmost m nodes on the stack
     ReplaceWith {
         /// The ID of the node we're going to replace with
@@ -164,8 +441,9 @@ pub enum Mutation<'a> {
         /// The path of the child of the topmost node on the stack
         ///
         /// A path of `[]` represents the topmost node. A path of `[0]` represents the first child.
-        /// `[0,1,2]` represents 1st child's 2nd child's 3rd child.
-        path: &'static [u8],
+        /// `[0,1,2]` represents 1st child's 2nd child's 3rd child. Most paths are packed into a single integer, see
+        /// [`TemplatePath`].
+        path: TemplatePath<'static>,
 
         /// The number of nodes on the stack to replace the target element with
         m: usize,
@@ -181,6 +459,9 @@ pub enum Mutation<'a> {
     },
 
     /// Insert a number of nodes before a given node.
+    ///
+    /// The diff prefers this over [`Mutation::InsertAfter`] when moving keyed nodes, anchoring them to the node that
+    /// follows them in the new list. The anchor is always a sibling that is already mounted.
     InsertBefore {
         /// The ID of the node to insert before.
         id: ElementId,
@@ -243,4 +524,166 @@ pub enum Mutation<'a> {
         /// The ID of the root node to push.
         id: ElementId,
     },
+
+    /// Read how far an element is scrolled and report it back with [`crate::VirtualDom::report_scroll`].
+    ///
+    /// This is sent right before the mutation that removes the element, while it is still attached to the document.
+    CaptureScroll {
+        /// The ID of the element to read the scroll position of.
+        id: ElementId,
+    },
+
+    /// Scroll an element back to a position it was at before it was removed.
+    ///
+    /// These are sent at the end of a batch of mutations, once the element is attached to the document.
+    RestoreScroll {
+        /// The ID of the element to scroll.
+        id: ElementId,
+
+        /// The horizontal scroll position.
+        x: f64,
+
+        /// The vertical scroll position.
+        y: f64,
+    },
+
+    /// Inject the style of a template into the document.
+    ///
+    /// This is sent once per template, before the template is first loaded. The style should only apply to elements
+    /// created from the template. Renderers can do this by marking each element of the template with a
+    /// `data-dioxus-scope` attribute set to the template's name, and wrapping the style in
+    /// `@scope ([data-dioxus-scope="name"]) { ... }`.
+    InjectStyle {
+        /// The name of the template the style belongs to
+        name: &'a str,
+
+        /// The CSS to inject
+        style: &'a str,
+    },
+
+    /// Pop the topmost m nodes from the stack and append them to an element outside of the VirtualDom.
+    ///
+    /// This is sent for a [`crate::Portal`] with a [`crate::PortalTarget::Anchor`]. Web renderers find the element by
+    /// its `id`.
+    AppendToAnchor {
+        /// The name of the element to append to.
+        anchor: &'a str,
+
+        /// The number of nodes to pop from the stack.
+        m: usize,
+    },
+
+    /// Attach a single listener for this event to the root of the document.
+    ///
+    /// Only sent when [`crate::Capabilities::delegated_events`] is enabled, in place of a
+    /// [`Mutation::NewEventListener`] for every element with a handler. This is sent when the first handler for the
+    /// event is created. The renderer should route each event to [`crate::VirtualDom::handle_event`] with the id of
+    /// the closest element the event fired on, and the VirtualDom finds the handlers from there.
+    RegisterDelegated {
+        /// The name of the event to listen for.
+        name: &'a str,
+    },
+
+    /// Remove the root listener for this event, once the last handler for it is gone.
+    UnregisterDelegated {
+        /// The name of the event to stop listening for.
+        name: &'a str,
+    },
+}
+
+/// An attribute set on an element as part of [`Mutation::CreateElementWithAttrs`]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, PartialEq)]
+pub struct InitialAttribute<'a> {
+    /// The name of the attribute to set.
+    pub name: &'a str,
+
+    /// The value of the attribute.
+    #[cfg_attr(feature = "serialize", serde(borrow))]
+    pub value: BorrowedAttributeValue<'a>,
+
+    /// The (optional) namespace of the attribute.
+    pub ns: Option<&'a str>,
+}
+
+impl<'a> Mutation<'a> {
+    /// Write this edit to the given renderer by calling the matching [`WriteMutations`] method
+    pub fn write_to(&self, to: &mut impl WriteMutations<'a>) {
+        match self {
+            Mutation::AppendChildren { id, m } => to.append_children(*id, *m),
+            Mutation::AssignId { path, id } => to.assign_id(*path, *id),
+            Mutation::CreatePlaceholder { id } => to.create_placeholder(*id),
+            Mutation::CreateTextNode { value, id } => to.create_text_node(value, *id),
+            Mutation::HydrateText { path, value, id } => to.hydrate_text(*path, value, *id),
+            Mutation::LoadTemplate { name, index, id } => to.load_template(name, *index, *id),
+            Mutation::CreateElementWithAttrs {
+                name,
+                index,
+                id,
+                tag,
+                attrs,
+                listeners,
+            } => to.create_element_with_attrs(name, *index, *id, tag, attrs, listeners),
+            Mutation::ReplaceWith { id, m } => to.replace_with(*id, *m),
+            Mutation::ReplacePlaceholder { path, m } => to.replace_placeholder(*path, *m),
+            Mutation::InsertAfter { id, m } => to.insert_after(*id, *m),
+            Mutation::InsertBefore { id, m } => to.insert_before(*id, *m),
+            Mutation::SetAttribute {
+                name,
+                value,
+                id,
+                ns,
+            } => to.set_attribute(name, *ns, value, *id),
+            Mutation::SetText { value, id } => to.set_text(value, *id),
+            Mutation::NewEventListener { name, id } => to.new_event_listener(name, *id),
+            Mutation::RemoveEventListener { name, id } => to.remove_event_listener(name, *id),
+            Mutation::Remove { id } => to.remove(*id),
+            Mutation::PushRoot { id } => to.push_root(*id),
+            Mutation::CaptureScroll { id } => to.capture_scroll(*id),
+            Mutation::RestoreScroll { id, x, y } => to.restore_scroll(*id, *x, *y),
+            Mutation::InjectStyle { name, style } => to.inject_style(name, style),
+            Mutation::AppendToAnchor { anchor, m } => to.append_to_anchor(anchor, *m),
+            Mutation::RegisterDelegated { name } => to.register_delegated(name),
+            Mutation::UnregisterDelegated { name } => to.unregister_delegated(name),
+        }
+    }
+
+    /// Can a renderer move this mutation past the reorderable mutations next to it when batching or coalescing edits?
+    ///
+    /// Only edits that change an element that already has an id are reorderable. Every mutation that works on the
+    /// stack, creates, moves or removes nodes, or assigns ids is not, so it splits the edits into runs and every id a
+    /// run refers to was assigned before the run started. Inside a run, two edits can only trade places if they
+    /// [commute](Mutation::commutes_with).
+    ///
+    /// Some edits that only change an element still keep their position because they have side effects outside the
+    /// tree:
+    /// - A `mounted` listener fires an event as soon as it is applied
+    /// - [`Mutation::CaptureScroll`] reads the document, so it needs to run before the element is removed
+    /// - [`Mutation::RestoreScroll`] scrolls the document, so it needs the edits before it to be applied
+    pub fn is_reorderable(&self) -> bool {
+        self.reorderable_target().is_some()
+    }
+
+    /// Does applying these two mutations in either order leave the renderer in the same state?
+    ///
+    /// Only reorderable mutations that change different elements commute. Two edits to the same element never do,
+    /// even if they touch different attributes, because attributes like `style` and its properties overwrite each
+    /// other.
+    pub fn commutes_with(&self, other: &Mutation) -> bool {
+        match (self.reorderable_target(), other.reorderable_target()) {
+            (Some(a), Some(b)) => a != b,
+            _ => false,
+        }
+    }
+
+    // The element a reorderable mutation changes
+    fn reorderable_target(&self) -> Option<ElementId> {
+        match self {
+            Mutation::SetAttribute { id, .. }
+            | Mutation::SetText { id, .. }
+            | Mutation::RemoveEventListener { id, .. } => Some(*id),
+            Mutation::NewEventListener { name, id } if *name != "mounted" => Some(*id),
+            _ => None,
+        }
+    }
 }
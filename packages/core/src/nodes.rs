@@ -1,14 +1,16 @@
 use crate::{
-    any_props::AnyProps, arena::ElementId, Element, Event, LazyNodes, ScopeId, ScopeState,
+    any_props::AnyProps, arena::ElementId, Element, Event, LazyNodes, PackedPath, ScopeId,
+    ScopeState,
 };
 use bumpalo::boxed::Box as BumpBox;
 use bumpalo::Bump;
 use std::{
     any::{Any, TypeId},
     cell::{Cell, RefCell},
-    fmt::{Arguments, Debug},
+    fmt::{Arguments, Debug, Display},
 };
 
+/// The name of a [`Template`], which is unique to the place in the source code it was created
 pub type TemplateId = &'static str;
 
 /// The actual state of the component's most recent computation
@@ -46,8 +48,11 @@ pub struct VNode<'a> {
     /// In fragments, this is the key of the first child. In other cases, it is the key of the root.
     pub key: Option<&'a str>,
 
-    /// When rendered, this template will be linked to its parent manually
-    pub parent: Option<ElementId>,
+    /// The element that events bubble to from the roots of this template.
+    ///
+    /// This is linked when the template is mounted: it is the closest element with an id around the dynamic node the
+    /// template is mounted in, or the parent of the template that node belongs to if it is a root.
+    pub parent: Cell<Option<ElementId>>,
 
     /// The static nodes and static descriptor of the template
     pub template: Cell<Template<'static>>,
@@ -68,16 +73,11 @@ impl<'a> VNode<'a> {
     pub fn empty(cx: &'a ScopeState) -> Element<'a> {
         Some(VNode {
             key: None,
-            parent: None,
+            parent: Default::default(),
             root_ids: RefCell::new(bumpalo::collections::Vec::new_in(cx.bump())),
             dynamic_nodes: &[],
             dynamic_attrs: &[],
-            template: Cell::new(Template {
-                name: "dioxus-empty",
-                roots: &[],
-                node_paths: &[],
-                attr_paths: &[],
-            }),
+            template: Cell::new(Template::EMPTY),
         })
     }
 
@@ -120,25 +120,43 @@ pub struct Template<'a> {
     #[cfg_attr(feature = "serialize", serde(deserialize_with = "deserialize_leaky"))]
     pub roots: &'a [TemplateNode<'a>],
 
-    /// The paths of each node relative to the root of the template.
+    /// The paths of each dynamic node relative to the root of the template, packed into a single integer.
+    ///
+    /// These will be one segment longer than the path sent to the renderer since those paths are relative to the
+    /// topmost element, not the `roots` field. Paths that are too deep or too wide to be packed are
+    /// [`PackedPath::unpacked`] and are read from [`Template::unpacked_paths`] instead, so read them through
+    /// [`Template::node_path`].
+    ///
+    /// This used to be a list of slices. Templates built by hand can pack their paths with [`PackedPath::new`].
+    #[cfg_attr(feature = "serialize", serde(deserialize_with = "deserialize_leaky"))]
+    pub node_paths: &'a [PackedPath],
+
+    /// The paths of each dynamic attribute relative to the root of the template, packed into a single integer.
+    ///
+    /// These follow the same rules as [`Template::node_paths`], so read them through [`Template::attr_path`].
+    #[cfg_attr(feature = "serialize", serde(deserialize_with = "deserialize_leaky"))]
+    pub attr_paths: &'a [PackedPath],
+
+    /// The node and attribute paths that were too deep or too wide to be packed, as slices of child indices.
     ///
-    /// These will be one segment shorter than the path sent to the renderer since those paths are relative to the
-    /// topmost element, not the `roots` field.
+    /// This is empty for almost every template. Each entry is only referenced by a [`PackedPath::unpacked`] in
+    /// [`Template::node_paths`] or [`Template::attr_paths`].
     #[cfg_attr(
         feature = "serialize",
-        serde(deserialize_with = "deserialize_bytes_leaky")
+        serde(default, deserialize_with = "deserialize_bytes_leaky")
     )]
-    pub node_paths: &'a [&'a [u8]],
+    pub unpacked_paths: &'a [&'a [u8]],
 
-    /// The paths of each dynamic attribute relative to the root of the template
+    /// A block of CSS that only applies to the elements created from this template
     ///
-    /// These will be one segment shorter than the path sent to the renderer since those paths are relative to the
-    /// topmost element, not the `roots` field.
+    /// The renderer receives the style through [`crate::Mutation::InjectStyle`] the first time the template is
+    /// mounted, no matter how many instances of the template exist. Styles are only injected when the `scoped-styles`
+    /// feature is enabled, and are `None` in templates built without it.
     #[cfg_attr(
         feature = "serialize",
-        serde(deserialize_with = "deserialize_bytes_leaky")
+        serde(default, deserialize_with = "deserialize_option_string_leaky")
     )]
-    pub attr_paths: &'a [&'a [u8]],
+    pub style: Option<&'a str>,
 }
 
 #[cfg(feature = "serialize")]
@@ -152,6 +170,17 @@ where
     Ok(&*Box::leak(deserialized.into_boxed_str()))
 }
 
+#[cfg(feature = "serialize")]
+fn deserialize_option_string_leaky<'a, 'de, D>(deserializer: D) -> Result<Option<&'a str>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    use serde::Deserialize;
+
+    let deserialized = Option::<String>::deserialize(deserializer)?;
+    Ok(deserialized.map(|deserialized| &*Box::leak(deserialized.into_boxed_str())))
+}
+
 #[cfg(feature = "serialize")]
 fn deserialize_bytes_leaky<'a, 'de, D>(deserializer: D) -> Result<&'a [&'a [u8]], D::Error>
 where
@@ -168,7 +197,7 @@ where
 }
 
 #[cfg(feature = "serialize")]
-fn deserialize_leaky<'a, 'de, T: serde::Deserialize<'de>, D>(
+pub(crate) fn deserialize_leaky<'a, 'de, T: serde::Deserialize<'de>, D>(
     deserializer: D,
 ) -> Result<&'a [T], D::Error>
 where
@@ -193,6 +222,19 @@ where
 }
 
 impl<'a> Template<'a> {
+    /// A template without any nodes. This is skipped over during diffing.
+    ///
+    /// Code that builds templates can use this as the base of struct update syntax to fill in any fields that are
+    /// behind optional features.
+    pub const EMPTY: Template<'static> = Template {
+        name: "dioxus-empty",
+        roots: &[],
+        node_paths: &[],
+        attr_paths: &[],
+        unpacked_paths: &[],
+        style: None,
+    };
+
     /// Is this template worth caching at all, since it's completely runtime?
     ///
     /// There's no point in saving templates that are completely dynamic, since they'll be recreated every time anyway.
@@ -284,7 +326,11 @@ pub enum DynamicNode<'a> {
 
     /// A placeholder
     ///
-    /// Used by suspense when a node isn't ready and by fragments that don't render anything
+    /// Used by suspense when a node isn't ready and by fragments that don't render anything. It is what `None` and empty
+    /// iterators render, so the slot can hold real content again later.
+    ///
+    /// When the slot changes between a placeholder and any other node, the old node is replaced in a single
+    /// [`crate::Mutation::ReplaceWith`].
     ///
     /// In code, this is just an ElementId whose initial value is set to 0 upon creation
     Placeholder(VPlaceholder),
@@ -412,6 +458,24 @@ pub enum TemplateAttribute<'a> {
         /// The index
         id: usize,
     },
+
+    /// A static attribute that is only applied when a runtime flag is enabled
+    ///
+    /// The VirtualDom turns this into a static attribute or drops it the first time the template is mounted, so
+    /// renderers never see it. Flags are looked up with the provider from [`crate::VirtualDom::with_flag_provider`].
+    Conditional {
+        /// The name of this attribute.
+        name: &'a str,
+
+        /// The value of this attribute, known at compile time
+        value: &'a str,
+
+        /// The namespace of this attribute. Does not exist in the HTML spec
+        namespace: Option<&'a str>,
+
+        /// The flag that decides if this attribute is applied
+        flag: &'a str,
+    },
 }
 
 /// An attribute on a DOM node, such as `id="my-thing"` or `href="https://example.com"`
@@ -429,6 +493,9 @@ pub struct Attribute<'a> {
     pub namespace: Option<&'static str>,
 
     /// An indication of we should always try and set the attribute. Used in controlled components to ensure changes are propagated
+    ///
+    /// Volatile attributes are rewritten in the same place a changed attribute would be, so they keep their source
+    /// order relative to the other attributes of the element.
     pub volatile: bool,
 
     /// The element in the DOM that this attribute belongs to
@@ -470,21 +537,249 @@ pub enum AttributeValue<'a> {
     Float(f64),
 
     /// Signed integer
+    ///
+    /// `u64` and `usize` values above `i64::MAX` are converted to [`AttributeValue::Text`] instead, so they keep every
+    /// digit.
     Int(i64),
 
     /// Boolean
     Bool(bool),
 
+    /// Raw binary data, like a vertex buffer or image data for a custom renderer
+    ///
+    /// The bytes are passed through to the renderer untouched. Renderers that can only write text, like the web and
+    /// ssr renderers, skip binary attributes.
+    Bytes(&'a [u8]),
+
     /// A listener, like "onclick"
+    ///
+    /// Listeners always compare equal, so a listener is attached once when its element is created and never detached
+    /// or re-attached while the element is mounted. Events are dispatched to the closure from the latest render, so
+    /// a new closure takes over the existing listener without any mutations.
     Listener(RefCell<Option<ListenerCb<'a>>>),
 
     /// An arbitrary value that implements PartialEq and is static
     Any(RefCell<Option<BumpBox<'a, dyn AnyValue>>>),
 
+    /// Static text with named slots that are filled in at runtime, like `"translate({x}px, {y}px)"`
+    Template(AttributeTemplate<'a>),
+
+    /// A value the renderer computes from the other attributes on the element
+    Derived(DerivedAttribute),
+
+    /// A map of style properties to their values, like `[("color", "red"), ("margin", "4px")]`, created from a
+    /// [`StyleMap`]
+    ///
+    /// The renderer never sees the map itself. Each property is sent as its own attribute in the `style` namespace,
+    /// and when the map changes, only the properties that were added, changed, or removed are sent again.
+    StyleMap(&'a [(&'a str, &'a str)]),
+
+    /// Attributes spread onto an element with `..attributes` in rsx, as name and value pairs
+    ///
+    /// Like a style map, the renderer never sees the list itself. Each entry is sent as its own attribute, and when the
+    /// list changes, only the attributes that were added, changed, or removed are sent again.
+    Spread(&'a [(&'a str, &'a str)]),
+
+    /// A reference to another element by the key of its node, for attributes like `for`, `aria-controls` and
+    /// `aria-owns`
+    ///
+    /// The renderer receives the [`ElementId`] of the first element of the mounted node with that key, as an integer.
+    /// The reference is resolved at the end of each batch of mutations, so the referenced node can be mounted after
+    /// the element that references it. If the referenced element is remounted, the attribute follows it, and if no
+    /// node with the key is mounted, the attribute is removed.
+    ElementRefByKey(&'a str),
+
     /// A "none" value, resulting in the removal of an attribute from the dom
     None,
 }
 
+/// An attribute whose value is computed by the renderer from the other attributes on the same element, like an
+/// `aria-valuetext` that describes the current `value` of a slider.
+///
+/// The VirtualDom only sends a derived attribute when it is first set or its function changes. The renderer
+/// re-evaluates the function whenever another attribute on the element changes, so the derived value stays up to date
+/// without any extra mutations. Returning `None` removes the attribute.
+///
+/// The function runs in the renderer, so only renderers written in Rust can evaluate it. Like
+/// [`AttributeValue::Any`], derived attributes cannot be serialized.
+///
+/// ```rust, ignore
+/// let valuetext = DerivedAttribute::new(|attrs| Some(format!("{} percent", attrs.get("value")?)));
+///
+/// cx.render(rsx! {
+///     input { r#type: "range", value: "{value}", "aria-valuetext": valuetext }
+/// })
+/// ```
+#[derive(Clone, Copy)]
+pub struct DerivedAttribute {
+    compute: fn(&DerivedInputs) -> Option<String>,
+}
+
+impl DerivedAttribute {
+    /// Create a derived attribute from a function of the other attributes on the element
+    pub fn new(compute: fn(&DerivedInputs) -> Option<String>) -> Self {
+        Self { compute }
+    }
+
+    /// Compute the value of the attribute from the other attributes on the element
+    pub fn compute(&self, inputs: &DerivedInputs) -> Option<String> {
+        (self.compute)(inputs)
+    }
+}
+
+impl Debug for DerivedAttribute {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_tuple("DerivedAttribute").finish()
+    }
+}
+
+impl PartialEq for DerivedAttribute {
+    fn eq(&self, other: &Self) -> bool {
+        // The same function may have different addresses in different codegen units. That only causes a redundant
+        // mutation, never a missed one.
+        self.compute as usize == other.compute as usize
+    }
+}
+
+/// The current attributes of the element a [`DerivedAttribute`] is being computed for
+pub struct DerivedInputs<'a> {
+    lookup: &'a dyn Fn(&str) -> Option<String>,
+}
+
+impl<'a> DerivedInputs<'a> {
+    /// Create the inputs for a derived attribute from a function that looks up the current value of an attribute on
+    /// the element
+    pub fn new(lookup: &'a dyn Fn(&str) -> Option<String>) -> Self {
+        Self { lookup }
+    }
+
+    /// Get the current value of an attribute on the element
+    pub fn get(&self, name: &str) -> Option<String> {
+        (self.lookup)(name)
+    }
+}
+
+/// Static text with named slots that are filled in at runtime, like `"transform: translate({x}px, {y}px)"`
+///
+/// The template is resolved into its full text when it is created. Two templates are equal if their resolved text is
+/// equal, so changing any of the slots updates the attribute with a single [`crate::Mutation::SetAttribute`].
+///
+/// Use `{{` and `}}` for literal braces.
+#[derive(Clone, Copy)]
+pub struct AttributeTemplate<'a> {
+    segments: &'a [AttributeSegment<'a>],
+    resolved: &'a str,
+}
+
+/// A piece of an [`AttributeTemplate`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AttributeSegment<'a> {
+    /// Text that is the same for every instance of the template
+    Static(&'a str),
+
+    /// A named slot and the value it was filled in with
+    Slot {
+        /// The name of the slot
+        name: &'a str,
+
+        /// The value of the slot
+        value: &'a str,
+    },
+}
+
+impl<'a> AttributeTemplate<'a> {
+    /// Parse a template and fill in its slots with the given values.
+    ///
+    /// Slots without a value are left empty.
+    pub fn new(bump: &'a Bump, template: &'a str, slots: &[(&str, &dyn Display)]) -> Self {
+        use bumpalo::core_alloc::fmt::Write;
+
+        let mut segments = bumpalo::collections::Vec::new_in(bump);
+        let mut resolved = bumpalo::collections::String::new_in(bump);
+        let mut rest = template;
+
+        while !rest.is_empty() {
+            if let Some(after) = rest.strip_prefix("{{") {
+                segments.push(AttributeSegment::Static("{"));
+                resolved.push('{');
+                rest = after;
+            } else if let Some(after) = rest.strip_prefix("}}") {
+                segments.push(AttributeSegment::Static("}"));
+                resolved.push('}');
+                rest = after;
+            } else if let Some((name, after)) =
+                rest.strip_prefix('{').and_then(|slot| slot.split_once('}'))
+            {
+                let start = resolved.len();
+                match slots.iter().find(|(slot, _)| *slot == name) {
+                    Some((_, value)) => write!(resolved, "{}", value).unwrap(),
+                    None => tracing::warn!(
+                        "No value provided for the slot {{{}}} in {:?}",
+                        name,
+                        template
+                    ),
+                }
+                let value = bump.alloc_str(&resolved[start..]);
+                segments.push(AttributeSegment::Slot { name, value });
+                rest = after;
+            } else {
+                // Copy everything up to the next brace that could start a slot or an escape. The first character is
+                // always taken so unmatched braces are kept as text
+                let first = rest.chars().next().map_or(0, char::len_utf8);
+                let end = rest[first..]
+                    .find(['{', '}'])
+                    .map_or(rest.len(), |idx| idx + first);
+                segments.push(AttributeSegment::Static(&rest[..end]));
+                resolved.push_str(&rest[..end]);
+                rest = &rest[end..];
+            }
+        }
+
+        Self {
+            segments: segments.into_bump_slice(),
+            resolved: resolved.into_bump_str(),
+        }
+    }
+
+    /// The pieces of the template with the values of its slots
+    pub fn segments(&self) -> &'a [AttributeSegment<'a>] {
+        self.segments
+    }
+
+    /// Get the value a slot was filled in with
+    pub fn slot(&self, name: &str) -> Option<&'a str> {
+        self.segments.iter().find_map(|segment| match segment {
+            AttributeSegment::Slot { name: slot, value } if *slot == name => Some(*value),
+            _ => None,
+        })
+    }
+
+    /// The full text of the template with all of its slots filled in
+    pub fn resolved(&self) -> &'a str {
+        self.resolved
+    }
+}
+
+impl Display for AttributeTemplate<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.resolved)
+    }
+}
+
+impl Debug for AttributeTemplate<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_tuple("AttributeTemplate")
+            .field(&self.resolved)
+            .finish()
+    }
+}
+
+impl PartialEq for AttributeTemplate<'_> {
+    fn eq(&self, other: &Self) -> bool {
+        self.resolved == other.resolved
+    }
+}
+
 pub type ListenerCb<'a> = BumpBox<'a, dyn FnMut(Event<dyn Any>) + 'a>;
 
 /// Any of the built-in values that the Dioxus VirtualDom supports as dynamic attributes on elements that are borrowed
@@ -496,15 +791,20 @@ pub enum BorrowedAttributeValue<'a> {
     /// Text attribute
     Text(&'a str),
 
-    /// A float
-    Float(f64),
-
+    // Variants are tried in order when deserializing, so integers come before floats to keep whole numbers an `Int`
     /// Signed integer
     Int(i64),
 
+    /// A float
+    Float(f64),
+
     /// Boolean
     Bool(bool),
 
+    /// Raw binary data
+    #[cfg_attr(feature = "serialize", serde(deserialize_with = "deserialize_leaky"))]
+    Bytes(&'a [u8]),
+
     /// An arbitrary value that implements PartialEq and is static
     #[cfg_attr(
         feature = "serialize",
@@ -515,10 +815,22 @@ pub enum BorrowedAttributeValue<'a> {
     )]
     Any(std::cell::Ref<'a, dyn AnyValue>),
 
+    /// A value the renderer computes from the other attributes on the element
+    #[cfg_attr(
+        feature = "serialize",
+        serde(
+            deserialize_with = "deserialize_derived_value",
+            serialize_with = "serialize_derived_value"
+        )
+    )]
+    Derived(DerivedAttribute),
+
     /// A "none" value, resulting in the removal of an attribute from the dom
     None,
 }
 
+/// Values the renderer never receives as a whole, like listeners, style maps and spread attributes, become
+/// [`BorrowedAttributeValue::None`]. The VirtualDom sends them in their own mutations instead.
 impl<'a> From<&'a AttributeValue<'a>> for BorrowedAttributeValue<'a> {
     fn from(value: &'a AttributeValue<'a>) -> Self {
         match value {
@@ -526,9 +838,13 @@ impl<'a> From<&'a AttributeValue<'a>> for BorrowedAttributeValue<'a> {
             AttributeValue::Float(value) => BorrowedAttributeValue::Float(*value),
             AttributeValue::Int(value) => BorrowedAttributeValue::Int(*value),
             AttributeValue::Bool(value) => BorrowedAttributeValue::Bool(*value),
-            AttributeValue::Listener(_) => {
-                panic!("A listener cannot be turned into a borrowed value")
-            }
+            AttributeValue::Bytes(value) => BorrowedAttributeValue::Bytes(value),
+            AttributeValue::Template(value) => BorrowedAttributeValue::Text(value.resolved),
+            AttributeValue::Derived(value) => BorrowedAttributeValue::Derived(*value),
+            AttributeValue::Listener(_)
+            | AttributeValue::StyleMap(_)
+            | AttributeValue::Spread(_)
+            | AttributeValue::ElementRefByKey(_) => BorrowedAttributeValue::None,
             AttributeValue::Any(value) => {
                 let value = value.borrow();
                 BorrowedAttributeValue::Any(std::cell::Ref::map(value, |value| {
@@ -547,12 +863,29 @@ impl Debug for BorrowedAttributeValue<'_> {
             Self::Float(arg0) => f.debug_tuple("Float").field(arg0).finish(),
             Self::Int(arg0) => f.debug_tuple("Int").field(arg0).finish(),
             Self::Bool(arg0) => f.debug_tuple("Bool").field(arg0).finish(),
+            Self::Bytes(arg0) => f.debug_tuple("Bytes").field(arg0).finish(),
             Self::Any(_) => f.debug_tuple("Any").field(&"...").finish(),
+            Self::Derived(arg0) => arg0.fmt(f),
             Self::None => write!(f, "None"),
         }
     }
 }
 
+impl Clone for BorrowedAttributeValue<'_> {
+    fn clone(&self) -> Self {
+        match self {
+            Self::Text(value) => Self::Text(value),
+            Self::Float(value) => Self::Float(*value),
+            Self::Int(value) => Self::Int(*value),
+            Self::Bool(value) => Self::Bool(*value),
+            Self::Bytes(value) => Self::Bytes(value),
+            Self::Any(value) => Self::Any(std::cell::Ref::clone(value)),
+            Self::Derived(value) => Self::Derived(*value),
+            Self::None => Self::None,
+        }
+    }
+}
+
 impl PartialEq for BorrowedAttributeValue<'_> {
     fn eq(&self, other: &Self) -> bool {
         match (self, other) {
@@ -560,7 +893,9 @@ impl PartialEq for BorrowedAttributeValue<'_> {
             (Self::Float(l0), Self::Float(r0)) => l0 == r0,
             (Self::Int(l0), Self::Int(r0)) => l0 == r0,
             (Self::Bool(l0), Self::Bool(r0)) => l0 == r0,
+            (Self::Bytes(l0), Self::Bytes(r0)) => l0 == r0,
             (Self::Any(l0), Self::Any(r0)) => l0.any_cmp(&**r0),
+            (Self::Derived(l0), Self::Derived(r0)) => l0 == r0,
             _ => core::mem::discriminant(self) == core::mem::discriminant(other),
         }
     }
@@ -579,7 +914,26 @@ fn deserialize_any_value<'de, 'a, D>(_: D) -> Result<std::cell::Ref<'a, dyn AnyV
 where
     D: serde::Deserializer<'de>,
 {
-    panic!("Any cannot be deserialized")
+    // An error instead of a panic lets the untagged enum move on to the next variant
+    Err(serde::de::Error::custom("Any cannot be deserialized"))
+}
+
+#[cfg(feature = "serialize")]
+fn serialize_derived_value<S>(_: &DerivedAttribute, _: S) -> Result<S::Ok, S::Error>
+where
+    S: serde::Serializer,
+{
+    panic!("Derived attributes cannot be serialized")
+}
+
+#[cfg(feature = "serialize")]
+fn deserialize_derived_value<'de, D>(_: D) -> Result<DerivedAttribute, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    Err(serde::de::Error::custom(
+        "Derived attributes cannot be deserialized",
+    ))
 }
 
 impl<'a> std::fmt::Debug for AttributeValue<'a> {
@@ -589,8 +943,14 @@ impl<'a> std::fmt::Debug for AttributeValue<'a> {
             Self::Float(arg0) => f.debug_tuple("Float").field(arg0).finish(),
             Self::Int(arg0) => f.debug_tuple("Int").field(arg0).finish(),
             Self::Bool(arg0) => f.debug_tuple("Bool").field(arg0).finish(),
+            Self::Bytes(arg0) => f.debug_tuple("Bytes").field(arg0).finish(),
             Self::Listener(_) => f.debug_tuple("Listener").finish(),
             Self::Any(_) => f.debug_tuple("Any").finish(),
+            Self::Template(arg0) => f.debug_tuple("Template").field(arg0).finish(),
+            Self::Derived(arg0) => arg0.fmt(f),
+            Self::StyleMap(arg0) => f.debug_tuple("StyleMap").field(arg0).finish(),
+            Self::Spread(arg0) => f.debug_tuple("Spread").field(arg0).finish(),
+            Self::ElementRefByKey(arg0) => f.debug_tuple("ElementRefByKey").field(arg0).finish(),
             Self::None => write!(f, "None"),
         }
     }
@@ -603,7 +963,13 @@ impl<'a> PartialEq for AttributeValue<'a> {
             (Self::Float(l0), Self::Float(r0)) => l0 == r0,
             (Self::Int(l0), Self::Int(r0)) => l0 == r0,
             (Self::Bool(l0), Self::Bool(r0)) => l0 == r0,
+            (Self::Bytes(l0), Self::Bytes(r0)) => l0 == r0,
             (Self::Listener(_), Self::Listener(_)) => true,
+            (Self::Template(l0), Self::Template(r0)) => l0 == r0,
+            (Self::Derived(l0), Self::Derived(r0)) => l0 == r0,
+            (Self::StyleMap(l0), Self::StyleMap(r0)) => l0 == r0,
+            (Self::Spread(l0), Self::Spread(r0)) => l0 == r0,
+            (Self::ElementRefByKey(l0), Self::ElementRefByKey(r0)) => l0 == r0,
             (Self::Any(l0), Self::Any(r0)) => {
                 let l0 = l0.borrow();
                 let r0 = r0.borrow();
@@ -614,15 +980,65 @@ impl<'a> PartialEq for AttributeValue<'a> {
     }
 }
 
-#[doc(hidden)]
+impl AttributeValue<'_> {
+    /// Check if two values point to the same allocation, like a `&'static str` that is reused every render.
+    ///
+    /// Values that point to the same allocation are always equal, so the diff checks this first and skips comparing
+    /// them. Values that hold their data inline are never the same allocation unless they are the same value.
+    pub fn ptr_eq(&self, other: &Self) -> bool {
+        fn same_str(left: &str, right: &str) -> bool {
+            left.as_ptr() == right.as_ptr() && left.len() == right.len()
+        }
+
+        match (self, other) {
+            (Self::Text(l0), Self::Text(r0)) => same_str(l0, r0),
+            (Self::ElementRefByKey(l0), Self::ElementRefByKey(r0)) => same_str(l0, r0),
+            (Self::Template(l0), Self::Template(r0)) => same_str(l0.resolved, r0.resolved),
+            (Self::StyleMap(l0), Self::StyleMap(r0)) | (Self::Spread(l0), Self::Spread(r0)) => {
+                l0.as_ptr() == r0.as_ptr() && l0.len() == r0.len()
+            }
+            _ => std::ptr::eq(self, other),
+        }
+    }
+}
+
+/// A value of any type in an [`AttributeValue::Any`] attribute, for renderers that take more than text and numbers
+///
+/// It is implemented for every `PartialEq` type. A custom renderer can get the value back with
+/// [`downcast_ref`](#method.downcast_ref):
+///
+/// ```rust, ignore
+/// if let BorrowedAttributeValue::Any(value) = value {
+///     if let Some(color) = value.downcast_ref::<Color>() {
+///         node.set_background(*color);
+///     }
+/// }
+/// ```
 pub trait AnyValue: 'static {
+    /// Check if the other value has the same type and is equal to this one
     fn any_cmp(&self, other: &dyn AnyValue) -> bool;
+
+    /// Get the value as [`Any`]
     fn as_any(&self) -> &dyn Any;
+
+    /// The [`TypeId`] of the value
     fn type_id(&self) -> TypeId {
         self.as_any().type_id()
     }
 }
 
+impl dyn AnyValue {
+    /// Get the value if it is a `T`
+    pub fn downcast_ref<T: Any>(&self) -> Option<&T> {
+        self.as_any().downcast_ref()
+    }
+
+    /// Check if the value is a `T`
+    pub fn is<T: Any>(&self) -> bool {
+        self.as_any().is::<T>()
+    }
+}
+
 impl<T: Any + PartialEq + 'static> AnyValue for T {
     fn any_cmp(&self, other: &dyn AnyValue) -> bool {
         if let Some(other) = other.as_any().downcast_ref() {
@@ -722,7 +1138,7 @@ impl<'b> IntoDynNode<'b> for Arguments<'_> {
 impl<'a> IntoDynNode<'a> for &'a VNode<'a> {
     fn into_vnode(self, _cx: &'a ScopeState) -> DynamicNode<'a> {
         DynamicNode::Fragment(_cx.bump().alloc([VNode {
-            parent: self.parent,
+            parent: self.parent.clone(),
             template: self.template.clone(),
             root_ids: self.root_ids.clone(),
             key: self.key,
@@ -791,6 +1207,30 @@ impl<'a> IntoAttributeValue<'a> for &'a str {
     }
 }
 
+impl<'a> IntoAttributeValue<'a> for String {
+    fn into_value(self, bump: &'a Bump) -> AttributeValue<'a> {
+        AttributeValue::Text(bump.alloc_str(&self))
+    }
+}
+
+impl<'a> IntoAttributeValue<'a> for &'a String {
+    fn into_value(self, _: &'a Bump) -> AttributeValue<'a> {
+        AttributeValue::Text(self)
+    }
+}
+
+impl<'a> IntoAttributeValue<'a> for &'a [u8] {
+    fn into_value(self, _: &'a Bump) -> AttributeValue<'a> {
+        AttributeValue::Bytes(self)
+    }
+}
+
+impl<'a> IntoAttributeValue<'a> for Vec<u8> {
+    fn into_value(self, bump: &'a Bump) -> AttributeValue<'a> {
+        AttributeValue::Bytes(bump.alloc_slice_copy(&self))
+    }
+}
+
 impl<'a> IntoAttributeValue<'a> for f64 {
     fn into_value(self, _: &'a Bump) -> AttributeValue<'a> {
         AttributeValue::Float(self)
@@ -803,6 +1243,41 @@ impl<'a> IntoAttributeValue<'a> for i64 {
     }
 }
 
+// Smaller numbers are widened without losing precision
+macro_rules! widen_into_attribute_value {
+    ($variant:ident($wide:ty): $($ty:ty),*) => {
+        $(
+            impl<'a> IntoAttributeValue<'a> for $ty {
+                fn into_value(self, _: &'a Bump) -> AttributeValue<'a> {
+                    AttributeValue::$variant(<$wide>::from(self))
+                }
+            }
+        )*
+    };
+}
+
+widen_into_attribute_value!(Int(i64): i8, i16, i32, u8, u16, u32);
+widen_into_attribute_value!(Float(f64): f32);
+
+// Unsigned numbers above `i64::MAX` don't fit in an `Int`, so they are sent as `Text` instead. Text keeps every digit,
+// where a `Float` would round anything above 2^53.
+macro_rules! checked_into_attribute_value {
+    ($($ty:ty),*) => {
+        $(
+            impl<'a> IntoAttributeValue<'a> for $ty {
+                fn into_value(self, bump: &'a Bump) -> AttributeValue<'a> {
+                    match <i64 as std::convert::TryFrom<$ty>>::try_from(self) {
+                        Ok(int) => AttributeValue::Int(int),
+                        Err(_) => format_args!("{}", self).into_value(bump),
+                    }
+                }
+            }
+        )*
+    };
+}
+
+checked_into_attribute_value!(u64, usize);
+
 impl<'a> IntoAttributeValue<'a> for bool {
     fn into_value(self, _: &'a Bump) -> AttributeValue<'a> {
         AttributeValue::Bool(self)
@@ -818,12 +1293,50 @@ impl<'a> IntoAttributeValue<'a> for Arguments<'_> {
     }
 }
 
+impl<'a> IntoAttributeValue<'a> for AttributeTemplate<'a> {
+    fn into_value(self, _: &'a Bump) -> AttributeValue<'a> {
+        AttributeValue::Template(self)
+    }
+}
+
+impl<'a> IntoAttributeValue<'a> for DerivedAttribute {
+    fn into_value(self, _: &'a Bump) -> AttributeValue<'a> {
+        AttributeValue::Derived(self)
+    }
+}
+
+/// Style properties that are diffed one by one instead of as a single style string
+///
+/// Only pairs wrapped in a `StyleMap` become an [`AttributeValue::StyleMap`], so a list of pairs given to any other
+/// attribute is never mistaken for styles.
+///
+/// ```rust, ignore
+/// rsx! {
+///     div { style: StyleMap([("color", color), ("margin", "4px")]) }
+/// }
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct StyleMap<T>(pub T);
+
+impl<'a> IntoAttributeValue<'a> for StyleMap<&'a [(&'a str, &'a str)]> {
+    fn into_value(self, _: &'a Bump) -> AttributeValue<'a> {
+        AttributeValue::StyleMap(self.0)
+    }
+}
+
+impl<'a, const N: usize> IntoAttributeValue<'a> for StyleMap<[(&'a str, &'a str); N]> {
+    fn into_value(self, bump: &'a Bump) -> AttributeValue<'a> {
+        AttributeValue::StyleMap(bump.alloc(self.0))
+    }
+}
+
 impl<'a> IntoAttributeValue<'a> for BumpBox<'a, dyn AnyValue> {
     fn into_value(self, _: &'a Bump) -> AttributeValue<'a> {
         AttributeValue::Any(RefCell::new(Some(self)))
     }
 }
 
+// An attribute set to `None` is left off the element, or removed from it if it was set before
 impl<'a, T: IntoAttributeValue<'a>> IntoAttributeValue<'a> for Option<T> {
     fn into_value(self, bump: &'a Bump) -> AttributeValue<'a> {
         match self {
@@ -832,3 +1345,13 @@ impl<'a, T: IntoAttributeValue<'a>> IntoAttributeValue<'a> for Option<T> {
         }
     }
 }
+
+// Lets optional props be passed straight through, like `class: &cx.props.class`
+impl<'a, T> IntoAttributeValue<'a> for &'a Option<T>
+where
+    &'a T: IntoAttributeValue<'a>,
+{
+    fn into_value(self, bump: &'a Bump) -> AttributeValue<'a> {
+        self.as_ref().into_value(bump)
+    }
+}
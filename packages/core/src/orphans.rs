@@ -0,0 +1,59 @@
+use crate::{
+    walk::{TreeVisitor, VisitedAttribute},
+    ElementId, RenderReturn, VirtualDom,
+};
+use rustc_hash::FxHashSet;
+
+impl VirtualDom {
+    /// Find any [`ElementId`]s that are still allocated but no longer referenced by the mounted tree.
+    ///
+    /// Every element id the diff hands out should be freed when the node it belongs to is removed. An id that is
+    /// allocated but unreachable from the root is leaked, which points to a bug in the diff. In debug builds, this check
+    /// runs after every batch of mutations and logs any orphans it finds.
+    pub fn orphaned_elements(&self) -> Vec<ElementId> {
+        let mut collector = ElementIdCollector::default();
+
+        // The root is the container the app is mounted into, so it is always live
+        collector.ids.insert(ElementId(0));
+
+        if let Some(root) = self.base_scope().try_root_node() {
+            match root {
+                RenderReturn::Ready(node) => self.visit_node(node, &mut collector),
+                RenderReturn::Aborted(placeholder) => {
+                    collector.placeholder(placeholder.mounted_element())
+                }
+            }
+        }
+
+        self.elements
+            .iter()
+            .map(|(id, _)| ElementId(id))
+            .filter(|id| !collector.ids.contains(id))
+            .collect()
+    }
+}
+
+#[derive(Default)]
+struct ElementIdCollector {
+    ids: FxHashSet<ElementId>,
+}
+
+impl<'b> TreeVisitor<'b> for ElementIdCollector {
+    fn enter_element(
+        &mut self,
+        _tag: &'b str,
+        _namespace: Option<&'b str>,
+        id: Option<ElementId>,
+        _attrs: &[VisitedAttribute<'b>],
+    ) {
+        self.ids.extend(id);
+    }
+
+    fn text(&mut self, _value: &'b str, id: Option<ElementId>) {
+        self.ids.extend(id);
+    }
+
+    fn placeholder(&mut self, id: Option<ElementId>) {
+        self.ids.extend(id);
+    }
+}
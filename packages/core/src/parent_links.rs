@@ -0,0 +1,38 @@
+use crate::{innerlude::VNode, ElementId, VirtualDom};
+
+impl VirtualDom {
+    /// Find the element that nodes mounted in a dynamic node of a template bubble events to.
+    ///
+    /// This is the closest element around the dynamic node that has an id, which is the root it is under or a deeper
+    /// element with a dynamic attribute. Every element with a listener has an id, so events can bubble through all of
+    /// them from there. Dynamic nodes that are roots bubble to the parent of their template.
+    pub(crate) fn slot_parent(&self, template: &VNode, idx: usize) -> Option<ElementId> {
+        let layout = template.template.get();
+        let path = layout.node_path(idx)?;
+        if path.len() < 2 {
+            return template.parent.get();
+        }
+
+        let root = template.root_ids.borrow()[path.first()? as usize];
+        let closest = layout
+            .attr_path_iter()
+            .zip(template.dynamic_attrs)
+            .filter(|(attr_path, _)| attr_path.len() < path.len() && path.starts_with(attr_path))
+            .max_by_key(|(attr_path, _)| attr_path.len())
+            .map(|(_, attr)| attr.mounted_element.get());
+
+        Some(closest.unwrap_or(root))
+    }
+
+    /// Mount every node created by `f` under a parent element
+    pub(crate) fn with_mount_parent<R>(
+        &mut self,
+        parent: Option<ElementId>,
+        f: impl FnOnce(&mut Self) -> R,
+    ) -> R {
+        let outer = std::mem::replace(&mut self.mount_parent, parent);
+        let result = f(self);
+        self.mount_parent = outer;
+        result
+    }
+}
@@ -0,0 +1,147 @@
+use std::cell::{Cell, RefCell};
+
+use crate::{
+    innerlude::{DynamicNode, PackedPath, Properties, Scope, TemplateNode, VNode},
+    Element, ElementId, ScopeId, Template, VirtualDom, WriteMutations,
+};
+
+/// Where a [`Portal`] mounts its children
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PortalTarget {
+    /// An element rendered by the VirtualDom, like one found with an element ref
+    Element(ElementId),
+
+    /// An element outside of the VirtualDom, found by the renderer by its name. Web renderers look it up by its `id`.
+    Anchor(&'static str),
+}
+
+impl From<ElementId> for PortalTarget {
+    fn from(id: ElementId) -> Self {
+        PortalTarget::Element(id)
+    }
+}
+
+impl From<&'static str> for PortalTarget {
+    fn from(anchor: &'static str) -> Self {
+        PortalTarget::Anchor(anchor)
+    }
+}
+
+static PORTAL_TEMPLATE: Template<'static> = Template {
+    name: concat!(file!(), ":", line!(), ":", column!(), ":0"),
+    roots: &[TemplateNode::Dynamic { id: 0 }],
+    node_paths: &[PackedPath::new(&[0])],
+    ..Template::EMPTY
+};
+
+/// Render the children into another element, like a modal root, instead of where the portal is
+///
+/// The portal is still part of the tree it is rendered in. Its children get context from the components above the
+/// portal, and events bubble from them to the elements around the portal, not the target. A placeholder marks the
+/// place of the portal, so siblings are inserted around it as usual.
+///
+/// ```rust, ignore
+/// rsx! {
+///     button { onclick: move |_| open.set(false), "Close" }
+///     Portal {
+///         target: "modal-root",
+///         div { class: "modal", "Are you sure?" }
+///     }
+/// }
+/// ```
+#[allow(non_snake_case)]
+pub fn Portal<'a>(cx: Scope<'a, PortalProps<'a>>) -> Element<'a> {
+    cx.portal_target.set(Some(cx.props.target));
+
+    // The children are always in the same slot, so a portal with no children still has a node in the target
+    let children = match cx.props.children.as_ref() {
+        Some(children) => DynamicNode::Fragment(cx.bump().alloc([VNode {
+            key: children.key,
+            parent: children.parent.clone(),
+            template: children.template.clone(),
+            root_ids: children.root_ids.clone(),
+            dynamic_nodes: children.dynamic_nodes,
+            dynamic_attrs: children.dynamic_attrs,
+        }])),
+        None => DynamicNode::Placeholder(Default::default()),
+    };
+
+    Some(VNode {
+        key: None,
+        parent: Default::default(),
+        template: Cell::new(PORTAL_TEMPLATE),
+        root_ids: RefCell::new(bumpalo::collections::Vec::new_in(cx.bump())),
+        dynamic_nodes: cx.bump().alloc([children]),
+        dynamic_attrs: &[],
+    })
+}
+
+/// The props of a [`Portal`]
+pub struct PortalProps<'a> {
+    target: PortalTarget,
+    children: Element<'a>,
+}
+
+/// The builder of [`PortalProps`]. The target has to be set before the props can be built.
+pub struct PortalBuilder<'a, const TARGET: bool>(Option<PortalTarget>, Element<'a>);
+
+impl<'a, const TARGET: bool> PortalBuilder<'a, TARGET> {
+    /// Set the element the children are mounted in
+    pub fn target(self, target: impl Into<PortalTarget>) -> PortalBuilder<'a, true> {
+        PortalBuilder(Some(target.into()), self.1)
+    }
+
+    /// Set the children of the portal
+    pub fn children(self, children: Element<'a>) -> Self {
+        PortalBuilder(self.0, children)
+    }
+}
+
+impl<'a> PortalBuilder<'a, true> {
+    /// Finish building the props
+    pub fn build(self) -> PortalProps<'a> {
+        PortalProps {
+            target: self.0.expect("the target of a portal is set"),
+            children: self.1,
+        }
+    }
+}
+
+impl<'a> Properties for PortalProps<'a> {
+    type Builder = PortalBuilder<'a, false>;
+    const IS_STATIC: bool = false;
+    fn builder() -> Self::Builder {
+        PortalBuilder(None, None)
+    }
+    unsafe fn memoize(&self, _other: &Self) -> bool {
+        false
+    }
+}
+
+impl VirtualDom {
+    /// Mount the root of a portal in its target and a placeholder in its place. Returns the number of nodes left on
+    /// the stack, which is just the placeholder.
+    pub(crate) fn create_portal<'b>(
+        &mut self,
+        to: &mut impl WriteMutations<'b>,
+        scope: ScopeId,
+        node: &'b VNode<'b>,
+        target: PortalTarget,
+    ) -> usize {
+        let m = self.create_scope(to, scope, node);
+        match target {
+            PortalTarget::Element(id) => to.append_children(id, m),
+            PortalTarget::Anchor(anchor) => to.append_to_anchor(anchor, m),
+        }
+
+        let placeholder = self.next_null();
+        to.create_placeholder(placeholder);
+        self.portals.insert(scope, placeholder);
+        1
+    }
+
+    /// The placeholder that marks the place of a portal in the tree it is rendered in
+    pub(crate) fn portal_placeholder(&self, scope: ScopeId) -> Option<ElementId> {
+        self.portals.get(&scope).copied()
+    }
+}
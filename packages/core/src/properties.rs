@@ -32,6 +32,22 @@ use crate::innerlude::*;
 ///     data: &'a str
 /// }
 /// ```
+///
+/// For 'static props that can't implement PartialEq, or where only some fields decide if the component should render
+/// again, a custom comparison can be used instead. The component skips rendering when it returns true:
+///
+/// ```rust, ignore
+/// #[derive(Props)]
+/// #[props(memo = same_row)]
+/// struct RowProps {
+///     id: usize,
+///     on_select: Rc<dyn Fn(usize)>,
+/// }
+///
+/// fn same_row(old: &RowProps, new: &RowProps) -> bool {
+///     old.id == new.id
+/// }
+/// ```
 pub trait Properties: Sized {
     /// The type of the builder for this component.
     /// Used to create "in-progress" versions of the props.
@@ -49,6 +65,40 @@ pub trait Properties: Sized {
     /// The user must know if their props are static, but if they make a mistake, UB happens
     /// Therefore it's unsafe to memoize.
     unsafe fn memoize(&self, other: &Self) -> bool;
+
+    /// Describe these props for tools like [`crate::VirtualDom::inspect`].
+    ///
+    /// The derive macro uses the [`std::fmt::Debug`] output of props that implement it.
+    fn inspect(&self) -> Option<String> {
+        None
+    }
+}
+
+// The derive macro picks the Debug output of props that implement it by autoref: the method on `InspectProps` is
+// found before the one on `&InspectProps`, but only applies if the props are Debug.
+#[doc(hidden)]
+pub struct InspectProps<'a, T>(pub &'a T);
+
+#[doc(hidden)]
+pub trait InspectDebug {
+    fn inspect_props(&self) -> Option<String>;
+}
+
+impl<T: std::fmt::Debug> InspectDebug for InspectProps<'_, T> {
+    fn inspect_props(&self) -> Option<String> {
+        Some(format!("{:?}", self.0))
+    }
+}
+
+#[doc(hidden)]
+pub trait InspectFallback {
+    fn inspect_props(&self) -> Option<String>;
+}
+
+impl<T> InspectFallback for &InspectProps<'_, T> {
+    fn inspect_props(&self) -> Option<String> {
+        None
+    }
 }
 
 impl Properties for () {
@@ -0,0 +1,56 @@
+use rustc_hash::FxHashSet;
+
+use crate::{innerlude::VNode, nodes::TemplateId, walk::TreeVisitor, Template, VirtualDom};
+
+impl VirtualDom {
+    /// Find every template used in the resolved tree of this node, following fragments and the rendered output of
+    /// components.
+    ///
+    /// Templates that were registered but are not reachable from the root are no longer on screen, so they can be
+    /// dropped with [`VirtualDom::retain_templates`].
+    ///
+    /// ```rust, ignore
+    /// let node = match dom.base_scope().root_node() {
+    ///     RenderReturn::Ready(node) => node,
+    ///     _ => return,
+    /// };
+    ///
+    /// let reachable = dom.reachable_templates(node);
+    /// ```
+    pub fn reachable_templates<'b>(&'b self, node: &'b VNode<'b>) -> FxHashSet<TemplateId> {
+        let mut collector = TemplateCollector::default();
+        self.visit_node(node, &mut collector);
+        collector.templates
+    }
+
+    /// Get the ids of every template in the VirtualDom's template registry
+    pub fn registered_templates(&self) -> FxHashSet<TemplateId> {
+        self.templates
+            .values()
+            .flat_map(|templates| templates.values())
+            .map(|template| template.name)
+            .collect()
+    }
+
+    /// Drop every template from the template registry that is not in `keep`
+    ///
+    /// If a dropped template is rendered again, it is registered again and sent to the renderer like a new template.
+    pub fn retain_templates(&mut self, keep: &FxHashSet<TemplateId>) {
+        for templates in self.templates.values_mut() {
+            templates.retain(|_, template| keep.contains(template.name));
+        }
+        self.templates.retain(|_, templates| !templates.is_empty());
+        self.seen_templates.clear();
+    }
+}
+
+#[derive(Default)]
+struct TemplateCollector {
+    templates: FxHashSet<TemplateId>,
+}
+
+impl<'b> TreeVisitor<'b> for TemplateCollector {
+    fn template(&mut self, template: Template<'static>) {
+        self.templates.insert(template.name);
+    }
+}
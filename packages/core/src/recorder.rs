@@ -0,0 +1,300 @@
+use bumpalo::Bump;
+
+use crate::{
+    innerlude::{BorrowedAttributeValue, InitialAttribute, MockDom, Mutation, Mutations},
+    PackedPath, Template, TemplateAttribute, TemplateNode,
+};
+
+/// Every batch of mutations a VirtualDom emitted over a session, in the order they were applied
+///
+/// With the `serialize` feature, the log can be saved and loaded to reproduce a bug or build a test fixture. Use
+/// [`replay`] to rebuild the tree the session ended with.
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serialize", serde(bound(deserialize = "'de: 'static")))]
+#[derive(Debug, Default, PartialEq)]
+pub struct MutationLog<'a> {
+    /// The recorded batches
+    #[cfg_attr(feature = "serialize", serde(borrow))]
+    pub batches: Vec<RecordedBatch<'a>>,
+}
+
+/// A single batch of mutations in a [`MutationLog`]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serialize", serde(bound(deserialize = "'de: 'static")))]
+#[derive(Debug, Default, PartialEq)]
+pub struct RecordedBatch<'a> {
+    /// The templates registered by the batch
+    #[cfg_attr(feature = "serialize", serde(borrow))]
+    pub templates: Vec<Template<'a>>,
+
+    /// The edits of the batch
+    #[cfg_attr(feature = "serialize", serde(borrow))]
+    pub edits: Vec<Mutation<'a>>,
+}
+
+/// Records the mutations of a session into a [`MutationLog`]
+///
+/// Mutations borrow from the VirtualDom, so the recorder copies everything it records. Custom
+/// [`crate::AnyValue`] attributes only mean something to the renderer they were made for and can't be copied out of
+/// the VirtualDom, so they are left out of the log.
+///
+/// ```rust, ignore
+/// let mut recorder = MutationRecorder::new();
+/// recorder.record(&dom.rebuild());
+/// recorder.record(&dom.render_immediate());
+///
+/// let json = serde_json::to_string(recorder.log())?;
+/// ```
+#[derive(Default)]
+pub struct MutationRecorder {
+    log: MutationLog<'static>,
+    strings: Bump,
+}
+
+impl MutationRecorder {
+    /// Create a recorder with an empty log
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Copy a batch of mutations to the end of the log
+    pub fn record(&mut self, mutations: &Mutations) {
+        let templates = mutations
+            .templates
+            .iter()
+            .map(|template| self.copy_template(template))
+            .collect();
+        let edits = mutations
+            .edits
+            .iter()
+            .filter_map(|edit| self.copy_edit(edit))
+            .collect();
+
+        self.log.batches.push(RecordedBatch { templates, edits });
+    }
+
+    /// Get everything recorded so far
+    pub fn log(&self) -> &MutationLog<'_> {
+        &self.log
+    }
+
+    fn copy_edit(&self, edit: &Mutation) -> Option<Mutation<'static>> {
+        Some(match *edit {
+            Mutation::AppendChildren { id, m } => Mutation::AppendChildren { id, m },
+            Mutation::AssignId { path, id } => Mutation::AssignId { path, id },
+            Mutation::CreatePlaceholder { id } => Mutation::CreatePlaceholder { id },
+            Mutation::CreateTextNode { value, id } => Mutation::CreateTextNode {
+                value: self.intern(value),
+                id,
+            },
+            Mutation::HydrateText { path, value, id } => Mutation::HydrateText {
+                path,
+                value: self.intern(value),
+                id,
+            },
+            Mutation::LoadTemplate { name, index, id } => {
+                Mutation::LoadTemplate { name, index, id }
+            }
+            Mutation::CreateElementWithAttrs {
+                name,
+                index,
+                id,
+                tag,
+                ref attrs,
+                ref listeners,
+            } => Mutation::CreateElementWithAttrs {
+                name,
+                index,
+                id,
+                tag: self.intern(tag),
+                attrs: attrs
+                    .iter()
+                    .filter_map(|attr| {
+                        Some(InitialAttribute {
+                            name: self.intern(attr.name),
+                            value: self.copy_value(&attr.value)?,
+                            ns: attr.ns.map(|ns| self.intern(ns)),
+                        })
+                    })
+                    .collect(),
+                listeners: listeners
+                    .iter()
+                    .map(|listener| self.intern(listener))
+                    .collect(),
+            },
+            Mutation::AppendToAnchor { anchor, m } => Mutation::AppendToAnchor {
+                anchor: self.intern(anchor),
+                m,
+            },
+            Mutation::ReplaceWith { id, m } => Mutation::ReplaceWith { id, m },
+            Mutation::ReplacePlaceholder { path, m } => Mutation::ReplacePlaceholder { path, m },
+            Mutation::InsertAfter { id, m } => Mutation::InsertAfter { id, m },
+            Mutation::InsertBefore { id, m } => Mutation::InsertBefore { id, m },
+            Mutation::SetAttribute {
+                name,
+                ref value,
+                id,
+                ns,
+            } => Mutation::SetAttribute {
+                name: self.intern(name),
+                value: self.copy_value(value)?,
+                id,
+                ns: ns.map(|ns| self.intern(ns)),
+            },
+            Mutation::SetText { value, id } => Mutation::SetText {
+                value: self.intern(value),
+                id,
+            },
+            Mutation::NewEventListener { name, id } => Mutation::NewEventListener {
+                name: self.intern(name),
+                id,
+            },
+            Mutation::RemoveEventListener { name, id } => Mutation::RemoveEventListener {
+                name: self.intern(name),
+                id,
+            },
+            Mutation::Remove { id } => Mutation::Remove { id },
+            Mutation::PushRoot { id } => Mutation::PushRoot { id },
+            Mutation::CaptureScroll { id } => Mutation::CaptureScroll { id },
+            Mutation::RestoreScroll { id, x, y } => Mutation::RestoreScroll { id, x, y },
+            Mutation::InjectStyle { name, style } => Mutation::InjectStyle {
+                name: self.intern(name),
+                style: self.intern(style),
+            },
+            Mutation::RegisterDelegated { name } => Mutation::RegisterDelegated {
+                name: self.intern(name),
+            },
+            Mutation::UnregisterDelegated { name } => Mutation::UnregisterDelegated {
+                name: self.intern(name),
+            },
+        })
+    }
+
+    fn copy_value(
+        &self,
+        value: &BorrowedAttributeValue,
+    ) -> Option<BorrowedAttributeValue<'static>> {
+        Some(match value {
+            BorrowedAttributeValue::Text(value) => BorrowedAttributeValue::Text(self.intern(value)),
+            BorrowedAttributeValue::Float(value) => BorrowedAttributeValue::Float(*value),
+            BorrowedAttributeValue::Int(value) => BorrowedAttributeValue::Int(*value),
+            BorrowedAttributeValue::Bool(value) => BorrowedAttributeValue::Bool(*value),
+            BorrowedAttributeValue::Bytes(value) => {
+                BorrowedAttributeValue::Bytes(self.intern_bytes(value))
+            }
+            BorrowedAttributeValue::Derived(derived) => BorrowedAttributeValue::Derived(*derived),
+            BorrowedAttributeValue::Any(_) => return None,
+            BorrowedAttributeValue::None => BorrowedAttributeValue::None,
+        })
+    }
+
+    fn copy_template(&self, template: &Template) -> Template<'static> {
+        Template {
+            name: self.intern(template.name),
+            roots: self.copy_nodes(template.roots),
+            node_paths: self.copy_packed_paths(template.node_paths),
+            attr_paths: self.copy_packed_paths(template.attr_paths),
+            unpacked_paths: self.copy_paths(template.unpacked_paths),
+            style: template.style.map(|style| self.intern(style)),
+        }
+    }
+
+    fn copy_nodes(&self, nodes: &[TemplateNode]) -> &'static [TemplateNode<'static>] {
+        let nodes = self
+            .strings
+            .alloc_slice_fill_iter(nodes.iter().map(|node| match *node {
+                TemplateNode::Element {
+                    tag,
+                    namespace,
+                    attrs,
+                    children,
+                } => TemplateNode::Element {
+                    tag: self.intern(tag),
+                    namespace: namespace.map(|ns| self.intern(ns)),
+                    attrs: self.copy_attrs(attrs),
+                    children: self.copy_nodes(children),
+                },
+                TemplateNode::Text { text } => TemplateNode::Text {
+                    text: self.intern(text),
+                },
+                TemplateNode::Dynamic { id } => TemplateNode::Dynamic { id },
+                TemplateNode::DynamicText { id } => TemplateNode::DynamicText { id },
+            }));
+        // Safety: the nodes only borrow from the recorder, see `intern`
+        unsafe { std::mem::transmute(&*nodes) }
+    }
+
+    fn copy_attrs(&self, attrs: &[TemplateAttribute]) -> &'static [TemplateAttribute<'static>] {
+        let attrs = self
+            .strings
+            .alloc_slice_fill_iter(attrs.iter().map(|attr| match *attr {
+                TemplateAttribute::Static {
+                    name,
+                    value,
+                    namespace,
+                } => TemplateAttribute::Static {
+                    name: self.intern(name),
+                    value: self.intern(value),
+                    namespace: namespace.map(|ns| self.intern(ns)),
+                },
+                TemplateAttribute::Dynamic { id } => TemplateAttribute::Dynamic { id },
+                TemplateAttribute::Conditional {
+                    name,
+                    value,
+                    namespace,
+                    flag,
+                } => TemplateAttribute::Conditional {
+                    name: self.intern(name),
+                    value: self.intern(value),
+                    namespace: namespace.map(|ns| self.intern(ns)),
+                    flag: self.intern(flag),
+                },
+            }));
+        // Safety: the attributes only borrow from the recorder, see `intern`
+        unsafe { std::mem::transmute(&*attrs) }
+    }
+
+    fn copy_paths(&self, paths: &[&[u8]]) -> &'static [&'static [u8]] {
+        let paths = self
+            .strings
+            .alloc_slice_fill_iter(paths.iter().map(|path| self.strings.alloc_slice_copy(path)));
+        // Safety: the paths only borrow from the recorder, see `intern`
+        unsafe { std::mem::transmute(&*paths) }
+    }
+
+    fn copy_packed_paths(&self, paths: &[PackedPath]) -> &'static [PackedPath] {
+        let paths = self.strings.alloc_slice_copy(paths);
+        // Safety: the paths only borrow from the recorder, see `intern`
+        unsafe { std::mem::transmute(&*paths) }
+    }
+
+    fn intern(&self, value: &str) -> &'static str {
+        // Safety: the strings are never freed before the recorder is dropped, and they are only handed out borrowed
+        // from the recorder
+        unsafe { std::mem::transmute(&*self.strings.alloc_str(value)) }
+    }
+
+    fn intern_bytes(&self, value: &[u8]) -> &'static [u8] {
+        // Safety: see intern
+        unsafe { std::mem::transmute(&*self.strings.alloc_slice_copy(value)) }
+    }
+}
+
+/// Apply every batch of a [`MutationLog`] to a fresh [`MockDom`], rebuilding the tree the session ended with
+///
+/// ```rust, ignore
+/// let log: MutationLog = serde_json::from_str(&json)?;
+/// println!("{}", replay(&log));
+/// ```
+pub fn replay(log: &MutationLog) -> MockDom {
+    let dom = MockDom::new();
+    for batch in &log.batches {
+        for template in &batch.templates {
+            dom.register_template(template);
+        }
+        for edit in &batch.edits {
+            dom.apply_edit(edit);
+        }
+    }
+    dom
+}
@@ -0,0 +1,48 @@
+use std::rc::Rc;
+
+use crate::{ElementId, VirtualDom};
+
+/// An event sent back from a remote renderer to the VirtualDom that owns the listeners.
+///
+/// When rendering remotely, the closures stay on the server and only the serialized [`crate::Mutation`]s reach the
+/// client. Each listener shows up there as a [`crate::Mutation::NewEventListener`] with the element and the event
+/// name, so the client knows which events to forward. When one fires, the client sends a `RemoteEvent` with the same
+/// element and name back, and the server hands it to [`VirtualDom::handle_remote_event`].
+///
+/// The payload is whatever the listener expects its event data to be, so the server should deserialize it into that
+/// type before handing it over.
+///
+/// ```rust, ignore
+/// let event: RemoteEvent<MouseData> = serde_json::from_str(&message)?;
+/// dom.handle_remote_event(event);
+/// dom.render_immediate_to(&mut client);
+/// ```
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, PartialEq)]
+pub struct RemoteEvent<T> {
+    /// The name of the event, without the `on` prefix
+    pub name: String,
+
+    /// The element the event fired on
+    pub element: ElementId,
+
+    /// Whether the event bubbles up to the listeners of the parent elements
+    pub bubbles: bool,
+
+    /// The data of the event
+    pub payload: T,
+}
+
+impl VirtualDom {
+    /// Call the listener a remote event was sent for.
+    ///
+    /// This is the same as calling [`VirtualDom::handle_event`] with the parts of the event.
+    pub fn handle_remote_event<T: 'static>(&mut self, event: RemoteEvent<T>) {
+        self.handle_event(
+            &event.name,
+            Rc::new(event.payload),
+            event.element,
+            event.bubbles,
+        );
+    }
+}
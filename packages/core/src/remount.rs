@@ -0,0 +1,30 @@
+use crate::{innerlude::VNode, Attribute};
+
+/// The name of the attribute that forces a node to remount when its value changes.
+///
+/// Diffing two nodes with the same template and key normally keeps the elements and component state around. If the
+/// value of this attribute changes, the old node and every scope under it are destroyed and the new node is created
+/// from scratch, just as if the key had changed. The attribute is never sent to the renderer.
+///
+/// ```rust, ignore
+/// rsx! {
+///     div { key: "{user.id}", "dioxus-remount": "{form_generation}",
+///         EditForm { user: user }
+///     }
+/// }
+/// ```
+pub const REMOUNT_TOKEN: &str = "dioxus-remount";
+
+impl Attribute<'_> {
+    pub(crate) fn is_remount_token(&self) -> bool {
+        self.name == REMOUNT_TOKEN
+    }
+}
+
+/// Check if two nodes built from the same template have a different remount token
+pub(crate) fn remount_token_changed<'b>(left: &VNode<'b>, right: &VNode<'b>) -> bool {
+    left.dynamic_attrs
+        .iter()
+        .zip(right.dynamic_attrs.iter())
+        .any(|(left, right)| left.is_remount_token() && left.value != right.value)
+}
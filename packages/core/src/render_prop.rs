@@ -0,0 +1,91 @@
+use crate::{
+    innerlude::{current_scope_id, Element},
+    runtime::with_runtime,
+    ScopeId, ScopeState,
+};
+
+/// A closure that a component calls to render part of its output, also known as a render prop or a slot.
+///
+/// Unlike children, which are rendered by the parent before the child runs, a render prop is rendered by the child
+/// with values only the child knows about. The nodes are still built by the parent's closure, so they can borrow
+/// anything the parent can.
+///
+/// # Example
+///
+/// ```rust, ignore
+/// #[derive(Props)]
+/// struct ListProps<'a> {
+///     items: &'a [String],
+///     row: RenderProp<'a, &'a str>,
+/// }
+///
+/// fn List<'a>(cx: Scope<'a, ListProps<'a>>) -> Element {
+///     cx.render(rsx! {
+///         ul { cx.props.items.iter().map(|item| cx.props.row.call(item)) }
+///     })
+/// }
+///
+/// rsx! {
+///     List {
+///         items: &names,
+///         row: move |name| render!(li { "Hello {name}" }),
+///     }
+/// }
+/// ```
+///
+/// Fields of this type in a `#[derive(Props)]` struct accept the closure directly, so `cx.render_prop` is only needed
+/// when building props by hand.
+pub struct RenderProp<'bump, T = ()> {
+    pub(crate) origin: ScopeId,
+    pub(super) render: Box<dyn Fn(T) -> Element<'bump> + 'bump>,
+}
+
+impl<'bump, T> RenderProp<'bump, T> {
+    /// Render the nodes for this value
+    ///
+    /// The closure runs as part of the scope that created it, so hooks like [`ScopeState::consume_context`] see the
+    /// context of the parent.
+    pub fn call(&self, value: T) -> Element<'bump> {
+        with_runtime(|rt| rt.scope_stack.borrow_mut().push(self.origin));
+        let element = (self.render)(value);
+        with_runtime(|rt| rt.scope_stack.borrow_mut().pop());
+        element
+    }
+}
+
+impl<'src> ScopeState {
+    /// Create a new [`RenderProp`] from a closure that renders nodes for a value
+    pub fn render_prop<T>(
+        &'src self,
+        render: impl Fn(T) -> Element<'src> + 'src,
+    ) -> RenderProp<'src, T> {
+        RenderProp {
+            origin: self.scope_id(),
+            render: Box::new(render),
+        }
+    }
+}
+
+/// Anything that can be passed to a [`RenderProp`] field of a component
+///
+/// This is implemented for closures that return an [`Element`], which are attached to the scope that is currently
+/// rendering.
+pub trait IntoRenderProp<'a, T> {
+    /// Convert this value into a [`RenderProp`]
+    fn into_render_prop(self) -> RenderProp<'a, T>;
+}
+
+impl<'a, T> IntoRenderProp<'a, T> for RenderProp<'a, T> {
+    fn into_render_prop(self) -> RenderProp<'a, T> {
+        self
+    }
+}
+
+impl<'a, T, F: Fn(T) -> Element<'a> + 'a> IntoRenderProp<'a, T> for F {
+    fn into_render_prop(self) -> RenderProp<'a, T> {
+        RenderProp {
+            origin: current_scope_id().unwrap_or(ScopeId::ROOT),
+            render: Box::new(self),
+        }
+    }
+}
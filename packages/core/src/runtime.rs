@@ -1,6 +1,12 @@
 use std::cell::{Cell, Ref, RefCell};
 
-use crate::{innerlude::Scheduler, scope_context::ScopeContext, scopes::ScopeId};
+use crate::{
+    dependencies::Dependencies,
+    head::Head,
+    innerlude::{DragPayload, Priority, Scheduler},
+    scope_context::ScopeContext,
+    scopes::ScopeId,
+};
 use std::rc::Rc;
 
 thread_local! {
@@ -49,6 +55,18 @@ pub struct Runtime {
     // We use this to track the current scope
     pub(crate) scope_stack: RefCell<Vec<ScopeId>>,
     pub(crate) rendering: Cell<bool>,
+
+    // The priority of updates that don't pick one, which is raised while events are handled
+    pub(crate) update_priority: Cell<Priority>,
+
+    // The payload of the element that is being dragged
+    pub(crate) drag_payload: RefCell<Option<DragPayload>>,
+
+    // The external values each scope read during its last render
+    pub(crate) dependencies: Dependencies,
+
+    // The elements components hoisted into the head of the document
+    pub(crate) head: Rc<Head>,
 }
 
 impl Runtime {
@@ -61,6 +79,14 @@ impl Runtime {
             scope_stack: Default::default(),
 
             rendering: Cell::new(true),
+
+            update_priority: Default::default(),
+
+            drag_payload: Default::default(),
+
+            dependencies: Default::default(),
+
+            head: Default::default(),
         })
     }
 
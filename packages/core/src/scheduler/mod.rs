@@ -1,9 +1,13 @@
-use crate::ScopeId;
+use crate::{effects::Effect, ScopeId};
 use slab::Slab;
 
+mod priority;
+mod suspense;
 mod task;
 mod wait;
 
+pub use priority::*;
+pub use suspense::*;
 pub use task::*;
 
 /// The type of message that can be sent to the scheduler.
@@ -12,7 +16,7 @@ pub use task::*;
 #[derive(Debug)]
 pub(crate) enum SchedulerMsg {
     /// Immediate updates from Components that mark them as dirty
-    Immediate(ScopeId),
+    Immediate(ScopeId, Priority),
 
     /// A task has woken and needs to be progressed
     TaskNotified(TaskId),
@@ -25,6 +29,9 @@ pub(crate) struct Scheduler {
 
     /// Tasks created with cx.spawn
     pub tasks: RefCell<Slab<LocalTask>>,
+
+    /// Effects queued with cx.queue_effect that run once the mutations of a render are applied
+    pub effects: RefCell<Vec<Effect>>,
 }
 
 impl Scheduler {
@@ -32,6 +39,7 @@ impl Scheduler {
         Rc::new(Scheduler {
             sender,
             tasks: RefCell::new(Slab::new()),
+            effects: RefCell::new(Vec::new()),
         })
     }
 }
@@ -0,0 +1,29 @@
+use crate::runtime::with_runtime;
+
+/// How urgently a scope needs to be rendered
+///
+/// Dirty scopes are rendered one lane at a time, starting with [`Priority::High`]. Updates scheduled while an event is
+/// being handled are high priority, so typing and clicking stay responsive while background work like data polling
+/// waits for its turn.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub enum Priority {
+    /// Updates caused by user input
+    High,
+
+    /// Updates that don't say how urgent they are
+    #[default]
+    Normal,
+
+    /// Background work that can wait for everything else
+    Low,
+}
+
+impl Priority {
+    /// The number of lanes dirty scopes are sorted into
+    pub(crate) const LANES: usize = 3;
+
+    /// The priority of updates scheduled right now, which is high while an event is being handled
+    pub(crate) fn current() -> Self {
+        with_runtime(|rt| rt.update_priority.get()).unwrap_or_default()
+    }
+}
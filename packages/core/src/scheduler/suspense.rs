@@ -1,14 +1,11 @@
-use futures_util::task::ArcWake;
-
-use super::SchedulerMsg;
-use crate::ElementId;
-use crate::{innerlude::Mutations, Element, ScopeId};
-use std::future::Future;
-use std::sync::Arc;
-use std::task::Waker;
+use crate::innerlude::{
+    DynamicNode, PackedPath, Properties, Scope, ScopeId, Template, TemplateNode, VNode, VirtualDom,
+};
+use crate::Element;
 use std::{
     cell::{Cell, RefCell},
     collections::HashSet,
+    rc::Rc,
 };
 
 /// A boundary in the VirtualDom that captures all suspended components below it
@@ -26,7 +23,160 @@ impl SuspenseContext {
         }
     }
 
-    pub fn mark_suspend(&self, id: ScopeId) {
-        self.waiting_on.borrow_mut().insert(id);
+    /// Start waiting on a suspended component. Returns true if the boundary wasn't waiting on it already.
+    pub fn mark_suspend(&self, id: ScopeId) -> bool {
+        self.waiting_on.borrow_mut().insert(id)
+    }
+
+    /// Check if any component below the boundary is suspended
+    pub fn is_suspended(&self) -> bool {
+        !self.waiting_on.borrow().is_empty()
+    }
+}
+
+impl VirtualDom {
+    /// Park a scope that suspended until one of its tasks finishes, and show the fallback of its boundary
+    pub(crate) fn park_suspended(&mut self, id: ScopeId) {
+        self.suspended_scopes.insert(id);
+        if let Some(boundary) = self.suspense_boundary(id) {
+            if boundary.mark_suspend(id) {
+                self.mark_dirty(boundary.id);
+            }
+        }
+    }
+
+    /// Stop waiting on a scope that rendered or was dropped, and hide the fallback of its boundary if nothing else below
+    /// it is suspended
+    pub(crate) fn resume_suspended(&mut self, id: ScopeId) {
+        if !self.suspended_scopes.remove(&id) {
+            return;
+        }
+        if let Some(boundary) = self.suspense_boundary(id) {
+            boundary.waiting_on.borrow_mut().remove(&id);
+            if !boundary.is_suspended() {
+                self.mark_dirty(boundary.id);
+            }
+        }
+    }
+
+    // Find the closest boundary above a scope
+    fn suspense_boundary(&self, id: ScopeId) -> Option<Rc<SuspenseContext>> {
+        let mut search = self.runtime.get_context(id)?.parent_id;
+        while let Some(parent) = search {
+            let context = self.runtime.get_context(parent)?;
+            if let Some(boundary) = context.has_context::<Rc<SuspenseContext>>() {
+                return Some(boundary);
+            }
+            search = context.parent_id;
+        }
+        None
+    }
+}
+
+static SUSPENSE_TEMPLATE: Template<'static> = Template {
+    name: concat!(file!(), ":", line!(), ":", column!(), ":0"),
+    roots: &[
+        TemplateNode::Dynamic { id: 0 },
+        TemplateNode::Dynamic { id: 1 },
+    ],
+    node_paths: &[PackedPath::new(&[0]), PackedPath::new(&[1])],
+    ..Template::EMPTY
+};
+
+/// Show a fallback while any component below the boundary is suspended
+///
+/// A component suspends by calling [`crate::ScopeState::suspend`] until the future it spawned has the data it needs.
+/// The component is parked until one of its tasks finishes and then renders again. The children stay mounted while the
+/// fallback is shown, so their futures keep running. A suspended component renders a placeholder in the meantime.
+///
+/// ```rust, ignore
+/// rsx! {
+///     SuspenseBoundary {
+///         fallback: render!("Loading..."),
+///         UserProfile { id: 1 }
+///     }
+/// }
+/// ```
+#[allow(non_snake_case)]
+pub fn SuspenseBoundary<'a>(cx: Scope<'a, SuspenseBoundaryProps<'a>>) -> Element<'a> {
+    let boundary = cx.use_hook(|| cx.provide_context(Rc::new(SuspenseContext::new(cx.scope_id()))));
+
+    // Both slots are always in the same template, so showing the fallback never remounts the children
+    let fallback = cx
+        .props
+        .fallback
+        .as_ref()
+        .filter(|_| boundary.is_suspended());
+    let nodes = [
+        fragment_or_placeholder(cx, fallback),
+        fragment_or_placeholder(cx, cx.props.children.as_ref()),
+    ];
+
+    Some(VNode {
+        key: None,
+        parent: Default::default(),
+        template: Cell::new(SUSPENSE_TEMPLATE),
+        root_ids: RefCell::new(bumpalo::collections::Vec::new_in(cx.bump())),
+        dynamic_nodes: cx.bump().alloc(nodes),
+        dynamic_attrs: &[],
+    })
+}
+
+fn fragment_or_placeholder<'a>(
+    cx: Scope<'a, SuspenseBoundaryProps<'a>>,
+    node: Option<&'a VNode<'a>>,
+) -> DynamicNode<'a> {
+    match node {
+        Some(node) => DynamicNode::Fragment(cx.bump().alloc([VNode {
+            key: node.key,
+            parent: node.parent.clone(),
+            template: node.template.clone(),
+            root_ids: node.root_ids.clone(),
+            dynamic_nodes: node.dynamic_nodes,
+            dynamic_attrs: node.dynamic_attrs,
+        }])),
+        None => DynamicNode::Placeholder(Default::default()),
+    }
+}
+
+/// The props of a [`SuspenseBoundary`]
+pub struct SuspenseBoundaryProps<'a> {
+    fallback: Element<'a>,
+    children: Element<'a>,
+}
+
+/// The builder of [`SuspenseBoundaryProps`]
+pub struct SuspenseBoundaryBuilder<'a>(SuspenseBoundaryProps<'a>);
+
+impl<'a> SuspenseBoundaryBuilder<'a> {
+    /// Set what is shown while a component below the boundary is suspended
+    pub fn fallback(mut self, fallback: Element<'a>) -> Self {
+        self.0.fallback = fallback;
+        self
+    }
+
+    /// Set the children of the boundary
+    pub fn children(mut self, children: Element<'a>) -> Self {
+        self.0.children = children;
+        self
+    }
+
+    /// Finish building the props
+    pub fn build(self) -> SuspenseBoundaryProps<'a> {
+        self.0
+    }
+}
+
+impl<'a> Properties for SuspenseBoundaryProps<'a> {
+    type Builder = SuspenseBoundaryBuilder<'a>;
+    const IS_STATIC: bool = false;
+    fn builder() -> Self::Builder {
+        SuspenseBoundaryBuilder(SuspenseBoundaryProps {
+            fallback: None,
+            children: None,
+        })
+    }
+    unsafe fn memoize(&self, _other: &Self) -> bool {
+        false
     }
 }
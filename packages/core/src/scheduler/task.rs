@@ -10,8 +10,8 @@ use std::task::Waker;
 
 /// A task's unique identifier.
 ///
-/// `TaskId` is a `usize` that is unique across the entire VirtualDOM and across time. TaskIDs will never be reused
-/// once a Task has been completed.
+/// `TaskId` is a `usize` that is unique across the entire VirtualDOM while the task is running. The id may be reused
+/// once the task completes or is removed.
 #[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
 #[derive(Copy, Clone, PartialEq, Eq, Hash, Debug)]
 pub struct TaskId(pub usize);
@@ -57,11 +57,12 @@ impl Scheduler {
         task_id
     }
 
-    /// Drop the future with the given TaskId
+    /// Drop the future with the given TaskId, and return the scope it was spawned in
     ///
     /// This does not abort the task, so you'll want to wrap it in an aborthandle if that's important to you
-    pub fn remove(&self, id: TaskId) {
-        self.tasks.borrow_mut().try_remove(id.0);
+    pub fn remove(&self, id: TaskId) -> Option<ScopeId> {
+        let task = self.tasks.borrow_mut().try_remove(id.0);
+        task.map(|task| task.scope)
     }
 }
 
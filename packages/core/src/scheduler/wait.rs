@@ -17,15 +17,17 @@ impl VirtualDom {
         };
 
         let mut cx = Context::from_waker(&task.waker);
+        let scope_id = task.scope;
 
         // update the scope stack
-        self.runtime.scope_stack.borrow_mut().push(task.scope);
+        self.runtime.scope_stack.borrow_mut().push(scope_id);
         self.runtime.rendering.set(false);
 
         // If the task completes...
-        if task.task.borrow_mut().as_mut().poll(&mut cx).is_ready() {
+        let finished = task.task.borrow_mut().as_mut().poll(&mut cx).is_ready();
+        if finished {
             // Remove it from the scope so we dont try to double drop it when the scope dropes
-            let scope = &self.get_scope(task.scope).unwrap();
+            let scope = &self.get_scope(scope_id).unwrap();
             scope.context().spawned_tasks.borrow_mut().remove(&id);
 
             // Remove it from the scheduler
@@ -35,5 +37,11 @@ impl VirtualDom {
         // Remove the scope from the stack
         self.runtime.scope_stack.borrow_mut().pop();
         self.runtime.rendering.set(true);
+        drop(tasks);
+
+        // A parked scope renders again once a task it was waiting on is done
+        if finished && self.suspended_scopes.contains(&scope_id) {
+            self.mark_dirty(scope_id);
+        }
     }
 }
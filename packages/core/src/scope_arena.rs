@@ -36,6 +36,9 @@ impl VirtualDom {
 
             borrowed_props: Default::default(),
             attributes_to_drop: Default::default(),
+
+            parent_element: Default::default(),
+            portal_target: Default::default(),
         }));
 
         let context =
@@ -52,6 +55,9 @@ impl VirtualDom {
         // Remove all the outdated listeners
         self.ensure_drop_safety(scope_id);
 
+        // The scope records everything it reads again while it renders
+        self.runtime.dependencies.forget_scope(scope_id);
+
         let new_nodes = unsafe {
             let scope = &self.scopes[scope_id.0];
             scope.previous_frame().bump_mut().reset();
@@ -87,17 +93,20 @@ impl VirtualDom {
             id: context.id,
         });
 
-        if context.suspended.get() {
-            if matches!(allocated, RenderReturn::Aborted(_)) {
-                self.suspended_scopes.insert(context.id);
-            }
-        } else if !self.suspended_scopes.is_empty() {
-            _ = self.suspended_scopes.remove(&context.id);
-        }
+        let suspended = context.suspended.get();
+        drop(context);
 
         // rebind the lifetime now that its stored internally
         let result = unsafe { allocated.extend_lifetime_ref() };
 
+        if suspended {
+            if matches!(result, RenderReturn::Aborted(_)) {
+                self.park_suspended(scope_id);
+            }
+        } else if !self.suspended_scopes.is_empty() {
+            self.resume_suspended(scope_id);
+        }
+
         self.runtime.scope_stack.borrow_mut().pop();
 
         result
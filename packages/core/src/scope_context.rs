@@ -1,5 +1,5 @@
 use crate::{
-    innerlude::{ErrorBoundary, Scheduler, SchedulerMsg},
+    innerlude::{ErrorBoundary, Priority, Scheduler, SchedulerMsg},
     runtime::{with_current_scope, with_runtime},
     Element, ScopeId, TaskId,
 };
@@ -64,7 +64,9 @@ impl ScopeContext {
     /// ## Notice: you should prefer using [`Self::schedule_update_any`] and [`Self::scope_id`]
     pub fn schedule_update(&self) -> Arc<dyn Fn() + Send + Sync + 'static> {
         let (chan, id) = (self.tasks.sender.clone(), self.scope_id());
-        Arc::new(move || drop(chan.unbounded_send(SchedulerMsg::Immediate(id))))
+        Arc::new(move || {
+            drop(chan.unbounded_send(SchedulerMsg::Immediate(id, Priority::current())))
+        })
     }
 
     /// Schedule an update for any component given its [`ScopeId`].
@@ -75,7 +77,8 @@ impl ScopeContext {
     pub fn schedule_update_any(&self) -> Arc<dyn Fn(ScopeId) + Send + Sync> {
         let chan = self.tasks.sender.clone();
         Arc::new(move |id| {
-            chan.unbounded_send(SchedulerMsg::Immediate(id)).unwrap();
+            chan.unbounded_send(SchedulerMsg::Immediate(id, Priority::current()))
+                .unwrap();
         })
     }
 
@@ -84,13 +87,21 @@ impl ScopeContext {
         self.needs_update_any(self.scope_id());
     }
 
+    /// Mark this scope as dirty, and schedule a render for it in the lane of the given [`Priority`].
+    pub fn needs_update_with_priority(&self, priority: Priority) {
+        self.tasks
+            .sender
+            .unbounded_send(SchedulerMsg::Immediate(self.scope_id(), priority))
+            .expect("Scheduler to exist if scope exists");
+    }
+
     /// Get the [`ScopeId`] of a mounted component.
     ///
     /// `ScopeId` is not unique for the lifetime of the [`crate::VirtualDom`] - a [`ScopeId`] will be reused if a component is unmounted.
     pub fn needs_update_any(&self, id: ScopeId) {
         self.tasks
             .sender
-            .unbounded_send(SchedulerMsg::Immediate(id))
+            .unbounded_send(SchedulerMsg::Immediate(id, Priority::current()))
             .expect("Scheduler to exist if scope exists");
     }
 
@@ -220,34 +231,40 @@ impl ScopeContext {
         id
     }
 
-    /// Spawns the future but does not return the [`TaskId`]
-    pub fn spawn(&self, fut: impl Future<Output = ()> + 'static) {
-        self.push_future(fut);
+    /// Spawns the future, and drops it when this component is unmounted
+    ///
+    /// The returned [`TaskId`] can be passed to [`ScopeContext::remove_future`] to cancel the task before that.
+    pub fn spawn(&self, fut: impl Future<Output = ()> + 'static) -> TaskId {
+        self.push_future(fut)
     }
 
     /// Spawn a future that Dioxus won't clean up when this component is unmounted
     ///
-    /// This is good for tasks that need to be run after the component has been dropped.
+    /// This is good for tasks that need to be run after the component has been dropped. The task runs until it
+    /// completes, it is cancelled with [`ScopeContext::remove_future`], or the VirtualDom is dropped.
     pub fn spawn_forever(&self, fut: impl Future<Output = ()> + 'static) -> TaskId {
-        // The root scope will never be unmounted so we can just add the task at the top of the app
-        let id = self.tasks.spawn(ScopeId::ROOT, fut);
-
-        // wake up the scheduler if it is sleeping
-        self.tasks
-            .sender
-            .unbounded_send(SchedulerMsg::TaskNotified(id))
-            .expect("Scheduler should exist");
-
-        self.spawned_tasks.borrow_mut().insert(id);
-
-        id
+        // The root scope will never be unmounted so we can just add the task at the top of the app. It isn't tracked by
+        // this scope, so unmounting this scope doesn't drop it.
+        self.tasks.spawn(ScopeId::ROOT, fut)
     }
 
     /// Informs the scheduler that this task is no longer needed and should be removed.
     ///
     /// This drops the task immediately.
     pub fn remove_future(&self, id: TaskId) {
-        self.tasks.remove(id);
+        // Task ids are reused, so the scope that spawned the task must forget it, or unmounting that scope would drop
+        // the next task with the same id
+        if let Some(scope) = self.tasks.remove(id) {
+            if scope == self.id {
+                self.spawned_tasks.borrow_mut().remove(&id);
+            } else {
+                with_runtime(|runtime| {
+                    if let Some(cx) = runtime.get_context(scope) {
+                        cx.spawned_tasks.borrow_mut().remove(&id);
+                    }
+                });
+            }
+        }
     }
 
     /// Inject an error into the nearest error boundary and quit rendering
@@ -342,9 +359,9 @@ pub fn push_future(fut: impl Future<Output = ()> + 'static) -> Option<TaskId> {
     with_current_scope(|cx| cx.push_future(fut))
 }
 
-/// Spawns the future but does not return the [`TaskId`]
-pub fn spawn(fut: impl Future<Output = ()> + 'static) {
-    with_current_scope(|cx| cx.spawn(fut));
+/// Spawns the future, and drops it when the current component is unmounted
+pub fn spawn(fut: impl Future<Output = ()> + 'static) -> Option<TaskId> {
+    with_current_scope(|cx| cx.spawn(fut))
 }
 
 /// Spawn a future that Dioxus won't clean up when this component is unmounted
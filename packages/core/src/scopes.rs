@@ -3,12 +3,15 @@ use crate::{
     any_props::VProps,
     bump_frame::BumpFrame,
     innerlude::ErrorBoundary,
-    innerlude::{DynamicNode, EventHandler, VComponent, VText},
+    innerlude::{
+        DynamicNode, EventHandler, NodeAllocator, PortalTarget, Priority, VComponent, VText,
+    },
     lazynodes::LazyNodes,
     nodes::{IntoAttributeValue, IntoDynNode, RenderReturn},
     runtime::Runtime,
     scope_context::ScopeContext,
-    AnyValue, Attribute, AttributeValue, Element, Event, Properties, TaskId,
+    AnyValue, Attribute, AttributeTemplate, AttributeValue, Element, ElementId, Event, Properties,
+    TaskId,
 };
 use bumpalo::{boxed::Box as BumpBox, Bump};
 use std::{
@@ -97,6 +100,12 @@ pub struct ScopeState {
     pub(crate) attributes_to_drop: RefCell<Vec<*const Attribute<'static>>>,
 
     pub(crate) props: Option<Box<dyn AnyProps<'static>>>,
+
+    // The element the root of the component bubbles events to
+    pub(crate) parent_element: Cell<Option<ElementId>>,
+
+    // Where the root of the component is mounted, if it is a portal
+    pub(crate) portal_target: Cell<Option<PortalTarget>>,
 }
 
 impl Drop for ScopeState {
@@ -253,6 +262,15 @@ impl<'src> ScopeState {
         self.context().needs_update()
     }
 
+    /// Mark this scope as dirty, and schedule a render for it in the lane of the given [`Priority`].
+    ///
+    /// Updates scheduled while an event is handled are [`Priority::High`] and everything else is
+    /// [`Priority::Normal`]. Use [`Priority::Low`] for background work like polling so it never delays a response to
+    /// the user.
+    pub fn needs_update_with_priority(&self, priority: Priority) {
+        self.context().needs_update_with_priority(priority)
+    }
+
     /// Get the [`ScopeId`] of a mounted component.
     ///
     /// `ScopeId` is not unique for the lifetime of the [`crate::VirtualDom`] - a [`ScopeId`] will be reused if a component is unmounted.
@@ -313,14 +331,25 @@ impl<'src> ScopeState {
         self.context().push_future(fut)
     }
 
-    /// Spawns the future but does not return the [`TaskId`]
-    pub fn spawn(&self, fut: impl Future<Output = ()> + 'static) {
-        self.context().spawn(fut);
+    /// Spawns the future, and drops it when this component is unmounted
+    ///
+    /// The returned [`TaskId`] can be passed to [`ScopeState::remove_future`] to cancel the task before that.
+    ///
+    /// ```rust, ignore
+    /// let task = cx.spawn(async move { poll_server().await });
+    ///
+    /// render! {
+    ///     button { onclick: move |_| cx.remove_future(task), "Stop polling" }
+    /// }
+    /// ```
+    pub fn spawn(&self, fut: impl Future<Output = ()> + 'static) -> TaskId {
+        self.context().spawn(fut)
     }
 
     /// Spawn a future that Dioxus won't clean up when this component is unmounted
     ///
-    /// This is good for tasks that need to be run after the component has been dropped.
+    /// This is good for tasks that need to be run after the component has been dropped. The task runs until it
+    /// completes, it is cancelled with [`ScopeState::remove_future`], or the VirtualDom is dropped.
     pub fn spawn_forever(&self, fut: impl Future<Output = ()> + 'static) -> TaskId {
         self.context().spawn_forever(fut)
     }
@@ -385,12 +414,7 @@ impl<'src> ScopeState {
     ///
     /// Uses the currently active [`Bump`] allocator
     pub fn raw_text(&'src self, args: Arguments) -> &'src str {
-        args.as_str().unwrap_or_else(|| {
-            use bumpalo::core_alloc::fmt::Write;
-            let mut str_buf = bumpalo::collections::String::new_in(self.bump());
-            str_buf.write_fmt(args).unwrap();
-            str_buf.into_bump_str()
-        })
+        self.bump().alloc_fmt(args)
     }
 
     /// Convert any item that implements [`IntoDynNode`] into a [`DynamicNode`] using the internal [`Bump`] allocator
@@ -444,7 +468,7 @@ impl<'src> ScopeState {
         // The current bump allocator frame must outlive the child's borrowed props
         'src: 'child,
     {
-        let vcomp = VProps::new(component, P::memoize, props);
+        let vcomp = VProps::new(component, P::memoize, P::inspect, props);
 
         // cast off the lifetime of the render return
         let as_dyn: Box<dyn AnyProps<'child> + '_> = Box::new(vcomp);
@@ -475,35 +499,31 @@ impl<'src> ScopeState {
     /// The callback must be confined to the lifetime of the ScopeState
     pub fn listener<T: 'static>(
         &'src self,
-        mut callback: impl FnMut(Event<T>) + 'src,
+        callback: impl FnMut(Event<T>) + 'src,
     ) -> AttributeValue<'src> {
-        // safety: there's no other way to create a dynamicly-dispatched bump box other than alloc + from-raw
-        // This is the suggested way to build a bumpbox
-        //
-        // In theory, we could just use regular boxes
-        let boxed: BumpBox<'src, dyn FnMut(_) + 'src> = unsafe {
-            BumpBox::from_raw(self.bump().alloc(move |event: Event<dyn Any>| {
-                if let Ok(data) = event.data.downcast::<T>() {
-                    callback(Event {
-                        propagates: event.propagates,
-                        data,
-                    });
-                }
-            }))
-        };
-
-        AttributeValue::Listener(RefCell::new(Some(boxed)))
+        self.bump().listener(callback)
     }
 
     /// Create a new [`AttributeValue`] with a value that implements [`AnyValue`]
     pub fn any_value<T: AnyValue>(&'src self, value: T) -> AttributeValue<'src> {
-        // safety: there's no other way to create a dynamicly-dispatched bump box other than alloc + from-raw
-        // This is the suggested way to build a bumpbox
-        //
-        // In theory, we could just use regular boxes
-        let boxed: BumpBox<'src, dyn AnyValue> =
-            unsafe { BumpBox::from_raw(self.bump().alloc(value)) };
-        AttributeValue::Any(RefCell::new(Some(boxed)))
+        self.bump().any_value(value)
+    }
+
+    /// Create a new [`AttributeValue`] from a template with named slots, like `"translate({x}px, {y}px)"`
+    ///
+    /// ```rust, ignore
+    /// let x = 10;
+    /// let y = 20;
+    /// cx.render(rsx! {
+    ///     div { style: cx.attr_template("transform: translate({x}px, {y}px)", &[("x", &x), ("y", &y)]) }
+    /// })
+    /// ```
+    pub fn attr_template(
+        &'src self,
+        template: &'src str,
+        slots: &[(&str, &dyn std::fmt::Display)],
+    ) -> AttributeValue<'src> {
+        AttributeValue::Template(AttributeTemplate::new(self.bump(), template, slots))
     }
 
     /// Inject an error into the nearest error boundary and quit rendering
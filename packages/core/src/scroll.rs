@@ -0,0 +1,73 @@
+use crate::{innerlude::VNode, ElementId, VirtualDom, WriteMutations};
+
+impl VirtualDom {
+    /// Restore the scroll position of elements with this key when they are remounted.
+    ///
+    /// Before a node with this key is removed, the VirtualDom sends a [`Mutation::CaptureScroll`] for its elements, and
+    /// the renderer reports how far they were scrolled with [`VirtualDom::report_scroll`]. The next time a node with
+    /// the same key is created, the VirtualDom sends a [`Mutation::RestoreScroll`] for its first element.
+    ///
+    /// Only the roots of a removed subtree are captured, so keyed nodes nested inside a removed subtree won't have
+    /// their position restored.
+    ///
+    /// ```rust, ignore
+    /// let mut dom = VirtualDom::new(app);
+    /// dom.register_scroll_restoration("article-list");
+    /// ```
+    pub fn register_scroll_restoration(&mut self, key: impl Into<String>) {
+        self.scroll_restoration.entry(key.into()).or_default();
+    }
+
+    /// Report the scroll position of an element the renderer was asked to capture with [`Mutation::CaptureScroll`].
+    ///
+    /// Reports for elements that weren't captured are ignored.
+    pub fn report_scroll(&mut self, id: ElementId, x: f64, y: f64) {
+        if let Some(key) = self.unmounted_scroll.remove(&id) {
+            self.scroll_restoration.insert(key, Some((x, y)));
+        }
+    }
+
+    /// Queue a scroll restore if this node was registered and has a known position
+    pub(crate) fn mount_scroll_restoration(&mut self, node: &VNode) {
+        if !self.capabilities.scrolling {
+            return;
+        }
+
+        let position = match node.key.and_then(|key| self.scroll_restoration.get(key)) {
+            Some(Some(position)) => *position,
+            _ => return,
+        };
+
+        if let Some(id) = node.root_ids.borrow().iter().find(|id| id.0 != 0) {
+            self.pending_scroll_restores.push((*id, position));
+        }
+    }
+
+    /// Ask the renderer for the position of this node if it was registered, before the node is removed
+    pub(crate) fn unmount_scroll_restoration<'a>(
+        &mut self,
+        to: &mut impl WriteMutations<'a>,
+        node: &VNode,
+    ) {
+        if !self.capabilities.scrolling {
+            return;
+        }
+
+        let key = match node.key {
+            Some(key) if self.scroll_restoration.contains_key(key) => key,
+            _ => return,
+        };
+
+        for id in node.root_ids.borrow().iter().filter(|id| id.0 != 0) {
+            self.unmounted_scroll.insert(*id, key.to_string());
+            to.capture_scroll(*id);
+        }
+    }
+
+    /// Restores are sent last so the elements are attached by the time the renderer scrolls them
+    pub(crate) fn flush_scroll_restores<'a>(&mut self, to: &mut impl WriteMutations<'a>) {
+        for (id, (x, y)) in self.pending_scroll_restores.drain(..) {
+            to.restore_scroll(id, x, y);
+        }
+    }
+}
@@ -0,0 +1,223 @@
+use crate::{
+    innerlude::VNode,
+    walk::{TreeVisitor, VisitedAttribute},
+    ElementId, VirtualDom,
+};
+
+/// An element in the resolved tree that matched a selector
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SelectedElement<'a> {
+    /// The tag of the element
+    pub tag: &'a str,
+
+    /// The id the renderer knows the element by, if it has one
+    pub id: Option<ElementId>,
+
+    /// The attributes of the element that have a textual value
+    pub attributes: Vec<(&'a str, String)>,
+}
+
+impl SelectedElement<'_> {
+    /// Get the value of an attribute on this element
+    pub fn attribute(&self, name: &str) -> Option<&str> {
+        self.attributes
+            .iter()
+            .find(|(attr, _)| *attr == name)
+            .map(|(_, value)| value.as_str())
+    }
+}
+
+impl VirtualDom {
+    /// Find every element in the resolved tree of this node that matches a CSS-like selector.
+    ///
+    /// This is intended for tests that need to reach into a rendered tree. Only a small subset of CSS is supported:
+    /// - `div` matches by tag
+    /// - `.card` matches by class
+    /// - `#main` matches by id
+    /// - `[lang]` and `[lang=en]` match by attribute
+    ///
+    /// These can be combined into a compound selector like `div.card[lang=en]`, and compound selectors separated by
+    /// whitespace match descendants. Elements are returned in document order.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the selector is not valid.
+    ///
+    /// ```rust, ignore
+    /// let node = match dom.base_scope().root_node() {
+    ///     RenderReturn::Ready(node) => node,
+    ///     _ => return,
+    /// };
+    ///
+    /// let buttons = dom.select(node, "form button.primary");
+    /// ```
+    #[track_caller]
+    pub fn select<'b>(&'b self, node: &'b VNode<'b>, selector: &str) -> Vec<SelectedElement<'b>> {
+        let selector = match Selector::parse(selector) {
+            Some(selector) => selector,
+            None => panic!("invalid selector {:?}", selector),
+        };
+
+        let mut visitor = SelectVisitor {
+            selector: &selector,
+            ancestors: Vec::new(),
+            selected: Vec::new(),
+        };
+        self.visit_node(node, &mut visitor);
+        visitor.selected
+    }
+}
+
+/// A list of compound selectors, each of which must match an ancestor of the element matched by the next
+struct Selector<'s> {
+    compounds: Vec<Vec<SimpleSelector<'s>>>,
+}
+
+enum SimpleSelector<'s> {
+    Tag(&'s str),
+    Class(&'s str),
+    Id(&'s str),
+    Attribute {
+        name: &'s str,
+        value: Option<&'s str>,
+    },
+}
+
+impl<'s> Selector<'s> {
+    fn parse(selector: &'s str) -> Option<Self> {
+        let compounds = selector
+            .split_whitespace()
+            .map(Self::parse_compound)
+            .collect::<Option<Vec<_>>>()?;
+
+        if compounds.is_empty() {
+            return None;
+        }
+
+        Some(Self { compounds })
+    }
+
+    fn parse_compound(mut compound: &'s str) -> Option<Vec<SimpleSelector<'s>>> {
+        let mut simple = Vec::new();
+
+        // The tag has to come first
+        let tag_len = compound.find(['.', '#', '[']).unwrap_or(compound.len());
+        if tag_len > 0 {
+            simple.push(SimpleSelector::Tag(&compound[..tag_len]));
+            compound = &compound[tag_len..];
+        }
+
+        while let Some(first) = compound.chars().next() {
+            compound = &compound[first.len_utf8()..];
+            match first {
+                '.' | '#' => {
+                    let len = compound.find(['.', '#', '[']).unwrap_or(compound.len());
+                    let name = &compound[..len];
+                    if name.is_empty() {
+                        return None;
+                    }
+                    simple.push(match first {
+                        '.' => SimpleSelector::Class(name),
+                        _ => SimpleSelector::Id(name),
+                    });
+                    compound = &compound[len..];
+                }
+                '[' => {
+                    let end = compound.find(']')?;
+                    let (name, value) = match compound[..end].split_once('=') {
+                        Some((name, value)) => {
+                            (name, Some(value.trim_matches(|c| c == '"' || c == '\'')))
+                        }
+                        None => (&compound[..end], None),
+                    };
+                    if name.is_empty() {
+                        return None;
+                    }
+                    simple.push(SimpleSelector::Attribute { name, value });
+                    compound = &compound[end + 1..];
+                }
+                _ => return None,
+            }
+        }
+
+        Some(simple)
+    }
+}
+
+impl SimpleSelector<'_> {
+    fn matches(&self, element: &SelectedElement) -> bool {
+        match self {
+            SimpleSelector::Tag(tag) => element.tag == *tag,
+            SimpleSelector::Class(class) => element
+                .attributes
+                .iter()
+                .filter(|(name, _)| *name == "class")
+                .any(|(_, value)| value.split_whitespace().any(|c| c == *class)),
+            SimpleSelector::Id(id) => element.attribute("id") == Some(*id),
+            SimpleSelector::Attribute { name, value } => match value {
+                Some(value) => element
+                    .attributes
+                    .iter()
+                    .any(|(attr, attr_value)| attr == name && attr_value == value),
+                None => element.attribute(name).is_some(),
+            },
+        }
+    }
+}
+
+fn matches_compound(compound: &[SimpleSelector], element: &SelectedElement) -> bool {
+    compound.iter().all(|simple| simple.matches(element))
+}
+
+struct SelectVisitor<'a, 'b> {
+    selector: &'a Selector<'a>,
+    ancestors: Vec<SelectedElement<'b>>,
+    selected: Vec<SelectedElement<'b>>,
+}
+
+impl SelectVisitor<'_, '_> {
+    fn is_match(&self, element: &SelectedElement) -> bool {
+        let (last, rest) = match self.selector.compounds.split_last() {
+            Some(split) => split,
+            None => return false,
+        };
+        if !matches_compound(last, element) {
+            return false;
+        }
+
+        // Match the remaining compounds against the nearest ancestors that fit them
+        let mut ancestors = self.ancestors.iter().rev();
+        rest.iter()
+            .rev()
+            .all(|compound| ancestors.any(|ancestor| matches_compound(compound, ancestor)))
+    }
+}
+
+impl<'b> TreeVisitor<'b> for SelectVisitor<'_, 'b> {
+    fn enter_element(
+        &mut self,
+        tag: &'b str,
+        _namespace: Option<&'b str>,
+        id: Option<ElementId>,
+        attrs: &[VisitedAttribute<'b>],
+    ) {
+        let element = SelectedElement {
+            tag,
+            id,
+            attributes: attrs
+                .iter()
+                .filter_map(|attr| Some((attr.name(), attr.text_value(attrs)?)))
+                .collect(),
+        };
+
+        if self.is_match(&element) {
+            self.selected.push(element.clone());
+        }
+
+        self.ancestors.push(element);
+    }
+
+    fn exit_element(&mut self) {
+        self.ancestors.pop();
+    }
+}
@@ -0,0 +1,36 @@
+use crate::{innerlude::InitialAttribute, Attribute};
+
+/// The attributes that control the selection of a text field: `selection_start`, `selection_end` and
+/// `selection_direction`. A cursor is a selection that starts and ends at the same place.
+///
+/// Setting the value of a text field moves its selection, so the VirtualDom always sends the selection attributes of
+/// an element after its other attributes, both when the element is created and when it is diffed. A renderer that
+/// applies mutations in order never has the selection clobbered by a value update.
+///
+/// ```rust, ignore
+/// rsx! {
+///     textarea {
+///         value: "{text}",
+///         selection_start: cursor as i64,
+///         selection_end: cursor as i64,
+///     }
+/// }
+/// ```
+pub const SELECTION_ATTRIBUTES: [&str; 3] =
+    ["selection_start", "selection_end", "selection_direction"];
+
+fn is_selection(name: &str, namespace: Option<&str>) -> bool {
+    namespace.is_none() && SELECTION_ATTRIBUTES.contains(&name)
+}
+
+impl Attribute<'_> {
+    pub(crate) fn is_selection(&self) -> bool {
+        is_selection(self.name, self.namespace)
+    }
+}
+
+impl InitialAttribute<'_> {
+    pub(crate) fn is_selection(&self) -> bool {
+        is_selection(self.name, self.ns)
+    }
+}
@@ -0,0 +1,188 @@
+use crate::{
+    innerlude::VNode,
+    walk::{TreeVisitor, VisitedAttribute},
+    AttributeValue, ElementId, VirtualDom,
+};
+
+/// An owned copy of a node in the resolved tree that can be sent to and shared between threads.
+///
+/// [`VNode`]s borrow from the arena of the component that rendered them and use [`std::cell::Cell`]s to keep track of
+/// their mounted ids, so they have to stay on the thread of their VirtualDom. A `SendableNode` copies everything out,
+/// descending through fragments and into the rendered output of components, so it can be inspected anywhere.
+#[derive(Debug, Clone, PartialEq)]
+pub enum SendableNode {
+    /// An element with its attributes and children
+    Element {
+        /// The tag of the element, like `div`
+        tag: String,
+
+        /// The namespace of the element, if it has one
+        namespace: Option<String>,
+
+        /// The id the renderer knows the element by, if it has one
+        id: Option<ElementId>,
+
+        /// The static and dynamic attributes of the element, in the order they were declared
+        attributes: Vec<SendableAttribute>,
+
+        /// The children of the element
+        children: Vec<SendableNode>,
+    },
+
+    /// A text node
+    Text {
+        /// The text of the node
+        value: String,
+
+        /// The id the renderer knows the node by, if it has one
+        id: Option<ElementId>,
+    },
+
+    /// A placeholder for a node that isn't rendered
+    Placeholder {
+        /// The id the renderer knows the placeholder by, if it has one
+        id: Option<ElementId>,
+    },
+}
+
+/// An owned copy of an attribute on a [`SendableNode::Element`]
+#[derive(Debug, Clone, PartialEq)]
+pub struct SendableAttribute {
+    /// The name of the attribute
+    pub name: String,
+
+    /// The namespace of the attribute, if it has one
+    pub namespace: Option<String>,
+
+    /// The value of the attribute
+    pub value: SendableValue,
+}
+
+/// The value of a [`SendableAttribute`]
+///
+/// Values that only make sense on the thread of the VirtualDom are left as markers.
+#[derive(Debug, Clone, PartialEq)]
+pub enum SendableValue {
+    /// Text, including attribute templates, style maps and derived attributes as the renderer would write them
+    Text(String),
+
+    /// A float
+    Float(f64),
+
+    /// Signed integer
+    Int(i64),
+
+    /// Boolean
+    Bool(bool),
+
+    /// Raw binary data
+    Bytes(Vec<u8>),
+
+    /// An event listener. The closure itself is left behind.
+    Listener,
+
+    /// An arbitrary value. The value itself is left behind.
+    Any,
+
+    /// No value
+    None,
+}
+
+impl<'a> VNode<'a> {
+    /// Copy the resolved tree of this node into owned nodes that can be sent to another thread.
+    ///
+    /// The VirtualDom is used to follow components into what they rendered. One node is returned for each root of the
+    /// resolved tree.
+    ///
+    /// ```rust, ignore
+    /// let roots = node.to_sendable(&dom);
+    /// std::thread::spawn(move || inspect(roots));
+    /// ```
+    pub fn to_sendable(&'a self, dom: &'a VirtualDom) -> Vec<SendableNode> {
+        let mut builder = SendableBuilder::default();
+        dom.visit_node(self, &mut builder);
+        builder.roots
+    }
+}
+
+#[derive(Default)]
+struct SendableBuilder {
+    roots: Vec<SendableNode>,
+    // The elements we are currently inside of
+    open: Vec<SendableNode>,
+}
+
+impl SendableBuilder {
+    fn push(&mut self, node: SendableNode) {
+        match self.open.last_mut() {
+            Some(SendableNode::Element { children, .. }) => children.push(node),
+            _ => self.roots.push(node),
+        }
+    }
+}
+
+impl<'b> TreeVisitor<'b> for SendableBuilder {
+    fn enter_element(
+        &mut self,
+        tag: &'b str,
+        namespace: Option<&'b str>,
+        id: Option<ElementId>,
+        attrs: &[VisitedAttribute<'b>],
+    ) {
+        let attributes = attrs
+            .iter()
+            .map(|attr| SendableAttribute {
+                name: attr.name().to_string(),
+                namespace: attr.namespace().map(str::to_string),
+                value: sendable_value(attr, attrs),
+            })
+            .collect();
+
+        self.open.push(SendableNode::Element {
+            tag: tag.to_string(),
+            namespace: namespace.map(str::to_string),
+            id,
+            attributes,
+            children: Vec::new(),
+        });
+    }
+
+    fn exit_element(&mut self) {
+        if let Some(element) = self.open.pop() {
+            self.push(element);
+        }
+    }
+
+    fn text(&mut self, value: &'b str, id: Option<ElementId>) {
+        self.push(SendableNode::Text {
+            value: value.to_string(),
+            id,
+        });
+    }
+
+    fn placeholder(&mut self, id: Option<ElementId>) {
+        self.push(SendableNode::Placeholder { id });
+    }
+}
+
+fn sendable_value<'b>(
+    attr: &VisitedAttribute<'b>,
+    element: &[VisitedAttribute<'b>],
+) -> SendableValue {
+    if let VisitedAttribute::Dynamic(attr) = attr {
+        match attr.value {
+            AttributeValue::Float(value) => return SendableValue::Float(value),
+            AttributeValue::Int(value) => return SendableValue::Int(value),
+            AttributeValue::Bool(value) => return SendableValue::Bool(value),
+            AttributeValue::Bytes(value) => return SendableValue::Bytes(value.to_vec()),
+            AttributeValue::Listener(_) => return SendableValue::Listener,
+            AttributeValue::Any(_) => return SendableValue::Any,
+            _ => {}
+        }
+    }
+
+    match attr.text_value(element) {
+        Some(value) => SendableValue::Text(value),
+        None => SendableValue::None,
+    }
+}
@@ -0,0 +1,292 @@
+use std::ops::Deref;
+
+use crate::{
+    events::listener_event,
+    innerlude::{BorrowedAttributeValue, Mutation},
+    SendableAttribute, SendableNode, SendableValue,
+};
+
+/// Mutations computed from [`SendableNode`]s by [`diff_owned`], which can be sent back to the thread of the renderer
+#[derive(Debug, PartialEq)]
+pub struct SendableMutations<'a> {
+    edits: Vec<Mutation<'a>>,
+}
+
+// Safety: the only part of a mutation that can't be sent to another thread is a borrowed `Any` attribute value.
+// Those can't be created from a sendable node, so `diff_owned` never puts one in the list.
+unsafe impl Send for SendableMutations<'_> {}
+
+impl<'a> Deref for SendableMutations<'a> {
+    type Target = [Mutation<'a>];
+
+    fn deref(&self) -> &Self::Target {
+        &self.edits
+    }
+}
+
+impl<'a> SendableMutations<'a> {
+    /// Take the mutations out of the list
+    pub fn into_edits(self) -> Vec<Mutation<'a>> {
+        self.edits
+    }
+}
+
+/// Diff two snapshots of the resolved tree without a VirtualDom, so the work can happen on any thread.
+///
+/// The mutations target the elements of the old tree by their [`crate::ElementId`], borrow any new text from the new
+/// tree, and borrow the names of removed attributes and listeners from the old tree. This only works if the two trees have the same structure: the same elements with the same tags and the
+/// same number of children. Attributes, listeners and text can all change.
+///
+/// Creating new elements requires the templates they came from, so if the structure changed, or a node that changed
+/// doesn't have an id, `None` is returned and the diff has to run in the VirtualDom instead.
+///
+/// ```rust, ignore
+/// let edits = std::thread::scope(|s| s.spawn(|| diff_owned(&old, &new)).join().unwrap());
+/// if let Some(edits) = edits {
+///     for edit in edits.iter() {
+///         renderer.apply(edit);
+///     }
+/// }
+/// ```
+pub fn diff_owned<'a>(
+    old: &'a [SendableNode],
+    new: &'a [SendableNode],
+) -> Option<SendableMutations<'a>> {
+    let mut diff = OwnedDiff::default();
+    diff.diff_roots(old, new)?;
+    Some(SendableMutations { edits: diff.edits })
+}
+
+/// Diff two snapshots of the resolved tree like [`diff_owned`], and explain why each mutation was emitted.
+///
+/// Every mutation gets one line of explanation in the same order, like `set the text at path [0, 2] because it
+/// changed from "a" to "b"`. Roots where nothing changed are noted as skipped. The explanations are meant for bug
+/// reports and debugging, their wording can change between versions.
+///
+/// ```rust, ignore
+/// if let Some((edits, explanations)) = diff_explained(&old, &new) {
+///     for explanation in explanations {
+///         tracing::debug!("{explanation}");
+///     }
+/// }
+/// ```
+pub fn diff_explained<'a>(
+    old: &'a [SendableNode],
+    new: &'a [SendableNode],
+) -> Option<(SendableMutations<'a>, Vec<String>)> {
+    let mut diff = OwnedDiff {
+        explanations: Some(Vec::new()),
+        ..Default::default()
+    };
+    diff.diff_roots(old, new)?;
+    Some((
+        SendableMutations { edits: diff.edits },
+        diff.explanations.unwrap_or_default(),
+    ))
+}
+
+#[derive(Default)]
+struct OwnedDiff<'a> {
+    edits: Vec<Mutation<'a>>,
+    // Only collected when the diff is explained
+    explanations: Option<Vec<String>>,
+    // The child indices leading to the node being diffed
+    path: Vec<usize>,
+}
+
+impl<'a> OwnedDiff<'a> {
+    fn push(&mut self, edit: Mutation<'a>, explain: impl FnOnce(&[usize]) -> String) {
+        if let Some(explanations) = &mut self.explanations {
+            explanations.push(explain(&self.path));
+        }
+        self.edits.push(edit);
+    }
+
+    fn diff_roots(&mut self, old: &'a [SendableNode], new: &'a [SendableNode]) -> Option<()> {
+        if old.len() != new.len() {
+            return None;
+        }
+
+        for (idx, (old, new)) in old.iter().zip(new.iter()).enumerate() {
+            let edits = self.edits.len();
+            self.path.push(idx);
+            self.diff_node(old, new)?;
+            self.path.pop();
+
+            if let Some(explanations) = &mut self.explanations {
+                if self.edits.len() == edits {
+                    explanations.push(format!(
+                        "skipped root {} because nothing in it changed",
+                        idx
+                    ));
+                }
+            }
+        }
+
+        Some(())
+    }
+
+    fn diff_children(&mut self, old: &'a [SendableNode], new: &'a [SendableNode]) -> Option<()> {
+        if old.len() != new.len() {
+            return None;
+        }
+
+        for (idx, (old, new)) in old.iter().zip(new.iter()).enumerate() {
+            self.path.push(idx);
+            self.diff_node(old, new)?;
+            self.path.pop();
+        }
+
+        Some(())
+    }
+
+    fn diff_node(&mut self, old: &'a SendableNode, new: &'a SendableNode) -> Option<()> {
+        match (old, new) {
+            (
+                SendableNode::Element {
+                    tag: old_tag,
+                    namespace: old_namespace,
+                    id,
+                    attributes: old_attributes,
+                    children: old_children,
+                },
+                SendableNode::Element {
+                    tag: new_tag,
+                    namespace: new_namespace,
+                    attributes: new_attributes,
+                    children: new_children,
+                    ..
+                },
+            ) if old_tag == new_tag && old_namespace == new_namespace => {
+                for new_attr in new_attributes {
+                    let old_value =
+                        find_attribute(old_attributes, new_attr).map(|attr| &attr.value);
+                    if old_value == Some(&new_attr.value) {
+                        continue;
+                    }
+
+                    let id = (*id)?;
+                    let name = &new_attr.name;
+                    match &new_attr.value {
+                        // Listeners are swapped in place, so they only need to be attached once
+                        SendableValue::Listener if old_value.is_some() => {}
+                        SendableValue::Listener => self.push(
+                            Mutation::NewEventListener {
+                                name: listener_name(name),
+                                id,
+                            },
+                            |path| {
+                                format!(
+                                    "added listener `{}` to <{}> at path {:?} because it is new",
+                                    name, new_tag, path
+                                )
+                            },
+                        ),
+                        value => self.push(
+                            Mutation::SetAttribute {
+                                name,
+                                value: borrowed_value(value)?,
+                                id,
+                                ns: new_attr.namespace.as_deref(),
+                            },
+                            |path| match old_value {
+                                Some(old_value) => format!(
+                                    "set attribute `{}` on <{}> at path {:?} because its value changed from {:?} to {:?}",
+                                    name, new_tag, path, old_value, value
+                                ),
+                                None => format!(
+                                    "set attribute `{}` on <{}> at path {:?} because it is new",
+                                    name, new_tag, path
+                                ),
+                            },
+                        ),
+                    }
+                }
+
+                for old_attr in old_attributes {
+                    if find_attribute(new_attributes, old_attr).is_some() {
+                        continue;
+                    }
+
+                    let id = (*id)?;
+                    let name = &old_attr.name;
+                    match old_attr.value {
+                        SendableValue::Listener => self.push(
+                            Mutation::RemoveEventListener {
+                                name: listener_name(name),
+                                id,
+                            },
+                            |path| {
+                                format!(
+                                    "removed listener `{}` from <{}> at path {:?} because it is gone",
+                                    name, new_tag, path
+                                )
+                            },
+                        ),
+                        _ => self.push(
+                            Mutation::SetAttribute {
+                                name,
+                                value: BorrowedAttributeValue::None,
+                                id,
+                                ns: old_attr.namespace.as_deref(),
+                            },
+                            |path| {
+                                format!(
+                                    "removed attribute `{}` from <{}> at path {:?} because it is gone",
+                                    name, new_tag, path
+                                )
+                            },
+                        ),
+                    }
+                }
+
+                self.diff_children(old_children, new_children)
+            }
+            (SendableNode::Text { value: old, id }, SendableNode::Text { value: new, .. }) => {
+                if old != new {
+                    self.push(
+                        Mutation::SetText {
+                            value: new,
+                            id: (*id)?,
+                        },
+                        |path| {
+                            format!(
+                                "set the text at path {:?} because it changed from {:?} to {:?}",
+                                path, old, new
+                            )
+                        },
+                    );
+                }
+                Some(())
+            }
+            (SendableNode::Placeholder { .. }, SendableNode::Placeholder { .. }) => Some(()),
+            _ => None,
+        }
+    }
+}
+
+fn find_attribute<'a>(
+    attributes: &'a [SendableAttribute],
+    like: &SendableAttribute,
+) -> Option<&'a SendableAttribute> {
+    attributes
+        .iter()
+        .find(|attr| attr.name == like.name && attr.namespace == like.namespace)
+}
+
+fn borrowed_value(value: &SendableValue) -> Option<BorrowedAttributeValue<'_>> {
+    Some(match value {
+        SendableValue::Text(value) => BorrowedAttributeValue::Text(value),
+        SendableValue::Float(value) => BorrowedAttributeValue::Float(*value),
+        SendableValue::Int(value) => BorrowedAttributeValue::Int(*value),
+        SendableValue::Bool(value) => BorrowedAttributeValue::Bool(*value),
+        SendableValue::Bytes(value) => BorrowedAttributeValue::Bytes(value),
+        SendableValue::None => BorrowedAttributeValue::None,
+        // The value was left behind on the thread of the VirtualDom
+        SendableValue::Listener | SendableValue::Any => return None,
+    })
+}
+
+fn listener_name(name: &str) -> &str {
+    listener_event(name).0
+}
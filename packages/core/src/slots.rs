@@ -0,0 +1,228 @@
+use std::{
+    cell::{Cell, RefCell},
+    rc::Rc,
+};
+
+use rustc_hash::{FxHashMap, FxHashSet};
+
+use crate::{
+    innerlude::{DynamicNode, Mutations, ScopeState, VNode, VText, ValueId},
+    nodes::RenderReturn,
+    ElementId, ScopeId, VirtualDom, WriteMutations,
+};
+
+/// The text slots each scope created during its last render, and the values each of them read
+#[derive(Default)]
+pub(crate) struct SlotDependencies {
+    slots: RefCell<FxHashMap<ScopeId, Vec<Slot>>>,
+    readers: RefCell<FxHashMap<ValueId, FxHashSet<(ScopeId, usize)>>>,
+
+    // The slot that is being evaluated, which reads are recorded for instead of its scope
+    current: Cell<Option<(ScopeId, usize)>>,
+}
+
+struct Slot {
+    compute: Rc<dyn Fn(&ScopeState) -> String>,
+    reads: FxHashSet<ValueId>,
+
+    // The address of the text the scope rendered for the slot, which finds its text node in the rendered VNode
+    address: *const u8,
+
+    // The text the renderer has
+    text: String,
+}
+
+impl SlotDependencies {
+    /// Record a read for the slot that is being evaluated. Returns false if no slot is being evaluated.
+    pub(crate) fn read(&self, value: ValueId) -> bool {
+        let slot = match self.current.get() {
+            Some(slot) => slot,
+            None => return false,
+        };
+
+        self.readers
+            .borrow_mut()
+            .entry(value)
+            .or_default()
+            .insert(slot);
+        self.slot_mut(slot, |slot| slot.reads.insert(value));
+        true
+    }
+
+    /// Forget every slot of the scope. Called before it renders again and when it is dropped.
+    pub(crate) fn forget_scope(&self, scope: ScopeId) {
+        let slots = match self.slots.borrow_mut().remove(&scope) {
+            Some(slots) => slots,
+            None => return,
+        };
+
+        for (index, slot) in slots.into_iter().enumerate() {
+            self.forget_reads((scope, index), slot.reads);
+        }
+    }
+
+    // Run the function of a slot and record what it reads
+    fn evaluate(&self, cx: &ScopeState, index: usize) -> Option<String> {
+        let slot = (cx.scope_id(), index);
+        let reads = self.slot_mut(slot, |slot| std::mem::take(&mut slot.reads))?;
+        self.forget_reads(slot, reads);
+
+        let compute = self.slot_mut(slot, |slot| slot.compute.clone())?;
+        let parent = self.current.replace(Some(slot));
+        let text = compute(cx);
+        self.current.set(parent);
+
+        Some(text)
+    }
+
+    fn forget_reads(&self, slot: (ScopeId, usize), reads: FxHashSet<ValueId>) {
+        let mut readers = self.readers.borrow_mut();
+        for value in reads {
+            if let Some(slots) = readers.get_mut(&value) {
+                slots.remove(&slot);
+                if slots.is_empty() {
+                    readers.remove(&value);
+                }
+            }
+        }
+    }
+
+    fn slot_mut<R>(
+        &self,
+        (scope, index): (ScopeId, usize),
+        f: impl FnOnce(&mut Slot) -> R,
+    ) -> Option<R> {
+        self.slots
+            .borrow_mut()
+            .get_mut(&scope)
+            .and_then(|slots| slots.get_mut(index))
+            .map(f)
+    }
+}
+
+impl ScopeState {
+    /// Create a text node whose text can be re-evaluated on its own, without rendering the component again.
+    ///
+    /// The values the function reads with [`ScopeState::read_value`] are recorded for the slot instead of the
+    /// component. When one of them changes, [`VirtualDom::render_slots`] runs the function again and updates only the
+    /// text of the slot. The function runs outside of the component's render, so it should only read values, not
+    /// use hooks.
+    ///
+    /// ```rust, ignore
+    /// fn Price(cx: Scope<PriceProps>) -> Element {
+    ///     let product = cx.props.product;
+    ///     cx.render(rsx! {
+    ///         span { cx.text_slot(move |cx| {
+    ///             cx.read_value(ValueId(product));
+    ///             format!("{}", store.price(product))
+    ///         }) }
+    ///     })
+    /// }
+    /// ```
+    pub fn text_slot(&self, compute: impl Fn(&ScopeState) -> String + 'static) -> DynamicNode<'_> {
+        let slots = &self.runtime.dependencies.slots;
+        let index = {
+            let mut slots = slots.slots.borrow_mut();
+            let scope_slots = slots.entry(self.scope_id()).or_default();
+            scope_slots.push(Slot {
+                compute: Rc::new(compute),
+                reads: FxHashSet::default(),
+                address: std::ptr::null(),
+                text: String::new(),
+            });
+            scope_slots.len() - 1
+        };
+
+        let text = slots.evaluate(self, index).unwrap_or_default();
+
+        // Text is copied with a byte after it, so its address is unique even if the text is empty
+        let mut buffer =
+            bumpalo::collections::String::with_capacity_in(text.len() + 1, self.bump());
+        buffer.push_str(&text);
+        buffer.push(' ');
+        let value = &buffer.into_bump_str()[..text.len()];
+
+        slots.slot_mut((self.scope_id(), index), |slot| {
+            slot.address = value.as_ptr();
+            slot.text = text;
+        });
+
+        DynamicNode::Text(VText::new(value))
+    }
+}
+
+impl VirtualDom {
+    /// Get the text slots that read the value when they were last evaluated, in ascending order.
+    pub fn affected_slots(&self, value: ValueId) -> Vec<(ScopeId, usize)> {
+        let mut slots: Vec<_> = self
+            .runtime
+            .dependencies
+            .slots
+            .readers
+            .borrow()
+            .get(&value)
+            .map(|slots| slots.iter().copied().collect())
+            .unwrap_or_default();
+        slots.sort_unstable();
+        slots
+    }
+
+    /// Re-evaluate the [text slots](ScopeState::text_slot) that read a value, without rendering their components.
+    ///
+    /// Only slots whose text changed are sent to the renderer. Components that read the value outside of a slot still
+    /// need to be marked dirty, see [`VirtualDom::affected_scopes`].
+    ///
+    /// ```rust, ignore
+    /// store.set_price(product, 10);
+    /// renderer.apply(dom.render_slots(ValueId(product)));
+    /// ```
+    pub fn render_slots(&mut self, value: ValueId) -> Mutations<'_> {
+        let mut mutations = Mutations::default();
+        for (scope, index) in self.affected_slots(value) {
+            let slots = &self.runtime.dependencies.slots;
+            let cx = match self.scopes.get(scope.0) {
+                Some(cx) => cx,
+                None => continue,
+            };
+            let text = match slots.evaluate(cx, index) {
+                Some(text) => text,
+                None => continue,
+            };
+
+            // Only the text the renderer doesn't have yet is sent
+            let address = slots.slot_mut((scope, index), |slot| {
+                let changed = slot.text != text;
+                slot.text = text.clone();
+                Some(slot.address).filter(|_| changed)
+            });
+            let id = match (address.flatten(), cx.try_root_node()) {
+                (Some(address), Some(RenderReturn::Ready(node))) => find_text(node, address),
+                _ => None,
+            };
+
+            if let Some(id) = id {
+                // The text node in the VNode still has its old text, so the next diff has to send the text no matter
+                // what it is
+                self.stale_text.insert(id);
+
+                let value = cx.current_frame().bump().alloc_str(&text);
+                let value = unsafe { std::mem::transmute::<&str, &'static str>(value) };
+                mutations.set_text(value, id);
+            }
+        }
+
+        self.flush_batch(&mut mutations);
+        mutations
+    }
+}
+
+// Find the mounted text node with the text at this address, in this node and its fragments
+fn find_text(node: &VNode, address: *const u8) -> Option<ElementId> {
+    node.dynamic_nodes.iter().find_map(|dynamic| match dynamic {
+        DynamicNode::Text(text) if text.value.as_ptr() == address => text.id.get(),
+        DynamicNode::Fragment(children) => {
+            children.iter().find_map(|child| find_text(child, address))
+        }
+        _ => None,
+    })
+}
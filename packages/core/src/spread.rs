@@ -0,0 +1,164 @@
+use std::iter::FromIterator;
+
+use crate::{
+    innerlude::{Attribute, AttributeValue, InitialAttribute, ScopeState},
+    style_map::properties_attributes,
+    ElementId, VirtualDom, WriteMutations,
+};
+
+/// The name of the dynamic attribute that holds the attributes spread onto an element. The renderer never sees it.
+pub(crate) const SPREAD_ATTRIBUTES: &str = "..";
+
+/// A list of attributes that can be spread onto an element with `..attributes` in rsx, so a wrapper component can
+/// forward arbitrary attributes to the element it renders.
+///
+/// Attributes written out on the element always win over spread attributes with the same name. Only plain attributes
+/// can be spread: listeners still need to be written on the element.
+///
+/// ```rust, ignore
+/// #[derive(Props, PartialEq)]
+/// struct ButtonProps {
+///     attributes: SpreadAttributes,
+/// }
+///
+/// fn Button(cx: Scope<ButtonProps>) -> Element {
+///     cx.render(rsx! {
+///         button { class: "button", ..cx.props.attributes, "Click me" }
+///     })
+/// }
+///
+/// rsx! {
+///     Button { attributes: SpreadAttributes::new().with("id", "submit").with("title", "Submit the form") }
+/// }
+/// ```
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct SpreadAttributes {
+    attributes: Vec<(String, String)>,
+}
+
+impl SpreadAttributes {
+    /// Create an empty list of attributes
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add an attribute to the list, replacing any attribute with the same name
+    pub fn with(mut self, name: impl Into<String>, value: impl ToString) -> Self {
+        self.insert(name, value);
+        self
+    }
+
+    /// Set an attribute, replacing any attribute with the same name
+    pub fn insert(&mut self, name: impl Into<String>, value: impl ToString) {
+        let name = name.into();
+        let value = value.to_string();
+        match self.attributes.iter_mut().find(|(n, _)| *n == name) {
+            Some((_, old)) => *old = value,
+            None => self.attributes.push((name, value)),
+        }
+    }
+
+    /// Remove an attribute from the list, returning its value
+    pub fn remove(&mut self, name: &str) -> Option<String> {
+        let idx = self.attributes.iter().position(|(n, _)| n == name)?;
+        Some(self.attributes.remove(idx).1)
+    }
+
+    /// Get the value of an attribute in the list
+    pub fn get(&self, name: &str) -> Option<&str> {
+        self.attributes
+            .iter()
+            .find(|(n, _)| n == name)
+            .map(|(_, value)| value.as_str())
+    }
+
+    /// Iterate over the names and values of the attributes in the order they were added
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &str)> {
+        self.attributes
+            .iter()
+            .map(|(name, value)| (name.as_str(), value.as_str()))
+    }
+
+    /// The number of attributes in the list
+    pub fn len(&self) -> usize {
+        self.attributes.len()
+    }
+
+    /// Check if the list has no attributes
+    pub fn is_empty(&self) -> bool {
+        self.attributes.is_empty()
+    }
+}
+
+impl<K: Into<String>, V: ToString> Extend<(K, V)> for SpreadAttributes {
+    fn extend<T: IntoIterator<Item = (K, V)>>(&mut self, iter: T) {
+        for (name, value) in iter {
+            self.insert(name, value);
+        }
+    }
+}
+
+impl<K: Into<String>, V: ToString> FromIterator<(K, V)> for SpreadAttributes {
+    fn from_iter<T: IntoIterator<Item = (K, V)>>(iter: T) -> Self {
+        let mut attributes = Self::new();
+        attributes.extend(iter);
+        attributes
+    }
+}
+
+impl ScopeState {
+    /// Create the dynamic attribute for `..attributes` in rsx
+    ///
+    /// Attributes named in `explicit` are written out on the element, so they are left out of the spread.
+    pub fn spread_attr<'src>(
+        &'src self,
+        attributes: &SpreadAttributes,
+        explicit: &[&str],
+    ) -> Attribute<'src> {
+        let bump = self.bump();
+        let mut spread = bumpalo::collections::Vec::new_in(bump);
+        for (name, value) in attributes.iter() {
+            if !explicit.contains(&name) {
+                spread.push((&*bump.alloc_str(name), &*bump.alloc_str(value)));
+            }
+        }
+
+        Attribute {
+            name: SPREAD_ATTRIBUTES,
+            namespace: None,
+            volatile: false,
+            mounted_element: Default::default(),
+            value: AttributeValue::Spread(spread.into_bump_slice()),
+        }
+    }
+}
+
+impl VirtualDom {
+    /// Send every spread attribute to the renderer
+    pub(crate) fn set_spread<'a>(
+        &mut self,
+        to: &mut impl WriteMutations<'a>,
+        attributes: &[(&str, &str)],
+        id: ElementId,
+    ) {
+        self.set_properties(to, attributes, None, id)
+    }
+
+    /// Send only the spread attributes that were added, changed, or removed between two renders
+    pub(crate) fn diff_spread<'a>(
+        &mut self,
+        to: &mut impl WriteMutations<'a>,
+        old: &[(&str, &str)],
+        new: &[(&str, &str)],
+        id: ElementId,
+    ) {
+        self.diff_properties(to, old, new, None, id)
+    }
+}
+
+/// The spread attributes as the attributes an element is created with
+pub(crate) fn spread_attributes<'a>(
+    attributes: &'a [(&'a str, &'a str)],
+) -> impl Iterator<Item = InitialAttribute<'a>> {
+    properties_attributes(attributes, None)
+}
@@ -0,0 +1,123 @@
+use crate::{
+    innerlude::{BorrowedAttributeValue, InitialAttribute},
+    ElementId, VirtualDom, WriteMutations,
+};
+
+/// Style properties are sent to the renderer as attributes in this namespace
+const STYLE_NAMESPACE: Option<&str> = Some("style");
+
+impl VirtualDom {
+    /// Send every property of a style map to the renderer
+    pub(crate) fn set_style_map<'a>(
+        &mut self,
+        to: &mut impl WriteMutations<'a>,
+        properties: &[(&str, &str)],
+        id: ElementId,
+    ) {
+        self.set_properties(to, properties, STYLE_NAMESPACE, id)
+    }
+
+    /// Send only the properties that were added, changed, or removed between two style maps
+    pub(crate) fn diff_style_map<'a>(
+        &mut self,
+        to: &mut impl WriteMutations<'a>,
+        old: &[(&str, &str)],
+        new: &[(&str, &str)],
+        id: ElementId,
+    ) {
+        self.diff_properties(to, old, new, STYLE_NAMESPACE, id)
+    }
+
+    /// Send a list of name and value pairs to the renderer as attributes in one namespace
+    pub(crate) fn set_properties<'a>(
+        &mut self,
+        to: &mut impl WriteMutations<'a>,
+        properties: &[(&str, &str)],
+        ns: Option<&'static str>,
+        id: ElementId,
+    ) {
+        for (name, value) in properties {
+            self.set_property(to, name, BorrowedAttributeValue::Text(value), ns, id);
+        }
+    }
+
+    /// Send only the pairs that were added, changed, or removed between two lists
+    pub(crate) fn diff_properties<'a>(
+        &mut self,
+        to: &mut impl WriteMutations<'a>,
+        old: &[(&str, &str)],
+        new: &[(&str, &str)],
+        ns: Option<&'static str>,
+        id: ElementId,
+    ) {
+        for (name, value) in new {
+            if property(old, name) != Some(value) {
+                self.set_property(to, name, BorrowedAttributeValue::Text(value), ns, id);
+            }
+        }
+
+        for (name, _) in old {
+            if property(new, name).is_none() {
+                self.set_property(to, name, BorrowedAttributeValue::None, ns, id);
+            }
+        }
+    }
+
+    fn set_property<'a>(
+        &mut self,
+        to: &mut impl WriteMutations<'a>,
+        name: &str,
+        value: BorrowedAttributeValue,
+        ns: Option<&'static str>,
+        id: ElementId,
+    ) {
+        // Safety: we promise not to re-alias this text later on after committing it to the mutation
+        let name: &'static str = unsafe { std::mem::transmute(name) };
+        let value: BorrowedAttributeValue<'static> = unsafe { std::mem::transmute(value) };
+
+        to.set_attribute(name, ns, &value, id);
+    }
+}
+
+/// The properties of a style map as the attributes an element is created with
+pub(crate) fn style_map_attributes<'a>(
+    properties: &'a [(&'a str, &'a str)],
+) -> impl Iterator<Item = InitialAttribute<'a>> {
+    properties_attributes(properties, STYLE_NAMESPACE)
+}
+
+/// A list of name and value pairs as the attributes an element is created with
+pub(crate) fn properties_attributes<'a>(
+    properties: &'a [(&'a str, &'a str)],
+    ns: Option<&'static str>,
+) -> impl Iterator<Item = InitialAttribute<'a>> {
+    properties
+        .iter()
+        .map(move |(name, value)| InitialAttribute {
+            name,
+            value: BorrowedAttributeValue::Text(value),
+            ns,
+        })
+}
+
+/// The number of pairs [`VirtualDom::diff_properties`] would send to the renderer
+pub(crate) fn property_changes(old: &[(&str, &str)], new: &[(&str, &str)]) -> usize {
+    let changed = new
+        .iter()
+        .filter(|(name, value)| property(old, name) != Some(value))
+        .count();
+    let removed = old
+        .iter()
+        .filter(|(name, _)| property(new, name).is_none())
+        .count();
+    changed + removed
+}
+
+// Later properties win, like they would in a style string
+fn property<'a>(properties: &[(&'a str, &'a str)], name: &str) -> Option<&'a str> {
+    properties
+        .iter()
+        .rev()
+        .find(|(property, _)| *property == name)
+        .map(|(_, value)| *value)
+}
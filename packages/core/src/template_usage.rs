@@ -0,0 +1,60 @@
+use crate::{innerlude::VNode, nodes::TemplateId, VirtualDom};
+
+impl VirtualDom {
+    /// Count a node that was just mounted as a use of its template
+    pub(crate) fn mount_template_use(&mut self, node: &VNode) {
+        *self
+            .template_usage
+            .entry(node.template.get().name)
+            .or_default() += 1;
+    }
+
+    /// Stop counting a node that was removed as a use of its template
+    pub(crate) fn unmount_template_use(&mut self, node: &VNode) {
+        let name = node.template.get().name;
+        if let Some(count) = self.template_usage.get_mut(name) {
+            *count -= 1;
+            if *count == 0 {
+                self.template_usage.remove(name);
+            }
+        }
+    }
+
+    /// Get how many mounted nodes use a template
+    ///
+    /// A template that is no longer used by any node can be dropped from the template registry with
+    /// [`VirtualDom::evict_unused`].
+    pub fn template_usage(&self, template: TemplateId) -> usize {
+        self.template_usage.get(template).copied().unwrap_or(0)
+    }
+
+    /// Drop every template from the template registry that no mounted node uses, and return their ids in ascending
+    /// order.
+    ///
+    /// Apps that generate templates at runtime can call this every so often to keep the registry from growing forever.
+    /// If an evicted template is rendered again, it is registered again and sent to the renderer like a new template.
+    ///
+    /// ```rust, ignore
+    /// for template in dom.evict_unused() {
+    ///     renderer.forget_template(template);
+    /// }
+    /// ```
+    pub fn evict_unused(&mut self) -> Vec<TemplateId> {
+        let usage = &self.template_usage;
+        let mut evicted = Vec::new();
+        for templates in self.templates.values_mut() {
+            templates.retain(|_, template| {
+                let used = usage.contains_key(template.name);
+                if !used {
+                    evicted.push(template.name);
+                }
+                used
+            });
+        }
+        self.templates.retain(|_, templates| !templates.is_empty());
+        self.seen_templates.clear();
+
+        evicted.sort_unstable();
+        evicted
+    }
+}
@@ -5,18 +5,30 @@
 use crate::{
     any_props::VProps,
     arena::{ElementId, ElementRef},
-    innerlude::{DirtyScope, ErrorBoundary, Mutations, Scheduler, SchedulerMsg},
-    mutations::Mutation,
+    delegation::DelegatedEvents,
+    element_refs::ElementRefs,
+    events::listener_event,
+    flags::FlagState,
+    innerlude::{
+        Animations, Capabilities, DirtyScope, DirtyScopes, ErrorBoundary, Mutations, Priority,
+        RenderBudgetState, Scheduler, SchedulerMsg,
+    },
+    mutations::WriteMutations,
     nodes::RenderReturn,
     nodes::{Template, TemplateId},
     runtime::{Runtime, RuntimeGuard},
     scopes::{ScopeId, ScopeState},
     AttributeValue, Element, Event, Scope,
 };
-use futures_util::{pin_mut, StreamExt};
+use futures_util::{pin_mut, FutureExt, StreamExt};
 use rustc_hash::{FxHashMap, FxHashSet};
 use slab::Slab;
-use std::{any::Any, cell::Cell, collections::BTreeSet, future::Future, rc::Rc};
+use std::{
+    any::{Any, TypeId},
+    cell::Cell,
+    future::Future,
+    rc::Rc,
+};
 
 /// A virtual node system that progresses user events and diffs UI trees.
 ///
@@ -180,21 +192,71 @@ use std::{any::Any, cell::Cell, collections::BTreeSet, future::Future, rc::Rc};
 pub struct VirtualDom {
     pub(crate) scopes: Slab<Box<ScopeState>>,
 
-    pub(crate) dirty_scopes: BTreeSet<DirtyScope>,
+    pub(crate) dirty_scopes: DirtyScopes,
 
     // Maps a template path to a map of byteindexes to templates
     pub(crate) templates: FxHashMap<TemplateId, FxHashMap<usize, Template<'static>>>,
 
+    // The templates that are already registered, by the address of their name. Every rsx! call has its own static name,
+    // so this skips parsing the name for templates that were seen before.
+    pub(crate) seen_templates: FxHashSet<usize>,
+
+    // If a template was replaced with replace_template, so nodes need to check for the template that overrides theirs
+    pub(crate) templates_replaced: bool,
+
+    // How many mounted nodes use each template
+    pub(crate) template_usage: FxHashMap<TemplateId, usize>,
+
     // Every element is actually a dual reference - one to the template and the other to the dynamic node in that template
     pub(crate) elements: Slab<ElementRef>,
 
-    pub(crate) mutations: Mutations<'static>,
+    // Templates replaced with replace_template, sent to the renderer at the start of the next render
+    pub(crate) queued_templates: Vec<Template<'static>>,
 
     pub(crate) runtime: Rc<Runtime>,
 
     // Currently suspended scopes
     pub(crate) suspended_scopes: FxHashSet<ScopeId>,
 
+    // What the renderer can handle
+    pub(crate) capabilities: Capabilities,
+
+    // Keys registered for scroll restoration, with the last position reported for them
+    pub(crate) scroll_restoration: FxHashMap<String, Option<(f64, f64)>>,
+
+    // Registered elements sent in a [`Mutation::CaptureScroll`] that the renderer hasn't reported the position of yet
+    pub(crate) unmounted_scroll: FxHashMap<ElementId, String>,
+
+    // Scroll positions to restore at the end of the current batch of mutations
+    pub(crate) pending_scroll_restores: Vec<(ElementId, (f64, f64))>,
+
+    // How often each scope may render in a tick, and how often it has so far
+    pub(crate) render_budget: Option<RenderBudgetState>,
+
+    // Attributes that animate when they change, and the animations that are still running
+    pub(crate) animations: Animations,
+
+    // Decides which conditional template attributes are applied
+    pub(crate) flags: FlagState,
+
+    // The elements with a handler for each event, when listeners are delegated to the root
+    pub(crate) delegated_events: DelegatedEvents,
+
+    // Attributes that reference another element by key, and the keyed elements they can point to
+    pub(crate) element_refs: ElementRefs,
+
+    // Text nodes that text slots updated outside of a diff, which still have their old text in the VNode
+    pub(crate) stale_text: FxHashSet<ElementId>,
+
+    // The element that the nodes being created right now bubble events to
+    pub(crate) mount_parent: Option<ElementId>,
+
+    // The placeholder of each mounted portal
+    pub(crate) portals: FxHashMap<ScopeId, ElementId>,
+
+    // The type of the props of the root component, so they can only be replaced with props of the same type
+    pub(crate) root_props: TypeId,
+
     pub(crate) rx: futures_channel::mpsc::UnboundedReceiver<SchedulerMsg>,
 }
 
@@ -262,13 +324,34 @@ impl VirtualDom {
             scopes: Default::default(),
             dirty_scopes: Default::default(),
             templates: Default::default(),
+            seen_templates: Default::default(),
+            templates_replaced: false,
+            template_usage: Default::default(),
             elements: Default::default(),
-            mutations: Mutations::default(),
+            queued_templates: Default::default(),
             suspended_scopes: Default::default(),
+            capabilities: Default::default(),
+            scroll_restoration: Default::default(),
+            unmounted_scroll: Default::default(),
+            pending_scroll_restores: Default::default(),
+            render_budget: None,
+            animations: Default::default(),
+            flags: Default::default(),
+            delegated_events: Default::default(),
+            element_refs: Default::default(),
+            stale_text: Default::default(),
+            mount_parent: None,
+            portals: Default::default(),
+            root_props: TypeId::of::<P>(),
         };
 
         let root = dom.new_scope(
-            Box::new(VProps::new(root, |_, _| unreachable!(), root_props)),
+            Box::new(VProps::new(
+                root,
+                |_, _| unreachable!(),
+                |_| None,
+                root_props,
+            )),
             "app",
         );
 
@@ -297,20 +380,72 @@ impl VirtualDom {
 
     /// Build the virtualdom with a global context inserted into the base scope
     ///
-    /// This is useful for what is essentially dependency injection when building the app
+    /// This is useful for what is essentially dependency injection when building the app. Every component can read the
+    /// context with `consume_context`, including the root component in its first render.
+    ///
+    /// ```rust, ignore
+    /// let dom = VirtualDom::new(app)
+    ///     .with_root_context(Config { dark_mode: true })
+    ///     .with_root_context(Rc::new(window));
+    /// ```
     pub fn with_root_context<T: Clone + 'static>(self, context: T) -> Self {
-        self.base_scope().provide_context(context);
+        self.provide_root_context(context);
         self
     }
 
+    /// Insert a global context into the base scope of a VirtualDom that already exists, replacing any context of the
+    /// same type
+    ///
+    /// This is the same as [`VirtualDom::with_root_context`], for renderers that are handed a VirtualDom to run.
+    pub fn provide_root_context<T: Clone + 'static>(&self, context: T) -> T {
+        self.base_scope().provide_context(context)
+    }
+
+    /// Replace the props of the root component, and mark it dirty so it renders with the new props the next time the
+    /// VirtualDom does work. Components below it that get the same props are memoized as usual.
+    ///
+    /// ```rust, ignore
+    /// let mut dom = VirtualDom::new_with_props(Example, SomeProps { name: "jane" });
+    /// let mutations = dom.rebuild();
+    ///
+    /// dom.update_root_props(SomeProps { name: "jack" });
+    /// let mutations = dom.render_immediate();
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// If the props aren't the type the VirtualDom was created with
+    pub fn update_root_props<P: 'static>(&mut self, props: P) {
+        assert!(
+            self.root_props == TypeId::of::<P>(),
+            "The root props can only be replaced with props of the same type. Expected the props of the root component, found {}",
+            std::any::type_name::<P>()
+        );
+
+        let root = self.scopes[ScopeId::ROOT.0].props.as_mut().unwrap();
+
+        // Safety: the root props are always a VProps<P>, and we just checked that P is their type
+        unsafe { *(root.props_mut_ptr() as *mut P) = props };
+
+        self.mark_dirty(ScopeId::ROOT);
+    }
+
     /// Manually mark a scope as requiring a re-render
     ///
     /// Whenever the Runtime "works", it will re-render this scope
     pub fn mark_dirty(&mut self, id: ScopeId) {
+        self.mark_dirty_with_priority(id, Priority::Normal);
+    }
+
+    /// Mark a scope as requiring a re-render in the lane of the given [`Priority`]
+    ///
+    /// More urgent lanes are rendered first. If the scope is already dirty in a more urgent lane, it stays there.
+    pub fn mark_dirty_with_priority(&mut self, id: ScopeId, priority: Priority) {
         if let Some(scope) = self.get_scope(id) {
             let height = scope.height();
             tracing::trace!("Marking scope {:?} ({}) as dirty", id, scope.context().name);
-            self.dirty_scopes.insert(DirtyScope { height, id });
+            self.dirty_scopes
+                .insert(DirtyScope { height, id }, priority);
         }
     }
 
@@ -323,15 +458,56 @@ impl VirtualDom {
     /// It is up to the listeners themselves to mark nodes as dirty.
     ///
     /// If you have multiple events, you can call this method multiple times before calling "render_with_deadline"
+    ///
+    /// Returns `false` if a listener called [`Event::prevent_default`], in which case the renderer should skip the
+    /// default action of the event.
     pub fn handle_event(
         &mut self,
         name: &str,
         data: Rc<dyn Any>,
         element: ElementId,
         bubbles: bool,
-    ) {
+    ) -> bool {
         let _runtime = RuntimeGuard::new(self.runtime.clone());
 
+        let uievent = Event {
+            // Events that don't bubble are still captured, so they can be stopped too
+            propagates: Rc::new(Cell::new(true)),
+            default_prevented: Rc::new(Cell::new(false)),
+            data,
+        };
+
+        // Anything the listeners schedule is a response to the user, so it is rendered before other work
+        let priority = self.runtime.update_priority.replace(Priority::High);
+
+        self.track_drag(name, element);
+        self.dispatch_event(name, &uievent, element, bubbles);
+        self.finish_drag(name);
+
+        self.runtime.update_priority.set(priority);
+
+        let prevented = uievent.default_prevented.get();
+        if prevented && !self.capabilities.runtime_prevent_default {
+            tracing::warn!(
+                "A listener for {name} called prevent_default, but this renderer runs the default action before the listeners. Use the prevent_default attribute instead."
+            );
+        }
+        !prevented
+    }
+
+    /// Call the listeners for an event: first the capture listeners from the root down to the target element, then the
+    /// listeners on the target element, bubbling up from it if the event bubbles
+    fn dispatch_event(
+        &mut self,
+        name: &str,
+        uievent: &Event<dyn Any>,
+        element: ElementId,
+        bubbles: bool,
+    ) {
+        if !self.capture_event(name, uievent, element) {
+            return;
+        }
+
         /*
         ------------------------
         The algorithm works by walking through the list of dynamic attributes, checking their paths, and breaking when
@@ -340,7 +516,7 @@ impl VirtualDom {
         With the target path, we try and move up to the parent until there is no parent.
         Due to how bubbling works, we call the listeners before walking to the parent.
 
-        If we wanted to do capturing, then we would accumulate all the listeners and call them in reverse order.
+        Capturing accumulates all the listeners and calls them in reverse order, see `capture_event`.
         ----------------------
 
         For a visual demonstration, here we present a tree on the left and whether or not a listener is collected on the
@@ -356,12 +532,6 @@ impl VirtualDom {
         let mut parent_path = self.elements.get(element.0);
         let mut listeners = vec![];
 
-        // We will clone this later. The data itself is wrapped in RC to be used in callbacks if required
-        let uievent = Event {
-            propagates: Rc::new(Cell::new(bubbles)),
-            data,
-        };
-
         // If the event bubbles, we traverse through the tree until we find the target element.
         if bubbles {
             // Loop through each dynamic attribute (in a depth first order) in this template before moving up to the template's parent.
@@ -373,10 +543,9 @@ impl VirtualDom {
                     let target_path = el_ref.path;
 
                     for (idx, attr) in template.dynamic_attrs.iter().enumerate() {
-                        let this_path = node_template.attr_paths[idx];
+                        let this_path = node_template.attr_path(idx).unwrap();
 
-                        // Remove the "on" prefix if it exists, TODO, we should remove this and settle on one
-                        if attr.name.trim_start_matches("on") == name
+                        if listener_event(attr.name) == (name, false)
                             && target_path.is_decendant(&this_path)
                         {
                             listeners.push(&attr.value);
@@ -393,23 +562,13 @@ impl VirtualDom {
                     // Now that we've accumulated all the parent attributes for the target element, call them in reverse order
                     // We check the bubble state between each call to see if the event has been stopped from bubbling
                     for listener in listeners.drain(..).rev() {
-                        if let AttributeValue::Listener(listener) = listener {
-                            let origin = el_ref.scope;
-                            self.runtime.scope_stack.borrow_mut().push(origin);
-                            self.runtime.rendering.set(false);
-                            if let Some(cb) = listener.borrow_mut().as_deref_mut() {
-                                cb(uievent.clone());
-                            }
-                            self.runtime.scope_stack.borrow_mut().pop();
-                            self.runtime.rendering.set(true);
-
-                            if !uievent.propagates.get() {
-                                return;
-                            }
+                        self.call_listener(listener, el_ref.scope, uievent);
+                        if !uievent.propagates.get() {
+                            return;
                         }
                     }
 
-                    parent_path = template.parent.and_then(|id| self.elements.get(id.0));
+                    parent_path = template.parent.get().and_then(|id| self.elements.get(id.0));
                 } else {
                     break;
                 }
@@ -424,23 +583,12 @@ impl VirtualDom {
                     let target_path = el_ref.path;
 
                     for (idx, attr) in template.dynamic_attrs.iter().enumerate() {
-                        let this_path = node_template.attr_paths[idx];
+                        let this_path = node_template.attr_path(idx).unwrap();
 
-                        // Remove the "on" prefix if it exists, TODO, we should remove this and settle on one
                         // Only call the listener if this is the exact target element.
-                        if attr.name.trim_start_matches("on") == name && target_path == this_path {
-                            if let AttributeValue::Listener(listener) = &attr.value {
-                                let origin = el_ref.scope;
-                                self.runtime.scope_stack.borrow_mut().push(origin);
-                                self.runtime.rendering.set(false);
-                                if let Some(cb) = listener.borrow_mut().as_deref_mut() {
-                                    cb(uievent.clone());
-                                }
-                                self.runtime.scope_stack.borrow_mut().pop();
-                                self.runtime.rendering.set(true);
-
-                                break;
-                            }
+                        if listener_event(attr.name) == (name, false) && target_path == this_path {
+                            self.call_listener(&attr.value, el_ref.scope, uievent);
+                            break;
                         }
                     }
                 }
@@ -448,6 +596,57 @@ impl VirtualDom {
         }
     }
 
+    /// Call the capture listeners of the target element and its ancestors, starting at the root. Capturing happens even
+    /// if the event doesn't bubble.
+    ///
+    /// Returns `false` if a listener stopped the propagation of the event.
+    fn capture_event(&mut self, name: &str, uievent: &Event<dyn Any>, element: ElementId) -> bool {
+        // Collect the listeners from the target up, then call them in reverse
+        let mut listeners = vec![];
+        let mut parent_path = self.elements.get(element.0);
+        while let Some(el_ref) = parent_path {
+            let template = match el_ref.template {
+                // safety: we maintain references of all vnodes in the element slab
+                Some(template) => unsafe { template.as_ref() },
+                None => break,
+            };
+            let node_template = template.template.get();
+
+            for (idx, attr) in template.dynamic_attrs.iter().enumerate().rev() {
+                if listener_event(attr.name) == (name, true)
+                    && el_ref
+                        .path
+                        .is_decendant(&node_template.attr_path(idx).unwrap())
+                {
+                    listeners.push((&attr.value, el_ref.scope));
+                }
+            }
+
+            parent_path = template.parent.get().and_then(|id| self.elements.get(id.0));
+        }
+
+        for (listener, scope) in listeners.into_iter().rev() {
+            self.call_listener(listener, scope, uievent);
+            if !uievent.propagates.get() {
+                return false;
+            }
+        }
+
+        true
+    }
+
+    fn call_listener(&self, listener: &AttributeValue, scope: ScopeId, uievent: &Event<dyn Any>) {
+        if let AttributeValue::Listener(listener) = listener {
+            self.runtime.scope_stack.borrow_mut().push(scope);
+            self.runtime.rendering.set(false);
+            if let Some(cb) = listener.borrow_mut().as_deref_mut() {
+                cb(uievent.clone());
+            }
+            self.runtime.scope_stack.borrow_mut().pop();
+            self.runtime.rendering.set(true);
+        }
+    }
+
     /// Wait for the scheduler to have any work.
     ///
     /// This method polls the internal future queue, waiting for suspense nodes, tasks, or other work. This completes when
@@ -458,6 +657,10 @@ impl VirtualDom {
     ///
     /// This lets us poll async tasks and suspended trees during idle periods without blocking the main thread.
     ///
+    /// Any effects queued by the last render are run before waiting, since the renderer has applied its mutations by
+    /// then. If the [render budget](VirtualDom::with_render_budget) put off any renders, this completes right away so
+    /// the next tick can pick them up.
+    ///
     /// # Example
     ///
     /// ```rust, ignore
@@ -465,13 +668,20 @@ impl VirtualDom {
     /// let sender = dom.get_scheduler_channel();
     /// ```
     pub async fn wait_for_work(&mut self) {
+        self.run_effects();
+        self.wait_for_messages().await
+    }
+
+    async fn wait_for_messages(&mut self) {
         let mut some_msg = None;
 
         loop {
             match some_msg.take() {
                 // If a bunch of messages are ready in a sequence, try to pop them off synchronously
                 Some(msg) => match msg {
-                    SchedulerMsg::Immediate(id) => self.mark_dirty(id),
+                    SchedulerMsg::Immediate(id, priority) => {
+                        self.mark_dirty_with_priority(id, priority)
+                    }
                     SchedulerMsg::TaskNotified(task) => self.handle_task_wakeup(task),
                 },
 
@@ -481,8 +691,10 @@ impl VirtualDom {
                         Ok(Some(val)) => some_msg = Some(val),
                         Ok(None) => return,
                         Err(_) => {
-                            // If we have any dirty scopes, or finished fiber trees then we should exit
-                            if !self.dirty_scopes.is_empty() || !self.suspended_scopes.is_empty() {
+                            // If we have any dirty scopes then we should exit. Suspended scopes are parked until
+                            // one of their tasks finishes, which marks them dirty. Renders put off by the render
+                            // budget are marked dirty again as soon as the next tick starts
+                            if !self.dirty_scopes.is_empty() || self.has_deferred_renders() {
                                 return;
                             }
 
@@ -498,7 +710,9 @@ impl VirtualDom {
     pub fn process_events(&mut self) {
         while let Ok(Some(msg)) = self.rx.try_next() {
             match msg {
-                SchedulerMsg::Immediate(id) => self.mark_dirty(id),
+                SchedulerMsg::Immediate(id, priority) => {
+                    self.mark_dirty_with_priority(id, priority)
+                }
                 SchedulerMsg::TaskNotified(task) => self.handle_task_wakeup(task),
             }
         }
@@ -509,23 +723,13 @@ impl VirtualDom {
     ///
     /// The caller must ensure that the template refrences the same dynamic attributes and nodes as the original template.
     ///
-    /// This will only replace the the parent template, not any nested templates.
+    /// Every scope that renders the template is marked dirty, whether it is the root of the scope or nested in one of
+    /// its fragments. Templates rendered by child components are left to those components.
     pub fn replace_template(&mut self, template: Template<'static>) {
         self.register_template_first_byte_index(template);
         // iterating a slab is very inefficient, but this is a rare operation that will only happen during development so it's fine
-        for (_, scope) in self.scopes.iter() {
-            if let Some(RenderReturn::Ready(sync)) = scope.try_root_node() {
-                if sync.template.get().name.rsplit_once(':').unwrap().0
-                    == template.name.rsplit_once(':').unwrap().0
-                {
-                    let context = scope.context();
-                    let height = context.height;
-                    self.dirty_scopes.insert(DirtyScope {
-                        height,
-                        id: context.id,
-                    });
-                }
-            }
+        for (id, _) in self.hot_reload_plan(&[template]) {
+            self.mark_dirty(id);
         }
     }
 
@@ -550,44 +754,52 @@ impl VirtualDom {
     /// apply_edits(edits);
     /// ```
     pub fn rebuild(&mut self) -> Mutations {
+        let mut mutations = Mutations::default();
+        self.rebuild_to(&mut mutations);
+        mutations
+    }
+
+    /// Rebuild the VirtualDom like [`VirtualDom::rebuild`], but write the edits straight to a renderer instead of
+    /// returning them.
+    ///
+    /// Each edit is written as soon as the diff makes it, so no list of edits is built up in between.
+    pub fn rebuild_to<'a>(&'a mut self, to: &mut impl WriteMutations<'a>) {
+        self.unmounted_scroll.clear();
+        self.reset_delegated_events();
+        // Send the templates that were replaced since the last render, before any edits load them
+        for template in self.queued_templates.drain(..) {
+            to.register_template(template);
+        }
+
         let _runtime = RuntimeGuard::new(self.runtime.clone());
         match unsafe { self.run_scope(ScopeId::ROOT).extend_lifetime_ref() } {
             // Rebuilding implies we append the created elements to the root
             RenderReturn::Ready(node) => {
-                let m = self.create_scope(ScopeId::ROOT, node);
-                self.mutations.edits.push(Mutation::AppendChildren {
-                    id: ElementId(0),
-                    m,
-                });
+                let m = self.create_scope(to, ScopeId::ROOT, node);
+                to.append_children(ElementId(0), m);
             }
             // If an error occurs, we should try to render the default error component and context where the error occured
             RenderReturn::Aborted(placeholder) => {
                 tracing::debug!("Ran into suspended or aborted scope during rebuild");
                 let id = self.next_null();
                 placeholder.id.set(Some(id));
-                self.mutations.push(Mutation::CreatePlaceholder { id });
+                to.create_placeholder(id);
             }
         }
-
-        self.finalize()
+        self.flush_batch(to);
     }
 
     /// Render whatever the VirtualDom has ready as fast as possible without requiring an executor to progress
     /// suspended subtrees.
     pub fn render_immediate(&mut self) -> Mutations {
-        // Build a waker that won't wake up since our deadline is already expired when it's polled
-        let waker = futures_util::task::noop_waker();
-        let mut cx = std::task::Context::from_waker(&waker);
-
-        // Now run render with deadline but dont even try to poll any async tasks
-        let fut = self.render_with_deadline(std::future::ready(()));
-        pin_mut!(fut);
-
-        // The root component is not allowed to be async
-        match fut.poll(&mut cx) {
-            std::task::Poll::Ready(mutations) => mutations,
-            std::task::Poll::Pending => panic!("render_immediate should never return pending"),
-        }
+        // Never yield, so every dirty scope is rendered without polling any async tasks
+        self.render_with_yield(|| false)
+    }
+
+    /// Render whatever the VirtualDom has ready like [`VirtualDom::render_immediate`], but write the edits straight
+    /// to a renderer instead of returning them.
+    pub fn render_immediate_to<'a>(&'a mut self, to: &mut impl WriteMutations<'a>) {
+        self.render_until(to, false, || false);
     }
 
     /// Render the virtual dom, waiting for all suspense to be finished
@@ -612,55 +824,135 @@ impl VirtualDom {
     ///
     /// It's generally a good idea to put some sort of limit on the suspense process in case a future is having issues.
     ///
-    /// If no suspense trees are present
+    /// The deadline is checked after every scope is diffed, so a large update is split across several calls instead of
+    /// blocking the frame. The returned mutations always move the renderer from one valid state to another, and any
+    /// scopes that are still dirty are rendered by the next call. If nothing is dirty yet, this waits for work until the
+    /// deadline passes.
+    ///
+    /// ```rust, ignore
+    /// loop {
+    ///     let frame = request_animation_frame();
+    ///     apply_edits(dom.render_with_deadline(frame).await);
+    /// }
+    /// ```
     pub async fn render_with_deadline(&mut self, deadline: impl Future<Output = ()>) -> Mutations {
         pin_mut!(deadline);
 
+        // If there's nothing to render yet, poll the suspense leaves until there is or the deadline passes
+        // Nothing is diffed while waiting, so the mutations never borrow the VirtualDom across an await
         self.process_events();
-
-        loop {
-            // Next, diff any dirty scopes
-            // We choose not to poll the deadline since we complete pretty quickly anyways
-            if let Some(dirty) = self.dirty_scopes.iter().next().cloned() {
-                self.dirty_scopes.remove(&dirty);
-
-                // If the scope doesn't exist for whatever reason, then we should skip it
-                if !self.scopes.contains(dirty.id.0) {
-                    continue;
-                }
-
-                {
-                    let _runtime = RuntimeGuard::new(self.runtime.clone());
-                    // Run the scope and get the mutations
-                    self.run_scope(dirty.id);
-                    self.diff_scope(dirty.id);
-                }
-            }
-
-            // If there's more work, then just continue, plenty of work to do
-            if !self.dirty_scopes.is_empty() {
-                continue;
-            }
-
-            // Poll the suspense leaves in the meantime
+        let mut deadline_passed = false;
+        if self.dirty_scopes.is_empty() {
             let mut work = self.wait_for_work();
 
             // safety: this is okay since we don't touch the original future
             let pinned = unsafe { std::pin::Pin::new_unchecked(&mut work) };
 
-            // If the deadline is exceded (left) then we should return the mutations we have
+            // If the deadline is exceded (left) then we only render what is already dirty
             use futures_util::future::{select, Either};
             if let Either::Left((_, _)) = select(&mut deadline, pinned).await {
                 // release the borrowed
                 drop(work);
-                return self.finalize();
+                deadline_passed = true;
             }
         }
+
+        // Next, diff any dirty scopes, one at a time so we can stop once the deadline passes
+        let mut mutations = Mutations::default();
+        self.render_until(&mut mutations, true, || {
+            deadline_passed || deadline.as_mut().now_or_never().is_some()
+        });
+        mutations
     }
 
-    /// Swap the current mutations with a new
-    fn finalize(&mut self) -> Mutations {
-        std::mem::take(&mut self.mutations)
+    /// Render dirty scopes one at a time until `should_yield` returns true or there is no more work.
+    ///
+    /// This lets a large update be split across several frames so it doesn't block the main thread. Every scope is
+    /// diffed completely before `should_yield` is checked, so the returned mutations always move the renderer from one
+    /// valid state to another. Any scopes that are still dirty are rendered by the next call.
+    ///
+    /// ```rust, ignore
+    /// let start = Instant::now();
+    /// let edits = dom.render_with_yield(|| start.elapsed() > Duration::from_millis(8));
+    /// apply_edits(edits);
+    ///
+    /// if dom.has_dirty_scopes() {
+    ///     // Continue on the next frame
+    /// }
+    /// ```
+    pub fn render_with_yield(&mut self, should_yield: impl FnMut() -> bool) -> Mutations {
+        let mut mutations = Mutations::default();
+        self.render_until(&mut mutations, false, should_yield);
+        mutations
+    }
+
+    /// Render dirty scopes until `should_yield` returns true or there is no more work.
+    ///
+    /// If `poll_messages` is set, scopes that the renders in this batch marked dirty are rendered in the same batch.
+    fn render_until<'a>(
+        &'a mut self,
+        to: &mut impl WriteMutations<'a>,
+        poll_messages: bool,
+        mut should_yield: impl FnMut() -> bool,
+    ) {
+        self.run_effects();
+        self.start_render_budget_tick();
+        self.process_events();
+
+        // Send the templates that were replaced since the last render, before any edits load them
+        for template in self.queued_templates.drain(..) {
+            to.register_template(template);
+        }
+
+        loop {
+            if poll_messages && self.dirty_scopes.is_empty() {
+                self.process_events();
+            }
+            let dirty = match self.dirty_scopes.pop() {
+                Some(dirty) => dirty,
+                None => break,
+            };
+
+            // If the scope doesn't exist for whatever reason, then we should skip it
+            if self.scopes.contains(dirty.id.0) && !self.over_render_budget(dirty.id) {
+                let _runtime = RuntimeGuard::new(self.runtime.clone());
+                // Run the scope and write its mutations
+                self.run_scope(dirty.id);
+                self.diff_scope(to, dirty.id);
+            }
+
+            if should_yield() {
+                break;
+            }
+        }
+
+        self.flush_batch(to);
+    }
+
+    /// Check if there are any scopes that still need to be rendered
+    pub fn has_dirty_scopes(&self) -> bool {
+        !self.dirty_scopes.is_empty()
+    }
+
+    /// Write the edits that are sent at the end of every batch
+    ///
+    /// This ends the batch, so the VirtualDom stays borrowed for as long as the edits are kept around.
+    pub(crate) fn flush_batch<'a>(&'a mut self, to: &mut impl WriteMutations<'a>) {
+        self.flush_scroll_restores(to);
+        self.flush_delegated_events(to);
+        self.flush_element_refs(to);
+
+        // Leaked ids point to a bug in the diff, so check for them while developing
+        #[cfg(debug_assertions)]
+        {
+            let orphans = self.orphaned_elements();
+            if !orphans.is_empty() {
+                tracing::warn!(
+                    "ElementIds {:?} are no longer part of the tree but were never freed",
+                    orphans
+                );
+            }
+        }
     }
 
     /// Get the current runtime
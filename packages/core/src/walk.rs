@@ -0,0 +1,225 @@
+//! Walk the resolved tree of a [`VNode`], descending through fragments and into the rendered output of components.
+//!
+//! This is the shared machinery for any analysis that needs to see the tree the way the renderer sees it, rather than
+//! as a collection of templates with holes in them.
+
+use crate::{
+    innerlude::{DynamicNode, VNode},
+    Attribute, AttributeValue, DerivedInputs, ElementId, RenderReturn, Template, TemplateAttribute,
+    TemplateNode, VirtualDom,
+};
+
+/// An attribute encountered while walking an element in the tree
+pub(crate) enum VisitedAttribute<'b> {
+    /// An attribute that was known when the template was created
+    Static {
+        name: &'b str,
+        value: &'b str,
+        namespace: Option<&'b str>,
+    },
+
+    /// An attribute that was filled in at runtime
+    Dynamic(&'b Attribute<'b>),
+}
+
+impl<'b> VisitedAttribute<'b> {
+    pub(crate) fn name(&self) -> &'b str {
+        match self {
+            VisitedAttribute::Static { name, .. } => name,
+            VisitedAttribute::Dynamic(attr) => attr.name,
+        }
+    }
+
+    pub(crate) fn namespace(&self) -> Option<&'b str> {
+        match self {
+            VisitedAttribute::Static { namespace, .. } => *namespace,
+            VisitedAttribute::Dynamic(attr) => attr.namespace,
+        }
+    }
+
+    /// The value of this attribute as the renderer would write it, if it has a textual form at all
+    ///
+    /// Derived attributes are computed from the other attributes of the element they are on.
+    pub(crate) fn text_value(&self, element: &[VisitedAttribute<'b>]) -> Option<String> {
+        match self {
+            VisitedAttribute::Dynamic(Attribute {
+                value: AttributeValue::Derived(derived),
+                ..
+            }) => {
+                let lookup = |name: &str| {
+                    element
+                        .iter()
+                        .filter(|attr| attr.name() == name)
+                        .find_map(|attr| attr.plain_value())
+                };
+                derived.compute(&DerivedInputs::new(&lookup))
+            }
+            _ => self.plain_value(),
+        }
+    }
+
+    /// The value of this attribute without evaluating derived attributes
+    fn plain_value(&self) -> Option<String> {
+        match self {
+            VisitedAttribute::Static { value, .. } => Some(value.to_string()),
+            VisitedAttribute::Dynamic(attr) => match &attr.value {
+                AttributeValue::Text(value) => Some(value.to_string()),
+                AttributeValue::Float(value) => Some(value.to_string()),
+                AttributeValue::Int(value) => Some(value.to_string()),
+                AttributeValue::Bool(value) => Some(value.to_string()),
+                AttributeValue::Template(value) => Some(value.to_string()),
+                AttributeValue::StyleMap(properties) => Some(
+                    properties
+                        .iter()
+                        .map(|(name, value)| format!("{}:{};", name, value))
+                        .collect(),
+                ),
+                AttributeValue::Listener(_)
+                | AttributeValue::Any(_)
+                | AttributeValue::Bytes(_)
+                | AttributeValue::Derived(_)
+                | AttributeValue::ElementRefByKey(_)
+                | AttributeValue::Spread(_)
+                | AttributeValue::None => None,
+            },
+        }
+    }
+}
+
+/// Callbacks for each kind of node found while walking the resolved tree
+///
+/// Elements are reported with the [`ElementId`] the renderer knows them by, if they have one. Elements that are
+/// entirely static and not a root of their template are never assigned an id.
+#[allow(unused_variables)]
+pub(crate) trait TreeVisitor<'b> {
+    fn template(&mut self, template: Template<'static>) {}
+
+    fn enter_element(
+        &mut self,
+        tag: &'b str,
+        namespace: Option<&'b str>,
+        id: Option<ElementId>,
+        attrs: &[VisitedAttribute<'b>],
+    ) {
+    }
+
+    fn exit_element(&mut self) {}
+
+    fn text(&mut self, value: &'b str, id: Option<ElementId>) {}
+
+    fn placeholder(&mut self, id: Option<ElementId>) {}
+}
+
+impl VirtualDom {
+    /// Walk every root of this node, calling into the visitor for each node in the resolved tree
+    pub(crate) fn visit_node<'b>(
+        &'b self,
+        node: &'b VNode<'b>,
+        visitor: &mut impl TreeVisitor<'b>,
+    ) {
+        let template = node.template.get();
+        visitor.template(template);
+        for (idx, root) in template.roots.iter().enumerate() {
+            let root_id = node.root_ids.borrow().get(idx).copied();
+            self.visit_template_node(node, root, root_id, visitor);
+        }
+    }
+
+    fn visit_template_node<'b>(
+        &'b self,
+        node: &'b VNode<'b>,
+        template_node: &'b TemplateNode<'b>,
+        root_id: Option<ElementId>,
+        visitor: &mut impl TreeVisitor<'b>,
+    ) {
+        match template_node {
+            TemplateNode::Element {
+                tag,
+                namespace,
+                attrs,
+                children,
+            } => {
+                let mut visited = Vec::with_capacity(attrs.len());
+                for attr in attrs.iter() {
+                    match attr {
+                        TemplateAttribute::Static {
+                            name,
+                            value,
+                            namespace,
+                        } => visited.push(VisitedAttribute::Static {
+                            name,
+                            value,
+                            namespace: *namespace,
+                        }),
+                        TemplateAttribute::Dynamic { id } => {
+                            let attr = &node.dynamic_attrs[*id];
+                            visited.push(VisitedAttribute::Dynamic(attr));
+                            // Spread attributes are visited like the attributes they were spread from
+                            if let AttributeValue::Spread(spread) = attr.value {
+                                visited.extend(spread.iter().map(|(name, value)| {
+                                    VisitedAttribute::Static {
+                                        name,
+                                        value,
+                                        namespace: None,
+                                    }
+                                }));
+                            }
+                        }
+                        // Mounted templates never have unresolved conditional attributes
+                        TemplateAttribute::Conditional { .. } => {}
+                    }
+                }
+                let attrs = visited;
+
+                // Nested elements only have an id if a dynamic attribute claimed them
+                let id = root_id.or_else(|| {
+                    attrs.iter().find_map(|attr| match attr {
+                        VisitedAttribute::Dynamic(attr) => Some(attr.mounted_element()),
+                        VisitedAttribute::Static { .. } => None,
+                    })
+                });
+
+                visitor.enter_element(tag, *namespace, id, &attrs);
+                for child in children.iter() {
+                    self.visit_template_node(node, child, None, visitor);
+                }
+                visitor.exit_element();
+            }
+            TemplateNode::Text { text } => visitor.text(text, root_id),
+            TemplateNode::Dynamic { id } | TemplateNode::DynamicText { id } => {
+                self.visit_dynamic_node(&node.dynamic_nodes[*id], visitor)
+            }
+        }
+    }
+
+    fn visit_dynamic_node<'b>(
+        &'b self,
+        node: &'b DynamicNode<'b>,
+        visitor: &mut impl TreeVisitor<'b>,
+    ) {
+        match node {
+            DynamicNode::Text(text) => visitor.text(text.value, text.mounted_element()),
+            DynamicNode::Placeholder(placeholder) => {
+                visitor.placeholder(placeholder.mounted_element())
+            }
+            DynamicNode::Fragment(nodes) => {
+                for node in nodes.iter() {
+                    self.visit_node(node, visitor);
+                }
+            }
+            DynamicNode::Component(component) => {
+                let scope = match component.mounted_scope().and_then(|id| self.get_scope(id)) {
+                    Some(scope) => scope,
+                    None => return,
+                };
+                match scope.try_root_node() {
+                    Some(RenderReturn::Ready(node)) => self.visit_node(node, visitor),
+                    Some(RenderReturn::Aborted(placeholder)) => {
+                        visitor.placeholder(placeholder.mounted_element())
+                    }
+                    None => {}
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,66 @@
+use dioxus::core::Mutation::*;
+use dioxus::prelude::*;
+use dioxus_core::{ElementId, RenderReturn};
+
+fn app(cx: Scope) -> Element {
+    let label = "Close dialog";
+    cx.render(rsx! {
+        button { "aria-label": "{label}", "X" }
+        span { id: "caption", "Search the docs" }
+        input { "aria-labelledby": "caption", placeholder: "{label}" }
+        a { title: "{label}",
+            "Read "
+            span { "aria-hidden": "true", "(opens in a new tab) " }
+            b { "more" }
+        }
+        textarea { placeholder: "{label}" }
+    })
+}
+
+fn roots(dom: &mut VirtualDom) -> Vec<ElementId> {
+    dom.rebuild()
+        .santize()
+        .edits
+        .iter()
+        .filter_map(|edit| match edit {
+            LoadTemplate { id, .. } => Some(*id),
+            _ => None,
+        })
+        .collect()
+}
+
+fn name(dom: &VirtualDom, element: ElementId) -> String {
+    match dom.base_scope().root_node() {
+        RenderReturn::Ready(node) => dom.accessible_name(node, element),
+        RenderReturn::Aborted(_) => panic!("root should render"),
+    }
+}
+
+#[test]
+fn aria_label_wins_over_text() {
+    let mut dom = VirtualDom::new(app);
+    let roots = roots(&mut dom);
+    assert_eq!(name(&dom, roots[0]), "Close dialog");
+}
+
+#[test]
+fn labelled_by_resolves_referenced_text() {
+    let mut dom = VirtualDom::new(app);
+    let roots = roots(&mut dom);
+    assert_eq!(name(&dom, roots[2]), "Search the docs");
+}
+
+#[test]
+fn text_content_skips_hidden_elements() {
+    let mut dom = VirtualDom::new(app);
+    let roots = roots(&mut dom);
+    assert_eq!(name(&dom, roots[3]), "Read more");
+}
+
+#[test]
+fn falls_back_to_placeholder() {
+    let mut dom = VirtualDom::new(app);
+    let roots = roots(&mut dom);
+    assert_eq!(name(&dom, roots[4]), "Close dialog");
+    assert_eq!(name(&dom, ElementId(100)), "");
+}
@@ -0,0 +1,51 @@
+#![allow(non_snake_case)]
+//! Components record the external values they read, so a change to a value only re-renders its readers
+
+use dioxus::core::ValueId;
+use dioxus::prelude::*;
+
+const PRICE: ValueId = ValueId(0);
+
+fn app(cx: Scope) -> Element {
+    cx.render(rsx! {
+        Reader {}
+        Bystander {}
+        Reader {}
+    })
+}
+
+fn Reader(cx: Scope) -> Element {
+    cx.read_value(PRICE);
+    cx.render(rsx! { span { "price" } })
+}
+
+fn Bystander(cx: Scope) -> Element {
+    cx.render(rsx! { span { "unrelated" } })
+}
+
+#[test]
+fn only_readers_are_affected() {
+    let mut dom = VirtualDom::new(app);
+    _ = dom.rebuild();
+
+    assert_eq!(dom.affected_scopes(PRICE), [ScopeId(1), ScopeId(3)]);
+    assert!(dom.affected_scopes(ValueId(1)).is_empty());
+}
+
+#[test]
+fn reads_only_last_until_the_next_render() {
+    fn app(cx: Scope) -> Element {
+        if cx.generation() == 0 {
+            cx.read_value(PRICE);
+        }
+        cx.render(rsx! { Reader {} })
+    }
+
+    let mut dom = VirtualDom::new(app);
+    _ = dom.rebuild();
+    assert_eq!(dom.affected_scopes(PRICE), [ScopeId(0), ScopeId(1)]);
+
+    dom.mark_dirty(ScopeId::ROOT);
+    _ = dom.render_immediate();
+    assert_eq!(dom.affected_scopes(PRICE), [ScopeId(1)]);
+}
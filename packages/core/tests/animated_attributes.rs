@@ -0,0 +1,66 @@
+//! Float attributes with a transition are animated by core, driven by the renderer's clock
+
+use dioxus::core::{AttributeTransition, BorrowedAttributeValue, ElementId, Mutation::*};
+use dioxus::prelude::*;
+use std::time::Duration;
+
+fn fade_in(cx: Scope) -> Element {
+    let opacity = if cx.generation() == 0 { 0.0 } else { 1.0 };
+    cx.render(rsx! { div { opacity: opacity } })
+}
+
+fn opacity(value: f64) -> dioxus_core::Mutation<'static> {
+    SetAttribute {
+        name: "opacity",
+        value: BorrowedAttributeValue::Float(value),
+        id: ElementId(1),
+        ns: Some("style"),
+    }
+}
+
+fn fading_dom() -> VirtualDom {
+    VirtualDom::new(fade_in).with_attribute_transition(
+        AttributeTransition::new("opacity", Duration::from_millis(100)).with_namespace("style"),
+    )
+}
+
+#[test]
+fn fade_steps_through_intermediate_values() {
+    let mut dom = fading_dom();
+    _ = dom.rebuild();
+
+    dom.mark_dirty(ScopeId::ROOT);
+    assert!(dom.render_immediate().edits.is_empty());
+    assert!(dom.has_running_animations());
+
+    let start = Duration::from_secs(3);
+    assert_eq!(dom.tick(start).edits, [opacity(0.0)]);
+    assert_eq!(
+        dom.tick(start + Duration::from_millis(25)).edits,
+        [opacity(0.25)]
+    );
+    assert_eq!(
+        dom.tick(start + Duration::from_millis(50)).edits,
+        [opacity(0.5)]
+    );
+    assert_eq!(
+        dom.tick(start + Duration::from_millis(120)).edits,
+        [opacity(1.0)]
+    );
+
+    assert!(!dom.has_running_animations());
+    assert!(dom
+        .tick(start + Duration::from_millis(200))
+        .edits
+        .is_empty());
+}
+
+#[test]
+fn attributes_without_transitions_are_set_immediately() {
+    let mut dom = VirtualDom::new(fade_in);
+    _ = dom.rebuild();
+
+    dom.mark_dirty(ScopeId::ROOT);
+    assert_eq!(dom.render_immediate().edits, [opacity(1.0)]);
+    assert!(!dom.has_running_animations());
+}
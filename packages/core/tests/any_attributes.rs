@@ -0,0 +1,30 @@
+//! Custom renderers can get typed values back out of `Any` attributes
+
+use dioxus::core::{BorrowedAttributeValue, Mutation};
+use dioxus::prelude::*;
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+struct Color(u8, u8, u8);
+
+fn app(cx: Scope) -> Element {
+    cx.render(rsx! { div { "background": cx.any_value(Color(255, 0, 0)) } })
+}
+
+#[test]
+fn any_attributes_downcast_to_their_type() {
+    let mut dom = VirtualDom::new(app);
+    let mutations = dom.rebuild();
+
+    let value = mutations
+        .edits
+        .iter()
+        .find_map(|edit| match edit {
+            Mutation::SetAttribute { value: BorrowedAttributeValue::Any(value), .. } => Some(value),
+            _ => None,
+        })
+        .unwrap();
+
+    assert!(value.is::<Color>());
+    assert_eq!(value.downcast_ref::<Color>(), Some(&Color(255, 0, 0)));
+    assert_eq!(value.downcast_ref::<String>(), None);
+}
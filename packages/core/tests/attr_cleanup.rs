@@ -3,7 +3,7 @@
 //! This tests to ensure we clean it up
 
 use bumpalo::Bump;
-use dioxus::core::{ElementId, Mutation::*};
+use dioxus::core::{ElementId, Mutation::*, TemplatePath};
 use dioxus::prelude::*;
 use dioxus_core::BorrowedAttributeValue;
 
@@ -39,7 +39,7 @@ fn attrs_cycle() {
         dom.render_immediate().santize().edits,
         [
             LoadTemplate { name: "template", index: 0, id: ElementId(2,) },
-            AssignId { path: &[0,], id: ElementId(3,) },
+            AssignId { path: TemplatePath::Slice(&[0,]), id: ElementId(3,) },
             SetAttribute {
                 name: "class",
                 value: (&*bump.alloc("1".into_value(&bump))).into(),
@@ -70,7 +70,7 @@ fn attrs_cycle() {
         dom.render_immediate().santize().edits,
         [
             LoadTemplate { name: "template", index: 0, id: ElementId(2) },
-            AssignId { path: &[0], id: ElementId(3) },
+            AssignId { path: TemplatePath::Slice(&[0]), id: ElementId(3) },
             SetAttribute {
                 name: "class",
                 value: BorrowedAttributeValue::Text("3"),
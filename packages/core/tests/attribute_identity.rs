@@ -0,0 +1,41 @@
+//! Attribute values reused across renders are recognized by their allocation, without comparing them
+
+use dioxus::core::{AttributeValue, RenderReturn};
+use dioxus::prelude::*;
+
+static LABEL: &str = "a long label that is the same every render";
+
+fn app(cx: Scope) -> Element {
+    cx.render(rsx! { div { title: LABEL } })
+}
+
+#[test]
+fn reused_values_are_the_same_allocation() {
+    let copied = LABEL.to_string();
+    let reused = AttributeValue::Text(LABEL);
+    assert!(reused.ptr_eq(&AttributeValue::Text(LABEL)));
+
+    // Equal text in another allocation has to be compared
+    assert!(!reused.ptr_eq(&AttributeValue::Text(&copied)));
+    assert!(reused == AttributeValue::Text(&copied));
+}
+
+#[test]
+fn reused_value_sends_no_mutation() {
+    let mut dom = VirtualDom::new(app);
+    _ = dom.rebuild();
+
+    for _ in 0..3 {
+        dom.mark_dirty(ScopeId(0));
+        assert!(dom.render_immediate().edits.is_empty());
+
+        // The attribute the next render is diffed against points at the same text
+        let node = match dom.base_scope().try_root_node() {
+            Some(RenderReturn::Ready(node)) => node,
+            _ => unreachable!(),
+        };
+        assert!(node.dynamic_attrs[0]
+            .value
+            .ptr_eq(&AttributeValue::Text(LABEL)));
+    }
+}
@@ -0,0 +1,65 @@
+use dioxus::core::Mutation::*;
+use dioxus::prelude::*;
+use dioxus_core::{AttributeSegment, AttributeTemplate, BorrowedAttributeValue, ElementId};
+
+#[test]
+fn changing_a_slot_sets_the_whole_attribute() {
+    fn app(cx: Scope) -> Element {
+        let x = cx.generation() * 10;
+        let y = 20;
+        cx.render(rsx! {
+            div { style: cx.attr_template("transform: translate({x}px, {y}px)", &[("x", &x), ("y", &y)]) }
+        })
+    }
+
+    let mut dom = VirtualDom::new(app);
+    _ = dom.rebuild();
+
+    dom.mark_dirty(ScopeId::ROOT);
+    assert_eq!(
+        dom.render_immediate().santize().edits,
+        [SetAttribute {
+            name: "style",
+            value: BorrowedAttributeValue::Text("transform: translate(10px, 20px)"),
+            id: ElementId(1),
+            ns: None,
+        }]
+    );
+}
+
+#[test]
+fn unchanged_slots_dont_set_the_attribute() {
+    fn app(cx: Scope) -> Element {
+        let x = 10;
+        cx.render(rsx! {
+            div { style: cx.attr_template("left: {x}px", &[("x", &x)]) }
+        })
+    }
+
+    let mut dom = VirtualDom::new(app);
+    _ = dom.rebuild();
+
+    dom.mark_dirty(ScopeId::ROOT);
+    assert!(dom.render_immediate().edits.is_empty());
+}
+
+#[test]
+fn templates_are_parsed_into_segments() {
+    let bump = dioxus_core::exports::bumpalo::Bump::new();
+    let template = AttributeTemplate::new(&bump, "{{x}}: {x}, {missing}", &[("x", &1.5)]);
+
+    assert_eq!(template.resolved(), "{x}: 1.5, ");
+    assert_eq!(template.slot("x"), Some("1.5"));
+    assert_eq!(
+        template.segments(),
+        [
+            AttributeSegment::Static("{"),
+            AttributeSegment::Static("x"),
+            AttributeSegment::Static("}"),
+            AttributeSegment::Static(": "),
+            AttributeSegment::Slot { name: "x", value: "1.5" },
+            AttributeSegment::Static(", "),
+            AttributeSegment::Slot { name: "missing", value: "" },
+        ]
+    );
+}
@@ -1,6 +1,6 @@
 #![allow(non_snake_case)]
 
-use dioxus::core::{ElementId, Mutation::*};
+use dioxus::core::{ElementId, Mutation::*, TemplatePath};
 use dioxus::prelude::*;
 
 #[test]
@@ -13,9 +13,9 @@ fn test_borrowed_state() {
             LoadTemplate { name: "template", index: 0, id: ElementId(1,) },
             LoadTemplate { name: "template", index: 0, id: ElementId(2,) },
             LoadTemplate { name: "template", index: 0, id: ElementId(3,) },
-            HydrateText { path: &[0,], value: "Hello w1!", id: ElementId(4,) },
-            ReplacePlaceholder { path: &[1,], m: 1 },
-            ReplacePlaceholder { path: &[0,], m: 1 },
+            HydrateText { path: TemplatePath::Slice(&[0,]), value: "Hello w1!", id: ElementId(4,) },
+            ReplacePlaceholder { path: TemplatePath::Slice(&[1,]), m: 1 },
+            ReplacePlaceholder { path: TemplatePath::Slice(&[0,]), m: 1 },
             AppendChildren { m: 1, id: ElementId(0) },
         ]
     );
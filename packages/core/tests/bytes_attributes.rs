@@ -0,0 +1,41 @@
+use dioxus::core::Mutation::*;
+use dioxus::prelude::*;
+use dioxus_core::{BorrowedAttributeValue, ElementId};
+
+fn app(cx: Scope) -> Element {
+    let buffer: &[u8] = if cx.generation() < 2 {
+        &[0, 1, 2]
+    } else {
+        &[3]
+    };
+    cx.render(rsx! {
+        canvas { "vertices": buffer }
+    })
+}
+
+#[test]
+fn bytes_are_passed_through_and_diffed() {
+    let mut vdom = VirtualDom::new(app);
+
+    assert!(vdom.rebuild().santize().edits.contains(&SetAttribute {
+        name: "vertices",
+        value: BorrowedAttributeValue::Bytes(&[0, 1, 2]),
+        id: ElementId(1),
+        ns: None,
+    }));
+
+    // The same bytes are not sent again
+    vdom.mark_dirty(ScopeId::ROOT);
+    assert!(vdom.render_immediate().edits.is_empty());
+
+    vdom.mark_dirty(ScopeId::ROOT);
+    assert_eq!(
+        vdom.render_immediate().edits,
+        [SetAttribute {
+            name: "vertices",
+            value: BorrowedAttributeValue::Bytes(&[3]),
+            id: ElementId(1),
+            ns: None,
+        }]
+    );
+}
@@ -0,0 +1,52 @@
+use dioxus::core::Mutation::*;
+use dioxus::prelude::*;
+use dioxus_core::{BorrowedAttributeValue, ElementId};
+use std::cell::Cell;
+
+thread_local! {
+    static RENDERS: Cell<usize> = const { Cell::new(0) };
+}
+
+fn app(cx: Scope) -> Element {
+    // The dependency only changes on the third render
+    let deps = cx.generation() / 2;
+    let generation = cx.generation();
+    cx.render(rsx! {
+        div { "generation {generation}" }
+        cx.cache_child("expensive", deps, move |cx| {
+            RENDERS.with(|renders| renders.set(renders.get() + 1));
+            cx.render(rsx! { p { class: "{deps}", "expensive" } })
+        })
+    })
+}
+
+#[test]
+fn unchanged_deps_reuse_the_cached_child() {
+    let mut dom = VirtualDom::new(app);
+    _ = dom.rebuild();
+    assert_eq!(RENDERS.with(Cell::get), 1);
+
+    // The parent re-renders but the child is left alone
+    dom.mark_dirty(ScopeId::ROOT);
+    assert_eq!(
+        dom.render_immediate().santize().edits,
+        [SetText { value: "generation 1", id: ElementId(2) }]
+    );
+    assert_eq!(RENDERS.with(Cell::get), 1);
+
+    // Changing the dependencies renders the child again
+    dom.mark_dirty(ScopeId::ROOT);
+    assert_eq!(
+        dom.render_immediate().santize().edits,
+        [
+            SetText { value: "generation 2", id: ElementId(2) },
+            SetAttribute {
+                name: "class",
+                value: BorrowedAttributeValue::Text("1"),
+                id: ElementId(3),
+                ns: None
+            },
+        ]
+    );
+    assert_eq!(RENDERS.with(Cell::get), 2);
+}
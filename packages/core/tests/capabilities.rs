@@ -0,0 +1,42 @@
+use dioxus::core::Mutation::*;
+use dioxus::prelude::*;
+use dioxus_core::{BorrowedAttributeValue, Capabilities, ElementId};
+
+fn app(cx: Scope) -> Element {
+    let html = "<b>bold</b>";
+    cx.render(rsx! { div { dangerous_inner_html: "{html}" } })
+}
+
+#[test]
+fn raw_html_is_set_as_an_attribute() {
+    let mut dom = VirtualDom::new(app);
+
+    assert_eq!(
+        dom.rebuild().santize().edits,
+        [
+            LoadTemplate { name: "template", index: 0, id: ElementId(1) },
+            SetAttribute {
+                name: "dangerous_inner_html",
+                value: BorrowedAttributeValue::Text("<b>bold</b>"),
+                id: ElementId(1),
+                ns: None
+            },
+            AppendChildren { id: ElementId(0), m: 1 },
+        ]
+    );
+}
+
+#[test]
+fn raw_html_falls_back_to_text() {
+    let mut dom = VirtualDom::new(app)
+        .with_capabilities(Capabilities { raw_html: false, ..Default::default() });
+
+    assert_eq!(
+        dom.rebuild().santize().edits,
+        [
+            LoadTemplate { name: "template", index: 0, id: ElementId(1) },
+            SetText { value: "<b>bold</b>", id: ElementId(1) },
+            AppendChildren { id: ElementId(0), m: 1 },
+        ]
+    );
+}
@@ -0,0 +1,56 @@
+//! The clipboard hook forwards to the clipboard the renderer provides
+
+use async_trait::async_trait;
+use dioxus::prelude::*;
+use std::{cell::RefCell, rc::Rc};
+
+thread_local! {
+    static CLIPBOARD: RefCell<Option<UseClipboard>> = const { RefCell::new(None) };
+}
+
+/// A clipboard that only lives as long as the test
+#[derive(Default)]
+struct TestClipboard(RefCell<String>);
+
+#[async_trait(?Send)]
+impl ClipboardProvider for TestClipboard {
+    async fn read_text(&self) -> Result<String, ClipboardError> {
+        Ok(self.0.borrow().clone())
+    }
+
+    async fn write_text(&self, text: String) -> Result<(), ClipboardError> {
+        *self.0.borrow_mut() = text;
+        Ok(())
+    }
+}
+
+fn app(cx: Scope) -> Element {
+    let clipboard = use_clipboard(cx);
+    CLIPBOARD.with(|stored| *stored.borrow_mut() = Some(clipboard.clone()));
+    None
+}
+
+fn clipboard() -> UseClipboard {
+    CLIPBOARD.with(|stored| stored.borrow().clone()).unwrap()
+}
+
+#[tokio::test]
+async fn reads_what_was_written() {
+    let provider: Rc<dyn ClipboardProvider> = Rc::new(TestClipboard::default());
+    let mut dom = VirtualDom::new(app).with_root_context(provider);
+    _ = dom.rebuild();
+
+    clipboard().write_text("copied").await.unwrap();
+    assert_eq!(clipboard().read_text().await.unwrap(), "copied");
+}
+
+#[tokio::test]
+async fn missing_provider_is_not_supported() {
+    let mut dom = VirtualDom::new(app);
+    _ = dom.rebuild();
+
+    assert!(matches!(
+        clipboard().read_text().await,
+        Err(ClipboardError::NotSupported)
+    ));
+}
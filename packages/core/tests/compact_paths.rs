@@ -0,0 +1,151 @@
+//! Template paths can be packed into a single integer when they are shallow and narrow enough
+
+use dioxus::prelude::*;
+use dioxus_core::{
+    pack_paths, MockDom, Mutation, PackedPath, Template, TemplateNode, TemplatePath,
+};
+
+#[test]
+fn packed_paths_round_trip() {
+    let paths: &[&[u8]] = &[&[], &[0], &[3, 1], &[15, 0, 7], &[1, 2, 3, 4, 5, 6, 7]];
+
+    for path in paths {
+        let packed = PackedPath::pack(path).unwrap();
+        assert_eq!(packed.len(), path.len());
+        assert_eq!(packed.to_vec(), path.to_vec());
+        assert_eq!(packed.get(path.len()), None);
+        assert_eq!(PackedPath::new(path), packed);
+    }
+}
+
+#[test]
+fn deep_or_wide_paths_fall_back_to_slices() {
+    assert_eq!(PackedPath::pack(&[0; 8]), None);
+    assert_eq!(PackedPath::pack(&[0, 16]), None);
+    assert_eq!(PackedPath::unpacked(1).unpacked_index(), Some(1));
+    assert!(!PackedPath::new(&[0, 15]).is_unpacked());
+
+    // Only the paths that don't fit are kept as slices
+    let paths: [&[u8]; 3] = [&[0], &[0, 1, 2, 3, 4, 5, 6, 7], &[0, 20]];
+    let mut unpacked = Vec::new();
+    let packed = pack_paths(paths, &mut unpacked);
+    assert_eq!(
+        packed,
+        [
+            PackedPath::new(&[0]),
+            PackedPath::unpacked(0),
+            PackedPath::unpacked(1)
+        ]
+    );
+    assert_eq!(unpacked, [&[0, 1, 2, 3, 4, 5, 6, 7][..], &[0, 20][..]]);
+
+    let template = Template {
+        name: "compact_paths.rs:1:1:0",
+        roots: &[TemplateNode::Dynamic { id: 0 }],
+        node_paths: &[PackedPath::new(&[0]), PackedPath::unpacked(0)],
+        attr_paths: &[PackedPath::unpacked(1)],
+        unpacked_paths: &[&[0, 1, 2, 3, 4, 5, 6, 7], &[0, 20]],
+        ..Template::EMPTY
+    };
+
+    assert_eq!(
+        template.node_path(0),
+        Some(TemplatePath::Packed(PackedPath::new(&[0])))
+    );
+    assert_eq!(
+        template.node_path(1),
+        Some(TemplatePath::Slice(&[0, 1, 2, 3, 4, 5, 6, 7]))
+    );
+    assert_eq!(template.node_path(2), None);
+    assert_eq!(template.node_path_iter().count(), 2);
+    assert_eq!(template.attr_path(0), Some(TemplatePath::Slice(&[0, 20])));
+
+    // Packed and slice paths compare by the steps they take
+    assert_eq!(
+        TemplatePath::Packed(PackedPath::new(&[3, 1])),
+        TemplatePath::Slice(&[3, 1])
+    );
+    assert_ne!(
+        TemplatePath::Packed(PackedPath::new(&[3, 1])),
+        TemplatePath::Slice(&[3])
+    );
+}
+
+#[test]
+fn rsx_packs_template_paths() {
+    fn app(cx: Scope) -> Element {
+        let name = "world";
+        cx.render(rsx! {
+            div {
+                class: "{name}",
+                span { "hello {name}" }
+                p { id: "{name}", "{name}" }
+            }
+        })
+    }
+
+    let mut dom = VirtualDom::new(app);
+    let renderer = MockDom::new();
+    let edits = dom.rebuild();
+    renderer.apply(&edits);
+    let template = edits.templates[0];
+
+    // Every path fits, so none of them are stored as slices
+    assert!(template.unpacked_paths.is_empty());
+    assert_eq!(template.node_paths.len(), 2);
+    assert_eq!(template.attr_paths.len(), 2);
+
+    // One u32 per path instead of a fat pointer plus the bytes of the path
+    let paths = template
+        .node_path_iter()
+        .chain(template.attr_path_iter())
+        .map(|path| path.to_vec())
+        .collect::<Vec<_>>();
+    let as_slices = paths.len() * std::mem::size_of::<&[u8]>()
+        + paths.iter().map(|path| path.len()).sum::<usize>();
+    let stored = std::mem::size_of_val(template.node_paths)
+        + std::mem::size_of_val(template.attr_paths)
+        + std::mem::size_of_val(template.unpacked_paths);
+    assert_eq!(stored, 4 * paths.len());
+    assert!(stored * 4 < as_slices, "{} vs {}", stored, as_slices);
+
+    assert_eq!(paths, [vec![0, 0, 0], vec![0, 1, 0], vec![0], vec![0, 1]]);
+
+    // The diff walks the packed paths to the same nodes the slices pointed to
+    assert_eq!(
+        renderer.to_string(),
+        r#"<div class="world"><span>hello world</span><p id="world">world</p></div>"#
+    );
+}
+
+#[test]
+fn renderers_receive_packed_paths() {
+    fn app(cx: Scope) -> Element {
+        let items = ["a", "b"];
+        cx.render(rsx! {
+            div {
+                p { "first" }
+                ul { items.iter().map(|item| rsx!(li { key: "{item}", "{item}" })) }
+            }
+        })
+    }
+
+    let mut dom = VirtualDom::new(app);
+    let edits = dom.rebuild().santize();
+
+    // The path of the list is relative to its root, so the first step is dropped before it is sent
+    let paths = edits
+        .edits
+        .iter()
+        .filter_map(|edit| match edit {
+            Mutation::AssignId { path, .. }
+            | Mutation::HydrateText { path, .. }
+            | Mutation::ReplacePlaceholder { path, .. } => Some(*path),
+            _ => None,
+        })
+        .collect::<Vec<_>>();
+    assert!(!paths.is_empty());
+    for path in paths {
+        assert!(matches!(path, TemplatePath::Packed(_)), "{:?}", path);
+    }
+}
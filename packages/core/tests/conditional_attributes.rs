@@ -0,0 +1,107 @@
+//! Templates can carry static attributes that are only applied when a runtime flag is enabled
+
+use dioxus::core::{ElementId, Mutation::*};
+use dioxus::prelude::*;
+use dioxus_core::{Template, TemplateAttribute, TemplateNode};
+use std::cell::Cell;
+
+static BANNER: Template = Template {
+    name: concat!(file!(), ":", line!(), ":", column!(), ":0"),
+    roots: &[TemplateNode::Element {
+        tag: "div",
+        namespace: None,
+        attrs: &[
+            TemplateAttribute::Static { name: "class", value: "banner", namespace: None },
+            TemplateAttribute::Conditional {
+                name: "data-variant",
+                value: "b",
+                namespace: None,
+                flag: "new-banner",
+            },
+        ],
+        children: &[TemplateNode::Text { text: "Welcome" }],
+    }],
+    node_paths: &[],
+    attr_paths: &[],
+    ..Template::EMPTY
+};
+
+fn app(cx: Scope) -> Element {
+    Some(VNode {
+        key: None,
+        parent: Default::default(),
+        template: Cell::new(BANNER),
+        root_ids: dioxus_core::exports::bumpalo::collections::Vec::new_in(cx.bump()).into(),
+        dynamic_nodes: &[],
+        dynamic_attrs: &[],
+    })
+}
+
+fn registered_attrs(dom: &mut VirtualDom) -> Vec<(String, String)> {
+    let edits = dom.rebuild().santize();
+    assert_eq!(
+        edits.edits,
+        [
+            LoadTemplate { name: "template", index: 0, id: ElementId(1) },
+            AppendChildren { id: ElementId(0), m: 1 },
+        ]
+    );
+    match edits.templates[0].roots[0] {
+        TemplateNode::Element { attrs, .. } => attrs
+            .iter()
+            .map(|attr| match attr {
+                TemplateAttribute::Static { name, value, .. } => {
+                    (name.to_string(), value.to_string())
+                }
+                other => panic!(
+                    "renderers should only see static attributes, found {:?}",
+                    other
+                ),
+            })
+            .collect(),
+        _ => unreachable!(),
+    }
+}
+
+#[test]
+fn conditional_attribute_applies_when_flag_is_on() {
+    let mut dom = VirtualDom::new(app).with_flag_provider(|flag| flag == "new-banner");
+
+    assert_eq!(
+        registered_attrs(&mut dom),
+        [
+            ("class".to_string(), "banner".to_string()),
+            ("data-variant".to_string(), "b".to_string()),
+        ]
+    );
+    assert_eq!(
+        dioxus_ssr::render(&dom),
+        "<div class=\"banner\" data-variant=\"b\">Welcome</div>"
+    );
+
+    // Re-rendering keeps the resolved template
+    dom.mark_dirty(ScopeId::ROOT);
+    assert!(dom.render_immediate().edits.is_empty());
+    assert_eq!(
+        dioxus_ssr::render(&dom),
+        "<div class=\"banner\" data-variant=\"b\">Welcome</div>"
+    );
+}
+
+#[test]
+fn conditional_attribute_is_dropped_when_flag_is_off() {
+    let mut dom = VirtualDom::new(app).with_flag_provider(|_| false);
+
+    assert_eq!(
+        registered_attrs(&mut dom),
+        [("class".to_string(), "banner".to_string())]
+    );
+    assert_eq!(
+        dioxus_ssr::render(&dom),
+        "<div class=\"banner\">Welcome</div>"
+    );
+
+    // Without any provider, every flag is off
+    let mut dom = VirtualDom::new(app);
+    assert_eq!(registered_attrs(&mut dom).len(), 1);
+}
@@ -49,3 +49,30 @@ fn state_shares() {
         [SetText { value: "Value is 3", id: ElementId(1,) },]
     );
 }
+
+#[test]
+fn root_contexts_are_provided_before_the_first_render() {
+    #[derive(Clone)]
+    struct Config(&'static str);
+
+    fn app(cx: Scope) -> Element {
+        let config = cx.consume_context::<Config>().unwrap();
+        let count = cx.consume_context::<i32>().unwrap();
+        cx.render(rsx!("{config.0} {count}"))
+    }
+
+    let mut dom = VirtualDom::new(app)
+        .with_root_context(Config("dark"))
+        .with_root_context(1);
+
+    // Providing a context of the same type replaces it
+    dom.provide_root_context(2);
+
+    assert_eq!(
+        dom.rebuild().santize().edits,
+        [
+            CreateTextNode { value: "dark 2", id: ElementId(1,) },
+            AppendChildren { m: 1, id: ElementId(0) },
+        ]
+    );
+}
@@ -0,0 +1,38 @@
+//! Controlled form fields are rewritten every render, so the renderer can undo input the app didn't accept
+
+use dioxus::core::{Mutation, Mutation::*};
+use dioxus::prelude::*;
+
+fn app(cx: Scope) -> Element {
+    // None of these values change between renders
+    let (text, checked) = ("fixed", true);
+
+    cx.render(rsx! {
+        input { value: "{text}" }
+        input { r#type: "checkbox", checked: checked }
+    })
+}
+
+fn attribute_names<'a>(edits: &[Mutation<'a>]) -> Vec<&'a str> {
+    edits
+        .iter()
+        .filter_map(|edit| match edit {
+            SetAttribute { name, .. } => Some(*name),
+            _ => None,
+        })
+        .collect()
+}
+
+#[test]
+fn unchanged_controlled_values_are_rewritten() {
+    let mut dom = VirtualDom::new(app);
+    _ = dom.rebuild();
+
+    for _ in 0..3 {
+        dom.mark_dirty(ScopeId::ROOT);
+        assert_eq!(
+            attribute_names(&dom.render_immediate().edits),
+            ["value", "checked"]
+        );
+    }
+}
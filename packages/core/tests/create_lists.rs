@@ -1,6 +1,6 @@
 use dioxus::core::Mutation::*;
 use dioxus::prelude::*;
-use dioxus_core::ElementId;
+use dioxus_core::{ElementId, TemplatePath};
 
 // A real-world usecase of templates at peak performance
 // In react, this would be a lot of node creation.
@@ -58,13 +58,13 @@ fn list_renders() {
             LoadTemplate { name: "template", index: 0, id: ElementId(1) },
             // Load each template one-by-one, rehydrating it
             LoadTemplate { name: "template", index: 0, id: ElementId(2) },
-            HydrateText { path: &[1, 0], value: "0", id: ElementId(3) },
+            HydrateText { path: TemplatePath::Slice(&[1, 0]), value: "0", id: ElementId(3) },
             LoadTemplate { name: "template", index: 0, id: ElementId(4) },
-            HydrateText { path: &[1, 0], value: "1", id: ElementId(5) },
+            HydrateText { path: TemplatePath::Slice(&[1, 0]), value: "1", id: ElementId(5) },
             LoadTemplate { name: "template", index: 0, id: ElementId(6) },
-            HydrateText { path: &[1, 0], value: "2", id: ElementId(7) },
+            HydrateText { path: TemplatePath::Slice(&[1, 0]), value: "2", id: ElementId(7) },
             // Replace the 0th childn on the div with the 3 templates on the stack
-            ReplacePlaceholder { m: 3, path: &[0] },
+            ReplacePlaceholder { m: 3, path: TemplatePath::Slice(&[0]) },
             // Append the container div to the dom
             AppendChildren { m: 1, id: ElementId(0) }
         ],
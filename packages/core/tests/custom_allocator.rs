@@ -0,0 +1,85 @@
+//! VNodes can be built in an allocator other than the bump allocator of the scope
+
+use dioxus::core::{
+    Attribute, ElementId, Mutation::*, NodeAllocator, PackedPath, Template, TemplateAttribute,
+    TemplateNode, TemplatePath,
+};
+use dioxus::prelude::*;
+use std::{
+    cell::Cell,
+    rc::Rc,
+    sync::atomic::{AtomicUsize, Ordering},
+};
+
+static CLICKS: AtomicUsize = AtomicUsize::new(0);
+
+/// Leaks every value and counts how many there are
+#[derive(Default)]
+struct LeakingAllocator {
+    allocations: Cell<usize>,
+}
+
+impl<'a> NodeAllocator<'a> for LeakingAllocator {
+    #[allow(clippy::mut_from_ref)]
+    fn alloc<T>(&'a self, value: T) -> &'a mut T {
+        self.allocations.set(self.allocations.get() + 1);
+        Box::leak(Box::new(value))
+    }
+
+    fn alloc_str(&'a self, value: &str) -> &'a str {
+        self.allocations.set(self.allocations.get() + 1);
+        Box::leak(value.to_string().into_boxed_str())
+    }
+}
+
+static BUTTON: Template = Template {
+    name: concat!(file!(), ":", line!(), ":", column!(), ":0"),
+    roots: &[TemplateNode::Element {
+        tag: "button",
+        namespace: None,
+        attrs: &[TemplateAttribute::Dynamic { id: 0 }],
+        children: &[TemplateNode::DynamicText { id: 0 }],
+    }],
+    node_paths: &[PackedPath::new(&[0, 0])],
+    attr_paths: &[PackedPath::new(&[0])],
+    ..Template::EMPTY
+};
+
+fn app(cx: Scope) -> Element {
+    let allocator: &LeakingAllocator = cx.use_hook(LeakingAllocator::default);
+    let count = 3;
+
+    let onclick = allocator.listener(|_: Event<String>| {
+        CLICKS.fetch_add(1, Ordering::SeqCst);
+    });
+    let node = VNode {
+        key: None,
+        parent: Default::default(),
+        template: Cell::new(BUTTON),
+        root_ids: dioxus_core::exports::bumpalo::collections::Vec::new_in(cx.bump()).into(),
+        dynamic_nodes: allocator.alloc([allocator.text_node(format_args!("{count} clicks"))]),
+        dynamic_attrs: allocator.alloc([Attribute::new("onclick", onclick, None, false)]),
+    };
+
+    // The listener, the formatted text, the dynamic node list and the attribute list
+    assert_eq!(allocator.allocations.get(), 4);
+    Some(node)
+}
+
+#[test]
+fn vnode_built_in_custom_allocator() {
+    let mut dom = VirtualDom::new(app);
+
+    assert_eq!(
+        dom.rebuild().santize().edits,
+        [
+            LoadTemplate { name: "template", index: 0, id: ElementId(1) },
+            NewEventListener { name: "click", id: ElementId(1) },
+            HydrateText { path: TemplatePath::Slice(&[0]), value: "3 clicks", id: ElementId(2) },
+            AppendChildren { m: 1, id: ElementId(0) },
+        ]
+    );
+
+    dom.handle_event("click", Rc::new("clicked".to_string()), ElementId(1), true);
+    assert_eq!(CLICKS.load(Ordering::SeqCst), 1);
+}
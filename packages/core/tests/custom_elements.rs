@@ -0,0 +1,79 @@
+//! Custom elements take properties with `"prop:name"` and dispatch events that are listened to with `"on:name"`
+
+use dioxus::core::{BorrowedAttributeValue, ElementId, Mutation::*};
+use dioxus::html::CustomEventData;
+use dioxus::prelude::*;
+use std::{cell::RefCell, rc::Rc};
+
+#[derive(Debug, Clone, PartialEq)]
+struct Items(Vec<&'static str>);
+
+thread_local! {
+    static SELECTED: RefCell<Vec<Option<u32>>> = const { RefCell::new(Vec::new()) };
+}
+
+fn app(cx: Scope) -> Element {
+    cx.render(rsx! {
+        my-list {
+            "prop:items": cx.any_value(Items(vec!["a", "b"])),
+            "prop:label": "items",
+            "on:item-selected": move |evt: CustomEvent| {
+                SELECTED.with(|selected| selected.borrow_mut().push(evt.detail().ok()));
+            }
+        }
+    })
+}
+
+#[test]
+fn properties_are_dynamic_attributes() {
+    let mut dom = VirtualDom::new(app);
+    let edits = dom.rebuild().santize();
+
+    // Cloning a template doesn't copy properties, so even static text is set on every element
+    let label = edits.edits.iter().find_map(|edit| match edit {
+        SetAttribute { name: "label", value: BorrowedAttributeValue::Text(value), ns, .. } => {
+            Some((*value, *ns))
+        }
+        _ => None,
+    });
+    assert_eq!(label, Some(("items", Some("prop"))));
+
+    let items = edits.edits.iter().find_map(|edit| match edit {
+        SetAttribute { name: "items", value: BorrowedAttributeValue::Any(value), ns, .. } => {
+            Some((value.downcast_ref::<Items>().cloned(), *ns))
+        }
+        _ => None,
+    });
+    assert_eq!(items, Some((Some(Items(vec!["a", "b"])), Some("prop"))));
+}
+
+#[test]
+fn custom_events_have_typed_details() {
+    let mut dom = VirtualDom::new(app);
+    let id = dom
+        .rebuild()
+        .santize()
+        .edits
+        .iter()
+        .find_map(|edit| match edit {
+            NewEventListener { name: "item-selected", id } => Some(*id),
+            _ => None,
+        })
+        .unwrap();
+    assert_eq!(id, ElementId(1));
+
+    dom.handle_event(
+        "item-selected",
+        Rc::new(CustomEventData::new("2")),
+        id,
+        false,
+    );
+    dom.handle_event(
+        "item-selected",
+        Rc::new(CustomEventData::default()),
+        id,
+        false,
+    );
+
+    SELECTED.with(|selected| assert_eq!(*selected.borrow(), [Some(2), None]));
+}
@@ -0,0 +1,50 @@
+//! Props can pick which fields decide if a component renders again
+
+use dioxus::prelude::*;
+use std::{cell::Cell, rc::Rc};
+
+thread_local! {
+    static ROW_RENDERS: Cell<usize> = const { Cell::new(0) };
+}
+
+#[derive(Props)]
+#[props(memo = same_row)]
+struct RowProps {
+    id: usize,
+    label: Rc<dyn Fn() -> String>,
+}
+
+fn same_row(old: &RowProps, new: &RowProps) -> bool {
+    old.id == new.id
+}
+
+#[allow(non_snake_case)]
+fn Row(cx: Scope<RowProps>) -> Element {
+    ROW_RENDERS.with(|renders| renders.set(renders.get() + 1));
+    let label = (cx.props.label)();
+    render! { div { "{cx.props.id}: {label}" } }
+}
+
+fn app(cx: Scope) -> Element {
+    let generation = cx.generation();
+    render! {
+        Row { id: generation / 2, label: Rc::new(move || format!("row {}", generation)) }
+    }
+}
+
+#[test]
+fn custom_memo_decides_if_the_component_renders() {
+    let mut dom = VirtualDom::new(app);
+    _ = dom.rebuild();
+    assert_eq!(ROW_RENDERS.with(Cell::get), 1);
+
+    // The id stays the same, so the new closure is ignored
+    dom.mark_dirty(ScopeId::ROOT);
+    assert!(dom.render_immediate().edits.is_empty());
+    assert_eq!(ROW_RENDERS.with(Cell::get), 1);
+
+    // The id changed, so the row renders with the new props
+    dom.mark_dirty(ScopeId::ROOT);
+    assert!(!dom.render_immediate().edits.is_empty());
+    assert_eq!(ROW_RENDERS.with(Cell::get), 2);
+}
@@ -0,0 +1,102 @@
+//! With delegated events, the renderer listens once per event at the root instead of once per element
+
+use dioxus::core::{ElementId, Mutation::*};
+use dioxus::html::MouseData;
+use dioxus::prelude::*;
+use dioxus_core::Capabilities;
+use std::{cell::RefCell, rc::Rc};
+
+thread_local! {
+    static CLICKED: RefCell<Vec<usize>> = const { RefCell::new(Vec::new()) };
+}
+
+fn app(cx: Scope) -> Element {
+    // The buttons go away on the second render
+    let count = match cx.generation() {
+        0 => 3,
+        _ => 0,
+    };
+
+    cx.render(rsx! {
+        (0..count).map(|i| rsx! {
+            button { key: "{i}", onclick: move |_| CLICKED.with(|c| c.borrow_mut().push(i)) }
+        })
+    })
+}
+
+fn delegated() -> Capabilities {
+    Capabilities { delegated_events: true, ..Default::default() }
+}
+
+#[test]
+fn one_root_listener_for_three_handlers() {
+    let mut dom = VirtualDom::new(app).with_capabilities(delegated());
+
+    assert_eq!(
+        dom.rebuild().santize().edits,
+        [
+            LoadTemplate { name: "template", index: 0, id: ElementId(1) },
+            RegisterDelegated { name: "click" },
+            LoadTemplate { name: "template", index: 0, id: ElementId(2) },
+            LoadTemplate { name: "template", index: 0, id: ElementId(3) },
+            AppendChildren { id: ElementId(0), m: 3 },
+        ]
+    );
+
+    for id in 1..=3 {
+        assert!(dom.has_delegated_listener(ElementId(id), "click"));
+    }
+
+    // The renderer routes the event by the id of its target
+    dom.handle_event("click", Rc::new(MouseData::default()), ElementId(2), true);
+    CLICKED.with(|c| assert_eq!(*c.borrow(), [1]));
+}
+
+#[test]
+fn root_listener_is_removed_with_the_last_handler() {
+    let mut dom = VirtualDom::new(app).with_capabilities(delegated());
+    _ = dom.rebuild();
+
+    dom.mark_dirty(ScopeId::ROOT);
+    let unregistered = dom
+        .render_immediate()
+        .edits
+        .iter()
+        .filter(|edit| matches!(edit, UnregisterDelegated { name: "click" }))
+        .count();
+    assert_eq!(unregistered, 1);
+    assert!(!dom.has_delegated_listener(ElementId(1), "click"));
+}
+
+#[test]
+fn rebuilding_registers_events_again() {
+    fn app(cx: Scope) -> Element {
+        cx.render(rsx! { button { onclick: |_| {} } })
+    }
+
+    let mut dom = VirtualDom::new(app).with_capabilities(delegated());
+    _ = dom.rebuild();
+
+    // The new renderer isn't listening for anything yet
+    let registered = dom
+        .rebuild()
+        .edits
+        .iter()
+        .filter(|edit| matches!(edit, RegisterDelegated { name: "click" }))
+        .count();
+    assert_eq!(registered, 1);
+}
+
+#[test]
+fn listeners_are_per_element_by_default() {
+    let mut dom = VirtualDom::new(app);
+
+    let listeners = dom
+        .rebuild()
+        .edits
+        .iter()
+        .filter(|edit| matches!(edit, NewEventListener { .. }))
+        .count();
+    assert_eq!(listeners, 3);
+    assert!(!dom.has_delegated_listener(ElementId(1), "click"));
+}
@@ -0,0 +1,68 @@
+use dioxus::core::Mutation::*;
+use dioxus::prelude::*;
+use dioxus_core::{BorrowedAttributeValue, DerivedAttribute, ElementId, MockDom};
+
+fn valuetext() -> DerivedAttribute {
+    DerivedAttribute::new(|attrs| Some(format!("{} percent", attrs.get("value")?)))
+}
+
+fn app(cx: Scope) -> Element {
+    let value = 50 + cx.generation() * 10;
+    cx.render(rsx! {
+        input { r#type: "range", value: "{value}", "aria-valuetext": valuetext() }
+    })
+}
+
+#[test]
+fn derived_attributes_are_computed_by_the_renderer() {
+    let mut vdom = VirtualDom::new(app);
+    let dom = MockDom::new();
+    dom.apply(&vdom.rebuild());
+
+    assert_eq!(
+        dom.attribute(ElementId(1), "aria-valuetext"),
+        Some("50 percent")
+    );
+}
+
+#[test]
+fn derived_attributes_follow_their_inputs() {
+    let mut vdom = VirtualDom::new(app);
+    let dom = MockDom::new();
+    dom.apply(&vdom.rebuild());
+
+    vdom.mark_dirty(ScopeId::ROOT);
+    let mutations = vdom.render_immediate();
+
+    // Only the input is sent, the renderer takes care of the derived attribute
+    assert_eq!(
+        mutations.edits,
+        [SetAttribute {
+            name: "value",
+            value: BorrowedAttributeValue::Text("60"),
+            id: ElementId(1),
+            ns: None,
+        }]
+    );
+
+    dom.apply(&mutations);
+    assert_eq!(
+        dom.attribute(ElementId(1), "aria-valuetext"),
+        Some("60 percent")
+    );
+}
+
+#[test]
+fn derived_attributes_are_removed_without_their_inputs() {
+    let dom = MockDom::new();
+    let mut vdom = VirtualDom::new(app);
+    dom.apply(&vdom.rebuild());
+
+    dom.apply_edit(&SetAttribute {
+        name: "value",
+        value: BorrowedAttributeValue::None,
+        id: ElementId(1),
+        ns: None,
+    });
+    assert_eq!(dom.attribute(ElementId(1), "aria-valuetext"), None);
+}
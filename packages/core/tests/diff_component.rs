@@ -1,4 +1,4 @@
-use dioxus::core::{ElementId, Mutation::*};
+use dioxus::core::{ElementId, Mutation::*, TemplatePath};
 use dioxus::prelude::*;
 
 /// When returning sets of components, we do a light diff of the contents to preserve some react-like functionality
@@ -37,6 +37,7 @@ fn component_swap() {
         })
     }
 
+    #[allow(deprecated)]
     fn nav_bar(cx: Scope) -> Element {
         cx.render(rsx! {
             h1 {
@@ -68,7 +69,7 @@ fn component_swap() {
                 LoadTemplate { name: "template", index: 0, id: ElementId(2) },
                 LoadTemplate { name: "template", index: 0, id: ElementId(3) },
                 LoadTemplate { name: "template", index: 0, id: ElementId(4) },
-                ReplacePlaceholder { path: &[1], m: 3 },
+                ReplacePlaceholder { path: TemplatePath::Slice(&[1]), m: 3 },
                 LoadTemplate { name: "template", index: 0, id: ElementId(5) },
                 AppendChildren { m: 2, id: ElementId(0) }
             ]
@@ -0,0 +1,65 @@
+//! The cost of a diff can be estimated without running it
+
+use dioxus::prelude::*;
+use dioxus_core::{diff_cost, DiffCost};
+
+fn view<'a>(cx: &'a ScopeState, class: &str, text: &str, items: &[u32]) -> VNode<'a> {
+    rsx! {
+        div { class: "{class}", lang: "en",
+            "{text}"
+            items.iter().map(|i| rsx!(li { key: "{i}", "{i}" }))
+        }
+    }
+    .call(cx)
+}
+
+fn app(cx: Scope) -> Element {
+    match cx.generation() {
+        0 => Some(view(cx, "a", "hello", &[1, 2, 3])),
+        _ => Some(view(cx, "b", "world", &[1, 3, 4])),
+    }
+}
+
+#[test]
+fn cost_of_identical_nodes_is_empty() {
+    let mut dom = VirtualDom::new(app);
+    _ = dom.rebuild();
+
+    let scope = dom.base_scope();
+    let old = view(scope, "a", "hello", &[1, 2]);
+    let new = view(scope, "a", "hello", &[1, 2]);
+    assert!(diff_cost(&old, &new).is_empty());
+}
+
+#[test]
+fn cost_matches_the_real_diff() {
+    let mut dom = VirtualDom::new(app);
+    _ = dom.rebuild();
+
+    let cost = {
+        let scope = dom.base_scope();
+        let old = view(scope, "a", "hello", &[1, 2, 3]);
+        let new = view(scope, "b", "world", &[1, 3, 4]);
+        diff_cost(&old, &new)
+    };
+    assert_eq!(
+        cost,
+        DiffCost { set_attributes: 1, set_texts: 1, created: 1, removed: 1, components: 0 }
+    );
+
+    dom.mark_dirty(ScopeId::ROOT);
+    let edits = dom.render_immediate().santize();
+
+    // Creating the new item takes a few mutations to fill in, and moves are not counted
+    let updates = edits
+        .edits
+        .iter()
+        .filter(|edit| {
+            matches!(
+                edit,
+                dioxus_core::Mutation::SetAttribute { .. } | dioxus_core::Mutation::SetText { .. }
+            )
+        })
+        .count();
+    assert_eq!(updates, cost.set_attributes + cost.set_texts);
+}
@@ -82,3 +82,35 @@ fn element_swap() {
         ]
     );
 }
+
+#[test]
+fn text_swaps_with_placeholder() {
+    fn app(cx: Scope) -> Element {
+        let label = match cx.generation() % 2 {
+            0 => Some("loaded"),
+            _ => None,
+        };
+        cx.render(rsx!(div { label }))
+    }
+
+    let mut vdom = VirtualDom::new(app);
+    _ = vdom.rebuild();
+
+    vdom.mark_dirty(ScopeId::ROOT);
+    assert_eq!(
+        vdom.render_immediate().santize().edits,
+        [
+            CreatePlaceholder { id: ElementId(3) },
+            ReplaceWith { id: ElementId(2), m: 1 },
+        ]
+    );
+
+    vdom.mark_dirty(ScopeId::ROOT);
+    assert_eq!(
+        vdom.render_immediate().santize().edits,
+        [
+            CreateTextNode { value: "loaded", id: ElementId(2) },
+            ReplaceWith { id: ElementId(3), m: 1 },
+        ]
+    );
+}
@@ -0,0 +1,40 @@
+//! Diffs of sendable trees can explain why each mutation was emitted
+
+use dioxus::core::{diff_explained, ElementId, Mutation, RenderReturn, SendableNode};
+use dioxus::prelude::*;
+
+fn app(cx: Scope) -> Element {
+    let label = if cx.generation() == 0 { "a" } else { "b" };
+    cx.render(rsx! {
+        ul { li { "first" } li { "{label}" } }
+        p { "static" }
+    })
+}
+
+fn snapshot(dom: &VirtualDom) -> Vec<SendableNode> {
+    match dom.base_scope().root_node() {
+        RenderReturn::Ready(node) => node.to_sendable(dom),
+        RenderReturn::Aborted(_) => unreachable!(),
+    }
+}
+
+#[test]
+fn explanations_follow_mutations() {
+    let mut dom = VirtualDom::new(app);
+    _ = dom.rebuild();
+    let old = snapshot(&dom);
+
+    dom.mark_dirty(ScopeId::ROOT);
+    _ = dom.render_immediate();
+    let new = snapshot(&dom);
+
+    let (edits, explanations) = diff_explained(&old, &new).unwrap();
+    assert_eq!(*edits, [Mutation::SetText { value: "b", id: ElementId(2) }]);
+    assert_eq!(
+        explanations,
+        [
+            r#"set the text at path [0, 1, 0] because it changed from "a" to "b""#,
+            "skipped root 1 because nothing in it changed",
+        ]
+    );
+}
@@ -356,3 +356,66 @@ fn no_common_keys() {
         ]
     );
 }
+
+/// Templates with many roots move together, keyed by their first root
+#[test]
+fn keyed_diffing_multiple_roots() {
+    let mut dom = VirtualDom::new(|cx| {
+        let order: &[_] = match cx.generation() % 2 {
+            0 => &[0, 1, 2],
+            _ => &[2, 0, 1],
+        };
+
+        cx.render(rsx! {
+            order.iter().map(|i| rsx! {
+                dt { key: "{i}", "{i}" }
+                dd { "{i}" }
+            })
+        })
+    });
+    let renderer = dioxus_core::MockDom::new();
+    renderer.apply(&dom.rebuild());
+
+    dom.mark_dirty(ScopeId::ROOT);
+    let edits = dom.render_immediate();
+    renderer.apply(&edits);
+
+    // The items are moved, not created again
+    assert!(edits
+        .edits
+        .iter()
+        .all(|edit| matches!(edit, PushRoot { .. } | InsertBefore { .. })));
+    assert_eq!(
+        renderer.to_string(),
+        "<dt>2</dt><dd>2</dd><dt>0</dt><dd>0</dd><dt>1</dt><dd>1</dd>"
+    );
+}
+
+/// A key on a fragment keys everything in it
+#[test]
+fn keyed_diffing_fragments() {
+    let mut dom = VirtualDom::new(|cx| {
+        let order: &[_] = match cx.generation() % 2 {
+            0 => &[0, 1],
+            _ => &[1, 0],
+        };
+
+        cx.render(rsx! {
+            order.iter().map(|i| rsx! {
+                Fragment { key: "{i}", b { "{i}" } i { "{i}" } }
+            })
+        })
+    });
+    let renderer = dioxus_core::MockDom::new();
+    renderer.apply(&dom.rebuild());
+
+    dom.mark_dirty(ScopeId::ROOT);
+    let edits = dom.render_immediate();
+    renderer.apply(&edits);
+
+    assert!(edits
+        .edits
+        .iter()
+        .all(|edit| matches!(edit, PushRoot { .. } | InsertBefore { .. })));
+    assert_eq!(renderer.to_string(), "<b>1</b><i>1</i><b>0</b><i>0</i>");
+}
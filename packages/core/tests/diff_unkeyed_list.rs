@@ -1,4 +1,4 @@
-use dioxus::core::{ElementId, Mutation::*};
+use dioxus::core::{ElementId, Mutation::*, TemplatePath};
 use dioxus::prelude::*;
 use pretty_assertions::assert_eq;
 
@@ -21,7 +21,7 @@ fn list_creates_one_by_one() {
         dom.rebuild().santize().edits,
         [
             LoadTemplate { name: "template", index: 0, id: ElementId(1,) },
-            AssignId { path: &[0], id: ElementId(2,) },
+            AssignId { path: TemplatePath::Slice(&[0]), id: ElementId(2,) },
             AppendChildren { id: ElementId(0), m: 1 },
         ]
     );
@@ -32,7 +32,7 @@ fn list_creates_one_by_one() {
         dom.render_immediate().santize().edits,
         [
             LoadTemplate { name: "template", index: 0, id: ElementId(3,) },
-            HydrateText { path: &[0], value: "0", id: ElementId(4,) },
+            HydrateText { path: TemplatePath::Slice(&[0]), value: "0", id: ElementId(4,) },
             ReplaceWith { id: ElementId(2,), m: 1 },
         ]
     );
@@ -43,7 +43,7 @@ fn list_creates_one_by_one() {
         dom.render_immediate().santize().edits,
         [
             LoadTemplate { name: "template", index: 0, id: ElementId(2,) },
-            HydrateText { path: &[0], value: "1", id: ElementId(5,) },
+            HydrateText { path: TemplatePath::Slice(&[0]), value: "1", id: ElementId(5,) },
             InsertAfter { id: ElementId(3,), m: 1 },
         ]
     );
@@ -54,7 +54,7 @@ fn list_creates_one_by_one() {
         dom.render_immediate().santize().edits,
         [
             LoadTemplate { name: "template", index: 0, id: ElementId(6,) },
-            HydrateText { path: &[0], value: "2", id: ElementId(7,) },
+            HydrateText { path: TemplatePath::Slice(&[0]), value: "2", id: ElementId(7,) },
             InsertAfter { id: ElementId(2,), m: 1 },
         ]
     );
@@ -65,7 +65,7 @@ fn list_creates_one_by_one() {
         dom.render_immediate().santize().edits,
         [
             LoadTemplate { name: "template", index: 0, id: ElementId(8,) },
-            HydrateText { path: &[0], value: "3", id: ElementId(9,) },
+            HydrateText { path: TemplatePath::Slice(&[0]), value: "3", id: ElementId(9,) },
             InsertAfter { id: ElementId(6,), m: 1 },
         ]
     );
@@ -93,13 +93,13 @@ fn removes_one_by_one() {
             LoadTemplate { name: "template", index: 0, id: ElementId(1) },
             // each list item
             LoadTemplate { name: "template", index: 0, id: ElementId(2) },
-            HydrateText { path: &[0], value: "0", id: ElementId(3) },
+            HydrateText { path: TemplatePath::Slice(&[0]), value: "0", id: ElementId(3) },
             LoadTemplate { name: "template", index: 0, id: ElementId(4) },
-            HydrateText { path: &[0], value: "1", id: ElementId(5) },
+            HydrateText { path: TemplatePath::Slice(&[0]), value: "1", id: ElementId(5) },
             LoadTemplate { name: "template", index: 0, id: ElementId(6) },
-            HydrateText { path: &[0], value: "2", id: ElementId(7) },
+            HydrateText { path: TemplatePath::Slice(&[0]), value: "2", id: ElementId(7) },
             // replace the placeholder in the template with the 3 templates on the stack
-            ReplacePlaceholder { m: 3, path: &[0] },
+            ReplacePlaceholder { m: 3, path: TemplatePath::Slice(&[0]) },
             // Mount the div
             AppendChildren { id: ElementId(0), m: 1 }
         ]
@@ -138,11 +138,11 @@ fn removes_one_by_one() {
         dom.render_immediate().santize().edits,
         [
             LoadTemplate { name: "template", index: 0, id: ElementId(2) },
-            HydrateText { path: &[0], value: "0", id: ElementId(3) },
+            HydrateText { path: TemplatePath::Slice(&[0]), value: "0", id: ElementId(3) },
             LoadTemplate { name: "template", index: 0, id: ElementId(5) },
-            HydrateText { path: &[0], value: "1", id: ElementId(6) },
+            HydrateText { path: TemplatePath::Slice(&[0]), value: "1", id: ElementId(6) },
             LoadTemplate { name: "template", index: 0, id: ElementId(7) },
-            HydrateText { path: &[0], value: "2", id: ElementId(8) },
+            HydrateText { path: TemplatePath::Slice(&[0]), value: "2", id: ElementId(8) },
             ReplaceWith { id: ElementId(4), m: 3 }
         ]
     );
@@ -165,7 +165,7 @@ fn list_shrink_multiroot() {
         dom.rebuild().santize().edits,
         [
             LoadTemplate { name: "template", index: 0, id: ElementId(1,) },
-            AssignId { path: &[0,], id: ElementId(2,) },
+            AssignId { path: TemplatePath::Slice(&[0,]), id: ElementId(2,) },
             AppendChildren { id: ElementId(0), m: 1 }
         ]
     );
@@ -175,9 +175,9 @@ fn list_shrink_multiroot() {
         dom.render_immediate().santize().edits,
         [
             LoadTemplate { name: "template", index: 0, id: ElementId(3) },
-            HydrateText { path: &[0], value: "0", id: ElementId(4) },
+            HydrateText { path: TemplatePath::Slice(&[0]), value: "0", id: ElementId(4) },
             LoadTemplate { name: "template", index: 1, id: ElementId(5) },
-            HydrateText { path: &[0], value: "0", id: ElementId(6) },
+            HydrateText { path: TemplatePath::Slice(&[0]), value: "0", id: ElementId(6) },
             ReplaceWith { id: ElementId(2), m: 2 }
         ]
     );
@@ -187,9 +187,9 @@ fn list_shrink_multiroot() {
         dom.render_immediate().santize().edits,
         [
             LoadTemplate { name: "template", index: 0, id: ElementId(2) },
-            HydrateText { path: &[0], value: "1", id: ElementId(7) },
+            HydrateText { path: TemplatePath::Slice(&[0]), value: "1", id: ElementId(7) },
             LoadTemplate { name: "template", index: 1, id: ElementId(8) },
-            HydrateText { path: &[0], value: "1", id: ElementId(9) },
+            HydrateText { path: TemplatePath::Slice(&[0]), value: "1", id: ElementId(9) },
             InsertAfter { id: ElementId(5), m: 2 }
         ]
     );
@@ -199,9 +199,9 @@ fn list_shrink_multiroot() {
         dom.render_immediate().santize().edits,
         [
             LoadTemplate { name: "template", index: 0, id: ElementId(10) },
-            HydrateText { path: &[0], value: "2", id: ElementId(11) },
+            HydrateText { path: TemplatePath::Slice(&[0]), value: "2", id: ElementId(11) },
             LoadTemplate { name: "template", index: 1, id: ElementId(12) },
-            HydrateText { path: &[0], value: "2", id: ElementId(13) },
+            HydrateText { path: TemplatePath::Slice(&[0]), value: "2", id: ElementId(13) },
             InsertAfter { id: ElementId(8), m: 2 }
         ]
     );
@@ -229,21 +229,21 @@ fn removes_one_by_one_multiroot() {
             LoadTemplate { name: "template", index: 0, id: ElementId(1) },
             //
             LoadTemplate { name: "template", index: 0, id: ElementId(2) },
-            HydrateText { path: &[0], value: "0", id: ElementId(3) },
+            HydrateText { path: TemplatePath::Slice(&[0]), value: "0", id: ElementId(3) },
             LoadTemplate { name: "template", index: 1, id: ElementId(4) },
-            HydrateText { path: &[0], value: "0", id: ElementId(5) },
+            HydrateText { path: TemplatePath::Slice(&[0]), value: "0", id: ElementId(5) },
             //
             LoadTemplate { name: "template", index: 0, id: ElementId(6) },
-            HydrateText { path: &[0], value: "1", id: ElementId(7) },
+            HydrateText { path: TemplatePath::Slice(&[0]), value: "1", id: ElementId(7) },
             LoadTemplate { name: "template", index: 1, id: ElementId(8) },
-            HydrateText { path: &[0], value: "1", id: ElementId(9) },
+            HydrateText { path: TemplatePath::Slice(&[0]), value: "1", id: ElementId(9) },
             //
             LoadTemplate { name: "template", index: 0, id: ElementId(10) },
-            HydrateText { path: &[0], value: "2", id: ElementId(11) },
+            HydrateText { path: TemplatePath::Slice(&[0]), value: "2", id: ElementId(11) },
             LoadTemplate { name: "template", index: 1, id: ElementId(12) },
-            HydrateText { path: &[0], value: "2", id: ElementId(13) },
+            HydrateText { path: TemplatePath::Slice(&[0]), value: "2", id: ElementId(13) },
             //
-            ReplacePlaceholder { path: &[0], m: 6 },
+            ReplacePlaceholder { path: TemplatePath::Slice(&[0]), m: 6 },
             //
             AppendChildren { id: ElementId(0), m: 1 }
         ]
@@ -334,7 +334,11 @@ fn remove_many() {
             edits.edits,
             [
                 LoadTemplate { name: "template", index: 0, id: ElementId(2,) },
-                HydrateText { path: &[0,], value: "hello 0", id: ElementId(3,) },
+                HydrateText {
+                    path: TemplatePath::Slice(&[0,]),
+                    value: "hello 0",
+                    id: ElementId(3,)
+                },
                 ReplaceWith { id: ElementId(1,), m: 1 },
             ]
         );
@@ -347,13 +351,29 @@ fn remove_many() {
             edits.edits,
             [
                 LoadTemplate { name: "template", index: 0, id: ElementId(1,) },
-                HydrateText { path: &[0,], value: "hello 1", id: ElementId(4,) },
+                HydrateText {
+                    path: TemplatePath::Slice(&[0,]),
+                    value: "hello 1",
+                    id: ElementId(4,)
+                },
                 LoadTemplate { name: "template", index: 0, id: ElementId(5,) },
-                HydrateText { path: &[0,], value: "hello 2", id: ElementId(6,) },
+                HydrateText {
+                    path: TemplatePath::Slice(&[0,]),
+                    value: "hello 2",
+                    id: ElementId(6,)
+                },
                 LoadTemplate { name: "template", index: 0, id: ElementId(7,) },
-                HydrateText { path: &[0,], value: "hello 3", id: ElementId(8,) },
+                HydrateText {
+                    path: TemplatePath::Slice(&[0,]),
+                    value: "hello 3",
+                    id: ElementId(8,)
+                },
                 LoadTemplate { name: "template", index: 0, id: ElementId(9,) },
-                HydrateText { path: &[0,], value: "hello 4", id: ElementId(10,) },
+                HydrateText {
+                    path: TemplatePath::Slice(&[0,]),
+                    value: "hello 4",
+                    id: ElementId(10,)
+                },
                 InsertAfter { id: ElementId(2,), m: 4 },
             ]
         );
@@ -382,7 +402,11 @@ fn remove_many() {
             edits.edits,
             [
                 LoadTemplate { name: "template", index: 0, id: ElementId(2,) },
-                HydrateText { path: &[0,], value: "hello 0", id: ElementId(3,) },
+                HydrateText {
+                    path: TemplatePath::Slice(&[0,]),
+                    value: "hello 0",
+                    id: ElementId(3,)
+                },
                 ReplaceWith { id: ElementId(11,), m: 1 },
             ]
         )
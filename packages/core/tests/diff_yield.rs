@@ -0,0 +1,85 @@
+#![allow(non_snake_case)]
+
+use dioxus::prelude::*;
+
+fn app(cx: Scope) -> Element {
+    cx.render(rsx! {
+        (0..50).map(|i| rsx! { Row { key: "{i}", idx: i } })
+    })
+}
+
+#[component]
+fn Row(cx: Scope, idx: usize) -> Element {
+    let generation = cx.generation();
+    cx.render(rsx! { div { "{idx}: {generation}" } })
+}
+
+fn make_rows_dirty(dom: &mut VirtualDom) {
+    for id in 1..=50 {
+        dom.mark_dirty(ScopeId(id));
+    }
+}
+
+#[test]
+fn yielding_diff_produces_chunks_of_the_full_diff() {
+    let mut full = VirtualDom::new(app);
+    _ = full.rebuild();
+    make_rows_dirty(&mut full);
+    let full_edits = full.render_immediate().edits;
+    assert_eq!(full_edits.len(), 50);
+
+    // The edits borrow from the VirtualDom, so keep a description of each chunk around instead
+    let full_edits = full_edits
+        .iter()
+        .map(|edit| format!("{:?}", edit))
+        .collect::<Vec<_>>();
+
+    let mut chunked = VirtualDom::new(app);
+    _ = chunked.rebuild();
+    make_rows_dirty(&mut chunked);
+
+    // Yield as soon as possible, so every chunk only contains the diff of a single scope
+    let mut chunks = Vec::new();
+    while chunked.has_dirty_scopes() {
+        let edits = chunked.render_with_yield(|| true).edits;
+        assert!(!edits.is_empty());
+        chunks.push(
+            edits
+                .iter()
+                .map(|edit| format!("{:?}", edit))
+                .collect::<Vec<_>>(),
+        );
+    }
+
+    assert_eq!(chunks.len(), 50);
+    assert_eq!(chunks.concat(), full_edits);
+}
+
+#[test]
+fn never_yielding_renders_everything() {
+    let mut dom = VirtualDom::new(app);
+    _ = dom.rebuild();
+    make_rows_dirty(&mut dom);
+
+    let edits = dom.render_with_yield(|| false);
+    assert_eq!(edits.edits.len(), 50);
+    drop(edits);
+    assert!(!dom.has_dirty_scopes());
+}
+
+#[tokio::test]
+async fn deadline_splits_the_diff_across_calls() {
+    let mut dom = VirtualDom::new(app);
+    _ = dom.rebuild();
+    make_rows_dirty(&mut dom);
+
+    // A deadline that has already passed still diffs one scope so every call makes progress
+    let mut calls = 0;
+    while dom.has_dirty_scopes() {
+        let edits = dom.render_with_deadline(std::future::ready(())).await;
+        assert_eq!(edits.edits.len(), 1);
+        calls += 1;
+    }
+
+    assert_eq!(calls, 50);
+}
@@ -0,0 +1,74 @@
+//! Elements can carry a payload that drop targets receive while they are dragged
+
+use dioxus::core::{ElementId, Mutation::*};
+use dioxus::html::{DragData, MouseData};
+use dioxus::prelude::*;
+use std::{cell::RefCell, rc::Rc};
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct CardId(u32);
+
+thread_local! {
+    static DROPPED: RefCell<Vec<Option<CardId>>> = const { RefCell::new(Vec::new()) };
+}
+
+fn app(cx: Scope) -> Element {
+    cx.render(rsx! {
+        div { draggable: "true", "drag_payload": DragPayload::new(CardId(7)), "card" }
+        div {
+            ondrop: move |_| {
+                let card = drag_payload().and_then(|payload| payload.downcast_ref::<CardId>().copied());
+                DROPPED.with(|dropped| dropped.borrow_mut().push(card));
+            }
+        }
+    })
+}
+
+fn drag_event() -> Rc<DragData> {
+    Rc::new(DragData { mouse: MouseData::default(), files: None })
+}
+
+#[test]
+fn payload_is_delivered_on_drop() {
+    let mut dom = VirtualDom::new(app);
+
+    // The payload stays inside the VirtualDom
+    let edits = dom.rebuild().santize();
+    assert!(!edits
+        .edits
+        .iter()
+        .any(|edit| matches!(edit, SetAttribute { .. })));
+    drop(edits);
+
+    let card = ElementId(1);
+    let zone = ElementId(2);
+
+    dom.handle_event("dragstart", drag_event(), card, true);
+    dom.handle_event("drop", drag_event(), zone, true);
+    dom.handle_event("dragend", drag_event(), card, true);
+
+    // Once the drag is over, drops no longer see the payload
+    dom.handle_event("drop", drag_event(), zone, true);
+
+    DROPPED.with(|dropped| assert_eq!(*dropped.borrow(), [Some(CardId(7)), None]));
+}
+
+#[test]
+fn payload_makes_the_renderer_listen_for_drags() {
+    let mut dom = VirtualDom::new(app);
+    let edits = dom.rebuild().santize();
+
+    // The card has no drag listeners of its own, but the renderer still has to send its drag events
+    let card = ElementId(1);
+    let listens = |event: &str| {
+        edits.edits.iter().any(|edit| match edit {
+            CreateElementWithAttrs { id, listeners, .. } => {
+                *id == card && listeners.iter().any(|listener| *listener == event)
+            }
+            NewEventListener { name, id } => *id == card && *name == event,
+            _ => false,
+        })
+    };
+    assert!(listens("dragstart"));
+    assert!(listens("dragend"));
+}
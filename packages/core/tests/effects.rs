@@ -0,0 +1,74 @@
+//! Effects run after the mutations of a render are applied, and clean up before they run again
+
+use dioxus::prelude::*;
+use std::cell::{Cell, RefCell};
+
+thread_local! {
+    static LOG: RefCell<Vec<String>> = const { RefCell::new(Vec::new()) };
+    static SHOW: Cell<bool> = const { Cell::new(true) };
+}
+
+fn log(line: String) {
+    LOG.with(|log| log.borrow_mut().push(line));
+}
+
+fn take_log() -> Vec<String> {
+    LOG.with(|log| log.borrow_mut().drain(..).collect())
+}
+
+fn app(cx: Scope) -> Element {
+    let show = SHOW.with(Cell::get);
+    let generation = cx.generation();
+    render! {
+        if show {
+            rsx! { Child { value: generation } }
+        }
+    }
+}
+
+#[derive(Props, PartialEq)]
+struct ChildProps {
+    value: usize,
+}
+
+#[allow(non_snake_case)]
+fn Child(cx: Scope<ChildProps>) -> Element {
+    let value = cx.props.value;
+    log(format!("render {}", value));
+    use_render_effect(cx, (&value,), |(value,)| {
+        log(format!("effect {}", value));
+        move || log(format!("cleanup {}", value))
+    });
+    render! { "{value}" }
+}
+
+#[test]
+fn effects_run_after_render_and_clean_up() {
+    let mut dom = VirtualDom::new(app);
+    _ = dom.rebuild();
+    // The effect waits for the renderer to apply the mutations
+    assert_eq!(take_log(), ["render 0"]);
+
+    dom.run_effects();
+    assert_eq!(take_log(), ["effect 0"]);
+
+    // Same dependencies, so the effect doesn't run again
+    dom.mark_dirty(ScopeId(1));
+    _ = dom.render_immediate();
+    dom.run_effects();
+    assert_eq!(take_log(), ["render 0"]);
+
+    // The child renders with new props, so the old effect is cleaned up first
+    dom.mark_dirty(ScopeId::ROOT);
+    _ = dom.render_immediate();
+    assert_eq!(take_log(), ["render 1"]);
+    dom.run_effects();
+    assert_eq!(take_log(), ["cleanup 0", "effect 1"]);
+
+    // Unmounting the child cleans up the last effect
+    SHOW.with(|show| show.set(false));
+    dom.mark_dirty(ScopeId::ROOT);
+    _ = dom.render_immediate();
+    dom.run_effects();
+    assert_eq!(take_log(), ["cleanup 1"]);
+}
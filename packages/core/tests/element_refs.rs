@@ -0,0 +1,67 @@
+//! Attributes can reference another element by the key of its node, and get its id once it is mounted
+
+use dioxus::core::{AttributeValue, BorrowedAttributeValue, ElementId, Mutation::*};
+use dioxus::prelude::*;
+
+fn app(cx: Scope) -> Element {
+    let fields: &[&str] = match cx.generation() {
+        0 => &["email"],
+        1 => &["name", "email"],
+        _ => &[],
+    };
+
+    // The label comes before the input it points to
+    cx.render(rsx! {
+        label { "for": AttributeValue::ElementRefByKey("email"), "Email" }
+        fields.iter().map(|field| rsx! { input { key: "{field}", name: "{field}" } })
+    })
+}
+
+#[test]
+fn forward_reference_is_resolved() {
+    let mut dom = VirtualDom::new(app);
+    let edits = dom.rebuild().santize().edits;
+
+    // The input is created after the label, and the reference is sent once it exists
+    assert_eq!(
+        edits[edits.len() - 2..],
+        [
+            AppendChildren { id: ElementId(0), m: 2 },
+            SetAttribute {
+                name: "for",
+                value: BorrowedAttributeValue::Int(2),
+                id: ElementId(1),
+                ns: None
+            },
+        ]
+    );
+    assert!(edits.contains(&LoadTemplate { name: "template", index: 0, id: ElementId(2) }));
+}
+
+#[test]
+fn reference_follows_its_target() {
+    let mut dom = VirtualDom::new(app);
+    _ = dom.rebuild();
+
+    // Inserting a sibling before the input doesn't change what the label points to
+    dom.mark_dirty(ScopeId::ROOT);
+    let retargeted = dom
+        .render_immediate()
+        .edits
+        .iter()
+        .any(|edit| matches!(edit, SetAttribute { name: "for", .. }));
+    assert!(!retargeted);
+
+    // Without the input, the attribute is removed
+    dom.mark_dirty(ScopeId::ROOT);
+    let edits = dom.render_immediate().santize().edits;
+    assert_eq!(
+        edits.last(),
+        Some(&SetAttribute {
+            name: "for",
+            value: BorrowedAttributeValue::None,
+            id: ElementId(1),
+            ns: None
+        })
+    );
+}
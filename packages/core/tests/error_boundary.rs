@@ -1,6 +1,11 @@
 #![allow(non_snake_case)]
 
+use dioxus::core::{CapturedError, MockDom};
 use dioxus::prelude::*;
+use std::{
+    cell::{Cell, RefCell},
+    rc::Rc,
+};
 
 #[test]
 fn catches_panic() {
@@ -32,3 +37,67 @@ fn ThrowChild(cx: Scope) -> Element {
         div {}
     })
 }
+
+thread_local! {
+    static OFFLINE: Cell<bool> = const { Cell::new(true) };
+    static BOUNDARY: RefCell<Option<Rc<ErrorBoundary>>> = const { RefCell::new(None) };
+}
+
+fn boundary_app(cx: Scope) -> Element {
+    cx.render(rsx! {
+        div {
+            ErrorBoundary {
+                fallback: |error: Rc<CapturedError>, boundary: Rc<ErrorBoundary>| {
+                    BOUNDARY.with(|b| *b.borrow_mut() = Some(boundary));
+                    rsx! { "failed: {error.error:?}" }
+                },
+                Profile {}
+            }
+        }
+    })
+}
+
+fn Profile(cx: Scope) -> Element {
+    if OFFLINE.with(Cell::get) {
+        cx.throw("offline")?;
+    }
+    render!("profile")
+}
+
+#[test]
+fn boundary_renders_fallback_and_resets() {
+    let mut dom = VirtualDom::new(boundary_app);
+    let renderer = MockDom::new();
+    renderer.apply(&dom.rebuild());
+
+    renderer.apply(&dom.render_immediate());
+    assert_eq!(renderer.to_string(), "<div>failed: \"offline\"</div>");
+
+    // Resetting the boundary renders the children again
+    OFFLINE.with(|offline| offline.set(false));
+    BOUNDARY.with(|b| b.borrow().as_ref().unwrap().reset());
+    renderer.apply(&dom.render_immediate());
+    assert_eq!(renderer.to_string(), "<div>profile</div>");
+}
+
+#[test]
+fn panics_are_captured() {
+    fn app(cx: Scope) -> Element {
+        cx.render(rsx! {
+            ErrorBoundary {
+                fallback: |error: Rc<CapturedError>, _| rsx! { "{error.error:?}" },
+                Panicky {}
+            }
+        })
+    }
+
+    fn Panicky(_cx: Scope) -> Element {
+        panic!("boom")
+    }
+
+    let mut dom = VirtualDom::new(app);
+    let renderer = MockDom::new();
+    renderer.apply(&dom.rebuild());
+    renderer.apply(&dom.render_immediate());
+    assert_eq!(renderer.to_string(), "\"`Panicky` panicked: boom\"");
+}
@@ -0,0 +1,130 @@
+#![allow(non_snake_case)]
+//! Events bubble from their target up through the templates and components it is mounted in
+
+use dioxus::core::ElementId;
+use dioxus::html::MouseData;
+use dioxus::prelude::*;
+use dioxus_core::Capabilities;
+use std::{
+    cell::{Cell, RefCell},
+    rc::Rc,
+};
+
+thread_local! {
+    static FIRED: RefCell<Vec<&'static str>> = const { RefCell::new(Vec::new()) };
+    static STOP_AT_PARENT: Cell<bool> = const { Cell::new(false) };
+}
+
+fn fired() -> Vec<&'static str> {
+    FIRED.with(|fired| fired.borrow_mut().drain(..).collect())
+}
+
+// The target, its parent and its grandparent are all in different templates
+fn app(cx: Scope) -> Element {
+    let parent = rsx! {
+        section {
+            onclick: |evt: MouseEvent| {
+                FIRED.with(|fired| fired.borrow_mut().push("parent"));
+                if STOP_AT_PARENT.with(Cell::get) {
+                    evt.stop_propagation();
+                }
+            },
+            Target {}
+        }
+    };
+
+    cx.render(rsx! {
+        div {
+            onclick: |_| FIRED.with(|fired| fired.borrow_mut().push("grandparent")),
+            parent
+        }
+    })
+}
+
+fn Target(cx: Scope) -> Element {
+    cx.render(rsx! {
+        button { onclick: |_| FIRED.with(|fired| fired.borrow_mut().push("target")) }
+    })
+}
+
+fn click(dom: &mut VirtualDom, id: ElementId) {
+    dom.handle_event("click", Rc::new(MouseData::default()), id, true);
+}
+
+#[test]
+fn handlers_fire_from_the_target_up() {
+    let mut dom = VirtualDom::new(app)
+        .with_capabilities(Capabilities { delegated_events: true, ..Default::default() });
+    _ = dom.rebuild();
+
+    click(&mut dom, ElementId(3));
+    assert_eq!(fired(), ["target", "parent", "grandparent"]);
+
+    // The chain is rebuilt for nodes mounted by a diff too
+    dom.mark_dirty(ScopeId(0));
+    _ = dom.render_immediate();
+    click(&mut dom, ElementId(3));
+    assert_eq!(fired(), ["target", "parent", "grandparent"]);
+}
+
+#[test]
+fn stop_propagation_halts_the_chain() {
+    let mut dom = VirtualDom::new(app);
+    _ = dom.rebuild();
+
+    STOP_AT_PARENT.with(|stop| stop.set(true));
+    click(&mut dom, ElementId(3));
+    assert_eq!(fired(), ["target", "parent"]);
+
+    // Clicking the parent itself doesn't reach the target below it
+    click(&mut dom, ElementId(2));
+    assert_eq!(fired(), ["parent"]);
+}
+
+fn capturing_app(cx: Scope) -> Element {
+    let push =
+        |name: &'static str| move |_: MouseEvent| FIRED.with(|fired| fired.borrow_mut().push(name));
+    cx.render(rsx! {
+        div {
+            oncaptureclick: push("capture outer"),
+            onclick: push("bubble outer"),
+            section {
+                oncaptureclick: |evt: MouseEvent| {
+                    FIRED.with(|fired| fired.borrow_mut().push("capture inner"));
+                    if STOP_AT_PARENT.with(Cell::get) {
+                        evt.stop_propagation();
+                    }
+                },
+                Target {}
+            }
+        }
+    })
+}
+
+#[test]
+fn capture_listeners_fire_from_the_root_down() {
+    let mut dom = VirtualDom::new(capturing_app);
+    let edits = dom.rebuild().santize();
+
+    // The renderer only has to listen for clicks
+    assert!(edits.edits.iter().all(|edit| !matches!(
+        edit,
+        dioxus_core::Mutation::NewEventListener { name, .. } if name.starts_with("capture")
+    )));
+    drop(edits);
+
+    click(&mut dom, ElementId(3));
+    assert_eq!(
+        fired(),
+        ["capture outer", "capture inner", "target", "bubble outer"]
+    );
+
+    // Capture listeners run even if the event doesn't bubble
+    dom.handle_event("click", Rc::new(MouseData::default()), ElementId(3), false);
+    assert_eq!(fired(), ["capture outer", "capture inner", "target"]);
+
+    // Stopping propagation while capturing skips the target and the bubbling listeners
+    STOP_AT_PARENT.with(|stop| stop.set(true));
+    click(&mut dom, ElementId(3));
+    assert_eq!(fired(), ["capture outer", "capture inner"]);
+}
@@ -103,7 +103,7 @@ fn generate_paths(
         TemplateNode::Element { children, attrs, .. } => {
             for attr in *attrs {
                 match attr {
-                    TemplateAttribute::Static { .. } => {}
+                    TemplateAttribute::Static { .. } | TemplateAttribute::Conditional { .. } => {}
                     TemplateAttribute::Dynamic { .. } => {
                         attr_paths.push(current_path.to_vec());
                     }
@@ -146,22 +146,21 @@ fn create_random_template(name: &'static str) -> (Template<'static>, Vec<Dynamic
     for (i, root) in roots.iter().enumerate() {
         generate_paths(root, &[i as u8], &mut node_paths, &mut attr_paths);
     }
-    let node_paths = Box::leak(
-        node_paths
-            .into_iter()
-            .map(|v| &*Box::leak(v.into_boxed_slice()))
-            .collect::<Vec<_>>()
-            .into_boxed_slice(),
-    );
-    let attr_paths = Box::leak(
-        attr_paths
-            .into_iter()
-            .map(|v| &*Box::leak(v.into_boxed_slice()))
-            .collect::<Vec<_>>()
-            .into_boxed_slice(),
-    );
+    let node_paths: &'static [Vec<u8>] = Box::leak(node_paths.into_boxed_slice());
+    let attr_paths: &'static [Vec<u8>] = Box::leak(attr_paths.into_boxed_slice());
+    // Pack the paths that fit, so the diff walks a mix of packed and slice paths
+    let mut unpacked_paths = Vec::new();
+    let node_paths = pack_paths(node_paths.iter().map(Vec::as_slice), &mut unpacked_paths);
+    let attr_paths = pack_paths(attr_paths.iter().map(Vec::as_slice), &mut unpacked_paths);
     (
-        Template { name, roots, node_paths, attr_paths },
+        Template {
+            name,
+            roots,
+            node_paths: Box::leak(node_paths.into_boxed_slice()),
+            attr_paths: Box::leak(attr_paths.into_boxed_slice()),
+            unpacked_paths: Box::leak(unpacked_paths.into_boxed_slice()),
+            style: None,
+        },
         dynamic_node_type,
     )
 }
@@ -176,8 +175,9 @@ fn create_random_dynamic_node(cx: &ScopeState, depth: usize) -> DynamicNode {
             template: Cell::new(Template {
                 name: concat!(file!(), ":", line!(), ":", column!(), ":0"),
                 roots: &[TemplateNode::Dynamic { id: 0 }],
-                node_paths: &[&[0]],
+                node_paths: &[PackedPath::new(&[0])],
                 attr_paths: &[],
+                ..Template::EMPTY
             }),
             root_ids: bumpalo::collections::Vec::new_in(cx.bump()).into(),
             dynamic_nodes: cx.bump().alloc([cx.component(
@@ -274,7 +274,7 @@ fn create_random_element(cx: Scope<DepthProps>) -> Element {
             // println!("{template:#?}");
             let node = VNode {
                 key: None,
-                parent: None,
+                parent: Default::default(),
                 template: Cell::new(template),
                 root_ids: bumpalo::collections::Vec::new_in(cx.bump()).into(),
                 dynamic_nodes: {
@@ -292,7 +292,7 @@ fn create_random_element(cx: Scope<DepthProps>) -> Element {
                     cx.bump().alloc(dynamic_nodes)
                 },
                 dynamic_attrs: cx.bump().alloc(
-                    (0..template.attr_paths.len())
+                    (0..template.attr_path_iter().count())
                         .map(|_| create_random_dynamic_attr(cx))
                         .collect::<Vec<_>>(),
                 ),
@@ -0,0 +1,105 @@
+use dioxus::core::Mutation::*;
+use dioxus::prelude::*;
+use dioxus_core::{
+    BorrowedAttributeValue, Capabilities, ElementId, InitialAttribute, TemplatePath,
+};
+
+fn app(cx: Scope) -> Element {
+    let (class, title, lang) = ("big", "hello", "en");
+    cx.render(rsx! {
+        div { class: "{class}", title: "{title}", lang: "{lang}" }
+    })
+}
+
+#[test]
+fn attributes_are_sent_separately_by_default() {
+    let mut dom = VirtualDom::new(app);
+
+    let edits = dom.rebuild().santize().edits;
+    assert_eq!(edits.len(), 5);
+    assert_eq!(
+        edits[0],
+        LoadTemplate { name: "template", index: 0, id: ElementId(1) }
+    );
+    assert!(edits[1..4]
+        .iter()
+        .all(|edit| matches!(edit, SetAttribute { .. })));
+}
+
+#[test]
+fn attributes_are_grouped_with_the_element() {
+    let mut dom = VirtualDom::new(app)
+        .with_capabilities(Capabilities { grouped_attributes: true, ..Default::default() });
+
+    assert_eq!(
+        dom.rebuild().santize().edits,
+        [
+            CreateElementWithAttrs {
+                name: "template",
+                index: 0,
+                id: ElementId(1),
+                tag: "div",
+                attrs: vec![
+                    InitialAttribute {
+                        name: "class",
+                        value: BorrowedAttributeValue::Text("big"),
+                        ns: None
+                    },
+                    InitialAttribute {
+                        name: "title",
+                        value: BorrowedAttributeValue::Text("hello"),
+                        ns: None
+                    },
+                    InitialAttribute {
+                        name: "lang",
+                        value: BorrowedAttributeValue::Text("en"),
+                        ns: None
+                    },
+                ],
+                listeners: vec![],
+            },
+            AppendChildren { id: ElementId(0), m: 1 },
+        ]
+    );
+}
+
+#[test]
+fn nested_attributes_and_listeners() {
+    fn app(cx: Scope) -> Element {
+        let id = "outer";
+        cx.render(rsx! {
+            div { id: "{id}", onclick: |_| {},
+                span { id: "{id}-inner" }
+            }
+        })
+    }
+
+    let mut dom = VirtualDom::new(app)
+        .with_capabilities(Capabilities { grouped_attributes: true, ..Default::default() });
+
+    assert_eq!(
+        dom.rebuild().santize().edits,
+        [
+            CreateElementWithAttrs {
+                name: "template",
+                index: 0,
+                id: ElementId(1),
+                tag: "div",
+                attrs: vec![InitialAttribute {
+                    name: "id",
+                    value: BorrowedAttributeValue::Text("outer"),
+                    ns: None
+                }],
+                listeners: vec!["click"],
+            },
+            AssignId { path: TemplatePath::Slice(&[0]), id: ElementId(2) },
+            SetAttribute {
+                name: "id",
+                value: BorrowedAttributeValue::Text("outer-inner"),
+                id: ElementId(2),
+                ns: None
+            },
+            AppendChildren { id: ElementId(0), m: 1 },
+        ]
+    );
+}
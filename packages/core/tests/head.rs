@@ -0,0 +1,79 @@
+//! Components hoist title, meta and link elements into the head, deduplicated by their key
+
+use dioxus::core::{Link, Meta, Title};
+use dioxus::prelude::*;
+use dioxus_core::HeadElement;
+use std::cell::Cell;
+
+thread_local! {
+    static SHOW_ARTICLE: Cell<bool> = const { Cell::new(true) };
+}
+
+fn app(cx: Scope) -> Element {
+    let show = SHOW_ARTICLE.with(Cell::get);
+    render! {
+        Title { "Blog" }
+        Meta { charset: "utf-8" }
+        Link { rel: "stylesheet", href: "/blog.css" }
+        if show {
+            rsx! { Article { title: "Hello" } }
+        }
+    }
+}
+
+#[derive(Props, PartialEq)]
+struct ArticleProps {
+    title: &'static str,
+}
+
+#[allow(non_snake_case)]
+fn Article(cx: Scope<ArticleProps>) -> Element {
+    let title = cx.props.title;
+    cx.use_head(
+        HeadElement::new("meta")
+            .with_attribute("name", "author")
+            .with_attribute("content", "jane"),
+    );
+    render! {
+        Title { "{title} - Blog" }
+        article { "{title}" }
+    }
+}
+
+fn title(elements: &[HeadElement]) -> Option<&str> {
+    let titles: Vec<_> = elements
+        .iter()
+        .filter(|element| element.tag == "title")
+        .collect();
+    assert!(titles.len() <= 1);
+    titles.first().and_then(|title| title.text.as_deref())
+}
+
+#[test]
+fn deeper_elements_replace_their_parents() {
+    let mut dom = VirtualDom::new(app);
+    _ = dom.rebuild();
+
+    let elements = dom.take_head_changes().unwrap();
+    assert_eq!(title(&elements), Some("Hello - Blog"));
+    assert_eq!(elements.len(), 4);
+    assert!(elements.contains(&HeadElement::new("meta").with_attribute("charset", "utf-8")));
+    assert!(elements.contains(
+        &HeadElement::new("link")
+            .with_attribute("rel", "stylesheet")
+            .with_attribute("href", "/blog.css")
+    ));
+
+    // Nothing changed since the head was last taken
+    dom.mark_dirty(ScopeId::ROOT);
+    _ = dom.render_immediate();
+    assert_eq!(dom.take_head_changes(), None);
+
+    // Unmounting the article brings the title of its parent back
+    SHOW_ARTICLE.with(|show| show.set(false));
+    dom.mark_dirty(ScopeId::ROOT);
+    _ = dom.render_immediate();
+    let elements = dom.take_head_changes().unwrap();
+    assert_eq!(title(&elements), Some("Blog"));
+    assert_eq!(elements.len(), 3);
+}
@@ -0,0 +1,90 @@
+#![allow(non_snake_case)]
+//! A hot reload plan tells each scope whether it can patch its templates in place or has to remount them
+
+use dioxus::core::{PackedPath, ReloadAction, RenderReturn, Template, TemplateNode};
+use dioxus::prelude::*;
+
+fn app(cx: Scope) -> Element {
+    cx.render(rsx! {
+        Greeting {}
+        Counter {}
+    })
+}
+
+fn Greeting(cx: Scope) -> Element {
+    cx.render(rsx! { h1 { "hello" } })
+}
+
+fn Counter(cx: Scope) -> Element {
+    let count = 0;
+    cx.render(rsx! { p { "{count}" } })
+}
+
+fn mounted_template(dom: &VirtualDom, scope: ScopeId) -> Template<'static> {
+    match dom.get_scope(scope).unwrap().try_root_node() {
+        Some(RenderReturn::Ready(node)) => node.template.get(),
+        _ => panic!("scope {:?} did not render", scope),
+    }
+}
+
+fn leak<T>(value: T) -> &'static T {
+    Box::leak(Box::new(value))
+}
+
+#[test]
+fn static_and_dynamic_changes_get_different_actions() {
+    let mut dom = VirtualDom::new(app);
+    _ = dom.rebuild();
+
+    // Only the static text of the greeting changed
+    let greeting = mounted_template(&dom, ScopeId(1));
+    let greeting_roots: &'static [TemplateNode] = leak([TemplateNode::Element {
+        tag: "h1",
+        namespace: None,
+        attrs: &[],
+        children: leak([TemplateNode::Text { text: "hi there" }]),
+    }]);
+    let patched = Template { roots: greeting_roots, ..greeting };
+    assert!(greeting.is_hot_reload_compatible(&patched));
+
+    // The count of the counter moved into a new element
+    let counter = mounted_template(&dom, ScopeId(2));
+    let counter_roots: &'static [TemplateNode] = leak([TemplateNode::Element {
+        tag: "p",
+        namespace: None,
+        attrs: &[],
+        children: leak([TemplateNode::Element {
+            tag: "b",
+            namespace: None,
+            attrs: &[],
+            children: leak([TemplateNode::DynamicText { id: 0 }]),
+        }]),
+    }]);
+    let reshaped = Template {
+        roots: counter_roots,
+        node_paths: leak([PackedPath::new(&[0, 0, 0])]),
+        ..counter
+    };
+    assert!(!counter.is_hot_reload_compatible(&reshaped));
+
+    assert_eq!(
+        dom.hot_reload_plan(&[patched, reshaped]),
+        [
+            (ScopeId(1), ReloadAction::PatchInPlace),
+            (ScopeId(2), ReloadAction::Remount),
+        ]
+    );
+}
+
+#[test]
+fn unchanged_scopes_are_left_out() {
+    let mut dom = VirtualDom::new(app);
+    _ = dom.rebuild();
+
+    let counter = mounted_template(&dom, ScopeId(2));
+    assert_eq!(
+        dom.hot_reload_plan(&[counter]),
+        [(ScopeId(2), ReloadAction::PatchInPlace)]
+    );
+    assert!(dom.hot_reload_plan(&[]).is_empty());
+}
@@ -1 +1,45 @@
 //! It should be possible to swap out templates at runtime, enabling hotreloading
+
+use dioxus::prelude::*;
+use dioxus_core::{MockDom, TemplateNode};
+
+fn app(cx: Scope) -> Element {
+    render! {
+        div {
+            (0..2).map(|i| rsx! { span { key: "{i}", "old" } })
+        }
+    }
+}
+
+#[test]
+fn templates_nested_in_fragments_are_replaced() {
+    let mut dom = VirtualDom::new(app);
+    let renderer = MockDom::new();
+    let mutations = dom.rebuild();
+    renderer.apply(&mutations);
+    let row = mutations
+        .templates
+        .iter()
+        .find(|template| matches!(template.roots, [TemplateNode::Element { tag: "span", .. }]))
+        .unwrap();
+    let name: &'static str = Box::leak(row.name.to_string().into_boxed_str());
+    drop(mutations);
+    assert_eq!(
+        renderer.to_string(),
+        "<div><span>old</span><span>old</span></div>"
+    );
+
+    let roots: &'static [TemplateNode<'static>] = Box::leak(Box::new([TemplateNode::Element {
+        tag: "span",
+        namespace: None,
+        attrs: &[],
+        children: &[TemplateNode::Text { text: "new" }],
+    }]));
+    dom.replace_template(Template { name, roots, ..Template::EMPTY });
+
+    renderer.apply(&dom.render_immediate());
+    assert_eq!(
+        renderer.to_string(),
+        "<div><span>new</span><span>new</span></div>"
+    );
+}
@@ -0,0 +1,66 @@
+//! `if` chains in rsx render each branch as its own template
+
+use dioxus::core::{ElementId, Mutation::*};
+use dioxus::prelude::*;
+
+fn app(cx: Scope) -> Element {
+    let step = cx.generation() % 3;
+
+    cx.render(rsx! {
+        if step == 0 {
+            h1 { "first" }
+        } else if step == 1 {
+            h2 { "second {step}" }
+        } else {
+            "third"
+            span {}
+        }
+    })
+}
+
+#[test]
+fn switching_branches_replaces_the_subtree() {
+    let mut dom = VirtualDom::new(app);
+
+    assert_eq!(
+        dom.rebuild().santize().edits,
+        [
+            LoadTemplate { name: "template", index: 0, id: ElementId(1) },
+            AppendChildren { id: ElementId(0), m: 1 },
+        ]
+    );
+    assert_eq!(dioxus_ssr::render(&dom), "<h1>first</h1>");
+
+    dom.mark_dirty(ScopeId::ROOT);
+    assert!(dom
+        .render_immediate()
+        .santize()
+        .edits
+        .contains(&ReplaceWith { id: ElementId(1), m: 1 }));
+    assert_eq!(dioxus_ssr::render(&dom), "<h2>second 1</h2>");
+
+    dom.mark_dirty(ScopeId::ROOT);
+    _ = dom.render_immediate();
+    assert_eq!(dioxus_ssr::render(&dom), "third<span></span>");
+}
+
+#[test]
+fn unterminated_chains_render_nothing() {
+    let mut dom = VirtualDom::new(|cx| {
+        let show = cx.generation() % 2 == 1;
+        cx.render(rsx! {
+            div {
+                if show {
+                    "shown"
+                }
+            }
+        })
+    });
+
+    _ = dom.rebuild();
+    assert_eq!(dioxus_ssr::render(&dom), "<div></div>");
+
+    dom.mark_dirty(ScopeId::ROOT);
+    _ = dom.render_immediate();
+    assert_eq!(dioxus_ssr::render(&dom), "<div>shown</div>");
+}
@@ -0,0 +1,51 @@
+use dioxus::core::Mutation::*;
+use dioxus::prelude::*;
+use dioxus_core::{RenderReturn, TemplatePath};
+
+#[test]
+fn child_inherits_lang_from_grandparent() {
+    fn app(cx: Scope) -> Element {
+        let class = "label";
+        cx.render(rsx! {
+            div { lang: "fr",
+                div {
+                    span { class: "{class}", "bonjour" }
+                }
+                p { lang: "en", class: "{class}" }
+            }
+        })
+    }
+
+    let mut dom = VirtualDom::new(app);
+    let edits = dom.rebuild().santize();
+
+    let assigned = edits
+        .edits
+        .iter()
+        .filter_map(|edit| match edit {
+            AssignId { path, id } => Some((*path, *id)),
+            _ => None,
+        })
+        .collect::<Vec<_>>();
+    drop(edits);
+
+    let span = assigned
+        .iter()
+        .find(|(path, _)| *path == TemplatePath::Slice(&[0, 0]))
+        .unwrap()
+        .1;
+    let p = assigned
+        .iter()
+        .find(|(path, _)| *path == TemplatePath::Slice(&[1]))
+        .unwrap()
+        .1;
+
+    let node = match dom.base_scope().root_node() {
+        RenderReturn::Ready(node) => node,
+        RenderReturn::Aborted(_) => panic!("root should render"),
+    };
+    let inherited = dom.resolve_inherited(node, &["lang", "dir"]);
+
+    assert_eq!(inherited[&span], [("lang", "fr".to_string())]);
+    assert_eq!(inherited[&p], [("lang", "en".to_string())]);
+}
@@ -0,0 +1,70 @@
+//! Devtools can walk the mounted components of a VirtualDom
+
+use dioxus::prelude::*;
+use dioxus_core::ElementId;
+
+fn app(cx: Scope) -> Element {
+    cx.use_hook(|| ());
+    render! {
+        div {
+            Row { label: "first".to_string() }
+            Row { label: "second".to_string() }
+            Unlabeled { count: 1 }
+        }
+    }
+}
+
+#[derive(Props, PartialEq, Debug)]
+struct RowProps {
+    label: String,
+}
+
+#[allow(non_snake_case)]
+fn Row(cx: Scope<RowProps>) -> Element {
+    let mut count = use_state(cx, || 0);
+    render! { span { onclick: move |_| count += 1, "{cx.props.label}" } }
+}
+
+#[derive(Props, PartialEq)]
+struct UnlabeledProps {
+    count: usize,
+}
+
+#[allow(non_snake_case)]
+fn Unlabeled(cx: Scope<UnlabeledProps>) -> Element {
+    render! { "{cx.props.count}" }
+}
+
+#[test]
+fn inspect_walks_the_component_tree() {
+    let mut dom = VirtualDom::new(app);
+    _ = dom.rebuild();
+
+    let components = dom.inspect();
+    let names: Vec<_> = components.iter().map(|c| c.name).collect();
+    assert_eq!(names, ["app", "Row", "Row", "Unlabeled"]);
+
+    let root = &components[0];
+    assert_eq!(root.parent, None);
+    assert_eq!(root.hooks, 1);
+    assert_eq!(root.generation, 1);
+    assert_eq!(root.elements, [ElementId(1)]);
+    assert_eq!(
+        root.children,
+        [components[1].id, components[2].id, components[3].id]
+    );
+
+    let row = &components[1];
+    assert_eq!(row.parent, Some(root.id));
+    assert_eq!(row.props.as_deref(), Some(r#"RowProps { label: "first" }"#));
+    assert_eq!(row.hooks, 1);
+    assert_eq!(row.elements.len(), 2);
+    assert!(row.children.is_empty());
+
+    // Props without Debug aren't described
+    assert_eq!(components[3].props, None);
+    assert_eq!(
+        dom.inspect_scope(components[3].id),
+        Some(components[3].clone())
+    );
+}
@@ -0,0 +1,125 @@
+#![allow(non_snake_case)]
+//! Components can track the visibility of their elements through the renderer's intersection observer
+
+use dioxus::core::Mutation::NewEventListener;
+use dioxus::html::{
+    IntersectionData, IntersectionObserver, IntersectionObserverBacking, MountedData,
+    MountedResult, RenderedElementBacking,
+};
+use dioxus::prelude::*;
+use std::{cell::Cell, cell::RefCell, future::Future, pin::Pin, rc::Rc, time::Duration};
+use tokio::sync::mpsc::{unbounded_channel, UnboundedReceiver, UnboundedSender};
+
+thread_local! {
+    static CHANGES: RefCell<Option<UnboundedReceiver<IntersectionData>>> = const { RefCell::new(None) };
+    static OBSERVING: Cell<bool> = const { Cell::new(false) };
+    static SHOW_CHILD: Cell<bool> = const { Cell::new(true) };
+}
+
+/// An element whose visibility changes are sent by the test
+struct TestElement;
+
+impl RenderedElementBacking for TestElement {
+    fn observe_intersection(
+        &self,
+    ) -> Pin<Box<dyn Future<Output = MountedResult<IntersectionObserver>>>> {
+        let changes = CHANGES.with(|changes| changes.borrow_mut().take()).unwrap();
+        OBSERVING.with(|observing| observing.set(true));
+        Box::pin(async move { Ok(IntersectionObserver::new(TestObserver(changes))) })
+    }
+}
+
+struct TestObserver(UnboundedReceiver<IntersectionData>);
+
+impl IntersectionObserverBacking for TestObserver {
+    fn next(&mut self) -> Pin<Box<dyn Future<Output = Option<IntersectionData>> + '_>> {
+        Box::pin(self.0.recv())
+    }
+}
+
+impl Drop for TestObserver {
+    fn drop(&mut self) {
+        OBSERVING.with(|observing| observing.set(false));
+    }
+}
+
+fn app(cx: Scope) -> Element {
+    let show = SHOW_CHILD.with(|show| show.get());
+
+    cx.render(rsx! {
+        if show {
+            rsx! { Child {} }
+        }
+    })
+}
+
+fn Child(cx: Scope) -> Element {
+    let visibility = use_intersection_observer(cx);
+
+    cx.render(rsx! {
+        div {
+            onmounted: move |evt| visibility.observe(evt),
+            "{visibility.is_intersecting()} {visibility.ratio()}"
+        }
+    })
+}
+
+fn setup() -> (VirtualDom, UnboundedSender<IntersectionData>) {
+    let (sender, receiver) = unbounded_channel();
+    CHANGES.with(|changes| *changes.borrow_mut() = Some(receiver));
+    SHOW_CHILD.with(|show| show.set(true));
+
+    let mut dom = VirtualDom::new(app);
+    let id = dom
+        .rebuild()
+        .santize()
+        .edits
+        .iter()
+        .find_map(|edit| match edit {
+            NewEventListener { name: "mounted", id } => Some(*id),
+            _ => None,
+        })
+        .unwrap();
+
+    dom.handle_event("mounted", Rc::new(MountedData::new(TestElement)), id, false);
+
+    (dom, sender)
+}
+
+async fn work(dom: &mut VirtualDom) {
+    tokio::select! {
+        _ = dom.wait_for_work() => {}
+        _ = tokio::time::sleep(Duration::from_millis(100)) => {}
+    };
+    _ = dom.render_immediate();
+}
+
+#[tokio::test]
+async fn visibility_changes_rerender() {
+    let (mut dom, sender) = setup();
+
+    // The observer starts after the component renders with the new element
+    work(&mut dom).await;
+    work(&mut dom).await;
+    assert_eq!(dioxus_ssr::render(&dom), "<div>false 0</div>");
+
+    sender
+        .send(IntersectionData { is_intersecting: true, ratio: 0.5 })
+        .unwrap();
+    work(&mut dom).await;
+    assert_eq!(dioxus_ssr::render(&dom), "<div>true 0.5</div>");
+}
+
+#[tokio::test]
+async fn observer_stops_on_unmount() {
+    let (mut dom, _sender) = setup();
+
+    work(&mut dom).await;
+    work(&mut dom).await;
+    assert!(OBSERVING.with(|observing| observing.get()));
+
+    SHOW_CHILD.with(|show| show.set(false));
+    dom.mark_dirty(ScopeId::ROOT);
+    _ = dom.render_immediate();
+    assert!(!OBSERVING.with(|observing| observing.get()));
+}
@@ -0,0 +1,111 @@
+use dioxus::core::Mutation::*;
+use dioxus::prelude::*;
+use dioxus_core::{inverse_mutation, BorrowedAttributeValue, ElementId, MockDom, Mutation};
+
+fn app(cx: Scope) -> Element {
+    let (class, text) = ("big", "hello");
+    cx.render(rsx! { div { class: "{class}", "{text}" } })
+}
+
+fn assert_undoes(dom: &MockDom, edit: Mutation) {
+    let before = dom.to_string();
+    let inverse = inverse_mutation(&edit, dom).unwrap();
+
+    dom.apply_edit(&edit);
+    assert_ne!(dom.to_string(), before);
+
+    dom.apply_edit(&inverse);
+    assert_eq!(dom.to_string(), before);
+}
+
+#[test]
+fn mock_dom_renders_the_tree() {
+    let mut vdom = VirtualDom::new(app);
+    let dom = MockDom::new();
+    dom.apply(&vdom.rebuild());
+
+    assert_eq!(dom.to_string(), "<div class=\"big\">hello</div>");
+    assert_eq!(dom.attribute(ElementId(1), "class"), Some("big"));
+    assert_eq!(dom.text(ElementId(2)), Some("hello"));
+}
+
+#[test]
+fn set_text_is_undone() {
+    let mut vdom = VirtualDom::new(app);
+    let dom = MockDom::new();
+    dom.apply(&vdom.rebuild());
+
+    assert_undoes(&dom, SetText { value: "goodbye", id: ElementId(2) });
+}
+
+#[test]
+fn set_attribute_is_undone() {
+    let mut vdom = VirtualDom::new(app);
+    let dom = MockDom::new();
+    dom.apply(&vdom.rebuild());
+
+    // Changing an existing attribute restores the old value
+    assert_undoes(
+        &dom,
+        SetAttribute {
+            name: "class",
+            value: BorrowedAttributeValue::Text("small"),
+            id: ElementId(1),
+            ns: None,
+        },
+    );
+
+    // Setting a new attribute removes it again
+    assert_undoes(
+        &dom,
+        SetAttribute {
+            name: "title",
+            value: BorrowedAttributeValue::Int(1),
+            id: ElementId(1),
+            ns: None,
+        },
+    );
+}
+
+#[test]
+fn listeners_are_undone() {
+    let mut vdom = VirtualDom::new(app);
+    let dom = MockDom::new();
+    dom.apply(&vdom.rebuild());
+
+    let edit = NewEventListener { name: "click", id: ElementId(1) };
+    let inverse = inverse_mutation(&edit, &dom).unwrap();
+
+    dom.apply_edit(&edit);
+    assert_eq!(dom.listeners(ElementId(1)), ["click"]);
+
+    dom.apply_edit(&inverse);
+    assert!(dom.listeners(ElementId(1)).is_empty());
+}
+
+#[test]
+fn create_is_undone_by_remove() {
+    let mut vdom = VirtualDom::new(app);
+    let dom = MockDom::new();
+    dom.apply(&vdom.rebuild());
+
+    dom.apply_edit(&CreateTextNode { value: " world", id: ElementId(3) });
+
+    let edit = CreateTextNode { value: "new", id: ElementId(4) };
+    let inverse = inverse_mutation(&edit, &dom).unwrap();
+    assert_eq!(inverse, Remove { id: ElementId(4) });
+
+    // The new node is taken off the stack again, so only the first text node is appended
+    dom.apply_edit(&edit);
+    dom.apply_edit(&inverse);
+    dom.apply_edit(&AppendChildren { id: ElementId(1), m: 1 });
+    assert_eq!(dom.to_string(), "<div class=\"big\">hello world</div>");
+}
+
+#[test]
+fn structural_mutations_have_no_inverse() {
+    let dom = MockDom::new();
+
+    assert!(inverse_mutation(&AppendChildren { id: ElementId(0), m: 1 }, &dom).is_none());
+    assert!(inverse_mutation(&Remove { id: ElementId(1) }, &dom).is_none());
+}
@@ -0,0 +1,59 @@
+//! Keyed nodes that move or are created in the middle of a list are inserted before the next node in the list
+
+use dioxus::core::{ElementId, Mutation::*};
+use dioxus::prelude::*;
+use dioxus_core::MockDom;
+
+fn list(order: &'static [&'static [u32]]) -> VirtualDom {
+    VirtualDom::new_with_props(
+        |cx| {
+            let order = cx.props[cx.generation() % 2];
+            cx.render(rsx!(order.iter().map(|i| rsx!(div { key: "{i}", "{i}" }))))
+        },
+        order,
+    )
+}
+
+#[test]
+fn moves_are_anchored_to_the_next_sibling() {
+    let mut dom = list(&[&[1, 2, 3, 4, 5], &[2, 3, 1, 4, 5]]);
+    let renderer = MockDom::new();
+    renderer.apply(&dom.rebuild());
+
+    dom.mark_dirty(ScopeId::ROOT);
+    let edits = dom.render_immediate();
+    renderer.apply(&edits);
+
+    // 1 moves in front of 4, which is the first node after it in the new list
+    assert_eq!(
+        edits.santize().edits,
+        [
+            PushRoot { id: ElementId(1) },
+            InsertBefore { id: ElementId(7), m: 1 },
+        ]
+    );
+    assert_eq!(
+        renderer.to_string(),
+        "<div>2</div><div>3</div><div>1</div><div>4</div><div>5</div>"
+    );
+}
+
+#[test]
+fn insertions_are_anchored_to_the_next_sibling() {
+    let mut dom = list(&[&[1, 2, 4, 5], &[1, 2, 3, 4, 5]]);
+    let renderer = MockDom::new();
+    renderer.apply(&dom.rebuild());
+
+    dom.mark_dirty(ScopeId::ROOT);
+    let edits = dom.render_immediate();
+    renderer.apply(&edits);
+
+    assert!(matches!(
+        edits.edits.last(),
+        Some(InsertBefore { id: ElementId(5), m: 1 })
+    ));
+    assert_eq!(
+        renderer.to_string(),
+        "<div>1</div><div>2</div><div>3</div><div>4</div><div>5</div>"
+    );
+}
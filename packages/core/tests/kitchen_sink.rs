@@ -1,5 +1,5 @@
 use bumpalo::Bump;
-use dioxus::core::{ElementId, Mutation};
+use dioxus::core::{ElementId, Mutation, TemplatePath};
 use dioxus::prelude::*;
 
 fn basic_syntax_is_a_template(cx: Scope) -> Element {
@@ -41,7 +41,7 @@ fn dual_stream() {
                 ns: None,
             },
             NewEventListener { name: "click", id: ElementId(1) },
-            HydrateText { path: &[0, 0], value: "123", id: ElementId(2) },
+            HydrateText { path: TemplatePath::Slice(&[0, 0]), value: "123", id: ElementId(2) },
             AppendChildren { id: ElementId(0), m: 1 },
         ]
     });
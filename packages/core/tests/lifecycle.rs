@@ -2,7 +2,7 @@
 #![allow(non_snake_case)]
 
 //! Tests for the lifecycle of components.
-use dioxus::core::{ElementId, Mutation::*};
+use dioxus::core::{ElementId, Mutation::*, TemplatePath};
 use dioxus::prelude::*;
 use std::rc::Rc;
 use std::sync::{Arc, Mutex};
@@ -31,7 +31,7 @@ fn manual_diffing() {
         dom.rebuild().santize().edits,
         [
             LoadTemplate { name: "template", index: 0, id: ElementId(3) },
-            HydrateText { path: &[0], value: "goodbye", id: ElementId(4) },
+            HydrateText { path: TemplatePath::Slice(&[0]), value: "goodbye", id: ElementId(4) },
             AppendChildren { m: 1, id: ElementId(0) }
         ]
     );
@@ -0,0 +1,33 @@
+use dioxus::html::MouseData;
+use dioxus::prelude::*;
+use dioxus_core::ElementId;
+use std::{cell::RefCell, rc::Rc};
+
+thread_local! {
+    static CLICKS: RefCell<Vec<usize>> = const { RefCell::new(Vec::new()) };
+}
+
+fn app(cx: Scope) -> Element {
+    let generation = cx.generation();
+    cx.render(rsx! {
+        button { onclick: move |_| CLICKS.with(|clicks| clicks.borrow_mut().push(generation)) }
+    })
+}
+
+#[test]
+fn listeners_are_swapped_in_place() {
+    let mut dom = VirtualDom::new(app);
+    _ = dom.rebuild();
+
+    for _ in 0..3 {
+        dom.mark_dirty(ScopeId::ROOT);
+
+        // No detach or re-attach, even though the closure captured new state
+        assert!(dom.render_immediate().edits.is_empty());
+
+        dom.handle_event("click", Rc::new(MouseData::default()), ElementId(1), true);
+    }
+
+    // Every click went to the closure from the latest render
+    CLICKS.with(|clicks| assert_eq!(*clicks.borrow(), [1, 2, 3]));
+}
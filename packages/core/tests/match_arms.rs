@@ -0,0 +1,77 @@
+//! `match` expressions in rsx render each arm as its own template
+
+use dioxus::core::{ElementId, Mutation::*};
+use dioxus::prelude::*;
+
+enum Status {
+    Loading,
+    Failed(&'static str),
+    Ready(usize),
+}
+
+fn app(cx: Scope) -> Element {
+    let status = match cx.generation() {
+        0 => Status::Loading,
+        1 => Status::Failed("timeout"),
+        2 => Status::Ready(0),
+        _ => Status::Ready(3),
+    };
+
+    cx.render(rsx! {
+        div {
+            match status {
+                Status::Loading => "Loading...",
+                Status::Failed(reason) => {
+                    h1 { "Failed" }
+                    p { "{reason}" }
+                }
+                Status::Ready(0) => {}
+                Status::Ready(count) if count > 1 => span { "{count} items" },
+                Status::Ready(_) => span { "one item" },
+            }
+        }
+    })
+}
+
+#[test]
+fn each_arm_renders_its_own_nodes() {
+    let mut dom = VirtualDom::new(app);
+    _ = dom.rebuild();
+    assert_eq!(dioxus_ssr::render(&dom), "<div>Loading...</div>");
+
+    dom.mark_dirty(ScopeId::ROOT);
+    _ = dom.render_immediate();
+    assert_eq!(
+        dioxus_ssr::render(&dom),
+        "<div><h1>Failed</h1><p>timeout</p></div>"
+    );
+
+    dom.mark_dirty(ScopeId::ROOT);
+    _ = dom.render_immediate();
+    assert_eq!(dioxus_ssr::render(&dom), "<div></div>");
+
+    dom.mark_dirty(ScopeId::ROOT);
+    _ = dom.render_immediate();
+    assert_eq!(dioxus_ssr::render(&dom), "<div><span>3 items</span></div>");
+}
+
+#[test]
+fn the_same_arm_is_diffed_in_place() {
+    let mut dom = VirtualDom::new(|cx| {
+        let count = cx.generation() + 2;
+        cx.render(rsx! {
+            match count {
+                0 => "none",
+                count => span { "{count} items" },
+            }
+        })
+    });
+
+    _ = dom.rebuild();
+
+    dom.mark_dirty(ScopeId::ROOT);
+    assert_eq!(
+        dom.render_immediate().santize().edits,
+        [SetText { value: "3 items", id: ElementId(2) }]
+    );
+}
@@ -16,6 +16,7 @@ fn miri_rollover() {
 }
 
 #[component]
+#[allow(deprecated)]
 fn App(cx: Scope) -> Element {
     let mut idx = use_state(cx, || 0);
     let onhover = |_| println!("go!");
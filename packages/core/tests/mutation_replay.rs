@@ -0,0 +1,68 @@
+#![allow(non_snake_case)]
+//! A recorded session of mutations replays into the same tree as the live one
+
+use dioxus::core::{replay, MockDom, MutationRecorder};
+use dioxus::prelude::*;
+
+fn app(cx: Scope) -> Element {
+    let generation = cx.generation();
+    cx.render(rsx! {
+        h1 { class: "title-{generation}", "render {generation}" }
+        ul {
+            (0..generation % 3).map(|i| rsx!(li { key: "{i}", "item {i}" }))
+        }
+        if generation != 3 {
+            rsx! { Footer {} }
+        }
+    })
+}
+
+fn Footer(cx: Scope) -> Element {
+    cx.render(rsx! { footer { "the end" } })
+}
+
+#[test]
+fn replay_matches_live_dom() {
+    let mut dom = VirtualDom::new(app);
+    let live = MockDom::new();
+    let mut recorder = MutationRecorder::new();
+
+    {
+        let edits = dom.rebuild();
+        live.apply(&edits);
+        recorder.record(&edits);
+    }
+
+    for _ in 0..4 {
+        dom.mark_dirty(ScopeId(0));
+        let edits = dom.render_immediate();
+        live.apply(&edits);
+        recorder.record(&edits);
+    }
+
+    assert_eq!(recorder.log().batches.len(), 5);
+
+    let replayed = replay(recorder.log());
+    assert_eq!(replayed.to_string(), live.to_string());
+    assert_eq!(
+        replayed.to_string(),
+        "<h1 class=\"title-4\">render 4</h1><ul><li>item 0</li></ul><footer>the end</footer>"
+    );
+}
+
+#[cfg(feature = "serialize")]
+#[test]
+fn log_round_trips_through_json() {
+    let mut dom = VirtualDom::new(app);
+    let mut recorder = MutationRecorder::new();
+    recorder.record(&dom.rebuild());
+    dom.mark_dirty(ScopeId(0));
+    recorder.record(&dom.render_immediate());
+
+    let json = serde_json::to_string(recorder.log()).unwrap();
+    let log: dioxus::core::MutationLog =
+        serde_json::from_str(Box::leak(json.into_boxed_str())).unwrap();
+
+    assert_eq!(&log, recorder.log());
+    assert_eq!(replay(&log).to_string(), replay(recorder.log()).to_string());
+}
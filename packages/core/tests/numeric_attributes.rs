@@ -0,0 +1,37 @@
+use bumpalo::Bump;
+use dioxus::core::AttributeValue;
+use dioxus_core::prelude::IntoAttributeValue;
+
+#[test]
+fn narrow_numbers_widen_losslessly() {
+    let bump = Bump::new();
+
+    assert_eq!(7u8.into_value(&bump), AttributeValue::Int(7));
+    assert_eq!((-7i32).into_value(&bump), AttributeValue::Int(-7));
+    assert_eq!(
+        u32::MAX.into_value(&bump),
+        AttributeValue::Int(u32::MAX as i64)
+    );
+    assert_eq!(
+        0.1f32.into_value(&bump),
+        AttributeValue::Float(f64::from(0.1f32))
+    );
+}
+
+#[test]
+fn large_unsigned_numbers_fall_back_to_text() {
+    let bump = Bump::new();
+
+    assert_eq!(42u64.into_value(&bump), AttributeValue::Int(42));
+    assert_eq!(42usize.into_value(&bump), AttributeValue::Int(42));
+    assert_eq!(
+        (i64::MAX as u64).into_value(&bump),
+        AttributeValue::Int(i64::MAX)
+    );
+
+    // Anything past i64::MAX keeps every digit as text instead of rounding
+    assert_eq!(
+        u64::MAX.into_value(&bump),
+        AttributeValue::Text("18446744073709551615")
+    );
+}
@@ -0,0 +1,84 @@
+#![allow(non_snake_case)]
+//! Attributes set to `None` are left off the element and removed when they become `None`
+
+use dioxus::core::{BorrowedAttributeValue, ElementId, Mutation::*};
+use dioxus::prelude::*;
+
+fn app(cx: Scope) -> Element {
+    let class = match cx.generation() % 2 {
+        0 => None,
+        _ => Some(String::from("active")),
+    };
+
+    cx.render(rsx! { div { class: class, id: "item" } })
+}
+
+#[test]
+fn none_attributes_are_skipped_and_removed() {
+    let mut dom = VirtualDom::new(app);
+
+    assert_eq!(
+        dom.rebuild().santize().edits,
+        [
+            LoadTemplate { name: "template", index: 0, id: ElementId(1) },
+            AppendChildren { id: ElementId(0), m: 1 },
+        ]
+    );
+    assert_eq!(dioxus_ssr::render(&dom), "<div id=\"item\"></div>");
+
+    dom.mark_dirty(ScopeId::ROOT);
+    assert_eq!(
+        dom.render_immediate().santize().edits,
+        [SetAttribute {
+            name: "class",
+            value: BorrowedAttributeValue::Text("active"),
+            id: ElementId(1),
+            ns: None,
+        }]
+    );
+    assert_eq!(
+        dioxus_ssr::render(&dom),
+        "<div class=\"active\" id=\"item\"></div>"
+    );
+
+    dom.mark_dirty(ScopeId::ROOT);
+    assert_eq!(
+        dom.render_immediate().santize().edits,
+        [SetAttribute {
+            name: "class",
+            value: BorrowedAttributeValue::None,
+            id: ElementId(1),
+            ns: None,
+        }]
+    );
+    assert_eq!(dioxus_ssr::render(&dom), "<div id=\"item\"></div>");
+}
+
+#[test]
+fn optional_props_pass_through() {
+    #[derive(Props, PartialEq)]
+    struct LabelProps {
+        title: Option<String>,
+    }
+
+    fn Label(cx: Scope<LabelProps>) -> Element {
+        let hidden = match cx.props.title {
+            Some(_) => None,
+            None => Some(true),
+        };
+        cx.render(rsx! { span { title: &cx.props.title, hidden: hidden } })
+    }
+
+    let mut dom = VirtualDom::new(|cx| {
+        cx.render(rsx! {
+            Label { title: "Name".to_string() }
+            Label {}
+        })
+    });
+    _ = dom.rebuild();
+
+    assert_eq!(
+        dioxus_ssr::render(&dom),
+        "<span title=\"Name\"></span><span hidden=true></span>"
+    );
+}
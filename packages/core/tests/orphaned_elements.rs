@@ -0,0 +1,42 @@
+use dioxus::prelude::*;
+use dioxus_core::{ElementId, RenderReturn};
+
+fn app(cx: Scope) -> Element {
+    let generation = cx.generation();
+    cx.render(rsx! {
+        div { class: "{generation}",
+            p { "{generation}" }
+            if generation < 2 {
+                rsx! { span { "early" } }
+            }
+        }
+    })
+}
+
+#[test]
+fn diffing_leaves_no_orphans() {
+    let mut dom = VirtualDom::new(app);
+    _ = dom.rebuild();
+    assert!(dom.orphaned_elements().is_empty());
+
+    for _ in 0..4 {
+        dom.mark_dirty(ScopeId::ROOT);
+        _ = dom.render_immediate();
+        assert!(dom.orphaned_elements().is_empty());
+    }
+}
+
+#[test]
+fn leaked_ids_are_flagged() {
+    let mut dom = VirtualDom::new(app);
+    _ = dom.rebuild();
+
+    // Simulate a buggy diff that points the root at a new id without freeing the old one
+    let node = match dom.base_scope().root_node() {
+        RenderReturn::Ready(node) => node,
+        RenderReturn::Aborted(_) => panic!("root should render"),
+    };
+    let leaked = std::mem::replace(&mut node.root_ids.borrow_mut()[0], ElementId(0));
+
+    assert_eq!(dom.orphaned_elements(), [leaked]);
+}
@@ -0,0 +1,69 @@
+#![allow(non_snake_case)]
+//! Snapshots of the resolved tree can be diffed on another thread and the edits applied by the renderer
+
+use dioxus::core::{diff_owned, ElementId, MockDom, RenderReturn, SendableNode};
+use dioxus::prelude::*;
+
+fn app(cx: Scope) -> Element {
+    let count = cx.generation();
+    cx.render(rsx! {
+        ul { class: "list-{count}",
+            Item { label: "first", count: count }
+            Item { label: "second", count: count }
+        }
+    })
+}
+
+#[derive(Props, PartialEq)]
+struct ItemProps {
+    label: &'static str,
+    count: usize,
+}
+
+fn Item(cx: Scope<ItemProps>) -> Element {
+    let ItemProps { label, count } = cx.props;
+    cx.render(rsx! { li { "{label} {count}" } })
+}
+
+fn snapshot(dom: &VirtualDom) -> Vec<SendableNode> {
+    match dom.base_scope().root_node() {
+        RenderReturn::Ready(node) => node.to_sendable(dom),
+        RenderReturn::Aborted(_) => unreachable!(),
+    }
+}
+
+#[test]
+fn owned_diff_runs_off_thread() {
+    let mut dom = VirtualDom::new(app);
+    let mock = MockDom::new();
+    mock.apply(&dom.rebuild());
+    let old = snapshot(&dom);
+
+    dom.mark_dirty(ScopeId::ROOT);
+    _ = dom.render_immediate();
+    let new = snapshot(&dom);
+
+    let edits = std::thread::scope(|s| s.spawn(|| diff_owned(&old, &new)).join().unwrap())
+        .expect("the structure didn't change");
+
+    assert_eq!(edits.len(), 3);
+    for edit in edits.iter() {
+        mock.apply_edit(edit);
+    }
+
+    assert_eq!(mock.text(ElementId(3)), Some("second 1"));
+    assert_eq!(
+        mock.to_string(),
+        r#"<ul class="list-1"><li>first 1</li><li>second 1</li></ul>"#
+    );
+}
+
+#[test]
+fn owned_diff_gives_up_on_structural_changes() {
+    let text =
+        |value: &str| SendableNode::Text { value: value.to_string(), id: Some(ElementId(1)) };
+
+    assert!(diff_owned(&[text("a")], &[text("a"), text("b")]).is_none());
+    assert!(diff_owned(&[text("a")], &[SendableNode::Placeholder { id: None }]).is_none());
+    assert_eq!(diff_owned(&[text("a")], &[text("a")]).unwrap().len(), 0);
+}
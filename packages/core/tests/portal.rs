@@ -0,0 +1,72 @@
+#![allow(non_snake_case)]
+//! Portals mount their children in another element, but stay part of the tree they are rendered in
+
+use dioxus::core::{ElementId, MockDom};
+use dioxus::html::MouseData;
+use dioxus::prelude::*;
+use std::{cell::RefCell, rc::Rc};
+
+thread_local! {
+    static FIRED: RefCell<Vec<&'static str>> = const { RefCell::new(Vec::new()) };
+}
+
+#[derive(Clone)]
+struct Theme(&'static str);
+
+fn app(cx: Scope) -> Element {
+    cx.use_hook(|| cx.provide_context(Theme("dark")));
+    let open = cx.generation() == 0;
+
+    cx.render(rsx! {
+        div {
+            onclick: |_| FIRED.with(|fired| fired.borrow_mut().push("app")),
+            if open {
+                rsx! {
+                    Portal { target: "modal-root", Modal {} }
+                }
+            }
+            "after"
+        }
+    })
+}
+
+fn Modal(cx: Scope) -> Element {
+    let theme = cx.consume_context::<Theme>().unwrap();
+    cx.render(rsx! {
+        p {
+            onclick: |_| FIRED.with(|fired| fired.borrow_mut().push("modal")),
+            "{theme.0} modal"
+        }
+    })
+}
+
+#[test]
+fn children_mount_in_the_target() {
+    let mut dom = VirtualDom::new(app);
+    let renderer = MockDom::new();
+    renderer.apply(&dom.rebuild());
+
+    // The portal leaves a placeholder in its place, and its children get context from above the portal
+    assert_eq!(
+        renderer.to_string(),
+        "<div id=\"modal-root\"><p>dark modal</p></div><div><!--placeholder-->after</div>"
+    );
+
+    // Closing the portal removes its children from the target
+    dom.mark_dirty(ScopeId::ROOT);
+    renderer.apply(&dom.render_immediate());
+    assert_eq!(
+        renderer.to_string(),
+        "<div id=\"modal-root\"></div><div><!--placeholder-->after</div>"
+    );
+}
+
+#[test]
+fn events_bubble_to_the_logical_parent() {
+    let mut dom = VirtualDom::new(app);
+    _ = dom.rebuild();
+
+    // The paragraph in the modal is the second element created
+    dom.handle_event("click", Rc::new(MouseData::default()), ElementId(2), true);
+    FIRED.with(|fired| assert_eq!(*fired.borrow(), ["modal", "app"]));
+}
@@ -0,0 +1,48 @@
+//! Listeners can decide to prevent the default action of an event while they handle it
+
+use dioxus::core::ElementId;
+use dioxus::html::MouseData;
+use dioxus::prelude::*;
+use std::{cell::Cell, rc::Rc};
+
+thread_local! {
+    static PARENT_SAW_PREVENTED: Cell<bool> = const { Cell::new(false) };
+}
+
+fn app(cx: Scope) -> Element {
+    let blocked = cx.generation() == 0;
+
+    cx.render(rsx! {
+        div {
+            onclick: |evt: MouseEvent| PARENT_SAW_PREVENTED.with(|saw| saw.set(evt.default_prevented())),
+            a {
+                href: "/somewhere",
+                onclick: move |evt: MouseEvent| {
+                    if blocked {
+                        evt.prevent_default();
+                    }
+                }
+            }
+        }
+    })
+}
+
+fn click(dom: &mut VirtualDom) -> bool {
+    dom.handle_event("click", Rc::new(MouseData::default()), ElementId(2), true)
+}
+
+#[test]
+fn listeners_prevent_the_default_action_at_runtime() {
+    let mut dom = VirtualDom::new(app);
+    _ = dom.rebuild();
+
+    assert!(!click(&mut dom));
+    // The decision is shared with every listener the event bubbles through
+    assert!(PARENT_SAW_PREVENTED.with(Cell::get));
+
+    dom.mark_dirty(ScopeId::ROOT);
+    _ = dom.render_immediate();
+
+    assert!(click(&mut dom));
+    assert!(!PARENT_SAW_PREVENTED.with(Cell::get));
+}
@@ -0,0 +1,52 @@
+#![allow(non_snake_case)]
+//! Updates scheduled by events are rendered before background work
+
+use dioxus::core::ElementId;
+use dioxus::html::MouseData;
+use dioxus::prelude::*;
+use std::{cell::RefCell, rc::Rc};
+
+thread_local! {
+    static RENDERS: RefCell<Vec<&'static str>> = const { RefCell::new(Vec::new()) };
+}
+
+fn renders() -> Vec<&'static str> {
+    RENDERS.with(|renders| renders.borrow_mut().drain(..).collect())
+}
+
+fn app(cx: Scope) -> Element {
+    render! {
+        Poller {}
+        Input {}
+    }
+}
+
+fn Poller(cx: Scope) -> Element {
+    RENDERS.with(|renders| renders.borrow_mut().push("poller"));
+    if cx.generation() == 0 {
+        cx.needs_update_with_priority(Priority::Low);
+    }
+    render! { div {} }
+}
+
+fn Input(cx: Scope) -> Element {
+    RENDERS.with(|renders| renders.borrow_mut().push("input"));
+    render! { button { onclick: move |_| cx.needs_update() } }
+}
+
+#[test]
+fn event_updates_preempt_low_priority_work() {
+    let mut dom = VirtualDom::new(app);
+    _ = dom.rebuild();
+    renders();
+
+    dom.handle_event("click", Rc::new(MouseData::default()), ElementId(2), true);
+
+    // The input is rendered first even though the poller was scheduled before the click and is higher in the tree
+    _ = dom.render_with_yield(|| true);
+    assert_eq!(renders(), ["input"]);
+
+    _ = dom.render_with_yield(|| true);
+    assert_eq!(renders(), ["poller"]);
+    assert!(!dom.has_dirty_scopes());
+}
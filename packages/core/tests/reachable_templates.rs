@@ -0,0 +1,79 @@
+#![allow(non_snake_case)]
+//! Find which registered templates are still reachable from the root, so the rest can be pruned
+
+use dioxus::core::{RenderReturn, TemplateId};
+use dioxus::prelude::*;
+use rustc_hash::FxHashSet;
+
+fn app(cx: Scope) -> Element {
+    // The banner is only shown on the first render
+    let show_banner = cx.generation() == 0;
+
+    cx.render(rsx! {
+        div {
+            if show_banner {
+                rsx! { h1 { "Welcome!" } }
+            }
+            Card {}
+        }
+    })
+}
+
+fn Card(cx: Scope) -> Element {
+    cx.render(rsx! { p { "card" } })
+}
+
+fn reachable(dom: &VirtualDom) -> FxHashSet<TemplateId> {
+    match dom.base_scope().root_node() {
+        RenderReturn::Ready(node) => dom.reachable_templates(node),
+        RenderReturn::Aborted(_) => unreachable!(),
+    }
+}
+
+#[test]
+fn only_rendered_templates_are_reachable() {
+    let mut dom = VirtualDom::new(app);
+    _ = dom.rebuild();
+
+    assert_eq!(dom.registered_templates().len(), 3);
+    assert_eq!(reachable(&dom).len(), 3);
+
+    dom.mark_dirty(ScopeId::ROOT);
+    _ = dom.render_immediate();
+
+    // The banner template is still registered, but nothing renders it anymore
+    let registered = dom.registered_templates();
+    let reachable = reachable(&dom);
+    assert_eq!(registered.len(), 3);
+    assert_eq!(reachable.len(), 2);
+    assert!(reachable.is_subset(&registered));
+
+    dom.retain_templates(&reachable);
+    assert_eq!(dom.registered_templates(), reachable);
+}
+
+#[test]
+fn pruned_templates_are_sent_again_when_rendered() {
+    fn toggle(cx: Scope) -> Element {
+        // Shown, then hidden, then shown again
+        let show = cx.generation() != 1;
+        cx.render(rsx! {
+            if show {
+                rsx! { span { "shown" } }
+            }
+        })
+    }
+
+    let mut dom = VirtualDom::new(toggle);
+    _ = dom.rebuild();
+
+    dom.mark_dirty(ScopeId::ROOT);
+    _ = dom.render_immediate();
+    let reachable = reachable(&dom);
+    dom.retain_templates(&reachable);
+
+    dom.mark_dirty(ScopeId::ROOT);
+    // The span was pruned while hidden, so it is sent to the renderer again
+    let edits = dom.render_immediate();
+    assert_eq!(edits.templates.len(), 1);
+}
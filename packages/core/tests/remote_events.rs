@@ -0,0 +1,75 @@
+#![cfg(feature = "serialize")]
+//! Listener registrations serialize for remote renderers, and events sent back invoke the right closure
+
+use dioxus::core::{ElementId, Mutation, RemoteEvent};
+use dioxus::prelude::*;
+use std::cell::RefCell;
+
+thread_local! {
+    static RENAMED: RefCell<Vec<(&'static str, String)>> = const { RefCell::new(Vec::new()) };
+}
+
+fn record(listener: &'static str, name: &str) {
+    RENAMED.with(|renamed| renamed.borrow_mut().push((listener, name.to_string())));
+}
+
+fn app(cx: Scope) -> Element {
+    cx.render(rsx! {
+        div { "onrename": cx.listener(|evt: Event<String>| record("first", &evt.data)) }
+        div { "onrename": cx.listener(|evt: Event<String>| record("second", &evt.data)) }
+    })
+}
+
+const SECOND_LISTENER: &str = r#"{"type":"NewEventListener","name":"rename","id":2}"#;
+
+#[test]
+fn listener_registrations_serialize() {
+    let mut dom = VirtualDom::new(app);
+    let edits = dom.rebuild().santize();
+
+    let listeners: Vec<String> = edits
+        .edits
+        .iter()
+        .filter(|edit| matches!(edit, Mutation::NewEventListener { .. }))
+        .map(|edit| serde_json::to_string(edit).unwrap())
+        .collect();
+
+    assert_eq!(
+        listeners,
+        [
+            r#"{"type":"NewEventListener","name":"rename","id":1}"#,
+            SECOND_LISTENER,
+        ]
+    );
+
+    // The client can read them back without knowing anything about the closures
+    let parsed: Mutation = serde_json::from_str(SECOND_LISTENER).unwrap();
+    assert_eq!(
+        parsed,
+        Mutation::NewEventListener { name: "rename", id: ElementId(2) }
+    );
+}
+
+#[test]
+fn round_tripped_event_invokes_the_right_closure() {
+    let mut dom = VirtualDom::new(app);
+    _ = dom.rebuild();
+
+    let sent = RemoteEvent {
+        name: "rename".to_string(),
+        element: ElementId(2),
+        bubbles: false,
+        payload: "hello".to_string(),
+    };
+    let message = serde_json::to_string(&sent).unwrap();
+    assert_eq!(
+        message,
+        r#"{"name":"rename","element":2,"bubbles":false,"payload":"hello"}"#
+    );
+
+    let received: RemoteEvent<String> = serde_json::from_str(&message).unwrap();
+    assert_eq!(received, sent);
+    dom.handle_remote_event(received);
+
+    RENAMED.with(|r| assert_eq!(*r.borrow(), [("second", "hello".to_string())]));
+}
@@ -0,0 +1,62 @@
+#![allow(non_snake_case)]
+
+//! Changing the remount token recreates a node and its scopes even though the key stays the same
+
+use dioxus::core::{ElementId, Mutation::*, TemplatePath};
+use dioxus::prelude::*;
+use std::cell::RefCell;
+
+thread_local! {
+    static MOUNTED: RefCell<Vec<ScopeId>> = const { RefCell::new(Vec::new()) };
+}
+
+fn Counter(cx: Scope) -> Element {
+    cx.use_hook(|| MOUNTED.with(|mounted| mounted.borrow_mut().push(cx.scope_id())));
+    cx.render(rsx! { "count" })
+}
+
+fn app(cx: Scope) -> Element {
+    let token = if cx.generation() < 2 {
+        "first"
+    } else {
+        "second"
+    };
+    cx.render(rsx! {
+        div { key: "form", "dioxus-remount": "{token}", Counter {} }
+    })
+}
+
+#[test]
+fn changing_the_token_remounts() {
+    let mut dom = VirtualDom::new(app);
+    let edits = dom.rebuild().santize();
+
+    // The token is never sent to the renderer
+    assert!(!edits
+        .edits
+        .iter()
+        .any(|edit| matches!(edit, SetAttribute { .. })));
+    drop(edits);
+    assert_eq!(MOUNTED.with(|m| m.borrow().len()), 1);
+
+    // Same token, nothing changes
+    dom.mark_dirty(ScopeId::ROOT);
+    assert!(dom.render_immediate().edits.is_empty());
+    assert_eq!(MOUNTED.with(|m| m.borrow().len()), 1);
+
+    // A new token recreates both the element and the component under it
+    dom.mark_dirty(ScopeId::ROOT);
+    assert_eq!(
+        dom.render_immediate().santize().edits,
+        [
+            LoadTemplate { name: "template", index: 0, id: ElementId(3) },
+            LoadTemplate { name: "template", index: 0, id: ElementId(4) },
+            ReplacePlaceholder { path: TemplatePath::Slice(&[0]), m: 1 },
+            ReplaceWith { id: ElementId(1), m: 1 },
+        ]
+    );
+
+    let mounted = MOUNTED.with(|m| m.borrow().clone());
+    assert_eq!(mounted.len(), 2);
+    assert_ne!(mounted[0], mounted[1]);
+}
@@ -0,0 +1,73 @@
+use dioxus::prelude::*;
+use dioxus_core::RenderBudget;
+use std::{cell::RefCell, rc::Rc, time::Duration};
+
+/// A component that keeps re-rendering itself, up to 50 times
+fn runaway(cx: Scope) -> Element {
+    if cx.generation() < 50 {
+        cx.needs_update();
+    }
+    cx.render(rsx! { div { "{cx.generation()}" } })
+}
+
+fn budgeted_dom(defer: bool) -> (VirtualDom, Rc<RefCell<Vec<ScopeId>>>) {
+    let exceeded = Rc::new(RefCell::new(Vec::new()));
+    let dom =
+        VirtualDom::new(runaway).with_render_budget(RenderBudget { max_renders: 10, defer }, {
+            let exceeded = exceeded.clone();
+            move |scope| exceeded.borrow_mut().push(scope)
+        });
+    (dom, exceeded)
+}
+
+#[tokio::test]
+async fn runaway_renders_are_reported() {
+    let (mut dom, exceeded) = budgeted_dom(false);
+    _ = dom.rebuild();
+
+    _ = dom
+        .render_with_deadline(tokio::time::sleep(Duration::from_millis(50)))
+        .await;
+
+    // The scope is only reported once, and keeps rendering until it settles
+    assert_eq!(*exceeded.borrow(), [ScopeId::ROOT]);
+    assert_eq!(dom.base_scope().generation(), 51);
+}
+
+#[tokio::test]
+async fn runaway_renders_are_deferred() {
+    let (mut dom, exceeded) = budgeted_dom(true);
+    _ = dom.rebuild();
+
+    _ = dom
+        .render_with_deadline(tokio::time::sleep(Duration::from_millis(50)))
+        .await;
+    assert_eq!(*exceeded.borrow(), [ScopeId::ROOT]);
+    assert_eq!(dom.base_scope().generation(), 11);
+
+    // The next tick picks up where the last one stopped
+    _ = dom
+        .render_with_deadline(tokio::time::sleep(Duration::from_millis(50)))
+        .await;
+    assert_eq!(*exceeded.borrow(), [ScopeId::ROOT, ScopeId::ROOT]);
+    assert_eq!(dom.base_scope().generation(), 21);
+}
+
+#[tokio::test]
+async fn deferred_renders_wake_an_idle_dom() {
+    let (mut dom, _) = budgeted_dom(true);
+    _ = dom.rebuild();
+
+    _ = dom
+        .render_with_deadline(tokio::time::sleep(Duration::from_millis(50)))
+        .await;
+    assert_eq!(dom.base_scope().generation(), 11);
+    assert!(!dom.has_dirty_scopes());
+
+    // Nothing else is going on, so only the deferred render can wake the dom up
+    tokio::time::timeout(Duration::from_millis(50), dom.wait_for_work())
+        .await
+        .expect("the deferred render should be picked up");
+    _ = dom.render_immediate();
+    assert_eq!(dom.base_scope().generation(), 12);
+}
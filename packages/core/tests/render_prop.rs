@@ -0,0 +1,54 @@
+#![allow(non_snake_case)]
+
+//! Components can take closures that render part of their output with values only they know about
+
+use dioxus::prelude::*;
+
+#[derive(Props)]
+struct ListProps<'a> {
+    count: u32,
+    row: RenderProp<'a, u32>,
+}
+
+fn List<'a>(cx: Scope<'a, ListProps<'a>>) -> Element<'a> {
+    cx.render(rsx! {
+        ul { (0..cx.props.count).map(|i| cx.props.row.call(i)) }
+    })
+}
+
+fn app(cx: Scope) -> Element {
+    let prefix = "row";
+    cx.render(rsx! {
+        List {
+            count: 3,
+            row: cx.render_prop(move |i| cx.render(rsx!(li { key: "{i}", "{prefix} {i}" }))),
+        }
+    })
+}
+
+#[test]
+fn child_renders_the_closure_with_its_own_values() {
+    let mut dom = VirtualDom::new(app);
+    _ = dom.rebuild();
+
+    assert_eq!(
+        dioxus_ssr::render(&dom),
+        "<ul><li>row 0</li><li>row 1</li><li>row 2</li></ul>"
+    );
+}
+
+#[test]
+fn child_can_rerender_on_its_own() {
+    let mut dom = VirtualDom::new(app);
+    _ = dom.rebuild();
+
+    for _ in 0..3 {
+        dom.mark_dirty(ScopeId(1));
+        _ = dom.render_immediate();
+    }
+
+    assert_eq!(
+        dioxus_ssr::render(&dom),
+        "<ul><li>row 0</li><li>row 1</li><li>row 2</li></ul>"
+    );
+}
@@ -0,0 +1,172 @@
+use dioxus::core::Mutation::*;
+use dioxus::prelude::*;
+use dioxus_core::{BorrowedAttributeValue, ElementId, MockDom, Mutation, Mutations, TemplatePath};
+
+/// Move every text edit as far forward as it can go, the way a renderer might batch similar edits together. An edit
+/// only trades places with the edit before it if the two commute. Returns the new order of the edits.
+fn batch(edits: &[Mutation]) -> Vec<usize> {
+    let mut order = (0..edits.len()).collect::<Vec<_>>();
+    for start in 1..order.len() {
+        let mut idx = start;
+        while idx > 0 {
+            let (before, edit) = (&edits[order[idx - 1]], &edits[order[idx]]);
+            if !matches!(edit, SetText { .. })
+                || matches!(before, SetText { .. })
+                || !edit.commutes_with(before)
+            {
+                break;
+            }
+            order.swap(idx - 1, idx);
+            idx -= 1;
+        }
+    }
+    order
+}
+
+/// Apply the mutations to one renderer as they are and to another after batching them. Checks that every two edits
+/// that don't commute are still in the same order, and returns the batched edits.
+fn apply_batched<'a>(
+    mut mutations: Mutations<'a>,
+    original: &MockDom,
+    batched: &MockDom,
+) -> Vec<Mutation<'a>> {
+    original.apply(&mutations);
+
+    let order = batch(&mutations.edits);
+    let position = |idx: usize| order.iter().position(|moved| *moved == idx).unwrap();
+    for (first_idx, first) in mutations.edits.iter().enumerate() {
+        for (second_idx, second) in mutations.edits.iter().enumerate().skip(first_idx + 1) {
+            if !first.commutes_with(second) {
+                assert!(
+                    position(first_idx) < position(second_idx),
+                    "{:?} was moved before {:?}",
+                    second,
+                    first
+                );
+            }
+        }
+    }
+
+    let mut edits = mutations.edits.drain(..).map(Some).collect::<Vec<_>>();
+    mutations.edits = order
+        .into_iter()
+        .map(|idx| edits[idx].take().unwrap())
+        .collect();
+    batched.apply(&mutations);
+    mutations.edits
+}
+
+#[test]
+fn side_effects_are_not_reordered() {
+    fn app(cx: Scope) -> Element {
+        let show = cx.generation() != 1;
+        cx.render(rsx! {
+            div {
+                if show {
+                    rsx! { ul { key: "articles", class: "{show}", li { onmounted: |_| {}, "first" } } }
+                }
+            }
+        })
+    }
+
+    let (original, batched) = (MockDom::new(), MockDom::new());
+    let mut dom = VirtualDom::new(app);
+    dom.register_scroll_restoration("articles");
+    apply_batched(dom.rebuild(), &original, &batched);
+
+    dom.mark_dirty(ScopeId::ROOT);
+    let edits = apply_batched(dom.render_immediate(), &original, &batched);
+    let removed = match edits.last() {
+        Some(ReplaceWith { id, .. }) => *id,
+        edit => panic!("expected the list to be replaced, got {:?}", edit),
+    };
+    dom.report_scroll(removed, 0.0, 120.0);
+
+    dom.mark_dirty(ScopeId::ROOT);
+    let edits = apply_batched(dom.render_immediate(), &original, &batched);
+
+    // The side effects still come after the creates that were before them
+    let position = |target: &Mutation| edits.iter().position(|edit| edit == target).unwrap();
+    let mounted = NewEventListener { name: "mounted", id: ElementId(3) };
+    let scroll = RestoreScroll { id: ElementId(2), x: 0.0, y: 120.0 };
+    assert!(!mounted.is_reorderable());
+    assert!(!scroll.is_reorderable());
+    let load = edits
+        .iter()
+        .position(|edit| matches!(edit, LoadTemplate { id: ElementId(2), .. }))
+        .unwrap();
+    assert!(load < position(&mounted));
+    assert!(position(&mounted) < position(&scroll));
+    assert_eq!(position(&scroll), edits.len() - 1);
+
+    assert_eq!(original.to_string(), batched.to_string());
+}
+
+#[test]
+fn edits_to_the_same_element_keep_their_order() {
+    fn app(cx: Scope) -> Element {
+        let count = cx.generation();
+        cx.render(rsx! {
+            div { class: "{count}", title: "{count}", "{count}" }
+        })
+    }
+
+    let (original, batched) = (MockDom::new(), MockDom::new());
+    let mut dom = VirtualDom::new(app);
+    apply_batched(dom.rebuild(), &original, &batched);
+
+    dom.mark_dirty(ScopeId::ROOT);
+    let edits = apply_batched(dom.render_immediate(), &original, &batched);
+    assert!(edits.iter().all(Mutation::is_reorderable));
+
+    // Both attributes change the div, so the title is still set after the class
+    let attributes = edits
+        .iter()
+        .filter_map(|edit| match edit {
+            SetAttribute { name, .. } => Some(*name),
+            _ => None,
+        })
+        .collect::<Vec<_>>();
+    assert_eq!(attributes, ["class", "title"]);
+
+    assert_eq!(original.to_string(), batched.to_string());
+}
+
+#[test]
+fn only_edits_to_different_elements_commute() {
+    let style = SetAttribute {
+        name: "style",
+        value: BorrowedAttributeValue::Text("color: red"),
+        id: ElementId(1),
+        ns: None,
+    };
+    let color = SetAttribute {
+        name: "color",
+        value: BorrowedAttributeValue::Text("blue"),
+        id: ElementId(1),
+        ns: Some("style"),
+    };
+    let text = SetText { value: "hello", id: ElementId(2) };
+    let mounted = NewEventListener { name: "mounted", id: ElementId(2) };
+
+    // The style attribute overwrites its properties, so they can't be swapped
+    assert!(!style.commutes_with(&color));
+    assert!(style.commutes_with(&text));
+    assert!(text.commutes_with(&color));
+    assert!(!text.commutes_with(&text));
+    assert!(!mounted.commutes_with(&style));
+
+    // Creating, moving and removing nodes never commutes with the edits around it
+    let structural = [
+        LoadTemplate { name: "template", index: 0, id: ElementId(3) },
+        AssignId { path: TemplatePath::Slice(&[0]), id: ElementId(3) },
+        AppendChildren { id: ElementId(0), m: 1 },
+        PushRoot { id: ElementId(3) },
+        Remove { id: ElementId(3) },
+    ];
+    for edit in &structural {
+        assert!(!edit.is_reorderable());
+        assert!(!edit.commutes_with(&text));
+        assert!(!text.commutes_with(edit));
+    }
+}
@@ -0,0 +1,90 @@
+#![allow(non_snake_case)]
+//! Components can track the size of their elements through the renderer's resize observer
+
+use dioxus::core::Mutation::NewEventListener;
+use dioxus::html::{
+    geometry::euclid::Size2D, MountedData, MountedResult, RenderedElementBacking, ResizeObserver,
+    ResizeObserverBacking,
+};
+use dioxus::prelude::*;
+use std::{cell::Cell, cell::RefCell, future::Future, pin::Pin, rc::Rc, time::Duration};
+use tokio::sync::mpsc::{unbounded_channel, UnboundedReceiver};
+
+thread_local! {
+    static SIZES: RefCell<Option<UnboundedReceiver<Size2D<f64, f64>>>> = const { RefCell::new(None) };
+    static RENDERS: Cell<usize> = const { Cell::new(0) };
+}
+
+/// An element whose sizes are sent by the test
+struct TestElement;
+
+impl RenderedElementBacking for TestElement {
+    fn observe_resize(&self) -> Pin<Box<dyn Future<Output = MountedResult<ResizeObserver>>>> {
+        let sizes = SIZES.with(|sizes| sizes.borrow_mut().take()).unwrap();
+        Box::pin(async move { Ok(ResizeObserver::new(TestObserver(sizes))) })
+    }
+}
+
+struct TestObserver(UnboundedReceiver<Size2D<f64, f64>>);
+
+impl ResizeObserverBacking for TestObserver {
+    fn next(&mut self) -> Pin<Box<dyn Future<Output = Option<Size2D<f64, f64>>> + '_>> {
+        Box::pin(self.0.recv())
+    }
+}
+
+fn app(cx: Scope) -> Element {
+    let size = use_resize_observer(cx);
+    RENDERS.with(|renders| renders.set(renders.get() + 1));
+
+    let text = match size.size() {
+        Some(size) => format!("{}x{}", size.width, size.height),
+        None => "unknown".to_string(),
+    };
+
+    cx.render(rsx! {
+        div { onmounted: move |evt| size.observe(evt), "{text}" }
+    })
+}
+
+async fn work(dom: &mut VirtualDom) {
+    tokio::select! {
+        _ = dom.wait_for_work() => {}
+        _ = tokio::time::sleep(Duration::from_millis(100)) => {}
+    };
+    _ = dom.render_immediate();
+}
+
+#[tokio::test]
+async fn new_sizes_rerender() {
+    let (sender, receiver) = unbounded_channel();
+    SIZES.with(|sizes| *sizes.borrow_mut() = Some(receiver));
+
+    let mut dom = VirtualDom::new(app);
+    let id = dom
+        .rebuild()
+        .santize()
+        .edits
+        .iter()
+        .find_map(|edit| match edit {
+            NewEventListener { name: "mounted", id } => Some(*id),
+            _ => None,
+        })
+        .unwrap();
+    dom.handle_event("mounted", Rc::new(MountedData::new(TestElement)), id, false);
+
+    // The observer starts after the component renders with the new element
+    work(&mut dom).await;
+    work(&mut dom).await;
+    assert_eq!(dioxus_ssr::render(&dom), "<div>unknown</div>");
+
+    sender.send(Size2D::new(300.0, 200.0)).unwrap();
+    work(&mut dom).await;
+    assert_eq!(dioxus_ssr::render(&dom), "<div>300x200</div>");
+
+    // The same size again doesn't render the component
+    let renders = RENDERS.with(|renders| renders.get());
+    sender.send(Size2D::new(300.0, 200.0)).unwrap();
+    work(&mut dom).await;
+    assert_eq!(RENDERS.with(|renders| renders.get()), renders);
+}
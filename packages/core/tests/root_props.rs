@@ -0,0 +1,54 @@
+//! The root component can be given new props without recreating the VirtualDom
+
+use dioxus::core::{ElementId, Mutation::*};
+use dioxus::prelude::*;
+
+#[derive(PartialEq)]
+struct AppProps {
+    name: String,
+    label: &'static str,
+}
+
+fn app(cx: Scope<AppProps>) -> Element {
+    let name = &cx.props.name;
+    render! {
+        div { "{name}" }
+        Label { text: cx.props.label }
+    }
+}
+
+#[derive(Props, PartialEq)]
+struct LabelProps {
+    text: &'static str,
+}
+
+#[allow(non_snake_case)]
+fn Label(cx: Scope<LabelProps>) -> Element {
+    render! { "{cx.props.text}" }
+}
+
+#[test]
+fn update_root_props_diffs_the_root() {
+    let mut dom =
+        VirtualDom::new_with_props(app, AppProps { name: "jane".to_string(), label: "hello" });
+    _ = dom.rebuild();
+
+    dom.update_root_props(AppProps { name: "jack".to_string(), label: "hello" });
+    assert!(dom.has_dirty_scopes());
+
+    // The label gets the same props, so only the text of the root changes
+    assert_eq!(
+        dom.render_immediate().santize().edits,
+        [SetText { value: "jack", id: ElementId(2) }]
+    );
+    assert_eq!(dom.get_scope(ScopeId(1)).unwrap().generation(), 1);
+}
+
+#[test]
+#[should_panic]
+fn update_root_props_checks_the_type() {
+    let mut dom =
+        VirtualDom::new_with_props(app, AppProps { name: "jane".to_string(), label: "hello" });
+    _ = dom.rebuild();
+    dom.update_root_props("jack".to_string());
+}
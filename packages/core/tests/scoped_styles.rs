@@ -0,0 +1,76 @@
+#![cfg(feature = "scoped-styles")]
+#![allow(non_snake_case)]
+
+use dioxus::core::Mutation::*;
+use dioxus::prelude::*;
+use dioxus_core::{Template, TemplateNode};
+use std::cell::Cell;
+
+static STYLED: Template = Template {
+    name: concat!(file!(), ":", line!(), ":", column!(), ":0"),
+    roots: &[TemplateNode::Element { tag: "div", namespace: None, attrs: &[], children: &[] }],
+    node_paths: &[],
+    attr_paths: &[],
+    style: Some("div { color: red; }"),
+    ..Template::EMPTY
+};
+
+fn Styled(cx: Scope) -> Element {
+    Some(VNode {
+        key: None,
+        parent: Default::default(),
+        template: Cell::new(STYLED),
+        root_ids: dioxus_core::exports::bumpalo::collections::Vec::new_in(cx.bump()).into(),
+        dynamic_nodes: &[],
+        dynamic_attrs: &[],
+    })
+}
+
+#[test]
+fn style_is_injected_once_per_template() {
+    fn app(cx: Scope) -> Element {
+        let count = if cx.generation() == 0 { 3 } else { 5 };
+        cx.render(rsx! {
+            (0..count).map(|i| rsx! { Styled { key: "{i}" } })
+        })
+    }
+
+    let mut dom = VirtualDom::new(app);
+    let edits = dom.rebuild().santize();
+
+    let injected = edits
+        .edits
+        .iter()
+        .filter(|edit| matches!(edit, InjectStyle { .. }))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        injected,
+        [&InjectStyle { name: STYLED.name, style: "div { color: red; }" }]
+    );
+
+    // The style is sent before the template is loaded for the first time
+    let first_inject = edits
+        .edits
+        .iter()
+        .position(|edit| matches!(edit, InjectStyle { .. }))
+        .unwrap();
+    let first_load = edits
+        .edits
+        .iter()
+        .position(|edit| matches!(edit, LoadTemplate { .. }))
+        .unwrap();
+    assert!(first_inject < first_load);
+    drop(edits);
+
+    // Mounting more instances of the template doesn't inject the style again
+    dom.mark_dirty(ScopeId::ROOT);
+    let edits = dom.render_immediate();
+    assert!(edits
+        .edits
+        .iter()
+        .any(|edit| matches!(edit, LoadTemplate { .. })));
+    assert!(!edits
+        .edits
+        .iter()
+        .any(|edit| matches!(edit, InjectStyle { .. })));
+}
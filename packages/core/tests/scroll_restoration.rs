@@ -0,0 +1,65 @@
+use dioxus::core::Mutation::*;
+use dioxus::prelude::*;
+use dioxus_core::ElementId;
+
+fn app(cx: Scope) -> Element {
+    let show = cx.generation() != 1;
+    cx.render(rsx! {
+        div {
+            if show {
+                rsx! { ul { key: "articles", li { "first" } } }
+            }
+        }
+    })
+}
+
+#[test]
+fn scroll_is_restored_on_remount() {
+    let mut dom = VirtualDom::new(app);
+    dom.register_scroll_restoration("articles");
+    _ = dom.rebuild();
+
+    // The renderer is asked for the position of the list while it is still attached, right before it unmounts
+    dom.mark_dirty(ScopeId::ROOT);
+    let removed = match dom.render_immediate().edits.as_slice() {
+        [.., CaptureScroll { id: captured }, ReplaceWith { id, .. }] if captured == id => *id,
+        edits => panic!(
+            "expected the list to be captured and replaced, got {:?}",
+            edits
+        ),
+    };
+    dom.report_scroll(removed, 0.0, 120.0);
+
+    // When it comes back, the position is restored after everything else is mounted
+    dom.mark_dirty(ScopeId::ROOT);
+    let edits = dom.render_immediate().santize();
+    assert_eq!(
+        edits.edits,
+        [
+            LoadTemplate { name: "template", index: 0, id: ElementId(2) },
+            ReplaceWith { id: ElementId(3), m: 1 },
+            RestoreScroll { id: ElementId(2), x: 0.0, y: 120.0 },
+        ]
+    );
+}
+
+#[test]
+fn unregistered_elements_are_not_restored() {
+    let mut dom = VirtualDom::new(app);
+    _ = dom.rebuild();
+
+    dom.mark_dirty(ScopeId::ROOT);
+    assert!(!dom
+        .render_immediate()
+        .edits
+        .iter()
+        .any(|edit| matches!(edit, CaptureScroll { .. })));
+    dom.report_scroll(ElementId(2), 0.0, 120.0);
+
+    dom.mark_dirty(ScopeId::ROOT);
+    let edits = dom.render_immediate();
+    assert!(!edits
+        .edits
+        .iter()
+        .any(|edit| matches!(edit, RestoreScroll { .. })));
+}
@@ -0,0 +1,96 @@
+#![allow(non_snake_case)]
+
+use dioxus::prelude::*;
+use dioxus_core::{ElementId, RenderReturn};
+
+fn Card(cx: Scope<CardProps>) -> Element {
+    cx.render(rsx! {
+        div { class: "card {cx.props.kind}", lang: "{cx.props.lang}",
+            h2 { "{cx.props.title}" }
+        }
+    })
+}
+
+#[derive(Props, PartialEq)]
+struct CardProps {
+    title: &'static str,
+    kind: &'static str,
+    lang: &'static str,
+}
+
+fn app(cx: Scope) -> Element {
+    cx.render(rsx! {
+        main { id: "content",
+            Card { title: "one", kind: "primary", lang: "en" }
+            Card { title: "two", kind: "secondary", lang: "fr" }
+        }
+        footer { h2 { "footer" } }
+    })
+}
+
+fn rendered() -> VirtualDom {
+    let mut dom = VirtualDom::new(app);
+    _ = dom.rebuild();
+    dom
+}
+
+fn root(dom: &VirtualDom) -> &VNode<'_> {
+    match dom.base_scope().root_node() {
+        RenderReturn::Ready(node) => node,
+        RenderReturn::Aborted(_) => panic!("root should render"),
+    }
+}
+
+#[test]
+fn select_by_tag() {
+    let dom = rendered();
+    let headings = dom.select(root(&dom), "h2");
+    assert_eq!(headings.len(), 3);
+    assert!(headings.iter().all(|el| el.tag == "h2"));
+
+    let main = dom.select(root(&dom), "main");
+    assert_eq!(main.len(), 1);
+    assert_eq!(main[0].id, Some(ElementId(1)));
+}
+
+#[test]
+fn select_by_class_and_id() {
+    let dom = rendered();
+
+    let cards = dom.select(root(&dom), ".card");
+    assert_eq!(cards.len(), 2);
+
+    let primary = dom.select(root(&dom), "div.card.primary");
+    assert_eq!(primary.len(), 1);
+    assert_eq!(primary[0].attribute("lang"), Some("en"));
+
+    // Descendant selectors skip headings outside of the main element
+    assert_eq!(dom.select(root(&dom), "#content h2").len(), 2);
+    assert!(dom.select(root(&dom), "#missing").is_empty());
+}
+
+#[test]
+fn select_by_attribute_value() {
+    let dom = rendered();
+
+    let french = dom.select(root(&dom), "[lang=fr]");
+    assert_eq!(french.len(), 1);
+    assert_eq!(french[0].attribute("class"), Some("card secondary"));
+
+    assert_eq!(dom.select(root(&dom), "div[lang]").len(), 2);
+    assert_eq!(dom.select(root(&dom), "[lang=\"en\"] h2").len(), 1);
+}
+
+#[test]
+#[should_panic(expected = "invalid selector")]
+fn invalid_selectors_panic() {
+    let dom = rendered();
+    dom.select(root(&dom), "div[lang");
+}
+
+#[test]
+#[should_panic(expected = "invalid selector")]
+fn non_ascii_after_attribute_is_invalid() {
+    let dom = rendered();
+    dom.select(root(&dom), "[lang]é");
+}
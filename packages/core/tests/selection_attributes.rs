@@ -0,0 +1,72 @@
+//! The selection of a text field is always sent after its value, so a value update can't clobber it
+
+use dioxus::core::{BorrowedAttributeValue, ElementId, Mutation::*};
+use dioxus::prelude::*;
+
+fn app(cx: Scope) -> Element {
+    let (text, cursor) = match cx.generation() {
+        0 => ("hello", 5),
+        _ => ("hello world", 11),
+    };
+
+    // The selection is declared before the value, but still has to be applied after it
+    cx.render(rsx! {
+        textarea { selection_start: cursor, selection_end: cursor, value: "{text}" }
+    })
+}
+
+#[test]
+fn selection_is_set_after_the_value() {
+    let mut dom = VirtualDom::new(app);
+
+    assert_eq!(
+        dom.rebuild().santize().edits,
+        [
+            LoadTemplate { name: "template", index: 0, id: ElementId(1) },
+            SetAttribute {
+                name: "value",
+                value: BorrowedAttributeValue::Text("hello"),
+                id: ElementId(1),
+                ns: None
+            },
+            SetAttribute {
+                name: "selection_start",
+                value: BorrowedAttributeValue::Int(5),
+                id: ElementId(1),
+                ns: None
+            },
+            SetAttribute {
+                name: "selection_end",
+                value: BorrowedAttributeValue::Int(5),
+                id: ElementId(1),
+                ns: None
+            },
+            AppendChildren { id: ElementId(0), m: 1 },
+        ]
+    );
+
+    dom.mark_dirty(ScopeId::ROOT);
+    assert_eq!(
+        dom.render_immediate().santize().edits,
+        [
+            SetAttribute {
+                name: "value",
+                value: BorrowedAttributeValue::Text("hello world"),
+                id: ElementId(1),
+                ns: None
+            },
+            SetAttribute {
+                name: "selection_start",
+                value: BorrowedAttributeValue::Int(11),
+                id: ElementId(1),
+                ns: None
+            },
+            SetAttribute {
+                name: "selection_end",
+                value: BorrowedAttributeValue::Int(11),
+                id: ElementId(1),
+                ns: None
+            },
+        ]
+    );
+}
@@ -0,0 +1,81 @@
+#![allow(non_snake_case)]
+//! The resolved tree can be copied out into owned nodes and read on another thread
+
+use dioxus::core::{ElementId, RenderReturn, SendableAttribute, SendableNode, SendableValue};
+use dioxus::prelude::*;
+
+fn app(cx: Scope) -> Element {
+    let count = 2;
+    cx.render(rsx! {
+        ul { class: "list", "data-count": count as i64, onclick: |_| {},
+            Item { label: "first" }
+            Item { label: "second" }
+        }
+    })
+}
+
+#[derive(Props, PartialEq)]
+struct ItemProps {
+    label: &'static str,
+}
+
+fn Item(cx: Scope<ItemProps>) -> Element {
+    cx.render(rsx! { li { "{cx.props.label}" } })
+}
+
+fn attribute(name: &str, value: SendableValue) -> SendableAttribute {
+    SendableAttribute { name: name.to_string(), namespace: None, value }
+}
+
+fn item(label: &str, id: usize) -> SendableNode {
+    SendableNode::Element {
+        tag: "li".to_string(),
+        namespace: None,
+        id: Some(ElementId(id)),
+        attributes: vec![],
+        children: vec![SendableNode::Text {
+            value: label.to_string(),
+            id: Some(ElementId(id + 1)),
+        }],
+    }
+}
+
+#[test]
+fn sendable_node_moves_to_another_thread() {
+    let mut dom = VirtualDom::new(app);
+    _ = dom.rebuild();
+
+    let roots = match dom.base_scope().root_node() {
+        RenderReturn::Ready(node) => node.to_sendable(&dom),
+        RenderReturn::Aborted(_) => unreachable!(),
+    };
+
+    let roots = std::thread::spawn(move || {
+        match &roots[0] {
+            SendableNode::Element { tag, children, .. } => {
+                assert_eq!(tag, "ul");
+                assert_eq!(children.len(), 2);
+            }
+            _ => panic!("expected the list"),
+        }
+        roots
+    })
+    .join()
+    .unwrap();
+
+    assert_eq!(
+        roots,
+        [SendableNode::Element {
+            tag: "ul".to_string(),
+            namespace: None,
+            id: Some(ElementId(1)),
+            attributes: vec![
+                attribute("class", SendableValue::Text("list".to_string())),
+                attribute("data-count", SendableValue::Int(2)),
+                attribute("onclick", SendableValue::Listener),
+            ],
+            // Siblings are created last to first
+            children: vec![item("first", 4), item("second", 2)],
+        }]
+    );
+}
@@ -0,0 +1,75 @@
+#![cfg(feature = "serialize")]
+//! Mutations and templates survive a trip through json, so they can be sent to a remote renderer
+
+use dioxus::core::{Mutations, Template};
+use dioxus::prelude::*;
+
+fn app(cx: Scope) -> Element {
+    cx.render(rsx! {
+        svg { width: 10,
+            circle { r: "{cx.generation()}" }
+        }
+        ul {
+            (0..3).map(|i| rsx!(li { key: "{i}", "item {i}" }))
+        }
+    })
+}
+
+// Deserialized strings borrow from the json, so it has to outlive them
+fn leak(json: String) -> &'static str {
+    Box::leak(json.into_boxed_str())
+}
+
+#[test]
+fn mutations_round_trip_through_json() {
+    let mut dom = VirtualDom::new(app);
+    let mutations = dom.rebuild();
+
+    let json = serde_json::to_string(&mutations).unwrap();
+    let decoded: Mutations = serde_json::from_str(leak(json)).unwrap();
+
+    assert_eq!(decoded.subtree, mutations.subtree);
+    assert_eq!(decoded.dirty_scopes, mutations.dirty_scopes);
+    assert_eq!(decoded.templates, mutations.templates);
+    assert_eq!(decoded.edits, mutations.edits);
+}
+
+#[test]
+fn templates_round_trip_through_json() {
+    let mut dom = VirtualDom::new(app);
+    let templates = dom.rebuild().templates;
+    assert!(!templates.is_empty());
+
+    for template in templates {
+        let json = serde_json::to_string(&template).unwrap();
+        let decoded: Template = serde_json::from_str(leak(json)).unwrap();
+        assert_eq!(decoded, template);
+    }
+}
+
+#[test]
+fn variants_are_tagged_by_name() {
+    let mut dom = VirtualDom::new(app);
+    let json = serde_json::to_value(dom.rebuild()).unwrap();
+
+    assert_eq!(json["edits"][0]["type"], "LoadTemplate");
+    assert_eq!(json["templates"][0]["roots"][0]["type"], "Element");
+}
+
+#[test]
+fn attribute_values_keep_their_type() {
+    use dioxus::core::BorrowedAttributeValue;
+
+    for value in [
+        BorrowedAttributeValue::Text("10"),
+        BorrowedAttributeValue::Int(10),
+        BorrowedAttributeValue::Float(10.5),
+        BorrowedAttributeValue::Bool(true),
+        BorrowedAttributeValue::Bytes(&[1, 2, 3]),
+        BorrowedAttributeValue::None,
+    ] {
+        let json = serde_json::to_string(&value).unwrap();
+        let decoded: BorrowedAttributeValue = serde_json::from_str(leak(json)).unwrap();
+        assert_eq!(decoded, value);
+    }
+}
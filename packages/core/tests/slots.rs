@@ -0,0 +1,77 @@
+#![allow(non_snake_case)]
+//! Components can take named slots and render props that borrow from the parent
+
+use dioxus::prelude::*;
+
+#[derive(Props)]
+struct CardProps<'a> {
+    header: Element<'a>,
+    footer: Element<'a>,
+    children: Element<'a>,
+}
+
+fn Card<'a>(cx: Scope<'a, CardProps<'a>>) -> Element<'a> {
+    cx.render(rsx! {
+        div {
+            header { &cx.props.header }
+            &cx.props.children
+            footer { &cx.props.footer }
+        }
+    })
+}
+
+#[test]
+fn named_slots_borrow_from_the_parent() {
+    fn app(cx: Scope) -> Element {
+        let title = String::from("Title");
+
+        cx.render(rsx! {
+            Card {
+                header: render! { h1 { "{title}" } },
+                "body"
+            }
+        })
+    }
+
+    let mut dom = VirtualDom::new(app);
+    _ = dom.rebuild();
+
+    assert_eq!(
+        dioxus_ssr::render(&dom),
+        "<div><header><h1>Title</h1></header>body<footer></footer></div>"
+    );
+}
+
+#[derive(Props)]
+struct ListProps<'a> {
+    count: u32,
+    row: RenderProp<'a, u32>,
+}
+
+fn List<'a>(cx: Scope<'a, ListProps<'a>>) -> Element<'a> {
+    cx.render(rsx! {
+        ul { (0..cx.props.count).map(|i| cx.props.row.call(i)) }
+    })
+}
+
+#[test]
+fn render_props_accept_closures() {
+    fn app(cx: Scope) -> Element {
+        let prefix = "row";
+
+        cx.render(rsx! {
+            List {
+                count: 2,
+                row: move |i| render! { li { key: "{i}", "{prefix} {i}" } }
+            }
+        })
+    }
+
+    let mut dom = VirtualDom::new(app);
+    _ = dom.rebuild();
+
+    assert_eq!(
+        dioxus_ssr::render(&dom),
+        "<ul><li>row 0</li><li>row 1</li></ul>"
+    );
+}
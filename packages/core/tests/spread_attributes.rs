@@ -0,0 +1,125 @@
+#![allow(non_snake_case)]
+//! Attributes spread onto an element with `..attributes` are merged with the ones written out on it
+
+use dioxus::core::{BorrowedAttributeValue, ElementId, Mutation::*};
+use dioxus::prelude::*;
+
+#[derive(Props, PartialEq)]
+struct ButtonProps {
+    attributes: SpreadAttributes,
+}
+
+fn Button(cx: Scope<ButtonProps>) -> Element {
+    cx.render(rsx! {
+        button { class: "button", ..cx.props.attributes }
+    })
+}
+
+#[test]
+fn explicit_attributes_win() {
+    fn app(cx: Scope) -> Element {
+        let attributes = SpreadAttributes::new()
+            .with("class", "ignored")
+            .with("title", "Submit");
+
+        cx.render(rsx! { Button { attributes: attributes } })
+    }
+
+    let mut dom = VirtualDom::new(app);
+
+    assert_eq!(
+        dom.rebuild().santize().edits,
+        [
+            LoadTemplate { name: "template", index: 0, id: ElementId(1) },
+            SetAttribute {
+                name: "title",
+                value: BorrowedAttributeValue::Text("Submit"),
+                id: ElementId(1),
+                ns: None,
+            },
+            AppendChildren { id: ElementId(0), m: 1 },
+        ]
+    );
+
+    assert_eq!(
+        dioxus_ssr::render(&dom),
+        "<button class=\"button\" title=\"Submit\"></button>"
+    );
+}
+
+#[test]
+fn only_changed_attributes_are_sent() {
+    fn app(cx: Scope) -> Element {
+        let attributes: SpreadAttributes = match cx.generation() {
+            0 => vec![("id", "submit"), ("title", "Submit")]
+                .into_iter()
+                .collect(),
+            _ => vec![("id", "submit"), ("role", "button")]
+                .into_iter()
+                .collect(),
+        };
+
+        cx.render(rsx! { div { ..attributes } })
+    }
+
+    let mut dom = VirtualDom::new(app);
+    _ = dom.rebuild();
+
+    dom.mark_dirty(ScopeId::ROOT);
+    assert_eq!(
+        dom.render_immediate().santize().edits,
+        [
+            SetAttribute {
+                name: "role",
+                value: BorrowedAttributeValue::Text("button"),
+                id: ElementId(1),
+                ns: None,
+            },
+            SetAttribute {
+                name: "title",
+                value: BorrowedAttributeValue::None,
+                id: ElementId(1),
+                ns: None,
+            },
+        ]
+    );
+
+    assert_eq!(
+        dioxus_ssr::render(&dom),
+        "<div id=\"submit\" role=\"button\"></div>"
+    );
+}
+
+#[test]
+fn spread_props_keep_explicit_fields_and_children() {
+    #[derive(Props)]
+    struct CardProps<'a> {
+        title: &'a str,
+        subtitle: &'a str,
+        children: Element<'a>,
+    }
+
+    fn Card<'a>(cx: Scope<'a, CardProps<'a>>) -> Element<'a> {
+        cx.render(rsx! {
+            div { h1 { "{cx.props.title}" } h2 { "{cx.props.subtitle}" } &cx.props.children }
+        })
+    }
+
+    fn app(cx: Scope) -> Element {
+        cx.render(rsx! {
+            Card {
+                title: "explicit",
+                ..CardProps { title: "spread", subtitle: "spread", children: None },
+                "body"
+            }
+        })
+    }
+
+    let mut dom = VirtualDom::new(app);
+    _ = dom.rebuild();
+
+    assert_eq!(
+        dioxus_ssr::render(&dom),
+        "<div><h1>explicit</h1><h2>spread</h2>body</div>"
+    );
+}
@@ -0,0 +1,62 @@
+#![allow(non_snake_case)]
+
+use dioxus::prelude::*;
+use dioxus_core::{RenderReturn, StructureHasher};
+
+fn checksum(dom: &VirtualDom) -> u64 {
+    match dom.base_scope().root_node() {
+        RenderReturn::Ready(node) => node.structure_checksum(dom),
+        RenderReturn::Aborted(_) => panic!("root should render"),
+    }
+}
+
+#[test]
+fn identical_trees_have_identical_checksums() {
+    fn app(cx: Scope) -> Element {
+        let name = "world";
+        cx.render(rsx! {
+            div { class: "greeting",
+                "hello {name}"
+                Child {}
+            }
+        })
+    }
+
+    fn Child(cx: Scope) -> Element {
+        cx.render(rsx! { span { id: "child", onclick: |_| {} } })
+    }
+
+    let mut first = VirtualDom::new(app);
+    _ = first.rebuild();
+    let mut second = VirtualDom::new(app);
+    _ = second.rebuild();
+
+    assert_eq!(checksum(&first), checksum(&second));
+
+    // A renderer walking the same tree gets the same checksum, listeners aren't part of the structure
+    let mut hasher = StructureHasher::new();
+    hasher.enter_element("div", [("class", "greeting")]);
+    hasher.text("hello world");
+    hasher.enter_element("span", [("id", "child")]);
+    hasher.exit_element();
+    hasher.exit_element();
+
+    assert_eq!(checksum(&first), hasher.finish());
+}
+
+#[test]
+fn text_changes_change_the_checksum() {
+    fn app(cx: Scope) -> Element {
+        let generation = cx.generation();
+        cx.render(rsx! { div { "{generation}" } })
+    }
+
+    let mut dom = VirtualDom::new(app);
+    _ = dom.rebuild();
+    let before = checksum(&dom);
+
+    dom.mark_dirty(ScopeId::ROOT);
+    _ = dom.render_immediate();
+
+    assert_ne!(before, checksum(&dom));
+}
@@ -0,0 +1,88 @@
+//! Style maps are diffed property by property instead of replacing the whole style
+
+use dioxus::core::{BorrowedAttributeValue, ElementId, Mutation::*};
+use dioxus::prelude::*;
+
+fn app(cx: Scope) -> Element {
+    let color = match cx.generation() {
+        0 => "red",
+        _ => "blue",
+    };
+
+    cx.render(rsx! {
+        div { style: StyleMap([("color", color), ("margin", "4px"), ("padding", "2px")]) }
+    })
+}
+
+#[test]
+fn changing_one_property_sends_one_mutation() {
+    let mut dom = VirtualDom::new(app);
+
+    assert_eq!(
+        dom.rebuild().santize().edits,
+        [
+            LoadTemplate { name: "template", index: 0, id: ElementId(1) },
+            SetAttribute {
+                name: "color",
+                value: BorrowedAttributeValue::Text("red"),
+                id: ElementId(1),
+                ns: Some("style"),
+            },
+            SetAttribute {
+                name: "margin",
+                value: BorrowedAttributeValue::Text("4px"),
+                id: ElementId(1),
+                ns: Some("style"),
+            },
+            SetAttribute {
+                name: "padding",
+                value: BorrowedAttributeValue::Text("2px"),
+                id: ElementId(1),
+                ns: Some("style"),
+            },
+            AppendChildren { id: ElementId(0), m: 1 },
+        ]
+    );
+
+    dom.mark_dirty(ScopeId::ROOT);
+    assert_eq!(
+        dom.render_immediate().santize().edits,
+        [SetAttribute {
+            name: "color",
+            value: BorrowedAttributeValue::Text("blue"),
+            ns: Some("style"),
+            id: ElementId(1),
+        }]
+    );
+
+    assert_eq!(
+        dioxus_ssr::render(&dom),
+        "<div style=\"color:blue;margin:4px;padding:2px;\"></div>"
+    );
+}
+
+#[test]
+fn removed_properties_are_cleared() {
+    fn app(cx: Scope) -> Element {
+        let style: &[(&str, &str)] = match cx.generation() {
+            0 => &[("color", "red"), ("margin", "4px")],
+            _ => &[("color", "red")],
+        };
+
+        cx.render(rsx! { div { style: StyleMap(style) } })
+    }
+
+    let mut dom = VirtualDom::new(app);
+    _ = dom.rebuild();
+
+    dom.mark_dirty(ScopeId::ROOT);
+    assert_eq!(
+        dom.render_immediate().santize().edits,
+        [SetAttribute {
+            name: "margin",
+            value: BorrowedAttributeValue::None,
+            ns: Some("style"),
+            id: ElementId(1),
+        }]
+    );
+}
@@ -1,4 +1,6 @@
+use dioxus::core::MockDom;
 use dioxus::prelude::*;
+use std::{cell::Cell, rc::Rc};
 
 #[test]
 fn it_works() {
@@ -41,3 +43,61 @@ fn suspended_child(cx: Scope) -> Element {
 
     render!("child")
 }
+
+thread_local! {
+    static PROFILE_MOUNTS: Cell<usize> = const { Cell::new(0) };
+}
+
+fn boundary_app(cx: Scope) -> Element {
+    cx.render(rsx!(
+        div {
+            SuspenseBoundary {
+                fallback: render!("loading"),
+                user_profile {}
+            }
+        }
+    ))
+}
+
+// Suspends until its task is done, without marking itself dirty
+fn user_profile(cx: Scope) -> Element {
+    let ready = cx.use_hook(|| Rc::new(Cell::new(false))).clone();
+    cx.use_hook(|| {
+        PROFILE_MOUNTS.with(|mounts| mounts.set(mounts.get() + 1));
+        let ready = ready.clone();
+        cx.spawn(async move {
+            tokio::task::yield_now().await;
+            ready.set(true);
+        })
+    });
+
+    if !ready.get() {
+        cx.suspend()?;
+    }
+
+    render!("profile")
+}
+
+#[test]
+fn boundary_shows_fallback_until_resolved() {
+    tokio::runtime::Builder::new_current_thread()
+        .build()
+        .unwrap()
+        .block_on(async {
+            let mut dom = VirtualDom::new(boundary_app);
+            let renderer = MockDom::new();
+            renderer.apply(&dom.rebuild());
+
+            // The boundary renders again with the fallback once the profile suspends
+            renderer.apply(&dom.render_immediate());
+            assert_eq!(renderer.to_string(), "<div>loading<!--placeholder--></div>");
+
+            // The profile is rendered again when its task finishes
+            dom.wait_for_work().await;
+            renderer.apply(&dom.render_immediate());
+            assert_eq!(renderer.to_string(), "<div><!--placeholder-->profile</div>");
+
+            // The profile stayed mounted the whole time
+            assert_eq!(PROFILE_MOUNTS.with(Cell::get), 1);
+        });
+}
@@ -44,3 +44,67 @@ fn app(cx: Scope) -> Element {
 
     cx.render(rsx!(()))
 }
+
+thread_local! {
+    static SHOW_CHILD: std::cell::Cell<bool> = const { std::cell::Cell::new(true) };
+    static DROPPED: std::cell::RefCell<Vec<&'static str>> = const { std::cell::RefCell::new(Vec::new()) };
+}
+
+/// Records when the task holding it is dropped
+struct DropGuard(&'static str);
+
+impl Drop for DropGuard {
+    fn drop(&mut self) {
+        DROPPED.with(|dropped| dropped.borrow_mut().push(self.0));
+    }
+}
+
+fn task(name: &'static str) -> impl std::future::Future<Output = ()> {
+    let guard = DropGuard(name);
+    async move {
+        let _guard = guard;
+        std::future::pending::<()>().await
+    }
+}
+
+fn take_dropped() -> Vec<&'static str> {
+    DROPPED.with(|dropped| dropped.borrow_mut().drain(..).collect())
+}
+
+fn parent(cx: Scope) -> Element {
+    let show = SHOW_CHILD.with(|show| show.get());
+    render! {
+        if show {
+            rsx! { Spawner {} }
+        }
+    }
+}
+
+#[allow(non_snake_case)]
+fn Spawner(cx: Scope) -> Element {
+    cx.use_hook(|| {
+        cx.spawn(task("scoped"));
+        cx.spawn_forever(task("forever"));
+        let cancelled = cx.spawn(task("cancelled"));
+        cx.remove_future(cancelled);
+    });
+    render! { "spawner" }
+}
+
+#[test]
+fn forever_tasks_outlive_their_scope() {
+    let mut dom = VirtualDom::new(parent);
+    _ = dom.rebuild();
+
+    // Cancelling a task drops it right away
+    assert_eq!(take_dropped(), ["cancelled"]);
+
+    // Unmounting the scope only drops the tasks bound to it
+    SHOW_CHILD.with(|show| show.set(false));
+    dom.mark_dirty(ScopeId::ROOT);
+    _ = dom.render_immediate();
+    assert_eq!(take_dropped(), ["scoped"]);
+
+    drop(dom);
+    assert_eq!(take_dropped(), ["forever"]);
+}
@@ -0,0 +1,56 @@
+//! Templates are registered once, even if their name is allocated more than once
+
+use dioxus::prelude::*;
+use dioxus_core::{Template, TemplateNode};
+use std::cell::Cell;
+
+static ROW: Template = Template {
+    name: "template_identity.rs:1:1:0",
+    roots: &[TemplateNode::Element { tag: "li", namespace: None, attrs: &[], children: &[] }],
+    node_paths: &[],
+    attr_paths: &[],
+    ..Template::EMPTY
+};
+
+thread_local! {
+    // The same name in another allocation, like a copy of the template from another codegen unit
+    static COPIED_NAME: &'static str = Box::leak(ROW.name.to_string().into_boxed_str());
+}
+
+fn row<'a>(cx: &'a ScopeState, template: Template<'static>) -> VNode<'a> {
+    VNode {
+        key: None,
+        parent: Default::default(),
+        template: Cell::new(template),
+        root_ids: dioxus_core::exports::bumpalo::collections::Vec::new_in(cx.bump()).into(),
+        dynamic_nodes: &[],
+        dynamic_attrs: &[],
+    }
+}
+
+fn app(cx: Scope) -> Element {
+    let copy = Template { name: COPIED_NAME.with(|name| *name), ..ROW };
+    render! {
+        (0..3).map(|_| row(cx, ROW)),
+        row(cx, copy)
+    }
+}
+
+#[test]
+fn templates_are_registered_once() {
+    let mut dom = VirtualDom::new(app);
+    let mutations = dom.rebuild();
+    assert_eq!(
+        mutations
+            .templates
+            .iter()
+            .filter(|template| template.name == ROW.name)
+            .count(),
+        1
+    );
+    drop(mutations);
+
+    // Rendering the rows again doesn't register them again
+    dom.mark_dirty(ScopeId::ROOT);
+    assert!(dom.render_immediate().templates.is_empty());
+}
@@ -0,0 +1,78 @@
+//! The VirtualDom counts the mounted nodes of each template, so templates nothing uses can be evicted
+
+use dioxus::core::{DynamicNode, RenderReturn, TemplateId};
+use dioxus::prelude::*;
+
+fn app(cx: Scope) -> Element {
+    let count = match cx.generation() {
+        0 => 3,
+        1 => 1,
+        _ => 0,
+    };
+
+    cx.render(rsx! {
+        ul {
+            (0..count).map(|i| rsx!(li { key: "{i}", "item {i}" }))
+        }
+    })
+}
+
+fn item_template(dom: &VirtualDom) -> TemplateId {
+    let node = match dom.base_scope().try_root_node() {
+        Some(RenderReturn::Ready(node)) => node,
+        _ => unreachable!(),
+    };
+    match &node.dynamic_nodes[0] {
+        DynamicNode::Fragment(items) => items[0].template.get().name,
+        _ => panic!("the list is empty"),
+    }
+}
+
+#[test]
+fn usage_returns_to_zero_when_unmounted() {
+    let mut dom = VirtualDom::new(app);
+    _ = dom.rebuild();
+
+    let item = item_template(&dom);
+    assert_eq!(dom.template_usage(item), 3);
+
+    dom.mark_dirty(ScopeId(0));
+    _ = dom.render_immediate();
+    assert_eq!(dom.template_usage(item), 1);
+
+    // Nothing is evicted while an item is still mounted
+    assert!(!dom.evict_unused().contains(&item));
+    assert!(dom.registered_templates().contains(item));
+
+    dom.mark_dirty(ScopeId(0));
+    _ = dom.render_immediate();
+    assert_eq!(dom.template_usage(item), 0);
+
+    assert_eq!(dom.evict_unused(), [item]);
+    assert!(!dom.registered_templates().contains(item));
+}
+
+#[test]
+fn evicted_templates_are_sent_again() {
+    fn app(cx: Scope) -> Element {
+        let show = cx.generation() != 1;
+        cx.render(rsx! {
+            div {
+                if show {
+                    rsx! { p { "back again" } }
+                }
+            }
+        })
+    }
+
+    let mut dom = VirtualDom::new(app);
+    let sent = dom.rebuild().templates.len();
+    assert_eq!(sent, 2);
+
+    dom.mark_dirty(ScopeId(0));
+    _ = dom.render_immediate();
+    assert_eq!(dom.evict_unused().len(), 1);
+
+    dom.mark_dirty(ScopeId(0));
+    assert_eq!(dom.render_immediate().templates.len(), 1);
+}
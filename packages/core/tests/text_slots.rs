@@ -0,0 +1,105 @@
+#![allow(non_snake_case)]
+//! Text slots record the values they read, so a change to a value re-evaluates only the slots that read it
+
+use std::cell::Cell;
+
+use dioxus::core::{ElementId, Mutation, ValueId};
+use dioxus::prelude::*;
+
+const NAME: ValueId = ValueId(0);
+const COUNT: ValueId = ValueId(1);
+
+thread_local! {
+    static NAME_VALUE: Cell<&'static str> = const { Cell::new("world") };
+    static COUNT_VALUE: Cell<usize> = const { Cell::new(0) };
+
+    static RENDERS: Cell<usize> = const { Cell::new(0) };
+    static NAME_EVALUATIONS: Cell<usize> = const { Cell::new(0) };
+    static COUNT_EVALUATIONS: Cell<usize> = const { Cell::new(0) };
+}
+
+fn bump(counter: &'static std::thread::LocalKey<Cell<usize>>) {
+    counter.with(|counter| counter.set(counter.get() + 1));
+}
+
+fn get(counter: &'static std::thread::LocalKey<Cell<usize>>) -> usize {
+    counter.with(Cell::get)
+}
+
+fn app(cx: Scope) -> Element {
+    bump(&RENDERS);
+    cx.render(rsx! {
+        h1 {
+            cx.text_slot(|cx| {
+                bump(&NAME_EVALUATIONS);
+                cx.read_value(NAME);
+                format!("hello {}", NAME_VALUE.with(Cell::get))
+            })
+        }
+        p {
+            cx.text_slot(|cx| {
+                bump(&COUNT_EVALUATIONS);
+                cx.read_value(COUNT);
+                format!("count {}", COUNT_VALUE.with(Cell::get))
+            })
+        }
+    })
+}
+
+#[test]
+fn changing_a_value_re_evaluates_only_its_slot() {
+    let mut dom = VirtualDom::new(app);
+    _ = dom.rebuild();
+
+    assert_eq!((get(&NAME_EVALUATIONS), get(&COUNT_EVALUATIONS)), (1, 1));
+    assert_eq!(dom.affected_slots(NAME), [(ScopeId(0), 0)]);
+    assert_eq!(dom.affected_slots(COUNT), [(ScopeId(0), 1)]);
+
+    // The component itself doesn't depend on either value
+    assert!(dom.affected_scopes(COUNT).is_empty());
+
+    COUNT_VALUE.with(|count| count.set(1));
+    assert_eq!(
+        dom.render_slots(COUNT).edits,
+        [Mutation::SetText { value: "count 1", id: ElementId(4) }]
+    );
+
+    assert_eq!(get(&RENDERS), 1);
+    assert_eq!((get(&NAME_EVALUATIONS), get(&COUNT_EVALUATIONS)), (1, 2));
+
+    // A slot whose text is the same is evaluated, but nothing is sent
+    assert!(dom.render_slots(COUNT).edits.is_empty());
+    assert_eq!(get(&COUNT_EVALUATIONS), 3);
+}
+
+#[test]
+fn full_render_after_a_slot_update_sends_the_text_again() {
+    fn app(cx: Scope) -> Element {
+        cx.render(rsx! {
+            div {
+                cx.text_slot(|cx| {
+                    cx.read_value(COUNT);
+                    match COUNT_VALUE.with(Cell::get) {
+                        0 => "idle".to_string(),
+                        count => format!("count {}", count),
+                    }
+                })
+            }
+        })
+    }
+
+    let mut dom = VirtualDom::new(app);
+    _ = dom.rebuild();
+
+    COUNT_VALUE.with(|count| count.set(5));
+    let slot_edits = dom.render_slots(COUNT).edits.len();
+    assert_eq!(slot_edits, 1);
+
+    // The text node in the VNode still says "idle", but the renderer doesn't, so the diff can't skip the update
+    COUNT_VALUE.with(|count| count.set(0));
+    dom.mark_dirty(ScopeId(0));
+    assert_eq!(
+        dom.render_immediate().edits,
+        [Mutation::SetText { value: "idle", id: ElementId(2) }]
+    );
+}
@@ -0,0 +1,48 @@
+//! Re-rendering a form never resets the values users typed into uncontrolled fields
+
+use dioxus::core::Mutation;
+use dioxus::prelude::*;
+use dioxus_core::MockDom;
+
+fn app(cx: Scope) -> Element {
+    let saves = cx.generation();
+    cx.render(rsx! {
+        form {
+            p { "Saved {saves} times" }
+            input { name: "title", value: "untitled", oninput: move |_| {} }
+        }
+    })
+}
+
+#[test]
+fn user_values_survive_parent_renders() {
+    let mut dom = VirtualDom::new(app);
+    let renderer = MockDom::new();
+
+    let edits = dom.rebuild();
+    renderer.apply(&edits);
+    let input = edits
+        .edits
+        .iter()
+        .find_map(|edit| match edit {
+            Mutation::NewEventListener { name: "input", id } => Some(*id),
+            _ => None,
+        })
+        .unwrap();
+    drop(edits);
+
+    renderer.set_user_value(input, "My first post");
+
+    for _ in 0..3 {
+        dom.mark_dirty(ScopeId::ROOT);
+        let edits = dom.render_immediate();
+        assert!(!edits
+            .edits
+            .iter()
+            .any(|edit| matches!(edit, Mutation::SetAttribute { name: "value", .. })));
+        renderer.apply(&edits);
+    }
+
+    assert_eq!(renderer.attribute(input, "value"), Some("My first post"));
+    assert!(renderer.to_string().contains("Saved 3 times"));
+}
@@ -0,0 +1,52 @@
+//! Volatile attributes are rewritten every render, but stay in source order with the rest of the attributes
+
+use dioxus::core::{Mutation, Mutation::*};
+use dioxus::prelude::*;
+
+fn app(cx: Scope) -> Element {
+    let generation = cx.generation();
+    let (text, cursor, direction, name) = ("fixed", 0, "forward", "field");
+
+    // `value` and `selection_direction` are volatile, the rest are only sent when they change
+    cx.render(rsx! {
+        input {
+            class: "input-{generation}",
+            value: "{text}",
+            name: "{name}",
+            "data-generation": "{generation}",
+            selection_direction: "{direction}",
+            selection_start: cursor,
+        }
+    })
+}
+
+fn attribute_names<'a>(edits: &[Mutation<'a>]) -> Vec<&'a str> {
+    edits
+        .iter()
+        .filter_map(|edit| match edit {
+            SetAttribute { name, .. } => Some(*name),
+            _ => None,
+        })
+        .collect()
+}
+
+#[test]
+fn volatile_attributes_keep_source_order() {
+    let mut dom = VirtualDom::new(app);
+    _ = dom.rebuild();
+
+    for _ in 0..3 {
+        dom.mark_dirty(ScopeId::ROOT);
+        assert_eq!(
+            attribute_names(&dom.render_immediate().edits),
+            [
+                "class",
+                "value",
+                "data-generation",
+                // Selection attributes are always sent last, in source order among themselves
+                "selection_direction",
+                "selection_start",
+            ]
+        );
+    }
+}
@@ -0,0 +1,184 @@
+//! Renderers can take edits one at a time through the WriteMutations trait
+
+use dioxus::prelude::*;
+use dioxus_core::{BorrowedAttributeValue, ElementId, TemplatePath, WriteMutations};
+use std::{cell::Cell, rc::Rc};
+
+thread_local! {
+    // How many edits every Log has been handed so far
+    static WRITTEN: Cell<usize> = const { Cell::new(0) };
+}
+
+/// Records every call as a line of text so two renders can be compared
+#[derive(Default)]
+struct Log(Vec<String>);
+
+impl Log {
+    fn push(&mut self, line: String) {
+        WRITTEN.with(|written| written.set(written.get() + 1));
+        self.0.push(line);
+    }
+}
+
+impl WriteMutations<'_> for Log {
+    fn register_template(&mut self, template: Template) {
+        self.push(format!("template {}", template.roots.len()));
+    }
+
+    fn append_children(&mut self, id: ElementId, m: usize) {
+        self.push(format!("append {:?} {}", id, m));
+    }
+
+    fn assign_id(&mut self, path: TemplatePath<'static>, id: ElementId) {
+        self.push(format!("assign {:?} {:?}", path, id));
+    }
+
+    fn create_placeholder(&mut self, id: ElementId) {
+        self.push(format!("placeholder {:?}", id));
+    }
+
+    fn create_text_node(&mut self, value: &str, id: ElementId) {
+        self.push(format!("text {} {:?}", value, id));
+    }
+
+    fn hydrate_text(&mut self, path: TemplatePath<'static>, value: &str, id: ElementId) {
+        self.push(format!("hydrate {:?} {} {:?}", path, value, id));
+    }
+
+    fn load_template(&mut self, _name: &'static str, index: usize, id: ElementId) {
+        self.push(format!("load {} {:?}", index, id));
+    }
+
+    fn replace_with(&mut self, id: ElementId, m: usize) {
+        self.push(format!("replace {:?} {}", id, m));
+    }
+
+    fn replace_placeholder(&mut self, path: TemplatePath<'static>, m: usize) {
+        self.push(format!("replace placeholder {:?} {}", path, m));
+    }
+
+    fn insert_after(&mut self, id: ElementId, m: usize) {
+        self.push(format!("insert after {:?} {}", id, m));
+    }
+
+    fn insert_before(&mut self, id: ElementId, m: usize) {
+        self.push(format!("insert before {:?} {}", id, m));
+    }
+
+    fn set_attribute(
+        &mut self,
+        name: &str,
+        _ns: Option<&str>,
+        value: &BorrowedAttributeValue,
+        id: ElementId,
+    ) {
+        self.push(format!("attr {} {:?} {:?}", name, value, id));
+    }
+
+    fn set_text(&mut self, value: &str, id: ElementId) {
+        self.push(format!("set text {} {:?}", value, id));
+    }
+
+    fn new_event_listener(&mut self, name: &str, id: ElementId) {
+        self.push(format!("listen {} {:?}", name, id));
+    }
+
+    fn remove_event_listener(&mut self, name: &str, id: ElementId) {
+        self.push(format!("unlisten {} {:?}", name, id));
+    }
+
+    fn remove(&mut self, id: ElementId) {
+        self.push(format!("remove {:?}", id));
+    }
+
+    fn push_root(&mut self, id: ElementId) {
+        self.push(format!("push {:?}", id));
+    }
+
+    fn append_to_anchor(&mut self, anchor: &str, m: usize) {
+        self.push(format!("anchor {} {}", anchor, m));
+    }
+}
+
+fn app(cx: Scope) -> Element {
+    let mut count = use_state(cx, || 0);
+    render! {
+        div { class: "count-{count}", onclick: move |_| count += 1,
+            "{count}"
+            (0..**count).map(|i| rsx! { span { key: "{i}", "{i}" } })
+        }
+    }
+}
+
+/// Split the templates from the edits. Templates are written right before they are first loaded, while Mutations
+/// keeps them in a separate list
+fn split(log: Log) -> (Vec<String>, Vec<String>) {
+    log.0
+        .into_iter()
+        .partition(|line| line.starts_with("template"))
+}
+
+#[test]
+fn writing_edits_matches_mutations() {
+    let mut streamed = VirtualDom::new(app);
+    let mut buffered = VirtualDom::new(app);
+
+    let mut log = Log::default();
+    streamed.rebuild_to(&mut log);
+    let mut expected = Log::default();
+    buffered.rebuild().write_to(&mut expected);
+    assert!(!log.0.is_empty());
+    assert_eq!(split(log), split(expected));
+
+    for _ in 0..3 {
+        streamed.handle_event("click", Rc::new(MouseData::default()), ElementId(1), true);
+        buffered.handle_event("click", Rc::new(MouseData::default()), ElementId(1), true);
+
+        let mut log = Log::default();
+        streamed.render_immediate_to(&mut log);
+        let mut expected = Log::default();
+        buffered.render_immediate().write_to(&mut expected);
+        assert!(log.0.iter().any(|line| line.starts_with("set text")));
+        assert_eq!(split(log), split(expected));
+    }
+}
+
+#[test]
+fn edits_are_written_while_diffing() {
+    thread_local! {
+        static SEEN_BY_CHILD: Cell<Option<usize>> = const { Cell::new(None) };
+    }
+
+    #[allow(non_snake_case)]
+    fn Parent(cx: Scope) -> Element {
+        render! {
+            div { id: "parent",
+                Child {}
+            }
+        }
+    }
+
+    #[allow(non_snake_case)]
+    fn Child(cx: Scope) -> Element {
+        SEEN_BY_CHILD.with(|seen| seen.set(Some(WRITTEN.with(|written| written.get()))));
+        render! { "child" }
+    }
+
+    // Nothing reaches the renderer until the whole tree is diffed when the edits are collected into Mutations
+    let mut buffered = VirtualDom::new(Parent);
+    let start = WRITTEN.with(|written| written.get());
+    let mutations = buffered.rebuild();
+    assert_eq!(SEEN_BY_CHILD.with(|seen| seen.get()), Some(start));
+    mutations.write_to(&mut Log::default());
+
+    // When writing straight to the renderer, the parent is loaded before the child has even rendered
+    let mut streamed = VirtualDom::new(Parent);
+    let mut log = Log::default();
+    let start = WRITTEN.with(|written| written.get());
+    streamed.rebuild_to(&mut log);
+    let seen = SEEN_BY_CHILD.with(|seen| seen.get()).unwrap();
+    assert!(seen > start);
+    assert!(log.0[..seen - start]
+        .iter()
+        .any(|line| line.starts_with("load")));
+}
@@ -36,6 +36,7 @@ pub struct Config {
     pub(crate) root_name: String,
     pub(crate) background_color: Option<(u8, u8, u8, u8)>,
     pub(crate) last_window_close_behaviour: WindowCloseBehaviour,
+    pub(crate) scroll_restoration: Vec<String>,
 }
 
 type DropHandler = Box<dyn Fn(&Window, FileDropEvent) -> bool>;
@@ -65,6 +66,7 @@ impl Config {
             root_name: "main".to_string(),
             background_color: None,
             last_window_close_behaviour: WindowCloseBehaviour::LastWindowExitsApp,
+            scroll_restoration: Vec::new(),
         }
     }
 
@@ -166,6 +168,14 @@ impl Config {
         self.background_color = Some(color);
         self
     }
+
+    /// Restore the scroll position of keyed elements with this key when they are mounted again.
+    ///
+    /// See [`dioxus_core::VirtualDom::register_scroll_restoration`] for more details.
+    pub fn with_scroll_restoration(mut self, key: impl Into<String>) -> Self {
+        self.scroll_restoration.push(key.into());
+        self
+    }
 }
 
 impl Default for Config {
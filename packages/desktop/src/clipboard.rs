@@ -0,0 +1,30 @@
+use async_trait::async_trait;
+use dioxus_core::ScopeState;
+use dioxus_html::prelude::{ClipboardError, ClipboardProvider};
+use std::rc::Rc;
+use wry::application::clipboard::Clipboard;
+
+/// Provides the DesktopClipboardProvider through [`cx.provide_context`].
+pub fn init_clipboard(cx: &ScopeState) {
+    let provider: Rc<dyn ClipboardProvider> = Rc::new(DesktopClipboardProvider);
+    cx.provide_context(provider);
+}
+
+/// Represents the desktop-target's clipboard, which is the clipboard of the operating system.
+///
+/// The clipboard is only accessed from the main thread, where the VirtualDom runs.
+pub struct DesktopClipboardProvider;
+
+#[async_trait(?Send)]
+impl ClipboardProvider for DesktopClipboardProvider {
+    async fn read_text(&self) -> Result<String, ClipboardError> {
+        Clipboard::new()
+            .read_text()
+            .ok_or_else(|| ClipboardError::Failed("the clipboard did not contain text".into()))
+    }
+
+    async fn write_text(&self, text: String) -> Result<(), ClipboardError> {
+        Clipboard::new().write_text(text);
+        Ok(())
+    }
+}
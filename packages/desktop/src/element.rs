@@ -1,7 +1,15 @@
 use dioxus_core::ElementId;
-use dioxus_html::{geometry::euclid::Rect, MountedResult, RenderedElementBacking};
+use dioxus_html::{
+    geometry::euclid::{Rect, Size2D},
+    IntersectionData, IntersectionObserverBacking, MountedResult, RenderedElementBacking,
+    ResizeObserverBacking,
+};
+use serde::de::DeserializeOwned;
 
-use crate::{desktop_context::DesktopContext, query::QueryEngine};
+use crate::{
+    desktop_context::DesktopContext,
+    query::{Query, QueryEngine, QueryError},
+};
 
 /// A mounted element passed to onmounted events
 pub struct DesktopElement {
@@ -102,6 +110,113 @@ impl RenderedElementBacking for DesktopElement {
             }
         })
     }
+
+    fn observe_intersection(
+        &self,
+    ) -> std::pin::Pin<
+        Box<
+            dyn futures_util::Future<
+                Output = dioxus_html::MountedResult<dioxus_html::IntersectionObserver>,
+            >,
+        >,
+    > {
+        let fut = self.observe("ObserveIntersection");
+        Box::pin(async move { fut.await.map(dioxus_html::IntersectionObserver::new) })
+    }
+
+    fn observe_resize(
+        &self,
+    ) -> std::pin::Pin<
+        Box<
+            dyn futures_util::Future<
+                Output = dioxus_html::MountedResult<dioxus_html::ResizeObserver>,
+            >,
+        >,
+    > {
+        let fut = self.observe("ObserveResize");
+        Box::pin(async move { fut.await.map(dioxus_html::ResizeObserver::new) })
+    }
+}
+
+impl DesktopElement {
+    /// Start one of the interpreter's observers on this element
+    fn observe(
+        &self,
+        method: &str,
+    ) -> impl futures_util::Future<Output = dioxus_html::MountedResult<DesktopObserver>> {
+        let script = format!(
+            "return window.interpreter.{method}({}, (data) => dioxus.send(data));",
+            self.id.0
+        );
+
+        let mut query = self
+            .query
+            .new_query::<Option<u64>>(&script, self.webview.clone());
+        let webview = self.webview.clone();
+
+        async move {
+            let handle = query.result().await.and_then(|handle| {
+                serde_json::from_value::<Option<u64>>(handle).map_err(QueryError::Deserialize)
+            });
+
+            match handle {
+                Ok(Some(handle)) => Ok(DesktopObserver {
+                    handle,
+                    query,
+                    webview,
+                }),
+                Ok(None) => MountedResult::Err(dioxus_html::MountedError::OperationFailed(
+                    Box::new(DesktopQueryError::FailedToQuery),
+                )),
+                Err(err) => {
+                    MountedResult::Err(dioxus_html::MountedError::OperationFailed(Box::new(err)))
+                }
+            }
+        }
+    }
+}
+
+/// Receives the changes an observer in the interpreter reports through a query that stays open
+struct DesktopObserver {
+    handle: u64,
+    query: Query<Option<u64>>,
+    webview: DesktopContext,
+}
+
+impl DesktopObserver {
+    async fn next<T: DeserializeOwned>(&mut self) -> Option<T> {
+        loop {
+            let data = self.query.recv().await.ok()?;
+            if let Ok(data) = serde_json::from_value(data) {
+                return Some(data);
+            }
+        }
+    }
+}
+
+impl IntersectionObserverBacking for DesktopObserver {
+    fn next(
+        &mut self,
+    ) -> std::pin::Pin<Box<dyn futures_util::Future<Output = Option<IntersectionData>> + '_>> {
+        Box::pin(DesktopObserver::next(self))
+    }
+}
+
+impl ResizeObserverBacking for DesktopObserver {
+    fn next(
+        &mut self,
+    ) -> std::pin::Pin<Box<dyn futures_util::Future<Output = Option<Size2D<f64, f64>>> + '_>> {
+        Box::pin(DesktopObserver::next(self))
+    }
+}
+
+impl Drop for DesktopObserver {
+    fn drop(&mut self) {
+        _ = self.webview.webview.evaluate_script(&format!(
+            "window.interpreter.StopObserving({});",
+            self.handle
+        ));
+    }
 }
 
 #[derive(Debug)]
@@ -4,6 +4,7 @@
 #![deny(missing_docs)]
 
 mod cfg;
+mod clipboard;
 mod desktop_context;
 mod element;
 mod escape;
@@ -21,6 +22,7 @@ mod mobile_shortcut;
 
 use crate::query::QueryResult;
 pub use cfg::{Config, WindowCloseBehaviour};
+use clipboard::init_clipboard;
 pub use desktop_context::DesktopContext;
 pub use desktop_context::{
     use_window, use_wry_event_handler, DesktopService, WryEventHandler, WryEventHandlerId,
@@ -208,7 +210,10 @@ pub fn launch_with_props<P: 'static>(root: Component<P>, props: P, cfg: Config)
                 let cfg = cfg.take().unwrap();
 
                 // Create a dom
-                let dom = VirtualDom::new_with_props(root, props);
+                let mut dom = VirtualDom::new_with_props(root, props);
+                for key in &cfg.scroll_restoration {
+                    dom.register_scroll_restoration(key.clone());
+                }
 
                 let handler = create_new_window(
                     cfg,
@@ -293,6 +298,16 @@ pub fn launch_with_props<P: 'static>(root: Component<P>, props: P, cfg: Config)
                             DesktopElement::new(element, view.desktop_context.clone(), query);
 
                         Rc::new(MountedData::new(element))
+                    } else if let dioxus_html::EventData::Drag(mut drag) = data {
+                        // attach the real paths of files dropped onto the window
+                        if name == "drop" {
+                            let files = std::mem::take(&mut *view.dropped_files.borrow_mut());
+                            if !files.is_empty() {
+                                drag.files = Some(Arc::new(NativeFileEngine::new(files)));
+                            }
+                        }
+
+                        Rc::new(drag)
                     } else {
                         data.into_any()
                     };
@@ -300,6 +315,8 @@ pub fn launch_with_props<P: 'static>(root: Component<P>, props: P, cfg: Config)
                     view.dom.handle_event(&name, as_any, element, bubbles);
 
                     send_edits(view.dom.render_immediate(), &view.desktop_context.webview);
+
+                    send_head(&mut view.dom, &view.desktop_context.webview);
                 }
 
                 // When the webview sends a query, we need to send it to the query manager which handles dispatching the data to the correct pending query
@@ -323,6 +340,16 @@ pub fn launch_with_props<P: 'static>(root: Component<P>, props: P, cfg: Config)
                 EventData::Ipc(msg) if msg.method() == "initialize" => {
                     let view = webviews.get_mut(&event.1).unwrap();
                     send_edits(view.dom.rebuild(), &view.desktop_context.webview);
+                    send_head(&mut view.dom, &view.desktop_context.webview);
+                }
+
+                // The webview read how far an element was scrolled right before removing it
+                EventData::Ipc(msg) if msg.method() == "report_scroll" => {
+                    if let Ok(report) = serde_json::from_value::<ScrollReport>(msg.params()) {
+                        let view = webviews.get_mut(&event.1).unwrap();
+                        view.dom
+                            .report_scroll(ElementId(report.id), report.x, report.y);
+                    }
                 }
 
                 EventData::Ipc(msg) if msg.method() == "browser_open" => {
@@ -361,6 +388,8 @@ pub fn launch_with_props<P: 'static>(root: Component<P>, props: P, cfg: Config)
                         }
 
                         send_edits(view.dom.render_immediate(), &view.desktop_context.webview);
+
+                        send_head(&mut view.dom, &view.desktop_context.webview);
                     }
                 }
 
@@ -381,7 +410,7 @@ fn create_new_window(
     event_handlers: &WindowEventHandlers,
     shortcut_manager: ShortcutRegistry,
 ) -> WebviewHandler {
-    let (webview, web_context) = webview::build(&mut cfg, event_loop, proxy.clone());
+    let (webview, web_context, dropped_files) = webview::build(&mut cfg, event_loop, proxy.clone());
     let desktop_context = Rc::from(DesktopService::new(
         webview,
         proxy.clone(),
@@ -391,17 +420,20 @@ fn create_new_window(
         shortcut_manager,
     ));
 
-    let cx = dom.base_scope();
-    cx.provide_context(desktop_context.clone());
+    dom.provide_root_context(desktop_context.clone());
 
     // Init eval
-    init_eval(cx);
+    init_eval(dom.base_scope());
+
+    // Init clipboard
+    init_clipboard(dom.base_scope());
 
     WebviewHandler {
         // We want to poll the virtualdom and the event loop at the same time, so the waker will be connected to both
         waker: waker::tao_waker(proxy, desktop_context.webview.window().id()),
         desktop_context,
         dom,
+        dropped_files,
         _web_context: web_context,
     }
 }
@@ -411,6 +443,9 @@ struct WebviewHandler {
     desktop_context: DesktopContext,
     waker: Waker,
 
+    // The paths of the files last dropped onto the window, attached to the next drop event
+    dropped_files: webview::DroppedFiles,
+
     // Wry assumes the webcontext is alive for the lifetime of the webview.
     // We need to keep the webcontext alive, otherwise the webview will crash
     _web_context: WebContext,
@@ -436,10 +471,19 @@ fn poll_vdom(view: &mut WebviewHandler) {
         }
 
         send_edits(view.dom.render_immediate(), &view.desktop_context.webview);
+
+        send_head(&mut view.dom, &view.desktop_context.webview);
     }
 }
 
 /// Send a list of mutations to the webview
+#[derive(serde::Deserialize)]
+struct ScrollReport {
+    id: usize,
+    x: f64,
+    y: f64,
+}
+
 fn send_edits(edits: Mutations, webview: &WebView) {
     let serialized = serde_json::to_string(&edits).unwrap();
 
@@ -447,6 +491,37 @@ fn send_edits(edits: Mutations, webview: &WebView) {
     _ = webview.evaluate_script(&format!("window.interpreter.handleEdits({serialized})"));
 }
 
+/// Replace the elements in the head of the page if components changed them since they were last sent
+fn send_head(dom: &mut VirtualDom, webview: &WebView) {
+    let changes = match dom.take_head_changes() {
+        Some(changes) => changes,
+        None => return,
+    };
+    let elements: Vec<_> = changes
+        .iter()
+        .map(|element| serde_json::json!([element.tag, element.attributes, element.text]))
+        .collect();
+
+    _ = webview.evaluate_script(&format!(
+        r#"(function(elements) {{
+            document.querySelectorAll("[{marker}]").forEach((el) => el.remove());
+            for (const [tag, attributes, text] of elements) {{
+                if (tag === "title") {{
+                    document.title = text ?? "";
+                    continue;
+                }}
+                const el = document.createElement(tag);
+                el.setAttribute("{marker}", "");
+                for (const [name, value] of attributes) el.setAttribute(name, value);
+                if (text !== null) el.textContent = text;
+                document.head.appendChild(el);
+            }}
+        }})({elements})"#,
+        marker = HEAD_ATTRIBUTE,
+        elements = serde_json::Value::from(elements),
+    ));
+}
+
 /// Different hide implementations per platform
 #[allow(unused)]
 fn hide_app_window(webview: &WebView) {
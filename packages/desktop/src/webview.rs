@@ -1,23 +1,30 @@
 use crate::desktop_context::EventData;
 use crate::protocol;
 use crate::{desktop_context::UserWindowEvent, Config};
+use std::cell::RefCell;
+use std::path::PathBuf;
+use std::rc::Rc;
 use tao::event_loop::{EventLoopProxy, EventLoopWindowTarget};
 pub use wry;
 pub use wry::application as tao;
 use wry::application::window::Window;
-use wry::webview::{WebContext, WebView, WebViewBuilder};
+use wry::webview::{FileDropEvent, WebContext, WebView, WebViewBuilder};
+
+/// The files that were last dropped onto a window, waiting for the drop event the webview sends for them
+pub(crate) type DroppedFiles = Rc<RefCell<Vec<PathBuf>>>;
 
 pub fn build(
     cfg: &mut Config,
     event_loop: &EventLoopWindowTarget<UserWindowEvent>,
     proxy: EventLoopProxy<UserWindowEvent>,
-) -> (WebView, WebContext) {
+) -> (WebView, WebContext, DroppedFiles) {
     let builder = cfg.window.clone();
     let window = builder.build(event_loop).unwrap();
     let file_handler = cfg.file_drop_handler.take();
     let custom_head = cfg.custom_head.clone();
     let index_file = cfg.custom_index.clone();
     let root_name = cfg.root_name.clone();
+    let dropped_files = DroppedFiles::default();
 
     // We assume that if the icon is None in cfg, then the user just didnt set it
     if cfg.window.window.window_icon.is_none() {
@@ -47,11 +54,19 @@ pub fn build(
         .with_custom_protocol(String::from("dioxus"), move |r| {
             protocol::desktop_handler(r, custom_head.clone(), index_file.clone(), &root_name)
         })
-        .with_file_drop_handler(move |window, evet| {
-            file_handler
-                .as_ref()
-                .map(|handler| handler(window, evet))
-                .unwrap_or_default()
+        .with_file_drop_handler({
+            let dropped_files = dropped_files.clone();
+            move |window, evet| {
+                // The webview only exposes the names of dropped files, so remember the real paths for the drop event
+                if let FileDropEvent::Dropped { paths, .. } = &evet {
+                    *dropped_files.borrow_mut() = paths.clone();
+                }
+
+                file_handler
+                    .as_ref()
+                    .map(|handler| handler(window, evet))
+                    .unwrap_or_default()
+            }
         })
         .with_web_context(&mut web_context);
 
@@ -94,5 +109,5 @@ pub fn build(
         webview = webview.with_devtools(true);
     }
 
-    (webview.build().unwrap(), web_context)
+    (webview.build().unwrap(), web_context, dropped_files)
 }
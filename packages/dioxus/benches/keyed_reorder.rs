@@ -0,0 +1,92 @@
+//! This benchmark measures diffing keyed lists that are reordered in common ways.
+//!
+//! Keyed children are reconciled by keeping the longest increasing subsequence of the old children in place and only
+//! moving the rest. Every iteration flips between the original order and the reordered list of 1_000 rows.
+//!
+//! Before each pattern is measured, the number of rows the diff moved is printed next to the number of rows a diff
+//! without the subsequence would move, which moves every row that is no longer at its old index.
+
+use criterion::{criterion_group, criterion_main, Criterion};
+use dioxus::core::Mutation;
+use dioxus::prelude::*;
+use rand::prelude::*;
+
+criterion_group!(mbenches, keyed_reorder);
+criterion_main!(mbenches);
+
+const ROWS: usize = 1_000;
+
+type Reorder = fn(&mut Vec<usize>);
+
+struct ReorderProps {
+    reorder: Reorder,
+}
+
+fn app(cx: Scope<ReorderProps>) -> Element {
+    let mut rows: Vec<usize> = (0..ROWS).collect();
+    if cx.generation() % 2 == 1 {
+        (cx.props.reorder)(&mut rows);
+    }
+
+    render!(
+        ul {
+            rows.into_iter().map(|row_id| rsx!( li { key: "{row_id}", "{row_id}" } ))
+        }
+    )
+}
+
+fn keyed_reorder(c: &mut Criterion) {
+    let patterns: [(&str, Reorder); 4] = [
+        ("swap two rows", |rows| rows.swap(1, ROWS - 2)),
+        ("move the last row to the front", |rows| {
+            rows.rotate_right(1)
+        }),
+        ("reverse", |rows| rows.reverse()),
+        ("shuffle", |rows| {
+            rows.shuffle(&mut SmallRng::seed_from_u64(0));
+        }),
+    ];
+
+    for (name, reorder) in patterns {
+        let mut rows: Vec<usize> = (0..ROWS).collect();
+        reorder(&mut rows);
+        let mut dom = VirtualDom::new_with_props(app, ReorderProps { reorder });
+        let _ = dom.rebuild();
+        dom.mark_dirty(ScopeId::ROOT);
+        println!(
+            "{name}: {} rows moved, {} without the longest increasing subsequence",
+            moved_rows(&dom.render_immediate().edits),
+            moved_without_subsequence(&rows)
+        );
+
+        c.bench_function(name, |b| {
+            let mut dom = VirtualDom::new_with_props(app, ReorderProps { reorder });
+            let _ = dom.rebuild();
+
+            b.iter(|| {
+                dom.mark_dirty(ScopeId::ROOT);
+                let edits = dom.render_immediate();
+                assert!(moved_rows(&edits.edits) > 0);
+            })
+        });
+    }
+}
+
+/// The number of rows the diff moved
+fn moved_rows(edits: &[Mutation]) -> usize {
+    edits
+        .iter()
+        .map(|edit| match edit {
+            Mutation::InsertBefore { m, .. } | Mutation::InsertAfter { m, .. } => *m,
+            _ => 0,
+        })
+        .sum()
+}
+
+/// The number of rows a diff that moves every row that left its index would move
+fn moved_without_subsequence(rows: &[usize]) -> usize {
+    rows.iter()
+        .enumerate()
+        .filter(|(idx, row_id)| idx != *row_id)
+        .count()
+}
@@ -0,0 +1,97 @@
+//! This benchmark measures creating rows whose template paths are packed against the same rows with their paths
+//! stored as slices.
+//!
+//! Both templates describe the same row, and the diff walks them to the same nodes. The only difference is how the
+//! paths are stored in the template and handed to the renderer, so the gap between the two is the cost of unpacking
+//! a path. Each iteration rebuilds a table of 1_000 rows.
+
+use criterion::{criterion_group, criterion_main, Criterion};
+use dioxus::core::{PackedPath, Template, TemplateAttribute, TemplateNode};
+use dioxus::prelude::*;
+use std::cell::Cell;
+
+criterion_group!(mbenches, template_paths);
+criterion_main!(mbenches);
+
+const ROWS: usize = 1_000;
+
+const ROW_ROOTS: &[TemplateNode<'static>] = &[TemplateNode::Element {
+    tag: "tr",
+    namespace: None,
+    attrs: &[],
+    children: &[
+        TemplateNode::Element {
+            tag: "td",
+            namespace: None,
+            attrs: &[TemplateAttribute::Dynamic { id: 0 }],
+            children: &[TemplateNode::DynamicText { id: 0 }],
+        },
+        TemplateNode::Element {
+            tag: "td",
+            namespace: None,
+            attrs: &[],
+            children: &[TemplateNode::Element {
+                tag: "a",
+                namespace: None,
+                attrs: &[],
+                children: &[TemplateNode::DynamicText { id: 1 }],
+            }],
+        },
+    ],
+}];
+
+static PACKED_ROW: Template<'static> = Template {
+    name: concat!(file!(), ":", line!(), ":", column!(), ":0"),
+    roots: ROW_ROOTS,
+    node_paths: &[PackedPath::new(&[0, 0, 0]), PackedPath::new(&[0, 1, 0, 0])],
+    attr_paths: &[PackedPath::new(&[0, 0])],
+    ..Template::EMPTY
+};
+
+static SLICE_ROW: Template<'static> = Template {
+    name: concat!(file!(), ":", line!(), ":", column!(), ":0"),
+    roots: ROW_ROOTS,
+    node_paths: &[PackedPath::unpacked(0), PackedPath::unpacked(1)],
+    attr_paths: &[PackedPath::unpacked(2)],
+    unpacked_paths: &[&[0, 0, 0], &[0, 1, 0, 0], &[0, 0]],
+    ..Template::EMPTY
+};
+
+struct TableProps {
+    row: &'static Template<'static>,
+}
+
+fn table(cx: Scope<TableProps>) -> Element {
+    let row = *cx.props.row;
+    let rows = (0..ROWS).map(|row_id| VNode {
+        key: None,
+        parent: Default::default(),
+        template: Cell::new(row),
+        root_ids: dioxus::core::exports::bumpalo::collections::Vec::new_in(cx.bump()).into(),
+        dynamic_nodes: cx.bump().alloc([
+            cx.text_node(format_args!("{row_id}")),
+            cx.text_node(format_args!("row {row_id}")),
+        ]),
+        dynamic_attrs: cx.bump().alloc([cx.attr("class", "cell", None, false)]),
+    });
+
+    render!( table { tbody { rows } } )
+}
+
+fn template_paths(c: &mut Criterion) {
+    let mut group = c.benchmark_group("template paths");
+
+    for (name, row) in [("packed", &PACKED_ROW), ("slices", &SLICE_ROW)] {
+        group.bench_function(name, |b| {
+            let mut dom = VirtualDom::new_with_props(table, TableProps { row });
+            let _ = dom.rebuild();
+
+            b.iter(|| {
+                let edits = dom.rebuild();
+                assert!(edits.edits.len() > ROWS);
+            })
+        });
+    }
+
+    group.finish();
+}
@@ -1 +1,100 @@
+use crate::{AtomId, AtomRoot, Readable};
+use std::rc::Rc;
 
+/// A value derived from other atoms and selectors.
+///
+/// The value is computed the first time it is read and cached in the [`AtomRoot`]. Whenever an atom it read is set, it
+/// is computed again, and the components that read it only re-render if the new value is different.
+///
+/// ```rust, ignore
+/// static COUNT: Atom<i32> = Atom(|_| 0);
+/// static IS_EVEN: Selector<bool> = Selector(|s| *s.get(&COUNT) % 2 == 0);
+///
+/// fn Parity(cx: Scope) -> Element {
+///     let is_even = use_selector(cx, &IS_EVEN);
+///     cx.render(rsx!{ "even: {is_even}" })
+/// }
+/// ```
+pub struct Selector<V>(pub fn(&SelectorBuilder) -> V);
+
+impl<V> Selector<V> {
+    /// The id of the selector's slot in the [`AtomRoot`]
+    pub fn unique_id(&'static self) -> AtomId {
+        self as *const Selector<V> as *const ()
+    }
+}
+
+/// Reads the atoms and selectors a [`Selector`] depends on
+pub struct SelectorBuilder<'a> {
+    pub(crate) root: &'a AtomRoot,
+    pub(crate) id: AtomId,
+}
+
+impl SelectorBuilder<'_> {
+    /// Read an atom, and compute the selector again whenever it is set
+    pub fn get<V: 'static>(&self, atom: impl Readable<V>) -> Rc<V> {
+        let source = atom.unique_id();
+        let value = self.root.read(atom);
+        self.root.add_dependent(source, self.id);
+        value
+    }
+
+    /// Read another selector, and compute this selector again whenever it changes
+    pub fn select<V: PartialEq + 'static>(&self, selector: &'static Selector<V>) -> Rc<V> {
+        let value = self.root.select(selector);
+        self.root.add_dependent(selector.unique_id(), self.id);
+        value
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Atom;
+    use dioxus_core::ScopeId;
+    use std::sync::{Arc, Mutex};
+
+    static COUNT: Atom<i32> = Atom(|_| 1);
+    static IS_EVEN: Selector<bool> = Selector(|s| *s.get(&COUNT) % 2 == 0);
+    static LABEL: Selector<String> = Selector(|s| {
+        if *s.select(&IS_EVEN) {
+            "even".to_string()
+        } else {
+            "odd".to_string()
+        }
+    });
+
+    fn root() -> (AtomRoot, Arc<Mutex<Vec<ScopeId>>>) {
+        let updated = Arc::new(Mutex::new(Vec::new()));
+        let root = AtomRoot::new(Arc::new({
+            let updated = updated.clone();
+            move |id| updated.lock().unwrap().push(id)
+        }));
+        (root, updated)
+    }
+
+    #[test]
+    fn selectors_are_cached() {
+        let (root, _) = root();
+        assert!(!*root.select(&IS_EVEN));
+        assert!(Rc::ptr_eq(&root.select(&IS_EVEN), &root.select(&IS_EVEN)));
+
+        root.set((&COUNT).unique_id(), 2);
+        assert!(*root.select(&IS_EVEN));
+        assert_eq!(*root.select(&LABEL), "even");
+    }
+
+    #[test]
+    fn subscribers_only_update_when_the_value_changes() {
+        let (root, updated) = root();
+        root.register_selector(&LABEL, ScopeId(1));
+
+        // 1 -> 3 is still odd
+        root.set((&COUNT).unique_id(), 3);
+        assert!(updated.lock().unwrap().is_empty());
+
+        root.set((&COUNT).unique_id(), 4);
+        assert_eq!(*updated.lock().unwrap(), [ScopeId(1)]);
+        assert_eq!(*root.select(&LABEL), "even");
+    }
+}
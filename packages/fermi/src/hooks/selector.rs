@@ -0,0 +1,35 @@
+use crate::{use_atom_root, AtomId, AtomRoot, Selector};
+use dioxus_core::{ScopeId, ScopeState};
+use std::rc::Rc;
+
+/// Read the value of a [`Selector`], and re-render the component whenever it changes
+#[must_use]
+pub fn use_selector<'a, V: PartialEq + 'static>(
+    cx: &'a ScopeState,
+    selector: &'static Selector<V>,
+) -> &'a V {
+    let root = use_atom_root(cx);
+
+    struct UseSelectorInner<V> {
+        root: Rc<AtomRoot>,
+        id: AtomId,
+        scope_id: ScopeId,
+        value: Option<Rc<V>>,
+    }
+
+    impl<V> Drop for UseSelectorInner<V> {
+        fn drop(&mut self) {
+            self.root.unsubscribe(self.id, self.scope_id)
+        }
+    }
+
+    let inner = cx.use_hook(|| UseSelectorInner {
+        value: None,
+        root: root.clone(),
+        scope_id: cx.scope_id(),
+        id: selector.unique_id(),
+    });
+
+    inner.value = Some(inner.root.register_selector(selector, cx.scope_id()));
+    inner.value.as_ref().unwrap()
+}
@@ -31,12 +31,14 @@ pub mod hooks {
     mod atom_root;
     mod init_atom_root;
     mod read;
+    mod selector;
     mod set;
     mod state;
     pub use atom_ref::*;
     pub use atom_root::*;
     pub use init_atom_root::*;
     pub use read::*;
+    pub use selector::*;
     pub use set::*;
     pub use state::*;
 }
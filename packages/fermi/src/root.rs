@@ -3,10 +3,13 @@ use std::{any::Any, cell::RefCell, collections::HashMap, rc::Rc, sync::Arc};
 use dioxus_core::ScopeId;
 use im_rc::HashSet;
 
-use crate::Readable;
+use crate::{Readable, Selector, SelectorBuilder};
 
 pub type AtomId = *const ();
 
+/// Computes a selector again, returning the new value if it is different from the old one
+type Recompute = Rc<dyn Fn(&AtomRoot, &Rc<dyn Any>) -> Option<Rc<dyn Any>>>;
+
 pub struct AtomRoot {
     pub atoms: RefCell<HashMap<AtomId, Slot>>,
     pub update_any: Arc<dyn Fn(ScopeId)>,
@@ -15,6 +18,20 @@ pub struct AtomRoot {
 pub struct Slot {
     pub value: Rc<dyn Any>,
     pub subscribers: HashSet<ScopeId>,
+    /// The selectors that read this slot
+    pub dependents: HashSet<AtomId>,
+    pub(crate) recompute: Option<Recompute>,
+}
+
+impl Slot {
+    fn new(value: Rc<dyn Any>) -> Self {
+        Self {
+            value,
+            subscribers: HashSet::new(),
+            dependents: HashSet::new(),
+            recompute: None,
+        }
+    }
 }
 
 impl AtomRoot {
@@ -28,13 +45,9 @@ impl AtomRoot {
     pub fn initialize<V: 'static>(&self, f: impl Readable<V>) {
         let id = f.unique_id();
         if self.atoms.borrow().get(&id).is_none() {
-            self.atoms.borrow_mut().insert(
-                id,
-                Slot {
-                    value: Rc::new(f.init()),
-                    subscribers: HashSet::new(),
-                },
-            );
+            self.atoms
+                .borrow_mut()
+                .insert(id, Slot::new(Rc::new(f.init())));
         }
     }
 
@@ -47,43 +60,103 @@ impl AtomRoot {
             slot.value.clone().downcast().unwrap()
         } else {
             let value = Rc::new(f.init());
-            let mut subscribers = HashSet::new();
-            subscribers.insert(scope);
-
-            atoms.insert(
-                f.unique_id(),
-                Slot {
-                    value: value.clone(),
-                    subscribers,
-                },
-            );
+            let mut slot = Slot::new(value.clone());
+            slot.subscribers.insert(scope);
+            atoms.insert(f.unique_id(), slot);
             value
         }
     }
 
     pub fn set<V: 'static>(&self, ptr: AtomId, value: V) {
-        let mut atoms = self.atoms.borrow_mut();
+        {
+            let mut atoms = self.atoms.borrow_mut();
+
+            if let Some(slot) = atoms.get_mut(&ptr) {
+                slot.value = Rc::new(value);
+                tracing::trace!("found item with subscribers {:?}", slot.subscribers);
+
+                for scope in &slot.subscribers {
+                    tracing::trace!("updating subcsriber");
+                    (self.update_any)(*scope);
+                }
+            } else {
+                tracing::trace!("no atoms found for {:?}", ptr);
+                atoms.insert(ptr, Slot::new(Rc::new(value)));
+            }
+        }
 
-        if let Some(slot) = atoms.get_mut(&ptr) {
-            slot.value = Rc::new(value);
-            tracing::trace!("found item with subscribers {:?}", slot.subscribers);
+        self.update_dependents(ptr);
+    }
 
-            for scope in &slot.subscribers {
-                tracing::trace!("updating subcsriber");
-                (self.update_any)(*scope);
+    /// Compute the selectors that read this slot again, and update the subscribers of the ones that changed
+    fn update_dependents(&self, ptr: AtomId) {
+        let dependents: Vec<AtomId> = match self.atoms.borrow().get(&ptr) {
+            Some(slot) => slot.dependents.iter().copied().collect(),
+            None => return,
+        };
+
+        for id in dependents {
+            let (recompute, old) = match self.atoms.borrow().get(&id) {
+                Some(Slot {
+                    recompute: Some(recompute),
+                    value,
+                    ..
+                }) => (recompute.clone(), value.clone()),
+                _ => continue,
+            };
+
+            // The selector is free to read the root while it is computed, so nothing can be borrowed here
+            if let Some(new) = recompute(self, &old) {
+                if let Some(slot) = self.atoms.borrow_mut().get_mut(&id) {
+                    slot.value = new;
+                    for scope in &slot.subscribers {
+                        (self.update_any)(*scope);
+                    }
+                }
+                self.update_dependents(id);
             }
-        } else {
-            tracing::trace!("no atoms found for {:?}", ptr);
-            atoms.insert(
-                ptr,
-                Slot {
-                    value: Rc::new(value),
-                    subscribers: HashSet::new(),
-                },
-            );
         }
     }
 
+    pub(crate) fn add_dependent(&self, ptr: AtomId, selector: AtomId) {
+        if let Some(slot) = self.atoms.borrow_mut().get_mut(&ptr) {
+            slot.dependents.insert(selector);
+        }
+    }
+
+    /// Read the value of a selector, computing it if it hasn't been read before
+    pub fn select<V: PartialEq + 'static>(&self, selector: &'static Selector<V>) -> Rc<V> {
+        let id = selector.unique_id();
+        if let Some(slot) = self.atoms.borrow().get(&id) {
+            return slot.value.clone().downcast().unwrap();
+        }
+
+        let value = Rc::new((selector.0)(&SelectorBuilder { root: self, id }));
+        let mut slot = Slot::new(value.clone());
+        slot.recompute = Some(Rc::new(move |root: &AtomRoot, old: &Rc<dyn Any>| {
+            let new = (selector.0)(&SelectorBuilder { root, id });
+            match old.downcast_ref::<V>() {
+                Some(old) if *old == new => None,
+                _ => Some(Rc::new(new) as Rc<dyn Any>),
+            }
+        }));
+        self.atoms.borrow_mut().insert(id, slot);
+        value
+    }
+
+    /// Read the value of a selector and subscribe the scope to it
+    pub fn register_selector<V: PartialEq + 'static>(
+        &self,
+        selector: &'static Selector<V>,
+        scope: ScopeId,
+    ) -> Rc<V> {
+        let value = self.select(selector);
+        if let Some(slot) = self.atoms.borrow_mut().get_mut(&selector.unique_id()) {
+            slot.subscribers.insert(scope);
+        }
+        value
+    }
+
     pub fn unsubscribe(&self, ptr: AtomId, scope: ScopeId) {
         let mut atoms = self.atoms.borrow_mut();
 
@@ -110,13 +183,7 @@ impl AtomRoot {
             slot.value.clone().downcast().unwrap()
         } else {
             let value = Rc::new(f.init());
-            atoms.insert(
-                f.unique_id(),
-                Slot {
-                    value: value.clone(),
-                    subscribers: HashSet::new(),
-                },
-            );
+            atoms.insert(f.unique_id(), Slot::new(value.clone()));
             value
         }
     }
@@ -81,6 +81,9 @@ pub use use_future::*;
 mod use_effect;
 pub use use_effect::*;
 
+mod use_render_effect;
+pub use use_render_effect::*;
+
 mod use_callback;
 pub use use_callback::*;
 
@@ -0,0 +1,97 @@
+use dioxus_core::ScopeState;
+use std::{any::Any, cell::RefCell, rc::Rc};
+
+use crate::UseFutureDep;
+
+/// A hook that runs a closure once the mutations of a render are applied, and again whenever its dependencies change.
+///
+/// Unlike [`use_effect`](crate::use_effect), the closure runs synchronously after the renderer has applied the edits,
+/// so the elements it rendered are already in the DOM. The closure can return a cleanup closure, which runs before the
+/// next time the effect runs, or when the component is unmounted.
+///
+/// ## Arguments
+///
+/// - `dependencies`: a tuple of references to values that are `PartialEq` + `Clone`.
+/// - `effect`: a closure that takes the `dependencies` as arguments and returns either `()` or a cleanup closure.
+///
+/// ## Examples
+///
+/// ```rust, ignore
+/// # use dioxus::prelude::*;
+/// #[component]
+/// fn Clock(cx: Scope, interval: u64) -> Element {
+///     // Restart the timer whenever the interval changes, and stop it when the component is unmounted
+///     use_render_effect(cx, (interval,), |(interval,)| {
+///         let timer = start_timer(interval);
+///         move || timer.stop()
+///     });
+///
+///     render!(p { "tick" })
+/// }
+/// ```
+pub fn use_render_effect<D, C>(
+    cx: &ScopeState,
+    dependencies: D,
+    effect: impl FnOnce(D::Out) -> C + 'static,
+) where
+    D: UseFutureDep,
+    D::Out: 'static,
+    C: EffectCleanup,
+{
+    struct UseRenderEffect {
+        needs_regen: bool,
+        dependencies: Vec<Box<dyn Any>>,
+        cleanup: Rc<RefCell<Cleanup>>,
+    }
+
+    impl Drop for UseRenderEffect {
+        fn drop(&mut self) {
+            let cleanup = self.cleanup.borrow_mut().take();
+            if let Some(cleanup) = cleanup {
+                cleanup();
+            }
+        }
+    }
+
+    let state = cx.use_hook(move || UseRenderEffect {
+        needs_regen: true,
+        dependencies: Vec::new(),
+        cleanup: Rc::new(RefCell::new(None)),
+    });
+
+    if dependencies.clone().apply(&mut state.dependencies) || state.needs_regen {
+        state.needs_regen = false;
+
+        let dependencies = dependencies.out();
+        let cleanup = state.cleanup.clone();
+        cx.queue_effect(move || {
+            let previous = cleanup.borrow_mut().take();
+            if let Some(previous) = previous {
+                previous();
+            }
+
+            let next = effect(dependencies).into_cleanup();
+            *cleanup.borrow_mut() = next;
+        });
+    }
+}
+
+type Cleanup = Option<Box<dyn FnOnce()>>;
+
+/// What an effect from [`use_render_effect`] can return: either nothing, or a closure that cleans up after it.
+pub trait EffectCleanup {
+    /// Turn this into the closure that runs before the next effect or when the component is unmounted
+    fn into_cleanup(self) -> Option<Box<dyn FnOnce()>>;
+}
+
+impl EffectCleanup for () {
+    fn into_cleanup(self) -> Option<Box<dyn FnOnce()>> {
+        None
+    }
+}
+
+impl<F: FnOnce() + 'static> EffectCleanup for F {
+    fn into_cleanup(self) -> Option<Box<dyn FnOnce()>> {
+        Some(Box::new(self))
+    }
+}
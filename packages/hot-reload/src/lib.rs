@@ -1,9 +1,6 @@
-use std::io::{BufRead, BufReader};
-
 use dioxus_core::Template;
 #[cfg(feature = "file_watcher")]
 pub use dioxus_html::HtmlCtx;
-use interprocess_docfix::local_socket::LocalSocketStream;
 use serde::{Deserialize, Serialize};
 
 #[cfg(feature = "custom_file_watcher")]
@@ -22,7 +19,13 @@ pub enum HotReloadMsg {
 }
 
 /// Connect to the hot reloading listener. The callback provided will be called every time a template change is detected
+///
+/// Native apps connect to the local socket opened by the hot reloading server.
+#[cfg(not(target_arch = "wasm32"))]
 pub fn connect(mut f: impl FnMut(HotReloadMsg) + Send + 'static) {
+    use interprocess_docfix::local_socket::LocalSocketStream;
+    use std::io::{BufRead, BufReader};
+
     std::thread::spawn(move || {
         if let Ok(socket) = LocalSocketStream::connect("@dioxusin") {
             let mut buf_reader = BufReader::new(socket);
@@ -45,6 +48,45 @@ pub fn connect(mut f: impl FnMut(HotReloadMsg) + Send + 'static) {
     });
 }
 
+/// Connect to the hot reloading listener. The callback provided will be called every time a template change is detected
+///
+/// Web apps connect to the websocket the dev server serves at `/_dioxus/hot_reload` on the same host as the page.
+#[cfg(target_arch = "wasm32")]
+pub fn connect(mut f: impl FnMut(HotReloadMsg) + 'static) {
+    use wasm_bindgen::{closure::Closure, JsCast};
+    use web_sys::{MessageEvent, WebSocket};
+
+    let window = match web_sys::window() {
+        Some(window) => window,
+        None => return,
+    };
+    let location = window.location();
+
+    let protocol = match location.protocol() {
+        Ok(protocol) if protocol == "https:" => "wss:",
+        _ => "ws:",
+    };
+    let host = location.host().unwrap_or_default();
+
+    let ws = match WebSocket::new(&format!("{protocol}//{host}/_dioxus/hot_reload")) {
+        Ok(ws) => ws,
+        Err(_) => return,
+    };
+
+    let on_message = Closure::wrap(Box::new(move |e: MessageEvent| {
+        if let Some(text) = e.data().as_string() {
+            // templates borrow from the message for the rest of the program
+            let text: &'static str = Box::leak(text.into_boxed_str());
+            if let Ok(msg) = serde_json::from_str::<HotReloadMsg>(text) {
+                f(msg);
+            }
+        }
+    }) as Box<dyn FnMut(MessageEvent)>);
+
+    ws.set_onmessage(Some(on_message.as_ref().unchecked_ref()));
+    on_message.forget();
+}
+
 /// Start the hot reloading server with the current directory as the root
 #[macro_export]
 macro_rules! hot_reload_init {
@@ -0,0 +1,84 @@
+use async_trait::async_trait;
+use dioxus_core::ScopeState;
+use std::fmt::{Display, Formatter};
+use std::rc::Rc;
+
+/// A struct that implements ClipboardProvider is sent through [`ScopeState`]'s provide_context function
+/// so that [`use_clipboard`] can provide a platform agnostic interface for the system clipboard.
+#[async_trait(?Send)]
+pub trait ClipboardProvider {
+    /// Read the text on the clipboard
+    async fn read_text(&self) -> Result<String, ClipboardError>;
+    /// Replace the contents of the clipboard with text
+    async fn write_text(&self, text: String) -> Result<(), ClipboardError>;
+}
+
+/// Get a handle to the system clipboard.
+///
+/// ```rust, ignore
+/// fn Copy(cx: Scope) -> Element {
+///     let clipboard = use_clipboard(cx);
+///
+///     cx.render(rsx! {
+///         button {
+///             onclick: move |_| {
+///                 to_owned![clipboard];
+///                 async move { _ = clipboard.write_text("copied!").await; }
+///             },
+///             "copy"
+///         }
+///     })
+/// }
+/// ```
+///
+/// Renderers that don't provide a clipboard return [`ClipboardError::NotSupported`].
+#[must_use]
+pub fn use_clipboard(cx: &ScopeState) -> &UseClipboard {
+    cx.use_hook(|| UseClipboard {
+        provider: cx.consume_context::<Rc<dyn ClipboardProvider>>(),
+    })
+}
+
+/// A wrapper around the target platform's clipboard.
+#[derive(Clone)]
+pub struct UseClipboard {
+    provider: Option<Rc<dyn ClipboardProvider>>,
+}
+
+impl UseClipboard {
+    /// Read the text on the clipboard
+    pub async fn read_text(&self) -> Result<String, ClipboardError> {
+        match &self.provider {
+            Some(provider) => provider.read_text().await,
+            None => Err(ClipboardError::NotSupported),
+        }
+    }
+
+    /// Replace the contents of the clipboard with text
+    pub async fn write_text(&self, text: impl ToString) -> Result<(), ClipboardError> {
+        match &self.provider {
+            Some(provider) => provider.write_text(text.to_string()).await,
+            None => Err(ClipboardError::NotSupported),
+        }
+    }
+}
+
+/// Represents an error when reading or writing the clipboard
+#[derive(Debug)]
+pub enum ClipboardError {
+    /// The renderer doesn't provide a clipboard
+    NotSupported,
+    /// The platform refused to read or write the clipboard, for example because the page isn't focused.
+    Failed(String),
+}
+
+impl Display for ClipboardError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ClipboardError::NotSupported => write!(f, "the renderer does not provide a clipboard"),
+            ClipboardError::Failed(err) => write!(f, "failed to access the clipboard: {err}"),
+        }
+    }
+}
+
+impl std::error::Error for ClipboardError {}
@@ -1097,7 +1097,7 @@ builder_constructors! {
         autocomplete: String DEFAULT,
         autofocus: Bool DEFAULT,
         capture: String DEFAULT,
-        checked: Bool DEFAULT,
+        checked: Bool volatile,
         directory: Bool "webkitdirectory",
         disabled: Bool DEFAULT,
         form: Id DEFAULT,
@@ -1157,6 +1157,15 @@ builder_constructors! {
         // value: String,
         value: String volatile,
         initial_value: String DEFAULT,
+
+        /// Where the selection of the field starts. Set after the value, so a new value doesn't move it
+        selection_start: usize volatile,
+
+        /// Where the selection of the field ends. Set after the value, so a new value doesn't move it
+        selection_end: usize volatile,
+
+        /// The direction of the selection of the field: `forward`, `backward` or `none`
+        selection_direction: String volatile,
     };
 
     /// Build a
@@ -1262,6 +1271,15 @@ builder_constructors! {
         value: String volatile,
 
         initial_value: String DEFAULT,
+
+        /// Where the selection of the field starts. Set after the value, so a new value doesn't move it
+        selection_start: usize volatile,
+
+        /// Where the selection of the field ends. Set after the value, so a new value doesn't move it
+        selection_end: usize volatile,
+
+        /// The direction of the selection of the field: `forward`, `backward` or `none`
+        selection_direction: String volatile,
     };
 
 
@@ -26,6 +26,7 @@ macro_rules! impl_event {
 mod animation;
 mod clipboard;
 mod composition;
+mod custom;
 mod drag;
 mod focus;
 mod form;
@@ -45,6 +46,7 @@ mod wheel;
 pub use animation::*;
 pub use clipboard::*;
 pub use composition::*;
+pub use custom::*;
 pub use drag::*;
 pub use focus::*;
 pub use form::*;
@@ -147,7 +149,9 @@ pub fn event_bubbles(evt: &str) -> bool {
         "transitionend" => true,
         "toggle" => true,
         "mounted" => false,
-        _ => true,
+        // Events that aren't built in, like the events custom elements dispatch, often don't bubble. They are
+        // listened for on the element itself so they are still caught.
+        _ => false,
     }
 }
 
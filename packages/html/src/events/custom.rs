@@ -0,0 +1,68 @@
+use dioxus_core::Event;
+
+pub type CustomEvent = Event<CustomEventData>;
+
+/// The data of an event a custom element dispatches, or any other event that isn't built in
+///
+/// The `detail` of a `CustomEvent` is passed along as JSON, so it can be read back as any type with
+/// [`CustomEventData::detail`].
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CustomEventData {
+    #[cfg_attr(
+        feature = "serialize",
+        serde(rename = "detail", default = "null_detail")
+    )]
+    raw_detail: String,
+}
+
+#[cfg(feature = "serialize")]
+fn null_detail() -> String {
+    "null".to_string()
+}
+
+impl CustomEventData {
+    /// Create event data from the `detail` of the event serialized as JSON
+    pub fn new(raw_detail: impl Into<String>) -> Self {
+        Self {
+            raw_detail: raw_detail.into(),
+        }
+    }
+
+    /// The `detail` of the event serialized as JSON. This is `null` for events that aren't a `CustomEvent`.
+    pub fn raw_detail(&self) -> &str {
+        &self.raw_detail
+    }
+
+    /// Read the `detail` of the event as a typed value
+    #[cfg(feature = "serialize")]
+    pub fn detail<T: serde::de::DeserializeOwned>(&self) -> Result<T, serde_json::Error> {
+        serde_json::from_str(&self.raw_detail)
+    }
+}
+
+impl Default for CustomEventData {
+    fn default() -> Self {
+        Self::new("null")
+    }
+}
+
+/// Listen for an event with any name, like the events custom elements dispatch.
+///
+/// This is what `"on:event-name": handler` in rsx creates. `name` is the name of the listener, which is the name of
+/// the event prefixed with `on`.
+#[inline]
+pub fn custom_event<'a, E: crate::EventReturn<T>, T>(
+    _cx: &'a ::dioxus_core::ScopeState,
+    name: &'static str,
+    mut _f: impl FnMut(CustomEvent) -> E + 'a,
+) -> ::dioxus_core::Attribute<'a> {
+    ::dioxus_core::Attribute::new(
+        name,
+        _cx.listener(move |e: CustomEvent| {
+            _f(e).spawn(_cx);
+        }),
+        None,
+        false,
+    )
+}
@@ -1,6 +1,8 @@
+use std::fmt::Debug;
+
 use dioxus_core::Event;
 
-use crate::MouseData;
+use crate::{FileEngine, MouseData};
 
 pub type DragEvent = Event<DragData>;
 
@@ -9,10 +11,39 @@ pub type DragEvent = Event<DragData>;
 /// (such as another DOM element). Applications are free to interpret a drag and drop interaction in an
 /// application-specific way.
 #[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Clone)]
 pub struct DragData {
     /// Inherit mouse data
     pub mouse: MouseData,
+
+    /// The files that are being dragged, if the platform exposes them. Only set for files dragged in from outside the
+    /// app, and only readable once they are dropped.
+    #[cfg_attr(
+        feature = "serialize",
+        serde(
+            default,
+            skip_serializing,
+            deserialize_with = "crate::events::form::deserialize_file_engine"
+        )
+    )]
+    pub files: Option<std::sync::Arc<dyn FileEngine>>,
+}
+
+impl PartialEq for DragData {
+    fn eq(&self, other: &Self) -> bool {
+        self.mouse == other.mouse
+    }
+}
+
+impl Eq for DragData {}
+
+impl Debug for DragData {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("DragData")
+            .field("mouse", &self.mouse)
+            .field("files", &self.files.as_ref().map(|files| files.files()))
+            .finish()
+    }
 }
 
 impl_event! {
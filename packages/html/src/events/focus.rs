@@ -21,3 +21,13 @@ impl_event! [
     /// onblur
     onblur
 ];
+
+impl_event! [
+    FocusData;
+
+    /// oncapturefocus
+    oncapturefocus
+
+    /// oncaptureblur
+    oncaptureblur
+];
@@ -82,6 +82,16 @@ impl_event! {
     onkeyup
 }
 
+impl_event! {
+    KeyboardData;
+
+    /// oncapturekeydown
+    oncapturekeydown
+
+    /// oncapturekeyup
+    oncapturekeyup
+}
+
 impl KeyboardData {
     pub fn new(
         key: Key,
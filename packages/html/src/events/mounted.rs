@@ -1,6 +1,6 @@
 //! Handles querying data from the renderer
 
-use euclid::Rect;
+use euclid::{Rect, Size2D};
 
 use std::{
     any::Any,
@@ -38,6 +38,20 @@ pub trait RenderedElementBacking {
     fn set_focus(&self, _focus: bool) -> Pin<Box<dyn Future<Output = MountedResult<()>>>> {
         Box::pin(async { Err(MountedError::NotSupported) })
     }
+
+    /// Start watching how much of the element is visible in the viewport
+    #[allow(clippy::type_complexity)]
+    fn observe_intersection(
+        &self,
+    ) -> Pin<Box<dyn Future<Output = MountedResult<IntersectionObserver>>>> {
+        Box::pin(async { Err(MountedError::NotSupported) })
+    }
+
+    /// Start watching the size of the element's content box
+    #[allow(clippy::type_complexity)]
+    fn observe_resize(&self) -> Pin<Box<dyn Future<Output = MountedResult<ResizeObserver>>>> {
+        Box::pin(async { Err(MountedError::NotSupported) })
+    }
 }
 
 impl RenderedElementBacking for () {}
@@ -53,6 +67,71 @@ pub enum ScrollBehavior {
     Smooth,
 }
 
+/// How much of an element is visible in the viewport
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct IntersectionData {
+    /// If any part of the element is visible
+    pub is_intersecting: bool,
+    /// The fraction of the element that is visible, from `0.0` to `1.0`
+    pub ratio: f64,
+}
+
+/// The renderer specific part of an [`IntersectionObserver`]. The renderer should stop observing the element when it
+/// is dropped.
+pub trait IntersectionObserverBacking {
+    /// Wait for the next change in the visibility of the element. Returns `None` once no more changes will be reported
+    #[allow(clippy::type_complexity)]
+    fn next(&mut self) -> Pin<Box<dyn Future<Output = Option<IntersectionData>> + '_>>;
+}
+
+/// Reports changes in the visibility of a mounted element. The element stops being observed when this is dropped.
+pub struct IntersectionObserver {
+    inner: Box<dyn IntersectionObserverBacking>,
+}
+
+impl IntersectionObserver {
+    /// Create a new IntersectionObserver
+    pub fn new(observer: impl IntersectionObserverBacking + 'static) -> Self {
+        Self {
+            inner: Box::new(observer),
+        }
+    }
+
+    /// Wait for the next change in the visibility of the element. Returns `None` once no more changes will be reported
+    pub async fn next(&mut self) -> Option<IntersectionData> {
+        self.inner.next().await
+    }
+}
+
+/// The renderer specific part of a [`ResizeObserver`]. The renderer should stop observing the element when it is
+/// dropped.
+pub trait ResizeObserverBacking {
+    /// Wait for the next size of the element's content box. Renderers should report at most one size per animation
+    /// frame. Returns `None` once no more changes will be reported
+    #[allow(clippy::type_complexity)]
+    fn next(&mut self) -> Pin<Box<dyn Future<Output = Option<Size2D<f64, f64>>> + '_>>;
+}
+
+/// Reports changes in the size of a mounted element. The element stops being observed when this is dropped.
+pub struct ResizeObserver {
+    inner: Box<dyn ResizeObserverBacking>,
+}
+
+impl ResizeObserver {
+    /// Create a new ResizeObserver
+    pub fn new(observer: impl ResizeObserverBacking + 'static) -> Self {
+        Self {
+            inner: Box::new(observer),
+        }
+    }
+
+    /// Wait for the next size of the element's content box. Returns `None` once no more changes will be reported
+    pub async fn next(&mut self) -> Option<Size2D<f64, f64>> {
+        self.inner.next().await
+    }
+}
+
 /// An Element that has been rendered and allows reading and modifying information about it.
 ///
 /// Different platforms will have different implementations and different levels of support for this trait. Renderers that do not support specific features will return `None` for those queries.
@@ -90,6 +169,16 @@ impl MountedData {
     pub fn set_focus(&self, focus: bool) -> Pin<Box<dyn Future<Output = MountedResult<()>>>> {
         self.inner.set_focus(focus)
     }
+
+    /// Start watching how much of the element is visible in the viewport
+    pub async fn observe_intersection(&self) -> MountedResult<IntersectionObserver> {
+        self.inner.observe_intersection().await
+    }
+
+    /// Start watching the size of the element's content box
+    pub async fn observe_resize(&self) -> MountedResult<ResizeObserver> {
+        self.inner.observe_resize().await
+    }
 }
 
 use dioxus_core::Event;
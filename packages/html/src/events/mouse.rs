@@ -149,6 +149,37 @@ impl_event! {
     onmouseup
 }
 
+impl_event! {
+    MouseData;
+
+    /// Execute a callback when a button is clicked, before the `onclick` listeners of the element and its
+    /// ancestors.
+    ///
+    /// Capture listeners are called from the root of the tree down to the target element. If one of them stops the
+    /// propagation of the event, the remaining capture and `onclick` listeners are skipped.
+    ///
+    /// ## Example
+    /// ```rust, ignore
+    /// rsx!( div { oncaptureclick: move |_| tracing::info!("Clicked inside!"), button { "click me" } } )
+    /// ```
+    oncaptureclick
+
+    /// oncapturecontextmenu
+    oncapturecontextmenu
+
+    /// oncapturedblclick
+    oncapturedblclick
+
+    /// oncapturemousedown
+    oncapturemousedown
+
+    /// oncapturemousemove
+    oncapturemousemove
+
+    /// oncapturemouseup
+    oncapturemouseup
+}
+
 impl MouseData {
     /// Construct MouseData with the specified properties
     ///
@@ -62,3 +62,15 @@ impl_event![
     /// pointerout
     onpointerout
 ];
+
+impl_event![
+    PointerData;
+    /// capture pointerdown
+    oncapturepointerdown
+
+    /// capture pointermove
+    oncapturepointermove
+
+    /// capture pointerup
+    oncapturepointerup
+];
@@ -0,0 +1,95 @@
+use crate::{IntersectionData, MountedData, MountedEvent};
+use dioxus_core::{ScopeState, TaskId};
+use std::cell::{Cell, RefCell};
+use std::rc::Rc;
+use std::sync::Arc;
+
+/// Track how much of an element is visible in the viewport.
+///
+/// Pass the `onmounted` event of the element to [`UseIntersectionObserver::observe`]. The component re-renders every
+/// time the visibility of the element changes, and the element stops being observed when the component is unmounted.
+///
+/// ```rust, ignore
+/// fn LazyImage(cx: Scope) -> Element {
+///     let visibility = use_intersection_observer(cx);
+///
+///     cx.render(rsx! {
+///         div {
+///             onmounted: move |evt| visibility.observe(evt),
+///             if visibility.is_intersecting() {
+///                 rsx! { img { src: "https://example.com/image.png" } }
+///             }
+///         }
+///     })
+/// }
+/// ```
+///
+/// Renderers that can't observe elements never report a change, so the element is never considered visible.
+pub fn use_intersection_observer(cx: &ScopeState) -> &UseIntersectionObserver {
+    let state = cx.use_hook(|| UseIntersectionObserver {
+        data: Rc::new(Cell::new(None)),
+        element: Rc::new(RefCell::new(None)),
+        task: Cell::new(None),
+        update: cx.schedule_update(),
+    });
+
+    // A new element was mounted, so replace the task watching the old one
+    if let Some(element) = state.element.borrow_mut().take() {
+        if let Some(task) = state.task.take() {
+            cx.remove_future(task);
+        }
+
+        let data = state.data.clone();
+        let update = state.update.clone();
+        state.task.set(Some(cx.spawn(async move {
+            let mut observer = match element.observe_intersection().await {
+                Ok(observer) => observer,
+                Err(_) => return,
+            };
+
+            while let Some(new) = observer.next().await {
+                data.set(Some(new));
+                update();
+            }
+        })));
+    }
+
+    state
+}
+
+/// The visibility of an element tracked by [`use_intersection_observer`]
+pub struct UseIntersectionObserver {
+    data: Rc<Cell<Option<IntersectionData>>>,
+    element: Rc<RefCell<Option<Rc<MountedData>>>>,
+    task: Cell<Option<TaskId>>,
+    update: Arc<dyn Fn() + Send + Sync + 'static>,
+}
+
+impl UseIntersectionObserver {
+    /// Start watching the element an `onmounted` event was fired for. Any element that was watched before is dropped.
+    pub fn observe(&self, event: MountedEvent) {
+        *self.element.borrow_mut() = Some(event.data);
+        (self.update)();
+    }
+
+    /// The last visibility the renderer reported, or `None` if it hasn't reported one yet
+    pub fn data(&self) -> Option<IntersectionData> {
+        self.data.get()
+    }
+
+    /// If any part of the element is visible
+    pub fn is_intersecting(&self) -> bool {
+        match self.data.get() {
+            Some(data) => data.is_intersecting,
+            None => false,
+        }
+    }
+
+    /// The fraction of the element that is visible, from `0.0` to `1.0`
+    pub fn ratio(&self) -> f64 {
+        match self.data.get() {
+            Some(data) => data.ratio,
+            None => 0.0,
+        }
+    }
+}
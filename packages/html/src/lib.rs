@@ -40,9 +40,15 @@ pub use events::*;
 pub use global_attributes::*;
 pub use render_template::*;
 
+mod clipboard;
 mod eval;
+mod intersection_observer;
+mod resize_observer;
 
 pub mod prelude {
+    pub use crate::clipboard::*;
     pub use crate::eval::*;
     pub use crate::events::*;
+    pub use crate::intersection_observer::*;
+    pub use crate::resize_observer::*;
 }
@@ -0,0 +1,87 @@
+use crate::{MountedData, MountedEvent};
+use dioxus_core::{ScopeState, TaskId};
+use euclid::Size2D;
+use std::cell::{Cell, RefCell};
+use std::rc::Rc;
+use std::sync::Arc;
+
+/// Track the size of an element's content box.
+///
+/// Pass the `onmounted` event of the element to [`UseResizeObserver::observe`]. The component re-renders when the
+/// size of the element changes, at most once per animation frame, and the element stops being observed when the
+/// component is unmounted.
+///
+/// ```rust, ignore
+/// fn Panel(cx: Scope) -> Element {
+///     let size = use_resize_observer(cx);
+///     let width = size.size().map(|size| size.width).unwrap_or_default();
+///
+///     cx.render(rsx! {
+///         div {
+///             onmounted: move |evt| size.observe(evt),
+///             if width > 600.0 {
+///                 rsx! { "wide layout" }
+///             } else {
+///                 rsx! { "narrow layout" }
+///             }
+///         }
+///     })
+/// }
+/// ```
+///
+/// Renderers that can't observe elements never report a size.
+pub fn use_resize_observer(cx: &ScopeState) -> &UseResizeObserver {
+    let state = cx.use_hook(|| UseResizeObserver {
+        size: Rc::new(Cell::new(None)),
+        element: Rc::new(RefCell::new(None)),
+        task: Cell::new(None),
+        update: cx.schedule_update(),
+    });
+
+    // A new element was mounted, so replace the task watching the old one
+    if let Some(element) = state.element.borrow_mut().take() {
+        if let Some(task) = state.task.take() {
+            cx.remove_future(task);
+        }
+
+        let size = state.size.clone();
+        let update = state.update.clone();
+        state.task.set(Some(cx.spawn(async move {
+            let mut observer = match element.observe_resize().await {
+                Ok(observer) => observer,
+                Err(_) => return,
+            };
+
+            while let Some(new) = observer.next().await {
+                // Layout often settles on the size it already had, which doesn't need another render
+                if size.get() != Some(new) {
+                    size.set(Some(new));
+                    update();
+                }
+            }
+        })));
+    }
+
+    state
+}
+
+/// The size of an element tracked by [`use_resize_observer`]
+pub struct UseResizeObserver {
+    size: Rc<Cell<Option<Size2D<f64, f64>>>>,
+    element: Rc<RefCell<Option<Rc<MountedData>>>>,
+    task: Cell<Option<TaskId>>,
+    update: Arc<dyn Fn() + Send + Sync + 'static>,
+}
+
+impl UseResizeObserver {
+    /// Start watching the element an `onmounted` event was fired for. Any element that was watched before is dropped.
+    pub fn observe(&self, event: MountedEvent) {
+        *self.element.borrow_mut() = Some(event.data);
+        (self.update)();
+    }
+
+    /// The last size of the element's content box the renderer reported, or `None` if it hasn't reported one yet
+    pub fn size(&self) -> Option<Size2D<f64, f64>> {
+        self.size.get()
+    }
+}
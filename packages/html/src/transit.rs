@@ -118,13 +118,8 @@ fn fun_name(
         // Mounted
         "mounted" => Mounted,
 
-        // OtherData => "abort" | "afterprint" | "beforeprint" | "beforeunload" | "hashchange" | "languagechange" | "message" | "offline" | "online" | "pagehide" | "pageshow" | "popstate" | "rejectionhandled" | "storage" | "unhandledrejection" | "unload" | "userproximity" | "vrdisplayactivate" | "vrdisplayblur" | "vrdisplayconnect" | "vrdisplaydeactivate" | "vrdisplaydisconnect" | "vrdisplayfocus" | "vrdisplaypointerrestricted" | "vrdisplaypointerunrestricted" | "vrdisplaypresentchange";
-        other => {
-            return Err(serde_value::DeserializerError::UnknownVariant(
-                other.to_string(),
-                &[],
-            ))
-        }
+        // Any other event, like the events custom elements dispatch
+        _ => Custom(de(data)?),
     };
 
     Ok(data)
@@ -158,6 +153,7 @@ pub enum EventData {
     Toggle(ToggleData),
     Image(ImageData),
     Mounted,
+    Custom(CustomEventData),
 }
 
 impl EventData {
@@ -181,6 +177,7 @@ impl EventData {
             EventData::Toggle(data) => Rc::new(data) as Rc<dyn Any>,
             EventData::Image(data) => Rc::new(data) as Rc<dyn Any>,
             EventData::Mounted => Rc::new(MountedData::new(())) as Rc<dyn Any>,
+            EventData::Custom(data) => Rc::new(data) as Rc<dyn Any>,
         }
     }
 }
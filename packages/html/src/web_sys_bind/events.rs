@@ -8,7 +8,7 @@ use crate::{DragData, MountedData};
 use keyboard_types::{Code, Key, Modifiers};
 use std::convert::TryInto;
 use std::str::FromStr;
-use wasm_bindgen::{JsCast, JsValue};
+use wasm_bindgen::{closure::Closure, JsCast, JsValue};
 use web_sys::{
     AnimationEvent, CompositionEvent, Event, KeyboardEvent, MouseEvent, PointerEvent, TouchEvent,
     TransitionEvent, WheelEvent,
@@ -123,6 +123,7 @@ impl From<&MouseEvent> for DragData {
     fn from(value: &MouseEvent) -> Self {
         Self {
             mouse: MouseData::from(value),
+            files: None,
         }
     }
 }
@@ -249,6 +250,63 @@ impl crate::RenderedElementBacking for web_sys::Element {
             });
         Box::pin(async { result })
     }
+
+    fn observe_intersection(
+        &self,
+    ) -> std::pin::Pin<
+        Box<dyn std::future::Future<Output = crate::MountedResult<crate::IntersectionObserver>>>,
+    > {
+        let (sender, receiver) = async_channel::unbounded();
+        let callback = Closure::<dyn FnMut(JsValue)>::new(move |entries: JsValue| {
+            for entry in js_sys::Array::from(&entries).iter() {
+                let entry: web_sys::IntersectionObserverEntry = entry.unchecked_into();
+                let _ = sender.try_send(crate::IntersectionData {
+                    is_intersecting: entry.is_intersecting(),
+                    ratio: entry.intersection_ratio(),
+                });
+            }
+        });
+
+        let result = web_sys::IntersectionObserver::new(callback.as_ref().unchecked_ref())
+            .map(|observer| {
+                observer.observe(self);
+                crate::IntersectionObserver::new(WebIntersectionObserver {
+                    observer,
+                    receiver,
+                    _callback: callback,
+                })
+            })
+            .map_err(|err| crate::MountedError::OperationFailed(Box::new(ObserverError(err))));
+        Box::pin(async { result })
+    }
+
+    fn observe_resize(
+        &self,
+    ) -> std::pin::Pin<
+        Box<dyn std::future::Future<Output = crate::MountedResult<crate::ResizeObserver>>>,
+    > {
+        let (sender, receiver) = async_channel::unbounded();
+        // The browser calls this at most once per animation frame, and only the last size of the element matters
+        let callback = Closure::<dyn FnMut(JsValue)>::new(move |entries: JsValue| {
+            if let Some(entry) = js_sys::Array::from(&entries).iter().last() {
+                let entry: web_sys::ResizeObserverEntry = entry.unchecked_into();
+                let rect = entry.content_rect();
+                let _ = sender.try_send(euclid::Size2D::new(rect.width(), rect.height()));
+            }
+        });
+
+        let result = web_sys::ResizeObserver::new(callback.as_ref().unchecked_ref())
+            .map(|observer| {
+                observer.observe(self);
+                crate::ResizeObserver::new(WebResizeObserver {
+                    observer,
+                    receiver,
+                    _callback: callback,
+                })
+            })
+            .map_err(|err| crate::MountedError::OperationFailed(Box::new(ObserverError(err))));
+        Box::pin(async { result })
+    }
 }
 
 #[derive(Debug)]
@@ -261,3 +319,62 @@ impl std::fmt::Display for FocusError {
 }
 
 impl std::error::Error for FocusError {}
+
+#[cfg(feature = "mounted")]
+struct WebIntersectionObserver {
+    observer: web_sys::IntersectionObserver,
+    receiver: async_channel::Receiver<crate::IntersectionData>,
+    _callback: Closure<dyn FnMut(JsValue)>,
+}
+
+#[cfg(feature = "mounted")]
+impl crate::IntersectionObserverBacking for WebIntersectionObserver {
+    fn next(
+        &mut self,
+    ) -> std::pin::Pin<Box<dyn std::future::Future<Output = Option<crate::IntersectionData>> + '_>>
+    {
+        Box::pin(async move { self.receiver.recv().await.ok() })
+    }
+}
+
+#[cfg(feature = "mounted")]
+impl Drop for WebIntersectionObserver {
+    fn drop(&mut self) {
+        self.observer.disconnect();
+    }
+}
+
+#[cfg(feature = "mounted")]
+struct WebResizeObserver {
+    observer: web_sys::ResizeObserver,
+    receiver: async_channel::Receiver<euclid::Size2D<f64, f64>>,
+    _callback: Closure<dyn FnMut(JsValue)>,
+}
+
+#[cfg(feature = "mounted")]
+impl crate::ResizeObserverBacking for WebResizeObserver {
+    fn next(
+        &mut self,
+    ) -> std::pin::Pin<Box<dyn std::future::Future<Output = Option<euclid::Size2D<f64, f64>>> + '_>>
+    {
+        Box::pin(async move { self.receiver.recv().await.ok() })
+    }
+}
+
+#[cfg(feature = "mounted")]
+impl Drop for WebResizeObserver {
+    fn drop(&mut self) {
+        self.observer.disconnect();
+    }
+}
+
+#[derive(Debug)]
+struct ObserverError(JsValue);
+
+impl std::fmt::Display for ObserverError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "failed to observe element {:?}", self.0)
+    }
+}
+
+impl std::error::Error for ObserverError {}
@@ -36,6 +36,8 @@ export function setAttributeInner(node, field, value, ns) {
       node.style = {};
     }
     node.style[name] = value;
+  } else if (ns === "prop") {
+    node[name] = value;
   } else if (ns != null && ns != undefined) {
     node.setAttributeNS(ns, name, value);
   } else {
@@ -63,6 +65,15 @@ export function setAttributeInner(node, field, value, ns) {
       case "dangerous_inner_html":
         node.innerHTML = value;
         break;
+      case "selection_start":
+        node.selectionStart = value;
+        break;
+      case "selection_end":
+        node.selectionEnd = value;
+        break;
+      case "selection_direction":
+        node.selectionDirection = value;
+        break;
       default:
         // https://github.com/facebook/react/blob/8b88ac2592c5f555f315f9440cbb665dd1e7457a/packages/react-dom/src/shared/DOMProperty.js#L352-L364
         if (!truthy(value) && bool_attrs.hasOwnProperty(name)) {
@@ -6,9 +6,27 @@ class ListenerMap {
     this.global = {};
     // non bubbling events listen at the element the listener was created at
     this.local = {};
+    // delegated events listen once at the root for every element
+    this.delegated = {};
     this.root = root;
   }
 
+  delegate(event_name, bubbles, handler) {
+    if (this.delegated[event_name] === undefined) {
+      // events that don't bubble only reach the root while capturing
+      this.delegated[event_name] = { handler, capture: !bubbles };
+      this.root.addEventListener(event_name, handler, !bubbles);
+    }
+  }
+
+  undelegate(event_name) {
+    const delegated = this.delegated[event_name];
+    if (delegated !== undefined) {
+      this.root.removeEventListener(event_name, delegated.handler, delegated.capture);
+      delete this.delegated[event_name];
+    }
+  }
+
   create(event_name, element, handler, bubbles) {
     if (bubbles) {
       if (this.global[event_name] === undefined) {
@@ -41,8 +59,9 @@ class ListenerMap {
       }
     } else {
       const id = element.getAttribute("data-dioxus-id");
+      const handler = this.local[id][event_name];
       delete this.local[id][event_name];
-      if (this.local[id].length === 0) {
+      if (Object.keys(this.local[id]).length === 0) {
         delete this.local[id];
       }
       element.removeEventListener(event_name, handler);
@@ -71,6 +90,8 @@ class Interpreter {
     this.handlers = {};
     this.templates = {};
     this.lastNodeWasText = false;
+    this.observers = {};
+    this.nextObserver = 0;
   }
   top() {
     return this.stack[this.stack.length - 1];
@@ -82,6 +103,8 @@ class Interpreter {
     this.AppendChildren(this.stack.length - 1);
   }
   SetNode(id, node) {
+    // delegated events find the element they fired on through this id
+    node.dioxusId = id;
     this.nodes[id] = node;
   }
   PushRoot(root) {
@@ -128,24 +151,30 @@ class Interpreter {
   }
   CreateTextNode(text, root) {
     const node = document.createTextNode(text);
-    this.nodes[root] = node;
+    this.SetNode(root, node);
     this.stack.push(node);
   }
   CreatePlaceholder(root) {
     let el = document.createElement("pre");
     el.hidden = true;
     this.stack.push(el);
-    this.nodes[root] = el;
+    this.SetNode(root, el);
   }
   NewEventListener(event_name, root, bubbles, handler) {
     const element = this.nodes[root];
+    // bubbling events are delegated to a single listener on the root, which finds the target through this id
     element.setAttribute("data-dioxus-id", `${root}`);
+    element.listening = (element.listening || 0) + 1;
     this.listeners.create(event_name, element, handler, bubbles);
   }
   RemoveEventListener(root, event_name, bubbles) {
     const element = this.nodes[root];
-    element.removeAttribute(`data-dioxus-id`);
     this.listeners.remove(element, event_name, bubbles);
+    // other listeners on this element still need the id to be routed
+    element.listening -= 1;
+    if (element.listening === 0) {
+      element.removeAttribute(`data-dioxus-id`);
+    }
   }
   SetText(root, text) {
     this.nodes[root].textContent = text;
@@ -180,6 +209,8 @@ class Interpreter {
       }
     } else if (ns == "style") {
       node.style.removeProperty(name);
+    } else if (ns == "prop") {
+      node[field] = undefined;
     } else {
       node.removeAttributeNS(ns, field);
     }
@@ -223,6 +254,54 @@ class Interpreter {
     return true;
   }
 
+  /// Report changes in the visibility of the element until StopObserving is called with the returned handle
+  ObserveIntersection(id, callback) {
+    const node = this.nodes[id];
+    if (!node) {
+      return null;
+    }
+    const observer = new IntersectionObserver((entries) => {
+      for (const entry of entries) {
+        callback({
+          is_intersecting: entry.isIntersecting,
+          ratio: entry.intersectionRatio,
+        });
+      }
+    });
+    return this.AddObserver(observer, node);
+  }
+
+  /// Report the size of the element's content box until StopObserving is called with the returned handle
+  ObserveResize(id, callback) {
+    const node = this.nodes[id];
+    if (!node) {
+      return null;
+    }
+    // Called at most once per animation frame, only the last size of the element matters
+    const observer = new ResizeObserver((entries) => {
+      const entry = entries[entries.length - 1];
+      if (entry) {
+        callback([entry.contentRect.width, entry.contentRect.height]);
+      }
+    });
+    return this.AddObserver(observer, node);
+  }
+
+  AddObserver(observer, node) {
+    observer.observe(node);
+    const handle = this.nextObserver++;
+    this.observers[handle] = observer;
+    return handle;
+  }
+
+  StopObserving(handle) {
+    const observer = this.observers[handle];
+    if (observer) {
+      observer.disconnect();
+      delete this.observers[handle];
+    }
+  }
+
   handleEdits(edits) {
     for (let template of edits.templates) {
       this.SaveTemplate(template);
@@ -237,13 +316,21 @@ class Interpreter {
 
   SaveTemplate(template) {
     let roots = [];
+    // elements of templates with a style are marked so the style can be scoped to them
+    let scope = template.style != null ? template.name : null;
     for (let root of template.roots) {
-      roots.push(this.MakeTemplateNode(root));
+      roots.push(this.MakeTemplateNode(root, scope));
     }
     this.templates[template.name] = roots;
   }
 
-  MakeTemplateNode(node) {
+  InjectStyle(name, style) {
+    let el = document.createElement("style");
+    el.textContent = `@scope ([data-dioxus-scope="${name}"]) { ${style} }`;
+    document.head.appendChild(el);
+  }
+
+  MakeTemplateNode(node, scope) {
     switch (node.type) {
       case "Text":
         return document.createTextNode(node.text);
@@ -268,15 +355,19 @@ class Interpreter {
           }
         }
 
+        if (scope != null) {
+          el.setAttribute("data-dioxus-scope", scope);
+        }
+
         for (let child of node.children) {
-          el.appendChild(this.MakeTemplateNode(child));
+          el.appendChild(this.MakeTemplateNode(child, scope));
         }
 
         return el;
     }
   }
   AssignId(path, id) {
-    this.nodes[id] = this.LoadChild(path);
+    this.SetNode(id, this.LoadChild(path));
   }
   LoadChild(path) {
     // iterate through each number and get that child
@@ -300,7 +391,7 @@ class Interpreter {
       node = text;
     }
 
-    this.nodes[id] = node;
+    this.SetNode(id, node);
   }
   ReplacePlaceholder(path, m) {
     let els = this.stack.splice(this.stack.length - m);
@@ -309,7 +400,7 @@ class Interpreter {
   }
   LoadTemplate(name, index, id) {
     let node = this.templates[name][index].cloneNode(true);
-    this.nodes[id] = node;
+    this.SetNode(id, node);
     this.stack.push(node);
   }
   handleEdit(edit) {
@@ -317,6 +408,11 @@ class Interpreter {
       case "AppendChildren":
         this.AppendChildren(edit.m);
         break;
+      case "AppendToAnchor":
+        document
+          .getElementById(edit.anchor)
+          .append(...this.stack.splice(this.stack.length - edit.m));
+        break;
       case "AssignId":
         this.AssignId(edit.path, edit.id);
         break;
@@ -332,9 +428,35 @@ class Interpreter {
       case "LoadTemplate":
         this.LoadTemplate(edit.name, edit.index, edit.id);
         break;
+      case "CreateElementWithAttrs":
+        this.LoadTemplate(edit.name, edit.index, edit.id);
+        for (const attr of edit.attrs) {
+          this.SetAttribute(edit.id, attr.name, attr.value, attr.ns);
+        }
+        for (const name of edit.listeners) {
+          this.handleEdit({ type: "NewEventListener", name, id: edit.id });
+        }
+        break;
       case "PushRoot":
         this.PushRoot(edit.id);
         break;
+      case "CaptureScroll": {
+        const node = this.nodes[edit.id];
+        window.ipc.postMessage(
+          serializeIpcMessage("report_scroll", {
+            id: edit.id,
+            x: node.scrollLeft,
+            y: node.scrollTop,
+          })
+        );
+        break;
+      }
+      case "RestoreScroll":
+        this.nodes[edit.id].scrollTo(edit.x, edit.y);
+        break;
+      case "InjectStyle":
+        this.InjectStyle(edit.name, edit.style);
+        break;
       case "ReplaceWith":
         this.ReplaceWith(edit.id, edit.m);
         break;
@@ -360,7 +482,17 @@ class Interpreter {
         this.RemoveAttribute(edit.id, edit.name, edit.ns);
         break;
       case "RemoveEventListener":
-        this.RemoveEventListener(edit.id, edit.name);
+        this.RemoveEventListener(edit.id, edit.name, event_bubbles(edit.name));
+        break;
+      case "RegisterDelegated": {
+        const bubbles = event_bubbles(edit.name);
+        this.listeners.delegate(edit.name, bubbles, (event) => {
+          handler(event, edit.name, bubbles, this.config, true);
+        });
+        break;
+      }
+      case "UnregisterDelegated":
+        this.listeners.undelegate(edit.name);
         break;
       case "NewEventListener":
         let bubbles = event_bubbles(edit.name);
@@ -387,7 +519,7 @@ class Interpreter {
 
 // this handler is only provided on the desktop and liveview implementations since this
 // method is not used by the web implementation
-function handler(event, name, bubbles, config) {
+function handler(event, name, bubbles, config, delegated) {
   let target = event.target;
   if (target != null) {
     let preventDefaultRequests = null;
@@ -429,7 +561,10 @@ function handler(event, name, bubbles, config) {
       }
     }
 
-    const realId = find_real_id(target);
+    // Listeners for events that don't bubble are on the element itself, which may be an ancestor of the target
+    const realId = delegated
+      ? find_delegated_id(target, bubbles)
+      : find_real_id(bubbles ? target : event.currentTarget);
 
     if (
       preventDefaultRequests &&
@@ -506,6 +641,20 @@ function find_real_id(target) {
   return realId;
 }
 
+// delegated events go to the closest element with an id, or only to the target if the event doesn't bubble
+function find_delegated_id(target, bubbles) {
+  while (target !== null) {
+    if (target instanceof Element && target.dioxusId !== undefined) {
+      return target.dioxusId;
+    }
+    if (!bubbles) {
+      return null;
+    }
+    target = target.parentElement;
+  }
+  return null;
+}
+
 function get_mouse_data(event) {
   const {
     altKey,
@@ -778,6 +927,9 @@ function serialize_event(event) {
       return {};
     }
     default: {
+      if (event instanceof CustomEvent) {
+        return { detail: JSON.stringify(event.detail ?? null) };
+      }
       return {};
     }
   }
@@ -959,5 +1111,7 @@ function event_bubbles(event) {
       return false;
   }
 
-  return true;
+  // Events that aren't built in, like the events custom elements dispatch, often don't bubble. They are listened for
+  // on the element itself so they are still caught.
+  return false;
 }
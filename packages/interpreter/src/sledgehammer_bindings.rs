@@ -11,10 +11,28 @@ mod js {
             this.global = {};
             // non bubbling events listen at the element the listener was created at
             this.local = {};
+            // delegated events listen once at the root for every element
+            this.delegated = {};
             this.root = null;
             this.handler = null;
         }
 
+        delegate(event_name, bubbles) {
+            if (this.delegated[event_name] === undefined) {
+                // events that don't bubble only reach the root while capturing
+                this.delegated[event_name] = !bubbles;
+                this.root.addEventListener(event_name, this.handler, !bubbles);
+            }
+        }
+
+        undelegate(event_name) {
+            const capture = this.delegated[event_name];
+            if (capture !== undefined) {
+                this.root.removeEventListener(event_name, this.handler, capture);
+                delete this.delegated[event_name];
+            }
+        }
+
         create(event_name, element, bubbles) {
             if (bubbles) {
                 if (this.global[event_name] === undefined) {
@@ -30,6 +48,7 @@ mod js {
                 if (!this.local[id]) {
                     this.local[id] = {};
                 }
+                this.local[id][event_name] = true;
                 element.addEventListener(event_name, this.handler);
             }
         }
@@ -38,14 +57,14 @@ mod js {
             if (bubbles) {
                 this.global[event_name].active--;
                 if (this.global[event_name].active === 0) {
-                    this.root.removeEventListener(event_name, this.global[event_name].callback);
+                    this.root.removeEventListener(event_name, this.handler);
                     delete this.global[event_name];
                 }
             }
             else {
                 const id = element.getAttribute("data-dioxus-id");
                 delete this.local[id][event_name];
-                if (this.local[id].length === 0) {
+                if (Object.keys(this.local[id]).length === 0) {
                     delete this.local[id];
                 }
                 element.removeEventListener(event_name, this.handler);
@@ -65,11 +84,14 @@ mod js {
                 node.style = {};
             }
             node.style[name] = value;
+        } else if (ns === "prop") {
+            node[name] = value;
         } else if (ns !== null && ns !== undefined && ns !== "") {
             node.setAttributeNS(ns, name, value);
         } else {
             switch (name) {
                 case "value":
+                    SetControlled(node, name, value);
                     if (value !== node.value) {
                         node.value = value;
                     }
@@ -78,6 +100,7 @@ mod js {
                     node.defaultValue = value;
                     break;
                 case "checked":
+                    SetControlled(node, name, value);
                     node.checked = truthy(value);
                     break;
                 case "initial_checked":
@@ -92,6 +115,15 @@ mod js {
                 case "dangerous_inner_html":
                     node.innerHTML = value;
                     break;
+                case "selection_start":
+                    node.selectionStart = value;
+                    break;
+                case "selection_end":
+                    node.selectionEnd = value;
+                    break;
+                case "selection_direction":
+                    node.selectionDirection = value;
+                    break;
                 default:
                     // https://github.com/facebook/react/blob/8b88ac2592c5f555f315f9440cbb665dd1e7457a/packages/react-dom/src/shared/DOMProperty.js#L352-L364
                     if (!truthy(value) && bool_attrs.hasOwnProperty(name)) {
@@ -102,6 +134,13 @@ mod js {
             }
         }
     }
+    // controlled fields remember the value the app set, so it can be written back over input the app rejected
+    function SetControlled(node, name, value) {
+        if (node.controlled === undefined) {
+            node.controlled = {};
+        }
+        node.controlled[name] = value;
+    }
     function LoadChild(ptr, len) {
         // iterate through each number and get that child
         node = stack[stack.length - 1];
@@ -114,6 +153,23 @@ mod js {
         }
         return node;
     }
+    // walk a path packed into a single integer: one nibble per child index, and the length in the last nibble
+    function LoadChildPacked(path) {
+        node = stack[stack.length - 1];
+        ptr_end = path >>> 28;
+        for (k = 0; k < ptr_end; k++) {
+            end = (path >>> (k * 4)) & 0xF;
+            for (node = node.firstChild; end > 0; end--) {
+                node = node.nextSibling;
+            }
+        }
+        return node;
+    }
+    // delegated events find the element they fired on through the id set here
+    function SetNode(id, node) {
+        node.dioxusId = id;
+        nodes[id] = node;
+    }
     const listeners = new ListenerMap();
     let nodes = [];
     let stack = [];
@@ -124,11 +180,18 @@ mod js {
         templates[tmpl_id] = nodes;
     }
     export function set_node(id, node) {
-        nodes[id] = node;
+        SetNode(id, node);
     }
     export function get_node(id) {
         return nodes[id];
     }
+    export function reassert_controlled(node) {
+        if (node.controlled !== undefined) {
+            for (const name in node.controlled) {
+                SetAttributeInner(node, name, node.controlled[name], null);
+            }
+        }
+    }
     export function initilize(root, handler) {
         listeners.handler = handler;
         nodes = [root];
@@ -186,6 +249,9 @@ mod js {
         #[wasm_bindgen]
         pub fn get_node(id: u32) -> Node;
 
+        #[wasm_bindgen]
+        pub fn reassert_controlled(node: Node);
+
         #[wasm_bindgen]
         pub fn initilize(root: Node, handler: &Function);
     }
@@ -199,6 +265,9 @@ mod js {
     fn append_children(id: u32, many: u32) {
         "{AppendChildren($id$, $many$);}"
     }
+    fn append_to_anchor(anchor: &str, many: u32) {
+        "{document.getElementById($anchor$).append(...stack.splice(stack.length-$many$));}"
+    }
     fn pop_root() {
         "{stack.pop();}"
     }
@@ -218,16 +287,22 @@ mod js {
         "{stack.push(document.createTextNode($text$));}"
     }
     fn create_text_node(text: &str, id: u32) {
-        "{node = document.createTextNode($text$); nodes[$id$] = node; stack.push(node);}"
+        "{node = document.createTextNode($text$); SetNode($id$, node); stack.push(node);}"
     }
     fn create_placeholder(id: u32) {
-        "{node = document.createElement('pre'); node.hidden = true; stack.push(node); nodes[$id$] = node;}"
+        "{node = document.createElement('pre'); node.hidden = true; stack.push(node); SetNode($id$, node);}"
     }
     fn new_event_listener(event_name: &str<u8, evt>, id: u32, bubbles: u8) {
         r#"node = nodes[id]; if(node.listening){node.listening += 1;}else{node.listening = 1;} node.setAttribute('data-dioxus-id', `\${id}`); listeners.create($event_name$, node, $bubbles$);"#
     }
     fn remove_event_listener(event_name: &str<u8, evt>, id: u32, bubbles: u8) {
-        "{node = nodes[$id$]; node.listening -= 1; node.removeAttribute('data-dioxus-id'); listeners.remove(node, $event_name$, $bubbles$);}"
+        "{node = nodes[$id$]; listeners.remove(node, $event_name$, $bubbles$); node.listening -= 1; if (node.listening === 0) { node.removeAttribute('data-dioxus-id'); }}"
+    }
+    fn register_delegated(event_name: &str<u8, evt>, bubbles: u8) {
+        "{listeners.delegate($event_name$, $bubbles$);}"
+    }
+    fn unregister_delegated(event_name: &str<u8, evt>) {
+        "{listeners.undelegate($event_name$);}"
     }
     fn set_text(id: u32, text: &str) {
         "{nodes[$id$].textContent = $text$;}"
@@ -241,9 +316,11 @@ mod js {
             if (!ns) {
                 switch (field) {
                     case "value":
+                        if (node.controlled !== undefined) { delete node.controlled.value; }
                         node.value = "";
                         break;
                     case "checked":
+                        if (node.controlled !== undefined) { delete node.controlled.checked; }
                         node.checked = false;
                         break;
                     case "selected":
@@ -258,13 +335,18 @@ mod js {
                 }
             } else if (ns == "style") {
                 node.style.removeProperty(name);
+            } else if (ns == "prop") {
+                node[field] = undefined;
             } else {
                 node.removeAttributeNS(ns, field);
             }
         }"#
     }
     fn assign_id(ptr: u32, len: u8, id: u32) {
-        "{nodes[$id$] = LoadChild($ptr$, $len$);}"
+        "{SetNode($id$, LoadChild($ptr$, $len$));}"
+    }
+    fn assign_id_packed(path: u32, id: u32) {
+        "{SetNode($id$, LoadChildPacked($path$));}"
     }
     fn hydrate_text(ptr: u32, len: u8, value: &str, id: u32) {
         r#"{
@@ -276,13 +358,29 @@ mod js {
                 node.replaceWith(text);
                 node = text;
             }
-            nodes[$id$] = node;
+            SetNode($id$, node);
+        }"#
+    }
+    fn hydrate_text_packed(path: u32, value: &str, id: u32) {
+        r#"{
+            node = LoadChildPacked($path$);
+            if (node.nodeType == Node.TEXT_NODE) {
+                node.textContent = value;
+            } else {
+                let text = document.createTextNode(value);
+                node.replaceWith(text);
+                node = text;
+            }
+            SetNode($id$, node);
         }"#
     }
     fn replace_placeholder(ptr: u32, len: u8, n: u32) {
         "{els = stack.splice(stack.length - $n$); node = LoadChild($ptr$, $len$); node.replaceWith(...els);}"
     }
+    fn replace_placeholder_packed(path: u32, n: u32) {
+        "{els = stack.splice(stack.length - $n$); node = LoadChildPacked($path$); node.replaceWith(...els);}"
+    }
     fn load_template(tmpl_id: u32, index: u32, id: u32) {
-        "{node = templates[$tmpl_id$][$index$].cloneNode(true); nodes[$id$] = node; stack.push(node);}"
+        "{node = templates[$tmpl_id$][$index$].cloneNode(true); SetNode($id$, node); stack.push(node);}"
     }
 }
@@ -50,11 +50,7 @@ async fn main() {
             )
             .route(
                 "/ws",
-                get(move |ws: WebSocketUpgrade| async move {
-                    ws.on_upgrade(move |socket| async move {
-                        _ = view.launch(dioxus_liveview::axum_socket(socket), app).await;
-                    })
-                }),
+                get(move |ws: WebSocketUpgrade| async move { view.axum_upgrade(ws, app) }),
             );
 
     println!("Listening on http://{addr}");
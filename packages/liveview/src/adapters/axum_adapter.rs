@@ -1,5 +1,11 @@
-use crate::{LiveViewError, LiveViewSocket};
-use axum::extract::ws::{Message, WebSocket};
+use crate::{index_html, LiveViewError, LiveViewPool, LiveViewSocket};
+use axum::{
+    extract::ws::{Message, WebSocket, WebSocketUpgrade},
+    response::{Html, Response},
+    routing::get,
+    Router,
+};
+use dioxus_core::{Element, Scope};
 use futures_util::{SinkExt, StreamExt};
 
 /// Convert a warp websocket into a LiveViewSocket
@@ -20,5 +26,58 @@ fn transform_rx(message: Result<Message, axum::Error>) -> Result<Vec<u8>, LiveVi
 }
 
 async fn transform_tx(message: Vec<u8>) -> Result<Message, axum::Error> {
-    Ok(Message::Text(String::from_utf8_lossy(&message).to_string()))
+    Ok(match String::from_utf8(message) {
+        Ok(text) => Message::Text(text),
+        Err(binary) => Message::Binary(binary.into_bytes()),
+    })
+}
+
+impl LiveViewPool {
+    /// Upgrade a websocket request and run the app on it until the client disconnects
+    ///
+    /// The app sends its edits over the socket and handles the events the client sends back.
+    ///
+    /// ```rust, ignore
+    /// let pool = LiveViewPool::new();
+    /// let router = Router::new().route("/ws", get(move |ws: WebSocketUpgrade| async move { pool.axum_upgrade(ws, app) }));
+    /// ```
+    pub fn axum_upgrade(&self, ws: WebSocketUpgrade, app: fn(Scope) -> Element) -> Response {
+        self.axum_upgrade_with_props(ws, app, ())
+    }
+
+    /// Upgrade a websocket request and run the app with the props on it until the client disconnects
+    pub fn axum_upgrade_with_props<T: Send + 'static>(
+        &self,
+        ws: WebSocketUpgrade,
+        app: fn(Scope<T>) -> Element,
+        props: T,
+    ) -> Response {
+        let pool = self.clone();
+        ws.on_upgrade(move |socket| async move {
+            if let Err(err) = pool
+                .launch_with_props(axum_socket(socket), app, props)
+                .await
+            {
+                tracing::warn!("LiveView app stopped: {}", err);
+            }
+        })
+    }
+}
+
+/// Create a router that serves the app: the page that boots it at `/`, and the websocket it connects to at `/ws`
+///
+/// ```rust, ignore
+/// axum::Server::bind(&addr)
+///     .serve(dioxus_liveview::axum_router("Counter", app).into_make_service())
+///     .await?;
+/// ```
+pub fn axum_router(title: &str, app: fn(Scope) -> Element) -> Router {
+    let page = Html(index_html(title, "/ws"));
+    let pool = LiveViewPool::new();
+    Router::new()
+        .route("/", get(move || async move { page }))
+        .route(
+            "/ws",
+            get(move |ws: WebSocketUpgrade| async move { pool.axum_upgrade(ws, app) }),
+        )
 }
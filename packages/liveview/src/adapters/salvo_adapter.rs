@@ -19,5 +19,8 @@ fn transform_rx(message: Result<Message, salvo::Error>) -> Result<Vec<u8>, LiveV
 }
 
 async fn transform_tx(message: Vec<u8>) -> Result<Message, salvo::Error> {
-    Ok(Message::text(String::from_utf8_lossy(&message).to_string()))
+    Ok(match String::from_utf8(message) {
+        Ok(text) => Message::text(text),
+        Err(binary) => Message::binary(binary.into_bytes()),
+    })
 }
@@ -21,5 +21,8 @@ fn transform_rx(message: Result<Message, warp::Error>) -> Result<Vec<u8>, LiveVi
 }
 
 async fn transform_tx(message: Vec<u8>) -> Result<Message, warp::Error> {
-    Ok(Message::text(String::from_utf8_lossy(&message).to_string()))
+    Ok(match String::from_utf8(message) {
+        Ok(text) => Message::text(text),
+        Err(binary) => Message::binary(binary.into_bytes()),
+    })
 }
@@ -148,3 +148,25 @@ pub fn interpreter_glue(url_or_path: &str) -> String {
     "#
     )
 }
+
+/// Render a page that boots a LiveView app, with the element the app is mounted in and the script from
+/// [`interpreter_glue`] that connects it to the websocket at `url_or_path`
+///
+/// ```
+/// let page = dioxus_liveview::index_html("Counter", "/ws");
+/// ```
+pub fn index_html(title: &str, url_or_path: &str) -> String {
+    let title = title
+        .replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;");
+    let glue = interpreter_glue(url_or_path);
+    format!(
+        r#"<!DOCTYPE html>
+<html>
+    <head><title>{title}</title></head>
+    <body><div id="main"></div></body>
+    {glue}
+</html>"#
+    )
+}
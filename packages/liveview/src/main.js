@@ -7,36 +7,75 @@ function main() {
 
 class IPC {
   constructor(root) {
-    // connect to the websocket
+    this.root = root;
     window.interpreter = new Interpreter(root, new InterpreterConfig(false));
 
+    // The session this page is showing, so the server can resume it if the connection drops
+    this.session = null;
+    // The last message of the session this page applied, so the server only replays the ones after it
+    this.lastApplied = 0;
+    this.retryDelay = 500;
+    this.connect();
+  }
+
+  connect() {
+    // connect to the websocket
     let ws = new WebSocket(WS_ADDR);
+    ws.binaryType = "arraybuffer";
+    let pingInterval = null;
 
     function ping() {
       ws.send("__ping__");
     }
 
     ws.onopen = () => {
+      this.retryDelay = 500;
       // we ping every 30 seconds to keep the websocket alive
-      setInterval(ping, 30000);
-      ws.send(serializeIpcMessage("initialize"));
+      pingInterval = setInterval(ping, 30000);
+      ws.send(
+        serializeIpcMessage("initialize", {
+          session: this.session,
+          last_applied: this.lastApplied,
+          encoding: "msgpack",
+        })
+      );
     };
 
-    ws.onerror = (err) => {
-      // todo: retry the connection
+    ws.onclose = () => {
+      clearInterval(pingInterval);
+      // try to reconnect, waiting longer after every failed attempt
+      setTimeout(() => this.connect(), this.retryDelay);
+      this.retryDelay = Math.min(this.retryDelay * 2, 10000);
     };
 
     ws.onmessage = (message) => {
       // Ignore pongs
       if (message.data != "__pong__") {
-        const event = JSON.parse(message.data);
+        const event =
+          typeof message.data === "string"
+            ? JSON.parse(message.data)
+            : decodeMessagePack(message.data);
         switch (event.type) {
+          case "session":
+            // The old session expired or lost messages this page missed, so the server renders the whole app again
+            // in a new one
+            if (event.data.fresh) {
+              if (this.session !== null) {
+                this.reset();
+              }
+              this.lastApplied = 0;
+            }
+            this.session = event.data.token;
+            break;
           case "edits":
-            let edits = event.data;
-            window.interpreter.handleEdits(edits);
+            if (this.apply(event.data.seq)) {
+              window.interpreter.handleEdits(event.data.edits);
+            }
             break;
           case "query":
-            Function("Eval", `"use strict";${event.data};`)();
+            if (this.apply(event.data.seq)) {
+              Function("Eval", `"use strict";${event.data.script};`)();
+            }
             break;
         }
       }
@@ -45,7 +84,143 @@ class IPC {
     this.ws = ws;
   }
 
+  // Mark a message of the session as applied. Returns false if it was already applied before the connection dropped.
+  apply(seq) {
+    if (seq <= this.lastApplied) {
+      return false;
+    }
+    this.lastApplied = seq;
+    return true;
+  }
+
+  // Throw away everything the last session rendered, including its listeners
+  reset() {
+    const root = this.root.cloneNode(false);
+    this.root.replaceWith(root);
+    this.root = root;
+    window.interpreter = new Interpreter(root, new InterpreterConfig(false));
+  }
+
   postMessage(msg) {
-    this.ws.send(msg);
+    if (this.ws.readyState === WebSocket.OPEN) {
+      this.ws.send(msg);
+    }
   }
 }
+
+// Decode a binary message from the server. The first byte only marks the message as binary, the rest is MessagePack.
+function decodeMessagePack(buffer) {
+  const view = new DataView(buffer);
+  const utf8 = new TextDecoder();
+  let offset = 1;
+
+  function bytes(length) {
+    const slice = new Uint8Array(buffer, offset, length);
+    offset += length;
+    return slice;
+  }
+
+  function array(length) {
+    const items = new Array(length);
+    for (let i = 0; i < length; i++) {
+      items[i] = value();
+    }
+    return items;
+  }
+
+  function map(length) {
+    const object = {};
+    for (let i = 0; i < length; i++) {
+      const key = value();
+      object[key] = value();
+    }
+    return object;
+  }
+
+  function value() {
+    const byte = view.getUint8(offset++);
+    if (byte < 0x80) return byte;
+    if (byte < 0x90) return map(byte & 0x0f);
+    if (byte < 0xa0) return array(byte & 0x0f);
+    if (byte < 0xc0) return utf8.decode(bytes(byte & 0x1f));
+    if (byte >= 0xe0) return byte - 0x100;
+
+    let result;
+    switch (byte) {
+      case 0xc0:
+        return null;
+      case 0xc2:
+        return false;
+      case 0xc3:
+        return true;
+      case 0xc4:
+        return bytes(view.getUint8(offset++));
+      case 0xc5:
+        offset += 2;
+        return bytes(view.getUint16(offset - 2));
+      case 0xc6:
+        offset += 4;
+        return bytes(view.getUint32(offset - 4));
+      case 0xca:
+        result = view.getFloat32(offset);
+        offset += 4;
+        return result;
+      case 0xcb:
+        result = view.getFloat64(offset);
+        offset += 8;
+        return result;
+      case 0xcc:
+        return view.getUint8(offset++);
+      case 0xcd:
+        result = view.getUint16(offset);
+        offset += 2;
+        return result;
+      case 0xce:
+        result = view.getUint32(offset);
+        offset += 4;
+        return result;
+      case 0xcf:
+        result = Number(view.getBigUint64(offset));
+        offset += 8;
+        return result;
+      case 0xd0:
+        return view.getInt8(offset++);
+      case 0xd1:
+        result = view.getInt16(offset);
+        offset += 2;
+        return result;
+      case 0xd2:
+        result = view.getInt32(offset);
+        offset += 4;
+        return result;
+      case 0xd3:
+        result = Number(view.getBigInt64(offset));
+        offset += 8;
+        return result;
+      case 0xd9:
+        return utf8.decode(bytes(view.getUint8(offset++)));
+      case 0xda:
+        offset += 2;
+        return utf8.decode(bytes(view.getUint16(offset - 2)));
+      case 0xdb:
+        offset += 4;
+        return utf8.decode(bytes(view.getUint32(offset - 4)));
+      case 0xdc:
+        offset += 2;
+        return array(view.getUint16(offset - 2));
+      case 0xdd:
+        offset += 4;
+        return array(view.getUint32(offset - 4));
+      case 0xde:
+        offset += 2;
+        return map(view.getUint16(offset - 2));
+      case 0xdf:
+        offset += 4;
+        return map(view.getUint32(offset - 4));
+      default:
+        throw new Error(`unsupported MessagePack type ${byte}`);
+    }
+  }
+
+  return value();
+}
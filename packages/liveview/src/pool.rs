@@ -4,16 +4,34 @@ use crate::{
     query::{QueryEngine, QueryResult},
     LiveViewError,
 };
-use dioxus_core::{prelude::*, Mutations};
+use dioxus_core::{prelude::*, ElementId, Mutations};
 use dioxus_html::{EventData, HtmlEvent, MountedData};
-use futures_util::{pin_mut, SinkExt, StreamExt};
-use serde::Serialize;
-use std::{rc::Rc, time::Duration};
+use futures_util::{Sink, SinkExt, Stream, StreamExt};
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::{HashMap, VecDeque},
+    pin::Pin,
+    rc::Rc,
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+use tokio::sync::{
+    mpsc::{error::SendError, unbounded_channel, UnboundedReceiver, UnboundedSender},
+    oneshot,
+};
 use tokio_util::task::LocalPoolHandle;
 
+/// How long a session waits for its client to reconnect before it is dropped, unless configured otherwise
+const DEFAULT_SESSION_TTL: Duration = Duration::from_secs(30);
+
+/// How many of the latest messages a session keeps to replay to a client that reconnects
+const REPLAY_LIMIT: usize = 256;
+
 #[derive(Clone)]
 pub struct LiveViewPool {
     pub(crate) pool: LocalPoolHandle,
+    sessions: Sessions,
+    session_ttl: Duration,
 }
 
 impl Default for LiveViewPool {
@@ -26,9 +44,21 @@ impl LiveViewPool {
     pub fn new() -> Self {
         LiveViewPool {
             pool: LocalPoolHandle::new(16),
+            sessions: Default::default(),
+            session_ttl: DEFAULT_SESSION_TTL,
         }
     }
 
+    /// Set how long a session is kept alive after its client disconnects
+    ///
+    /// A client that reconnects within this time resumes the same VirtualDom and receives the edits it missed. After
+    /// that, the session is dropped and a reconnecting client gets a freshly rendered app instead. A client that missed
+    /// more messages than the session keeps also gets a freshly rendered app.
+    pub fn with_session_ttl(mut self, ttl: Duration) -> Self {
+        self.session_ttl = ttl;
+        self
+    }
+
     pub async fn launch(
         &self,
         ws: impl LiveViewSocket,
@@ -47,15 +77,85 @@ impl LiveViewPool {
             .await
     }
 
+    /// Run the app on the socket until the client disconnects
+    ///
+    /// If the client asks to resume a session that is still alive, the socket is handed to that session and
+    /// `make_app` is never called. Otherwise a new session is created for the client.
     pub async fn launch_virtualdom<F: FnOnce() -> VirtualDom + Send + 'static>(
         &self,
         ws: impl LiveViewSocket,
         make_app: F,
     ) -> Result<(), LiveViewError> {
-        match self.pool.spawn_pinned(move || run(make_app(), ws)).await {
-            Ok(Ok(_)) => Ok(()),
-            Ok(Err(e)) => Err(e),
-            Err(_) => Err(LiveViewError::SendingFailed),
+        let mut ws: BoxedSocket = Box::pin(ws);
+
+        // The client tells us which session it had and how to encode messages in the first message it sends
+        let initialize = match ws.next().await {
+            Some(Ok(message)) => match serde_json::from_slice(&message) {
+                Ok(IpcMessage::Initialize(initialize)) => initialize,
+                _ => Initialize::default(),
+            },
+            Some(Err(err)) => return Err(err),
+            None => return Ok(()),
+        };
+
+        if let Some(token) = &initialize.session {
+            match self.resume(token, ws, initialize.last_applied).await {
+                Ok(result) => return result,
+                // The session expired or can't replay what the client missed, so the client starts over
+                Err(rejected) => ws = rejected,
+            }
+        }
+
+        let token = new_token();
+        let (connections_tx, connections_rx) = unbounded_channel();
+        let (connection, done) = Connection::new(ws, 0);
+        _ = connections_tx.send(connection);
+        self.sessions
+            .lock()
+            .unwrap()
+            .insert(token.clone(), connections_tx);
+
+        // The session outlives this connection, so we only wait for the client to disconnect
+        let sessions = self.sessions.clone();
+        let ttl = self.session_ttl;
+        let encoding = initialize.encoding;
+        drop(self.pool.spawn_pinned(move || {
+            run_session(
+                Session::new(make_app(), token, encoding),
+                connections_rx,
+                sessions,
+                ttl,
+            )
+        }));
+
+        match done.await {
+            Ok(ConnectionEnd::Closed(result)) => result,
+            _ => Err(LiveViewError::SendingFailed),
+        }
+    }
+
+    /// Hand the socket to the session with the token and wait until the client disconnects
+    ///
+    /// Gives the socket back if that session expired or no longer has every message the client missed.
+    async fn resume(
+        &self,
+        token: &str,
+        ws: BoxedSocket,
+        last_applied: u64,
+    ) -> Result<Result<(), LiveViewError>, BoxedSocket> {
+        let (connection, done) = Connection::new(ws, last_applied);
+        // The session removes itself while holding the lock, so a connection is never sent to a session that stopped
+        match self.sessions.lock().unwrap().get(token) {
+            Some(session) => session
+                .send(connection)
+                .map_err(|SendError(connection)| connection.ws)?,
+            None => return Err(connection.ws),
+        }
+
+        match done.await {
+            Ok(ConnectionEnd::Closed(result)) => Ok(result),
+            Ok(ConnectionEnd::Rejected(ws)) => Err(ws),
+            Err(_) => Ok(Err(LiveViewError::SendingFailed)),
         }
     }
 }
@@ -87,6 +187,9 @@ impl LiveViewPool {
 ///     Ok(Message::Text(message))
 /// }
 /// ```
+///
+/// Messages that are valid UTF-8 should be sent to the client as text messages, and all other messages as binary
+/// messages.
 pub trait LiveViewSocket:
     SinkExt<Vec<u8>, Error = LiveViewError>
     + StreamExt<Item = Result<Vec<u8>, LiveViewError>>
@@ -110,131 +213,408 @@ impl<S> LiveViewSocket for S where
 /// As long as your framework can provide a Sink and Stream of Strings, you can use this function.
 ///
 /// You might need to transform the error types of the web backend into the LiveView error type.
-pub async fn run(mut vdom: VirtualDom, ws: impl LiveViewSocket) -> Result<(), LiveViewError> {
-    #[cfg(all(feature = "hot-reload", debug_assertions))]
-    let mut hot_reload_rx = {
-        let (tx, rx) = tokio::sync::mpsc::unbounded_channel();
-        dioxus_hot_reload::connect(move |template| {
-            let _ = tx.send(template);
-        });
-        rx
-    };
+///
+/// The VirtualDom is dropped when the client disconnects. Use a [`LiveViewPool`] to keep it alive for clients that
+/// reconnect.
+pub async fn run(vdom: VirtualDom, ws: impl LiveViewSocket) -> Result<(), LiveViewError> {
+    Session::new(vdom, new_token(), Encoding::Json)
+        .pump(Box::pin(ws), 0)
+        .await
+}
+
+/// A socket with its type erased, so sessions can be handed sockets from any adapter
+type BoxedSocket = Pin<Box<dyn Socket>>;
+
+trait Socket:
+    Sink<Vec<u8>, Error = LiveViewError> + Stream<Item = Result<Vec<u8>, LiveViewError>> + Send
+{
+}
+
+impl<S> Socket for S where
+    S: Sink<Vec<u8>, Error = LiveViewError> + Stream<Item = Result<Vec<u8>, LiveViewError>> + Send
+{
+}
+
+/// A client connecting to a session
+struct Connection {
+    ws: BoxedSocket,
+    /// The sequence number of the last message the client applied, or 0 if it never applied any
+    last_applied: u64,
+    /// Where the session reports how the connection ended
+    done: oneshot::Sender<ConnectionEnd>,
+}
+
+impl Connection {
+    fn new(ws: BoxedSocket, last_applied: u64) -> (Self, oneshot::Receiver<ConnectionEnd>) {
+        let (done, done_rx) = oneshot::channel();
+        let connection = Connection {
+            ws,
+            last_applied,
+            done,
+        };
+        (connection, done_rx)
+    }
+}
 
-    // Create the a proxy for query engine
-    let (query_tx, mut query_rx) = tokio::sync::mpsc::unbounded_channel();
-    let query_engine = QueryEngine::new(query_tx);
-    vdom.base_scope().provide_context(query_engine.clone());
-    init_eval(vdom.base_scope());
+enum ConnectionEnd {
+    /// The session served the client until it disconnected
+    Closed(Result<(), LiveViewError>),
+    /// The session can't bring the client up to date, so it gave the socket back
+    Rejected(BoxedSocket),
+}
 
-    // todo: use an efficient binary packed format for this
-    let edits = serde_json::to_string(&ClientUpdate::Edits(vdom.rebuild())).unwrap();
+/// The sessions that are alive, by their token
+type Sessions = Arc<Mutex<HashMap<String, UnboundedSender<Connection>>>>;
 
-    // pin the futures so we can use select!
-    pin_mut!(ws);
+fn new_token() -> String {
+    format!("{:032x}", rand::random::<u128>())
+}
 
-    // send the initial render to the client
-    ws.send(edits.into_bytes()).await?;
+/// Serve every client that connects to the session until none reconnects within the TTL
+async fn run_session(
+    mut session: Session,
+    mut connections: UnboundedReceiver<Connection>,
+    sessions: Sessions,
+    ttl: Duration,
+) {
+    let mut next = connections.recv().await;
+    while let Some(connection) = next {
+        if !session.can_replay(connection.last_applied) {
+            _ = connection.done.send(ConnectionEnd::Rejected(connection.ws));
+            break;
+        }
+        let result = session.pump(connection.ws, connection.last_applied).await;
+        _ = connection.done.send(ConnectionEnd::Closed(result));
+
+        next = match session.wait_for_reconnect(&mut connections, ttl).await {
+            Some(connection) => Some(connection),
+            // A client might have been handed to us right before the session expired
+            None => connections.try_recv().ok(),
+        };
+    }
+
+    // Clients that are handed to the session after it stopped start over in a new session
+    let mut sessions = sessions.lock().unwrap();
+    sessions.remove(&session.token);
+    while let Ok(connection) = connections.try_recv() {
+        _ = connection.done.send(ConnectionEnd::Rejected(connection.ws));
+    }
+}
+
+// desktop uses this wrapper struct thing around the actual event itself
+// this is sorta driven by tao/wry
+#[derive(Deserialize, Debug)]
+#[serde(tag = "method", content = "params")]
+enum IpcMessage {
+    #[serde(rename = "initialize")]
+    Initialize(Initialize),
+    #[serde(rename = "user_event")]
+    Event(HtmlEvent),
+    #[serde(rename = "query")]
+    Query(QueryResult),
+    #[serde(rename = "report_scroll")]
+    ReportScroll(ScrollReport),
+}
+
+/// How far an element was scrolled right before the client removed it
+#[derive(Deserialize, Debug)]
+struct ScrollReport {
+    id: usize,
+    x: f64,
+    y: f64,
+}
+
+#[derive(Deserialize, Debug, Default)]
+struct Initialize {
+    /// The token of the session the client was connected to before
+    #[serde(default)]
+    session: Option<String>,
+    /// The sequence number of the last message the client applied in that session
+    #[serde(default)]
+    last_applied: u64,
+    #[serde(default)]
+    encoding: Encoding,
+}
+
+/// Marks a message as MessagePack. It is never valid UTF-8, so adapters send these messages as binary messages.
+const BINARY_MARKER: u8 = 0xff;
+
+/// How messages to the client are encoded, picked by the client when it connects
+#[derive(Deserialize, Debug, Clone, Copy, Default)]
+enum Encoding {
+    #[default]
+    #[serde(rename = "json")]
+    Json,
+    /// A MessagePack message after [`BINARY_MARKER`], which is smaller and faster to create than JSON
+    #[serde(rename = "msgpack")]
+    MessagePack,
+}
 
-    // desktop uses this wrapper struct thing around the actual event itself
-    // this is sorta driven by tao/wry
-    #[derive(serde::Deserialize, Debug)]
-    #[serde(tag = "method", content = "params")]
-    enum IpcMessage {
-        #[serde(rename = "user_event")]
-        Event(HtmlEvent),
-        #[serde(rename = "query")]
-        Query(QueryResult),
+impl Encoding {
+    fn encode(self, update: &ClientUpdate) -> Vec<u8> {
+        match self {
+            Encoding::Json => serde_json::to_vec(update).unwrap(),
+            Encoding::MessagePack => {
+                let mut message = vec![BINARY_MARKER];
+                rmp_serde::encode::write_named(&mut message, update).unwrap();
+                message
+            }
+        }
     }
+}
 
-    loop {
+/// A VirtualDom and the state it needs to talk to whichever client is connected to it
+struct Session {
+    vdom: VirtualDom,
+    token: String,
+    encoding: Encoding,
+    query_engine: QueryEngine,
+    query_rx: UnboundedReceiver<String>,
+    #[cfg(all(feature = "hot-reload", debug_assertions))]
+    hot_reload_rx: UnboundedReceiver<dioxus_hot_reload::HotReloadMsg>,
+    /// The latest messages sent in this session with their sequence numbers, so they can be replayed to a client that
+    /// reconnects without having applied them
+    history: VecDeque<(u64, Vec<u8>)>,
+    /// The sequence number of the next message
+    next_seq: u64,
+    /// If no client has been told about this session yet
+    fresh: bool,
+}
+
+impl Session {
+    fn new(mut vdom: VirtualDom, token: String, encoding: Encoding) -> Self {
         #[cfg(all(feature = "hot-reload", debug_assertions))]
-        let hot_reload_wait = hot_reload_rx.recv();
-        #[cfg(not(all(feature = "hot-reload", debug_assertions)))]
-        let hot_reload_wait: std::future::Pending<Option<()>> = std::future::pending();
-
-        tokio::select! {
-            // poll any futures or suspense
-            _ = vdom.wait_for_work() => {}
-
-            evt = ws.next() => {
-                match evt.as_ref().map(|o| o.as_deref()) {
-                    // respond with a pong every ping to keep the websocket alive
-                    Some(Ok(b"__ping__")) => {
-                        ws.send(b"__pong__".to_vec()).await?;
-                    }
-                    Some(Ok(evt)) => {
-                        if let Ok(message) = serde_json::from_str::<IpcMessage>(&String::from_utf8_lossy(evt)) {
-                            match message {
-                                IpcMessage::Event(evt) => {
-                                    // Intercept the mounted event and insert a custom element type
-                                    if let EventData::Mounted = &evt.data {
-                                        let element = LiveviewElement::new(evt.element, query_engine.clone());
-                                        vdom.handle_event(
-                                            &evt.name,
-                                            Rc::new(MountedData::new(element)),
-                                            evt.element,
-                                            evt.bubbles,
-                                        );
+        let hot_reload_rx = {
+            let (tx, rx) = unbounded_channel();
+            dioxus_hot_reload::connect(move |template| {
+                let _ = tx.send(template);
+            });
+            rx
+        };
+
+        // Create the a proxy for query engine
+        let (query_tx, query_rx) = unbounded_channel();
+        let query_engine = QueryEngine::new(query_tx);
+        vdom.provide_root_context(query_engine.clone());
+        init_eval(vdom.base_scope());
+
+        let edits = encoding.encode(&ClientUpdate::Edits {
+            seq: 1,
+            edits: vdom.rebuild(),
+        });
+
+        Self {
+            vdom,
+            token,
+            encoding,
+            query_engine,
+            query_rx,
+            #[cfg(all(feature = "hot-reload", debug_assertions))]
+            hot_reload_rx,
+            history: VecDeque::from([(1, edits)]),
+            next_seq: 2,
+            fresh: true,
+        }
+    }
+
+    /// Run the app on the socket until the client disconnects
+    ///
+    /// The client is first sent every message after the last one it applied.
+    async fn pump(&mut self, mut ws: BoxedSocket, last_applied: u64) -> Result<(), LiveViewError> {
+        let session = self.encoding.encode(&ClientUpdate::Session {
+            token: &self.token,
+            fresh: self.fresh,
+        });
+        ws.send(session).await?;
+        self.fresh = false;
+
+        // send everything the client missed, starting with the initial render for new clients
+        for (seq, message) in &self.history {
+            if *seq > last_applied {
+                ws.send(message.clone()).await?;
+            }
+        }
+
+        loop {
+            #[cfg(all(feature = "hot-reload", debug_assertions))]
+            let hot_reload_wait = self.hot_reload_rx.recv();
+            #[cfg(not(all(feature = "hot-reload", debug_assertions)))]
+            let hot_reload_wait: std::future::Pending<Option<()>> = std::future::pending();
+
+            tokio::select! {
+                // poll any futures or suspense
+                _ = self.vdom.wait_for_work() => {}
+
+                evt = ws.next() => {
+                    match evt.as_ref().map(|o| o.as_deref()) {
+                        // respond with a pong every ping to keep the websocket alive
+                        Some(Ok(b"__ping__")) => {
+                            ws.send(b"__pong__".to_vec()).await?;
+                        }
+                        Some(Ok(evt)) => {
+                            if let Ok(message) = serde_json::from_str::<IpcMessage>(&String::from_utf8_lossy(evt)) {
+                                match message {
+                                    IpcMessage::Event(evt) => {
+                                        // Intercept the mounted event and insert a custom element type
+                                        if let EventData::Mounted = &evt.data {
+                                            let element = LiveviewElement::new(evt.element, self.query_engine.clone());
+                                            self.vdom.handle_event(
+                                                &evt.name,
+                                                Rc::new(MountedData::new(element)),
+                                                evt.element,
+                                                evt.bubbles,
+                                            );
+                                        }
+                                        else{
+                                            self.vdom.handle_event(
+                                                &evt.name,
+                                                evt.data.into_any(),
+                                                evt.element,
+                                                evt.bubbles,
+                                            );
+                                        }
                                     }
-                                    else{
-                                        vdom.handle_event(
-                                            &evt.name,
-                                            evt.data.into_any(),
-                                            evt.element,
-                                            evt.bubbles,
-                                        );
+                                    IpcMessage::Query(result) => {
+                                        self.query_engine.send(result);
+                                    },
+                                    IpcMessage::ReportScroll(report) => {
+                                        self.vdom.report_scroll(ElementId(report.id), report.x, report.y);
                                     }
+                                    // the session was picked before the client was handed to it
+                                    IpcMessage::Initialize(_) => {}
                                 }
-                                IpcMessage::Query(result) => {
-                                    query_engine.send(result);
-                                },
                             }
                         }
+                        // log this I guess? when would we get an error here?
+                        Some(Err(_e)) => {}
+                        None => return Ok(()),
                     }
-                    // log this I guess? when would we get an error here?
-                    Some(Err(_e)) => {}
-                    None => return Ok(()),
                 }
-            }
 
-            // handle any new queries
-            Some(query) = query_rx.recv() => {
-                ws.send(serde_json::to_string(&ClientUpdate::Query(query)).unwrap().into_bytes()).await?;
+                // handle any new queries
+                Some(query) = self.query_rx.recv() => {
+                    let query = self.query(query);
+                    ws.send(query).await?;
+                }
+
+                Some(msg) = hot_reload_wait => {
+                    #[cfg(all(feature = "hot-reload", debug_assertions))]
+                    self.hot_reload(msg);
+                    #[cfg(not(all(feature = "hot-reload", debug_assertions)))]
+                    let () = msg;
+                }
             }
 
-            Some(msg) = hot_reload_wait => {
-                #[cfg(all(feature = "hot-reload", debug_assertions))]
-                match msg{
-                    dioxus_hot_reload::HotReloadMsg::UpdateTemplate(new_template) => {
-                        vdom.replace_template(new_template);
-                    }
-                    dioxus_hot_reload::HotReloadMsg::Shutdown => {
-                        std::process::exit(0);
-                    },
+            let edits = self.render().await;
+            ws.send(edits).await?;
+        }
+    }
+
+    /// Keep the app running while no client is connected, keeping everything it sends for the next client
+    ///
+    /// Returns the next client that connects, or `None` if none connects within the TTL.
+    async fn wait_for_reconnect(
+        &mut self,
+        connections: &mut UnboundedReceiver<Connection>,
+        ttl: Duration,
+    ) -> Option<Connection> {
+        let expired = tokio::time::sleep(ttl);
+        tokio::pin!(expired);
+
+        loop {
+            #[cfg(all(feature = "hot-reload", debug_assertions))]
+            let hot_reload_wait = self.hot_reload_rx.recv();
+            #[cfg(not(all(feature = "hot-reload", debug_assertions)))]
+            let hot_reload_wait: std::future::Pending<Option<()>> = std::future::pending();
+
+            tokio::select! {
+                connection = connections.recv() => return connection,
+
+                _ = &mut expired => return None,
+
+                _ = self.vdom.wait_for_work() => {}
+
+                Some(query) = self.query_rx.recv() => {
+                    self.query(query);
+                    continue;
+                }
+
+                Some(msg) = hot_reload_wait => {
+                    #[cfg(all(feature = "hot-reload", debug_assertions))]
+                    self.hot_reload(msg);
+                    #[cfg(not(all(feature = "hot-reload", debug_assertions)))]
+                    let () = msg;
                 }
-                #[cfg(not(all(feature = "hot-reload", debug_assertions)))]
-                let () = msg;
             }
+
+            self.render().await;
         }
+    }
 
-        let edits = vdom
+    /// Render the dirty components and serialize the edits
+    async fn render(&mut self) -> Vec<u8> {
+        let seq = self.take_seq();
+        let encoding = self.encoding;
+        let edits = self
+            .vdom
             .render_with_deadline(tokio::time::sleep(Duration::from_millis(10)))
             .await;
 
-        ws.send(
-            serde_json::to_string(&ClientUpdate::Edits(edits))
-                .unwrap()
-                .into_bytes(),
-        )
-        .await?;
+        let message = encoding.encode(&ClientUpdate::Edits { seq, edits });
+        self.keep(seq, &message);
+        message
+    }
+
+    /// Serialize a script the client should run
+    fn query(&mut self, script: String) -> Vec<u8> {
+        let seq = self.take_seq();
+        let message = self.encoding.encode(&ClientUpdate::Query { seq, script });
+        self.keep(seq, &message);
+        message
+    }
+
+    fn take_seq(&mut self) -> u64 {
+        let seq = self.next_seq;
+        self.next_seq += 1;
+        seq
+    }
+
+    /// Keep a message until it is too old to be replayed. The socket might accept a message the client never
+    /// receives, so every message is kept, not only the ones sent while no client is connected.
+    fn keep(&mut self, seq: u64, message: &[u8]) {
+        if self.history.len() == REPLAY_LIMIT {
+            self.history.pop_front();
+        }
+        self.history.push_back((seq, message.to_vec()));
+    }
+
+    /// Does the session still have every message after the last one the client applied?
+    fn can_replay(&self, last_applied: u64) -> bool {
+        let oldest = self.next_seq - self.history.len() as u64;
+        last_applied + 1 >= oldest && last_applied < self.next_seq
+    }
+
+    #[cfg(all(feature = "hot-reload", debug_assertions))]
+    fn hot_reload(&mut self, msg: dioxus_hot_reload::HotReloadMsg) {
+        match msg {
+            dioxus_hot_reload::HotReloadMsg::UpdateTemplate(new_template) => {
+                self.vdom.replace_template(new_template);
+            }
+            dioxus_hot_reload::HotReloadMsg::Shutdown => {
+                std::process::exit(0);
+            }
+        }
     }
 }
 
 #[derive(Serialize)]
 #[serde(tag = "type", content = "data")]
 enum ClientUpdate<'a> {
+    #[serde(rename = "session")]
+    Session { token: &'a str, fresh: bool },
+    /// Messages that belong to the session are numbered, so a client that reconnects can say which it applied
     #[serde(rename = "edits")]
-    Edits(Mutations<'a>),
+    Edits { seq: u64, edits: Mutations<'a> },
     #[serde(rename = "query")]
-    Query(String),
+    Query { seq: u64, script: String },
 }
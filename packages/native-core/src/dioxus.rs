@@ -1,7 +1,10 @@
 //! Integration between Dioxus and the RealDom
 
 use crate::tree::TreeMut;
-use dioxus_core::{BorrowedAttributeValue, ElementId, Mutations, TemplateNode};
+use dioxus_core::{
+    BorrowedAttributeValue, DerivedAttribute, DerivedInputs, ElementId, Mutations, TemplateNode,
+    TemplatePath,
+};
 use rustc_hash::{FxHashMap, FxHashSet};
 use shipyard::Component;
 
@@ -23,6 +26,7 @@ pub struct DioxusState {
     templates: FxHashMap<String, Vec<NodeId>>,
     stack: Vec<NodeId>,
     node_id_mapping: Vec<Option<NodeId>>,
+    derived: FxHashMap<NodeId, Vec<(OwnedAttributeDiscription, DerivedAttribute)>>,
 }
 
 impl DioxusState {
@@ -35,6 +39,7 @@ impl DioxusState {
             templates: FxHashMap::default(),
             stack: vec![root_id],
             node_id_mapping: vec![Some(root_id)],
+            derived: FxHashMap::default(),
         }
     }
 
@@ -61,15 +66,94 @@ impl DioxusState {
         self.node_id_mapping[element_id.0] = Some(node_id);
     }
 
-    fn load_child<V: FromAnyValue + Send + Sync>(&self, rdom: &RealDom<V>, path: &[u8]) -> NodeId {
+    fn load_child<V: FromAnyValue + Send + Sync>(
+        &self,
+        rdom: &RealDom<V>,
+        path: TemplatePath,
+    ) -> NodeId {
         let mut current = rdom.get(*self.stack.last().unwrap()).unwrap();
-        for i in path {
-            let new_id = current.child_ids()[*i as usize];
+        for i in path.iter() {
+            let new_id = current.child_ids()[i as usize];
             current = rdom.get(new_id).unwrap();
         }
         current.id()
     }
 
+    fn set_attribute<V: FromAnyValue + Send + Sync>(
+        &mut self,
+        rdom: &mut RealDom<V>,
+        node_id: NodeId,
+        name: &str,
+        value: BorrowedAttributeValue,
+        ns: Option<&str>,
+    ) {
+        let attribute = OwnedAttributeDiscription {
+            name: name.to_string(),
+            namespace: ns.map(|s| s.to_string()),
+        };
+
+        // Setting any other value replaces a derived attribute with the same name
+        if let Some(derived) = self.derived.get_mut(&node_id) {
+            derived.retain(|(other, _)| *other != attribute);
+        }
+
+        if let BorrowedAttributeValue::Derived(derived) = value {
+            self.derived
+                .entry(node_id)
+                .or_default()
+                .push((attribute, derived));
+        } else {
+            let mut node = rdom.get_mut(node_id).unwrap();
+            let mut node_type_mut = node.node_type_mut();
+            if let NodeTypeMut::Element(element) = &mut node_type_mut {
+                if let BorrowedAttributeValue::None = &value {
+                    element.remove_attribute(&attribute);
+                } else {
+                    element.set_attribute(attribute, OwnedAttributeValue::from(value));
+                }
+            }
+        }
+
+        self.update_derived(rdom, node_id);
+    }
+
+    /// Recompute the derived attributes of a node after its other attributes changed
+    fn update_derived<V: FromAnyValue + Send + Sync>(
+        &self,
+        rdom: &mut RealDom<V>,
+        node_id: NodeId,
+    ) {
+        let derived = match self.derived.get(&node_id) {
+            Some(derived) => derived,
+            None => return,
+        };
+
+        let mut node = rdom.get_mut(node_id).unwrap();
+        let mut node_type_mut = node.node_type_mut();
+        if let NodeTypeMut::Element(element) = &mut node_type_mut {
+            for (attribute, derived) in derived {
+                let value = {
+                    let current = element.attributes();
+                    let lookup = |name: &str| {
+                        current
+                            .iter()
+                            .find(|(attribute, _)| attribute.name == name)
+                            .map(|(_, value)| value.to_string())
+                    };
+                    derived.compute(&DerivedInputs::new(&lookup))
+                };
+                match value {
+                    Some(value) => {
+                        element.set_attribute(attribute.clone(), OwnedAttributeValue::Text(value));
+                    }
+                    None => {
+                        element.remove_attribute(attribute);
+                    }
+                }
+            }
+        }
+    }
+
     /// Updates the dom with some mutations and return a set of nodes that were updated. Pass the dirty nodes to update_state.
     pub fn apply_mutations<V: FromAnyValue + Send + Sync>(
         &mut self,
@@ -96,6 +180,14 @@ impl DioxusState {
                         rdom.get_mut(parent).unwrap().add_child(child);
                     }
                 }
+                // There is nothing outside of the RealDom to mount into, so the nodes go in the root
+                AppendToAnchor { m, .. } => {
+                    let children = self.stack.split_off(self.stack.len() - m);
+                    let root = self.element_to_node_id(ElementId(0));
+                    for child in children {
+                        rdom.get_mut(root).unwrap().add_child(child);
+                    }
+                }
                 AssignId { path, id } => {
                     let node_id = self.load_child(rdom, path);
                     self.set_element_id(rdom.get_mut(node_id).unwrap(), id);
@@ -140,6 +232,27 @@ impl DioxusState {
                     self.set_element_id(clone, id);
                     self.stack.push(clone_id);
                 }
+                CreateElementWithAttrs {
+                    name,
+                    index,
+                    id,
+                    attrs,
+                    listeners,
+                    ..
+                } => {
+                    let template_id = self.templates[name][index];
+                    let clone_id = rdom.get_mut(template_id).unwrap().clone_node();
+                    let clone = rdom.get_mut(clone_id).unwrap();
+                    self.set_element_id(clone, id);
+                    for attr in attrs {
+                        self.set_attribute(rdom, clone_id, attr.name, attr.value, attr.ns);
+                    }
+                    let mut clone = rdom.get_mut(clone_id).unwrap();
+                    for listener in listeners {
+                        clone.add_event_listener(listener);
+                    }
+                    self.stack.push(clone_id);
+                }
                 ReplaceWith { id, m } => {
                     let new_nodes = self.stack.split_off(self.stack.len() - m);
                     let old_node_id = self.element_to_node_id(id);
@@ -180,24 +293,7 @@ impl DioxusState {
                     ns,
                 } => {
                     let node_id = self.element_to_node_id(id);
-                    let mut node = rdom.get_mut(node_id).unwrap();
-                    let mut node_type_mut = node.node_type_mut();
-                    if let NodeTypeMut::Element(element) = &mut node_type_mut {
-                        if let BorrowedAttributeValue::None = &value {
-                            element.remove_attribute(&OwnedAttributeDiscription {
-                                name: name.to_string(),
-                                namespace: ns.map(|s| s.to_string()),
-                            });
-                        } else {
-                            element.set_attribute(
-                                OwnedAttributeDiscription {
-                                    name: name.to_string(),
-                                    namespace: ns.map(|s| s.to_string()),
-                                },
-                                OwnedAttributeValue::from(value),
-                            );
-                        }
-                    }
+                    self.set_attribute(rdom, node_id, name, value, ns);
                 }
                 SetText { value, id } => {
                     let node_id = self.element_to_node_id(id);
@@ -225,6 +321,12 @@ impl DioxusState {
                     let node_id = self.element_to_node_id(id);
                     self.stack.push(node_id);
                 }
+                // There is no stylesheet to inject styles into
+                InjectStyle { .. } => {}
+                CaptureScroll { .. } => {}
+                RestoreScroll { .. } => {}
+                // Listeners are tracked on each node, so delegated events are never enabled
+                RegisterDelegated { .. } | UnregisterDelegated { .. } => {}
             }
         }
     }
@@ -258,7 +360,8 @@ fn create_template_node<V: FromAnyValue + Send + Sync>(
                             },
                             OwnedAttributeValue::Text(value.to_string()),
                         )),
-                        dioxus_core::TemplateAttribute::Dynamic { .. } => None,
+                        dioxus_core::TemplateAttribute::Dynamic { .. }
+                        | dioxus_core::TemplateAttribute::Conditional { .. } => None,
                     })
                     .collect(),
                 listeners: FxHashSet::default(),
@@ -132,6 +132,8 @@ pub enum OwnedAttributeValue<V: FromAnyValue = ()> {
     Int(i64),
     /// A boolean value.
     Bool(bool),
+    /// Raw binary data.
+    Bytes(Vec<u8>),
     /// A custom value specific to the renderer
     Custom(V),
 }
@@ -160,6 +162,12 @@ impl<V: FromAnyValue> From<bool> for OwnedAttributeValue<V> {
     }
 }
 
+impl<V: FromAnyValue> From<Vec<u8>> for OwnedAttributeValue<V> {
+    fn from(value: Vec<u8>) -> Self {
+        Self::Bytes(value)
+    }
+}
+
 impl<V: FromAnyValue> From<V> for OwnedAttributeValue<V> {
     fn from(value: V) -> Self {
         Self::Custom(value)
@@ -183,6 +191,7 @@ impl<V: FromAnyValue> Debug for OwnedAttributeValue<V> {
             Self::Float(arg0) => f.debug_tuple("Float").field(arg0).finish(),
             Self::Int(arg0) => f.debug_tuple("Int").field(arg0).finish(),
             Self::Bool(arg0) => f.debug_tuple("Bool").field(arg0).finish(),
+            Self::Bytes(arg0) => f.debug_tuple("Bytes").field(arg0).finish(),
             Self::Custom(_) => f.debug_tuple("Any").finish(),
         }
     }
@@ -195,6 +204,7 @@ impl<V: FromAnyValue> Display for OwnedAttributeValue<V> {
             Self::Float(arg0) => f.write_str(&arg0.to_string()),
             Self::Int(arg0) => f.write_str(&arg0.to_string()),
             Self::Bool(arg0) => f.write_str(&arg0.to_string()),
+            Self::Bytes(arg0) => write!(f, "{} bytes", arg0.len()),
             Self::Custom(_) => f.write_str("custom"),
         }
     }
@@ -208,7 +218,9 @@ impl<V: FromAnyValue> From<dioxus_core::BorrowedAttributeValue<'_>> for OwnedAtt
             dioxus_core::BorrowedAttributeValue::Float(float) => Self::Float(float),
             dioxus_core::BorrowedAttributeValue::Int(int) => Self::Int(int),
             dioxus_core::BorrowedAttributeValue::Bool(bool) => Self::Bool(bool),
+            dioxus_core::BorrowedAttributeValue::Bytes(bytes) => Self::Bytes(bytes.to_vec()),
             dioxus_core::BorrowedAttributeValue::Any(any) => Self::Custom(V::from_any_value(any.as_any())),
+            dioxus_core::BorrowedAttributeValue::Derived(_) => panic!("Derived attribute values are computed by the renderer, not converted to a value."),
             dioxus_core::BorrowedAttributeValue::None => panic!("None attribute values result in removing the attribute, not converting it to a None value.")
         }
     }
@@ -249,6 +261,14 @@ impl<V: FromAnyValue> OwnedAttributeValue<V> {
         }
     }
 
+    /// Attempt to convert the attribute value to raw binary data.
+    pub fn as_bytes(&self) -> Option<&[u8]> {
+        match self {
+            OwnedAttributeValue::Bytes(bytes) => Some(bytes),
+            _ => None,
+        }
+    }
+
     /// Attempt to convert the attribute value to a custom value.
     pub fn as_custom(&self) -> Option<&V> {
         match self {
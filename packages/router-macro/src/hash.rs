@@ -0,0 +1,32 @@
+use quote::quote;
+use syn::{Ident, Type};
+
+use proc_macro2::TokenStream as TokenStream2;
+
+#[derive(Debug)]
+pub struct HashFragment {
+    pub ident: Ident,
+    pub ty: Type,
+}
+
+impl HashFragment {
+    pub fn parse(&self) -> TokenStream2 {
+        let ident = &self.ident;
+        let ty = &self.ty;
+        quote! {
+            let #ident = <#ty as dioxus_router::routable::FromHashFragment>::from_hash_fragment(&*hash);
+        }
+    }
+
+    pub fn write(&self) -> TokenStream2 {
+        let ident = &self.ident;
+        quote! {
+            {
+                let hash = #ident.to_string();
+                if !hash.is_empty() {
+                    write!(f, "#{}", hash)?;
+                }
+            }
+        }
+    }
+}
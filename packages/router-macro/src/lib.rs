@@ -17,6 +17,7 @@ use proc_macro2::TokenStream as TokenStream2;
 
 use crate::{layout::LayoutId, route_tree::RouteTree};
 
+mod hash;
 mod layout;
 mod nest;
 mod query;
@@ -37,6 +38,7 @@ mod segment;
 /// 2. Dynamic Segments: "/:dynamic" (where dynamic has a type that is FromStr in all child Variants)
 /// 3. Catch all Segments: "/:..segments" (where segments has a type that is FromSegments in all child Variants)
 /// 4. Query Segments: "/?:query" (where query has a type that is FromQuery in all child Variants)
+/// 5. Hash Fragments: "/#:hash" (where hash has a type that is FromHashFragment in all child Variants)
 ///
 /// Routes are matched:
 /// 1. By there specificity this order: Query Routes ("/?:query"), Static Routes ("/route"), Dynamic Routes ("/:route"), Catch All Routes ("/:..route")
@@ -57,12 +59,13 @@ mod segment;
 ///         // Creates a Layout UserFrame that has the parameter `user_id: usize`
 ///         #[layout(UserFrame)]
 ///             // If there is a component with the name Route1, you do not need to pass in the component name
-///             #[route("/:dynamic?:query")]
+///             #[route("/:dynamic?:query#:hash")]
 ///             Route1 {
 ///                 // The type is taken from the first instance of the dynamic parameter
 ///                 user_id: usize,
 ///                 dynamic: usize,
 ///                 query: String,
+///                 hash: String,
 ///                 extra: String,
 ///             },
 ///             #[route("/hello_world")]
@@ -89,6 +92,7 @@ mod segment;
 /// - (optional) `component`: The component to render when the route is matched. If not specified, the name of the variant is used
 ///
 /// Routes are the most basic attribute. They allow you to define a route and the component to render when the route is matched. The component must take all dynamic parameters of the route and all parent nests.
+/// Since the fields of the variant are passed to the component as props, the variant must have named fields or no fields.
 /// The next variant will be tied to the component. If you link to that variant, the component will be rendered.
 ///
 /// ```rust, skip
@@ -484,9 +488,8 @@ impl RouteEnum {
 
                 fn from_str(s: &str) -> Result<Self, Self::Err> {
                     let route = s;
-                    let (route, _hash) = route.split_once('#').unwrap_or((route, ""));
+                    let (route, hash) = route.split_once('#').unwrap_or((route, ""));
                     let (route, query) = route.split_once('?').unwrap_or((route, ""));
-                    let query = dioxus_router::exports::urlencoding::decode(query).unwrap_or(query.into());
                     let mut segments = route.split('/').map(|s| dioxus_router::exports::urlencoding::decode(s).unwrap_or(s.into()));
                     // skip the first empty segment
                     if s.starts_with('/') {
@@ -21,7 +21,12 @@ impl QuerySegment {
     pub fn write(&self) -> TokenStream2 {
         let ident = &self.ident;
         quote! {
-            write!(f, "?{}", #ident)?;
+            {
+                let query = #ident.to_string();
+                if !query.is_empty() {
+                    write!(f, "?{}", query)?;
+                }
+            }
         }
     }
 }
@@ -3,6 +3,7 @@ use quote::{format_ident, quote};
 use syn::LitStr;
 
 use crate::{
+    hash::HashFragment,
     nest::NestId,
     query::QuerySegment,
     segment::{create_error_type, parse_route_segments, RouteSegment},
@@ -14,6 +15,7 @@ pub(crate) struct Redirect {
     pub nests: Vec<NestId>,
     pub segments: Vec<RouteSegment>,
     pub query: Option<QuerySegment>,
+    pub hash: Option<HashFragment>,
     pub function: syn::ExprClosure,
     pub index: usize,
 }
@@ -34,9 +36,11 @@ impl Redirect {
     }
 
     pub fn parse_query(&self) -> TokenStream {
-        match &self.query {
-            Some(query) => query.parse(),
-            None => quote! {},
+        let parse_query = self.query.as_ref().map(|query| query.parse());
+        let parse_hash = self.hash.as_ref().map(|hash| hash.parse());
+        quote! {
+            #parse_query
+            #parse_hash
         }
     }
 
@@ -73,7 +77,7 @@ impl Redirect {
             }
         }
 
-        let (segments, query) = parse_route_segments(
+        let (segments, query, hash) = parse_route_segments(
             path.span(),
             closure_arguments.iter().map(|(name, ty)| (name, ty)),
             &path.value(),
@@ -84,6 +88,7 @@ impl Redirect {
             nests: active_nests,
             segments,
             query,
+            hash,
             function,
             index,
         })
@@ -9,6 +9,7 @@ use syn::{Ident, LitStr};
 
 use proc_macro2::TokenStream as TokenStream2;
 
+use crate::hash::HashFragment;
 use crate::layout::Layout;
 use crate::layout::LayoutId;
 use crate::nest::Nest;
@@ -27,13 +28,17 @@ impl Parse for RouteArgs {
     fn parse(input: ParseStream<'_>) -> syn::Result<Self> {
         let route = input.parse::<LitStr>()?;
 
-        Ok(RouteArgs {
-            route,
-            comp_name: {
-                let _ = input.parse::<syn::Token![,]>();
-                input.parse().ok()
-            },
-        })
+        let mut comp_name = None;
+        if input.parse::<Option<syn::Token![,]>>()?.is_some() && !input.is_empty() {
+            comp_name = Some(input.parse::<Path>().map_err(|err| {
+                syn::Error::new(
+                    err.span(),
+                    "expected the component that renders the route, like #[route(\"/\", Index)]",
+                )
+            })?);
+        }
+
+        Ok(RouteArgs { route, comp_name })
     }
 }
 
@@ -56,6 +61,7 @@ pub(crate) struct Route {
     pub route: String,
     pub segments: Vec<RouteSegment>,
     pub query: Option<QuerySegment>,
+    pub hash: Option<HashFragment>,
     pub nests: Vec<NestId>,
     pub layouts: Vec<LayoutId>,
     fields: Vec<(Ident, Type)>,
@@ -76,6 +82,13 @@ impl Route {
         let route_name = variant.ident.clone();
         match route_attr {
             Some(attr) => {
+                // The fields are passed to the component as props, so they need names
+                if let syn::Fields::Unnamed(fields) = &variant.fields {
+                    return Err(syn::Error::new_spanned(
+                        fields,
+                        "Routable variants with a #[route(..)] attribute must have named fields or no fields",
+                    ));
+                }
                 let args = attr.parse_args::<RouteArgs>()?;
                 let comp_name = args.comp_name.unwrap_or_else(|| parse_quote!(#route_name));
                 ty = RouteType::Leaf {
@@ -122,7 +135,7 @@ impl Route {
                 } else {
                     return Err(syn::Error::new_spanned(
                             variant.clone(),
-                            "Routable variants must either have a #[route(..)] attribute or a #[child(..)] attribute",
+                            "Routable variants must either have a #[route(..)] attribute with the component that renders them or a #[child(..)] attribute",
                         ));
                 }
             }
@@ -144,7 +157,7 @@ impl Route {
             _ => Vec::new(),
         };
 
-        let (route_segments, query) = {
+        let (route_segments, query, hash) = {
             parse_route_segments(
                 variant.ident.span(),
                 fields.iter().map(|f| (&f.0, &f.1)),
@@ -158,6 +171,7 @@ impl Route {
             segments: route_segments,
             route,
             query,
+            hash,
             nests,
             layouts,
             fields,
@@ -168,6 +182,7 @@ impl Route {
         let name = &self.route_name;
         let dynamic_segments = self.dynamic_segments();
         let write_query = self.query.as_ref().map(|q| q.write());
+        let write_hash = self.hash.as_ref().map(|h| h.write());
 
         match &self.ty {
             RouteType::Child(field) => {
@@ -200,6 +215,7 @@ impl Route {
                         #(#write_nests)*
                         #(#write_segments)*
                         #write_query
+                        #write_hash
                     }
                 }
             }
@@ -286,6 +302,11 @@ impl Route {
                     from_route = true
                 }
             }
+            if let Some(hash) = &self.hash {
+                if &hash.ident == name {
+                    from_route = true
+                }
+            }
 
             if from_route {
                 quote! {#name}
@@ -332,9 +353,11 @@ impl Route {
     }
 
     pub fn parse_query(&self) -> TokenStream2 {
-        match &self.query {
-            Some(query) => query.parse(),
-            None => quote! {},
+        let parse_query = self.query.as_ref().map(|query| query.parse());
+        let parse_hash = self.hash.as_ref().map(|hash| hash.parse());
+        quote! {
+            #parse_query
+            #parse_hash
         }
     }
 }
@@ -3,6 +3,7 @@ use syn::{Ident, Type};
 
 use proc_macro2::{Span, TokenStream as TokenStream2};
 
+use crate::hash::HashFragment;
 use crate::query::QuerySegment;
 
 #[derive(Debug, Clone)]
@@ -130,15 +131,24 @@ pub fn static_segment_idx(idx: usize) -> Ident {
 
 pub fn parse_route_segments<'a>(
     route_span: Span,
-    mut fields: impl Iterator<Item = (&'a Ident, &'a Type)>,
+    fields: impl Iterator<Item = (&'a Ident, &'a Type)>,
     route: &str,
-) -> syn::Result<(Vec<RouteSegment>, Option<QuerySegment>)> {
+) -> syn::Result<(
+    Vec<RouteSegment>,
+    Option<QuerySegment>,
+    Option<HashFragment>,
+)> {
+    let fields: Vec<_> = fields.collect();
     let mut route_segments = Vec::new();
 
-    let (route_string, query) = match route.rsplit_once('?') {
-        Some((route, query)) => (route, Some(query)),
+    let (route_string, hash) = match route.rsplit_once('#') {
+        Some((route, hash)) => (route, Some(hash)),
         None => (route, None),
     };
+    let (route_string, query) = match route_string.rsplit_once('?') {
+        Some((route_string, query)) => (route_string, Some(query)),
+        None => (route_string, None),
+    };
     let mut iterator = route_string.split('/');
 
     // skip the first empty segment
@@ -163,7 +173,7 @@ pub fn parse_route_segments<'a>(
                 segment.to_string()
             };
 
-            let field = fields.find(|(name, _)| **name == ident);
+            let field = fields.iter().find(|(name, _)| **name == ident);
 
             let ty = if let Some(field) = field {
                 field.1.clone()
@@ -199,33 +209,50 @@ pub fn parse_route_segments<'a>(
     }
 
     // check if the route has a query string
-    let parsed_query = match query {
+    let parsed_query = match query.and_then(|query| query.strip_prefix(':')) {
         Some(query) => {
-            if let Some(query) = query.strip_prefix(':') {
-                let query_ident = Ident::new(query, Span::call_site());
-                let field = fields.find(|(name, _)| *name == &query_ident);
-
-                let ty = if let Some((_, ty)) = field {
-                    ty.clone()
-                } else {
-                    return Err(syn::Error::new(
-                        route_span,
-                        format!("Could not find a field with the name '{}'", query_ident),
-                    ));
-                };
+            let (ident, ty) = find_field(route_span, &fields, query)?;
+            Some(QuerySegment { ident, ty })
+        }
+        None => None,
+    };
 
-                Some(QuerySegment {
-                    ident: query_ident,
-                    ty,
-                })
-            } else {
-                None
+    // check if the route captures the hash fragment
+    let parsed_hash = match hash {
+        Some(hash) => match hash.strip_prefix(':') {
+            Some(hash) => {
+                let (ident, ty) = find_field(route_span, &fields, hash)?;
+                Some(HashFragment { ident, ty })
             }
-        }
+            None => {
+                return Err(syn::Error::new(
+                    route_span,
+                    format!(
+                        "The hash fragment must be captured by a field, like '#:section'. Error found in the route '{}'",
+                        route
+                    ),
+                ))
+            }
+        },
         None => None,
     };
 
-    Ok((route_segments, parsed_query))
+    Ok((route_segments, parsed_query, parsed_hash))
+}
+
+fn find_field(
+    route_span: Span,
+    fields: &[(&Ident, &Type)],
+    name: &str,
+) -> syn::Result<(Ident, Type)> {
+    let ident = Ident::new(name, Span::call_site());
+    match fields.iter().find(|(name, _)| *name == &ident) {
+        Some((_, ty)) => Ok((ident, (*ty).clone())),
+        None => Err(syn::Error::new(
+            route_span,
+            format!("Could not find a field with the name '{}'", ident),
+        )),
+    }
 }
 
 pub(crate) fn create_error_type(
@@ -1,10 +1,18 @@
 use dioxus::prelude::*;
 
-use crate::{routable::Routable, utils::use_router_internal::use_router_internal};
+use crate::{
+    hooks::use_outlet_context, routable::Routable, utils::use_router_internal::use_router_internal,
+};
 
-pub(crate) struct OutletContext<R> {
-    pub current_level: usize,
-    pub _marker: std::marker::PhantomData<R>,
+/// Where an [`Outlet`](crate::components::Outlet) is in the chain of layouts and the route that matched the current
+/// location.
+///
+/// The router resolves the current location to a chain: every layout the route is nested in, from the outermost to
+/// the innermost, followed by the component of the route itself. The [`Router`](crate::components::Router) renders the
+/// first element of the chain, and each outlet renders the element after the one it is in.
+pub struct OutletContext<R> {
+    pub(crate) current_level: usize,
+    pub(crate) _marker: std::marker::PhantomData<R>,
 }
 
 impl<R> Clone for OutletContext<R> {
@@ -16,17 +24,12 @@ impl<R> Clone for OutletContext<R> {
     }
 }
 
-pub(crate) fn use_outlet_context<R: 'static>(cx: &ScopeState) -> &OutletContext<R> {
-    let outlet_context = cx.use_hook(|| {
-        cx.consume_context().unwrap_or(OutletContext::<R> {
-            current_level: 1,
-            _marker: std::marker::PhantomData,
-        })
-    });
-    outlet_context
-}
-
 impl<R> OutletContext<R> {
+    /// The index in the chain of the layout or route the next [`Outlet`](crate::components::Outlet) renders
+    pub fn level(&self) -> usize {
+        self.current_level
+    }
+
     pub(crate) fn render(cx: Scope) -> Element<'_>
     where
         R: Routable + Clone,
@@ -43,6 +46,13 @@ impl<R> OutletContext<R> {
             }
         });
 
+        if current_level == 0 {
+            router.guard_history_change();
+            if router.is_waiting_for_guard() {
+                return None;
+            }
+        }
+
         if let Some(error) = router.render_error(cx) {
             if current_level == 0 {
                 return Some(error);
@@ -1,15 +1,22 @@
 use std::{
     any::Any,
     collections::HashSet,
+    future::Future,
+    pin::Pin,
     rc::Rc,
-    sync::{Arc, RwLock},
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc, RwLock,
+    },
+    task::{Poll, Waker},
 };
 
 use dioxus::prelude::*;
+use futures_util::future::poll_fn;
 
 use crate::{
-    navigation::NavigationTarget,
-    prelude::{AnyHistoryProvider, IntoRoutable},
+    navigation::{GuardResult, NavigationTarget},
+    prelude::{AnyHistoryProvider, IntoRoutable, ScrollConfig},
     routable::Routable,
     router_cfg::RouterConfig,
 };
@@ -24,6 +31,122 @@ pub(crate) type RoutingCallback<R> =
 pub(crate) type AnyRoutingCallback =
     Arc<dyn Fn(RouterContext) -> Option<NavigationTarget<Rc<dyn Any>>>>;
 
+/// The decision of an async guard.
+type GuardFuture<R> = Pin<Box<dyn Future<Output = GuardResult<R>>>>;
+
+/// A guard that decides about a navigation right away.
+pub(crate) type SyncGuard<R> = Arc<dyn Fn(&R) -> GuardResult<R>>;
+/// A guard that decides about a navigation once its future resolves.
+pub(crate) type AsyncGuard<R> = Arc<dyn Fn(R) -> GuardFuture<R>>;
+type AnySyncGuard = Arc<dyn Fn(&dyn Any) -> GuardResult<Rc<dyn Any>>>;
+type AnyAsyncGuard = Arc<dyn Fn(&dyn Any) -> GuardFuture<Rc<dyn Any>>>;
+
+/// A function the router will call before navigating to a route.
+pub(crate) enum RouteGuard<R> {
+    Sync(SyncGuard<R>),
+    Async(AsyncGuard<R>),
+}
+
+#[derive(Clone)]
+enum AnyRouteGuard {
+    Sync(AnySyncGuard),
+    Async(AnyAsyncGuard),
+}
+
+/// How many times the guard can redirect a single navigation before the router gives up on it.
+const MAX_REDIRECTS: usize = 16;
+
+/// How a navigation changes the history once the guard allows it.
+#[derive(Clone, Copy)]
+enum Navigation {
+    Push,
+    Replace,
+    /// The route the router starts at, which is already in the history.
+    Initial,
+    /// Going back, which already moved the history.
+    Back,
+    /// Going forward, which already moved the history.
+    Forward,
+    /// The history moved by itself, like when the browser goes back or forward.
+    Popped,
+}
+
+/// A navigation waiting for the async guard, with the number of times it was redirected.
+type PendingNavigation = (Rc<dyn Any>, Navigation, usize);
+
+fn erase_target<R: Routable>(target: NavigationTarget<R>) -> NavigationTarget<Rc<dyn Any>> {
+    match target {
+        NavigationTarget::Internal(r) => NavigationTarget::Internal(Rc::new(r) as Rc<dyn Any>),
+        NavigationTarget::External(s) => NavigationTarget::External(s),
+    }
+}
+
+fn erase_guard_result<R: Routable>(result: GuardResult<R>) -> GuardResult<Rc<dyn Any>> {
+    match result {
+        GuardResult::Allow => GuardResult::Allow,
+        GuardResult::Cancel => GuardResult::Cancel,
+        GuardResult::Redirect(target) => GuardResult::Redirect(erase_target(target)),
+    }
+}
+
+/// Checks a type erased route against a [`ScrollConfig`].
+type ScrollCheck = Rc<dyn Fn(&dyn Any) -> bool>;
+
+/// Remembers the scroll position of every history entry through JavaScript, for histories that
+/// leave the scroll position to the router.
+#[derive(Clone)]
+struct ScrollMemory {
+    eval: Rc<dyn EvalProvider>,
+    scroll_to_top: ScrollCheck,
+    restores: ScrollCheck,
+}
+
+impl ScrollMemory {
+    fn new<R: Routable>(config: ScrollConfig<R>, eval: Rc<dyn EvalProvider>) -> Self {
+        let restores = config.clone();
+        Self {
+            eval,
+            scroll_to_top: Rc::new(move |route| {
+                config.scrolls_to_top(route.downcast_ref::<R>().unwrap())
+            }),
+            restores: Rc::new(move |route| restores.restores(route.downcast_ref::<R>().unwrap())),
+        }
+    }
+
+    fn run(&self, script: String) {
+        if let Err(err) = self.eval.new_evaluator(script) {
+            tracing::error!("Failed to update the scroll position: {:?}", err);
+        }
+    }
+
+    /// Remember the scroll position of the entry the page is leaving
+    fn save(&self, entry: usize) {
+        self.run(format!(
+            "(window.__dioxusRouterScroll = window.__dioxusRouterScroll || {{}})[{}] = [window.scrollX, window.scrollY];",
+            entry
+        ));
+    }
+
+    fn scroll_to_top(&self, route: &dyn Any) {
+        if (self.scroll_to_top)(route) {
+            self.run("window.scrollTo(0, 0);".to_string());
+        }
+    }
+
+    /// Scroll back to where the entry was left, once the page shows it again
+    fn restore(&self, entry: usize, route: &dyn Any) {
+        if (self.restores)(route) {
+            self.run(format!(
+                r#"
+                const position = (window.__dioxusRouterScroll || {{}})[{}] || [0, 0];
+                requestAnimationFrame(() => requestAnimationFrame(() => window.scrollTo(position[0], position[1])));
+                "#,
+                entry
+            ));
+        }
+    }
+}
+
 struct MutableRouterState {
     /// The current prefix.
     prefix: Option<String>,
@@ -31,6 +154,20 @@ struct MutableRouterState {
     history: Box<dyn AnyHistoryProvider>,
 
     unresolved_error: Option<ExternalNavigationFailure>,
+
+    /// The navigation waiting for the async guard to decide about it.
+    pending_navigation: Option<PendingNavigation>,
+
+    /// The last route the guard allowed. Routers with a guard show this route instead of the
+    /// current route of the history, which may still be waiting for the guard.
+    allowed: Option<Rc<dyn Any>>,
+
+    /// Wakes the task that runs the async guard.
+    guard_waker: Option<Waker>,
+
+    /// The position of the current entry in the history, which the scroll memory uses to tell
+    /// entries apart.
+    history_index: usize,
 }
 
 /// A collection of router data that manages all routing functionality.
@@ -41,6 +178,11 @@ pub struct RouterContext {
     subscribers: Arc<RwLock<HashSet<ScopeId>>>,
     subscriber_update: Arc<dyn Fn(ScopeId)>,
     routing_callback: Option<AnyRoutingCallback>,
+    guard: Option<AnyRouteGuard>,
+    scroll: Option<ScrollMemory>,
+
+    /// Set when the history moved by itself, so the guard can decide about the new route.
+    history_moved: Arc<AtomicBool>,
 
     failure_external_navigation: fn(Scope) -> Element,
 
@@ -60,10 +202,23 @@ impl RouterContext {
             prefix: Default::default(),
             history: cfg.take_history(),
             unresolved_error: None,
+            pending_navigation: None,
+            allowed: None,
+            guard_waker: None,
+            history_index: 0,
         }));
 
+        // the history either handles the scroll position itself, or leaves it to the router
+        let scroll_config = std::mem::take(&mut cfg.scroll);
+        let scroll = match state.borrow_mut().history.handle_scroll(&scroll_config) {
+            true => None,
+            false => dioxus::core::prelude::consume_context::<Rc<dyn EvalProvider>>()
+                .map(|eval| ScrollMemory::new(scroll_config, eval)),
+        };
+
         let subscriber_update = mark_dirty.clone();
         let subscribers = Arc::new(RwLock::new(HashSet::new()));
+        let history_moved = Arc::new(AtomicBool::new(false));
 
         let myself = Self {
             state,
@@ -76,16 +231,25 @@ impl RouterContext {
                         inner: ctx,
                         _marker: std::marker::PhantomData,
                     };
-                    update(ctx).map(|t| match t {
-                        NavigationTarget::Internal(r) => {
-                            NavigationTarget::Internal(Rc::new(r) as Rc<dyn Any>)
-                        }
-                        NavigationTarget::External(s) => NavigationTarget::External(s),
-                    })
+                    update(ctx).map(erase_target)
                 })
                     as Arc<dyn Fn(RouterContext) -> Option<NavigationTarget<Rc<dyn Any>>>>
             }),
 
+            guard: cfg.guard.map(|guard| match guard {
+                RouteGuard::Sync(guard) => AnyRouteGuard::Sync(Arc::new(move |route| {
+                    erase_guard_result(guard(route.downcast_ref::<R>().unwrap()))
+                })),
+                RouteGuard::Async(guard) => AnyRouteGuard::Async(Arc::new(move |route| {
+                    let result = guard(route.downcast_ref::<R>().unwrap().clone());
+                    Box::pin(async move { erase_guard_result(result.await) })
+                })),
+            }),
+
+            scroll,
+
+            history_moved: history_moved.clone(),
+
             failure_external_navigation: cfg.failure_external_navigation,
 
             any_route_to_string: |route| {
@@ -107,12 +271,24 @@ impl RouterContext {
         {
             let mut state = myself.state.borrow_mut();
             state.history.updater(Arc::new(move || {
+                history_moved.store(true, Ordering::Relaxed);
                 for &id in subscribers.read().unwrap().iter() {
                     (mark_dirty)(id);
                 }
             }));
         }
 
+        // async guards decide in a task that lives as long as the router
+        if let Some(AnyRouteGuard::Async(guard)) = &myself.guard {
+            spawn(myself.clone().run_async_guard(guard.clone()));
+        }
+
+        // the route the router starts at has to pass the guard as well
+        if myself.guard.is_some() {
+            let route = myself.state.borrow().history.current_route();
+            myself.navigate(NavigationTarget::Internal(route), Navigation::Initial);
+        }
+
         myself
     }
 
@@ -136,67 +312,253 @@ impl RouterContext {
     /// Go back to the previous location.
     ///
     /// Will fail silently if there is no previous location to go to.
+    ///
+    /// If the router has a guard, the guard decides about the previous location. If it cancels,
+    /// the router goes forward again.
     pub fn go_back(&self) {
-        {
-            self.state.borrow_mut().history.go_back();
-        }
+        let route = {
+            let mut state = self.state_mut();
+            if !state.history.can_go_back() {
+                return;
+            }
+            state.history.go_back();
+            if let Some(scroll) = &self.scroll {
+                scroll.save(state.history_index);
+                state.history_index -= 1;
+            }
+            state.history.current_route()
+        };
 
-        self.change_route();
+        self.navigate(NavigationTarget::Internal(route), Navigation::Back);
     }
 
     /// Go back to the next location.
     ///
     /// Will fail silently if there is no next location to go to.
+    ///
+    /// If the router has a guard, the guard decides about the next location. If it cancels, the
+    /// router goes back again.
     pub fn go_forward(&self) {
-        {
-            self.state.borrow_mut().history.go_forward();
+        let route = {
+            let mut state = self.state_mut();
+            if !state.history.can_go_forward() {
+                return;
+            }
+            state.history.go_forward();
+            if let Some(scroll) = &self.scroll {
+                scroll.save(state.history_index);
+                state.history_index += 1;
+            }
+            state.history.current_route()
+        };
+
+        self.navigate(NavigationTarget::Internal(route), Navigation::Forward);
+    }
+
+    /// Let the guard decide about the current route of the history, if the history moved by itself
+    /// since the last time this was called
+    pub(crate) fn guard_history_change(&self) {
+        if self.guard.is_none() || !self.history_moved.swap(false, Ordering::Relaxed) {
+            return;
+        }
+
+        let route = self.state.borrow().history.current_route();
+        let unchanged = self
+            .state
+            .borrow()
+            .allowed
+            .as_ref()
+            .map_or(false, |allowed| {
+                self.any_route_to_string(&**allowed) == self.any_route_to_string(&*route)
+            });
+        if !unchanged {
+            self.navigate(NavigationTarget::Internal(route), Navigation::Popped);
         }
+    }
 
-        self.change_route();
+    /// Is the router waiting for the guard to allow the route it starts at?
+    ///
+    /// The router shows nothing until then.
+    pub(crate) fn is_waiting_for_guard(&self) -> bool {
+        self.guard.is_some() && self.state.borrow().allowed.is_none()
     }
 
     pub(crate) fn push_any(
         &self,
         target: NavigationTarget<Rc<dyn Any>>,
     ) -> Option<ExternalNavigationFailure> {
-        match target {
-            NavigationTarget::Internal(p) => {
-                let mut state = self.state_mut();
-                state.history.push(p)
-            }
-            NavigationTarget::External(e) => return self.external(e),
-        }
-
-        self.change_route()
+        self.navigate(target, Navigation::Push)
     }
 
     /// Push a new location.
     ///
     /// The previous location will be available to go back to.
+    ///
+    /// If the router has a guard, the navigation only happens once the guard allows it.
     pub fn push(&self, target: impl Into<IntoRoutable>) -> Option<ExternalNavigationFailure> {
         let target = self.resolve_into_routable(target.into());
-        match target {
-            NavigationTarget::Internal(p) => {
-                let mut state = self.state_mut();
-                state.history.push(p)
-            }
-            NavigationTarget::External(e) => return self.external(e),
-        }
-
-        self.change_route()
+        self.navigate(target, Navigation::Push)
     }
 
     /// Replace the current location.
     ///
     /// The previous location will **not** be available to go back to.
+    ///
+    /// If the router has a guard, the navigation only happens once the guard allows it.
     pub fn replace(&self, target: impl Into<IntoRoutable>) -> Option<ExternalNavigationFailure> {
         let target = self.resolve_into_routable(target.into());
+        self.navigate(target, Navigation::Replace)
+    }
+
+    /// Ask the guard about navigating to the target, and navigate if it allows it
+    fn navigate(
+        &self,
+        target: NavigationTarget<Rc<dyn Any>>,
+        navigation: Navigation,
+    ) -> Option<ExternalNavigationFailure> {
+        match target {
+            NavigationTarget::Internal(route) => self.guard_route(route, navigation, 0),
+            NavigationTarget::External(e) => self.external(e),
+        }
+    }
+
+    fn guard_route(
+        &self,
+        route: Rc<dyn Any>,
+        navigation: Navigation,
+        redirects: usize,
+    ) -> Option<ExternalNavigationFailure> {
+        match &self.guard {
+            None => self.apply(route, navigation, redirects),
+            Some(AnyRouteGuard::Sync(guard)) => {
+                let result = guard(&*route);
+                self.resolve_guard(result, route, navigation, redirects)
+            }
+            Some(AnyRouteGuard::Async(_)) => {
+                let mut state = self.state_mut();
+                state.pending_navigation = Some((route, navigation, redirects));
+                if let Some(waker) = state.guard_waker.take() {
+                    waker.wake();
+                }
+                None
+            }
+        }
+    }
+
+    /// Decide about every navigation with the async guard, as long as the router is alive
+    ///
+    /// This runs in a single task instead of a task for each navigation, because navigations can
+    /// start in other tasks, where no task can be spawned. Every poll decides about at most one
+    /// route. A redirect wakes the task again instead of deciding about the next route right away.
+    async fn run_async_guard(self, guard: AnyAsyncGuard) {
+        let mut deciding: Option<(GuardFuture<Rc<dyn Any>>, PendingNavigation)> = None;
+        poll_fn(|cx| {
+            self.state_mut().guard_waker = Some(cx.waker().clone());
+
+            // a newer navigation replaces the one being decided
+            let pending = self.state_mut().pending_navigation.take();
+            if let Some(pending) = pending {
+                deciding = Some((guard(&*pending.0), pending));
+            }
+
+            if let Some((decision, _)) = &mut deciding {
+                if let Poll::Ready(result) = decision.as_mut().poll(cx) {
+                    let (_, (route, navigation, redirects)) = deciding.take().unwrap();
+                    self.resolve_guard(result, route, navigation, redirects);
+                }
+            }
 
+            Poll::<()>::Pending
+        })
+        .await
+    }
+
+    fn resolve_guard(
+        &self,
+        result: GuardResult<Rc<dyn Any>>,
+        route: Rc<dyn Any>,
+        navigation: Navigation,
+        redirects: usize,
+    ) -> Option<ExternalNavigationFailure> {
+        match result {
+            GuardResult::Allow => self.apply(route, navigation, redirects),
+            GuardResult::Cancel => {
+                self.cancel(navigation);
+                None
+            }
+            GuardResult::Redirect(_) if redirects >= MAX_REDIRECTS => {
+                tracing::error!(
+                    "The route guard redirected the navigation to {} more than {} times. The navigation was cancelled.",
+                    self.any_route_to_string(&*route),
+                    MAX_REDIRECTS
+                );
+                self.cancel(navigation);
+                None
+            }
+            GuardResult::Redirect(NavigationTarget::Internal(redirect)) => {
+                self.guard_route(redirect, navigation, redirects + 1)
+            }
+            GuardResult::Redirect(NavigationTarget::External(e)) => self.external(e),
+        }
+    }
+
+    /// Undo the part of a navigation that happened before the guard decided about it
+    fn cancel(&self, navigation: Navigation) {
+        let mut state = self.state_mut();
+        match navigation {
+            Navigation::Push | Navigation::Replace | Navigation::Initial => {}
+            Navigation::Back => {
+                state.history.go_forward();
+                if self.scroll.is_some() {
+                    state.history_index += 1;
+                }
+            }
+            Navigation::Forward => {
+                state.history.go_back();
+                if self.scroll.is_some() {
+                    state.history_index -= 1;
+                }
+            }
+            Navigation::Popped => {
+                if let Some(allowed) = state.allowed.clone() {
+                    state.history.replace(allowed);
+                }
+            }
+        }
+    }
+
+    fn apply(
+        &self,
+        route: Rc<dyn Any>,
+        navigation: Navigation,
+        redirects: usize,
+    ) -> Option<ExternalNavigationFailure> {
         {
             let mut state = self.state_mut();
-            match target {
-                NavigationTarget::Internal(p) => state.history.replace(p),
-                NavigationTarget::External(e) => return self.external(e),
+            match navigation {
+                Navigation::Push => {
+                    if let Some(scroll) = &self.scroll {
+                        // histories don't push the route that is already active
+                        let current = state.history.current_route();
+                        if self.any_route_to_string(&*current) != self.any_route_to_string(&*route)
+                        {
+                            scroll.save(state.history_index);
+                            scroll.scroll_to_top(&*route);
+                            state.history_index += 1;
+                        }
+                    }
+                    state.history.push(route)
+                }
+                Navigation::Replace => state.history.replace(route),
+                // the history is already at a route that was redirected from, so the redirect
+                // takes its place
+                _ if redirects > 0 => state.history.replace(route),
+                Navigation::Back | Navigation::Forward => {
+                    if let Some(scroll) = &self.scroll {
+                        scroll.restore(state.history_index, &*route);
+                    }
+                }
+                Navigation::Initial | Navigation::Popped => {}
             }
         }
 
@@ -205,10 +567,7 @@ impl RouterContext {
 
     /// The route that is currently active.
     pub fn current<R: Routable>(&self) -> R {
-        self.state
-            .borrow()
-            .history
-            .current_route()
+        self.current_route()
             .downcast::<R>()
             .unwrap()
             .as_ref()
@@ -217,7 +576,15 @@ impl RouterContext {
 
     /// The route that is currently active.
     pub fn current_route_string(&self) -> String {
-        self.any_route_to_string(&*self.state.borrow().history.current_route())
+        self.any_route_to_string(&*self.current_route())
+    }
+
+    fn current_route(&self) -> Rc<dyn Any> {
+        let state = self.state.borrow();
+        match (&self.guard, &state.allowed) {
+            (Some(_), Some(allowed)) => allowed.clone(),
+            _ => state.history.current_route(),
+        }
     }
 
     pub(crate) fn any_route_to_string(&self, route: &dyn Any) -> String {
@@ -307,6 +674,11 @@ impl RouterContext {
             }
         }
 
+        {
+            let mut state = self.state_mut();
+            state.allowed = Some(state.history.current_route());
+        }
+
         self.update_subscribers();
 
         None
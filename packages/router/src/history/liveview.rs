@@ -1,4 +1,4 @@
-use super::HistoryProvider;
+use super::{HistoryProvider, ScrollConfig};
 use crate::routable::Routable;
 use dioxus::prelude::*;
 use serde::{Deserialize, Serialize};
@@ -13,6 +13,7 @@ where
     action_tx: tokio::sync::mpsc::UnboundedSender<Action<R>>,
     timeline: Arc<Mutex<Timeline<R>>>,
     updater_callback: Arc<RwLock<Arc<dyn Fn() + Send + Sync>>>,
+    scroll: ScrollConfig<R>,
 }
 
 struct Timeline<R: Routable>
@@ -46,7 +47,8 @@ struct SessionStorage {
 enum Action<R: Routable> {
     GoBack,
     GoForward,
-    Push(R),
+    /// Push a route, and scroll to the top of the page if the flag is set
+    Push(R, bool),
     Replace(R),
     External(String),
 }
@@ -196,17 +198,23 @@ where
                                 history.forward();
                             "#,
                         ),
-                        Action::Push(route) => {
+                        Action::Push(route, scroll_to_top) => {
                             let mut timeline = timeline.lock().expect("unpoisoned mutex");
                             let state = timeline.push(route.clone());
                             let state = serde_json::to_string(&state).expect("serializable state");
                             let session = serde_json::to_string(&timeline.session())
                                 .expect("serializable session");
+                            let scroll = if scroll_to_top {
+                                "window.scrollTo(0, 0);"
+                            } else {
+                                ""
+                            };
                             create_eval(&format!(
                                 r#"
                                 // this does not trigger a PopState event
                                 history.pushState({state}, "", "{route}");
                                 sessionStorage.setItem("liveview", '{session}');
+                                {scroll}
                             "#
                             ))
                         }
@@ -315,6 +323,7 @@ where
             action_tx,
             timeline,
             updater_callback,
+            scroll: ScrollConfig::default(),
         }
     }
 }
@@ -332,7 +341,8 @@ where
     }
 
     fn push(&mut self, route: R) {
-        let _ = self.action_tx.send(Action::Push(route));
+        let scroll_to_top = self.scroll.scrolls_to_top(&route);
+        let _ = self.action_tx.send(Action::Push(route, scroll_to_top));
     }
 
     fn replace(&mut self, route: R) {
@@ -378,6 +388,13 @@ where
         let mut updater_callback = self.updater_callback.write().unwrap();
         *updater_callback = callback;
     }
+
+    /// Pushing a route scrolls to the top of the page as configured. Going back or forward is
+    /// left to the browser, which restores the scroll position of the entry on its own.
+    fn handle_scroll(&mut self, config: ScrollConfig<R>) -> bool {
+        self.scroll = config;
+        true
+    }
 }
 
 mod routes {
@@ -284,6 +284,73 @@ pub trait HistoryProvider<R: Routable> {
     /// updates are received, they should call `callback`, which will cause the router to update.
     #[allow(unused_variables)]
     fn updater(&mut self, callback: Arc<dyn Fn() + Send + Sync>) {}
+
+    /// Take over handling the scroll position of the page.
+    ///
+    /// [`HistoryProvider`]s that are connected to a browser history, like [`WebHistory`], can
+    /// remember the scroll position of every entry and restore it when the user goes back or
+    /// forward, even if the router did not trigger the navigation. They should follow the `config`
+    /// and return [`true`].
+    ///
+    /// If this returns [`false`], the router handles the scroll position itself, as long as the
+    /// platform can evaluate JavaScript (like a desktop webview). Defaults to [`false`].
+    #[allow(unused_variables)]
+    fn handle_scroll(&mut self, config: ScrollConfig<R>) -> bool {
+        false
+    }
+}
+
+/// Decides whether the scroll position of a route is kept when navigating back to it.
+type PreserveScroll<R> = Rc<dyn Fn(&R) -> bool>;
+
+/// How the scroll position of the page changes when the router navigates.
+///
+/// Set it up with [`RouterConfig::scroll_to_top`](crate::prelude::RouterConfig::scroll_to_top)
+/// and [`RouterConfig::preserve_scroll`](crate::prelude::RouterConfig::preserve_scroll).
+pub struct ScrollConfig<R> {
+    scroll_to_top: bool,
+    preserve_scroll: Option<PreserveScroll<R>>,
+}
+
+impl<R> ScrollConfig<R> {
+    pub(crate) fn set_scroll_to_top(&mut self, scroll_to_top: bool) {
+        self.scroll_to_top = scroll_to_top;
+    }
+
+    pub(crate) fn set_preserve_scroll(&mut self, preserve: impl Fn(&R) -> bool + 'static) {
+        self.preserve_scroll = Some(Rc::new(preserve));
+    }
+
+    /// Check whether the scroll position is restored when going back or forward to `route`.
+    pub fn restores(&self, route: &R) -> bool {
+        match &self.preserve_scroll {
+            Some(preserve) => !preserve(route),
+            None => true,
+        }
+    }
+
+    /// Check whether pushing `route` scrolls to the top of the page.
+    pub fn scrolls_to_top(&self, route: &R) -> bool {
+        self.scroll_to_top && self.restores(route)
+    }
+}
+
+impl<R> Default for ScrollConfig<R> {
+    fn default() -> Self {
+        Self {
+            scroll_to_top: true,
+            preserve_scroll: None,
+        }
+    }
+}
+
+impl<R> Clone for ScrollConfig<R> {
+    fn clone(&self) -> Self {
+        Self {
+            scroll_to_top: self.scroll_to_top,
+            preserve_scroll: self.preserve_scroll.clone(),
+        }
+    }
 }
 
 pub(crate) trait AnyHistoryProvider {
@@ -325,6 +392,11 @@ pub(crate) trait AnyHistoryProvider {
 
     #[allow(unused_variables)]
     fn updater(&mut self, callback: Arc<dyn Fn() + Send + Sync>) {}
+
+    #[allow(unused_variables)]
+    fn handle_scroll(&mut self, config: &dyn Any) -> bool {
+        false
+    }
 }
 
 pub(crate) struct AnyHistoryProviderImplWrapper<R, H> {
@@ -405,4 +477,9 @@ where
     fn updater(&mut self, callback: Arc<dyn Fn() + Send + Sync>) {
         self.inner.updater(callback)
     }
+
+    fn handle_scroll(&mut self, config: &dyn Any) -> bool {
+        let config = config.downcast_ref::<ScrollConfig<R>>().unwrap().clone();
+        self.inner.handle_scroll(config)
+    }
 }
@@ -10,9 +10,17 @@ use crate::routable::Routable;
 use super::{
     web_history::{get_current, push_state_and_url, replace_state_with_url},
     web_scroll::ScrollPosition,
-    HistoryProvider,
+    HistoryProvider, ScrollConfig,
 };
 
+/// The path of the current url, including the query and the hash fragment
+fn location_path(window: &Window) -> String {
+    let location = window.location();
+    location.pathname().unwrap_or_else(|_| "/".into())
+        + &location.search().unwrap_or("".into())
+        + &location.hash().unwrap_or("".into())
+}
+
 #[cfg(not(feature = "serde"))]
 #[allow(clippy::extra_unused_type_parameters)]
 fn update_scroll<R>(window: &Window, history: &History) {
@@ -61,6 +69,7 @@ pub struct WebHistory<R: Routable> {
     listener_navigation: Option<EventListener>,
     listener_animation_frame: Arc<Mutex<Option<AnimationFrame>>>,
     prefix: Option<String>,
+    scroll: ScrollConfig<R>,
     window: Window,
     phantom: std::marker::PhantomData<R>,
 }
@@ -166,6 +175,7 @@ impl<R: Routable> WebHistory<R> {
             listener_navigation: None,
             listener_animation_frame: Default::default(),
             prefix,
+            scroll: ScrollConfig::default(),
             window,
             phantom: Default::default(),
         }
@@ -195,10 +205,7 @@ where
     <R as std::str::FromStr>::Err: std::fmt::Display,
 {
     fn route_from_location(&self) -> R {
-        let location = self.window.location();
-        let path = location.pathname().unwrap_or_else(|_| "/".into())
-            + &location.search().unwrap_or("".into());
-        R::from_str(&path).unwrap_or_else(|err| panic!("{}", err))
+        R::from_str(&location_path(&self.window)).unwrap_or_else(|err| panic!("{}", err))
     }
 
     fn full_path(&self, state: &R) -> String {
@@ -208,10 +215,10 @@ where
         }
     }
 
-    fn handle_nav(&self, result: Result<(), JsValue>) {
+    fn handle_nav(&self, result: Result<(), JsValue>, scroll_to_top: bool) {
         match result {
             Ok(_) => {
-                if self.do_scroll_restoration {
+                if self.do_scroll_restoration && scroll_to_top {
                     self.window.scroll_to_with_x_and_y(0.0, 0.0)
                 }
             }
@@ -275,10 +282,14 @@ where
         update_scroll::<R>(&w, &h);
 
         let path = self.full_path(&state);
+        let scroll_to_top = self.scroll.scrolls_to_top(&state);
 
         let state = self.create_state(state);
 
-        self.handle_nav(push_state_and_url(&self.history, &state, path));
+        self.handle_nav(
+            push_state_and_url(&self.history, &state, path),
+            scroll_to_top,
+        );
     }
 
     fn replace(&mut self, state: R) {
@@ -289,7 +300,10 @@ where
 
         let state = self.create_state(state);
 
-        self.handle_nav(replace_state_with_url(&self.history, &state, Some(&path)));
+        self.handle_nav(
+            replace_state_with_url(&self.history, &state, Some(&path)),
+            false,
+        );
     }
 
     fn external(&mut self, url: String) -> bool {
@@ -301,17 +315,25 @@ where
         let h = self.history.clone();
         let s = self.listener_animation_frame.clone();
         let d = self.do_scroll_restoration;
+        let scroll = self.scroll.clone();
 
         self.listener_navigation = Some(EventListener::new(&self.window, "popstate", move |_| {
             (*callback)();
             if d {
                 let mut s = s.lock().expect("unpoisoned scroll mutex");
                 if let Some(current_state) = get_current::<WebHistoryState<R>>(&h) {
-                    *s = Some(current_state.scroll.scroll_to(w.clone()));
+                    if scroll.restores(&current_state.state) {
+                        *s = Some(current_state.scroll.scroll_to(w.clone()));
+                    }
                 }
             }
         }));
     }
+
+    fn handle_scroll(&mut self, config: ScrollConfig<R>) -> bool {
+        self.scroll = config;
+        true
+    }
 }
 
 #[cfg(not(feature = "serde"))]
@@ -352,9 +374,13 @@ where
         update_scroll::<R>(&w, &h);
 
         let path = self.full_path(&state);
+        let scroll_to_top = self.scroll.scrolls_to_top(&state);
 
         let state: [f64; 2] = self.create_state(state);
-        self.handle_nav(push_state_and_url(&self.history, &state, path));
+        self.handle_nav(
+            push_state_and_url(&self.history, &state, path),
+            scroll_to_top,
+        );
     }
 
     fn replace(&mut self, state: R) {
@@ -364,7 +390,10 @@ where
         };
 
         let state = self.create_state(state);
-        self.handle_nav(replace_state_with_url(&self.history, &state, Some(&path)));
+        self.handle_nav(
+            replace_state_with_url(&self.history, &state, Some(&path)),
+            false,
+        );
     }
 
     fn external(&mut self, url: String) -> bool {
@@ -376,10 +405,13 @@ where
         let h = self.history.clone();
         let s = self.listener_animation_frame.clone();
         let d = self.do_scroll_restoration;
+        let scroll = self.scroll.clone();
 
         self.listener_navigation = Some(EventListener::new(&self.window, "popstate", move |_| {
             (*callback)();
-            if d {
+            let restores =
+                R::from_str(&location_path(&w)).map_or(true, |route| scroll.restores(&route));
+            if d && restores {
                 let mut s = s.lock().expect("unpoisoned scroll mutex");
                 if let Some([x, y]) = get_current(&h) {
                     *s = Some(ScrollPosition { x, y }.scroll_to(w.clone()));
@@ -387,4 +419,9 @@ where
             }
         }));
     }
+
+    fn handle_scroll(&mut self, config: ScrollConfig<R>) -> bool {
+        self.scroll = config;
+        true
+    }
 }
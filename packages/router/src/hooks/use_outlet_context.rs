@@ -0,0 +1,51 @@
+use dioxus::prelude::ScopeState;
+
+use crate::prelude::OutletContext;
+
+/// A hook that provides access to where the next [`Outlet`](crate::components::Outlet) is in the chain of layouts
+/// and the route that matched the current location.
+///
+/// In a layout, this is the position of the layout or route its outlet renders.
+///
+/// ```rust
+/// # use dioxus::prelude::*;
+/// # use dioxus_router::prelude::*;
+/// #[derive(Clone, Routable)]
+/// #[rustfmt::skip]
+/// enum Route {
+///     #[layout(Frame)]
+///         #[route("/")]
+///         Index {},
+/// }
+///
+/// #[component]
+/// fn App(cx: Scope) -> Element {
+///     render! { Router::<Route> {} }
+/// }
+///
+/// #[component]
+/// fn Frame(cx: Scope) -> Element {
+///     let level = use_outlet_context::<Route>(cx).level();
+///     render! {
+///         h1 { "Frame renders level {level}" }
+///         Outlet::<Route> {}
+///     }
+/// }
+///
+/// #[component]
+/// fn Index(cx: Scope) -> Element {
+///     render! { p { "Index" } }
+/// }
+/// #
+/// # let mut vdom = VirtualDom::new(App);
+/// # let _ = vdom.rebuild();
+/// # assert_eq!(dioxus_ssr::render(&vdom), "<h1>Frame renders level 1</h1><p>Index</p>");
+/// ```
+pub fn use_outlet_context<R: 'static>(cx: &ScopeState) -> &OutletContext<R> {
+    cx.use_hook(|| {
+        cx.consume_context().unwrap_or(OutletContext::<R> {
+            current_level: 1,
+            _marker: std::marker::PhantomData,
+        })
+    })
+}
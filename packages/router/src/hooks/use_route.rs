@@ -7,6 +7,8 @@ use crate::utils::use_router_internal::use_router_internal;
 ///
 /// > The Routable macro will define a version of this hook with an explicit type.
 ///
+/// Query segments (`?:query`) and hash fragments (`#:hash`) declared in the route are fields of the returned route.
+///
 /// # Return values
 /// - None, when not called inside a [`Link`] component.
 /// - Otherwise the current route.
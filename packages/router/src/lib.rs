@@ -34,6 +34,7 @@ mod contexts {
     pub(crate) mod outlet;
     pub(crate) mod router;
     pub use navigator::*;
+    pub use outlet::OutletContext;
     pub(crate) use router::*;
 }
 
@@ -51,6 +52,9 @@ pub mod hooks {
 
     mod use_navigator;
     pub use use_navigator::*;
+
+    mod use_outlet_context;
+    pub use use_outlet_context::*;
 }
 
 /// A collection of useful items most applications might need.
@@ -54,6 +54,20 @@ pub enum NavigationTarget<R> {
     External(String),
 }
 
+/// What a navigation guard decides to do with a navigation.
+///
+/// Guards are set with [`RouterConfig::guard`](crate::prelude::RouterConfig::guard) and
+/// [`RouterConfig::async_guard`](crate::prelude::RouterConfig::async_guard).
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub enum GuardResult<R> {
+    /// Navigate to the route.
+    Allow,
+    /// Stay on the current route.
+    Cancel,
+    /// Navigate to another target instead. The route the guard redirects to is guarded as well.
+    Redirect(NavigationTarget<R>),
+}
+
 impl<R: Routable> From<&str> for NavigationTarget<R> {
     fn from(value: &str) -> Self {
         value
@@ -31,6 +31,8 @@ impl<E: Display> Display for RouteParseError<E> {
 /// A working example can be found in the `examples` folder in the root package under `query_segments_demo`.
 pub trait FromQuery {
     /// Create an instance of `Self` from a query string.
+    ///
+    /// The query string is passed exactly as it appears in the url, so it is still url encoded.
     fn from_query(query: &str) -> Self;
 }
 
@@ -40,6 +42,111 @@ impl<T: for<'a> From<&'a str>> FromQuery for T {
     }
 }
 
+/// Query parameters that are deserialized from the query string with serde.
+///
+/// Use it as the type of a query segment to read the parameters of a url like `/posts?page=2&sort=asc` into a struct.
+/// Displaying the route serializes the parameters back into the query string.
+///
+/// If the query string does not match the struct, an error is logged and the default value is used instead.
+///
+/// ```rust
+/// # use dioxus::prelude::*;
+/// # use dioxus_router::prelude::*;
+/// # use serde::{Deserialize, Serialize};
+/// #[derive(Routable, Clone, PartialEq)]
+/// enum Route {
+///     #[route("/posts?:query")]
+///     Posts { query: Query<PostsQuery> },
+/// }
+///
+/// #[derive(Serialize, Deserialize, Clone, Default, PartialEq)]
+/// struct PostsQuery {
+///     page: Option<usize>,
+///     sort: Option<String>,
+/// }
+///
+/// #[component]
+/// fn Posts(cx: Scope, query: Query<PostsQuery>) -> Element {
+///     let page = query.page.unwrap_or(1);
+///     render! { "Page {page}" }
+/// }
+///
+/// let route: Route = "/posts?page=2&sort=asc".parse().unwrap();
+/// assert_eq!(route.to_string(), "/posts?page=2&sort=asc");
+/// ```
+#[cfg(feature = "query")]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct Query<T>(pub T);
+
+#[cfg(feature = "query")]
+impl<T: serde::de::DeserializeOwned + Default> FromQuery for Query<T> {
+    fn from_query(query: &str) -> Self {
+        match serde_urlencoded::from_str(query) {
+            Ok(value) => Self(value),
+            Err(err) => {
+                tracing::error!(
+                    "Failed to deserialize the query string '{}': {}",
+                    query,
+                    err
+                );
+                Self(T::default())
+            }
+        }
+    }
+}
+
+#[cfg(feature = "query")]
+impl<T: serde::Serialize> Display for Query<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let query = serde_urlencoded::to_string(&self.0).map_err(|_| std::fmt::Error)?;
+        f.write_str(&query)
+    }
+}
+
+#[cfg(feature = "query")]
+impl<T> std::ops::Deref for Query<T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.0
+    }
+}
+
+#[cfg(feature = "query")]
+impl<T> std::ops::DerefMut for Query<T> {
+    fn deref_mut(&mut self) -> &mut T {
+        &mut self.0
+    }
+}
+
+/// Something that can be created from the hash fragment of a url.
+///
+/// This trait needs to be implemented if you want to turn the part of the url after the `#` into a struct.
+pub trait FromHashFragment {
+    /// Create an instance of `Self` from a hash fragment, without the leading `#`.
+    ///
+    /// The hash fragment is passed exactly as it appears in the url, so it is still url encoded.
+    fn from_hash_fragment(hash: &str) -> Self;
+}
+
+impl<T: for<'a> From<&'a str>> FromHashFragment for T {
+    fn from_hash_fragment(hash: &str) -> Self {
+        match urlencoding::decode(hash) {
+            Ok(decoded) => T::from(&*decoded),
+            Err(err) => {
+                tracing::error!("Failed to decode url encoding: {}", err);
+                T::from(hash)
+            }
+        }
+    }
+}
+
+#[test]
+fn malformed_hash_fragment() {
+    let hash = "section%FF";
+    assert_eq!(String::from_hash_fragment(hash), hash);
+}
+
 /// Something that can be created from a route segment.
 pub trait FromRouteSegment: Sized {
     /// The error that can occur when parsing a route segment.
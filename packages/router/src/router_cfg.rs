@@ -1,7 +1,7 @@
-use std::sync::Arc;
+use std::{future::Future, sync::Arc};
 
-use crate::contexts::router::RoutingCallback;
-use crate::history::HistoryProvider;
+use crate::contexts::router::{RouteGuard, RoutingCallback};
+use crate::history::{HistoryProvider, ScrollConfig};
 use crate::routable::Routable;
 use dioxus::prelude::*;
 
@@ -28,6 +28,8 @@ pub struct RouterConfig<R: Routable> {
     pub(crate) failure_external_navigation: fn(Scope) -> Element,
     pub(crate) history: Option<Box<dyn AnyHistoryProvider>>,
     pub(crate) on_update: Option<RoutingCallback<R>>,
+    pub(crate) guard: Option<RouteGuard<R>>,
+    pub(crate) scroll: ScrollConfig<R>,
 }
 
 #[cfg(feature = "serde")]
@@ -41,6 +43,8 @@ where
             failure_external_navigation: FailureExternalNavigation::<R>,
             history: None,
             on_update: None,
+            guard: None,
+            scroll: ScrollConfig::default(),
         }
     }
 }
@@ -72,6 +76,8 @@ where
             failure_external_navigation: FailureExternalNavigation,
             history: None,
             on_update: None,
+            guard: None,
+            scroll: ScrollConfig::default(),
         }
     }
 }
@@ -120,6 +126,104 @@ where
         }
     }
 
+    /// A function to be called before the router navigates to a new route.
+    ///
+    /// The guard decides whether the navigation happens, is cancelled, or is redirected to another
+    /// target, which makes it a good place for checks like authentication. It runs when a route is
+    /// pushed or replaced, including by a [`Link`], for the route the router starts at, and when
+    /// going back or forward in the history. A cancelled step back or forward is undone.
+    ///
+    /// The route the guard redirects to is guarded as well. If the guard keeps redirecting more than
+    /// 16 times, the router reports an error and cancels the navigation. Nothing is shown until the
+    /// guard allows a route for the first time.
+    ///
+    /// ```rust,no_run
+    /// # use dioxus_router::prelude::*;
+    /// # use dioxus::prelude::*;
+    /// # #[component]
+    /// # fn Login(cx: Scope) -> Element { todo!() }
+    /// # #[component]
+    /// # fn Admin(cx: Scope) -> Element { todo!() }
+    /// # fn logged_in() -> bool { todo!() }
+    /// #[derive(Clone, Routable)]
+    /// enum Route {
+    ///     #[route("/login")]
+    ///     Login {},
+    ///     #[route("/admin")]
+    ///     Admin {},
+    /// }
+    ///
+    /// let cfg = RouterConfig::default().guard(|route: &Route| match route {
+    ///     Route::Admin {} if !logged_in() => GuardResult::Redirect(Route::Login {}.into()),
+    ///     _ => GuardResult::Allow,
+    /// });
+    /// ```
+    ///
+    /// Replaces any guard set with [`RouterConfig::async_guard`]. Defaults to [`None`].
+    pub fn guard(self, guard: impl Fn(&R) -> GuardResult<R> + 'static) -> Self {
+        Self {
+            guard: Some(RouteGuard::Sync(Arc::new(guard))),
+            ..self
+        }
+    }
+
+    /// A function to be called before the router navigates to a new route, that decides
+    /// asynchronously.
+    ///
+    /// This works like [`RouterConfig::guard`], but the router keeps showing the current route until
+    /// the future resolves. If another navigation starts before that, the result is ignored. Nothing
+    /// is shown until the guard allows the route the router starts at.
+    ///
+    /// Replaces any guard set with [`RouterConfig::guard`]. Defaults to [`None`].
+    pub fn async_guard<F>(self, guard: impl Fn(R) -> F + 'static) -> Self
+    where
+        F: Future<Output = GuardResult<R>> + 'static,
+    {
+        Self {
+            guard: Some(RouteGuard::Async(Arc::new(move |route| {
+                Box::pin(guard(route))
+            }))),
+            ..self
+        }
+    }
+
+    /// Whether pushing a new route scrolls to the top of the page.
+    ///
+    /// Going back or forward always restores the scroll position the page had when it was left.
+    ///
+    /// Defaults to [`true`].
+    pub fn scroll_to_top(mut self, scroll_to_top: bool) -> Self {
+        self.scroll.set_scroll_to_top(scroll_to_top);
+        self
+    }
+
+    /// A function that decides which routes keep the scroll position the page has when navigating
+    /// to them.
+    ///
+    /// The router neither scrolls to the top when these routes are pushed, nor restores a scroll
+    /// position when going back or forward to them. This is useful for routes that only change a
+    /// part of the page, like a tab or a dialog.
+    ///
+    /// ```rust,no_run
+    /// # use dioxus_router::prelude::*;
+    /// # use dioxus::prelude::*;
+    /// # #[component]
+    /// # fn Settings(cx: Scope, tab: String) -> Element { todo!() }
+    /// #[derive(Clone, Routable)]
+    /// enum Route {
+    ///     #[route("/settings/:tab")]
+    ///     Settings { tab: String },
+    /// }
+    ///
+    /// let cfg = RouterConfig::default().preserve_scroll(|route: &Route| matches!(route, Route::Settings { .. }));
+    /// ```
+    ///
+    /// Defaults to [`None`].
+    pub fn preserve_scroll(mut self, preserve: impl Fn(&R) -> bool + 'static) -> Self {
+        self.scroll.set_preserve_scroll(preserve);
+        self
+    }
+
     /// The [`HistoryProvider`] the router should use.
     ///
     /// Defaults to a default [`MemoryHistory`].
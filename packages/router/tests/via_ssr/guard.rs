@@ -0,0 +1,242 @@
+#![allow(unused)]
+
+use dioxus::prelude::*;
+use dioxus_router::prelude::*;
+use std::{cell::Cell, time::Duration};
+
+fn prepare(start: &'static str, navigate_to: Option<&'static str>) -> String {
+    let mut vdom = VirtualDom::new_with_props(App, AppProps { start, navigate_to });
+    let _ = vdom.rebuild();
+    let _ = vdom.render_immediate();
+    return dioxus_ssr::render(&vdom);
+
+    #[derive(Routable, Clone, PartialEq)]
+    enum Route {
+        #[route("/")]
+        Home {},
+        #[route("/login")]
+        Login {},
+        #[route("/admin")]
+        Admin {},
+        #[route("/blocked")]
+        Blocked {},
+        #[route("/ping")]
+        Ping {},
+        #[route("/pong")]
+        Pong {},
+    }
+
+    fn guard(route: &Route) -> GuardResult<Route> {
+        match route {
+            Route::Admin {} => GuardResult::Redirect(Route::Login {}.into()),
+            Route::Blocked {} => GuardResult::Cancel,
+            Route::Ping {} => GuardResult::Redirect(Route::Pong {}.into()),
+            Route::Pong {} => GuardResult::Redirect(Route::Ping {}.into()),
+            _ => GuardResult::Allow,
+        }
+    }
+
+    #[derive(Props, PartialEq)]
+    struct AppProps {
+        start: &'static str,
+        navigate_to: Option<&'static str>,
+    }
+
+    #[component]
+    fn App(cx: Scope<AppProps>) -> Element {
+        let start = cx.props.start;
+        let navigate_to = cx.props.navigate_to;
+        cx.provide_context(navigate_to);
+        render! {
+            Router::<Route> {
+                config: move || {
+                    RouterConfig::default()
+                        .history(MemoryHistory::with_initial_path(start.parse().unwrap()))
+                        .guard(guard)
+                }
+            }
+        }
+    }
+
+    #[component]
+    fn Home(cx: Scope) -> Element {
+        let navigator = use_navigator(cx);
+        let navigate_to = cx.consume_context::<Option<&'static str>>().flatten();
+        cx.use_hook(|| {
+            if let Some(target) = navigate_to {
+                navigator.push(target);
+            }
+        });
+        render! { h1 { "Home" } }
+    }
+
+    #[component]
+    fn Login(cx: Scope) -> Element {
+        render! { h1 { "Login" } }
+    }
+
+    #[component]
+    fn Admin(cx: Scope) -> Element {
+        render! { h1 { "Admin" } }
+    }
+
+    #[component]
+    fn Blocked(cx: Scope) -> Element {
+        render! { h1 { "Blocked" } }
+    }
+
+    #[component]
+    fn Ping(cx: Scope) -> Element {
+        render! { h1 { "Ping" } }
+    }
+
+    #[component]
+    fn Pong(cx: Scope) -> Element {
+        render! { h1 { "Pong" } }
+    }
+}
+
+fn go_back(home_locked: bool) -> String {
+    thread_local! {
+        static LOCKED: Cell<bool> = Cell::new(false);
+        static PUSHED: Cell<bool> = Cell::new(false);
+    }
+
+    let mut vdom = VirtualDom::new_with_props(App, AppProps { home_locked });
+    let _ = vdom.rebuild();
+    for _ in 0..3 {
+        let _ = vdom.render_immediate();
+    }
+    return dioxus_ssr::render(&vdom);
+
+    #[derive(Routable, Clone, PartialEq)]
+    enum Route {
+        #[route("/")]
+        Home {},
+        #[route("/settings")]
+        Settings {},
+    }
+
+    #[derive(Props, PartialEq)]
+    struct AppProps {
+        home_locked: bool,
+    }
+
+    #[component]
+    fn App(cx: Scope<AppProps>) -> Element {
+        cx.provide_context(cx.props.home_locked);
+        render! {
+            Router::<Route> {
+                config: || {
+                    RouterConfig::default()
+                        .history(MemoryHistory::default())
+                        .guard(|route: &Route| match route {
+                            Route::Home {} if LOCKED.with(Cell::get) => GuardResult::Cancel,
+                            _ => GuardResult::Allow,
+                        })
+                }
+            }
+        }
+    }
+
+    #[component]
+    fn Home(cx: Scope) -> Element {
+        let navigator = use_navigator(cx);
+        cx.use_hook(|| {
+            if !PUSHED.with(|pushed| pushed.replace(true)) {
+                navigator.push(Route::Settings {});
+            }
+        });
+        render! { h1 { "Home" } }
+    }
+
+    #[component]
+    fn Settings(cx: Scope) -> Element {
+        let navigator = use_navigator(cx);
+        let home_locked = *cx.consume_context::<bool>().unwrap();
+        cx.use_hook(|| {
+            LOCKED.with(|locked| locked.set(home_locked));
+            navigator.go_back();
+        });
+        render! { h1 { "Settings" } }
+    }
+}
+
+#[test]
+fn redirect_loop_is_cancelled() {
+    assert_eq!(prepare("/", Some("/ping")), "<h1>Home</h1>");
+}
+
+#[test]
+fn going_back_is_guarded() {
+    assert_eq!(go_back(false), "<h1>Home</h1>");
+}
+
+#[test]
+fn cancelled_going_back_stays() {
+    assert_eq!(go_back(true), "<h1>Settings</h1>");
+}
+
+#[tokio::test]
+async fn async_guard_holds_the_first_render() {
+    let mut vdom = VirtualDom::new(App);
+    let _ = vdom.rebuild();
+    assert_eq!(dioxus_ssr::render(&vdom), "");
+
+    tokio::time::timeout(Duration::from_secs(1), vdom.wait_for_work())
+        .await
+        .unwrap();
+    let _ = vdom.render_immediate();
+    assert_eq!(dioxus_ssr::render(&vdom), "<h1>Login</h1>");
+
+    #[derive(Routable, Clone, PartialEq)]
+    enum Route {
+        #[route("/login")]
+        Login {},
+        #[route("/admin")]
+        Admin {},
+    }
+
+    #[component]
+    fn App(cx: Scope) -> Element {
+        render! {
+            Router::<Route> {
+                config: || {
+                    RouterConfig::default()
+                        .history(MemoryHistory::with_initial_path(Route::Admin {}))
+                        .async_guard(|route| async move {
+                            match route {
+                                Route::Admin {} => GuardResult::Redirect(Route::Login {}.into()),
+                                Route::Login {} => GuardResult::Allow,
+                            }
+                        })
+                }
+            }
+        }
+    }
+
+    #[component]
+    fn Login(cx: Scope) -> Element {
+        render! { h1 { "Login" } }
+    }
+
+    #[component]
+    fn Admin(cx: Scope) -> Element {
+        render! { h1 { "Admin" } }
+    }
+}
+
+#[test]
+fn initial_route_is_guarded() {
+    assert_eq!(prepare("/admin", None), "<h1>Login</h1>");
+}
+
+#[test]
+fn cancelled_navigation_stays() {
+    assert_eq!(prepare("/", Some("/blocked")), "<h1>Home</h1>");
+}
+
+#[test]
+fn redirected_navigation() {
+    assert_eq!(prepare("/", Some("/admin")), "<h1>Login</h1>");
+}
@@ -1,2 +1,5 @@
+mod guard;
 mod link;
 mod outlet;
+mod query;
+mod scroll;
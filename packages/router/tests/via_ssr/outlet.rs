@@ -144,3 +144,64 @@ fn parameter_fixed() {
         "<h1>App</h1><h2>Parameter 18</h2><h3>Parameter - Fixed</h3>"
     );
 }
+
+#[test]
+fn outlet_levels() {
+    #[derive(Routable, Clone)]
+    #[rustfmt::skip]
+    enum Route {
+        #[layout(Outer)]
+            #[nest("/inner")]
+                #[layout(Inner)]
+                    #[route("/")]
+                    InnerIndex {},
+                #[end_layout]
+            #[end_nest]
+            #[route("/")]
+            OuterIndex {},
+    }
+
+    #[component]
+    fn App(cx: Scope) -> Element {
+        render! {
+            Router::<Route> {
+                config: || RouterConfig::default().history(MemoryHistory::with_initial_path(Route::InnerIndex {}))
+            }
+        }
+    }
+
+    #[component]
+    fn Outer(cx: Scope) -> Element {
+        let level = use_outlet_context::<Route>(cx).level();
+        render! {
+            h1 { "Outer {level}" }
+            Outlet::<Route> { }
+        }
+    }
+
+    #[component]
+    fn Inner(cx: Scope) -> Element {
+        let level = use_outlet_context::<Route>(cx).level();
+        render! {
+            h2 { "Inner {level}" }
+            Outlet::<Route> { }
+        }
+    }
+
+    #[component]
+    fn InnerIndex(cx: Scope) -> Element {
+        render! { h3 { "Inner Index" } }
+    }
+
+    #[component]
+    fn OuterIndex(cx: Scope) -> Element {
+        render! { h3 { "Outer Index" } }
+    }
+
+    let mut vdom = VirtualDom::new(App);
+    let _ = vdom.rebuild();
+    assert_eq!(
+        dioxus_ssr::render(&vdom),
+        "<h1>Outer 1</h1><h2>Inner 2</h2><h3>Inner Index</h3>"
+    );
+}
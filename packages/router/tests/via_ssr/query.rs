@@ -0,0 +1,109 @@
+#![allow(unused)]
+
+use dioxus::prelude::*;
+use dioxus_router::prelude::*;
+use serde::{Deserialize, Serialize};
+
+#[derive(Routable, Clone, Debug, PartialEq)]
+enum Route {
+    #[route("/posts?:query")]
+    Posts { query: Query<PostsQuery> },
+    #[route("/docs/:page#:section")]
+    Docs { page: String, section: String },
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq)]
+struct PostsQuery {
+    page: Option<usize>,
+    sort: Option<String>,
+}
+
+#[component]
+fn Posts(cx: Scope, query: Query<PostsQuery>) -> Element {
+    // The hook sees the same query as the props
+    match use_route::<Route>(cx) {
+        Some(Route::Posts { query: from_hook }) => assert_eq!(&from_hook, query),
+        _ => unreachable!(),
+    }
+    let page = query.page.unwrap_or(1);
+    let sort = query.sort.as_deref().unwrap_or("none");
+    render! { "page {page} sorted {sort}" }
+}
+
+#[component]
+fn Docs(cx: Scope, page: String, section: String) -> Element {
+    render! { "{page} at {section}" }
+}
+
+fn prepare(path: &str) -> String {
+    let mut vdom = VirtualDom::new_with_props(
+        App,
+        AppProps {
+            path: path.parse().unwrap(),
+        },
+    );
+    let _ = vdom.rebuild();
+    return dioxus_ssr::render(&vdom);
+
+    #[derive(Props, PartialEq)]
+    struct AppProps {
+        path: Route,
+    }
+
+    #[component]
+    fn App(cx: Scope<AppProps>) -> Element {
+        let path = cx.props.path.clone();
+        render! {
+            Router::<Route> {
+                config: move || RouterConfig::default().history(MemoryHistory::with_initial_path(path.clone()))
+            }
+        }
+    }
+}
+
+#[test]
+fn query_deserializes_into_struct() {
+    assert_eq!(prepare("/posts?page=2&sort=asc"), "page 2 sorted asc");
+    assert_eq!(prepare("/posts"), "page 1 sorted none");
+    // Values are url decoded after the query is split into parameters
+    assert_eq!(prepare("/posts?sort=a%26b"), "page 1 sorted a&amp;b");
+}
+
+#[test]
+fn query_round_trips() {
+    let route = Route::Posts {
+        query: Query(PostsQuery {
+            page: Some(3),
+            sort: Some("new & old".to_string()),
+        }),
+    };
+    let url = route.to_string();
+    assert_eq!(url, "/posts?page=3&sort=new+%26+old");
+    assert_eq!(url.parse::<Route>().unwrap(), route);
+
+    let empty = Route::Posts {
+        query: Query::default(),
+    };
+    assert_eq!(empty.to_string(), "/posts");
+}
+
+#[test]
+fn hash_fragment() {
+    assert_eq!(
+        prepare("/docs/router#query%20segments"),
+        "router at query segments"
+    );
+
+    let route: Route = "/docs/router#hooks".parse().unwrap();
+    assert_eq!(
+        route,
+        Route::Docs {
+            page: "router".to_string(),
+            section: "hooks".to_string()
+        }
+    );
+    assert_eq!(route.to_string(), "/docs/router#hooks");
+
+    let without_hash: Route = "/docs/router".parse().unwrap();
+    assert_eq!(without_hash.to_string(), "/docs/router");
+}
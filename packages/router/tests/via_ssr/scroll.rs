@@ -0,0 +1,119 @@
+#![allow(unused)]
+
+use std::{cell::RefCell, rc::Rc};
+
+use async_trait::async_trait;
+use dioxus::prelude::*;
+use dioxus_router::prelude::*;
+
+thread_local! {
+    static SCRIPTS: RefCell<Vec<String>> = RefCell::new(Vec::new());
+    static NAVIGATOR: RefCell<Option<Navigator>> = RefCell::new(None);
+}
+
+/// Records the scripts the router evaluates instead of running them
+struct RecordingEval;
+
+impl EvalProvider for RecordingEval {
+    fn new_evaluator(&self, js: String) -> Result<Rc<dyn Evaluator>, EvalError> {
+        SCRIPTS.with(|scripts| scripts.borrow_mut().push(js));
+        Ok(Rc::new(RecordingEval))
+    }
+}
+
+#[async_trait(?Send)]
+impl Evaluator for RecordingEval {
+    fn send(&self, _: serde_json::Value) -> Result<(), EvalError> {
+        Ok(())
+    }
+
+    async fn recv(&self) -> Result<serde_json::Value, EvalError> {
+        Err(EvalError::Finished)
+    }
+
+    async fn join(&self) -> Result<serde_json::Value, EvalError> {
+        Ok(serde_json::Value::Null)
+    }
+}
+
+#[derive(Routable, Clone, PartialEq)]
+enum Route {
+    #[route("/")]
+    Home {},
+    #[route("/article")]
+    Article {},
+    #[route("/tab")]
+    Tab {},
+}
+
+#[component]
+fn Home(cx: Scope) -> Element {
+    let navigator = use_navigator(cx).clone();
+    NAVIGATOR.with(|nav| *nav.borrow_mut() = Some(navigator));
+    render! { "home" }
+}
+
+#[component]
+fn Article(cx: Scope) -> Element {
+    render! { "article" }
+}
+
+#[component]
+fn Tab(cx: Scope) -> Element {
+    render! { "tab" }
+}
+
+#[component]
+fn App(cx: Scope) -> Element {
+    cx.provide_context(Rc::new(RecordingEval) as Rc<dyn EvalProvider>);
+    render! {
+        Router::<Route> {
+            config: || RouterConfig::default().preserve_scroll(|route| matches!(route, Route::Tab {}))
+        }
+    }
+}
+
+fn navigate(action: impl FnOnce(&Navigator)) -> Vec<String> {
+    SCRIPTS.with(|scripts| scripts.borrow_mut().clear());
+    NAVIGATOR.with(|nav| action(nav.borrow().as_ref().unwrap()));
+    SCRIPTS.with(|scripts| scripts.take())
+}
+
+#[test]
+fn scroll_restoration() {
+    let mut vdom = VirtualDom::new(App);
+    let _ = vdom.rebuild();
+
+    // pushing saves the position of the page that is left and scrolls to the top
+    let scripts = navigate(|nav| {
+        nav.push(Route::Article {});
+    });
+    assert_eq!(scripts.len(), 2);
+    assert!(scripts[0].contains("[0] = [window.scrollX, window.scrollY]"));
+    assert!(scripts[1].contains("window.scrollTo(0, 0)"));
+
+    // pushing the active route again does nothing
+    assert!(navigate(|nav| {
+        nav.push(Route::Article {});
+    })
+    .is_empty());
+
+    // going back restores the position of the previous page
+    let scripts = navigate(|nav| nav.go_back());
+    assert_eq!(scripts.len(), 2);
+    assert!(scripts[0].contains("[1] = [window.scrollX, window.scrollY]"));
+    assert!(scripts[1].contains("window.__dioxusRouterScroll || {})[0]"));
+
+    // replacing the route leaves the scroll position alone
+    assert!(navigate(|nav| {
+        nav.replace(Route::Article {});
+    })
+    .is_empty());
+
+    // routes that preserve the scroll position are not scrolled to the top
+    let scripts = navigate(|nav| {
+        nav.push(Route::Tab {});
+    });
+    assert_eq!(scripts.len(), 1);
+    assert!(scripts[0].contains("[0] = [window.scrollX, window.scrollY]"));
+}
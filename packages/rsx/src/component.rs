@@ -9,7 +9,7 @@
 //! - [x] Optional commas
 //! - [ ] Children
 //! - [ ] Keys
-//! - [ ] Properties spreading with with `..` syntax
+//! - [x] Properties spreading with with `..` syntax
 
 use super::*;
 
@@ -142,6 +142,15 @@ impl ToTokens for Component {
                         });
                     }
                 }
+                if !self.children.is_empty() {
+                    let renderer: TemplateRenderer = TemplateRenderer {
+                        roots: &self.children,
+                        location: None,
+                    };
+                    toks.append_all(quote! {
+                        __manual_props.children = Some({ #renderer });
+                    });
+                }
                 toks.append_all(quote! {
                     __manual_props
                 });
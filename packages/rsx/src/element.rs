@@ -42,6 +42,29 @@ impl Parse for Element {
         // "def": 456,
         // abc: 123,
         loop {
+            // Parse the attributes spread onto the element
+            if content.peek(Token![..]) {
+                content.parse::<Token![..]>()?;
+                let value = content.parse::<Expr>()?;
+                let span = value.span();
+                attributes.push(ElementAttrNamed {
+                    el_name: el_name.clone(),
+                    attr: ElementAttr::Spread {
+                        value,
+                        explicit: Vec::new(),
+                    },
+                });
+
+                if content.is_empty() {
+                    break;
+                }
+
+                if content.parse::<Token![,]>().is_err() {
+                    missing_trailing_comma!(span);
+                }
+                continue;
+            }
+
             // Parse the raw literal fields
             if content.peek(LitStr) && content.peek2(Token![:]) && !content.peek3(Token![:]) {
                 let name = content.parse::<LitStr>()?;
@@ -137,7 +160,34 @@ impl Parse for Element {
             break;
         }
 
+        // Attributes written out on the element win over the ones spread onto it
+        let explicit: Vec<_> = attributes
+            .iter()
+            .filter_map(|attr| match &attr.attr {
+                ElementAttr::AttrText { name, .. } | ElementAttr::AttrExpression { name, .. } => {
+                    Some(ElementAttrName::BuiltIn(name.clone()))
+                }
+                ElementAttr::CustomAttrText { name, .. }
+                | ElementAttr::CustomAttrExpression { name, .. } => {
+                    Some(ElementAttrName::Custom(name.clone()))
+                }
+                ElementAttr::EventTokens { .. } | ElementAttr::Spread { .. } => None,
+            })
+            .collect();
+        for attr in &mut attributes {
+            if let ElementAttr::Spread {
+                explicit: names, ..
+            } = &mut attr.attr
+            {
+                *names = explicit.clone();
+            }
+        }
+
         while !content.is_empty() {
+            if content.peek(Token![..]) {
+                attr_after_element!(content.span());
+            }
+
             if (content.peek(LitStr) && content.peek2(Token![:])) && !content.peek3(Token![:]) {
                 attr_after_element!(content.span());
             }
@@ -283,6 +333,53 @@ pub enum ElementAttr {
     // EventClosure { name: Ident, closure: ExprClosure },
     /// onclick: {}
     EventTokens { name: Ident, tokens: Expr },
+
+    /// `..attributes`
+    ///
+    /// `explicit` holds the names of the attributes written out on the same element, which the spread can't override.
+    Spread {
+        value: Expr,
+        explicit: Vec<ElementAttrName>,
+    },
+}
+
+/// The name of an attribute written out on an element
+#[derive(PartialEq, Eq, Clone, Debug, Hash)]
+pub enum ElementAttrName {
+    /// `attribute`
+    BuiltIn(Ident),
+
+    /// `"attribute"`
+    Custom(LitStr),
+}
+
+impl ElementAttrName {
+    /// The name the attribute is set with on the element. `"prop:value"` sets the `value` property.
+    fn to_rendered_tokens(&self, attribute: impl Fn(&Ident) -> TokenStream2) -> TokenStream2 {
+        match self {
+            ElementAttrName::BuiltIn(name) => attribute(name),
+            ElementAttrName::Custom(name) => match custom_property(name) {
+                Some(property) => quote! { #property },
+                None => quote! { #name },
+            },
+        }
+    }
+}
+
+/// Get the property a custom attribute written as `"prop:name"` sets. Properties are set on the element object
+/// instead of as an attribute, which lets web components take values that aren't strings.
+pub fn custom_property(name: &LitStr) -> Option<LitStr> {
+    name.value()
+        .strip_prefix("prop:")
+        .map(|property| LitStr::new(property, name.span()))
+}
+
+/// Get the name of the listener a custom attribute written as `"on:event-name"` creates. Custom elements often
+/// dispatch events with names that aren't valid identifiers.
+pub fn custom_event(name: &LitStr) -> Option<LitStr> {
+    name.value()
+        .strip_prefix("on:")
+        .map(|event| LitStr::new(&format!("on{event}"), name.span()))
 }
 
 impl ElementAttr {
@@ -293,6 +390,7 @@ impl ElementAttr {
             ElementAttr::CustomAttrText { name, .. } => name.span(),
             ElementAttr::CustomAttrExpression { name, .. } => name.span(),
             ElementAttr::EventTokens { name, .. } => name.span(),
+            ElementAttr::Spread { value, .. } => value.span(),
         }
     }
 
@@ -302,6 +400,7 @@ impl ElementAttr {
             ElementAttr::AttrExpression { .. }
                 | ElementAttr::CustomAttrExpression { .. }
                 | ElementAttr::EventTokens { .. }
+                | ElementAttr::Spread { .. }
         )
     }
 }
@@ -359,24 +458,47 @@ impl ToTokens for ElementAttrNamed {
                     )
                 }
             }
-            ElementAttr::CustomAttrText { name, value } => {
-                quote! {
+            ElementAttr::CustomAttrText { name, value } => match custom_property(name) {
+                Some(property) => quote! {
                     __cx.attr(
-                        #name,
+                        #property,
                         #value,
-                        None,
+                        Some("prop"),
                         false
                     )
-                }
-            }
-            ElementAttr::CustomAttrExpression { name, value } => {
-                quote! {
+                },
+                None => quote! {
                     __cx.attr(
                         #name,
                         #value,
                         None,
                         false
                     )
+                },
+            },
+            ElementAttr::CustomAttrExpression { name, value } => {
+                if let Some(listener) = custom_event(name) {
+                    quote! {
+                        dioxus_elements::events::custom_event(__cx, #listener, #value)
+                    }
+                } else if let Some(property) = custom_property(name) {
+                    quote! {
+                        __cx.attr(
+                            #property,
+                            #value,
+                            Some("prop"),
+                            false
+                        )
+                    }
+                } else {
+                    quote! {
+                        __cx.attr(
+                            #name,
+                            #value,
+                            None,
+                            false
+                        )
+                    }
                 }
             }
             ElementAttr::EventTokens { name, tokens } => {
@@ -384,6 +506,17 @@ impl ToTokens for ElementAttrNamed {
                     dioxus_elements::events::#name(__cx, #tokens)
                 }
             }
+            ElementAttr::Spread { value, explicit } => {
+                let explicit = explicit
+                    .iter()
+                    .map(|name| name.to_rendered_tokens(attribute));
+                quote! {
+                    __cx.spread_attr(
+                        &(#value),
+                        &[#(#explicit),*]
+                    )
+                }
+            }
         };
 
         tokens.append_all(attribute);
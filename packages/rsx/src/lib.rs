@@ -27,6 +27,7 @@ use std::{fmt::Debug, hash::Hash};
 
 // Re-export the namespaces into each other
 pub use component::*;
+use dioxus_core::{pack_paths, PackedPath};
 #[cfg(feature = "hot_reload")]
 use dioxus_core::{Template, TemplateAttribute, TemplateNode};
 pub use element::*;
@@ -150,6 +151,8 @@ pub struct TemplateRenderer<'a> {
 
 impl<'a> TemplateRenderer<'a> {
     #[cfg(feature = "hot_reload")]
+    // Fields behind optional features of dioxus-core are filled in by the empty template
+    #[allow(clippy::needless_update)]
     fn update_template<Ctx: HotReloadingContext>(
         &mut self,
         previous_call: Option<CallBody>,
@@ -166,25 +169,37 @@ impl<'a> TemplateRenderer<'a> {
             context.current_path.pop();
         }
 
+        // The compiled code still creates every dynamic node and attribute of the previous call, so each of them needs
+        // a place in the new template
+        if let Some(mapping) = &mapping {
+            if !mapping.is_exhausted() {
+                return None;
+            }
+        }
+
+        let mut unpacked_paths = Vec::new();
+        let node_paths = pack_paths(
+            context.node_paths.iter().map(Vec::as_slice),
+            &mut unpacked_paths,
+        );
+        let attr_paths = pack_paths(
+            context.attr_paths.iter().map(Vec::as_slice),
+            &mut unpacked_paths,
+        );
+
         Some(Template {
             name: location,
             roots: intern(roots.as_slice()),
-            node_paths: intern(
-                context
-                    .node_paths
-                    .into_iter()
-                    .map(|path| intern(path.as_slice()))
-                    .collect::<Vec<_>>()
-                    .as_slice(),
-            ),
-            attr_paths: intern(
-                context
-                    .attr_paths
-                    .into_iter()
-                    .map(|path| intern(path.as_slice()))
+            node_paths: intern(node_paths.as_slice()),
+            attr_paths: intern(attr_paths.as_slice()),
+            unpacked_paths: intern(
+                unpacked_paths
+                    .iter()
+                    .map(|path| intern(*path))
                     .collect::<Vec<_>>()
                     .as_slice(),
             ),
+            ..Template::EMPTY
         })
     }
 }
@@ -193,11 +208,9 @@ impl<'a> ToTokens for TemplateRenderer<'a> {
     fn to_tokens(&self, out_tokens: &mut TokenStream2) {
         let mut context = DynamicContext::default();
 
-        let key = match self.roots.get(0) {
-            Some(BodyNode::Element(el)) if self.roots.len() == 1 => el.key.clone(),
-            Some(BodyNode::Component(comp)) if self.roots.len() == 1 => comp.key().cloned(),
-            _ => None,
-        };
+        // The key of the first root is the key of the whole template, so templates with many roots move together in
+        // keyed lists
+        let key = self.roots.first().and_then(BodyNode::key).cloned();
 
         let key_tokens = match key {
             Some(tok) => quote! { Some( __cx.raw_text(#tok) ) },
@@ -237,18 +250,32 @@ impl<'a> ToTokens for TemplateRenderer<'a> {
         let root_count = self.roots.len();
         let node_printer = &context.dynamic_nodes;
         let dyn_attr_printer = &context.dynamic_attributes;
-        let node_paths = context.node_paths.iter().map(|it| quote!(&[#(#it),*]));
-        let attr_paths = context.attr_paths.iter().map(|it| quote!(&[#(#it),*]));
+        // Only the paths that are too deep or too wide to pack are kept as slices
+        let mut unpacked_paths = Vec::new();
+        let node_paths = pack_paths(
+            context.node_paths.iter().map(Vec::as_slice),
+            &mut unpacked_paths,
+        );
+        let attr_paths = pack_paths(
+            context.attr_paths.iter().map(Vec::as_slice),
+            &mut unpacked_paths,
+        );
+        let node_paths = node_paths.iter().map(packed_path_tokens);
+        let attr_paths = attr_paths.iter().map(packed_path_tokens);
+        let unpacked_paths = unpacked_paths.iter().map(|it| quote!(&[#(#it),*]));
 
         out_tokens.append_all(quote! {
+            #[allow(clippy::needless_update)]
             static TEMPLATE: ::dioxus::core::Template = ::dioxus::core::Template {
                 name: #name,
                 roots: &[ #roots ],
                 node_paths: &[ #(#node_paths),* ],
                 attr_paths: &[ #(#attr_paths),* ],
+                unpacked_paths: &[ #(#unpacked_paths),* ],
+                ..::dioxus::core::Template::EMPTY
             };
             ::dioxus::core::VNode {
-                parent: None,
+                parent: Default::default(),
                 key: #key_tokens,
                 template: std::cell::Cell::new(TEMPLATE),
                 root_ids: dioxus::core::exports::bumpalo::collections::Vec::with_capacity_in(#root_count, __cx.bump()).into(),
@@ -259,6 +286,16 @@ impl<'a> ToTokens for TemplateRenderer<'a> {
     }
 }
 
+fn packed_path_tokens(path: &PackedPath) -> TokenStream2 {
+    match path.unpacked_index() {
+        Some(index) => quote!(::dioxus::core::PackedPath::unpacked(#index)),
+        None => {
+            let steps = path.iter();
+            quote!(::dioxus::core::PackedPath::new(&[#(#steps),*]))
+        }
+    }
+}
+
 #[cfg(feature = "hot_reload")]
 #[derive(Default, Debug)]
 struct DynamicMapping {
@@ -288,6 +325,11 @@ impl DynamicMapping {
         self.node_to_idx.get_mut(node).and_then(|idxs| idxs.pop())
     }
 
+    fn is_exhausted(&self) -> bool {
+        self.attribute_to_idx.values().all(Vec::is_empty)
+            && self.node_to_idx.values().all(Vec::is_empty)
+    }
+
     fn insert_attribute(&mut self, attr: ElementAttr) -> usize {
         let idx = self.last_attribute_idx;
         self.last_attribute_idx += 1;
@@ -311,15 +353,16 @@ impl DynamicMapping {
             BodyNode::Element(el) => {
                 for attr in el.attributes {
                     match &attr.attr {
-                        ElementAttr::CustomAttrText { value, .. }
-                        | ElementAttr::AttrText { value, .. }
-                            if value.is_static() => {}
+                        ElementAttr::AttrText { value, .. } if value.is_static() => {}
+                        ElementAttr::CustomAttrText { name, value }
+                            if value.is_static() && custom_property(name).is_none() => {}
 
                         ElementAttr::AttrExpression { .. }
                         | ElementAttr::AttrText { .. }
                         | ElementAttr::CustomAttrText { .. }
                         | ElementAttr::CustomAttrExpression { .. }
-                        | ElementAttr::EventTokens { .. } => {
+                        | ElementAttr::EventTokens { .. }
+                        | ElementAttr::Spread { .. } => {
                             self.insert_attribute(attr.attr);
                         }
                     }
@@ -336,6 +379,7 @@ impl DynamicMapping {
             | BodyNode::Text(_)
             | BodyNode::ForLoop(_)
             | BodyNode::IfChain(_)
+            | BodyNode::Match(_)
             | BodyNode::Component(_) => {
                 self.insert_node(node);
             }
@@ -382,7 +426,10 @@ impl<'a> DynamicContext<'a> {
                             })
                         }
 
-                        ElementAttr::CustomAttrText { name, value } if value.is_static() => {
+                        // Properties aren't copied when a template is cloned, so they are always set as dynamic attributes
+                        ElementAttr::CustomAttrText { name, value }
+                            if value.is_static() && custom_property(name).is_none() =>
+                        {
                             let value = value.source.as_ref().unwrap();
                             static_attrs.push(TemplateAttribute::Static {
                                 name: intern(name.value().as_str()),
@@ -395,7 +442,8 @@ impl<'a> DynamicContext<'a> {
                         | ElementAttr::AttrText { .. }
                         | ElementAttr::CustomAttrText { .. }
                         | ElementAttr::CustomAttrExpression { .. }
-                        | ElementAttr::EventTokens { .. } => {
+                        | ElementAttr::EventTokens { .. }
+                        | ElementAttr::Spread { .. } => {
                             let idx = match mapping {
                                 Some(mapping) => mapping.get_attribute_idx(&attr.attr)?,
                                 None => self.dynamic_attributes.len(),
@@ -439,6 +487,7 @@ impl<'a> DynamicContext<'a> {
             | BodyNode::Text(_)
             | BodyNode::ForLoop(_)
             | BodyNode::IfChain(_)
+            | BodyNode::Match(_)
             | BodyNode::Component(_) => {
                 let idx = match mapping {
                     Some(mapping) => mapping.get_node_idx(root)?,
@@ -490,7 +539,9 @@ impl<'a> DynamicContext<'a> {
                         }
                     }
 
-                    ElementAttr::CustomAttrText { name, value } if value.is_static() => {
+                    ElementAttr::CustomAttrText { name, value }
+                        if value.is_static() && custom_property(name).is_none() =>
+                    {
                         let value = value.to_static().unwrap();
                         quote! {
                             ::dioxus::core::TemplateAttribute::Static {
@@ -508,7 +559,8 @@ impl<'a> DynamicContext<'a> {
                     | ElementAttr::AttrText { .. }
                     | ElementAttr::CustomAttrText { .. }
                     | ElementAttr::CustomAttrExpression { .. }
-                    | ElementAttr::EventTokens { .. } => {
+                    | ElementAttr::EventTokens { .. }
+                    | ElementAttr::Spread { .. } => {
                         let ct = self.dynamic_attributes.len();
                         self.dynamic_attributes.push(attr);
                         self.attr_paths.push(self.current_path.clone());
@@ -550,6 +602,7 @@ impl<'a> DynamicContext<'a> {
             | BodyNode::Text(_)
             | BodyNode::ForLoop(_)
             | BodyNode::IfChain(_)
+            | BodyNode::Match(_)
             | BodyNode::Component(_) => {
                 let ct = self.dynamic_nodes.len();
                 self.dynamic_nodes.push(root);
@@ -646,8 +699,10 @@ fn create_template() {
                     TemplateNode::Dynamic { id: 0 }
                 ],
             }],
-            node_paths: &[&[0, 1,],],
-            attr_paths: &[&[0,], &[0,],],
+            node_paths: &[PackedPath::new(&[0, 1])],
+            attr_paths: &[PackedPath::new(&[0]), PackedPath::new(&[0])],
+            unpacked_paths: &[],
+            style: None,
         },
     )
 }
@@ -768,8 +823,15 @@ fn diff_template() {
                     },
                 ],
             }],
-            node_paths: &[&[0, 3], &[0, 2], &[0, 1], &[0, 0]],
-            attr_paths: &[&[0], &[0]]
+            node_paths: &[
+                PackedPath::new(&[0, 3]),
+                PackedPath::new(&[0, 2]),
+                PackedPath::new(&[0, 1]),
+                PackedPath::new(&[0, 0])
+            ],
+            attr_paths: &[PackedPath::new(&[0]), PackedPath::new(&[0])],
+            unpacked_paths: &[],
+            style: None,
         },
     )
 }
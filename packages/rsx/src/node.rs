@@ -1,12 +1,12 @@
 use super::*;
 
 use proc_macro2::{Span, TokenStream as TokenStream2};
-use quote::{quote, ToTokens, TokenStreamExt};
+use quote::{quote, quote_spanned, ToTokens, TokenStreamExt};
 use syn::{
     braced,
     parse::{Parse, ParseStream},
     spanned::Spanned,
-    token, Expr, ExprIf, LitStr, Pat, Result,
+    token, Error, Expr, LitStr, Pat, Result, Token,
 };
 
 /*
@@ -22,7 +22,8 @@ pub enum BodyNode {
     Element(Element),
     Component(Component),
     ForLoop(ForLoop),
-    IfChain(ExprIf),
+    IfChain(IfChain),
+    Match(Match),
     Text(IfmtInput),
     RawExpr(Expr),
 }
@@ -40,6 +41,16 @@ impl BodyNode {
             BodyNode::RawExpr(exp) => exp.span(),
             BodyNode::ForLoop(fl) => fl.for_token.span(),
             BodyNode::IfChain(f) => f.if_token.span(),
+            BodyNode::Match(m) => m.match_token.span(),
+        }
+    }
+
+    /// The key of this node, if it is an element or component with one
+    pub fn key(&self) -> Option<&IfmtInput> {
+        match self {
+            BodyNode::Element(el) => el.key.as_ref(),
+            BodyNode::Component(comp) => comp.key(),
+            _ => None,
         }
     }
 }
@@ -112,7 +123,17 @@ impl Parse for BodyNode {
             return Ok(BodyNode::IfChain(stream.parse()?));
         }
 
-        Ok(BodyNode::RawExpr(stream.parse::<Expr>()?))
+        // Match expressions render one arm per template
+        if stream.peek(Token![match]) {
+            return Ok(BodyNode::Match(stream.parse()?));
+        }
+
+        let expr = stream.parse::<Expr>()?;
+        if let Some((_, roots)) = mapped_rsx(&expr) {
+            check_list_key(&roots)?;
+        }
+
+        Ok(BodyNode::RawExpr(expr))
     }
 }
 
@@ -124,17 +145,31 @@ impl ToTokens for BodyNode {
             BodyNode::Text(txt) => tokens.append_all(quote! {
                 __cx.text_node(#txt)
             }),
-            BodyNode::RawExpr(exp) => tokens.append_all(quote! {
-                {
-                    let ___nodes = (#exp).into_vnode(__cx);
-                    ___nodes
-                }
-            }),
+            BodyNode::RawExpr(exp) => {
+                let warning = match mapped_rsx(exp) {
+                    Some((span, roots)) => missing_key_warning(&roots, span),
+                    None => quote! {},
+                };
+
+                tokens.append_all(quote! {
+                    {
+                        #warning
+                        let ___nodes = (#exp).into_vnode(__cx);
+                        ___nodes
+                    }
+                })
+            }
             BodyNode::ForLoop(exp) => {
                 let ForLoop {
-                    pat, expr, body, ..
+                    for_token,
+                    pat,
+                    expr,
+                    body,
+                    ..
                 } = exp;
 
+                let warning = missing_key_warning(body, for_token.span);
+
                 let renderer: TemplateRenderer = TemplateRenderer {
                     roots: body,
                     location: None,
@@ -145,78 +180,14 @@ impl ToTokens for BodyNode {
                 // And then we can return them into the dyn loop
                 tokens.append_all(quote! {
                     {
+                        #warning
                         let ___nodes =(#expr).into_iter().map(|#pat| { #renderer }).into_vnode(__cx);
                         ___nodes
                     }
                 })
             }
-            BodyNode::IfChain(chain) => {
-                if is_if_chain_terminated(chain) {
-                    tokens.append_all(quote! {
-                        {
-                            let ___nodes = (#chain).into_vnode(__cx);
-                            ___nodes
-                        }
-                    });
-                } else {
-                    let ExprIf {
-                        cond,
-                        then_branch,
-                        else_branch,
-                        ..
-                    } = chain;
-
-                    let mut body = TokenStream2::new();
-
-                    body.append_all(quote! {
-                        if #cond {
-                            Some(#then_branch)
-                        }
-                    });
-
-                    let mut elif = else_branch;
-
-                    while let Some((_, ref branch)) = elif {
-                        match branch.as_ref() {
-                            Expr::If(ref eelif) => {
-                                let ExprIf {
-                                    cond,
-                                    then_branch,
-                                    else_branch,
-                                    ..
-                                } = eelif;
-
-                                body.append_all(quote! {
-                                    else if #cond {
-                                        Some(#then_branch)
-                                    }
-                                });
-
-                                elif = else_branch;
-                            }
-                            _ => {
-                                body.append_all(quote! {
-                                    else {
-                                        #branch
-                                    }
-                                });
-                                break;
-                            }
-                        }
-                    }
-
-                    body.append_all(quote! {
-                        else { None }
-                    });
-
-                    tokens.append_all(quote! {
-                        {
-                            let ___nodes = (#body).into_vnode(__cx);
-                            ___nodes
-                        }
-                    });
-                }
-            }
+            BodyNode::IfChain(chain) => chain.to_tokens(tokens),
+            BodyNode::Match(m) => m.to_tokens(tokens),
         }
     }
 }
@@ -249,6 +220,8 @@ impl Parse for ForLoop {
             children.push(content.parse()?);
         }
 
+        check_list_key(&children)?;
+
         Ok(Self {
             for_token,
             pat,
@@ -260,17 +233,298 @@ impl Parse for ForLoop {
     }
 }
 
-fn is_if_chain_terminated(chain: &ExprIf) -> bool {
-    let mut current = chain;
-    loop {
-        if let Some((_, else_block)) = &current.else_branch {
-            if let Expr::If(else_if) = else_block.as_ref() {
-                current = else_if;
+/// Find the rsx in an iterator like `items.iter().map(|item| rsx! { .. })`, along with the span of the closure
+fn mapped_rsx(expr: &Expr) -> Option<(Span, Vec<BodyNode>)> {
+    let call = match expr {
+        Expr::MethodCall(call) if call.method == "map" && call.args.len() == 1 => call,
+        _ => return None,
+    };
+    let closure = match &call.args[0] {
+        Expr::Closure(closure) => closure,
+        _ => return None,
+    };
+    let mac = match closure.body.as_ref() {
+        Expr::Macro(mac) if mac.mac.path.is_ident("rsx") || mac.mac.path.is_ident("render") => mac,
+        _ => return None,
+    };
+    let body = mac.mac.parse_body::<CallBody>().ok()?;
+
+    Some((closure.or1_token.span, body.roots))
+}
+
+/// Every item in a list is keyed by its first root, so a key without any formatted segments is the same for every item
+fn check_list_key(roots: &[BodyNode]) -> Result<()> {
+    match roots.first().and_then(BodyNode::key) {
+        Some(key) if key.is_static() => Err(Error::new(
+            key.source.as_ref().map(LitStr::span).unwrap_or_else(Span::call_site),
+            "duplicate key: this key is the same for every item in the list, include a value from the item like `key: \"{item.id}\"`",
+        )),
+        _ => Ok(()),
+    }
+}
+
+/// Components in a list without a key are diffed by their position, so removing or reordering items will pass the
+/// wrong props to the wrong components and keep their state in the wrong place.
+///
+/// Proc macros can't emit warnings directly, so this uses a deprecated constant at the span of the list instead.
+fn missing_key_warning(roots: &[BodyNode], span: Span) -> TokenStream2 {
+    let has_component = roots
+        .iter()
+        .any(|root| matches!(root, BodyNode::Component(_)));
+    if !has_component || roots.first().and_then(BodyNode::key).is_some() {
+        return quote! {};
+    }
+
+    let usage = quote_spanned! { span=> MISSING_KEY };
+    quote! {
+        #[deprecated(note = "components rendered in a list should have a key, like `key: \"{item.id}\"`, so the list is diffed correctly")]
+        const MISSING_KEY: () = ();
+        let _ = #usage;
+    }
+}
+
+/// An `if` chain whose branches are rsx bodies
+///
+/// Each branch is rendered as its own template, so switching branches replaces the subtree instead of diffing two
+/// unrelated structures against each other. An unterminated chain renders nothing when no branch matches.
+#[derive(PartialEq, Eq, Clone, Debug, Hash)]
+pub struct IfChain {
+    pub if_token: Token![if],
+    pub cond: Box<Expr>,
+    pub then_branch: Vec<BodyNode>,
+    pub else_if_branch: Option<Box<IfChain>>,
+    pub else_branch: Option<Vec<BodyNode>>,
+}
+
+impl Parse for IfChain {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let if_token: Token![if] = input.parse()?;
+
+        // stop parsing the condition when we hit the brace of the body
+        let cond = Box::new(input.call(Expr::parse_without_eager_brace)?);
+        let then_branch = parse_branch(input)?;
+
+        let mut else_if_branch = None;
+        let mut else_branch = None;
+        if input.peek(Token![else]) {
+            input.parse::<Token![else]>()?;
+            if input.peek(Token![if]) {
+                else_if_branch = Some(Box::new(input.parse::<IfChain>()?));
             } else {
-                return true;
+                else_branch = Some(parse_branch(input)?);
+            }
+        }
+
+        Ok(Self {
+            if_token,
+            cond,
+            then_branch,
+            else_if_branch,
+            else_branch,
+        })
+    }
+}
+
+fn parse_branch(input: ParseStream) -> Result<Vec<BodyNode>> {
+    let content;
+    braced!(content in input);
+
+    let mut branch = vec![];
+    while !content.is_empty() {
+        branch.push(content.parse()?);
+    }
+    Ok(branch)
+}
+
+impl ToTokens for IfChain {
+    fn to_tokens(&self, tokens: &mut TokenStream2) {
+        let mut body = TokenStream2::new();
+        let mut terminated = false;
+
+        let mut elif = Some(self);
+        while let Some(chain) = elif {
+            let IfChain {
+                if_token,
+                cond,
+                then_branch,
+                else_if_branch,
+                else_branch,
+            } = chain;
+
+            let then_branch = render_branch(then_branch);
+            body.append_all(quote! {
+                #if_token #cond {
+                    #then_branch
+                }
+            });
+
+            if let Some(else_branch) = else_branch {
+                let else_branch = render_branch(else_branch);
+                body.append_all(quote! {
+                    else {
+                        #else_branch
+                    }
+                });
+                terminated = true;
             }
+
+            elif = else_if_branch.as_deref();
+            if elif.is_some() {
+                body.append_all(quote! { else });
+            }
+        }
+
+        if !terminated {
+            body.append_all(quote! {
+                else { ::dioxus::core::DynamicNode::default() }
+            });
+        }
+
+        tokens.append_all(quote! {
+            {
+                let ___nodes = #body;
+                ___nodes
+            }
+        });
+    }
+}
+
+fn is_diverging(expr: &Expr) -> bool {
+    match expr {
+        Expr::Macro(expr) => match expr.mac.path.segments.last() {
+            Some(segment) => matches!(
+                segment.ident.to_string().as_str(),
+                "panic" | "todo" | "unimplemented" | "unreachable"
+            ),
+            None => false,
+        },
+        _ => false,
+    }
+}
+
+fn render_branch(roots: &[BodyNode]) -> TokenStream2 {
+    match roots {
+        // An empty branch has no template to render
+        [] => quote! { ::dioxus::core::DynamicNode::default() },
+        // A lone expression, like a nested `rsx!` call, is already a node of its own
+        // Panicking macros like `unreachable!()` never produce a node, so they are left to coerce to one
+        [BodyNode::RawExpr(expr)] if is_diverging(expr) => quote! { #expr },
+        [BodyNode::RawExpr(expr)] => quote! { (#expr).into_vnode(__cx) },
+        roots => {
+            let renderer = TemplateRenderer {
+                roots,
+                location: None,
+            };
+            quote! { ({ #renderer }).into_vnode(__cx) }
+        }
+    }
+}
+
+/// A `match` expression whose arms are rsx bodies
+///
+/// Like the branches of an [`IfChain`], each arm is rendered as its own template. An arm is either a braced body,
+/// like `State::Done => { h1 { "Done" } p { "All set" } }`, or a single node followed by a comma.
+#[derive(PartialEq, Eq, Clone, Debug, Hash)]
+pub struct Match {
+    pub match_token: Token![match],
+    pub expr: Box<Expr>,
+    pub arms: Vec<MatchArm>,
+}
+
+#[derive(PartialEq, Eq, Clone, Debug, Hash)]
+pub struct MatchArm {
+    pub pat: Pat,
+    pub guard: Option<Box<Expr>>,
+    pub body: Vec<BodyNode>,
+    pub brace_token: Option<token::Brace>,
+}
+
+impl Parse for Match {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let match_token: Token![match] = input.parse()?;
+        let expr = Box::new(input.call(Expr::parse_without_eager_brace)?);
+
+        let content;
+        braced!(content in input);
+
+        let mut arms = vec![];
+        while !content.is_empty() {
+            arms.push(content.parse()?);
+        }
+
+        Ok(Self {
+            match_token,
+            expr,
+            arms,
+        })
+    }
+}
+
+impl Parse for MatchArm {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let pat = Pat::parse_multi_with_leading_vert(input)?;
+
+        let guard = if input.peek(Token![if]) {
+            input.parse::<Token![if]>()?;
+            Some(Box::new(input.parse()?))
+        } else {
+            None
+        };
+
+        input.parse::<Token![=>]>()?;
+
+        let (body, brace_token) = if input.peek(token::Brace) {
+            let content;
+            let brace_token = braced!(content in input);
+            let mut body = vec![];
+            while !content.is_empty() {
+                body.push(content.parse()?);
+            }
+            (body, Some(brace_token))
         } else {
-            return false;
+            (vec![input.parse()?], None)
+        };
+
+        // The comma is only required after an arm without braces, but allowed after either
+        if input.peek(Token![,]) {
+            input.parse::<Token![,]>()?;
+        } else if brace_token.is_none() && !input.is_empty() {
+            return Err(input.error("expected `,` after the match arm"));
         }
+
+        Ok(Self {
+            pat,
+            guard,
+            body,
+            brace_token,
+        })
+    }
+}
+
+impl ToTokens for Match {
+    fn to_tokens(&self, tokens: &mut TokenStream2) {
+        let Match {
+            match_token, expr, ..
+        } = self;
+
+        let arms = self.arms.iter().map(|arm| {
+            let MatchArm {
+                pat, guard, body, ..
+            } = arm;
+            let guard = guard.as_ref().map(|guard| quote! { if #guard });
+            let body = render_branch(body);
+            quote! {
+                #pat #guard => { #body }
+            }
+        });
+
+        tokens.append_all(quote! {
+            {
+                let ___nodes = #match_token #expr {
+                    #( #arms )*
+                };
+                ___nodes
+            }
+        });
     }
 }
@@ -0,0 +1,61 @@
+#![cfg(feature = "hot_reload")]
+//! Changes to an rsx call that only touch literals and structure can be applied as a new template
+
+use dioxus_core::{TemplateAttribute, TemplateNode};
+use dioxus_rsx::{hot_reload::Empty, CallBody};
+
+fn update(old: &str, new: &str) -> Option<dioxus_core::Template<'static>> {
+    let old = syn::parse_str::<CallBody>(old).unwrap();
+    let new = syn::parse_str::<CallBody>(new).unwrap();
+    new.update_template::<Empty>(Some(old), "src/main.rs:1:1:0")
+}
+
+#[test]
+fn literals_can_change() {
+    let template = update(
+        r#"div { class: "old", "{count}", "old" }"#,
+        r#"div { class: "new", "{count}", "new" }"#,
+    )
+    .unwrap();
+
+    assert_eq!(
+        template.roots,
+        &[TemplateNode::Element {
+            tag: "div",
+            namespace: None,
+            attrs: &[TemplateAttribute::Static {
+                name: "class",
+                namespace: None,
+                value: "new",
+            }],
+            children: &[
+                TemplateNode::DynamicText { id: 0 },
+                TemplateNode::Text { text: "new" },
+            ],
+        }]
+    );
+}
+
+#[test]
+fn structure_can_change() {
+    let template = update(
+        r#"div { onclick: move |_| {}, "{count}" }"#,
+        r#"div { section { onclick: move |_| {}, "{count}" } }"#,
+    )
+    .unwrap();
+
+    assert_eq!(template.node_path(0).unwrap().to_vec(), [0, 0, 0]);
+    assert_eq!(template.attr_path(0).unwrap().to_vec(), [0, 0]);
+}
+
+#[test]
+fn new_expressions_need_a_rebuild() {
+    assert!(update(r#"div { "{count}" }"#, r#"div { "{count}", "{other}" }"#).is_none());
+    assert!(update(r#"div { }"#, r#"div { class: "{class}" }"#).is_none());
+}
+
+#[test]
+fn removed_expressions_need_a_rebuild() {
+    assert!(update(r#"div { "{count}", "{other}" }"#, r#"div { "{count}" }"#).is_none());
+    assert!(update(r#"div { class: "{class}" }"#, r#"div { }"#).is_none());
+}
@@ -88,6 +88,7 @@ fn ReadsSecond(cx: Scope) -> Element {
     }
 }
 
+#[allow(deprecated)]
 fn ReadsManySignals(cx: Scope) -> Element {
     println!("running many signals");
     let data = use_app_data(cx);
@@ -49,15 +49,19 @@ pub fn use_signal<T: 'static>(cx: &ScopeState, f: impl FnOnce() -> T) -> Signal<
     *cx.use_hook(|| Signal::new(f()))
 }
 
+/// Removes a scope from every signal it subscribed to once the scope is dropped.
+///
+/// This is stored as a context of the scope, so it is cloned every time it is looked up. The scope is only
+/// unsubscribed when the last clone, owned by the scope, is dropped.
 #[derive(Clone)]
-struct Unsubscriber {
+struct Unsubscriber(Rc<UnsubscriberInner>);
+
+struct UnsubscriberInner {
     scope: ScopeId,
-    subscribers: UnsubscriberArray,
+    subscribers: RefCell<Vec<Rc<RefCell<Vec<ScopeId>>>>>,
 }
 
-type UnsubscriberArray = Rc<RefCell<Vec<Rc<RefCell<Vec<ScopeId>>>>>>;
-
-impl Drop for Unsubscriber {
+impl Drop for UnsubscriberInner {
     fn drop(&mut self) {
         for subscribers in self.subscribers.borrow().iter() {
             subscribers.borrow_mut().retain(|s| *s != self.scope);
@@ -69,10 +73,10 @@ fn current_unsubscriber() -> Unsubscriber {
     match has_context() {
         Some(rt) => rt,
         None => {
-            let owner = Unsubscriber {
+            let owner = Unsubscriber(Rc::new(UnsubscriberInner {
                 scope: current_scope_id().expect("in a virtual dom"),
                 subscribers: Default::default(),
-            };
+            }));
             provide_context(owner).expect("in a virtual dom")
         }
     }
@@ -193,7 +197,11 @@ impl<T: 'static> Signal<T> {
                     subscribers.push(current_scope_id);
                     drop(subscribers);
                     let unsubscriber = current_unsubscriber();
-                    inner.subscribers.borrow_mut().push(unsubscriber.scope);
+                    unsubscriber
+                        .0
+                        .subscribers
+                        .borrow_mut()
+                        .push(inner.subscribers.clone());
                 }
             }
         }
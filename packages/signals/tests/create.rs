@@ -5,6 +5,7 @@ use dioxus_core::ElementId;
 use dioxus_signals::*;
 
 #[test]
+#[allow(deprecated)]
 fn create_signals_global() {
     let mut dom = VirtualDom::new(|cx| {
         render! {
@@ -30,6 +31,7 @@ fn create_signals_global() {
 }
 
 #[test]
+#[allow(deprecated)]
 fn deref_signal() {
     let mut dom = VirtualDom::new(|cx| {
         render! {
@@ -54,6 +56,7 @@ fn deref_signal() {
 }
 
 #[test]
+#[allow(deprecated)]
 fn drop_signals() {
     let mut dom = VirtualDom::new(|cx| {
         let generation = cx.generation();
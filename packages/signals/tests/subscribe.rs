@@ -7,6 +7,7 @@ use dioxus_core::ElementId;
 use dioxus_signals::*;
 
 #[test]
+#[allow(deprecated)]
 fn reading_subscribes() {
     simple_logger::SimpleLogger::new().init().unwrap();
 
@@ -90,3 +91,67 @@ fn reading_subscribes() {
         }
     }
 }
+
+#[test]
+fn dropped_scopes_unsubscribe() {
+    #[derive(Default)]
+    struct State {
+        signal: Option<Signal<usize>>,
+        other_renders: usize,
+    }
+
+    let state = Rc::new(RefCell::new(State::default()));
+    let mut dom = VirtualDom::new_with_props(
+        |cx| {
+            let signal = use_signal(cx, || 0);
+            cx.props.borrow_mut().signal = Some(signal);
+
+            match cx.generation() {
+                0 => render! { Reader { signal: signal } },
+                1 => render! { "nothing" },
+                _ => render! { Other { state: cx.props.clone() } },
+            }
+        },
+        state.clone(),
+    );
+
+    #[derive(Props, Clone, PartialEq)]
+    struct ReaderProps {
+        signal: Signal<usize>,
+    }
+
+    fn Reader(cx: Scope<ReaderProps>) -> Element {
+        render! { "{cx.props.signal}" }
+    }
+
+    #[derive(Props, Clone)]
+    struct OtherProps {
+        state: Rc<RefCell<State>>,
+    }
+
+    impl PartialEq for OtherProps {
+        fn eq(&self, other: &Self) -> bool {
+            Rc::ptr_eq(&self.state, &other.state)
+        }
+    }
+
+    fn Other(cx: Scope<OtherProps>) -> Element {
+        cx.props.state.borrow_mut().other_renders += 1;
+        render! { "other" }
+    }
+
+    let _ = dom.rebuild();
+
+    // The reader is removed, and the next component reuses its ScopeId
+    dom.mark_dirty(ScopeId::ROOT);
+    let _ = dom.render_immediate();
+    dom.mark_dirty(ScopeId::ROOT);
+    let _ = dom.render_immediate();
+    assert_eq!(state.borrow().other_renders, 1);
+
+    // Nothing that is mounted reads the signal, so writing to it doesn't render anything
+    let signal = state.borrow().signal.unwrap();
+    signal.set(1);
+    let _ = dom.render_immediate();
+    assert_eq!(state.borrow().other_renders, 1);
+}
@@ -88,6 +88,8 @@ impl StringCache {
                                 inner_html = Some(value);
                             } else if let Some("style") = namespace {
                                 styles.push((name, value));
+                            } else if let Some("prop") = namespace {
+                                // Properties are set on the element object, not written to the html
                             } else if BOOL_ATTRS.contains(name) {
                                 if str_truthy(value) {
                                     write!(chain, " {name}=\"{value}\"",)?;
@@ -100,6 +102,8 @@ impl StringCache {
                             chain.segments.push(Segment::Attr(*index));
                             has_dynamic_attrs = true;
                         }
+                        // Resolved by the VirtualDom when the template is mounted
+                        TemplateAttribute::Conditional { .. } => {}
                     }
                 }
 
@@ -10,7 +10,9 @@ pub mod incremental;
 #[cfg(feature = "incremental")]
 mod incremental_cfg;
 
+mod pretty;
 pub mod renderer;
+mod streaming;
 pub mod template;
 
 use dioxus_core::{Element, LazyNodes, Scope, VirtualDom};
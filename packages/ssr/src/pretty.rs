@@ -0,0 +1,174 @@
+//! Reformat rendered html so every element starts on its own line
+
+use std::fmt::Write;
+
+/// Elements that never have children or a closing tag
+const VOID_ELEMENTS: &[&str] = &[
+    "area", "base", "br", "col", "embed", "hr", "img", "input", "link", "meta", "source", "track",
+    "wbr",
+];
+
+/// Elements whose contents are written as they are, because whitespace is significant in them
+const RAW_ELEMENTS: &[&str] = &["pre", "textarea", "script", "style"];
+
+#[derive(Debug, PartialEq)]
+enum Token<'a> {
+    Open(&'a str),
+    Close(&'a str),
+    Void(&'a str),
+    // Text and comments stay on the line they are on
+    Inline(&'a str),
+}
+
+/// Write the html with every element on its own line, nested elements indented by `indent`
+///
+/// Elements that only contain text stay on one line, and the contents of elements like `pre` are left as they are.
+pub(crate) fn write_pretty(buf: &mut impl Write, html: &str, indent: &str) -> std::fmt::Result {
+    let tokens = tokenize(html);
+    let mut depth = 0;
+    let mut first = true;
+    let mut line = |buf: &mut dyn Write, depth: usize, tokens: &[Token]| -> std::fmt::Result {
+        if !first {
+            buf.write_char('\n')?;
+        }
+        first = false;
+        for _ in 0..depth {
+            buf.write_str(indent)?;
+        }
+        for token in tokens {
+            match token {
+                Token::Open(raw) | Token::Close(raw) | Token::Void(raw) | Token::Inline(raw) => {
+                    buf.write_str(raw)?
+                }
+            }
+        }
+        Ok(())
+    };
+
+    let mut i = 0;
+    while i < tokens.len() {
+        // The first token that doesn't belong on this line
+        let mut end = i + 1;
+        match tokens[i] {
+            Token::Open(_) => {
+                while let Some(Token::Inline(_)) = tokens.get(end) {
+                    end += 1;
+                }
+                match tokens.get(end) {
+                    // An element that only holds text
+                    Some(Token::Close(_)) => {
+                        end += 1;
+                        line(buf, depth, &tokens[i..end])?;
+                    }
+                    _ => {
+                        end = i + 1;
+                        line(buf, depth, &tokens[i..end])?;
+                        depth += 1;
+                    }
+                }
+            }
+            Token::Close(_) => {
+                depth = depth.saturating_sub(1);
+                line(buf, depth, &tokens[i..end])?;
+            }
+            Token::Void(_) => line(buf, depth, &tokens[i..end])?,
+            Token::Inline(_) => {
+                while let Some(Token::Inline(_)) = tokens.get(end) {
+                    end += 1;
+                }
+                line(buf, depth, &tokens[i..end])?;
+            }
+        }
+        i = end;
+    }
+
+    Ok(())
+}
+
+fn tokenize(html: &str) -> Vec<Token<'_>> {
+    let mut tokens = Vec::new();
+    let mut rest = html;
+
+    while !rest.is_empty() {
+        if rest.starts_with("<!--") {
+            let end = rest.find("-->").map(|end| end + 3).unwrap_or(rest.len());
+            tokens.push(Token::Inline(&rest[..end]));
+            rest = &rest[end..];
+        } else if rest.starts_with("</") {
+            let end = tag_end(rest);
+            tokens.push(Token::Close(&rest[..end]));
+            rest = &rest[end..];
+        } else if rest.starts_with('<') && rest[1..].starts_with(|c: char| c.is_ascii_alphabetic())
+        {
+            let end = tag_end(rest);
+            let raw = &rest[..end];
+            rest = &rest[end..];
+
+            let name = raw[1..]
+                .split(|c: char| c.is_whitespace() || c == '>' || c == '/')
+                .next()
+                .unwrap_or_default();
+            if VOID_ELEMENTS.contains(&name) || raw.ends_with("/>") {
+                tokens.push(Token::Void(raw));
+                continue;
+            }
+            tokens.push(Token::Open(raw));
+
+            // Keep everything up to the closing tag as it is
+            if RAW_ELEMENTS.contains(&name) {
+                let close = format!("</{}", name);
+                let end = rest.find(&close).unwrap_or(rest.len());
+                if end > 0 {
+                    tokens.push(Token::Inline(&rest[..end]));
+                }
+                rest = &rest[end..];
+            }
+        } else {
+            let end = rest[1..].find('<').map(|end| end + 1).unwrap_or(rest.len());
+            let text = &rest[..end];
+            if !text.trim().is_empty() {
+                tokens.push(Token::Inline(text));
+            }
+            rest = &rest[end..];
+        }
+    }
+
+    tokens
+}
+
+/// Find the end of the tag at the start of the html, skipping over quoted attribute values
+fn tag_end(html: &str) -> usize {
+    let mut quote = None;
+    for (idx, c) in html.char_indices() {
+        match (quote, c) {
+            (None, '"' | '\'') => quote = Some(c),
+            (Some(open), _) if open == c => quote = None,
+            (None, '>') => return idx + 1,
+            _ => {}
+        }
+    }
+    html.len()
+}
+
+#[test]
+fn pretty_html() {
+    let html = "<div class=\"a>b\"><h1>title</h1><!--#-->text<!--#--><br/><ul><li>one</li><li><b>two</b></li></ul><pre>  keep\n  this</pre></div>";
+    let mut out = String::new();
+    write_pretty(&mut out, html, "  ").unwrap();
+    assert_eq!(
+        out,
+        "<div class=\"a>b\">
+  <h1>title</h1>
+  <!--#-->text<!--#-->
+  <br/>
+  <ul>
+    <li>one</li>
+    <li>
+      <b>two</b>
+    </li>
+  </ul>
+  <pre>  keep
+  this</pre>
+</div>"
+    );
+}
@@ -1,25 +1,46 @@
 use super::cache::Segment;
 use crate::cache::StringCache;
 
+use dioxus_core::HEAD_ATTRIBUTE;
 use dioxus_core::Attribute;
-use dioxus_core::{prelude::*, AttributeValue, DynamicNode, RenderReturn};
+use dioxus_core::{prelude::*, AttributeValue, DynamicNode, RenderReturn, SELECTION_ATTRIBUTES};
 use std::collections::HashMap;
 use std::fmt::Write;
 use std::sync::Arc;
 
 /// A virtualdom renderer that caches the templates it has seen for faster rendering
-#[derive(Default)]
+///
+/// By default, the html is minified, without any whitespace between elements. Set [`Renderer::pretty`] to get
+/// indented html that is easier to read and diff:
+///
+/// ```rust, ignore
+/// let mut renderer = Renderer::new();
+/// renderer.pretty = true;
+/// renderer.indent = "\t".to_string();
+/// std::fs::write("index.html", renderer.render(&dom))?;
+/// ```
+///
+/// Pages with suspense boundaries can be streamed with [`Renderer::render_streaming`], which sends everything that is
+/// ready first and then each boundary as it resolves.
 pub struct Renderer {
-    /// should we do our best to prettify the output?
+    /// Write every element on its own line, with its children indented by [`Renderer::indent`]
+    ///
+    /// Elements that only contain text stay on one line, and the contents of `pre`, `textarea`, `script` and `style`
+    /// elements are written as they are. Whitespace between elements is dropped.
     pub pretty: bool,
 
-    /// Control if elements are written onto a new line
+    /// Write every element on its own line, like [`Renderer::pretty`] but without indentation
     pub newline: bool,
 
+    /// The indentation of each level of nesting when [`Renderer::pretty`] is set. Two spaces by default.
+    pub indent: String,
+
     /// Should we sanitize text nodes? (escape HTML)
     pub sanitize: bool,
 
-    /// Choose to write ElementIDs into elements so the page can be re-hydrated later on
+    /// Write markers around dynamic text and in place of empty nodes, so the page can be hydrated later on
+    ///
+    /// The markers let the web renderer find every dynamic node in the html and claim it instead of creating it again.
     pub pre_render: bool,
 
     // Currently not implemented
@@ -28,6 +49,24 @@ pub struct Renderer {
 
     /// A cache of templates that have been rendered
     template_cache: HashMap<&'static str, Arc<StringCache>>,
+
+    /// The suspense boundaries that were streamed with their fallback and still need to be sent, while streaming
+    pub(crate) pending_boundaries: Option<Vec<ScopeId>>,
+}
+
+impl Default for Renderer {
+    fn default() -> Self {
+        Self {
+            pretty: false,
+            newline: false,
+            indent: "  ".to_string(),
+            sanitize: false,
+            pre_render: false,
+            skip_components: false,
+            template_cache: Default::default(),
+            pending_boundaries: None,
+        }
+    }
 }
 
 impl Renderer {
@@ -45,11 +84,69 @@ impl Renderer {
         self.render_scope(buf, dom, ScopeId::ROOT)
     }
 
+    /// Render the elements components hoisted into the head of the document, like the title of the page
+    ///
+    /// The output goes inside the `<head>` of the page, next to the scripts and styles the page always has.
+    pub fn render_head(&self, dom: &VirtualDom) -> String {
+        let mut buf = String::new();
+        self.render_head_to(&mut buf, dom).unwrap();
+        buf
+    }
+
+    /// Render the elements components hoisted into the head of the document into a buffer
+    pub fn render_head_to(&self, buf: &mut impl Write, dom: &VirtualDom) -> std::fmt::Result {
+        for element in dom.head() {
+            write!(buf, "<{}", element.tag)?;
+            // The title is set on the document when the page is hydrated, so it isn't marked
+            if element.tag != "title" {
+                write!(buf, " {}", HEAD_ATTRIBUTE)?;
+            }
+            for (name, value) in &element.attributes {
+                write!(
+                    buf,
+                    " {}=\"{}\"",
+                    name,
+                    askama_escape::escape(value, askama_escape::Html)
+                )?;
+            }
+            match &element.text {
+                Some(text) => write!(
+                    buf,
+                    ">{}</{}>",
+                    askama_escape::escape(text, askama_escape::Html),
+                    element.tag
+                )?,
+                None => write!(buf, "/>")?,
+            }
+        }
+        Ok(())
+    }
+
     pub fn render_scope(
         &mut self,
         buf: &mut impl Write,
         dom: &VirtualDom,
         scope: ScopeId,
+    ) -> std::fmt::Result {
+        if self.pretty || self.newline {
+            let mut html = String::new();
+            self.render_scope_minified(&mut html, dom, scope)?;
+            let indent = if self.pretty {
+                self.indent.as_str()
+            } else {
+                ""
+            };
+            return crate::pretty::write_pretty(buf, &html, indent);
+        }
+
+        self.render_scope_minified(buf, dom, scope)
+    }
+
+    fn render_scope_minified(
+        &mut self,
+        buf: &mut impl Write,
+        dom: &VirtualDom,
+        scope: ScopeId,
     ) -> std::fmt::Result {
         // We should never ever run into async or errored nodes in SSR
         // Error boundaries and suspense boundaries will convert these to sync
@@ -83,6 +180,10 @@ impl Renderer {
                     let attr = &template.dynamic_attrs[*idx];
                     if attr.name == "dangerous_inner_html" {
                         inner_html = Some(attr);
+                    } else if SELECTION_ATTRIBUTES.contains(&attr.name) {
+                        // The selection only exists once the page is interactive
+                    } else if attr.namespace == Some("prop") {
+                        // Properties are set on the element object, which only exists once the page is interactive
                     } else if attr.namespace == Some("style") {
                         accumulated_dynamic_styles.push(attr);
                     } else if BOOL_ATTRS.contains(&attr.name) {
@@ -101,14 +202,32 @@ impl Renderer {
                         } else {
                             let id = node.mounted_scope().unwrap();
                             let scope = dom.get_scope(id).unwrap();
+
+                            // A boundary showing its fallback is wrapped so its contents can be swapped in later
+                            let streamed = self.pending_boundaries.is_some()
+                                && crate::streaming::is_suspended_boundary(scope);
+                            if streamed {
+                                write!(buf, "<div id=\"ds-{}\" style=\"display:contents\">", id.0)?;
+                            }
+
                             let node = scope.root_node();
                             match node {
                                 RenderReturn::Ready(node) => {
                                     self.render_template(buf, dom, node)?
                                 }
-                                _ => todo!(
-                                    "generally, scopes should be sync, only if being traversed"
-                                ),
+                                // A suspended component holds a placeholder until it renders again
+                                RenderReturn::Aborted(_) => {
+                                    if self.pre_render {
+                                        write!(buf, "<pre></pre>")?;
+                                    }
+                                }
+                            }
+
+                            if streamed {
+                                write!(buf, "</div>")?;
+                                if let Some(pending) = &mut self.pending_boundaries {
+                                    pending.push(id);
+                                }
                             }
                         }
                     }
@@ -171,6 +290,7 @@ impl Renderer {
                             AttributeValue::Bool(value) => write!(buf, "{}", value)?,
                             AttributeValue::Float(f) => write!(buf, "{}", f)?,
                             AttributeValue::Int(i) => write!(buf, "{}", i)?,
+                            AttributeValue::Template(value) => write!(buf, "{}", value)?,
                             _ => {}
                         }
                     }
@@ -278,6 +398,7 @@ pub(crate) fn truthy(value: &AttributeValue) -> bool {
         AttributeValue::Bool(value) => *value,
         AttributeValue::Int(value) => *value != 0,
         AttributeValue::Float(value) => *value != 0.0,
+        AttributeValue::Template(value) => str_truthy(value.resolved()),
         _ => false,
     }
 }
@@ -289,6 +410,22 @@ pub(crate) fn write_attribute(buf: &mut impl Write, attr: &Attribute) -> std::fm
         AttributeValue::Bool(value) => write!(buf, " {name}={value}"),
         AttributeValue::Int(value) => write!(buf, " {name}={value}"),
         AttributeValue::Float(value) => write!(buf, " {name}={value}"),
+        AttributeValue::Template(value) => write!(buf, " {name}=\"{value}\""),
+        AttributeValue::StyleMap(properties) => {
+            write!(buf, " {name}=\"")?;
+            for (property, value) in properties {
+                write!(buf, "{property}:{value};")?;
+            }
+            write!(buf, "\"")
+        }
+        AttributeValue::Spread(attributes) => {
+            for (name, value) in attributes {
+                if !BOOL_ATTRS.contains(name) || str_truthy(value) {
+                    write!(buf, " {name}=\"{value}\"")?;
+                }
+            }
+            Ok(())
+        }
         _ => Ok(()),
     }
 }
@@ -299,6 +436,7 @@ pub(crate) fn write_value(buf: &mut impl Write, value: &AttributeValue) -> std::
         AttributeValue::Bool(value) => write!(buf, "{}", value),
         AttributeValue::Int(value) => write!(buf, "{}", value),
         AttributeValue::Float(value) => write!(buf, "{}", value),
+        AttributeValue::Template(value) => write!(buf, "\"{}\"", value),
         _ => Ok(()),
     }
 }
@@ -312,6 +450,7 @@ pub(crate) fn write_value_unquoted(
         AttributeValue::Bool(value) => write!(buf, "{}", value),
         AttributeValue::Int(value) => write!(buf, "{}", value),
         AttributeValue::Float(value) => write!(buf, "{}", value),
+        AttributeValue::Template(value) => write!(buf, "{}", value),
         _ => Ok(()),
     }
 }
@@ -0,0 +1,119 @@
+//! Stream the html of a page, sending suspense boundaries as they resolve
+
+use crate::Renderer;
+use dioxus_core::{ScopeId, ScopeState, SuspenseContext, VirtualDom};
+use std::fmt::Write;
+use std::rc::Rc;
+
+impl Renderer {
+    /// Render the VirtualDom into `out` without buffering the whole page
+    ///
+    /// Everything that is ready is written and flushed first, with every suspended [`dioxus_core::SuspenseBoundary`]
+    /// showing its fallback. Each boundary is then written as soon as it resolves, in a `template` with a small inline
+    /// script that swaps it in place of the fallback. This returns once every boundary has been sent.
+    ///
+    /// The VirtualDom should be built with [`VirtualDom::rebuild`] first, like for [`Renderer::render`].
+    ///
+    /// ```rust, ignore
+    /// let mut dom = VirtualDom::new(app);
+    /// _ = dom.rebuild();
+    /// Renderer::new().render_streaming(&mut dom, &mut std::io::stdout()).await?;
+    /// ```
+    pub async fn render_streaming(
+        &mut self,
+        dom: &mut VirtualDom,
+        out: &mut impl std::io::Write,
+    ) -> std::io::Result<()> {
+        out.write_all(self.render_shell(dom).as_bytes())?;
+        out.flush()?;
+
+        while let Some(chunk) = self.next_chunk(dom).await {
+            out.write_all(chunk.as_bytes())?;
+            out.flush()?;
+        }
+
+        Ok(())
+    }
+
+    /// Render the VirtualDom into an async writer without buffering the whole page, like
+    /// [`Renderer::render_streaming`]
+    ///
+    /// This can write straight into the body of a response.
+    #[cfg(feature = "streaming")]
+    pub async fn render_streaming_async(
+        &mut self,
+        dom: &mut VirtualDom,
+        out: &mut (impl tokio::io::AsyncWrite + Unpin),
+    ) -> std::io::Result<()> {
+        use tokio::io::AsyncWriteExt;
+
+        out.write_all(self.render_shell(dom).as_bytes()).await?;
+        out.flush().await?;
+
+        while let Some(chunk) = self.next_chunk(dom).await {
+            out.write_all(chunk.as_bytes()).await?;
+            out.flush().await?;
+        }
+
+        Ok(())
+    }
+
+    /// Render everything that is ready, and remember the boundaries that still need to be sent
+    fn render_shell(&mut self, dom: &mut VirtualDom) -> String {
+        // Boundaries only show their fallback once they render again
+        _ = dom.render_immediate();
+
+        self.pending_boundaries = Some(Vec::new());
+        let mut shell = String::new();
+        self.render_to(&mut shell, dom).unwrap();
+        shell
+    }
+
+    /// Wait for the next boundary to resolve and render it, or return None once every boundary has been sent
+    async fn next_chunk(&mut self, dom: &mut VirtualDom) -> Option<String> {
+        loop {
+            let pending = self.pending_boundaries.as_mut()?;
+            if pending.is_empty() {
+                self.pending_boundaries = None;
+                return None;
+            }
+
+            let resolved = pending.iter().position(|id| match dom.get_scope(*id) {
+                Some(scope) => !is_suspended_boundary(scope),
+                None => true,
+            });
+
+            match resolved {
+                Some(idx) => {
+                    let id = pending.remove(idx);
+                    // The boundary was removed while it was suspended, so there is nothing to send
+                    if dom.get_scope(id).is_some() {
+                        return Some(self.render_chunk(dom, id));
+                    }
+                }
+                None => {
+                    dom.wait_for_work().await;
+                    _ = dom.render_immediate();
+                }
+            }
+        }
+    }
+
+    fn render_chunk(&mut self, dom: &VirtualDom, id: ScopeId) -> String {
+        let mut chunk = String::new();
+        write!(chunk, "<template id=\"ds-{}-chunk\">", id.0).unwrap();
+        self.render_scope(&mut chunk, dom, id).unwrap();
+        write!(
+            chunk,
+            "</template><script>(function(){{var t=document.getElementById(\"ds-{0}-chunk\");document.getElementById(\"ds-{0}\").replaceChildren(t.content);t.remove()}})()</script>",
+            id.0
+        )
+        .unwrap();
+        chunk
+    }
+}
+
+/// Check if the scope is a suspense boundary that is showing its fallback
+pub(crate) fn is_suspended_boundary(scope: &ScopeState) -> bool {
+    matches!(scope.has_context::<Rc<SuspenseContext>>(), Some(boundary) if boundary.is_suspended())
+}
@@ -0,0 +1,36 @@
+use dioxus::prelude::*;
+
+#[test]
+fn custom_elements_pass_through() {
+    fn app(cx: Scope) -> Element {
+        let label = "Save";
+        render! {
+            my-button { "variant": "primary", "label": "{label}", "click me" }
+        }
+    }
+
+    let mut dom = VirtualDom::new(app);
+    _ = dom.rebuild();
+
+    assert_eq!(
+        dioxus_ssr::render(&dom),
+        r#"<my-button variant="primary" label="Save">click me</my-button>"#
+    );
+}
+
+#[test]
+fn properties_are_not_rendered() {
+    fn app(cx: Scope) -> Element {
+        render! {
+            my-list { "prop:items": "static", "prop:count": 3, "class": "list" }
+        }
+    }
+
+    let mut dom = VirtualDom::new(app);
+    _ = dom.rebuild();
+
+    assert_eq!(
+        dioxus_ssr::render(&dom),
+        r#"<my-list class="list"></my-list>"#
+    );
+}
@@ -0,0 +1,24 @@
+use dioxus::core::{Meta, Title};
+use dioxus::prelude::*;
+use dioxus_ssr::Renderer;
+
+fn app(cx: Scope) -> Element {
+    render! {
+        Title { "Fish & Chips" }
+        Meta { name: "description", content: "A \"good\" menu" }
+        main { "menu" }
+    }
+}
+
+#[test]
+fn head_is_rendered_separately() {
+    let mut dom = VirtualDom::new(app);
+    _ = dom.rebuild();
+
+    let renderer = Renderer::new();
+    assert_eq!(
+        renderer.render_head(&dom),
+        "<title>Fish &amp; Chips</title><meta data-dioxus-head name=\"description\" content=\"A &quot;good&quot; menu\"/>"
+    );
+    assert_eq!(dioxus_ssr::render(&dom), "<main>menu</main>");
+}
@@ -0,0 +1,42 @@
+use dioxus::prelude::*;
+use dioxus_ssr::Renderer;
+
+fn app(cx: Scope) -> Element {
+    render! {
+        main {
+            h1 { "Pricing" }
+            ul {
+                (0..2).map(|i| rsx! { li { "plan {i}" } })
+            }
+            img { src: "logo.png" }
+        }
+    }
+}
+
+#[test]
+fn pretty_output_is_indented() {
+    let mut dom = VirtualDom::new(app);
+    _ = dom.rebuild();
+
+    let mut renderer = Renderer::new();
+    assert_eq!(
+        renderer.render(&dom),
+        "<main><h1>Pricing</h1><ul><li>plan 0</li><li>plan 1</li></ul><img src=\"logo.png\"/></main>"
+    );
+
+    renderer.pretty = true;
+    assert_eq!(
+        renderer.render(&dom),
+        "<main>
+  <h1>Pricing</h1>
+  <ul>
+    <li>plan 0</li>
+    <li>plan 1</li>
+  </ul>
+  <img src=\"logo.png\"/>
+</main>"
+    );
+
+    renderer.indent = "\t".to_string();
+    assert_eq!(renderer.render(&dom).lines().nth(2), Some("\t<ul>"));
+}
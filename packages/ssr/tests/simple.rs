@@ -44,6 +44,7 @@ fn dynamic() {
 }
 
 #[test]
+#[allow(deprecated)]
 fn components() {
     #[component]
     fn MyComponent(cx: Scope, name: i32) -> Element {
@@ -0,0 +1,76 @@
+use dioxus::prelude::*;
+use dioxus_ssr::Renderer;
+use std::{cell::Cell, rc::Rc};
+
+fn app(cx: Scope) -> Element {
+    render! {
+        main {
+            h1 { "Profile" }
+            SuspenseBoundary {
+                fallback: render!("loading"),
+                user_profile {}
+            }
+        }
+    }
+}
+
+fn user_profile(cx: Scope) -> Element {
+    let ready = cx.use_hook(|| Rc::new(Cell::new(false))).clone();
+    cx.use_hook(|| {
+        let ready = ready.clone();
+        cx.spawn(async move {
+            tokio::task::yield_now().await;
+            ready.set(true);
+        })
+    });
+
+    if !ready.get() {
+        cx.suspend()?;
+    }
+
+    render! { p { "ferris" } }
+}
+
+#[test]
+fn boundaries_are_streamed_after_the_shell() {
+    tokio::runtime::Builder::new_current_thread()
+        .build()
+        .unwrap()
+        .block_on(async {
+            let mut dom = VirtualDom::new(app);
+            _ = dom.rebuild();
+
+            let mut out = Vec::new();
+            Renderer::new()
+                .render_streaming(&mut dom, &mut out)
+                .await
+                .unwrap();
+            let out = String::from_utf8(out).unwrap();
+
+            let (shell, chunk) = out.split_at(out.find("<template").unwrap());
+            assert_eq!(
+                shell,
+                "<main><h1>Profile</h1><div id=\"ds-1\" style=\"display:contents\">loading</div></main>"
+            );
+            assert!(chunk.starts_with("<template id=\"ds-1-chunk\"><p>ferris</p></template><script>"));
+            assert!(chunk.ends_with("</script>"));
+        });
+}
+
+#[test]
+fn pages_without_suspense_are_sent_at_once() {
+    tokio::runtime::Builder::new_current_thread()
+        .build()
+        .unwrap()
+        .block_on(async {
+            let mut dom = VirtualDom::new(|cx| render! { div { "hello" } });
+            _ = dom.rebuild();
+
+            let mut out = Vec::new();
+            Renderer::new()
+                .render_streaming(&mut dom, &mut out)
+                .await
+                .unwrap();
+            assert_eq!(out, b"<div>hello</div>");
+        });
+}
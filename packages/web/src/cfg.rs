@@ -10,9 +10,12 @@
 pub struct Config {
     #[cfg(feature = "hydrate")]
     pub(crate) hydrate: bool,
+    #[cfg(feature = "hydrate")]
+    pub(crate) debug_hydration: bool,
     pub(crate) rootname: String,
     pub(crate) cached_strings: Vec<String>,
     pub(crate) default_panic_hook: bool,
+    pub(crate) scroll_restoration: Vec<String>,
 }
 
 impl Default for Config {
@@ -20,9 +23,12 @@ impl Default for Config {
         Self {
             #[cfg(feature = "hydrate")]
             hydrate: false,
+            #[cfg(feature = "hydrate")]
+            debug_hydration: cfg!(debug_assertions),
             rootname: "main".to_string(),
             cached_strings: Vec::new(),
             default_panic_hook: true,
+            scroll_restoration: Vec::new(),
         }
     }
 }
@@ -47,6 +53,17 @@ impl Config {
         self
     }
 
+    #[cfg(feature = "hydrate")]
+    /// Report every difference between the pre-rendered HTML and the VirtualDom while hydrating
+    ///
+    /// Each mismatch is logged to the console with the name of the component and the template it comes from. Text and
+    /// attributes that differ are patched either way, and a page whose structure differs is rendered again from
+    /// scratch. This is on by default in debug builds.
+    pub fn debug_hydration(mut self, f: bool) -> Self {
+        self.debug_hydration = f;
+        self
+    }
+
     /// Set the name of the element that Dioxus will use as the root.
     ///
     /// This is akin to calling React.render() on the element with the specified name.
@@ -71,4 +88,12 @@ impl Config {
         self.default_panic_hook = f;
         self
     }
+
+    /// Restore the scroll position of keyed elements with this key when they are mounted again.
+    ///
+    /// See [`dioxus_core::VirtualDom::register_scroll_restoration`] for more details.
+    pub fn scroll_restoration(mut self, key: impl Into<String>) -> Self {
+        self.scroll_restoration.push(key.into());
+        self
+    }
 }
@@ -0,0 +1,57 @@
+use async_trait::async_trait;
+use dioxus_core::ScopeState;
+use dioxus_html::prelude::{ClipboardError, ClipboardProvider};
+use std::rc::Rc;
+use wasm_bindgen::prelude::*;
+use wasm_bindgen_futures::JsFuture;
+
+/// Provides the WebClipboardProvider through [`cx.provide_context`].
+pub fn init_clipboard(cx: &ScopeState) {
+    let provider: Rc<dyn ClipboardProvider> = Rc::new(WebClipboardProvider);
+    cx.provide_context(provider);
+}
+
+/// Represents the web-target's clipboard, backed by the async Clipboard API.
+///
+/// Browsers only allow the clipboard to be used on secure pages, and usually only while the page is focused.
+pub struct WebClipboardProvider;
+
+#[async_trait(?Send)]
+impl ClipboardProvider for WebClipboardProvider {
+    async fn read_text(&self) -> Result<String, ClipboardError> {
+        let text = JsFuture::from(read_clipboard_text().map_err(clipboard_error)?)
+            .await
+            .map_err(clipboard_error)?;
+        text.as_string()
+            .ok_or_else(|| ClipboardError::Failed("the clipboard did not contain text".into()))
+    }
+
+    async fn write_text(&self, text: String) -> Result<(), ClipboardError> {
+        JsFuture::from(write_clipboard_text(&text).map_err(clipboard_error)?)
+            .await
+            .map_err(clipboard_error)?;
+        Ok(())
+    }
+}
+
+fn clipboard_error(err: JsValue) -> ClipboardError {
+    ClipboardError::Failed(format!("{:?}", err))
+}
+
+// navigator.clipboard is only bound by web-sys behind unstable apis, so we bind to it manually
+#[wasm_bindgen(inline_js = r#"
+    export function read_clipboard_text() {
+        return navigator.clipboard.readText();
+    }
+
+    export function write_clipboard_text(text) {
+        return navigator.clipboard.writeText(text);
+    }
+"#)]
+extern "C" {
+    #[wasm_bindgen(catch)]
+    fn read_clipboard_text() -> Result<js_sys::Promise, JsValue>;
+
+    #[wasm_bindgen(catch)]
+    fn write_clipboard_text(text: &str) -> Result<js_sys::Promise, JsValue>;
+}
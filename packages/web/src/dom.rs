@@ -8,14 +8,16 @@
 //! - Partial delegation?>
 
 use dioxus_core::{
-    BorrowedAttributeValue, ElementId, Mutation, Template, TemplateAttribute, TemplateNode,
+    BorrowedAttributeValue, DerivedAttribute, DerivedInputs, ElementId, HeadElement,
+    InitialAttribute, Template, TemplateAttribute, TemplateNode, TemplatePath, VirtualDom,
+    WriteMutations, HEAD_ATTRIBUTE,
 };
-use dioxus_html::{event_bubbles, CompositionData, FormData, MountedData};
+use dioxus_html::{event_bubbles, CompositionData, CustomEventData, FormData, MountedData};
 use dioxus_interpreter_js::{get_node, minimal_bindings, save_template, Channel};
 use futures_channel::mpsc;
 use js_sys::Array;
-use rustc_hash::FxHashMap;
-use std::{any::Any, rc::Rc};
+use rustc_hash::{FxHashMap, FxHashSet};
+use std::{any::Any, cell::RefCell, rc::Rc};
 use wasm_bindgen::{closure::Closure, prelude::wasm_bindgen, JsCast, JsValue};
 use web_sys::{Document, Element, Event};
 
@@ -29,6 +31,20 @@ pub struct WebsysDom {
     max_template_id: u32,
     pub(crate) interpreter: Channel,
     event_channel: mpsc::UnboundedSender<UiEvent>,
+    derived: DerivedAttributes,
+    // Scroll positions read from elements right before they were removed
+    captured_scroll: Vec<(ElementId, f64, f64)>,
+    // Elements with a mounted listener, which get their mounted event once the edits are flushed
+    to_mount: Vec<ElementId>,
+    // Scrolling only works once the element is attached, so scroll positions are restored after flushing
+    to_scroll: Vec<(ElementId, f64, f64)>,
+    // Controlled fields that got input, which get the value the app set written back once the edits are flushed
+    to_reassert: Rc<RefCell<Vec<Element>>>,
+    // Elements with a mounted listener that were found while rehydrating
+    #[cfg(feature = "hydrate")]
+    pub(crate) hydrated_mounts: Vec<ElementId>,
+    #[cfg(feature = "hydrate")]
+    pub(crate) debug_hydration: bool,
 }
 
 pub struct UiEvent {
@@ -39,7 +55,11 @@ pub struct UiEvent {
 }
 
 impl WebsysDom {
-    pub fn new(cfg: Config, event_channel: mpsc::UnboundedSender<UiEvent>) -> Self {
+    pub fn new(
+        cfg: Config,
+        event_channel: mpsc::UnboundedSender<UiEvent>,
+        dom: Rc<RefCell<VirtualDom>>,
+    ) -> Self {
         // eventually, we just want to let the interpreter do all the work of decoding events into our event type
         // a match here in order to avoid some error during runtime browser test
         let document = load_document();
@@ -48,13 +68,15 @@ impl WebsysDom {
             None => document.create_element("body").ok().unwrap(),
         };
         let interpreter = Channel::default();
+        let to_reassert = Rc::new(RefCell::new(Vec::new()));
 
         let handler: Closure<dyn FnMut(&Event)> = Closure::wrap(Box::new({
             let event_channel = event_channel.clone();
+            let to_reassert = to_reassert.clone();
             move |event: &web_sys::Event| {
                 let name = event.type_();
-                let element = walk_event_for_id(event);
                 let bubbles = dioxus_html::event_bubbles(name.as_str());
+                let element = walk_event_for_id(event, bubbles);
                 if let Some((element, target)) = element {
                     let prevent_event;
                     if let Some(prevent_requests) = target
@@ -79,13 +101,32 @@ impl WebsysDom {
                         event.prevent_default();
                     }
 
+                    let controlled = match name.as_str() {
+                        "input" | "change" => Some(target.clone()),
+                        _ => None,
+                    };
+
                     let data = virtual_event_from_websys_event(event.clone(), target);
-                    let _ = event_channel.unbounded_send(UiEvent {
-                        name,
-                        bubbles,
-                        element,
-                        data,
-                    });
+
+                    // If the VirtualDom is idle, run the listeners right away so they can prevent the default action
+                    // before the browser runs it. Events fired while the VirtualDom is busy, like a blur caused by
+                    // applying edits, are queued instead.
+                    if let Ok(mut dom) = dom.try_borrow_mut() {
+                        if !dom.handle_event(&name, data, element, bubbles) {
+                            event.prevent_default();
+                        }
+                    } else {
+                        let _ = event_channel.unbounded_send(UiEvent {
+                            name,
+                            bubbles,
+                            element,
+                            data,
+                        });
+                    }
+
+                    if let Some(target) = controlled {
+                        to_reassert.borrow_mut().push(target);
+                    }
                 }
             }
         }));
@@ -102,6 +143,15 @@ impl WebsysDom {
             templates: FxHashMap::default(),
             max_template_id: 0,
             event_channel,
+            derived: DerivedAttributes::default(),
+            captured_scroll: Vec::new(),
+            to_mount: Vec::new(),
+            to_scroll: Vec::new(),
+            to_reassert,
+            #[cfg(feature = "hydrate")]
+            hydrated_mounts: Vec::new(),
+            #[cfg(feature = "hydrate")]
+            debug_hydration: cfg.debug_hydration,
         }
     }
 
@@ -109,12 +159,78 @@ impl WebsysDom {
         self.interpreter.mount_to_root();
     }
 
+    /// Replace the elements in the head of the page that were created for the VirtualDom with the ones it has now
+    pub fn update_head(&mut self, elements: &[HeadElement]) {
+        let head = match self.document.head() {
+            Some(head) => head,
+            None => return,
+        };
+
+        // This also removes the elements the server rendered into the head
+        if let Ok(old) = self
+            .document
+            .query_selector_all(&format!("[{}]", HEAD_ATTRIBUTE))
+        {
+            for idx in 0..old.length() {
+                if let Some(node) = old.item(idx) {
+                    _ = head.remove_child(&node);
+                }
+            }
+        }
+
+        for element in elements {
+            if element.tag == "title" {
+                self.document
+                    .set_title(element.text.as_deref().unwrap_or_default());
+                continue;
+            }
+
+            let el = match self.document.create_element(element.tag) {
+                Ok(el) => el,
+                Err(_) => continue,
+            };
+            _ = el.set_attribute(HEAD_ATTRIBUTE, "");
+            for (name, value) in &element.attributes {
+                _ = el.set_attribute(name, value);
+            }
+            if let Some(text) = &element.text {
+                el.set_text_content(Some(text));
+            }
+            _ = head.append_child(&el);
+        }
+    }
+
+    /// Set an attribute of an element that was claimed while rehydrating
+    #[cfg(feature = "hydrate")]
+    pub(crate) fn set_hydrated_attribute(
+        &mut self,
+        id: ElementId,
+        name: &str,
+        value: &BorrowedAttributeValue,
+        ns: Option<&str>,
+    ) {
+        set_attribute(
+            &mut self.interpreter,
+            &mut self.derived,
+            id,
+            name,
+            value,
+            ns,
+        );
+    }
+
     pub fn load_templates(&mut self, templates: &[Template]) {
         for template in templates {
             let mut roots = vec![];
 
+            // Elements of templates with a style are marked so the style can be scoped to them
+            #[cfg(feature = "scoped-styles")]
+            let scope = template.style.map(|_| template.name);
+            #[cfg(not(feature = "scoped-styles"))]
+            let scope: Option<&str> = None;
+
             for root in template.roots {
-                roots.push(self.create_template_node(root))
+                roots.push(self.create_template_node(root, scope))
             }
 
             self.templates
@@ -124,7 +240,7 @@ impl WebsysDom {
         }
     }
 
-    fn create_template_node(&self, v: &TemplateNode) -> web_sys::Node {
+    fn create_template_node(&self, v: &TemplateNode, scope: Option<&str>) -> web_sys::Node {
         use TemplateNode::*;
         match v {
             Element {
@@ -153,8 +269,11 @@ impl WebsysDom {
                         );
                     }
                 }
+                if let Some(scope) = scope {
+                    let _ = el.set_attribute("data-dioxus-scope", scope);
+                }
                 for child in *children {
-                    let _ = el.append_child(&self.create_template_node(child));
+                    let _ = el.append_child(&self.create_template_node(child, scope));
                 }
                 el.dyn_into().unwrap()
             }
@@ -168,98 +287,232 @@ impl WebsysDom {
         }
     }
 
-    pub fn apply_edits(&mut self, mut edits: Vec<Mutation>) {
-        use Mutation::*;
-        let i = &mut self.interpreter;
-        // we need to apply the mount events last, so we collect them here
-        let mut to_mount = Vec::new();
-        for edit in &edits {
-            match edit {
-                AppendChildren { id, m } => i.append_children(id.0 as u32, *m as u32),
-                AssignId { path, id } => {
-                    i.assign_id(path.as_ptr() as u32, path.len() as u8, id.0 as u32)
-                }
-                CreatePlaceholder { id } => i.create_placeholder(id.0 as u32),
-                CreateTextNode { value, id } => i.create_text_node(value, id.0 as u32),
-                HydrateText { path, value, id } => {
-                    i.hydrate_text(path.as_ptr() as u32, path.len() as u8, value, id.0 as u32)
-                }
-                LoadTemplate { name, index, id } => {
-                    if let Some(tmpl_id) = self.templates.get(*name) {
-                        i.load_template(*tmpl_id, *index as u32, id.0 as u32)
-                    }
-                }
-                ReplaceWith { id, m } => i.replace_with(id.0 as u32, *m as u32),
-                ReplacePlaceholder { path, m } => {
-                    i.replace_placeholder(path.as_ptr() as u32, path.len() as u8, *m as u32)
-                }
-                InsertAfter { id, m } => i.insert_after(id.0 as u32, *m as u32),
-                InsertBefore { id, m } => i.insert_before(id.0 as u32, *m as u32),
-                SetAttribute {
-                    name,
-                    value,
-                    id,
-                    ns,
-                } => match value {
-                    BorrowedAttributeValue::Text(txt) => {
-                        i.set_attribute(id.0 as u32, name, txt, ns.unwrap_or_default())
-                    }
-                    BorrowedAttributeValue::Float(f) => {
-                        i.set_attribute(id.0 as u32, name, &f.to_string(), ns.unwrap_or_default())
-                    }
-                    BorrowedAttributeValue::Int(n) => {
-                        i.set_attribute(id.0 as u32, name, &n.to_string(), ns.unwrap_or_default())
-                    }
-                    BorrowedAttributeValue::Bool(b) => i.set_attribute(
-                        id.0 as u32,
-                        name,
-                        if *b { "true" } else { "false" },
-                        ns.unwrap_or_default(),
-                    ),
-                    BorrowedAttributeValue::None => {
-                        i.remove_attribute(id.0 as u32, name, ns.unwrap_or_default())
-                    }
-                    _ => unreachable!(),
-                },
-                SetText { value, id } => i.set_text(id.0 as u32, value),
-                NewEventListener { name, id, .. } => {
-                    match *name {
-                        // mounted events are fired immediately after the element is mounted.
-                        "mounted" => {
-                            to_mount.push(*id);
-                        }
-                        _ => {
-                            i.new_event_listener(name, id.0 as u32, event_bubbles(name) as u8);
-                        }
-                    }
-                }
-                RemoveEventListener { name, id } => match *name {
-                    "mounted" => {}
-                    _ => {
-                        i.remove_event_listener(name, id.0 as u32, event_bubbles(name) as u8);
-                    }
-                },
-                Remove { id } => i.remove(id.0 as u32),
-                PushRoot { id } => i.push_root(id.0 as u32),
+    /// Finish applying the edits written since the last flush. Mounted events and scroll restores are sent once the
+    /// elements are attached to the document.
+    pub fn flush_edits(&mut self) {
+        self.interpreter.flush();
+
+        // Derived attributes read the attributes of the element, so they are computed once the edits are applied
+        self.derived.update();
+
+        for (id, x, y) in std::mem::take(&mut self.to_scroll) {
+            if let Some(element) = get_node(id.0 as u32).dyn_ref::<Element>() {
+                element.set_scroll_left(x as i32);
+                element.set_scroll_top(y as i32);
             }
         }
-        edits.clear();
-        i.flush();
-
-        for id in to_mount {
-            let node = get_node(id.0 as u32);
-            if let Some(element) = node.dyn_ref::<Element>() {
-                let data: MountedData = element.into();
-                let data = Rc::new(data);
-                let _ = self.event_channel.unbounded_send(UiEvent {
-                    name: "mounted".to_string(),
-                    bubbles: false,
-                    element: id,
-                    data,
-                });
+
+        // Write the value the app set back into controlled fields, so input the app rejected doesn't stay in the DOM
+        for target in std::mem::take(&mut *self.to_reassert.borrow_mut()) {
+            dioxus_interpreter_js::reassert_controlled(target.unchecked_into());
+        }
+
+        for id in std::mem::take(&mut self.to_mount) {
+            self.send_mount_event(id);
+        }
+    }
+
+    /// Report the scroll positions captured while applying edits back to the VirtualDom
+    pub fn report_scroll(&mut self, dom: &mut VirtualDom) {
+        for (id, x, y) in self.captured_scroll.drain(..) {
+            dom.report_scroll(id, x, y);
+        }
+    }
+
+    /// Send the mounted event of an element that is attached to the document
+    pub(crate) fn send_mount_event(&self, id: ElementId) {
+        let node = get_node(id.0 as u32);
+        if let Some(element) = node.dyn_ref::<Element>() {
+            let data: MountedData = element.into();
+            let data = Rc::new(data);
+            let _ = self.event_channel.unbounded_send(UiEvent {
+                name: "mounted".to_string(),
+                bubbles: false,
+                element: id,
+                data,
+            });
+        }
+    }
+}
+
+impl<'a> WriteMutations<'a> for WebsysDom {
+    fn register_template(&mut self, template: Template<'a>) {
+        self.load_templates(&[template]);
+    }
+
+    fn append_children(&mut self, id: ElementId, m: usize) {
+        self.interpreter.append_children(id.0 as u32, m as u32)
+    }
+
+    fn assign_id(&mut self, path: TemplatePath<'static>, id: ElementId) {
+        self.derived.forget(id);
+        match path {
+            TemplatePath::Packed(path) => self
+                .interpreter
+                .assign_id_packed(path.to_bits(), id.0 as u32),
+            TemplatePath::Slice(path) => {
+                self.interpreter
+                    .assign_id(path.as_ptr() as u32, path.len() as u8, id.0 as u32)
             }
         }
     }
+
+    fn create_placeholder(&mut self, id: ElementId) {
+        self.derived.forget(id);
+        self.interpreter.create_placeholder(id.0 as u32)
+    }
+
+    fn create_text_node(&mut self, value: &'a str, id: ElementId) {
+        self.derived.forget(id);
+        self.interpreter.create_text_node(value, id.0 as u32)
+    }
+
+    fn hydrate_text(&mut self, path: TemplatePath<'static>, value: &'a str, id: ElementId) {
+        self.derived.forget(id);
+        match path {
+            TemplatePath::Packed(path) => {
+                self.interpreter
+                    .hydrate_text_packed(path.to_bits(), value, id.0 as u32)
+            }
+            TemplatePath::Slice(path) => self.interpreter.hydrate_text(
+                path.as_ptr() as u32,
+                path.len() as u8,
+                value,
+                id.0 as u32,
+            ),
+        }
+    }
+
+    fn load_template(&mut self, name: &'static str, index: usize, id: ElementId) {
+        self.derived.forget(id);
+        if let Some(tmpl_id) = self.templates.get(name) {
+            self.interpreter
+                .load_template(*tmpl_id, index as u32, id.0 as u32)
+        }
+    }
+
+    fn create_element_with_attrs(
+        &mut self,
+        name: &'static str,
+        index: usize,
+        id: ElementId,
+        _tag: &'a str,
+        attrs: &[InitialAttribute<'a>],
+        listeners: &[&'a str],
+    ) {
+        self.load_template(name, index, id);
+        for attr in attrs {
+            set_attribute(
+                &mut self.interpreter,
+                &mut self.derived,
+                id,
+                attr.name,
+                &attr.value,
+                attr.ns,
+            );
+        }
+        for listener in listeners {
+            new_event_listener(&mut self.interpreter, &mut self.to_mount, id, listener);
+        }
+    }
+
+    fn replace_with(&mut self, id: ElementId, m: usize) {
+        self.interpreter.replace_with(id.0 as u32, m as u32)
+    }
+
+    fn replace_placeholder(&mut self, path: TemplatePath<'static>, m: usize) {
+        match path {
+            TemplatePath::Packed(path) => self
+                .interpreter
+                .replace_placeholder_packed(path.to_bits(), m as u32),
+            TemplatePath::Slice(path) => self.interpreter.replace_placeholder(
+                path.as_ptr() as u32,
+                path.len() as u8,
+                m as u32,
+            ),
+        }
+    }
+
+    fn insert_after(&mut self, id: ElementId, m: usize) {
+        self.interpreter.insert_after(id.0 as u32, m as u32)
+    }
+
+    fn insert_before(&mut self, id: ElementId, m: usize) {
+        self.interpreter.insert_before(id.0 as u32, m as u32)
+    }
+
+    fn set_attribute(
+        &mut self,
+        name: &'a str,
+        ns: Option<&'a str>,
+        value: &BorrowedAttributeValue<'a>,
+        id: ElementId,
+    ) {
+        set_attribute(
+            &mut self.interpreter,
+            &mut self.derived,
+            id,
+            name,
+            value,
+            ns,
+        )
+    }
+
+    fn set_text(&mut self, value: &'a str, id: ElementId) {
+        self.interpreter.set_text(id.0 as u32, value)
+    }
+
+    fn new_event_listener(&mut self, name: &'a str, id: ElementId) {
+        new_event_listener(&mut self.interpreter, &mut self.to_mount, id, name)
+    }
+
+    fn remove_event_listener(&mut self, name: &'a str, id: ElementId) {
+        if name != "mounted" {
+            self.interpreter
+                .remove_event_listener(name, id.0 as u32, event_bubbles(name) as u8);
+        }
+    }
+
+    fn remove(&mut self, id: ElementId) {
+        self.interpreter.remove(id.0 as u32)
+    }
+
+    fn push_root(&mut self, id: ElementId) {
+        self.interpreter.push_root(id.0 as u32)
+    }
+
+    fn append_to_anchor(&mut self, anchor: &'a str, m: usize) {
+        self.interpreter.append_to_anchor(anchor, m as u32)
+    }
+
+    fn capture_scroll(&mut self, id: ElementId) {
+        // The element may have been created by edits that are still queued, so flush before reading it
+        self.interpreter.flush();
+        if let Some(element) = get_node(id.0 as u32).dyn_ref::<Element>() {
+            self.captured_scroll.push((
+                id,
+                element.scroll_left() as f64,
+                element.scroll_top() as f64,
+            ));
+        }
+    }
+
+    fn restore_scroll(&mut self, id: ElementId, x: f64, y: f64) {
+        self.to_scroll.push((id, x, y));
+    }
+
+    fn inject_style(&mut self, name: &'a str, style: &'a str) {
+        inject_style(&self.document, name, style)
+    }
+
+    fn register_delegated(&mut self, name: &'a str) {
+        self.interpreter
+            .register_delegated(name, event_bubbles(name) as u8)
+    }
+
+    fn unregister_delegated(&mut self, name: &'a str) {
+        self.interpreter.unregister_delegated(name)
+    }
 }
 
 // todo: some of these events are being casted to the wrong event type.
@@ -283,8 +536,23 @@ pub fn virtual_event_from_websys_event(event: web_sys::Event, target: Element) -
         }
         "drag" | "dragend" | "dragenter" | "dragexit" | "dragleave" | "dragover" | "dragstart"
         | "drop" => {
+            #[cfg(not(feature = "file_engine"))]
+            let files = None;
+            #[cfg(feature = "file_engine")]
+            let files = event
+                .dyn_ref::<web_sys::DragEvent>()
+                .and_then(|event| event.data_transfer())
+                .and_then(|data_transfer| data_transfer.files())
+                .filter(|files| files.length() > 0)
+                .and_then(|files| {
+                    #[allow(clippy::arc_with_non_send_sync)]
+                    crate::file_engine::WebFileEngine::new(files).map(|f| {
+                        std::sync::Arc::new(f) as std::sync::Arc<dyn dioxus_html::FileEngine>
+                    })
+                });
+
             let mouse = MouseData::from(event);
-            Rc::new(DragData { mouse })
+            Rc::new(DragData { mouse, files })
         }
 
         "pointerdown" | "pointermove" | "pointerup" | "pointercancel" | "gotpointercapture"
@@ -308,7 +576,7 @@ pub fn virtual_event_from_websys_event(event: web_sys::Event, target: Element) -
         "load" => Rc::new(ImageData { load_error: false }),
         "toggle" => Rc::new(ToggleData {}),
 
-        _ => Rc::new(()),
+        _ => Rc::new(make_custom_event(&event)),
     }
 }
 
@@ -319,6 +587,19 @@ fn make_composition_event(event: &Event) -> Rc<CompositionData> {
     })
 }
 
+fn make_custom_event(event: &Event) -> CustomEventData {
+    let detail = event
+        .dyn_ref::<web_sys::CustomEvent>()
+        .map(|event| event.detail())
+        .filter(|detail| !detail.is_undefined())
+        .and_then(|detail| js_sys::JSON::stringify(&detail).ok())
+        .and_then(|detail| detail.as_string());
+    match detail {
+        Some(detail) => CustomEventData::new(detail),
+        None => CustomEventData::default(),
+    }
+}
+
 pub(crate) fn load_document() -> Document {
     web_sys::window()
         .expect("should have access to the Window")
@@ -421,39 +702,173 @@ extern "C" {
     fn get_form_data(form: &web_sys::HtmlFormElement) -> js_sys::Map;
 }
 
-fn walk_event_for_id(event: &web_sys::Event) -> Option<(ElementId, web_sys::Element)> {
-    let target = event
+fn walk_event_for_id(
+    event: &web_sys::Event,
+    bubbles: bool,
+) -> Option<(ElementId, web_sys::Element)> {
+    // Listeners are delegated to the root, so the event goes to the closest element the interpreter gave an id. Events
+    // that don't bubble only go to their target.
+    let mut node = event
         .target()
         .expect("missing target")
         .dyn_into::<web_sys::Node>()
         .expect("not a valid node");
-    let mut current_target_element = target.dyn_ref::<web_sys::Element>().cloned();
 
     loop {
-        match (
-            current_target_element
-                .as_ref()
-                .and_then(|el| el.get_attribute("data-dioxus-id").map(|f| f.parse())),
-            current_target_element,
-        ) {
-            // This node is an element, and has a dioxus id, so we can stop walking
-            (Some(Ok(id)), Some(target)) => return Some((ElementId(id), target)),
-
-            // Walk the tree upwards until we actually find an event target
-            (None, target_element) => {
-                let parent = match target_element.as_ref() {
-                    Some(el) => el.parent_element(),
-                    // if this is the first node and not an element, we need to get the parent from the target node
-                    None => target.parent_element(),
+        if let Some(element) = node.dyn_ref::<web_sys::Element>() {
+            let id = js_sys::Reflect::get(element, &JsValue::from_str("dioxusId"))
+                .ok()
+                .and_then(|id| id.as_f64());
+            if let Some(id) = id {
+                return Some((ElementId(id as usize), element.clone()));
+            }
+        }
+
+        if !bubbles {
+            return None;
+        }
+        node = node.parent_element()?.into();
+    }
+}
+
+fn set_attribute(
+    i: &mut Channel,
+    derived: &mut DerivedAttributes,
+    id: ElementId,
+    name: &str,
+    value: &BorrowedAttributeValue,
+    ns: Option<&str>,
+) {
+    if let BorrowedAttributeValue::Derived(value) = value {
+        return derived.set(id, name, ns, Some(*value));
+    }
+    derived.set(id, name, ns, None);
+
+    if ns == Some("prop") {
+        return set_property(i, id, name, value);
+    }
+
+    match value {
+        BorrowedAttributeValue::Text(txt) => {
+            i.set_attribute(id.0 as u32, name, txt, ns.unwrap_or_default())
+        }
+        BorrowedAttributeValue::Float(f) => {
+            i.set_attribute(id.0 as u32, name, &f.to_string(), ns.unwrap_or_default())
+        }
+        BorrowedAttributeValue::Int(n) => {
+            i.set_attribute(id.0 as u32, name, &n.to_string(), ns.unwrap_or_default())
+        }
+        BorrowedAttributeValue::Bool(b) => i.set_attribute(
+            id.0 as u32,
+            name,
+            if *b { "true" } else { "false" },
+            ns.unwrap_or_default(),
+        ),
+        BorrowedAttributeValue::None => {
+            i.remove_attribute(id.0 as u32, name, ns.unwrap_or_default())
+        }
+        // DOM attributes can only hold text
+        BorrowedAttributeValue::Bytes(_) => {}
+        _ => unreachable!(),
+    }
+}
+
+/// Set a property on the element object, like the `"prop:name"` attributes custom elements take. Properties can hold
+/// any JavaScript value, so they are set directly instead of as text through the channel.
+fn set_property(i: &mut Channel, id: ElementId, name: &str, value: &BorrowedAttributeValue) {
+    let value = match value {
+        BorrowedAttributeValue::Text(txt) => JsValue::from_str(txt),
+        BorrowedAttributeValue::Float(f) => JsValue::from_f64(*f),
+        BorrowedAttributeValue::Int(n) => JsValue::from_f64(*n as f64),
+        BorrowedAttributeValue::Bool(b) => JsValue::from_bool(*b),
+        BorrowedAttributeValue::Bytes(bytes) => js_sys::Uint8Array::from(*bytes).into(),
+        BorrowedAttributeValue::Any(value) => match value.downcast_ref::<JsValue>() {
+            Some(value) => value.clone(),
+            None => return,
+        },
+        BorrowedAttributeValue::None => JsValue::UNDEFINED,
+        _ => return,
+    };
+
+    // The element may be created by edits that are still queued in the channel
+    i.flush();
+    _ = js_sys::Reflect::set(&get_node(id.0 as u32), &JsValue::from_str(name), &value);
+}
+
+/// The derived attributes of each element, and the elements whose attributes changed since they were last computed
+#[derive(Default)]
+struct DerivedAttributes {
+    attributes: FxHashMap<ElementId, Vec<(String, Option<String>, DerivedAttribute)>>,
+    dirty: FxHashSet<ElementId>,
+}
+
+impl DerivedAttributes {
+    /// Record that an attribute changed. Setting any other value replaces a derived attribute with the same name.
+    fn set(
+        &mut self,
+        id: ElementId,
+        name: &str,
+        ns: Option<&str>,
+        derived: Option<DerivedAttribute>,
+    ) {
+        // Elements without derived attributes don't need to be tracked
+        if derived.is_none() && !self.attributes.contains_key(&id) {
+            return;
+        }
+
+        let attrs = self.attributes.entry(id).or_default();
+        attrs.retain(|(other, other_ns, _)| other != name || other_ns.as_deref() != ns);
+        if let Some(derived) = derived {
+            attrs.push((name.to_string(), ns.map(ToString::to_string), derived));
+        }
+        self.dirty.insert(id);
+    }
+
+    /// Stop tracking an id that is being reused for a new node
+    fn forget(&mut self, id: ElementId) {
+        self.attributes.remove(&id);
+    }
+
+    /// Recompute the derived attributes of every element that changed
+    fn update(&mut self) {
+        for id in self.dirty.drain() {
+            let (attrs, element) = match (
+                self.attributes.get(&id),
+                get_node(id.0 as u32).dyn_into::<Element>(),
+            ) {
+                (Some(attrs), Ok(element)) => (attrs, element),
+                _ => continue,
+            };
+
+            for (name, ns, derived) in attrs {
+                let lookup = |name: &str| element.get_attribute(name);
+                let _ = match derived.compute(&DerivedInputs::new(&lookup)) {
+                    Some(value) => element.set_attribute_ns(ns.as_deref(), name, &value),
+                    None => element.remove_attribute_ns(ns.as_deref(), name),
                 };
-                match parent {
-                    Some(parent) => current_target_element = Some(parent),
-                    _ => return None,
-                }
             }
+        }
+    }
+}
 
-            // This node is an element with an invalid dioxus id, give up
-            _ => return None,
+fn new_event_listener(i: &mut Channel, to_mount: &mut Vec<ElementId>, id: ElementId, name: &str) {
+    match name {
+        // mounted events are fired immediately after the element is mounted.
+        "mounted" => {
+            to_mount.push(id);
         }
+        _ => {
+            i.new_event_listener(name, id.0 as u32, event_bubbles(name) as u8);
+        }
+    }
+}
+
+fn inject_style(document: &Document, name: &str, style: &str) {
+    let el = document.create_element("style").unwrap();
+    el.set_text_content(Some(&format!(
+        "@scope ([data-dioxus-scope=\"{name}\"]) {{ {style} }}"
+    )));
+    if let Some(head) = document.head() {
+        let _ = head.append_child(&el);
     }
 }
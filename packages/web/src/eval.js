@@ -10,9 +10,8 @@ export class Dioxus {
   recv() {
     return new Promise((resolve, _reject) => {
       // If data already exists, resolve immediately
-      let data = this.received.shift();
-      if (data) {
-        resolve(data);
+      if (this.received.length > 0) {
+        resolve(this.received.shift());
         return;
       }
 
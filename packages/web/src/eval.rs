@@ -21,18 +21,19 @@ impl EvalProvider for WebEvalProvider {
 }
 
 /// Required to avoid blocking the Rust WASM thread.
+///
+/// The script runs inside an async function so it can `await dioxus.recv()` and `return` a value for [`Evaluator::join`].
 const PROMISE_WRAPPER: &str = r#"
-    return new Promise(async (resolve, _reject) => {
+    return (async () => {
         {JS_CODE}
-        resolve(null);
-    });
+    })();
     "#;
 
 /// Reprents a web-target's JavaScript evaluator.
 pub struct WebEvaluator {
     dioxus: Dioxus,
     channel_receiver: async_channel::Receiver<serde_json::Value>,
-    result: RefCell<Option<serde_json::Value>>,
+    result: RefCell<Option<js_sys::Promise>>,
 }
 
 impl WebEvaluator {
@@ -58,24 +59,7 @@ impl WebEvaluator {
         let code = PROMISE_WRAPPER.replace("{JS_CODE}", &js);
 
         let result = match Function::new_with_args("dioxus", &code).call1(&JsValue::NULL, &dioxus) {
-            Ok(result) => {
-                if let Ok(stringified) = js_sys::JSON::stringify(&result) {
-                    if !stringified.is_undefined() && stringified.is_valid_utf16() {
-                        let string: String = stringified.into();
-                        Value::from_str(&string).map_err(|e| {
-                            EvalError::Communication(format!("Failed to parse result - {}", e))
-                        })?
-                    } else {
-                        return Err(EvalError::Communication(
-                            "Failed to stringify result".into(),
-                        ));
-                    }
-                } else {
-                    return Err(EvalError::Communication(
-                        "Failed to stringify result".into(),
-                    ));
-                }
-            }
+            Ok(result) => js_sys::Promise::from(result),
             Err(err) => {
                 return Err(EvalError::InvalidJs(
                     err.as_string().unwrap_or("unknown".to_string()),
@@ -95,7 +79,29 @@ impl WebEvaluator {
 impl Evaluator for WebEvaluator {
     /// Runs the evaluated JavaScript.
     async fn join(&self) -> Result<serde_json::Value, EvalError> {
-        self.result.take().ok_or(EvalError::Finished)
+        let promise = self.result.take().ok_or(EvalError::Finished)?;
+
+        let result = wasm_bindgen_futures::JsFuture::from(promise)
+            .await
+            .map_err(|err| {
+                EvalError::Communication(err.as_string().unwrap_or("unknown".to_string()))
+            })?;
+
+        // A script without a return value resolves to undefined, which has no JSON representation
+        if result.is_undefined() {
+            return Ok(Value::Null);
+        }
+
+        let stringified = js_sys::JSON::stringify(&result)
+            .map_err(|_| EvalError::Communication("Failed to stringify result".into()))?;
+        if stringified.is_undefined() || !stringified.is_valid_utf16() {
+            return Err(EvalError::Communication(
+                "Failed to stringify result".into(),
+            ));
+        }
+        let string: String = stringified.into();
+        Value::from_str(&string)
+            .map_err(|e| EvalError::Communication(format!("Failed to parse result - {}", e)))
     }
 
     /// Sends a message to the evaluated JavaScript.
@@ -3,47 +3,23 @@
 use futures_channel::mpsc::UnboundedReceiver;
 
 use dioxus_core::Template;
+use dioxus_hot_reload::HotReloadMsg;
 
 pub(crate) fn init() -> UnboundedReceiver<Template<'static>> {
-    use std::convert::TryInto;
-    use wasm_bindgen::closure::Closure;
-    use wasm_bindgen::JsCast;
-    use web_sys::{MessageEvent, WebSocket};
-
-    use serde::Deserialize;
-
-    let window = web_sys::window().unwrap();
-
-    let protocol = match window.location().protocol().unwrap() {
-        prot if prot == "https:" => "wss:",
-        _ => "ws:",
-    };
-
-    let url = format!(
-        "{protocol}//{}/_dioxus/hot_reload",
-        window.location().host().unwrap()
-    );
-
-    let ws = WebSocket::new(&url).unwrap();
-
     let (tx, rx) = futures_channel::mpsc::unbounded();
 
-    // change the rsx when new data is received
-    let cl = Closure::wrap(Box::new(move |e: MessageEvent| {
-        if let Ok(text) = e.data().dyn_into::<js_sys::JsString>() {
-            let text: Result<String, _> = text.try_into();
-            if let Ok(string) = text {
-                let val = serde_json::from_str::<serde_json::Value>(&string).unwrap();
-                // leak the value
-                let val: &'static serde_json::Value = Box::leak(Box::new(val));
-                let template: Template<'_> = Template::deserialize(val).unwrap();
-                tx.unbounded_send(template).unwrap();
+    dioxus_hot_reload::connect(move |msg| match msg {
+        // change the rsx when new data is received
+        HotReloadMsg::UpdateTemplate(template) => {
+            _ = tx.unbounded_send(template);
+        }
+        // the app is being rebuilt, so load the new build
+        HotReloadMsg::Shutdown => {
+            if let Some(window) = web_sys::window() {
+                _ = window.location().reload();
             }
         }
-    }) as Box<dyn FnMut(MessageEvent)>);
-
-    ws.set_onmessage(Some(cl.as_ref().unchecked_ref()));
-    cl.forget();
+    });
 
     rx
 }
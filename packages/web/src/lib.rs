@@ -57,14 +57,17 @@
 
 pub use crate::cfg::Config;
 pub use crate::file_engine::WebFileEngineExt;
-use dioxus_core::{Element, Scope, VirtualDom};
+use dioxus_core::{Capabilities, Element, Scope, VirtualDom};
 use futures_util::{
-    future::{select, Either},
-    pin_mut, FutureExt, StreamExt,
+    future::{poll_fn, select, Either},
+    pin_mut, Future, FutureExt, StreamExt,
 };
+use std::{cell::RefCell, rc::Rc};
 
 mod cache;
 mod cfg;
+#[cfg(feature = "clipboard")]
+mod clipboard;
 mod dom;
 #[cfg(feature = "eval")]
 mod eval;
@@ -174,7 +177,16 @@ pub fn launch_with_props<T: 'static>(
 pub async fn run_with_props<T: 'static>(root: fn(Scope<T>) -> Element, root_props: T, cfg: Config) {
     tracing::info!("Starting up");
 
-    let mut dom = VirtualDom::new_with_props(root, root_props);
+    // Listeners are delegated to a single listener per event at the root, and run before the browser finishes the event
+    let mut dom = VirtualDom::new_with_props(root, root_props).with_capabilities(Capabilities {
+        delegated_events: true,
+        runtime_prevent_default: true,
+        ..Default::default()
+    });
+
+    for key in &cfg.scroll_restoration {
+        dom.register_scroll_restoration(key.clone());
+    }
 
     #[cfg(feature = "eval")]
     {
@@ -183,6 +195,9 @@ pub async fn run_with_props<T: 'static>(root: fn(Scope<T>) -> Element, root_prop
         eval::init_eval(cx);
     }
 
+    #[cfg(feature = "clipboard")]
+    clipboard::init_clipboard(dom.base_scope());
+
     #[cfg(feature = "panic_hook")]
     if cfg.default_panic_hook {
         console_error_panic_hook::set_once();
@@ -205,41 +220,47 @@ pub async fn run_with_props<T: 'static>(root: fn(Scope<T>) -> Element, root_prop
     #[cfg(not(feature = "hydrate"))]
     let should_hydrate = false;
 
-    let mut websys_dom = dom::WebsysDom::new(cfg, tx);
-
-    tracing::info!("rebuilding app");
+    // Listeners run inside the browser's event handler when the VirtualDom is idle, so it is shared with the handler
+    let shared_dom = Rc::new(RefCell::new(dom));
+    let mut websys_dom = dom::WebsysDom::new(cfg, tx, shared_dom.clone());
+    {
+        let mut dom = shared_dom.borrow_mut();
 
-    if should_hydrate {
-        #[cfg(feature = "hydrate")]
-        {
-            // todo: we need to split rebuild and initialize into two phases
-            // it's a waste to produce edits just to get the vdom loaded
+        tracing::info!("rebuilding app");
 
-            let templates = dom.rebuild().templates;
-            websys_dom.load_templates(&templates);
+        if should_hydrate {
+            #[cfg(feature = "hydrate")]
+            {
+                // todo: we need to split rebuild and initialize into two phases
+                // it's a waste to produce edits just to get the vdom loaded
 
-            if let Err(err) = websys_dom.rehydrate(&dom) {
-                tracing::error!(
-                    "Rehydration failed {:?}. Rebuild DOM into element from scratch",
-                    &err
-                );
-                websys_dom.root.set_text_content(None);
+                let templates = dom.rebuild().templates;
+                websys_dom.load_templates(&templates);
 
-                let edits = dom.rebuild();
+                if let Err(err) = websys_dom.rehydrate(&dom) {
+                    tracing::error!(
+                        "Rehydration failed {:?}. Rebuild DOM into element from scratch",
+                        &err
+                    );
+                    websys_dom.root.set_text_content(None);
 
-                websys_dom.load_templates(&edits.templates);
-                websys_dom.apply_edits(edits.edits);
+                    dom.rebuild_to(&mut websys_dom);
+                    websys_dom.flush_edits();
+                }
             }
+        } else {
+            dom.rebuild_to(&mut websys_dom);
+            websys_dom.flush_edits();
         }
-    } else {
-        let edits = dom.rebuild();
+        websys_dom.report_scroll(&mut dom);
 
-        websys_dom.load_templates(&edits.templates);
-        websys_dom.apply_edits(edits.edits);
-    }
+        // the mutations come back with nothing - we need to actually mount them
+        websys_dom.mount();
 
-    // the mutations come back with nothing - we need to actually mount them
-    websys_dom.mount();
+        if let Some(head) = dom.take_head_changes() {
+            websys_dom.update_head(&head);
+        }
+    }
 
     loop {
         tracing::trace!("waiting for work");
@@ -247,7 +268,14 @@ pub async fn run_with_props<T: 'static>(root: fn(Scope<T>) -> Element, root_prop
         // if virtualdom has nothing, wait for it to have something before requesting idle time
         // if there is work then this future resolves immediately.
         let (mut res, template) = {
-            let work = dom.wait_for_work().fuse();
+            // Only hold on to the VirtualDom while polling, so the event handler can use it between polls
+            let work = poll_fn(|cx| {
+                let mut dom = shared_dom.borrow_mut();
+                let work = dom.wait_for_work();
+                pin_mut!(work);
+                work.poll(cx)
+            })
+            .fuse();
             pin_mut!(work);
 
             #[cfg(all(feature = "hot_reload", debug_assertions))]
@@ -270,6 +298,8 @@ pub async fn run_with_props<T: 'static>(root: fn(Scope<T>) -> Element, root_prop
             }
         };
 
+        let mut dom = shared_dom.borrow_mut();
+
         if let Some(template) = template {
             dom.replace_template(template);
         }
@@ -292,13 +322,22 @@ pub async fn run_with_props<T: 'static>(root: fn(Scope<T>) -> Element, root_prop
         // wait for the mainthread to schedule us in
         // let deadline = work_loop.wait_for_idle_time().await;
 
-        // run the virtualdom work phase until the frame deadline is reached
-        let edits = dom.render_immediate();
+        {
+            // run the virtualdom work phase, writing the edits to the page as they are found
+            dom.render_immediate_to(&mut websys_dom);
 
-        // wait for the animation frame to fire so we can apply our changes
-        // work_loop.wait_for_raf().await;
+            // wait for the animation frame to fire so we can apply our changes
+            // work_loop.wait_for_raf().await;
 
-        websys_dom.load_templates(&edits.templates);
-        websys_dom.apply_edits(edits.edits);
+            websys_dom.flush_edits();
+        }
+
+        // Send the scroll positions read while removing elements back to the VirtualDom
+        websys_dom.report_scroll(&mut dom);
+
+        // Components may have changed the head while they rendered
+        if let Some(head) = dom.take_head_changes() {
+            websys_dom.update_head(&head);
+        }
     }
 }
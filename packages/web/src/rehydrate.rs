@@ -1,10 +1,11 @@
 use crate::dom::WebsysDom;
 use dioxus_core::{
-    AttributeValue, DynamicNode, ElementId, ScopeState, TemplateNode, VNode, VirtualDom,
+    Attribute, AttributeValue, BorrowedAttributeValue, DynamicNode, ElementId, ScopeState,
+    TemplateNode, VNode, VirtualDom, SELECTION_ATTRIBUTES,
 };
 use dioxus_html::event_bubbles;
 use wasm_bindgen::JsCast;
-use web_sys::{Comment, Node};
+use web_sys::{Comment, Element, Node};
 
 #[derive(Debug, Copy, Clone)]
 pub enum RehydrationError {
@@ -14,6 +15,32 @@ pub enum RehydrationError {
 }
 use RehydrationError::*;
 
+/// Where the walk is in the VirtualDom, so a mismatch can be traced back to the component and template it comes from
+#[derive(Default)]
+struct HydrationPath {
+    debug: bool,
+    component: String,
+    template: &'static str,
+    // The index of each node from the root of the template down to the current node
+    nodes: Vec<usize>,
+    // Static text next to other static text is merged into one text node
+    last_node_was_static_text: bool,
+}
+
+impl HydrationPath {
+    fn mismatch(&self, message: std::fmt::Arguments) {
+        if self.debug {
+            web_sys::console::warn_1(
+                &format!(
+                    "Hydration mismatch in `{}` at node {:?} of template {}: {}",
+                    self.component, self.nodes, self.template, message
+                )
+                .into(),
+            );
+        }
+    }
+}
+
 fn set_node(hydrated: &mut Vec<bool>, id: ElementId, node: Node) {
     let idx = id.0;
     if idx >= hydrated.len() {
@@ -25,6 +52,22 @@ fn set_node(hydrated: &mut Vec<bool>, id: ElementId, node: Node) {
     }
 }
 
+/// Check that the node is one of the comments the server writes around dynamic text
+fn expect_marker(
+    current_child: &Result<Node, RehydrationError>,
+    path: &HydrationPath,
+) -> Result<(), RehydrationError> {
+    let node = current_child.clone()?;
+    if !node.has_type::<Comment>() {
+        path.mismatch(format_args!(
+            "expected a text marker but found `{}`",
+            node.node_name()
+        ));
+        return Err(NodeTypeMismatch);
+    }
+    Ok(())
+}
+
 impl WebsysDom {
     // we're streaming in patches, but the nodes already exist
     // so we're just going to write the correct IDs to the node and load them in
@@ -41,18 +84,21 @@ impl WebsysDom {
 
         let mut hydrated = vec![true];
 
-        let mut last_node_was_static_text = false;
+        let mut path = HydrationPath {
+            debug: self.debug_hydration,
+            ..Default::default()
+        };
 
         // Recursively rehydrate the dom from the VirtualDom
-        self.rehydrate_scope(
-            root_scope,
-            &mut root,
-            &mut hydrated,
-            dom,
-            &mut last_node_was_static_text,
-        )?;
+        self.rehydrate_scope(root_scope, &mut root, &mut hydrated, dom, &mut path)?;
 
         self.interpreter.flush();
+
+        // Mounted events need the ids of the elements, which are only assigned once the interpreter is flushed
+        for id in std::mem::take(&mut self.hydrated_mounts) {
+            self.send_mount_event(id);
+        }
+
         Ok(())
     }
 
@@ -62,19 +108,16 @@ impl WebsysDom {
         current_child: &mut Result<Node, RehydrationError>,
         hydrated: &mut Vec<bool>,
         dom: &VirtualDom,
-        last_node_was_static_text: &mut bool,
+        path: &mut HydrationPath,
     ) -> Result<(), RehydrationError> {
         let vnode = match scope.root_node() {
             dioxus_core::RenderReturn::Ready(ready) => ready,
             _ => return Err(VNodeNotInitialized),
         };
-        self.rehydrate_vnode(
-            current_child,
-            hydrated,
-            dom,
-            vnode,
-            last_node_was_static_text,
-        )
+        let parent = std::mem::replace(&mut path.component, scope.name().to_string());
+        self.rehydrate_vnode(current_child, hydrated, dom, vnode, path)?;
+        path.component = parent;
+        Ok(())
     }
 
     fn rehydrate_vnode(
@@ -83,9 +126,13 @@ impl WebsysDom {
         hydrated: &mut Vec<bool>,
         dom: &VirtualDom,
         vnode: &VNode,
-        last_node_was_static_text: &mut bool,
+        path: &mut HydrationPath,
     ) -> Result<(), RehydrationError> {
-        for (i, root) in vnode.template.get().roots.iter().enumerate() {
+        let template = vnode.template.get();
+        let parent_template = std::mem::replace(&mut path.template, template.name);
+        let parent_nodes = std::mem::take(&mut path.nodes);
+
+        for (i, root) in template.roots.iter().enumerate() {
             // make sure we set the root node ids even if the node is not dynamic
             set_node(
                 hydrated,
@@ -93,15 +140,13 @@ impl WebsysDom {
                 current_child.clone()?,
             );
 
-            self.rehydrate_template_node(
-                current_child,
-                hydrated,
-                dom,
-                vnode,
-                root,
-                last_node_was_static_text,
-            )?;
+            path.nodes.push(i);
+            self.rehydrate_template_node(current_child, hydrated, dom, vnode, root, path)?;
+            path.nodes.pop();
         }
+
+        path.template = parent_template;
+        path.nodes = parent_nodes;
         Ok(())
     }
 
@@ -112,7 +157,7 @@ impl WebsysDom {
         dom: &VirtualDom,
         vnode: &VNode,
         node: &TemplateNode,
-        last_node_was_static_text: &mut bool,
+        path: &mut HydrationPath,
     ) -> Result<(), RehydrationError> {
         tracing::trace!("rehydrate template node: {:?}", node);
         if let Ok(current_child) = current_child {
@@ -122,8 +167,21 @@ impl WebsysDom {
         }
         match node {
             TemplateNode::Element {
-                children, attrs, ..
+                tag,
+                children,
+                attrs,
+                ..
             } => {
+                let element = current_child.clone()?;
+                if !element.node_name().eq_ignore_ascii_case(tag) {
+                    path.mismatch(format_args!(
+                        "expected a `{}` element but found `{}`",
+                        tag,
+                        element.node_name()
+                    ));
+                    return Err(NodeTypeMismatch);
+                }
+
                 let mut mounted_id = None;
                 for attr in *attrs {
                     if let dioxus_core::TemplateAttribute::Dynamic { id } = attr {
@@ -132,13 +190,17 @@ impl WebsysDom {
                         let id = attribute.mounted_element();
                         mounted_id = Some(id);
                         let name = attribute.name;
+                        self.rehydrate_attribute(&element, attribute, path);
                         if let AttributeValue::Listener(_) = value {
                             let event_name = &name[2..];
-                            self.interpreter.new_event_listener(
-                                event_name,
-                                id.0 as u32,
-                                event_bubbles(event_name) as u8,
-                            );
+                            // The element already exists, so it is mounted as soon as it is hydrated
+                            if event_name == "mounted" {
+                                self.hydrated_mounts.push(id);
+                                continue;
+                            }
+                            // Listeners are delegated, and the root may not be listening for this event yet
+                            self.interpreter
+                                .register_delegated(event_name, event_bubbles(event_name) as u8);
                         }
                     }
                 }
@@ -153,15 +215,17 @@ impl WebsysDom {
                         .ok_or(NodeNotFound)?
                         .dyn_into::<Node>()
                         .map_err(|_| NodeTypeMismatch);
-                    for child in *children {
+                    for (i, child) in children.iter().enumerate() {
+                        path.nodes.push(i);
                         self.rehydrate_template_node(
                             &mut children_current_child,
                             hydrated,
                             dom,
                             vnode,
                             child,
-                            last_node_was_static_text,
+                            path,
                         )?;
+                        path.nodes.pop();
                     }
                 }
                 *current_child = current_child
@@ -169,18 +233,18 @@ impl WebsysDom {
                     .map_err(|e| *e)?
                     .next_sibling()
                     .ok_or(NodeNotFound);
-                *last_node_was_static_text = false;
+                path.last_node_was_static_text = false;
             }
             TemplateNode::Text { .. } => {
                 // if the last node was static text, it got merged with this one
-                if !*last_node_was_static_text {
+                if !path.last_node_was_static_text {
                     *current_child = current_child
                         .as_mut()
                         .map_err(|e| *e)?
                         .next_sibling()
                         .ok_or(NodeNotFound);
                 }
-                *last_node_was_static_text = true;
+                path.last_node_was_static_text = true;
             }
             TemplateNode::Dynamic { id } | TemplateNode::DynamicText { id } => {
                 self.rehydrate_dynamic_node(
@@ -188,7 +252,7 @@ impl WebsysDom {
                     hydrated,
                     dom,
                     &vnode.dynamic_nodes[*id],
-                    last_node_was_static_text,
+                    path,
                 )?;
             }
         }
@@ -201,7 +265,7 @@ impl WebsysDom {
         hydrated: &mut Vec<bool>,
         dom: &VirtualDom,
         dynamic: &DynamicNode,
-        last_node_was_static_text: &mut bool,
+        path: &mut HydrationPath,
     ) -> Result<(), RehydrationError> {
         tracing::trace!("rehydrate dynamic node: {:?}", dynamic);
         if let Ok(current_child) = current_child {
@@ -213,43 +277,48 @@ impl WebsysDom {
             dioxus_core::DynamicNode::Text(text) => {
                 let id = text.mounted_element();
                 // skip comment separator before node
-                if cfg!(debug_assertions) {
-                    assert!(current_child
-                        .as_mut()
-                        .map_err(|e| *e)?
-                        .has_type::<Comment>());
-                }
+                expect_marker(current_child, path)?;
                 *current_child = current_child
                     .as_mut()
                     .map_err(|e| *e)?
                     .next_sibling()
                     .ok_or(NodeNotFound);
 
-                set_node(
-                    hydrated,
-                    id.ok_or(VNodeNotInitialized)?,
-                    current_child.clone()?,
-                );
-                *current_child = current_child
-                    .as_mut()
-                    .map_err(|e| *e)?
-                    .next_sibling()
-                    .ok_or(NodeNotFound);
+                // Empty text is rendered without a text node between the separators
+                let node = match current_child.clone()? {
+                    node if node.has_type::<Comment>() => {
+                        let document = node.owner_document().ok_or(NodeNotFound)?;
+                        let empty = document.create_text_node("");
+                        node.parent_node()
+                            .ok_or(NodeNotFound)?
+                            .insert_before(&empty, Some(&node))
+                            .map_err(|_| NodeTypeMismatch)?;
+                        empty.into()
+                    }
+                    node => {
+                        *current_child = node.next_sibling().ok_or(NodeNotFound);
+                        node
+                    }
+                };
+                if node.text_content().as_deref() != Some(text.value) {
+                    path.mismatch(format_args!(
+                        "expected the text {:?} but found {:?}",
+                        text.value,
+                        node.text_content().unwrap_or_default()
+                    ));
+                    node.set_text_content(Some(text.value));
+                }
+                set_node(hydrated, id.ok_or(VNodeNotInitialized)?, node);
 
                 // skip comment separator after node
-                if cfg!(debug_assertions) {
-                    assert!(current_child
-                        .as_mut()
-                        .map_err(|e| *e)?
-                        .has_type::<Comment>());
-                }
+                expect_marker(current_child, path)?;
                 *current_child = current_child
                     .as_mut()
                     .map_err(|e| *e)?
                     .next_sibling()
                     .ok_or(NodeNotFound);
 
-                *last_node_was_static_text = false;
+                path.last_node_was_static_text = false;
             }
             dioxus_core::DynamicNode::Placeholder(placeholder) => {
                 set_node(
@@ -262,7 +331,7 @@ impl WebsysDom {
                     .map_err(|e| *e)?
                     .next_sibling()
                     .ok_or(NodeNotFound);
-                *last_node_was_static_text = false;
+                path.last_node_was_static_text = false;
             }
             dioxus_core::DynamicNode::Component(comp) => {
                 let scope = comp.mounted_scope().ok_or(VNodeNotInitialized)?;
@@ -271,21 +340,49 @@ impl WebsysDom {
                     current_child,
                     hydrated,
                     dom,
-                    last_node_was_static_text,
+                    path,
                 )?;
             }
             dioxus_core::DynamicNode::Fragment(fragment) => {
                 for vnode in *fragment {
-                    self.rehydrate_vnode(
-                        current_child,
-                        hydrated,
-                        dom,
-                        vnode,
-                        last_node_was_static_text,
-                    )?;
+                    self.rehydrate_vnode(current_child, hydrated, dom, vnode, path)?;
                 }
             }
         }
         Ok(())
     }
+
+    /// Patch an attribute the server rendered differently
+    fn rehydrate_attribute(&mut self, element: &Node, attribute: &Attribute, path: &HydrationPath) {
+        // Only attributes the server writes as they are can be compared
+        let expected = match &attribute.value {
+            AttributeValue::Text(value) => value.to_string(),
+            AttributeValue::Template(value) => value.resolved().to_string(),
+            AttributeValue::Int(value) => value.to_string(),
+            AttributeValue::Float(value) => value.to_string(),
+            _ => return,
+        };
+        if attribute.namespace.is_some()
+            || attribute.name == "dangerous_inner_html"
+            || SELECTION_ATTRIBUTES.contains(&attribute.name)
+        {
+            return;
+        }
+
+        let found = element
+            .dyn_ref::<Element>()
+            .and_then(|element| element.get_attribute(attribute.name));
+        if found.as_deref() != Some(expected.as_str()) {
+            path.mismatch(format_args!(
+                "expected the attribute `{}` to be {:?} but found {:?}",
+                attribute.name, expected, found
+            ));
+            self.set_hydrated_attribute(
+                attribute.mounted_element(),
+                attribute.name,
+                &BorrowedAttributeValue::Text(&expected),
+                None,
+            );
+        }
+    }
 }
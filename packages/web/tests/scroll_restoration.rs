@@ -0,0 +1,82 @@
+use std::{
+    cell::{Cell, RefCell},
+    sync::Arc,
+};
+
+use dioxus::prelude::*;
+use dioxus_web::Config;
+use wasm_bindgen::{closure::Closure, JsCast};
+use wasm_bindgen_test::wasm_bindgen_test;
+use web_sys::window;
+
+wasm_bindgen_test::wasm_bindgen_test_configure!(run_in_browser);
+
+thread_local! {
+    static SHOW_LIST: Cell<bool> = const { Cell::new(true) };
+    static UPDATE: RefCell<Option<Arc<dyn Fn()>>> = RefCell::new(None);
+}
+
+fn app(cx: Scope) -> Element {
+    UPDATE.with(|update| *update.borrow_mut() = Some(cx.schedule_update()));
+
+    let show = SHOW_LIST.with(|show| show.get());
+    cx.render(rsx! {
+        if show {
+            rsx! {
+                div { key: "list", id: "list", style: "height: 100px; overflow: auto;",
+                    div { style: "height: 1000px;" }
+                }
+            }
+        }
+    })
+}
+
+fn toggle_list(show: bool) {
+    SHOW_LIST.with(|shown| shown.set(show));
+    let update = UPDATE.with(|update| update.borrow().clone()).unwrap();
+    update();
+}
+
+fn list() -> Option<web_sys::Element> {
+    window()?.document()?.get_element_by_id("list")
+}
+
+/// Give the renderer a chance to apply the edits of the last update
+async fn next_frame() {
+    let promise = js_sys::Promise::new(&mut |resolve, _| {
+        let callback = Closure::once_into_js(move || {
+            let _ = resolve.call0(&wasm_bindgen::JsValue::NULL);
+        });
+        window()
+            .unwrap()
+            .set_timeout_with_callback_and_timeout_and_arguments_0(callback.unchecked_ref(), 50)
+            .unwrap();
+    });
+    wasm_bindgen_futures::JsFuture::from(promise).await.unwrap();
+}
+
+#[wasm_bindgen_test]
+async fn scroll_is_restored_after_remount() {
+    window()
+        .unwrap()
+        .document()
+        .unwrap()
+        .body()
+        .unwrap()
+        .set_inner_html("<div id='main'></div>");
+
+    dioxus_web::launch_cfg(app, Config::new().scroll_restoration("list"));
+    next_frame().await;
+
+    list().unwrap().set_scroll_top(120);
+
+    // The renderer reads the position right before removing the list and reports it back to the VirtualDom
+    toggle_list(false);
+    next_frame().await;
+    assert!(list().is_none());
+
+    // When the list is mounted again, it is scrolled back to where it was
+    toggle_list(true);
+    next_frame().await;
+    assert_eq!(list().unwrap().scroll_top(), 120);
+}